@@ -1,17 +1,47 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use anyhow::bail;
 use anyhow::Error as AnyError;
-use deno_lint::rules::{get_all_rules, LintRule};
+use deno_lint::rules::{get_all_rules, get_config_schema, LintRule};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize)]
 #[serde(default)]
 pub struct RulesConfig {
   pub tags: Vec<String>,
   pub include: Vec<String>,
   pub exclude: Vec<String>,
+  /// Options for configurable rules, keyed by rule code.
+  pub options: HashMap<String, serde_json::Value>,
+}
+
+impl RulesConfig {
+  /// Applies `other` on top of this config: its tags replace these tags, its
+  /// included and excluded rules take precedence over these, and its options
+  /// replace the options of the same rules.
+  fn merge(&mut self, other: &RulesConfig) {
+    if !other.tags.is_empty() {
+      self.tags = other.tags.clone();
+    }
+    for code in &other.include {
+      self.exclude.retain(|excluded| excluded != code);
+      if !self.include.contains(code) {
+        self.include.push(code.clone());
+      }
+    }
+    for code in &other.exclude {
+      self.include.retain(|included| included != code);
+      if !self.exclude.contains(code) {
+        self.exclude.push(code.clone());
+      }
+    }
+    self
+      .options
+      .extend(other.options.iter().map(|(k, v)| (k.clone(), v.clone())));
+  }
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -21,23 +51,90 @@ pub struct FilesConfig {
   pub exclude: Vec<String>,
 }
 
+/// Rules config applying to a subset of the files, e.g. a package of a
+/// monorepo, on top of the root rules config.
+#[derive(Debug, Deserialize)]
+pub struct OverrideConfig {
+  /// Glob patterns of the files the override applies to, relative to the
+  /// current directory.
+  pub files: Vec<String>,
+  #[serde(default)]
+  pub rules: RulesConfig,
+  #[serde(skip, default = "GlobSet::empty")]
+  matcher: GlobSet,
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub struct Config {
   pub rules: RulesConfig,
   pub files: FilesConfig,
+  /// Applied in order to the files they match, so later overrides take
+  /// precedence over earlier ones.
+  pub overrides: Vec<OverrideConfig>,
+}
+
+/// Returns the JSON Schema of the config file: the lint config of the crate,
+/// along with `overrides`.
+pub fn get_schema() -> serde_json::Value {
+  let mut schema = get_config_schema();
+  schema["properties"]["overrides"] = serde_json::json!({
+    "description": "Rules config applying to a subset of the files, on top of the root rules config",
+    "type": "array",
+    "items": {
+      "type": "object",
+      "properties": {
+        "files": {
+          "description": "Globs of the files the override applies to",
+          "type": "array",
+          "items": { "type": "string" }
+        },
+        "rules": { "$ref": "#/definitions/rules" }
+      },
+      "required": ["files"]
+    }
+  });
+  schema
 }
 
 impl Config {
-  pub fn get_rules(&self) -> Vec<Box<dyn LintRule>> {
+  /// Returns the indexes of the overrides applying to `path`.
+  pub fn get_overrides(&self, path: &Path) -> Vec<usize> {
+    let cwd = std::env::current_dir().and_then(|cwd| cwd.canonicalize());
+    let path = match &cwd {
+      Ok(cwd) => path.strip_prefix(cwd).unwrap_or(path),
+      Err(_) => path,
+    };
+    let path = path.strip_prefix(".").unwrap_or(path);
+
+    self
+      .overrides
+      .iter()
+      .enumerate()
+      .filter(|(_, override_config)| override_config.matcher.is_match(path))
+      .map(|(index, _)| index)
+      .collect()
+  }
+
+  /// Returns the rules for the files the given overrides apply to, as
+  /// returned by `get_overrides`.
+  pub fn get_rules(
+    &self,
+    overrides: &[usize],
+  ) -> Result<Vec<Box<dyn LintRule>>, AnyError> {
+    let mut config = self.rules.clone();
+    for index in overrides {
+      config.merge(&self.overrides[*index].rules);
+    }
+
     let mut rules = get_all_rules();
 
-    if !self.rules.tags.is_empty() {
+    if !config.tags.is_empty() {
       rules = rules
         .into_iter()
         .filter(|rule| {
           for tag in rule.tags().to_owned() {
-            if self.rules.tags.contains(&tag.to_string()) {
+            if config.tags.contains(&tag.to_string()) {
               return true;
             }
           }
@@ -46,15 +143,19 @@ impl Config {
         .collect();
     }
 
-    if !self.rules.exclude.is_empty() {
+    if !config.exclude.is_empty() {
       rules = rules
         .into_iter()
-        .filter(|rule| !self.rules.exclude.contains(&rule.code().to_string()))
+        .filter(|rule| !config.exclude.contains(&rule.code().to_string()))
         .collect();
     }
 
-    if !self.rules.include.is_empty() {
-      for include_rule in &self.rules.include {
+    if !config.include.is_empty() {
+      for include_rule in &config.include {
+        // Rules can be both tagged and included, e.g. by an override
+        if rules.iter().any(|rule| rule.code() == include_rule) {
+          continue;
+        }
         if let Some(rule) = get_all_rules()
           .into_iter()
           .find(|rule| rule.code() == include_rule)
@@ -64,7 +165,15 @@ impl Config {
       }
     }
 
-    rules
+    for rule in rules.iter_mut() {
+      if let Some(options) = config.options.get(rule.code()) {
+        if let Err(err) = rule.set_options(options.clone()) {
+          bail!("Invalid options for rule \"{}\": {}", rule.code(), err);
+        }
+      }
+    }
+
+    Ok(rules)
   }
 
   pub fn get_files(&self) -> Result<Vec<PathBuf>, AnyError> {
@@ -72,12 +181,34 @@ impl Config {
   }
 }
 
-pub fn load_from_json(config_path: &Path) -> Result<Config, std::io::Error> {
+pub fn load_from_json(config_path: &Path) -> Result<Config, AnyError> {
   let json_str = std::fs::read_to_string(config_path)?;
-  let config: Config = serde_json::from_str(&json_str)?;
+  let mut config: Config = serde_json::from_str(&json_str)?;
+  for override_config in config.overrides.iter_mut() {
+    override_config.matcher = build_matcher(&override_config.files)?;
+  }
   Ok(config)
 }
 
+fn build_matcher(file_patterns: &[String]) -> Result<GlobSet, AnyError> {
+  let mut builder = GlobSetBuilder::new();
+  for file_pattern in file_patterns {
+    let file_pattern = file_pattern.strip_prefix("./").unwrap_or(file_pattern);
+    let glob = match GlobBuilder::new(file_pattern)
+      .literal_separator(true)
+      .build()
+    {
+      Ok(glob) => glob,
+      Err(err) => bail!("Error parsing override file patterns: {}", err),
+    };
+    builder.add(glob);
+  }
+  match builder.build() {
+    Ok(matcher) => Ok(matcher),
+    Err(err) => bail!("Error parsing override file patterns: {}", err),
+  }
+}
+
 // Ported from dprint
 // https://github.com/dprint/dprint/blob/358c91fbf0a545a0c9736cc496dc1d998028ae65/crates/dprint/src/cli/run_cli.rs#L686-L756
 fn resolve_file_paths(config: &FilesConfig) -> Result<Vec<PathBuf>, AnyError> {
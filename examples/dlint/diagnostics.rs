@@ -0,0 +1,222 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use annotate_snippets::display_list;
+use annotate_snippets::snippet;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::Range;
+use swc_common::BytePos;
+
+// Return slice of source code covered by diagnostic
+// and adjusted range of diagnostic (ie. original range - start line
+// of sliced source code).
+fn get_slice_source_and_range<'a>(
+  line_start_indexes: &[(usize, usize)],
+  source: &'a str,
+  range: &Range,
+) -> (&'a str, (usize, usize)) {
+  let (_, first_line_start) = line_start_indexes[range.start.line - 1];
+  let last_line_end = if range.end.line == line_start_indexes.len() {
+    source.len() - 1
+  } else {
+    let (last_line_no, _) = line_start_indexes[range.end.line - 1];
+    line_start_indexes[last_line_no + 1].1 - 1
+  };
+  let adjusted_start = range.start.byte_pos - first_line_start;
+  let adjusted_end = range.end.byte_pos - first_line_start;
+  let adjusted_range = (adjusted_start, adjusted_end);
+  let slice_str = &source[first_line_start..last_line_end];
+  (slice_str, adjusted_range)
+}
+
+// Sorts diagnostics by rule code and folds the ones with the same code and
+// message into their first occurrence, along with the number of occurrences.
+fn group_diagnostics(
+  diagnostics: &[LintDiagnostic],
+) -> Vec<(&LintDiagnostic, usize)> {
+  let mut sorted: Vec<&LintDiagnostic> = diagnostics.iter().collect();
+  sorted.sort_by(|a, b| a.code.cmp(&b.code));
+
+  let mut groups: Vec<(&LintDiagnostic, usize)> = Vec::new();
+  for diagnostic in sorted {
+    let existing = groups.iter_mut().find(|(first, _)| {
+      first.code == diagnostic.code && first.message == diagnostic.message
+    });
+    match existing {
+      Some((_, count)) => *count += 1,
+      None => groups.push((diagnostic, 1)),
+    }
+  }
+  groups
+}
+
+/// Renders the diagnostics of a file as annotated snippets of its source,
+/// grouping them by rule if `group` is set.
+pub fn render_diagnostics(
+  diagnostics: &[LintDiagnostic],
+  source_code: &str,
+  lines: &[BytePos],
+  group: bool,
+  color: bool,
+) -> String {
+  let line_start_indexes = lines
+    .iter()
+    .map(|pos| pos.0 as usize)
+    .enumerate()
+    .collect::<Vec<_>>();
+
+  let groups = if group {
+    group_diagnostics(diagnostics)
+  } else {
+    diagnostics.iter().map(|d| (d, 1)).collect()
+  };
+
+  let mut rendered = String::new();
+  for (diagnostic, count) in groups {
+    let (slice_source, range) = get_slice_source_and_range(
+      &line_start_indexes,
+      source_code,
+      &diagnostic.range,
+    );
+    let folded = if count > 1 {
+      Some(format!("found {} more times in this file", count - 1))
+    } else {
+      None
+    };
+    let related: Vec<String> = diagnostic
+      .related
+      .iter()
+      .map(|related| {
+        format!(
+          "{} ({}:{}:{})",
+          related.message,
+          related.filename,
+          related.range.start.line,
+          related.range.start.col + 1
+        )
+      })
+      .collect();
+    let mut footer = vec![];
+    for related in &related {
+      footer.push(snippet::Annotation {
+        label: Some(related),
+        id: None,
+        annotation_type: snippet::AnnotationType::Note,
+      });
+    }
+    if let Some(hint) = &diagnostic.hint {
+      footer.push(snippet::Annotation {
+        label: Some(hint),
+        id: None,
+        annotation_type: snippet::AnnotationType::Help,
+      });
+    }
+    if let Some(folded) = &folded {
+      footer.push(snippet::Annotation {
+        label: Some(folded),
+        id: None,
+        annotation_type: snippet::AnnotationType::Note,
+      });
+    }
+
+    let snippet = snippet::Snippet {
+      title: Some(snippet::Annotation {
+        label: Some(&diagnostic.message),
+        id: Some(&diagnostic.code),
+        annotation_type: snippet::AnnotationType::Error,
+      }),
+      footer,
+      slices: vec![snippet::Slice {
+        source: &slice_source,
+        line_start: diagnostic.range.start.line,
+        origin: Some(&diagnostic.filename),
+        fold: false,
+        annotations: vec![snippet::SourceAnnotation {
+          range,
+          label: "",
+          annotation_type: snippet::AnnotationType::Error,
+        }],
+      }],
+      opt: display_list::FormatOptions {
+        color,
+        anonymized_line_numbers: false,
+        margin: None,
+      },
+    };
+    let display_list = display_list::DisplayList::from(snippet);
+    rendered.push_str(&format!("{}\n", display_list));
+  }
+  rendered
+}
+
+// Golden tests rendering a fixed set of diagnostics in every output format of
+// dlint and as JSON, so changes to the output show up in the diff of the files
+// in `testdata`. Run them with `UPDATE_GOLDEN=1` to update the files after an
+// intended change.
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_lint::linter::LinterBuilder;
+  use deno_lint::rules::get_all_rules;
+  use std::path::PathBuf;
+
+  const SOURCE: &str = r#"var a = 1;
+var b = 2;
+debugger;
+
+export { a, b };
+export { a as c, a };
+"#;
+
+  /// Lints `SOURCE`, returning its diagnostics and line starts.
+  fn lint() -> (Vec<LintDiagnostic>, Vec<BytePos>) {
+    let codes = ["no-debugger", "no-duplicate-exports", "no-var"];
+    let rules = get_all_rules()
+      .into_iter()
+      .filter(|rule| codes.contains(&rule.code()))
+      .collect();
+    let mut linter = LinterBuilder::default().rules(rules).build();
+    let (source_file, diagnostics) = linter
+      .lint("golden.ts".to_string(), SOURCE.to_string())
+      .expect("Failed to lint");
+    (diagnostics, source_file.lines.clone())
+  }
+
+  fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+      .join("examples/dlint/testdata")
+      .join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+      std::fs::write(&path, actual).expect("Failed to write golden file");
+      return;
+    }
+    let expected =
+      std::fs::read_to_string(&path).expect("Failed to read golden file");
+    assert_eq!(
+      actual, expected,
+      "Output differs from {}, run with UPDATE_GOLDEN=1 if it's intended",
+      name
+    );
+  }
+
+  #[test]
+  fn golden_pretty() {
+    let (diagnostics, lines) = lint();
+    let rendered =
+      render_diagnostics(&diagnostics, SOURCE, &lines, false, false);
+    assert_golden("pretty.out", &rendered);
+  }
+
+  #[test]
+  fn golden_grouped() {
+    let (diagnostics, lines) = lint();
+    let rendered =
+      render_diagnostics(&diagnostics, SOURCE, &lines, true, false);
+    assert_golden("grouped.out", &rendered);
+  }
+
+  #[test]
+  fn golden_json() {
+    let (diagnostics, _) = lint();
+    let json = serde_json::to_string_pretty(&diagnostics).unwrap();
+    assert_golden("diagnostics.json", &format!("{}\n", json));
+  }
+}
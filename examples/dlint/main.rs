@@ -1,6 +1,4 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use annotate_snippets::display_list;
-use annotate_snippets::snippet;
 use anyhow::bail;
 use anyhow::Error as AnyError;
 use clap::App;
@@ -8,19 +6,20 @@ use clap::AppSettings;
 use clap::Arg;
 use clap::SubCommand;
 use deno_lint::diagnostic::LintDiagnostic;
-use deno_lint::diagnostic::Range;
 use deno_lint::linter::LinterBuilder;
 use deno_lint::rules::{get_all_rules, get_recommended_rules, LintRule};
 use log::debug;
 use rayon::prelude::*;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use swc_common::BytePos;
 
 mod config;
+mod diagnostics;
 mod js;
 
 fn create_cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -35,6 +34,10 @@ fn create_cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(Arg::with_name("json").long("json"))
         .arg(Arg::with_name("all").long("all")),
     )
+    .subcommand(
+      SubCommand::with_name("schema")
+        .about("Print the JSON Schema of the config file"),
+    )
     .subcommand(
       SubCommand::with_name("run")
         .arg(
@@ -54,6 +57,11 @@ fn create_cli_app<'a, 'b>() -> App<'a, 'b> {
             .help("Load config from file")
             .takes_value(true),
         )
+        .arg(
+          Arg::with_name("GROUP")
+            .long("group")
+            .help("Group diagnostics by rule and fold repeated ones"),
+        )
         .arg(
           Arg::with_name("PLUGIN")
             .long("plugin")
@@ -64,89 +72,12 @@ fn create_cli_app<'a, 'b>() -> App<'a, 'b> {
     )
 }
 
-// Return slice of source code covered by diagnostic
-// and adjusted range of diagnostic (ie. original range - start line
-// of sliced source code).
-fn get_slice_source_and_range<'a>(
-  line_start_indexes: &[(usize, usize)],
-  source: &'a str,
-  range: &Range,
-) -> (&'a str, (usize, usize)) {
-  let (_, first_line_start) = line_start_indexes[range.start.line - 1];
-  let last_line_end = if range.end.line == line_start_indexes.len() {
-    source.len() - 1
-  } else {
-    let (last_line_no, _) = line_start_indexes[range.end.line - 1];
-    line_start_indexes[last_line_no + 1].1 - 1
-  };
-  let adjusted_start = range.start.byte_pos - first_line_start;
-  let adjusted_end = range.end.byte_pos - first_line_start;
-  let adjusted_range = (adjusted_start, adjusted_end);
-  let slice_str = &source[first_line_start..last_line_end];
-  (slice_str, adjusted_range)
-}
-
-fn display_diagnostics(
-  diagnostics: &[LintDiagnostic],
-  source_code: &str,
-  lines: &[BytePos],
-) {
-  let line_start_indexes = lines
-    .iter()
-    .map(|pos| pos.0 as usize)
-    .enumerate()
-    .collect::<Vec<_>>();
-
-  for diagnostic in diagnostics {
-    let (slice_source, range) = get_slice_source_and_range(
-      &line_start_indexes,
-      source_code,
-      &diagnostic.range,
-    );
-    let footer = if let Some(hint) = &diagnostic.hint {
-      vec![snippet::Annotation {
-        label: Some(hint),
-        id: None,
-        annotation_type: snippet::AnnotationType::Help,
-      }]
-    } else {
-      vec![]
-    };
-
-    let snippet = snippet::Snippet {
-      title: Some(snippet::Annotation {
-        label: Some(&diagnostic.message),
-        id: Some(&diagnostic.code),
-        annotation_type: snippet::AnnotationType::Error,
-      }),
-      footer,
-      slices: vec![snippet::Slice {
-        source: &slice_source,
-        line_start: diagnostic.range.start.line,
-        origin: Some(&diagnostic.filename),
-        fold: false,
-        annotations: vec![snippet::SourceAnnotation {
-          range,
-          label: "",
-          annotation_type: snippet::AnnotationType::Error,
-        }],
-      }],
-      opt: display_list::FormatOptions {
-        color: true,
-        anonymized_line_numbers: false,
-        margin: None,
-      },
-    };
-    let display_list = display_list::DisplayList::from(snippet);
-    eprintln!("{}", display_list);
-  }
-}
-
 fn run_linter(
   paths: Vec<String>,
   filter_rule_name: Option<&str>,
   maybe_config: Option<Arc<config::Config>>,
   plugin_paths: Vec<&str>,
+  group: bool,
 ) -> Result<(), AnyError> {
   let mut paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
 
@@ -163,12 +94,14 @@ fn run_linter(
   }
 
   let file_diagnostics = Arc::new(Mutex::new(BTreeMap::new()));
-  paths.par_iter().for_each(|file_path| {
-    let source_code =
-      std::fs::read_to_string(&file_path).expect("Failed to load file");
-
+  // Linters are reused for all the files a worker lints, but they can't be
+  // shared between threads so every worker builds its own, one for every set
+  // of config overrides applying to its files.
+  let build_linter = |overrides: &[usize]| {
     let mut rules = if let Some(config) = maybe_config.clone() {
-      config.get_rules()
+      config
+        .get_rules(overrides)
+        .expect("Failed to configure rules")
     } else {
       get_recommended_rules()
     };
@@ -192,28 +125,52 @@ fn run_linter(
       linter_builder = linter_builder.add_plugin(js_runner);
     }
 
-    let linter = linter_builder.build();
-
-    let (source_file, diagnostics) = linter
-      .lint(file_path.to_string_lossy().to_string(), source_code)
-      .expect("Failed to lint");
-
-    error_counts.fetch_add(diagnostics.len(), Ordering::Relaxed);
-
-    let mut lock = file_diagnostics.lock().unwrap();
+    linter_builder.build()
+  };
 
-    lock.insert(
-      file_path,
-      FileDiagnostics {
-        diagnostics,
-        lines: source_file.lines.clone(),
-        source_code: source_file.src.to_string(),
-      },
-    );
-  });
+  paths
+    .par_iter()
+    .for_each_init(HashMap::new, |linters, file_path| {
+      let source_code =
+        std::fs::read_to_string(&file_path).expect("Failed to load file");
+
+      let overrides = maybe_config
+        .as_ref()
+        .map(|config| config.get_overrides(file_path))
+        .unwrap_or_default();
+      let linter = linters
+        .entry(overrides)
+        .or_insert_with_key(|overrides| build_linter(overrides));
+
+      let (source_file, diagnostics) = linter
+        .lint(file_path.to_string_lossy().to_string(), source_code)
+        .expect("Failed to lint");
+
+      error_counts.fetch_add(diagnostics.len(), Ordering::Relaxed);
+
+      let mut lock = file_diagnostics.lock().unwrap();
+
+      lock.insert(
+        file_path,
+        FileDiagnostics {
+          diagnostics,
+          lines: source_file.lines.clone(),
+          source_code: source_file.src.to_string(),
+        },
+      );
+    });
 
   for d in file_diagnostics.lock().unwrap().values() {
-    display_diagnostics(&d.diagnostics, &d.source_code, &d.lines);
+    eprint!(
+      "{}",
+      diagnostics::render_diagnostics(
+        &d.diagnostics,
+        &d.source_code,
+        &d.lines,
+        group,
+        true,
+      )
+    );
   }
 
   let err_count = error_counts.load(Ordering::Relaxed);
@@ -225,11 +182,14 @@ fn run_linter(
   Ok(())
 }
 
-#[derive(Clone, Copy, Serialize)]
+#[derive(Clone, Serialize)]
 struct Rule {
   code: &'static str,
   docs: &'static str,
   tags: &'static [&'static str],
+  fixable: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  options_schema: Option<serde_json::Value>,
 }
 
 enum RuleTag {
@@ -243,6 +203,8 @@ fn get_rules_by_tag(tag: RuleTag) -> Vec<Rule> {
       code: rule.code(),
       docs: rule.docs(),
       tags: rule.tags(),
+      fixable: rule.fixable(),
+      options_schema: rule.options_schema(),
     }
   }
 
@@ -353,6 +315,7 @@ fn main() -> Result<(), AnyError> {
         run_matches.value_of("RULE_CODE"),
         maybe_config,
         plugins,
+        run_matches.is_present("GROUP"),
       )?;
     }
     ("rules", Some(rules_matches)) => {
@@ -374,6 +337,10 @@ fn main() -> Result<(), AnyError> {
         print_rules::<PrettyFormatter>(&mut rules);
       }
     }
+    ("schema", Some(_)) => {
+      let schema = config::get_schema();
+      println!("{}", serde_json::to_string_pretty(&schema)?);
+    }
     _ => unreachable!(),
   };
 
@@ -0,0 +1,263 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+/// The states and properties of WAI-ARIA 1.2.
+///
+/// Must be sorted, so they can be binary searched.
+pub static ARIA_PROPERTIES: &[&str] = &[
+  "aria-activedescendant",
+  "aria-atomic",
+  "aria-autocomplete",
+  "aria-braillelabel",
+  "aria-brailleroledescription",
+  "aria-busy",
+  "aria-checked",
+  "aria-colcount",
+  "aria-colindex",
+  "aria-colindextext",
+  "aria-colspan",
+  "aria-controls",
+  "aria-current",
+  "aria-describedby",
+  "aria-description",
+  "aria-details",
+  "aria-disabled",
+  "aria-dropeffect",
+  "aria-errormessage",
+  "aria-expanded",
+  "aria-flowto",
+  "aria-grabbed",
+  "aria-haspopup",
+  "aria-hidden",
+  "aria-invalid",
+  "aria-keyshortcuts",
+  "aria-label",
+  "aria-labelledby",
+  "aria-level",
+  "aria-live",
+  "aria-modal",
+  "aria-multiline",
+  "aria-multiselectable",
+  "aria-orientation",
+  "aria-owns",
+  "aria-placeholder",
+  "aria-posinset",
+  "aria-pressed",
+  "aria-readonly",
+  "aria-relevant",
+  "aria-required",
+  "aria-roledescription",
+  "aria-rowcount",
+  "aria-rowindex",
+  "aria-rowindextext",
+  "aria-rowspan",
+  "aria-selected",
+  "aria-setsize",
+  "aria-sort",
+  "aria-valuemax",
+  "aria-valuemin",
+  "aria-valuenow",
+  "aria-valuetext",
+];
+
+/// The roles of WAI-ARIA 1.2, DPUB-ARIA and Graphics ARIA that can be used in
+/// documents, i.e. all of them but the abstract ones like `widget`.
+///
+/// Must be sorted, so they can be binary searched.
+pub static ARIA_ROLES: &[&str] = &[
+  "alert",
+  "alertdialog",
+  "application",
+  "article",
+  "banner",
+  "blockquote",
+  "button",
+  "caption",
+  "cell",
+  "checkbox",
+  "code",
+  "columnheader",
+  "combobox",
+  "complementary",
+  "contentinfo",
+  "definition",
+  "deletion",
+  "dialog",
+  "directory",
+  "doc-abstract",
+  "doc-acknowledgments",
+  "doc-afterword",
+  "doc-appendix",
+  "doc-backlink",
+  "doc-biblioentry",
+  "doc-bibliography",
+  "doc-biblioref",
+  "doc-chapter",
+  "doc-colophon",
+  "doc-conclusion",
+  "doc-cover",
+  "doc-credit",
+  "doc-credits",
+  "doc-dedication",
+  "doc-endnote",
+  "doc-endnotes",
+  "doc-epigraph",
+  "doc-epilogue",
+  "doc-errata",
+  "doc-example",
+  "doc-footnote",
+  "doc-foreword",
+  "doc-glossary",
+  "doc-glossref",
+  "doc-index",
+  "doc-introduction",
+  "doc-noteref",
+  "doc-notice",
+  "doc-pagebreak",
+  "doc-pagefooter",
+  "doc-pageheader",
+  "doc-pagelist",
+  "doc-part",
+  "doc-preface",
+  "doc-prologue",
+  "doc-pullquote",
+  "doc-qna",
+  "doc-subtitle",
+  "doc-tip",
+  "doc-toc",
+  "document",
+  "emphasis",
+  "feed",
+  "figure",
+  "form",
+  "generic",
+  "graphics-document",
+  "graphics-object",
+  "graphics-symbol",
+  "grid",
+  "gridcell",
+  "group",
+  "heading",
+  "img",
+  "insertion",
+  "link",
+  "list",
+  "listbox",
+  "listitem",
+  "log",
+  "main",
+  "mark",
+  "marquee",
+  "math",
+  "menu",
+  "menubar",
+  "menuitem",
+  "menuitemcheckbox",
+  "menuitemradio",
+  "meter",
+  "navigation",
+  "none",
+  "note",
+  "option",
+  "paragraph",
+  "presentation",
+  "progressbar",
+  "radio",
+  "radiogroup",
+  "region",
+  "row",
+  "rowgroup",
+  "rowheader",
+  "scrollbar",
+  "search",
+  "searchbox",
+  "separator",
+  "slider",
+  "spinbutton",
+  "status",
+  "strong",
+  "subscript",
+  "superscript",
+  "switch",
+  "tab",
+  "table",
+  "tablist",
+  "tabpanel",
+  "term",
+  "textbox",
+  "time",
+  "timer",
+  "toolbar",
+  "tooltip",
+  "tree",
+  "treegrid",
+  "treeitem",
+];
+
+/// Returns `true` if `name` is an ARIA state or property.
+pub fn is_aria_property(name: &str) -> bool {
+  ARIA_PROPERTIES.binary_search(&name).is_ok()
+}
+
+/// Returns `true` if `role` is a non-abstract ARIA role.
+pub fn is_aria_role(role: &str) -> bool {
+  ARIA_ROLES.binary_search(&role).is_ok()
+}
+
+/// Returns the name in `names` closest to `name`, if it's a likely typo of it,
+/// e.g. `aria-labelledby` for `aria-labeledby`.
+pub fn closest<'a>(name: &str, names: &[&'a str]) -> Option<&'a str> {
+  let name = name.to_lowercase();
+  names
+    .iter()
+    .map(|candidate| (edit_distance(&name, candidate), *candidate))
+    .filter(|(distance, candidate)| {
+      *distance <= 2 && *distance < candidate.len() / 2
+    })
+    .min_by_key(|(distance, _)| *distance)
+    .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut previous = row[0];
+    row[0] = i + 1;
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous + if a_char == *b_char { 0 } else { 1 };
+      previous = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+    }
+  }
+  row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tables_are_sorted() {
+    for table in &[ARIA_PROPERTIES, ARIA_ROLES] {
+      for pair in table.windows(2) {
+        assert!(pair[0] < pair[1], "{} isn't sorted", pair[1]);
+      }
+    }
+  }
+
+  #[test]
+  fn closest_test() {
+    assert_eq!(
+      closest("aria-labeledby", ARIA_PROPERTIES),
+      Some("aria-labelledby")
+    );
+    assert_eq!(
+      closest("aria-Describedby", ARIA_PROPERTIES),
+      Some("aria-describedby")
+    );
+    assert_eq!(closest("buton", ARIA_ROLES), Some("button"));
+    assert_eq!(closest("aria-foo", ARIA_PROPERTIES), None);
+    assert_eq!(closest("foo", ARIA_ROLES), None);
+  }
+}
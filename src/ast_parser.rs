@@ -1,8 +1,11 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use dprint_swc_ecma_ast_view::TokenAndSpan;
 use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 use swc_common::comments::SingleThreadedComments;
 use swc_common::errors::Diagnostic;
@@ -124,11 +127,15 @@ pub(crate) struct AstParser {
   pub(crate) buffered_error: SwcErrorBuffer,
   pub(crate) source_map: Rc<SourceMap>,
   pub(crate) handler: Handler,
-  pub(crate) globals: Globals,
+  pub(crate) globals: Rc<Globals>,
   /// The marker passed to the resolver (from swc).
   ///
   /// This mark is applied to top level bindings and unresolved references.
   pub(crate) top_level_mark: Mark,
+  /// Buffer the tokens of a file are collected into, handed back with
+  /// `reuse_tokens` once the file is linted so the next one doesn't have to
+  /// grow a new one.
+  tokens: RefCell<Vec<TokenAndSpan>>,
 }
 
 impl AstParser {
@@ -144,7 +151,7 @@ impl AstParser {
       },
     );
 
-    let globals = Globals::new();
+    let globals = Rc::new(Globals::new());
     let top_level_mark =
       swc_common::GLOBALS.set(&globals, || Mark::fresh(Mark::root()));
 
@@ -154,13 +161,45 @@ impl AstParser {
       handler,
       globals,
       top_level_mark,
+      tokens: RefCell::new(vec![]),
     }
   }
 
+  /// Prepares the parser for the next file, keeping the allocations that can
+  /// be reused: the error handler and its buffer are cleared in place, and the
+  /// token buffer handed back with `reuse_tokens` is kept.
+  ///
+  /// The source map and the hygiene marks of the resolver are replaced, as
+  /// swc can't clear them in place and they're tied to a single file: looking
+  /// up a source file by name returns the first match, and positions in a
+  /// shared source map wouldn't start at 0 for the next files.
+  pub(crate) fn reset(&mut self) {
+    self.buffered_error.0.borrow_mut().clear();
+    self.handler.reset_err_count();
+    self.source_map = Rc::new(SourceMap::default());
+    self.globals = Rc::new(Globals::new());
+    self.top_level_mark =
+      swc_common::GLOBALS.set(&self.globals, || Mark::fresh(Mark::root()));
+  }
+
+  /// Hands back the token buffer of a parsed file, once it's no longer used,
+  /// to collect the tokens of the next file into.
+  pub(crate) fn reuse_tokens(&self, tokens: Vec<TokenAndSpan>) {
+    self.tokens.replace(tokens);
+  }
+
   pub(crate) fn set_source_map(&mut self, source_map: Rc<SourceMap>) {
     self.source_map = source_map;
   }
 
+  /// Takes over the state a cached source was parsed with, which its spans
+  /// and syntax contexts refer to.
+  pub(crate) fn set_parsed_source(&mut self, parsed_source: &ParsedSource) {
+    self.source_map = Rc::clone(&parsed_source.source_map);
+    self.globals = Rc::clone(&parsed_source.globals);
+    self.top_level_mark = parsed_source.top_level_mark;
+  }
+
   pub(crate) fn parse_program(
     &self,
     file_name: &str,
@@ -184,7 +223,9 @@ impl AstParser {
       Some(&comments),
     );
 
-    let tokens: Vec<TokenAndSpan> = lexer.clone().into_iter().collect();
+    let mut tokens = self.tokens.take();
+    tokens.clear();
+    tokens.extend(lexer.clone());
 
     let mut parser = Parser::new_from(lexer);
 
@@ -223,3 +264,112 @@ pub(crate) struct ParsedData {
   pub(crate) comments: SingleThreadedComments,
   pub(crate) tokens: Vec<TokenAndSpan>,
 }
+
+/// A parsed file, along with the source map and hygiene state its spans and
+/// syntax contexts refer to.
+pub struct ParsedSource {
+  source_map: Rc<SourceMap>,
+  globals: Rc<Globals>,
+  top_level_mark: Mark,
+  hash: u64,
+  data: ParsedData,
+}
+
+impl ParsedSource {
+  /// The program, with the hygiene information of the resolver applied.
+  pub fn program(&self) -> &ast::Program {
+    &self.data.program
+  }
+
+  pub fn comments(&self) -> &SingleThreadedComments {
+    &self.data.comments
+  }
+
+  pub fn tokens(&self) -> &[TokenAndSpan] {
+    &self.data.tokens
+  }
+
+  pub fn source_map(&self) -> &Rc<SourceMap> {
+    &self.source_map
+  }
+
+  /// The globals of the hygiene marks, to be set with `swc_common::GLOBALS`
+  /// when transforming or analyzing the program.
+  pub fn globals(&self) -> &Globals {
+    &self.globals
+  }
+
+  /// The mark the resolver applied to top level bindings and unresolved
+  /// references.
+  pub fn top_level_mark(&self) -> Mark {
+    self.top_level_mark
+  }
+}
+
+/// An in-memory cache of parsed files, keyed by file name and validated with
+/// a hash of their contents, so linting a file again (e.g. in watch mode or
+/// on save in an editor) skips parsing it if it didn't change.
+///
+/// The cache isn't tied to the linter: it can be shared through an `Rc` with
+/// `LinterBuilder::parsed_source_cache` and any other code working on swc
+/// programs, so a file is parsed once for all of them. Only the latest
+/// contents of each file are kept, and sources that fail to parse aren't
+/// cached.
+#[derive(Default)]
+pub struct ParsedSourceCache {
+  sources: RefCell<HashMap<String, Rc<ParsedSource>>>,
+}
+
+impl ParsedSourceCache {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the cached program of the file if it was parsed from the same
+  /// contents with the same syntax, or parses and caches it otherwise.
+  pub fn get_or_parse(
+    &self,
+    file_name: &str,
+    syntax: Syntax,
+    source_code: &str,
+  ) -> Result<Rc<ParsedSource>, SwcDiagnosticBuffer> {
+    let hash = source_hash(syntax, source_code);
+    if let Some(parsed_source) = self.sources.borrow().get(file_name) {
+      if parsed_source.hash == hash {
+        return Ok(Rc::clone(parsed_source));
+      }
+    }
+
+    let ast_parser = AstParser::new();
+    let data = ast_parser.parse_program(file_name, syntax, source_code)?;
+    let parsed_source = Rc::new(ParsedSource {
+      source_map: ast_parser.source_map,
+      globals: ast_parser.globals,
+      top_level_mark: ast_parser.top_level_mark,
+      hash,
+      data,
+    });
+    self
+      .sources
+      .borrow_mut()
+      .insert(file_name.to_string(), Rc::clone(&parsed_source));
+    Ok(parsed_source)
+  }
+
+  /// Drops the cached program of the file, e.g. once it's closed or deleted.
+  pub fn remove(&self, file_name: &str) {
+    self.sources.borrow_mut().remove(file_name);
+  }
+
+  pub fn clear(&self) {
+    self.sources.borrow_mut().clear();
+  }
+}
+
+fn source_hash(syntax: Syntax, source_code: &str) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  // `Syntax` doesn't implement `Hash`, but its debug output covers all of it
+  format!("{:?}", syntax).hash(&mut hasher);
+  source_code.hash(&mut hasher);
+  hasher.finish()
+}
@@ -1,7 +1,11 @@
 use crate::control_flow::ControlFlow;
-use crate::diagnostic::{LintDiagnostic, Position, Range};
+use crate::diagnostic::{
+  LintDiagnostic, LintFix, LintFixChange, LintRelatedInformation, Position,
+  Range,
+};
 use crate::ignore_directives::IgnoreDirective;
 use crate::scopes::Scope;
+use crate::type_provider::TypeProvider;
 use dprint_swc_ecma_ast_view::{self as AstView, BytePos, RootNode};
 use std::collections::HashSet;
 use std::rc::Rc;
@@ -16,20 +20,23 @@ pub struct Context<'view> {
   source_map: Rc<SourceMap>,
   program: AstView::Program<'view>,
   ignore_directives: Vec<IgnoreDirective>,
-  scope: Scope,
-  control_flow: ControlFlow,
+  scope: Option<Scope>,
+  control_flow: Option<ControlFlow>,
   top_level_ctxt: SyntaxContext,
+  type_provider: Option<Rc<dyn TypeProvider>>,
 }
 
 impl<'view> Context<'view> {
+  #[allow(clippy::too_many_arguments)]
   pub(crate) fn new(
     file_name: String,
     source_map: Rc<SourceMap>,
     program: AstView::Program<'view>,
     ignore_directives: Vec<IgnoreDirective>,
-    scope: Scope,
-    control_flow: ControlFlow,
+    scope: Option<Scope>,
+    control_flow: Option<ControlFlow>,
     top_level_ctxt: SyntaxContext,
+    type_provider: Option<Rc<dyn TypeProvider>>,
   ) -> Self {
     Self {
       file_name,
@@ -39,6 +46,7 @@ impl<'view> Context<'view> {
       scope,
       control_flow,
       top_level_ctxt,
+      type_provider,
       diagnostics: Vec::new(),
       plugin_codes: HashSet::new(),
     }
@@ -71,23 +79,51 @@ impl<'view> Context<'view> {
     &mut self.ignore_directives
   }
 
+  /// Returns the scope analysis of the program, which is only available to
+  /// rules returning `true` from `LintRule::needs_scope`.
   pub fn scope(&self) -> &Scope {
-    &self.scope
+    self
+      .scope
+      .as_ref()
+      .expect("Scope analysis requires `LintRule::needs_scope`")
   }
 
+  /// Returns the control flow analysis of the program, which is only
+  /// available to rules returning `true` from `LintRule::needs_cfg`.
   pub fn control_flow(&self) -> &ControlFlow {
-    &self.control_flow
+    self
+      .control_flow
+      .as_ref()
+      .expect("Control flow analysis requires `LintRule::needs_cfg`")
   }
 
   pub(crate) fn top_level_ctxt(&self) -> SyntaxContext {
     self.top_level_ctxt
   }
 
+  /// Returns whether the expression with the given span evaluates to a
+  /// `Promise`, or `None` if no type information is available.
+  pub fn is_promise(&self, span: Span) -> Option<bool> {
+    self
+      .type_provider
+      .as_ref()?
+      .is_promise(&self.file_name, &self.range(span))
+  }
+
+  /// Returns whether the expression with the given span is a function
+  /// returning a `Promise`, or `None` if no type information is available.
+  pub fn returns_promise(&self, span: Span) -> Option<bool> {
+    self
+      .type_provider
+      .as_ref()?
+      .returns_promise(&self.file_name, &self.range(span))
+  }
+
   pub fn all_comments(&self) -> impl Iterator<Item = &'view Comment> {
     self
       .program
       .comments()
-      .expect("Program should have information about comments, but doesn't")
+      .expect("Comments are only available to rules that need them")
       .all_comments()
   }
 
@@ -98,7 +134,7 @@ impl<'view> Context<'view> {
     self
       .program
       .comments()
-      .expect("Program should have information about comments, but doesn't")
+      .expect("Comments are only available to rules that need them")
       .leading_comments(lo)
   }
 
@@ -109,7 +145,7 @@ impl<'view> Context<'view> {
     self
       .program
       .comments()
-      .expect("Program should have information about comments, but doesn't")
+      .expect("Comments are only available to rules that need them")
       .trailing_comments(hi)
   }
 
@@ -136,6 +172,69 @@ impl<'view> Context<'view> {
     self.diagnostics.push(diagnostic);
   }
 
+  /// Adds a diagnostic along with fixes for it, the first one being the
+  /// preferred fix.
+  pub fn add_diagnostic_with_fixes(
+    &mut self,
+    span: Span,
+    code: impl ToString,
+    message: impl ToString,
+    maybe_hint: Option<String>,
+    fixes: Vec<LintFix>,
+  ) {
+    let mut diagnostic =
+      self.create_diagnostic(span, code, message, maybe_hint);
+    diagnostic.fixes = fixes;
+    self.diagnostics.push(diagnostic);
+  }
+
+  /// Adds a diagnostic pointing at other relevant locations of the file,
+  /// e.g. the first declaration of a duplicated name.
+  pub fn add_diagnostic_with_related(
+    &mut self,
+    span: Span,
+    code: impl ToString,
+    message: impl ToString,
+    maybe_hint: Option<String>,
+    related: Vec<LintRelatedInformation>,
+  ) {
+    let mut diagnostic =
+      self.create_diagnostic(span, code, message, maybe_hint);
+    diagnostic.related = related;
+    self.diagnostics.push(diagnostic);
+  }
+
+  /// Creates related information pointing at a span of the file.
+  pub fn create_related(
+    &self,
+    span: Span,
+    message: impl ToString,
+  ) -> LintRelatedInformation {
+    LintRelatedInformation {
+      message: message.to_string(),
+      range: self.range(span),
+      filename: self.file_name.clone(),
+    }
+  }
+
+  /// Creates a fix replacing the text of each span with the given text.
+  pub fn create_fix(
+    &self,
+    description: impl ToString,
+    changes: Vec<(Span, String)>,
+  ) -> LintFix {
+    LintFix {
+      description: description.to_string(),
+      changes: changes
+        .into_iter()
+        .map(|(span, new_text)| LintFixChange {
+          new_text,
+          range: self.range(span),
+        })
+        .collect(),
+    }
+  }
+
   pub(crate) fn create_diagnostic(
     &self,
     span: Span,
@@ -144,21 +243,16 @@ impl<'view> Context<'view> {
     maybe_hint: Option<String>,
   ) -> LintDiagnostic {
     let time_start = Instant::now();
-    let start = Position::new(
-      self.source_map.lookup_byte_offset(span.lo()).pos,
-      self.source_map.lookup_char_pos(span.lo()),
-    );
-    let end = Position::new(
-      self.source_map.lookup_byte_offset(span.hi()).pos,
-      self.source_map.lookup_char_pos(span.hi()),
-    );
 
     let diagnostic = LintDiagnostic {
-      range: Range { start, end },
+      range: self.range(span),
       filename: self.file_name.clone(),
       message: message.to_string(),
       code: code.to_string(),
       hint: maybe_hint,
+      fixes: Vec::new(),
+      related: Vec::new(),
+      stable_location: None,
     };
 
     let time_end = Instant::now();
@@ -169,6 +263,18 @@ impl<'view> Context<'view> {
     diagnostic
   }
 
+  fn range(&self, span: Span) -> Range {
+    let start = Position::new(
+      self.source_map.lookup_byte_offset(span.lo()).pos,
+      self.source_map.lookup_char_pos(span.lo()),
+    );
+    let end = Position::new(
+      self.source_map.lookup_byte_offset(span.hi()).pos,
+      self.source_map.lookup_char_pos(span.hi()),
+    );
+    Range { start, end }
+  }
+
   pub fn set_plugin_codes(&mut self, codes: HashSet<String>) {
     self.plugin_codes = codes;
   }
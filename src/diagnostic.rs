@@ -30,6 +30,31 @@ pub struct Range {
   pub end: Position,
 }
 
+/// A single text edit, replacing the text in `range` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFixChange {
+  pub new_text: String,
+  pub range: Range,
+}
+
+/// An automatic fix for a diagnostic. All of its changes must be applied
+/// together.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFix {
+  pub description: String,
+  pub changes: Vec<LintFixChange>,
+}
+
+/// Another location relevant to a diagnostic, e.g. the first export of a
+/// name exported twice.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintRelatedInformation {
+  pub message: String,
+  pub range: Range,
+  pub filename: String,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct LintDiagnostic {
   pub range: Range,
@@ -37,4 +62,135 @@ pub struct LintDiagnostic {
   pub message: String,
   pub code: String,
   pub hint: Option<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub fixes: Vec<LintFix>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related: Vec<LintRelatedInformation>,
+  /// Where the diagnostic is, independently of the code around it, set by
+  /// the linter for `fingerprint`.
+  #[serde(skip)]
+  pub stable_location: Option<StableLocation>,
+}
+
+/// The location of a diagnostic in terms that don't change with edits
+/// elsewhere in the file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StableLocation {
+  /// The path of the innermost named declaration containing the diagnostic,
+  /// e.g. `Foo.bar` for a method, or `None` at the top level.
+  pub symbol: Option<String>,
+  /// The line of the diagnostic, relative to the start of `symbol`.
+  pub line_offset: usize,
+  /// The flagged code, with whitespace collapsed and truncated.
+  pub text: String,
+}
+
+impl LintDiagnostic {
+  /// Returns an identifier of the diagnostic that stays the same across
+  /// edits that don't touch the flagged code, e.g. to compare diagnostics
+  /// with a baseline or between CI runs.
+  ///
+  /// It's a hash of the rule code, the message with numbers normalized (so
+  /// `complexity` reports don't change with every branch added) and the
+  /// `stable_location`. Identical code flagged twice in the same declaration
+  /// has the same fingerprint.
+  pub fn fingerprint(&self) -> String {
+    let mut hasher = Fnv1a::default();
+    hasher.write(self.code.as_bytes());
+    hasher.write(normalize_message(&self.message).as_bytes());
+    if let Some(location) = &self.stable_location {
+      hasher.write(location.symbol.as_deref().unwrap_or("").as_bytes());
+      hasher.write(location.line_offset.to_string().as_bytes());
+      hasher.write(location.text.as_bytes());
+    }
+    format!("{:016x}", hasher.0)
+  }
+}
+
+/// Sorts diagnostics in the order the linter returns them: by file name, then
+/// by position of their start (line, then column), then by rule code and
+/// message. The order doesn't depend on the rules or on how they're run, so
+/// the output of different runs can be compared.
+pub fn sort_diagnostics(diagnostics: &mut [LintDiagnostic]) {
+  diagnostics.sort_by(|a, b| {
+    a.filename
+      .cmp(&b.filename)
+      .then(a.range.start.line.cmp(&b.range.start.line))
+      .then(a.range.start.col.cmp(&b.range.start.col))
+      .then_with(|| a.code.cmp(&b.code))
+      .then_with(|| a.message.cmp(&b.message))
+  });
+}
+
+/// Collapses whitespace and replaces numbers that aren't part of a name with
+/// `0`.
+fn normalize_message(message: &str) -> String {
+  let mut normalized = String::with_capacity(message.len());
+  let mut prev: Option<char> = None;
+  // Whether `prev` is part of a number that has been replaced with `0`
+  let mut in_number = false;
+  for c in message
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .chars()
+  {
+    let in_word = matches!(prev, Some(p) if p.is_alphanumeric() || p == '_');
+    if c.is_ascii_digit() && (!in_word || in_number) {
+      if !in_number {
+        normalized.push('0');
+      }
+      in_number = true;
+    } else {
+      normalized.push(c);
+      in_number = false;
+    }
+    prev = Some(c);
+  }
+  normalized
+}
+
+/// The 64 bit FNV-1a hash, used instead of `DefaultHasher` as fingerprints
+/// are persisted and must not change between Rust versions.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+  fn default() -> Self {
+    Fnv1a(0xcbf2_9ce4_8422_2325)
+  }
+}
+
+impl Fnv1a {
+  fn write(&mut self, bytes: &[u8]) {
+    // Separates the fields, so they can't run into each other
+    for byte in bytes.iter().chain(&[0xff]) {
+      self.0 ^= u64::from(*byte);
+      self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalize_message_test() {
+    assert_eq!(
+      normalize_message("Function has a complexity of 12 (max 10)"),
+      "Function has a complexity of 0 (max 0)"
+    );
+    assert_eq!(
+      normalize_message("`foo2`  is never   used"),
+      "`foo2` is never used"
+    );
+    assert_eq!(
+      normalize_message("`foo01` is never used"),
+      "`foo01` is never used"
+    );
+    assert_eq!(
+      normalize_message("`foo09` is never used"),
+      "`foo09` is never used"
+    );
+  }
 }
@@ -0,0 +1,388 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+/// List of common misspellings of English words and programming terms, along
+/// with their correction.
+///
+/// Misspellings must be lowercase and sorted, so they can be binary searched.
+pub static MISSPELLINGS: &[(&str, &str)] = &[
+  ("abscence", "absence"),
+  ("accesible", "accessible"),
+  ("accidently", "accidentally"),
+  ("accomodate", "accommodate"),
+  ("accross", "across"),
+  ("acheive", "achieve"),
+  ("acording", "according"),
+  ("adress", "address"),
+  ("adresses", "addresses"),
+  ("agressive", "aggressive"),
+  ("algorithim", "algorithm"),
+  ("algoritm", "algorithm"),
+  ("allready", "already"),
+  ("alredy", "already"),
+  ("alwasy", "always"),
+  ("amoung", "among"),
+  ("anonymus", "anonymous"),
+  ("apparantly", "apparently"),
+  ("appearence", "appearance"),
+  ("applicaton", "application"),
+  ("arbitary", "arbitrary"),
+  ("arguement", "argument"),
+  ("arguements", "arguments"),
+  ("arrray", "array"),
+  ("asign", "assign"),
+  ("asnyc", "async"),
+  ("assigment", "assignment"),
+  ("asssert", "assert"),
+  ("asynchronus", "asynchronous"),
+  ("atempt", "attempt"),
+  ("attemp", "attempt"),
+  ("attribtue", "attribute"),
+  ("attribue", "attribute"),
+  ("auxilary", "auxiliary"),
+  ("availabe", "available"),
+  ("availible", "available"),
+  ("avaliable", "available"),
+  ("awiat", "await"),
+  ("becasue", "because"),
+  ("becuase", "because"),
+  ("beggining", "beginning"),
+  ("begining", "beginning"),
+  ("beleive", "believe"),
+  ("belive", "believe"),
+  ("boundry", "boundary"),
+  ("buidl", "build"),
+  ("calback", "callback"),
+  ("calcualte", "calculate"),
+  ("callabck", "callback"),
+  ("caluclate", "calculate"),
+  ("cannnot", "cannot"),
+  ("charachter", "character"),
+  ("charater", "character"),
+  ("choosen", "chosen"),
+  ("collumn", "column"),
+  ("comand", "command"),
+  ("comitted", "committed"),
+  ("comming", "coming"),
+  ("commited", "committed"),
+  ("comparision", "comparison"),
+  ("compatability", "compatibility"),
+  ("compatable", "compatible"),
+  ("completly", "completely"),
+  ("concurent", "concurrent"),
+  ("configuraton", "configuration"),
+  ("connnection", "connection"),
+  ("consistant", "consistent"),
+  ("constuctor", "constructor"),
+  ("containg", "containing"),
+  ("continous", "continuous"),
+  ("controll", "control"),
+  ("contructor", "constructor"),
+  ("convertion", "conversion"),
+  ("coordiante", "coordinate"),
+  ("corect", "correct"),
+  ("coudl", "could"),
+  ("curent", "current"),
+  ("currenly", "currently"),
+  ("dafault", "default"),
+  ("deafult", "default"),
+  ("declaraton", "declaration"),
+  ("decleration", "declaration"),
+  ("definately", "definitely"),
+  ("defintion", "definition"),
+  ("defualt", "default"),
+  ("deleteing", "deleting"),
+  ("depedency", "dependency"),
+  ("dependancy", "dependency"),
+  ("dependecy", "dependency"),
+  ("desciption", "description"),
+  ("descripton", "description"),
+  ("destory", "destroy"),
+  ("developement", "development"),
+  ("differnt", "different"),
+  ("diffrent", "different"),
+  ("dimention", "dimension"),
+  ("directroy", "directory"),
+  ("disapear", "disappear"),
+  ("dispaly", "display"),
+  ("dupilcate", "duplicate"),
+  ("durring", "during"),
+  ("efficent", "efficient"),
+  ("elemnt", "element"),
+  ("elment", "element"),
+  ("embarass", "embarrass"),
+  ("enviornment", "environment"),
+  ("enviroment", "environment"),
+  ("equivelant", "equivalent"),
+  ("equivilent", "equivalent"),
+  ("evalute", "evaluate"),
+  ("exaclty", "exactly"),
+  ("excecute", "execute"),
+  ("exising", "existing"),
+  ("exisiting", "existing"),
+  ("existance", "existence"),
+  ("experiance", "experience"),
+  ("explicitely", "explicitly"),
+  ("expresion", "expression"),
+  ("extention", "extension"),
+  ("follwing", "following"),
+  ("foward", "forward"),
+  ("fucntion", "function"),
+  ("fullfill", "fulfill"),
+  ("funciton", "function"),
+  ("functon", "function"),
+  ("funtion", "function"),
+  ("garantee", "guarantee"),
+  ("gaurd", "guard"),
+  ("guarentee", "guarantee"),
+  ("heigth", "height"),
+  ("heirarchy", "hierarchy"),
+  ("hierachy", "hierarchy"),
+  ("idenitifer", "identifier"),
+  ("identifer", "identifier"),
+  ("immediatly", "immediately"),
+  ("implemenation", "implementation"),
+  ("implementaion", "implementation"),
+  ("implmentation", "implementation"),
+  ("indentifier", "identifier"),
+  ("independant", "independent"),
+  ("infomation", "information"),
+  ("inital", "initial"),
+  ("initalize", "initialize"),
+  ("initilize", "initialize"),
+  ("instace", "instance"),
+  ("instanciate", "instantiate"),
+  ("interupt", "interrupt"),
+  ("intial", "initial"),
+  ("itterate", "iterate"),
+  ("keybaord", "keyboard"),
+  ("langauge", "language"),
+  ("lengh", "length"),
+  ("lenght", "length"),
+  ("libary", "library"),
+  ("lisence", "license"),
+  ("listner", "listener"),
+  ("maintainance", "maintenance"),
+  ("maintenence", "maintenance"),
+  ("managment", "management"),
+  ("mesage", "message"),
+  ("messsage", "message"),
+  ("millenium", "millennium"),
+  ("mininum", "minimum"),
+  ("mispell", "misspell"),
+  ("missmatch", "mismatch"),
+  ("modfiy", "modify"),
+  ("neccessary", "necessary"),
+  ("necessery", "necessary"),
+  ("nessecary", "necessary"),
+  ("noticable", "noticeable"),
+  ("nubmer", "number"),
+  ("numebr", "number"),
+  ("objcet", "object"),
+  ("occassion", "occasion"),
+  ("occured", "occurred"),
+  ("occurence", "occurrence"),
+  ("occuring", "occurring"),
+  ("occurrance", "occurrence"),
+  ("ommit", "omit"),
+  ("ommited", "omitted"),
+  ("optinal", "optional"),
+  ("optoin", "option"),
+  ("orignal", "original"),
+  ("overide", "override"),
+  ("overriden", "overridden"),
+  ("paralel", "parallel"),
+  ("paramater", "parameter"),
+  ("parametr", "parameter"),
+  ("paramter", "parameter"),
+  ("parrallel", "parallel"),
+  ("passsword", "password"),
+  ("pasword", "password"),
+  ("perfomance", "performance"),
+  ("permision", "permission"),
+  ("persistant", "persistent"),
+  ("posible", "possible"),
+  ("possiblity", "possibility"),
+  ("postion", "position"),
+  ("preceeding", "preceding"),
+  ("prefered", "preferred"),
+  ("previos", "previous"),
+  ("privilage", "privilege"),
+  ("privledge", "privilege"),
+  ("probaly", "probably"),
+  ("proccess", "process"),
+  ("procesing", "processing"),
+  ("propery", "property"),
+  ("proprety", "property"),
+  ("protocal", "protocol"),
+  ("pubilc", "public"),
+  ("publically", "publicly"),
+  ("quering", "querying"),
+  ("reccommend", "recommend"),
+  ("recieve", "receive"),
+  ("recieved", "received"),
+  ("reciever", "receiver"),
+  ("recomend", "recommend"),
+  ("recursivly", "recursively"),
+  ("refered", "referred"),
+  ("referrence", "reference"),
+  ("refference", "reference"),
+  ("relevent", "relevant"),
+  ("remaing", "remaining"),
+  ("remeber", "remember"),
+  ("reponse", "response"),
+  ("repositry", "repository"),
+  ("reqeust", "request"),
+  ("requets", "request"),
+  ("requried", "required"),
+  ("resopnse", "response"),
+  ("resouce", "resource"),
+  ("respone", "response"),
+  ("retreive", "retrieve"),
+  ("retrive", "retrieve"),
+  ("retun", "return"),
+  ("reutrn", "return"),
+  ("seperate", "separate"),
+  ("seperator", "separator"),
+  ("serach", "search"),
+  ("succesful", "successful"),
+  ("successfull", "successful"),
+  ("sucess", "success"),
+  ("sucessful", "successful"),
+  ("sufficent", "sufficient"),
+  ("suport", "support"),
+  ("supress", "suppress"),
+  ("surpress", "suppress"),
+  ("syncronous", "synchronous"),
+  ("tempalte", "template"),
+  ("temporaray", "temporary"),
+  ("threshhold", "threshold"),
+  ("tommorow", "tomorrow"),
+  ("tranform", "transform"),
+  ("transfered", "transferred"),
+  ("truely", "truly"),
+  ("udpate", "update"),
+  ("unecessary", "unnecessary"),
+  ("unneccessary", "unnecessary"),
+  ("untill", "until"),
+  ("upadte", "update"),
+  ("usefull", "useful"),
+  ("usualy", "usually"),
+  ("valdiate", "validate"),
+  ("validaton", "validation"),
+  ("varaible", "variable"),
+  ("varible", "variable"),
+  ("vaule", "value"),
+  ("verison", "version"),
+  ("visable", "visible"),
+  ("wether", "whether"),
+  ("whitch", "which"),
+  ("widht", "width"),
+  ("wierd", "weird"),
+  ("writting", "writing"),
+];
+
+/// Returns the correction of `word` if it's a known misspelling, ignoring
+/// case. The correction follows the case of `word`, e.g. `Recieve` is
+/// corrected to `Receive`.
+pub fn correction(word: &str) -> Option<String> {
+  let lowercase = word.to_lowercase();
+  let index = MISSPELLINGS
+    .binary_search_by(|(misspelling, _)| misspelling.cmp(&lowercase.as_str()))
+    .ok()?;
+  let correction = MISSPELLINGS[index].1;
+
+  if word.len() > 1 && word.chars().all(char::is_uppercase) {
+    Some(correction.to_uppercase())
+  } else if word.starts_with(char::is_uppercase) {
+    Some(correction[..1].to_uppercase() + &correction[1..])
+  } else {
+    Some(correction.to_string())
+  }
+}
+
+/// Splits `text` into words, along with their byte offset. Words are
+/// separated by anything but letters, and by changes of case, so that
+/// identifiers such as `parseHTTPResponse` and `MAX_LENGTH` are split into
+/// their words.
+pub fn split_words(text: &str) -> Vec<(usize, &str)> {
+  let mut words = Vec::new();
+  let mut start: Option<usize> = None;
+  let mut chars = text.char_indices().peekable();
+  let mut prev: Option<char> = None;
+
+  while let Some((index, c)) = chars.next() {
+    if !c.is_alphabetic() {
+      if let Some(s) = start.take() {
+        words.push((s, &text[s..index]));
+      }
+      prev = None;
+      continue;
+    }
+
+    if let (Some(s), Some(p)) = (start, prev) {
+      let next_is_lower =
+        matches!(chars.peek(), Some((_, next)) if next.is_lowercase());
+      // `aB` starts a word at `B`, and so does `ABc`, the last uppercase
+      // letter of an acronym starting the next word
+      if c.is_uppercase()
+        && (p.is_lowercase() || (p.is_uppercase() && next_is_lower))
+      {
+        words.push((s, &text[s..index]));
+        start = Some(index);
+      }
+    } else {
+      start = Some(index);
+    }
+    prev = Some(c);
+  }
+  if let Some(s) = start {
+    words.push((s, &text[s..]));
+  }
+
+  words
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn misspellings_are_sorted_and_lowercase() {
+    for pair in MISSPELLINGS.windows(2) {
+      assert!(pair[0].0 < pair[1].0, "{} isn't sorted", pair[1].0);
+    }
+    for (misspelling, correction) in MISSPELLINGS {
+      assert_eq!(*misspelling, misspelling.to_lowercase());
+      assert_ne!(misspelling, correction);
+    }
+  }
+
+  #[test]
+  fn correction_follows_case() {
+    assert_eq!(correction("recieve"), Some("receive".to_string()));
+    assert_eq!(correction("Recieve"), Some("Receive".to_string()));
+    assert_eq!(correction("RECIEVE"), Some("RECEIVE".to_string()));
+    assert_eq!(correction("receive"), None);
+    assert_eq!(correction("R"), None);
+  }
+
+  #[test]
+  fn split_words_test() {
+    assert_eq!(
+      split_words("parseHTTPResponse"),
+      vec![(0, "parse"), (5, "HTTP"), (9, "Response")]
+    );
+    assert_eq!(split_words("MAX_LENGTH"), vec![(0, "MAX"), (4, "LENGTH")]);
+    assert_eq!(split_words("$el2Value"), vec![(1, "el"), (4, "Value")]);
+    assert_eq!(
+      split_words(" TODO: don't recieve it"),
+      vec![
+        (1, "TODO"),
+        (7, "don"),
+        (11, "t"),
+        (13, "recieve"),
+        (21, "it")
+      ]
+    );
+  }
+}
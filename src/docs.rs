@@ -0,0 +1,188 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+/// The parts of the documentation of a rule, as returned by `LintRule::docs`.
+#[derive(Debug, Default, PartialEq)]
+pub struct RuleDocs<'a> {
+  /// The first paragraph.
+  pub summary: &'a str,
+  /// The code blocks of the `### Invalid:` section, each reported by the rule.
+  pub invalid: Vec<CodeBlock<'a>>,
+  /// The code blocks of the `### Valid:` section, none reported by the rule.
+  pub valid: Vec<CodeBlock<'a>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CodeBlock<'a> {
+  /// The language of the fence, e.g. `typescript` or `tsx`.
+  pub language: &'a str,
+  pub code: &'a str,
+}
+
+impl<'a> CodeBlock<'a> {
+  /// Returns the options the example is linted with, given as JSON in a
+  /// leading comment, e.g. `// With { "allow": ["git"] }`.
+  pub fn options(&self) -> Option<&'a str> {
+    let first_line = self.code.lines().next()?;
+    let options = first_line.strip_prefix("// With ")?;
+    if options.starts_with('{') {
+      Some(options)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the name of a file the code can be linted as, or `None` for code
+  /// that isn't JavaScript or TypeScript.
+  pub fn file_name(&self) -> Option<&'static str> {
+    match self.language {
+      "typescript" | "ts" | "" => Some("example.ts"),
+      "tsx" => Some("example.tsx"),
+      "javascript" | "js" => Some("example.js"),
+      "jsx" => Some("example.jsx"),
+      _ => None,
+    }
+  }
+}
+
+enum Section {
+  Other,
+  Invalid,
+  Valid,
+}
+
+/// Splits the documentation of a rule into its summary and examples.
+pub fn parse_rule_docs(docs: &str) -> RuleDocs<'_> {
+  let mut rule_docs = RuleDocs {
+    summary: docs.split("\n\n").next().unwrap_or("").trim(),
+    ..Default::default()
+  };
+  let mut section = Section::Other;
+  // The language and the byte offset of the code of the open code block
+  let mut open_block: Option<(&str, usize)> = None;
+  let mut offset = 0;
+
+  for line in docs.split_inclusive('\n') {
+    let line_start = offset;
+    offset += line.len();
+    let trimmed = line.trim_end();
+
+    if let Some((language, code_start)) = open_block {
+      if trimmed != "```" {
+        continue;
+      }
+      open_block = None;
+      let block = CodeBlock {
+        language,
+        code: &docs[code_start..line_start],
+      };
+      match section {
+        Section::Invalid => rule_docs.invalid.push(block),
+        Section::Valid => rule_docs.valid.push(block),
+        Section::Other => {}
+      }
+    } else if let Some(language) = trimmed.strip_prefix("```") {
+      open_block = Some((language.trim(), offset));
+    } else if trimmed.starts_with('#') {
+      section = match trimmed.trim_start_matches('#').trim() {
+        "Invalid:" => Section::Invalid,
+        "Valid:" => Section::Valid,
+        _ => Section::Other,
+      };
+    }
+  }
+  rule_docs
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_rule_docs_test() {
+    let docs = "Disallows `foo`\n\nMore prose.\n\n```json\n{ \"allow\": [] }\n```\n\n### Invalid:\n```typescript\nfoo();\n```\n```tsx\n<Foo />;\n```\n\n### Valid:\n```typescript\nbar();\n```\n";
+    assert_eq!(
+      parse_rule_docs(docs),
+      RuleDocs {
+        summary: "Disallows `foo`",
+        invalid: vec![
+          CodeBlock {
+            language: "typescript",
+            code: "foo();\n",
+          },
+          CodeBlock {
+            language: "tsx",
+            code: "<Foo />;\n",
+          },
+        ],
+        valid: vec![CodeBlock {
+          language: "typescript",
+          code: "bar();\n",
+        }],
+      }
+    );
+  }
+
+  /// Rules whose examples can't be checked on their own, as they depend on
+  /// the name of the file or on other files.
+  const UNCHECKED_EXAMPLES: &[&str] = &[
+    "no-bare-node-style-specifiers",
+    "no-import-test-only-modules-in-prod",
+    "valid-import-map-specifiers",
+  ];
+
+  /// Lints the examples of every rule with the rule alone, so the docs don't
+  /// drift from what the rule reports.
+  #[cfg(feature = "docs")]
+  #[test]
+  fn rule_docs_examples() {
+    use crate::rules::get_all_rules;
+    use crate::test_util::lint;
+
+    let mut failures = Vec::new();
+
+    for rule in get_all_rules() {
+      let code = rule.code();
+      if UNCHECKED_EXAMPLES.contains(&code) {
+        continue;
+      }
+      let docs = parse_rule_docs(rule.docs());
+      let new_rule = |options: Option<&str>| {
+        let mut rule = get_all_rules()
+          .into_iter()
+          .find(|rule| rule.code() == code)
+          .unwrap();
+        if let Some(options) = options {
+          serde_json::from_str(options)
+            .and_then(|options| rule.set_options(options))
+            .unwrap_or_else(|err| panic!("{}: invalid options: {}", code, err));
+        }
+        rule
+      };
+
+      let examples = docs
+        .invalid
+        .iter()
+        .map(|block| (block, true))
+        .chain(docs.valid.iter().map(|block| (block, false)));
+      for (block, is_invalid) in examples {
+        let file_name = match block.file_name() {
+          Some(file_name) => file_name,
+          None => continue,
+        };
+        let reported =
+          lint(new_rule(block.options()), block.code, file_name.to_string())
+            .iter()
+            .any(|diagnostic| diagnostic.code == code);
+        if reported != is_invalid {
+          let expected = if is_invalid { "reported" } else { "accepted" };
+          failures.push(format!(
+            "{}: example should be {}\n{}",
+            code, expected, block.code
+          ));
+        }
+      }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+  }
+}
@@ -0,0 +1,185 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::{LintDiagnostic, StableLocation};
+use swc_common::{BytePos, SourceFile, Span};
+use swc_ecmascript::ast::{
+  ClassDecl, ClassMember, ClassMethod, ClassProp, Constructor, Expr, FnDecl,
+  Pat, PrivateMethod, PrivateProp, Program, PropName, TsEnumDecl,
+  TsInterfaceDecl, TsModuleDecl, TsModuleName, TsTypeAliasDecl, VarDeclarator,
+};
+use swc_ecmascript::visit::{Node, Visit, VisitWith};
+
+/// The maximum number of characters of the flagged code kept in a
+/// `StableLocation`.
+const MAX_TEXT_LEN: usize = 100;
+
+/// Sets the `stable_location` of the diagnostics of the program, from which
+/// `LintDiagnostic::fingerprint` is computed.
+pub(crate) fn set_stable_locations(
+  diagnostics: &mut [LintDiagnostic],
+  program: &Program,
+  source_file: &SourceFile,
+) {
+  if diagnostics.is_empty() {
+    return;
+  }
+  let mut collector = SymbolCollector {
+    path: vec![],
+    symbols: vec![],
+  };
+  program.visit_with(program, &mut collector);
+
+  for diagnostic in diagnostics {
+    let start =
+      source_file.start_pos + BytePos(diagnostic.range.start.byte_pos as u32);
+    // Symbols are either nested or disjoint, and collected in pre-order, so
+    // the innermost one containing the diagnostic is the last one
+    let symbol = collector
+      .symbols
+      .iter()
+      .rev()
+      .find(|symbol| symbol.span.lo <= start && start < symbol.span.hi);
+    let line_offset = match symbol {
+      Some(symbol) => {
+        let symbol_line =
+          source_file.lookup_line(symbol.span.lo).unwrap_or(0) + 1;
+        diagnostic.range.start.line.saturating_sub(symbol_line)
+      }
+      None => 0,
+    };
+    let text = source_file
+      .src
+      .get(diagnostic.range.start.byte_pos..diagnostic.range.end.byte_pos)
+      .unwrap_or("");
+    diagnostic.stable_location = Some(StableLocation {
+      symbol: symbol.map(|symbol| symbol.path.clone()),
+      line_offset,
+      text: text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_TEXT_LEN)
+        .collect(),
+    });
+  }
+}
+
+struct Symbol {
+  span: Span,
+  /// The names of the symbol and the symbols containing it, joined by `.`
+  path: String,
+}
+
+/// Collects the named declarations of a program, i.e. functions, classes and
+/// their members, variables and type declarations.
+struct SymbolCollector {
+  path: Vec<String>,
+  symbols: Vec<Symbol>,
+}
+
+impl SymbolCollector {
+  fn visit_symbol<N: VisitWith<Self>>(
+    &mut self,
+    name: String,
+    span: Span,
+    node: &N,
+  ) {
+    self.path.push(name);
+    self.symbols.push(Symbol {
+      span,
+      path: self.path.join("."),
+    });
+    node.visit_children_with(self);
+    self.path.pop();
+  }
+}
+
+fn prop_name(key: &PropName) -> String {
+  match key {
+    PropName::Ident(ident) => ident.sym.to_string(),
+    PropName::Str(str) => str.value.to_string(),
+    PropName::Num(num) => num.value.to_string(),
+    PropName::Computed(_) => "[computed]".to_string(),
+    PropName::BigInt(big_int) => big_int.value.to_string(),
+  }
+}
+
+impl Visit for SymbolCollector {
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    self.visit_symbol(
+      fn_decl.ident.sym.to_string(),
+      fn_decl.function.span,
+      fn_decl,
+    );
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, _: &dyn Node) {
+    self.visit_symbol(
+      class_decl.ident.sym.to_string(),
+      class_decl.class.span,
+      class_decl,
+    );
+  }
+
+  fn visit_class_member(&mut self, member: &ClassMember, _: &dyn Node) {
+    match member {
+      ClassMember::Constructor(Constructor { span, .. }) => {
+        self.visit_symbol("constructor".to_string(), *span, member)
+      }
+      ClassMember::Method(ClassMethod { key, span, .. }) => {
+        self.visit_symbol(prop_name(key), *span, member)
+      }
+      ClassMember::PrivateMethod(PrivateMethod { key, span, .. }) => {
+        self.visit_symbol(format!("#{}", key.id.sym), *span, member)
+      }
+      ClassMember::ClassProp(ClassProp { key, span, .. }) => {
+        let name = match &**key {
+          Expr::Ident(ident) => ident.sym.to_string(),
+          _ => "[computed]".to_string(),
+        };
+        self.visit_symbol(name, *span, member)
+      }
+      ClassMember::PrivateProp(PrivateProp { key, span, .. }) => {
+        self.visit_symbol(format!("#{}", key.id.sym), *span, member)
+      }
+      _ => member.visit_children_with(self),
+    }
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    match &declarator.name {
+      Pat::Ident(ident) => {
+        self.visit_symbol(ident.id.sym.to_string(), declarator.span, declarator)
+      }
+      _ => declarator.visit_children_with(self),
+    }
+  }
+
+  fn visit_ts_interface_decl(
+    &mut self,
+    interface: &TsInterfaceDecl,
+    _: &dyn Node,
+  ) {
+    self.visit_symbol(interface.id.sym.to_string(), interface.span, interface);
+  }
+
+  fn visit_ts_type_alias_decl(
+    &mut self,
+    alias: &TsTypeAliasDecl,
+    _: &dyn Node,
+  ) {
+    self.visit_symbol(alias.id.sym.to_string(), alias.span, alias);
+  }
+
+  fn visit_ts_enum_decl(&mut self, enum_decl: &TsEnumDecl, _: &dyn Node) {
+    self.visit_symbol(enum_decl.id.sym.to_string(), enum_decl.span, enum_decl);
+  }
+
+  fn visit_ts_module_decl(&mut self, module: &TsModuleDecl, _: &dyn Node) {
+    let name = match &module.id {
+      TsModuleName::Ident(ident) => ident.sym.to_string(),
+      TsModuleName::Str(str) => str.value.to_string(),
+    };
+    self.visit_symbol(name, module.span, module);
+  }
+}
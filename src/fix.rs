@@ -0,0 +1,124 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::{LintDiagnostic, LintFix, LintFixChange};
+
+/// Applies the given fixes to the source code and returns the fixed source.
+///
+/// Fixes are applied in order and a fix overlapping with a fix applied before
+/// it is skipped entirely, so linting the fixed source again might yield more
+/// fixes.
+pub fn apply_fixes<'a>(
+  source: &str,
+  fixes: impl IntoIterator<Item = &'a LintFix>,
+) -> String {
+  let mut changes: Vec<&LintFixChange> = Vec::new();
+  for fix in fixes {
+    let overlaps_applied = fix
+      .changes
+      .iter()
+      .any(|change| changes.iter().any(|applied| overlaps(change, applied)));
+    if !overlaps_applied {
+      changes.extend(&fix.changes);
+    }
+  }
+  changes.sort_by_key(|change| change.range.start.byte_pos);
+
+  let mut fixed = String::with_capacity(source.len());
+  let mut last_pos = 0;
+  for change in changes {
+    fixed.push_str(&source[last_pos..change.range.start.byte_pos]);
+    fixed.push_str(&change.new_text);
+    last_pos = change.range.end.byte_pos;
+  }
+  fixed.push_str(&source[last_pos..]);
+  fixed
+}
+
+/// Applies only the fix at `suggestion_index` of the diagnostic, e.g. the
+/// quick fix picked in an editor, and returns the fixed source. The source is
+/// returned unchanged if the diagnostic has no such fix.
+pub fn apply_suggestion(
+  source: &str,
+  diagnostic: &LintDiagnostic,
+  suggestion_index: usize,
+) -> String {
+  apply_fixes(source, diagnostic.fixes.get(suggestion_index))
+}
+
+/// Two insertions at the same position are considered to be overlapping as
+/// the order in which they should be applied is ambiguous.
+fn overlaps(a: &LintFixChange, b: &LintFixChange) -> bool {
+  let (a_start, a_end) = (a.range.start.byte_pos, a.range.end.byte_pos);
+  let (b_start, b_end) = (b.range.start.byte_pos, b.range.end.byte_pos);
+  a_start == b_start || (a_start < b_end && b_start < a_end)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::{Position, Range};
+
+  fn change(start: usize, end: usize, new_text: &str) -> LintFixChange {
+    let position = |byte_pos| Position {
+      line: 1,
+      col: byte_pos,
+      byte_pos,
+    };
+    LintFixChange {
+      new_text: new_text.to_string(),
+      range: Range {
+        start: position(start),
+        end: position(end),
+      },
+    }
+  }
+
+  fn fix(changes: Vec<LintFixChange>) -> LintFix {
+    LintFix {
+      description: "fix".to_string(),
+      changes,
+    }
+  }
+
+  #[test]
+  fn apply_fixes_in_source_order() {
+    let fixes = vec![
+      fix(vec![change(8, 9, "2")]),
+      fix(vec![change(0, 3, "let"), change(10, 10, " // two")]),
+    ];
+    assert_eq!(apply_fixes("var a = 1;", &fixes), "let a = 2; // two");
+  }
+
+  #[test]
+  fn apply_fixes_skips_overlapping() {
+    let fixes = vec![
+      fix(vec![change(4, 9, "b = 2")]),
+      fix(vec![change(0, 3, "let"), change(8, 9, "3")]),
+      fix(vec![change(9, 9, ";")]),
+      fix(vec![change(9, 9, "!")]),
+    ];
+    assert_eq!(apply_fixes("var a = 1", &fixes), "var b = 2;");
+  }
+
+  #[test]
+  fn apply_suggestion_test() {
+    let diagnostic = LintDiagnostic {
+      range: change(0, 9, "").range,
+      filename: "lint_test.ts".to_string(),
+      message: "message".to_string(),
+      code: "code".to_string(),
+      hint: None,
+      fixes: vec![
+        fix(vec![change(0, 3, "let")]),
+        fix(vec![change(0, 3, "const"), change(9, 9, ";")]),
+      ],
+      related: vec![],
+      stable_location: None,
+    };
+    assert_eq!(apply_suggestion("var a = 1", &diagnostic, 0), "let a = 1");
+    assert_eq!(
+      apply_suggestion("var a = 1", &diagnostic, 1),
+      "const a = 1;"
+    );
+    assert_eq!(apply_suggestion("var a = 1", &diagnostic, 2), "var a = 1");
+  }
+}
@@ -2,7 +2,7 @@
 use crate::diagnostic::{LintDiagnostic, Position};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use swc_common::comments::Comment;
 use swc_common::comments::CommentKind;
 use swc_common::SourceMap;
@@ -33,6 +33,24 @@ impl IgnoreDirective {
     &self.used_codes
   }
 
+  /// Returns `true` for `deno-lint-ignore-file` directives.
+  pub fn is_global(&self) -> bool {
+    self.is_global
+  }
+
+  /// Returns the codes listed more than once in the directive, each of them
+  /// only once.
+  pub fn duplicate_codes(&self) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    let mut duplicates = vec![];
+    for code in &self.codes {
+      if !seen.insert(code.as_str()) && !duplicates.contains(&code.as_str()) {
+        duplicates.push(code.as_str());
+      }
+    }
+    duplicates
+  }
+
   /// Check if `IgnoreDirective` supresses given `diagnostic` and if so
   /// mark the directive as used
   pub fn maybe_ignore_diagnostic(
@@ -124,6 +142,37 @@ pub fn parse_ignore_comment(
   None
 }
 
+/// Returns the code among `candidates` closest to the unknown `code`, if it's
+/// close enough to likely be a typo of it.
+pub(crate) fn suggest_code<'a>(
+  code: &str,
+  candidates: impl IntoIterator<Item = &'a String>,
+) -> Option<&'a str> {
+  let max_distance = (code.chars().count() / 3).max(1);
+  candidates
+    .into_iter()
+    .map(|candidate| (edit_distance(code, candidate), candidate))
+    .filter(|(distance, _)| *distance <= max_distance)
+    .min()
+    .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Levenshtein distance between `a` and `b`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut diagonal = row[0];
+    row[0] = i + 1;
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = diagonal + (a_char != *b_char) as usize;
+      diagonal = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+    }
+  }
+  row[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -209,4 +258,41 @@ object | undefined {}
     );
     assert_eq!(d.codes, vec!["ban-types"]);
   }
+
+  #[test]
+  fn test_duplicate_codes() {
+    let source_code = r#"
+// deno-lint-ignore no-empty no-debugger no-empty, no-empty
+function foo() {}
+"#;
+    let (_, comments, source_map, _) = test_util::parse(source_code);
+    let (leading, _) = comments.take_all();
+    let leading_coms = Rc::try_unwrap(leading)
+      .expect("Failed to get leading comments")
+      .into_inner();
+    let directives = parse_ignore_directives(
+      "deno-lint-ignore",
+      &source_map,
+      leading_coms.values().flatten(),
+    );
+
+    assert_eq!(directives.len(), 1);
+    assert_eq!(directives[0].duplicate_codes(), vec!["no-empty"]);
+  }
+
+  #[test]
+  fn test_suggest_code() {
+    let codes: Vec<String> = ["no-debugger", "no-empty", "no-explicit-any"]
+      .iter()
+      .map(|code| code.to_string())
+      .collect();
+    assert_eq!(
+      suggest_code("no-explict-any", &codes),
+      Some("no-explicit-any")
+    );
+    assert_eq!(suggest_code("no-debuger", &codes), Some("no-debugger"));
+    assert_eq!(suggest_code("no-emtpy", &codes), Some("no-empty"));
+    assert_eq!(suggest_code("some-rule", &codes), None);
+    assert_eq!(suggest_code("ab", &codes), None);
+  }
 }
@@ -0,0 +1,271 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use serde::de::Error as _;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ImportMapJson {
+  imports: HashMap<String, String>,
+  scopes: HashMap<String, HashMap<String, String>>,
+}
+
+/// Specifiers mapped to addresses, sorted by decreasing length so the first
+/// match is the most specific one.
+type SpecifierMap = Vec<(String, String)>;
+
+fn specifier_map(map: HashMap<String, String>) -> SpecifierMap {
+  let mut map: SpecifierMap = map.into_iter().collect();
+  map.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+  map
+}
+
+/// An import map, as used by Deno to resolve bare specifiers.
+///
+/// Only what's needed to check specifiers is supported: relative addresses are
+/// resolved against the directory of the import map, but specifier keys are
+/// matched as written.
+#[derive(Debug)]
+pub(crate) struct ImportMap {
+  base_dir: PathBuf,
+  imports: SpecifierMap,
+  /// Scopes with their prefix resolved, most specific first.
+  scopes: Vec<(String, SpecifierMap)>,
+}
+
+/// Where a specifier mapped by an import map resolves to.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Resolution {
+  /// A remote or `npm:`, `node:`, ... URL.
+  Url(String),
+  /// A local file.
+  Path(PathBuf),
+  /// Another bare specifier, which can't be imported.
+  Bare(String),
+}
+
+impl ImportMap {
+  /// Reads the import map at `path`.
+  pub fn load(path: &str) -> Result<Self, serde_json::Error> {
+    let contents = std::fs::read_to_string(path).map_err(|err| {
+      serde_json::Error::custom(format!(
+        "Failed to read import map \"{}\": {}",
+        path, err
+      ))
+    })?;
+    let base_dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+    Self::parse(&contents, &absolute(base_dir))
+  }
+
+  /// Parses an import map whose relative addresses and scopes are resolved
+  /// against `base_dir`.
+  pub fn parse(json: &str, base_dir: &Path) -> Result<Self, serde_json::Error> {
+    let import_map: ImportMapJson = serde_json::from_str(json)?;
+    let mut scopes: Vec<(String, SpecifierMap)> = import_map
+      .scopes
+      .into_iter()
+      .map(|(prefix, map)| {
+        let prefix = if is_relative(&prefix) {
+          path_to_string(&normalize(&base_dir.join(&prefix)))
+            + if prefix.ends_with('/') { "/" } else { "" }
+        } else {
+          prefix
+        };
+        (prefix, specifier_map(map))
+      })
+      .collect();
+    scopes
+      .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    Ok(ImportMap {
+      base_dir: base_dir.to_path_buf(),
+      imports: specifier_map(import_map.imports),
+      scopes,
+    })
+  }
+
+  /// Returns `true` if any of the mappings of the import map applies to the
+  /// specifier, in any scope.
+  pub fn is_mapped(&self, specifier: &str) -> bool {
+    self
+      .scopes
+      .iter()
+      .map(|(_, map)| map)
+      .chain(std::iter::once(&self.imports))
+      .any(|map| map_specifier(map, specifier).is_some())
+  }
+
+  /// Resolves a specifier imported from the `referrer` file, or returns `None`
+  /// if no mapping applies to it.
+  pub fn resolve(&self, specifier: &str, referrer: &str) -> Option<Resolution> {
+    let referrer = if is_url(referrer) {
+      referrer.to_string()
+    } else {
+      path_to_string(&normalize(&absolute(Path::new(referrer))))
+    };
+    let address = self
+      .scopes
+      .iter()
+      .filter(|(prefix, _)| referrer.starts_with(prefix.as_str()))
+      .map(|(_, map)| map)
+      .chain(std::iter::once(&self.imports))
+      .find_map(|map| map_specifier(map, specifier))?;
+
+    Some(if let Some(path) = address.strip_prefix("file://") {
+      Resolution::Path(PathBuf::from(path))
+    } else if is_url(&address) {
+      Resolution::Url(address)
+    } else if is_relative(&address) {
+      Resolution::Path(normalize(&self.base_dir.join(&address)))
+    } else {
+      Resolution::Bare(address)
+    })
+  }
+}
+
+/// Maps a specifier with the mapping equal to it, or the longest mapping
+/// ending with `/` it starts with.
+fn map_specifier(map: &[(String, String)], specifier: &str) -> Option<String> {
+  map.iter().find_map(|(key, address)| {
+    if key == specifier {
+      Some(address.clone())
+    } else if key.ends_with('/') && address.ends_with('/') {
+      specifier
+        .strip_prefix(key.as_str())
+        .map(|rest| format!("{}{}", address, rest))
+    } else {
+      None
+    }
+  })
+}
+
+/// Returns `true` for specifiers starting with a URL scheme, e.g.
+/// `https://deno.land/x/mod.ts`, `npm:preact` and `node:fs`.
+pub(crate) fn is_url(specifier: &str) -> bool {
+  match specifier.find(':') {
+    // A single letter is a Windows drive, e.g. `C:\`
+    Some(colon) if colon > 1 => {
+      let scheme = &specifier[..colon];
+      scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    }
+    _ => false,
+  }
+}
+
+/// Returns `true` for specifiers relative to the importing module, or to the
+/// root of the file system.
+pub(crate) fn is_relative(specifier: &str) -> bool {
+  specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || specifier.starts_with('/')
+}
+
+fn absolute(path: &Path) -> PathBuf {
+  if path.is_absolute() {
+    path.to_path_buf()
+  } else {
+    std::env::current_dir()
+      .map(|dir| dir.join(path))
+      .unwrap_or_else(|_| path.to_path_buf())
+  }
+}
+
+/// Removes the `.` and `..` components of a path, without looking at the
+/// file system.
+fn normalize(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+fn path_to_string(path: &Path) -> String {
+  path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn import_map() -> ImportMap {
+    ImportMap::parse(
+      r#"{
+        "imports": {
+          "preact": "https://esm.sh/preact",
+          "std/": "https://deno.land/std@0.200.0/",
+          "std/path/": "./vendor/path/",
+          "utils": "./src/utils.ts",
+          "lodash": "lodash-es"
+        },
+        "scopes": {
+          "./vendor/": { "preact": "./vendor/preact.ts" }
+        }
+      }"#,
+      Path::new("/project"),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn resolve_test() {
+    let import_map = import_map();
+    let referrer = "/project/main.ts";
+    assert_eq!(
+      import_map.resolve("preact", referrer),
+      Some(Resolution::Url("https://esm.sh/preact".to_string()))
+    );
+    assert_eq!(
+      import_map.resolve("std/fs/mod.ts", referrer),
+      Some(Resolution::Url(
+        "https://deno.land/std@0.200.0/fs/mod.ts".to_string()
+      ))
+    );
+    assert_eq!(
+      import_map.resolve("std/path/mod.ts", referrer),
+      Some(Resolution::Path(PathBuf::from(
+        "/project/vendor/path/mod.ts"
+      )))
+    );
+    assert_eq!(
+      import_map.resolve("utils", referrer),
+      Some(Resolution::Path(PathBuf::from("/project/src/utils.ts")))
+    );
+    assert_eq!(
+      import_map.resolve("lodash", referrer),
+      Some(Resolution::Bare("lodash-es".to_string()))
+    );
+    assert_eq!(import_map.resolve("preact/hooks", referrer), None);
+    assert_eq!(import_map.resolve("./main.ts", referrer), None);
+  }
+
+  #[test]
+  fn resolve_in_scope() {
+    let import_map = import_map();
+    assert_eq!(
+      import_map.resolve("preact", "/project/vendor/../vendor/mod.ts"),
+      Some(Resolution::Path(PathBuf::from("/project/vendor/preact.ts")))
+    );
+    assert!(import_map.is_mapped("preact"));
+    assert!(!import_map.is_mapped("react"));
+  }
+
+  #[test]
+  fn is_url_test() {
+    assert!(is_url("https://deno.land/x/mod.ts"));
+    assert!(is_url("npm:preact@10"));
+    assert!(is_url("node:fs"));
+    assert!(!is_url("C:/project/mod.ts"));
+    assert!(!is_url("preact"));
+    assert!(!is_url("./a:b.ts"));
+  }
+}
@@ -0,0 +1,388 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+/// A node of the syntax tree of a regular expression pattern, covering the
+/// bytes `start..end` of the pattern.
+#[derive(Debug, PartialEq)]
+pub struct RegexNode {
+  pub kind: RegexNodeKind,
+  pub start: usize,
+  pub end: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RegexNodeKind {
+  /// `a|b`
+  Disjunction(Vec<RegexNode>),
+  /// `ab`
+  Alternative(Vec<RegexNode>),
+  /// `(a)`, `(?:a)` or `(?<name>a)`
+  Group(Box<RegexNode>),
+  /// `(?=a)`, `(?!a)`, `(?<=a)` or `(?<!a)`
+  Lookaround(Box<RegexNode>),
+  /// `a*`, `a+`, `a?` or `a{1,2}`, where a `max` of `None` is unbounded.
+  Quantifier {
+    node: Box<RegexNode>,
+    min: u32,
+    max: Option<u32>,
+    greedy: bool,
+  },
+  /// `^`, `$`, `\b` or `\B`
+  Assertion,
+  /// A character, character class, escape or backreference.
+  Atom,
+}
+
+/// Parses the structure of a regular expression pattern, as written in a
+/// regular expression literal.
+///
+/// This is only as strict as needed to build the tree, so patterns should be
+/// checked with `EcmaRegexValidator` if their validity matters. `None` is
+/// returned for patterns whose structure can't be determined, like ones with
+/// unbalanced parentheses.
+pub fn parse_regex_pattern(pattern: &str) -> Option<RegexNode> {
+  let mut parser = Parser {
+    chars: pattern.char_indices().collect(),
+    index: 0,
+    len: pattern.len(),
+  };
+  let node = parser.parse_disjunction()?;
+  if parser.index == parser.chars.len() {
+    Some(node)
+  } else {
+    None
+  }
+}
+
+struct Parser {
+  chars: Vec<(usize, char)>,
+  index: usize,
+  len: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.index).map(|(_, c)| *c)
+  }
+
+  fn peek_at(&self, offset: usize) -> Option<char> {
+    self.chars.get(self.index + offset).map(|(_, c)| *c)
+  }
+
+  /// The byte offset of the current character.
+  fn offset(&self) -> usize {
+    self
+      .chars
+      .get(self.index)
+      .map(|(offset, _)| *offset)
+      .unwrap_or(self.len)
+  }
+
+  fn eat(&mut self, c: char) -> bool {
+    if self.peek() == Some(c) {
+      self.index += 1;
+      true
+    } else {
+      false
+    }
+  }
+
+  /// Skips past the next `end` character, failing if there's none.
+  fn skip_past(&mut self, end: char) -> Option<()> {
+    while let Some(c) = self.peek() {
+      self.index += 1;
+      if c == end {
+        return Some(());
+      }
+    }
+    None
+  }
+
+  fn parse_disjunction(&mut self) -> Option<RegexNode> {
+    let start = self.offset();
+    let mut alternatives = vec![self.parse_alternative()?];
+    while self.eat('|') {
+      alternatives.push(self.parse_alternative()?);
+    }
+    if alternatives.len() == 1 {
+      alternatives.pop()
+    } else {
+      Some(RegexNode {
+        kind: RegexNodeKind::Disjunction(alternatives),
+        start,
+        end: self.offset(),
+      })
+    }
+  }
+
+  fn parse_alternative(&mut self) -> Option<RegexNode> {
+    let start = self.offset();
+    let mut terms = Vec::new();
+    while let Some(c) = self.peek() {
+      if c == '|' || c == ')' {
+        break;
+      }
+      terms.push(self.parse_term()?);
+    }
+    Some(RegexNode {
+      kind: RegexNodeKind::Alternative(terms),
+      start,
+      end: self.offset(),
+    })
+  }
+
+  fn parse_term(&mut self) -> Option<RegexNode> {
+    let start = self.offset();
+    let kind = match self.peek()? {
+      '(' => self.parse_group()?,
+      '[' => {
+        self.parse_class()?;
+        RegexNodeKind::Atom
+      }
+      '\\' => self.parse_escape()?,
+      '^' | '$' => {
+        self.index += 1;
+        RegexNodeKind::Assertion
+      }
+      // Nothing to quantify
+      '*' | '+' | '?' => return None,
+      '{' if self.quantifier_bounds().is_some() => return None,
+      _ => {
+        self.index += 1;
+        RegexNodeKind::Atom
+      }
+    };
+    let atom = RegexNode {
+      kind,
+      start,
+      end: self.offset(),
+    };
+    self.parse_quantifier(atom)
+  }
+
+  fn parse_group(&mut self) -> Option<RegexNodeKind> {
+    self.index += 1;
+    let is_lookaround = if self.eat('?') {
+      match self.peek()? {
+        ':' => {
+          self.index += 1;
+          false
+        }
+        '=' | '!' => {
+          self.index += 1;
+          true
+        }
+        '<' if matches!(self.peek_at(1), Some('=') | Some('!')) => {
+          self.index += 2;
+          true
+        }
+        '<' => {
+          self.skip_past('>')?;
+          false
+        }
+        _ => return None,
+      }
+    } else {
+      false
+    };
+    let node = Box::new(self.parse_disjunction()?);
+    if !self.eat(')') {
+      return None;
+    }
+    if is_lookaround {
+      Some(RegexNodeKind::Lookaround(node))
+    } else {
+      Some(RegexNodeKind::Group(node))
+    }
+  }
+
+  fn parse_class(&mut self) -> Option<()> {
+    self.index += 1;
+    self.eat('^');
+    while let Some(c) = self.peek() {
+      self.index += 1;
+      match c {
+        ']' => return Some(()),
+        '\\' => self.index += 1,
+        _ => {}
+      }
+    }
+    None
+  }
+
+  fn parse_escape(&mut self) -> Option<RegexNodeKind> {
+    self.index += 1;
+    let c = self.peek()?;
+    self.index += 1;
+    match c {
+      'b' | 'B' => return Some(RegexNodeKind::Assertion),
+      // `\u{1F600}`, `\p{Letter}` and `\P{Letter}`
+      'u' | 'p' | 'P' if self.peek() == Some('{') => self.skip_past('}')?,
+      // `\k<name>`
+      'k' if self.peek() == Some('<') => self.skip_past('>')?,
+      _ => {}
+    }
+    Some(RegexNodeKind::Atom)
+  }
+
+  /// Returns the bounds of the braced quantifier at the current position and
+  /// the number of characters it spans, if there's one.
+  fn quantifier_bounds(&self) -> Option<(u32, Option<u32>, usize)> {
+    let rest: String = self.chars[self.index..]
+      .iter()
+      .map(|(_, c)| *c)
+      .take_while(|c| *c != '}')
+      .collect();
+    let body = rest.strip_prefix('{')?;
+    if self.peek_at(rest.chars().count()) != Some('}') {
+      return None;
+    }
+    let parse_bound = |bound: &str| -> Option<u32> {
+      if bound.is_empty() || !bound.chars().all(|c| c.is_ascii_digit()) {
+        None
+      } else {
+        Some(bound.parse().unwrap_or(u32::MAX))
+      }
+    };
+    let (min, max) = match body.find(',') {
+      Some(comma) => {
+        let min = parse_bound(&body[..comma])?;
+        let max = &body[comma + 1..];
+        if max.is_empty() {
+          (min, None)
+        } else {
+          (min, Some(parse_bound(max)?))
+        }
+      }
+      None => {
+        let bound = parse_bound(body)?;
+        (bound, Some(bound))
+      }
+    };
+    Some((min, max, rest.chars().count() + 1))
+  }
+
+  fn parse_quantifier(&mut self, atom: RegexNode) -> Option<RegexNode> {
+    let (min, max) = match self.peek() {
+      Some('*') => {
+        self.index += 1;
+        (0, None)
+      }
+      Some('+') => {
+        self.index += 1;
+        (1, None)
+      }
+      Some('?') => {
+        self.index += 1;
+        (0, Some(1))
+      }
+      Some('{') => match self.quantifier_bounds() {
+        Some((min, max, len)) => {
+          self.index += len;
+          (min, max)
+        }
+        None => return Some(atom),
+      },
+      _ => return Some(atom),
+    };
+    let greedy = !self.eat('?');
+    let start = atom.start;
+    Some(RegexNode {
+      kind: RegexNodeKind::Quantifier {
+        node: Box::new(atom),
+        min,
+        max,
+        greedy,
+      },
+      start,
+      end: self.offset(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn node(kind: RegexNodeKind, start: usize, end: usize) -> RegexNode {
+    RegexNode { kind, start, end }
+  }
+
+  #[test]
+  fn parse_regex_pattern_structure() {
+    assert_eq!(
+      parse_regex_pattern("(a+)*?|[)]"),
+      Some(node(
+        RegexNodeKind::Disjunction(vec![
+          node(
+            RegexNodeKind::Alternative(vec![node(
+              RegexNodeKind::Quantifier {
+                node: Box::new(node(
+                  RegexNodeKind::Group(Box::new(node(
+                    RegexNodeKind::Alternative(vec![node(
+                      RegexNodeKind::Quantifier {
+                        node: Box::new(node(RegexNodeKind::Atom, 1, 2)),
+                        min: 1,
+                        max: None,
+                        greedy: true,
+                      },
+                      1,
+                      3
+                    )]),
+                    1,
+                    3
+                  ))),
+                  0,
+                  4
+                )),
+                min: 0,
+                max: None,
+                greedy: false,
+              },
+              0,
+              6
+            )]),
+            0,
+            6
+          ),
+          node(
+            RegexNodeKind::Alternative(vec![node(RegexNodeKind::Atom, 7, 10)]),
+            7,
+            10
+          ),
+        ]),
+        0,
+        10
+      ))
+    );
+  }
+
+  #[test]
+  fn parse_regex_pattern_quantifiers() {
+    let bounds = |pattern: &str| match parse_regex_pattern(pattern) {
+      Some(RegexNode {
+        kind: RegexNodeKind::Alternative(mut terms),
+        ..
+      }) => match terms.pop().map(|term| term.kind) {
+        Some(RegexNodeKind::Quantifier { min, max, .. }) => Some((min, max)),
+        _ => None,
+      },
+      _ => None,
+    };
+    assert_eq!(bounds("a{2}"), Some((2, Some(2))));
+    assert_eq!(bounds("a{2,}"), Some((2, None)));
+    assert_eq!(bounds("a{2,5}"), Some((2, Some(5))));
+    assert_eq!(bounds("\\p{L}+"), Some((1, None)));
+    assert_eq!(bounds("(?<name>a)?"), Some((0, Some(1))));
+    assert_eq!(bounds("a{,5}"), None);
+    assert_eq!(bounds("a{"), None);
+  }
+
+  #[test]
+  fn parse_regex_pattern_invalid() {
+    assert_eq!(parse_regex_pattern("(a"), None);
+    assert_eq!(parse_regex_pattern("a)"), None);
+    assert_eq!(parse_regex_pattern("[a"), None);
+    assert_eq!(parse_regex_pattern("*a"), None);
+    assert_eq!(parse_regex_pattern("a|{1}"), None);
+  }
+}
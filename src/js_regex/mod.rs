@@ -1,9 +1,11 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 
+mod ast;
 mod reader;
 mod unicode;
 mod validator;
 
+pub use ast::{parse_regex_pattern, RegexNode, RegexNodeKind};
 pub use validator::{EcmaRegexValidator, EcmaVersion};
 
 #[cfg(test)]
@@ -9,20 +9,30 @@ extern crate log;
 #[macro_use]
 mod test_util;
 
+mod aria;
 pub mod ast_parser;
 pub mod context;
 // TODO(magurotuna): Making control_flow public is just needed for implementing plugin prototype.
 // It will be likely possible to remove `pub` later.
 pub mod control_flow;
 pub mod diagnostic;
+mod dictionary;
+pub mod docs;
+mod fingerprint;
+pub mod fix;
 mod globals;
 mod handler;
 mod ignore_directives;
+mod import_map;
 mod js_regex;
 pub mod linter;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+pub mod metrics;
 pub mod rules;
 mod scopes;
 pub mod swc_util;
+pub mod type_provider;
 
 #[cfg(test)]
 mod lint_tests {
@@ -42,7 +52,7 @@ mod lint_tests {
     unused_dir: bool,
     rules: Vec<Box<dyn LintRule>>,
   ) -> Vec<LintDiagnostic> {
-    let linter = LinterBuilder::default()
+    let mut linter = LinterBuilder::default()
       .lint_unknown_rules(unknown_rules)
       .lint_unused_ignore_directives(unused_dir)
       .rules(rules)
@@ -63,7 +73,7 @@ mod lint_tests {
     unused_dir: bool,
     rules: Vec<Box<dyn LintRule>>,
   ) -> Vec<LintDiagnostic> {
-    let linter = LinterBuilder::default()
+    let mut linter = LinterBuilder::default()
       .lint_unknown_rules(unknown_rules)
       .lint_unused_ignore_directives(unused_dir)
       .rules(rules)
@@ -263,6 +273,102 @@ const _fooBar = 42;
     assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 4, 1, src);
   }
 
+  #[test]
+  fn suggest_unknown_rule_code() {
+    let src = r#"
+// deno-lint-ignore no-explict-any
+const _a: any = 1;
+      "#;
+    let diagnostics = lint_recommended_rules(src, true, false);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "ban-unknown-rule-code", 2, 0, src);
+    assert_eq!(
+      diagnostics[0].hint.as_deref(),
+      Some("Did you mean \"no-explicit-any\"?")
+    );
+  }
+
+  #[test]
+  fn warn_duplicate_ignore_codes() {
+    let src = r#"
+// deno-lint-ignore no-explicit-any no-explicit-any
+const _a: any = 1;
+      "#;
+    let diagnostics = lint_recommended_rules(src, false, true);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-dupe-ignore-codes", 2, 0, src);
+  }
+
+  #[test]
+  fn warn_misplaced_ignore_directives() {
+    let src = r#"
+// deno-lint-ignore no-explicit-any
+
+const _a: any = 1;
+const _b = 2;
+// deno-lint-ignore-file no-debugger
+// deno-lint-ignore no-explicit-any"#;
+    let diagnostics = lint_recommended_rules(src, false, true);
+
+    assert_eq!(diagnostics.len(), 4);
+    assert_diagnostic(&diagnostics[0], "ban-misplaced-ignore", 2, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-explicit-any", 4, 10, src);
+    assert_diagnostic(&diagnostics[2], "ban-misplaced-ignore", 6, 0, src);
+    assert_diagnostic(&diagnostics[3], "ban-misplaced-ignore", 7, 0, src);
+  }
+
+  #[test]
+  fn ignore_directive_before_comment() {
+    use crate::rules::ban_untagged_todo::BanUntaggedTodo;
+    let diagnostics = lint_specified_rule::<BanUntaggedTodo>(
+      r#"
+// deno-lint-ignore ban-untagged-todo
+// TODO: remove
+const _a = 1;
+      "#,
+      false,
+      true,
+    );
+
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn require_ignore_codes() {
+    use crate::rules::no_debugger::NoDebugger;
+    let src = r#"// deno-lint-ignore-file
+
+// deno-lint-ignore
+debugger;
+      "#;
+    let mut linter = LinterBuilder::default()
+      .require_ignore_codes(true)
+      .rules(vec![NoDebugger::new()])
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_diagnostic(&diagnostics[0], "ban-untagged-ignore", 1, 0, src);
+    assert_diagnostic(&diagnostics[1], "ban-untagged-ignore", 3, 0, src);
+    assert_diagnostic(&diagnostics[2], "no-debugger", 4, 0, src);
+
+    // not reported twice when the rule is enabled
+    let diagnostics = lint_recommended_rules(src, false, false);
+    assert!(diagnostics.is_empty());
+    let mut linter = LinterBuilder::default()
+      .require_ignore_codes(true)
+      .rules(get_recommended_rules())
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 3);
+  }
+
   #[test]
   fn empty_file_with_ast() {
     let (ast, comments, source_map, tokens) = parse("");
@@ -271,4 +377,227 @@ const _fooBar = 42;
     );
     assert!(diagnostics.is_empty());
   }
+
+  #[test]
+  fn linter_is_reusable() {
+    let mut linter = LinterBuilder::default()
+      .lint_unknown_rules(true)
+      .lint_unused_ignore_directives(true)
+      .rules(get_recommended_rules())
+      .build();
+
+    let src = "debugger;";
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 1, 0, src);
+
+    // parse errors of a file aren't reported for the next ones
+    for _ in 0..2 {
+      let err = linter
+        .lint("lint_test.ts".to_string(), "const = ;".to_string())
+        .unwrap_err();
+      assert_eq!(err.diagnostics.len(), 1);
+    }
+
+    // the same file name is linted again with new contents
+    let src = r#"
+// deno-lint-ignore some-rule
+const _b = 2;
+      "#;
+    let (source_file, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(&*source_file.src, src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "ban-unknown-rule-code", 2, 0, src);
+  }
+
+  #[test]
+  fn lint_in_range() {
+    let mut linter = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build();
+
+    let src = r#"function a() { debugger; }
+const used = 1;
+function b() { debugger; return used; }
+function c() { debugger; }
+a(); b(); c();
+"#;
+    let start = src.find("function b").unwrap();
+    let end = src.find("function c").unwrap();
+    let report = linter
+      .lint_in_range("lint_test.ts".to_string(), src.to_string(), start..end)
+      .expect("Failed to lint");
+    // `b` and `used` are used outside of the range, so aren't reported as
+    // unused
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_diagnostic(&report.diagnostics[0], "no-debugger", 3, 15, src);
+  }
+
+  #[test]
+  fn parsed_source_cache() {
+    use crate::ast_parser::{get_default_ts_config, ParsedSourceCache};
+
+    let cache = Rc::new(ParsedSourceCache::new());
+    let mut linter = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .parsed_source_cache(Rc::clone(&cache))
+      .build();
+
+    let src = "foo();\ndebugger;";
+    for _ in 0..2 {
+      let (_, diagnostics) = linter
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .expect("Failed to lint");
+      assert_eq!(diagnostics.len(), 1);
+      assert_diagnostic(&diagnostics[0], "no-debugger", 2, 0, src);
+    }
+
+    // the program parsed by the linter is shared with other consumers
+    let syntax = get_default_ts_config();
+    let parsed = cache.get_or_parse("lint_test.ts", syntax, src).unwrap();
+    let src = "debugger;";
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-debugger", 1, 0, src);
+    let reparsed = cache.get_or_parse("lint_test.ts", syntax, src).unwrap();
+    assert!(!Rc::ptr_eq(&parsed, &reparsed));
+    assert!(Rc::ptr_eq(
+      &reparsed,
+      &cache.get_or_parse("lint_test.ts", syntax, src).unwrap()
+    ));
+  }
+
+  #[test]
+  fn diagnostic_fingerprints() {
+    let fingerprints = |src: &str| {
+      lint_recommended_rules(src, false, false)
+        .iter()
+        .map(|d| d.fingerprint())
+        .collect::<Vec<_>>()
+    };
+
+    let src = r#"
+export function a() {
+  debugger;
+}
+export class B {
+  c() {
+    debugger;
+  }
+}
+"#;
+    let original = fingerprints(src);
+    assert_eq!(original.len(), 2);
+    assert_ne!(original[0], original[1]);
+
+    // unrelated edits move the diagnostics, but don't change their
+    // fingerprints
+    let edited = src
+      .replace("export function a", "foo();\n\nexport function a")
+      .replace("class B {", "class B {\n  d = 1;");
+    assert_eq!(fingerprints(&edited), original);
+
+    let renamed = src.replace("c()", "e()");
+    assert_eq!(fingerprints(&renamed)[0], original[0]);
+    assert_ne!(fingerprints(&renamed)[1], original[1]);
+  }
+
+  #[test]
+  fn diagnostic_order() {
+    use crate::rules::{no_debugger::NoDebugger, no_var::NoVar};
+
+    let src = "var _a = 1; debugger;\ndebugger;";
+    let rules =
+      || -> Vec<Box<dyn LintRule>> { vec![NoDebugger::new(), NoVar::new()] };
+    let diagnostics = lint(src, false, false, rules());
+    assert_diagnostic(&diagnostics[0], "no-var", 1, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-debugger", 1, 12, src);
+    assert_diagnostic(&diagnostics[2], "no-debugger", 2, 0, src);
+
+    let mut linter = LinterBuilder::default()
+      .discovery_order(true)
+      .rules(rules())
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_diagnostic(&diagnostics[0], "no-debugger", 1, 12, src);
+    assert_diagnostic(&diagnostics[1], "no-debugger", 2, 0, src);
+    assert_diagnostic(&diagnostics[2], "no-var", 1, 0, src);
+  }
+
+  #[test]
+  fn lint_with_visitor() {
+    use crate::context::Context;
+    use crate::rules::ProgramRef;
+    use swc_common::BytePos;
+    use swc_ecmascript::ast::CallExpr;
+    use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+    struct CallCounter(usize);
+
+    impl Visit for CallCounter {
+      noop_visit_type!();
+
+      fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+        self.0 += 1;
+        call_expr.visit_children_with(self);
+      }
+    }
+
+    let mut linter = LinterBuilder::default()
+      .rules(get_recommended_rules())
+      .build();
+
+    let mut counter = CallCounter(0);
+    let mut comments = 0;
+    let mut visitor = |context: &Context, program: ProgramRef| {
+      comments = context.all_comments().count();
+      match program {
+        ProgramRef::Module(m) => counter.visit_module(m, m),
+        ProgramRef::Script(s) => counter.visit_script(s, s),
+      }
+    };
+
+    let src = "// a comment\nfoo(bar());\ndebugger;";
+    let report = linter
+      .lint_with_visitor(
+        "lint_test.ts".to_string(),
+        src.to_string(),
+        &mut visitor,
+      )
+      .expect("Failed to lint");
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(counter.0, 2);
+    assert_eq!(comments, 1);
+
+    // the visitor runs on ignored files and without any rules, with all the
+    // analyses available
+    let mut linter = LinterBuilder::default().build();
+    let mut counter = CallCounter(0);
+    let mut visitor = |context: &Context, program: ProgramRef| {
+      assert!(context.scope().ids_with_symbol(&"foo".into()).is_none());
+      assert!(context.control_flow().meta(BytePos(0)).is_none());
+      match program {
+        ProgramRef::Module(m) => counter.visit_module(m, m),
+        ProgramRef::Script(s) => counter.visit_script(s, s),
+      }
+    };
+    let src = "// deno-lint-ignore-file\nfoo();";
+    let report = linter
+      .lint_with_visitor(
+        "lint_test.ts".to_string(),
+        src.to_string(),
+        &mut visitor,
+      )
+      .expect("Failed to lint");
+    assert!(report.diagnostics.is_empty());
+    assert_eq!(counter.0, 1);
+  }
 }
@@ -1,20 +1,28 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use crate::ast_parser::get_default_ts_config;
 use crate::ast_parser::AstParser;
-use crate::ast_parser::ParsedData;
+use crate::ast_parser::ParsedSourceCache;
 use crate::ast_parser::SwcDiagnosticBuffer;
 use crate::context::Context;
 use crate::control_flow::ControlFlow;
-use crate::diagnostic::LintDiagnostic;
+use crate::diagnostic::{sort_diagnostics, LintDiagnostic};
+use crate::fingerprint::set_stable_locations;
 use crate::ignore_directives::parse_ignore_comment;
 use crate::ignore_directives::parse_ignore_directives;
-use crate::rules::{get_all_rules, LintRule};
+use crate::ignore_directives::suggest_code;
+use crate::ignore_directives::IgnoreDirective;
+use crate::metrics::{collect_metrics, FileMetrics};
+use crate::rules::{ban_untagged_ignore, get_all_rules, LintRule, ProgramRef};
 use crate::scopes::Scope;
-use dprint_swc_ecma_ast_view::{self as AstView, RootNode};
+use crate::type_provider::TypeProvider;
+use dprint_swc_ecma_ast_view as AstView;
+use std::collections::HashSet;
 use std::rc::Rc;
 use std::time::Instant;
 use swc_common::comments::SingleThreadedComments;
+use swc_common::BytePos;
 use swc_common::SourceMap;
+use swc_common::Span;
 use swc_common::Spanned;
 use swc_common::SyntaxContext;
 use swc_ecmascript::parser::token::TokenAndSpan;
@@ -27,9 +35,14 @@ pub struct LinterBuilder {
   ignore_diagnostic_directive: String,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
+  require_ignore_codes: bool,
+  collect_metrics: bool,
+  discovery_order: bool,
   syntax: swc_ecmascript::parser::Syntax,
   rules: Vec<Box<dyn LintRule>>,
   plugins: Vec<Box<dyn Plugin>>,
+  type_provider: Option<Rc<dyn TypeProvider>>,
+  parsed_source_cache: Option<Rc<ParsedSourceCache>>,
 }
 
 impl LinterBuilder {
@@ -39,22 +52,19 @@ impl LinterBuilder {
       ignore_diagnostic_directive: "deno-lint-ignore".to_string(),
       lint_unused_ignore_directives: true,
       lint_unknown_rules: true,
+      require_ignore_codes: false,
+      collect_metrics: false,
+      discovery_order: false,
       syntax: get_default_ts_config(),
       rules: vec![],
       plugins: vec![],
+      type_provider: None,
+      parsed_source_cache: None,
     }
   }
 
   pub fn build(self) -> Linter {
-    Linter::new(
-      self.ignore_file_directive,
-      self.ignore_diagnostic_directive,
-      self.lint_unused_ignore_directives,
-      self.lint_unknown_rules,
-      self.syntax,
-      self.rules,
-      self.plugins,
-    )
+    Linter::new(self)
   }
 
   pub fn ignore_file_directive(mut self, directive: &str) -> Self {
@@ -80,6 +90,28 @@ impl LinterBuilder {
     self
   }
 
+  /// Reports ignore directives without rule codes, including
+  /// `deno-lint-ignore-file`, which then no longer ignores the whole file.
+  pub fn require_ignore_codes(mut self, require_ignore_codes: bool) -> Self {
+    self.require_ignore_codes = require_ignore_codes;
+    self
+  }
+
+  /// Collects code metrics of each file in the same pass as linting, returned
+  /// in `LintReport::metrics`.
+  pub fn collect_metrics(mut self, collect_metrics: bool) -> Self {
+    self.collect_metrics = collect_metrics;
+    self
+  }
+
+  /// Returns the diagnostics of a file in the order they were reported, i.e.
+  /// grouped by rule in the order the rules run, instead of sorting them
+  /// with `sort_diagnostics`.
+  pub fn discovery_order(mut self, discovery_order: bool) -> Self {
+    self.discovery_order = discovery_order;
+    self
+  }
+
   pub fn syntax(mut self, syntax: Syntax) -> Self {
     self.syntax = syntax;
     self
@@ -94,81 +126,220 @@ impl LinterBuilder {
     self.plugins.push(plugin);
     self
   }
+
+  /// Sets the provider of type information used by type-aware rules.
+  pub fn type_provider(mut self, type_provider: Rc<dyn TypeProvider>) -> Self {
+    self.type_provider = Some(type_provider);
+    self
+  }
+
+  /// Sets a cache the linted files are parsed through, so unchanged files
+  /// aren't parsed again. The cache may be shared with other consumers of the
+  /// parsed programs.
+  pub fn parsed_source_cache(
+    mut self,
+    parsed_source_cache: Rc<ParsedSourceCache>,
+  ) -> Self {
+    self.parsed_source_cache = Some(parsed_source_cache);
+    self
+  }
+}
+
+/// The result of linting a single file.
+pub struct LintReport {
+  pub source_file: Rc<SourceFile>,
+  /// Sorted with `sort_diagnostics`, unless `LinterBuilder::discovery_order`
+  /// is enabled.
+  pub diagnostics: Vec<LintDiagnostic>,
+  /// Code metrics of the file, only collected when enabled with
+  /// `LinterBuilder::collect_metrics`.
+  pub metrics: Option<FileMetrics>,
 }
 
+/// Lints source files with the configured rules and plugins.
+///
+/// The same `Linter` value can be used to lint any number of files: the rule
+/// instances and their options, the plugins and the set of known rule codes
+/// are created once when it's built. The parser is kept as well, reusing its
+/// error handler and token buffer for the next files, while the source map,
+/// hygiene marks, scope analysis and diagnostics are created anew for each
+/// file, so a long running process such as a language server doesn't
+/// accumulate state from the files it linted. Identifiers and string values
+/// are interned by swc in a process-wide table, shared between all files and
+/// linters.
+///
+/// A `Linter` is neither `Send` nor `Sync`, as the parser state is reference
+/// counted and rules and plugins aren't required to be thread safe. Programs
+/// linting files in parallel should keep one `Linter` per thread (e.g. in a
+/// thread local or a pool of linters) instead of sharing a single one.
 pub struct Linter {
   ast_parser: AstParser,
   ignore_file_directive: String,
   ignore_diagnostic_directive: String,
   lint_unused_ignore_directives: bool,
   lint_unknown_rules: bool,
+  require_ignore_codes: bool,
+  collect_metrics: bool,
+  discovery_order: bool,
   syntax: Syntax,
   rules: Vec<Box<dyn LintRule>>,
   plugins: Vec<Box<dyn Plugin>>,
+  type_provider: Option<Rc<dyn TypeProvider>>,
+  parsed_source_cache: Option<Rc<ParsedSourceCache>>,
+  /// Codes of all the builtin rules, used to report ignore directives for
+  /// unknown rules.
+  available_rule_codes: HashSet<String>,
 }
 
 impl Linter {
-  fn new(
-    ignore_file_directive: String,
-    ignore_diagnostic_directive: String,
-    lint_unused_ignore_directives: bool,
-    lint_unknown_rules: bool,
-    syntax: Syntax,
-    rules: Vec<Box<dyn LintRule>>,
-    plugins: Vec<Box<dyn Plugin>>,
-  ) -> Self {
+  fn new(builder: LinterBuilder) -> Self {
     Linter {
       ast_parser: AstParser::new(),
-      ignore_file_directive,
-      ignore_diagnostic_directive,
-      lint_unused_ignore_directives,
-      lint_unknown_rules,
-      syntax,
-      rules,
-      plugins,
+      ignore_file_directive: builder.ignore_file_directive,
+      ignore_diagnostic_directive: builder.ignore_diagnostic_directive,
+      lint_unused_ignore_directives: builder.lint_unused_ignore_directives,
+      lint_unknown_rules: builder.lint_unknown_rules,
+      require_ignore_codes: builder.require_ignore_codes,
+      collect_metrics: builder.collect_metrics,
+      discovery_order: builder.discovery_order,
+      syntax: builder.syntax,
+      rules: builder.rules,
+      plugins: builder.plugins,
+      type_provider: builder.type_provider,
+      parsed_source_cache: builder.parsed_source_cache,
+      available_rule_codes: get_all_rules()
+        .iter()
+        .map(|r| r.code().to_string())
+        .collect(),
     }
   }
 
   pub fn lint(
-    mut self,
+    &mut self,
     file_name: String,
     source_code: String,
   ) -> Result<
     (Rc<swc_common::SourceFile>, Vec<LintDiagnostic>),
     SwcDiagnosticBuffer,
   > {
+    let report = self.lint_report(file_name, source_code)?;
+    Ok((report.source_file, report.diagnostics))
+  }
+
+  /// Lints the file like `lint`, also returning its code metrics if enabled
+  /// with `LinterBuilder::collect_metrics`.
+  pub fn lint_report(
+    &mut self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
+    self.lint_file(file_name, source_code, None, None)
+  }
+
+  /// Lints only the given byte range of the file, e.g. the part visible in an
+  /// editor or a function that was just modified, for faster feedback on
+  /// large files.
+  ///
+  /// The whole file is still parsed and analyzed, but rules only traverse the
+  /// top level items overlapping the range, unless they need the whole
+  /// program (see `LintRule::needs_whole_program`), and only diagnostics
+  /// overlapping the range are reported.
+  pub fn lint_in_range(
+    &mut self,
+    file_name: String,
+    source_code: String,
+    range: std::ops::Range<usize>,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
+    self.lint_file(file_name, source_code, None, Some(range))
+  }
+
+  /// Lints the file like `lint_report`, also running the visitor over the
+  /// parsed program. The visitor runs even if the file is ignored with a
+  /// `deno-lint-ignore-file` directive, and with no rules configured the
+  /// linter only serves to parse and analyze the file for it.
+  pub fn lint_with_visitor(
+    &mut self,
+    file_name: String,
+    source_code: String,
+    visitor: &mut dyn ProgramVisitor,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
+    self.lint_file(file_name, source_code, Some(visitor), None)
+  }
+
+  fn lint_file(
+    &mut self,
+    file_name: String,
+    source_code: String,
+    visitor: Option<&mut dyn ProgramVisitor>,
+    range: Option<std::ops::Range<usize>>,
+  ) -> Result<LintReport, SwcDiagnosticBuffer> {
     let start = Instant::now();
 
-    let parse_result =
-      self
-        .ast_parser
-        .parse_program(&file_name, self.syntax, &source_code);
+    self.ast_parser.reset();
+    let parsed_source;
+    let mut parsed_data = None;
+    let (program, comments, tokens) = match self.parsed_source_cache.clone() {
+      Some(cache) => {
+        parsed_source =
+          cache.get_or_parse(&file_name, self.syntax, &source_code)?;
+        self.ast_parser.set_parsed_source(&parsed_source);
+        (
+          parsed_source.program(),
+          parsed_source.comments(),
+          parsed_source.tokens(),
+        )
+      }
+      None => {
+        let data = parsed_data.get_or_insert(self.ast_parser.parse_program(
+          &file_name,
+          self.syntax,
+          &source_code,
+        )?);
+        (&data.program, &data.comments, &data.tokens[..])
+      }
+    };
     let end_parse_program = Instant::now();
     debug!(
       "ast_parser.parse_program took {:#?}",
       end_parse_program - start
     );
-    let ParsedData {
-      program,
-      comments,
-      tokens,
-    } = parse_result?;
     let source_file = self
       .ast_parser
       .source_map
       .get_source_file(&swc_common::FileName::Custom(file_name.clone()))
       .unwrap();
+    let range = range.map(|range| {
+      Span::new(
+        source_file.start_pos + BytePos(range.start as u32),
+        source_file.start_pos + BytePos(range.end as u32),
+        SyntaxContext::empty(),
+      )
+    });
 
-    let diagnostics =
-      self.lint_program(file_name, &program, &comments, &tokens, &source_file);
+    let (diagnostics, metrics) = self.lint_program(
+      file_name,
+      program,
+      comments,
+      tokens,
+      &source_file,
+      visitor,
+      range,
+    );
+    if let Some(parsed_data) = parsed_data {
+      self.ast_parser.reuse_tokens(parsed_data.tokens);
+    }
 
     let end = Instant::now();
     debug!("Linter::lint took {:#?}", end - start);
-    Ok((source_file, diagnostics))
+    Ok(LintReport {
+      source_file,
+      diagnostics,
+      metrics,
+    })
   }
 
   pub fn lint_with_ast(
-    mut self,
+    &mut self,
     file_name: String,
     ast: &swc_ecmascript::ast::Program,
     comments: &SingleThreadedComments,
@@ -180,6 +351,7 @@ impl Linter {
   > {
     let start = Instant::now();
 
+    self.ast_parser.reset();
     self.ast_parser.set_source_map(source_map);
     let source_file = self
       .ast_parser
@@ -187,8 +359,15 @@ impl Linter {
       .get_source_file(&swc_common::FileName::Custom(file_name.clone()))
       .unwrap();
 
-    let diagnostics =
-      self.lint_program(file_name, ast, comments, tokens, &source_file);
+    let (diagnostics, _) = self.lint_program(
+      file_name,
+      ast,
+      comments,
+      tokens,
+      &source_file,
+      None,
+      None,
+    );
 
     let end = Instant::now();
     debug!("Linter::lint_with_ast took {:#?}", end - start);
@@ -196,7 +375,14 @@ impl Linter {
     Ok((source_file, diagnostics))
   }
 
-  fn filter_diagnostics(&self, mut context: Context) -> Vec<LintDiagnostic> {
+  /// Filters out the ignored diagnostics and reports the problems with the
+  /// ignore directives. `file_directive_spans` are the spans of all the
+  /// comments parsed as file ignore directives, wherever they are.
+  fn filter_diagnostics(
+    &self,
+    mut context: Context,
+    file_directive_spans: Vec<Span>,
+  ) -> Vec<LintDiagnostic> {
     let start = Instant::now();
 
     let (executed_rule_codes, available_rule_codes) = {
@@ -206,7 +392,7 @@ impl Linter {
 
       let mut available = context.plugin_codes().clone();
       // builtin all available rules
-      available.extend(get_all_rules().iter().map(|r| r.code().to_string()));
+      available.extend(self.available_rule_codes.iter().cloned());
 
       (executed, available)
     };
@@ -230,6 +416,7 @@ impl Linter {
           if self.lint_unused_ignore_directives
             && !used
             && executed_rule_codes.contains(code)
+            && !is_misplaced(&context, ignore_directive)
           {
             let diagnostic = context.create_diagnostic(
               ignore_directive.span(),
@@ -241,11 +428,13 @@ impl Linter {
           }
 
           if self.lint_unknown_rules && !available_rule_codes.contains(code) {
+            let hint = suggest_code(code, &available_rule_codes)
+              .map(|suggestion| format!("Did you mean \"{}\"?", suggestion));
             let diagnostic = context.create_diagnostic(
               ignore_directive.span(),
               "ban-unknown-rule-code",
               format!("Unknown rule for code \"{}\"", code),
-              None,
+              hint,
             );
             filtered_diagnostics.push(diagnostic);
           }
@@ -253,7 +442,74 @@ impl Linter {
       }
     }
 
-    filtered_diagnostics.sort_by_key(|d| d.range.start.line);
+    // Directives that are redundant or can't ignore anything are reported
+    // along with the unused ones
+    if self.lint_unused_ignore_directives {
+      for ignore_directive in ignore_directives.iter() {
+        for code in ignore_directive.duplicate_codes() {
+          let diagnostic = context.create_diagnostic(
+            ignore_directive.span(),
+            "no-dupe-ignore-codes",
+            format!("Code \"{}\" is ignored more than once.", code),
+            Some("Remove the duplicate code".to_string()),
+          );
+          filtered_diagnostics.push(diagnostic);
+        }
+
+        if is_misplaced(&context, ignore_directive) {
+          let diagnostic = context.create_diagnostic(
+            ignore_directive.span(),
+            "ban-misplaced-ignore",
+            "Ignore directive is not followed by any code, so it has no effect.",
+            Some(
+              "Move the directive to the line right before the code it ignores"
+                .to_string(),
+            ),
+          );
+          filtered_diagnostics.push(diagnostic);
+        }
+      }
+
+      // Only a directive before any code ignores diagnostics in the whole file
+      let file_directive_span = ignore_directives
+        .iter()
+        .find(|ignore_directive| ignore_directive.is_global())
+        .map(|ignore_directive| ignore_directive.span());
+      let misplaced_file_directives = file_directive_spans
+        .into_iter()
+        .filter(|span| Some(*span) != file_directive_span);
+      for span in misplaced_file_directives {
+        let diagnostic = context.create_diagnostic(
+          span,
+          "ban-misplaced-ignore",
+          "File ignore directive is not at the top of the file, so it has no effect.",
+          Some("Move the directive before any code in the file".to_string()),
+        );
+        filtered_diagnostics.push(diagnostic);
+      }
+    }
+
+    // The `ban-untagged-ignore` rule reports these directives itself when
+    // it's enabled
+    if self.require_ignore_codes
+      && !executed_rule_codes.contains(ban_untagged_ignore::CODE)
+    {
+      for ignore_directive in ignore_directives.iter() {
+        if ignore_directive.codes().is_empty() {
+          let diagnostic = context.create_diagnostic(
+            ignore_directive.span(),
+            ban_untagged_ignore::CODE,
+            ban_untagged_ignore::MESSAGE,
+            Some(ban_untagged_ignore::HINT.to_string()),
+          );
+          filtered_diagnostics.push(diagnostic);
+        }
+      }
+    }
+
+    if !self.discovery_order {
+      sort_diagnostics(&mut filtered_diagnostics);
+    }
 
     let end = Instant::now();
     debug!("Linter::filter_diagnostics took {:#?}", end - start);
@@ -261,6 +517,7 @@ impl Linter {
     filtered_diagnostics
   }
 
+  #[allow(clippy::too_many_arguments)]
   fn lint_program(
     &mut self,
     file_name: String,
@@ -268,7 +525,9 @@ impl Linter {
     comments: &SingleThreadedComments,
     tokens: &[TokenAndSpan],
     source_file: &SourceFile,
-  ) -> Vec<LintDiagnostic> {
+    visitor: Option<&mut dyn ProgramVisitor>,
+    range: Option<Span>,
+  ) -> (Vec<LintDiagnostic>, Option<FileMetrics>) {
     let start = Instant::now();
     let file_ignore_directive =
       comments.with_leading(program.span().lo(), |c| {
@@ -283,41 +542,89 @@ impl Linter {
       });
 
     // If there's a file ignore directive that has no codes specified we must ignore
-    // whole file and skip linting it.
-    if matches!(
-      &file_ignore_directive,
-      Some(ignore_directive) if ignore_directive.codes().is_empty()
-    ) {
-      return vec![];
+    // whole file and skip linting it, unless codes are required. Its metrics
+    // are still collected and the visitor still runs.
+    let ignore_file = !self.require_ignore_codes
+      && matches!(
+        &file_ignore_directive,
+        Some(ignore_directive) if ignore_directive.codes().is_empty()
+      );
+    if ignore_file && !self.collect_metrics && visitor.is_none() {
+      return (vec![], None);
     }
 
-    let scope = Scope::analyze(&program);
-    let control_flow = ControlFlow::analyze(&program);
+    // Analyses are only built if something needs them, the rules declaring
+    // what they use while plugins and visitors may use anything
+    let needs = |needs_analysis: fn(&dyn LintRule) -> bool| {
+      visitor.is_some()
+        || !ignore_file
+          && (!self.plugins.is_empty()
+            || self.rules.iter().any(|rule| needs_analysis(&**rule)))
+    };
+    let scope = if needs(|rule| rule.needs_scope()) {
+      Some(Scope::analyze(&program))
+    } else {
+      None
+    };
+    let control_flow = if needs(|rule| rule.needs_cfg()) {
+      Some(ControlFlow::analyze(&program))
+    } else {
+      None
+    };
+    let type_provider = if needs(|rule| rule.needs_types()) {
+      self.type_provider.clone()
+    } else {
+      None
+    };
+    // Comments are always parsed for the ignore directives, but are only
+    // exposed through the view when something inspects them
+    let needs_comments =
+      self.collect_metrics || needs(|rule| rule.needs_comments());
+    let mut ignore_directives = if let Some(file_ignore) = file_ignore_directive
+    {
+      vec![file_ignore]
+    } else {
+      vec![]
+    };
+    let file_directive_spans = {
+      let (leading, trailing) = comments.borrow_all();
+      let all_comments = || leading.values().chain(trailing.values()).flatten();
+      ignore_directives.extend(parse_ignore_directives(
+        &self.ignore_diagnostic_directive,
+        &self.ast_parser.source_map,
+        all_comments(),
+      ));
+      if self.lint_unused_ignore_directives {
+        all_comments()
+          .filter_map(|comment| {
+            parse_ignore_comment(
+              &self.ignore_file_directive,
+              &self.ast_parser.source_map,
+              comment,
+              true,
+            )
+          })
+          .map(|ignore_directive| ignore_directive.span())
+          .collect()
+      } else {
+        vec![]
+      }
+    };
     let top_level_ctxt = swc_common::GLOBALS
       .set(&self.ast_parser.globals, || {
         SyntaxContext::empty().apply_mark(self.ast_parser.top_level_mark)
       });
 
+    let items_in_range = range.map(|range| program_in_range(program, range));
+
     let program_info = AstView::ProgramInfo {
       program,
       source_file: Some(source_file),
       tokens: Some(tokens),
-      comments: Some(comments),
+      comments: if needs_comments { Some(comments) } else { None },
     };
 
-    let diagnostics = AstView::with_ast_view(program_info, |pg| {
-      let mut ignore_directives =
-        if let Some(file_ignore) = file_ignore_directive {
-          vec![file_ignore]
-        } else {
-          vec![]
-        };
-      ignore_directives.extend(parse_ignore_directives(
-        &self.ignore_diagnostic_directive,
-        &self.ast_parser.source_map,
-        pg.comments().unwrap().all_comments(),
-      ));
-
+    let result = AstView::with_ast_view(program_info, |pg| {
       let mut context = Context::new(
         file_name,
         Rc::clone(&self.ast_parser.source_map),
@@ -326,11 +633,34 @@ impl Linter {
         scope,
         control_flow,
         top_level_ctxt,
+        type_provider,
       );
 
+      if let Some(visitor) = visitor {
+        let program_ref = match pg {
+          AstView::Program::Module(m) => ProgramRef::Module(m.inner),
+          AstView::Program::Script(s) => ProgramRef::Script(s.inner),
+        };
+        visitor.visit_program(&context, program_ref);
+      }
+
+      let metrics = if self.collect_metrics {
+        Some(collect_metrics(pg, &mut context, tokens, source_file))
+      } else {
+        None
+      };
+      if ignore_file {
+        return (vec![], metrics);
+      }
+
       // Run builtin rules
       for rule in &self.rules {
-        rule.lint_program_with_ast_view(&mut context, pg);
+        match &items_in_range {
+          Some(items) if !rule.needs_whole_program() => {
+            rule.lint_program(&mut context, program_ref(items))
+          }
+          _ => rule.lint_program_with_ast_view(&mut context, pg),
+        }
       }
 
       // Run plugin rules
@@ -339,13 +669,100 @@ impl Linter {
         let _ = plugin.run(&mut context, program.clone());
       }
 
-      self.filter_diagnostics(context)
+      let mut diagnostics =
+        self.filter_diagnostics(context, file_directive_spans);
+      set_stable_locations(&mut diagnostics, program, source_file);
+      if let Some(range) = range {
+        let start = (range.lo - source_file.start_pos).0 as usize;
+        let end = (range.hi - source_file.start_pos).0 as usize;
+        diagnostics.retain(|d| {
+          d.range.start.byte_pos <= end && start <= d.range.end.byte_pos
+        });
+      }
+      (diagnostics, metrics)
     });
 
     let end = Instant::now();
     debug!("Linter::lint_module took {:#?}", end - start);
 
-    diagnostics
+    result
+  }
+}
+
+/// Returns a copy of the program with only the top level items overlapping the
+/// range.
+fn program_in_range(
+  program: &swc_ecmascript::ast::Program,
+  range: Span,
+) -> swc_ecmascript::ast::Program {
+  use swc_ecmascript::ast::{Module, Program, Script};
+
+  let overlaps = |span: Span| span.lo <= range.hi && range.lo <= span.hi;
+  match program {
+    Program::Module(module) => Program::Module(Module {
+      span: module.span,
+      body: module
+        .body
+        .iter()
+        .filter(|item| overlaps(item.span()))
+        .cloned()
+        .collect(),
+      shebang: module.shebang.clone(),
+    }),
+    Program::Script(script) => Program::Script(Script {
+      span: script.span,
+      body: script
+        .body
+        .iter()
+        .filter(|stmt| overlaps(stmt.span()))
+        .cloned()
+        .collect(),
+      shebang: script.shebang.clone(),
+    }),
+  }
+}
+
+fn program_ref(program: &swc_ecmascript::ast::Program) -> ProgramRef<'_> {
+  match program {
+    swc_ecmascript::ast::Program::Module(m) => ProgramRef::Module(m),
+    swc_ecmascript::ast::Program::Script(s) => ProgramRef::Script(s),
+  }
+}
+
+/// Returns `true` if the directive can't ignore anything as it isn't followed
+/// by a line of code, since a directive only applies to the line following it.
+fn is_misplaced(context: &Context, ignore_directive: &IgnoreDirective) -> bool {
+  if ignore_directive.is_global() {
+    return false;
+  }
+  let location = context
+    .source_map()
+    .lookup_char_pos(ignore_directive.span().lo());
+  let next_line = location.file.get_line(location.line);
+  !matches!(next_line, Some(line) if !line.trim().is_empty())
+}
+
+/// A custom analysis run over the program being linted with
+/// `Linter::lint_with_visitor`, sharing the parse, scope analysis and comments
+/// of the linter through the `Context` instead of parsing the file again.
+pub trait ProgramVisitor {
+  fn visit_program<'view>(
+    &mut self,
+    context: &Context<'view>,
+    program: ProgramRef<'view>,
+  );
+}
+
+impl<F> ProgramVisitor for F
+where
+  F: for<'view> FnMut(&Context<'view>, ProgramRef<'view>),
+{
+  fn visit_program<'view>(
+    &mut self,
+    context: &Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    self(context, program)
   }
 }
 
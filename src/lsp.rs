@@ -0,0 +1,347 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::{
+  LintDiagnostic, LintFix, LintRelatedInformation, Position, Range,
+};
+use lsp_types::{
+  CodeAction, CodeActionKind, CodeDescription, Diagnostic,
+  DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag, Location,
+  NumberOrString, TextEdit, Url, WorkspaceEdit,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The `source` of the converted diagnostics.
+pub const SOURCE: &str = "deno-lint";
+
+/// The URL rule codes are appended to for `codeDescription`.
+pub const RULE_DOCS_URL: &str = "https://lint.deno.land/#";
+
+/// A fix carried in the `data` of a diagnostic, with its edits already in LSP
+/// ranges so code actions can be created without the source.
+#[derive(Debug, Deserialize, Serialize)]
+struct LspFix {
+  description: String,
+  edits: Vec<TextEdit>,
+}
+
+/// Converts a lint diagnostic of the file at `uri`, whose contents are
+/// `source`.
+///
+/// - Ranges are converted to zero-based lines and UTF-16 columns.
+/// - Unused ignore directives are hints tagged as unnecessary, everything else
+///   is a warning.
+/// - Related locations are attached as related information, followed by the
+///   hint, if any, at the same range as the diagnostic.
+/// - Fixes are carried in `data`, see `code_actions`.
+pub fn to_lsp_diagnostic(
+  diagnostic: &LintDiagnostic,
+  source: &str,
+  uri: &Url,
+) -> Diagnostic {
+  let range = to_lsp_range(&diagnostic.range, source);
+  let (severity, tags) = match diagnostic.code.as_str() {
+    "ban-unused-ignore" => (
+      DiagnosticSeverity::Hint,
+      Some(vec![DiagnosticTag::Unnecessary]),
+    ),
+    _ => (DiagnosticSeverity::Warning, None),
+  };
+  let mut related_information: Vec<DiagnosticRelatedInformation> = diagnostic
+    .related
+    .iter()
+    .filter_map(|related| to_lsp_related(related, diagnostic, source, uri))
+    .collect();
+  if let Some(hint) = &diagnostic.hint {
+    related_information.push(DiagnosticRelatedInformation {
+      location: Location::new(uri.clone(), range),
+      message: hint.clone(),
+    });
+  }
+  let related_information = if related_information.is_empty() {
+    None
+  } else {
+    Some(related_information)
+  };
+  let data = if diagnostic.fixes.is_empty() {
+    None
+  } else {
+    let fixes: Vec<LspFix> = diagnostic
+      .fixes
+      .iter()
+      .map(|fix| to_lsp_fix(fix, source))
+      .collect();
+    Some(serde_json::to_value(fixes).expect("Failed to serialize fixes"))
+  };
+
+  Diagnostic {
+    range,
+    severity: Some(severity),
+    code: Some(NumberOrString::String(diagnostic.code.clone())),
+    code_description: Url::parse(&format!(
+      "{}{}",
+      RULE_DOCS_URL, diagnostic.code
+    ))
+    .ok()
+    .map(|href| CodeDescription { href }),
+    source: Some(SOURCE.to_string()),
+    message: diagnostic.message.clone(),
+    related_information,
+    tags,
+    data,
+  }
+}
+
+/// Returns a quick fix for each of the fixes carried by a diagnostic created
+/// by `to_lsp_diagnostic`, as sent back by the client in a
+/// `textDocument/codeAction` request.
+pub fn code_actions(diagnostic: &Diagnostic, uri: &Url) -> Vec<CodeAction> {
+  let is_lint_diagnostic =
+    diagnostic.source.as_deref() == Some(SOURCE) && diagnostic.data.is_some();
+  if !is_lint_diagnostic {
+    return vec![];
+  }
+  let fixes: Vec<LspFix> =
+    match serde_json::from_value(diagnostic.data.clone().unwrap()) {
+      Ok(fixes) => fixes,
+      Err(_) => return vec![],
+    };
+
+  // Only a single fix is unambiguous enough to be applied automatically
+  let is_preferred = fixes.len() == 1;
+  fixes
+    .into_iter()
+    .map(|fix| {
+      let mut changes = HashMap::new();
+      changes.insert(uri.clone(), fix.edits);
+      CodeAction {
+        title: fix.description,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+          changes: Some(changes),
+          ..Default::default()
+        }),
+        is_preferred: Some(is_preferred),
+        ..Default::default()
+      }
+    })
+    .collect()
+}
+
+/// Converts a related location, which is only converted to UTF-16 columns if
+/// it's in the same file as the diagnostic since only its source is known.
+fn to_lsp_related(
+  related: &LintRelatedInformation,
+  diagnostic: &LintDiagnostic,
+  source: &str,
+  uri: &Url,
+) -> Option<DiagnosticRelatedInformation> {
+  let location = if related.filename == diagnostic.filename {
+    Location::new(uri.clone(), to_lsp_range(&related.range, source))
+  } else {
+    let position = |position: &Position| lsp_types::Position {
+      line: position.line.saturating_sub(1) as u32,
+      character: position.col as u32,
+    };
+    Location::new(
+      Url::from_file_path(&related.filename).ok()?,
+      lsp_types::Range::new(
+        position(&related.range.start),
+        position(&related.range.end),
+      ),
+    )
+  };
+  Some(DiagnosticRelatedInformation {
+    location,
+    message: related.message.clone(),
+  })
+}
+
+fn to_lsp_fix(fix: &LintFix, source: &str) -> LspFix {
+  LspFix {
+    description: fix.description.clone(),
+    edits: fix
+      .changes
+      .iter()
+      .map(|change| TextEdit {
+        range: to_lsp_range(&change.range, source),
+        new_text: change.new_text.clone(),
+      })
+      .collect(),
+  }
+}
+
+pub fn to_lsp_range(range: &Range, source: &str) -> lsp_types::Range {
+  lsp_types::Range {
+    start: to_lsp_position(&range.start, source),
+    end: to_lsp_position(&range.end, source),
+  }
+}
+
+/// Converts a position to a zero-based line and a column in UTF-16 code
+/// units, as counted by the LSP.
+pub fn to_lsp_position(
+  position: &Position,
+  source: &str,
+) -> lsp_types::Position {
+  let line_start = source
+    .get(..position.byte_pos)
+    .and_then(|before| before.rfind('\n'))
+    .map_or(0, |newline| newline + 1);
+  let character = match source.get(line_start..position.byte_pos) {
+    Some(line) => line.encode_utf16().count(),
+    // The position isn't in `source`, which is only expected if it's stale
+    None => position.col,
+  };
+  lsp_types::Position {
+    line: position.line.saturating_sub(1) as u32,
+    character: character as u32,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::LintFixChange;
+
+  fn position(source: &str, byte_pos: usize) -> Position {
+    let before = &source[..byte_pos];
+    Position {
+      line: before.matches('\n').count() + 1,
+      col: before.rsplit('\n').next().unwrap().chars().count(),
+      byte_pos,
+    }
+  }
+
+  fn range(source: &str, text: &str) -> Range {
+    let start = source.find(text).unwrap();
+    Range {
+      start: position(source, start),
+      end: position(source, start + text.len()),
+    }
+  }
+
+  #[test]
+  fn to_lsp_position_utf16() {
+    let source = "const a = 1;\nconst 𝒳 = \"é\"; debugger;";
+    let lsp_range = to_lsp_range(&range(source, "debugger"), source);
+    // `𝒳` is two UTF-16 code units and four bytes, `é` is one and two
+    assert_eq!(lsp_range.start, lsp_types::Position::new(1, 16));
+    assert_eq!(lsp_range.end, lsp_types::Position::new(1, 24));
+  }
+
+  #[test]
+  fn to_lsp_diagnostic_test() {
+    let source = "let a = 1;\nlet b = a;";
+    let uri = Url::parse("file:///project/mod.ts").unwrap();
+    let diagnostic = LintDiagnostic {
+      range: range(source, "let b = a;"),
+      filename: "/project/mod.ts".to_string(),
+      message: "`b` is never reassigned".to_string(),
+      code: "prefer-const".to_string(),
+      hint: Some("Use `const` instead".to_string()),
+      fixes: vec![LintFix {
+        description: "Replace `let` with `const`".to_string(),
+        changes: vec![LintFixChange {
+          new_text: "const b".to_string(),
+          range: range(source, "let b"),
+        }],
+      }],
+      related: vec![],
+      stable_location: None,
+    };
+
+    let lsp_diagnostic = to_lsp_diagnostic(&diagnostic, source, &uri);
+    assert_eq!(lsp_diagnostic.severity, Some(DiagnosticSeverity::Warning));
+    assert_eq!(
+      lsp_diagnostic.code,
+      Some(NumberOrString::String("prefer-const".to_string()))
+    );
+    assert_eq!(
+      lsp_diagnostic
+        .code_description
+        .as_ref()
+        .unwrap()
+        .href
+        .as_str(),
+      "https://lint.deno.land/#prefer-const"
+    );
+    assert_eq!(
+      lsp_diagnostic.related_information.as_ref().unwrap()[0].message,
+      "Use `const` instead"
+    );
+
+    let actions = code_actions(&lsp_diagnostic, &uri);
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].title, "Replace `let` with `const`");
+    assert_eq!(actions[0].is_preferred, Some(true));
+    let edits =
+      &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+    assert_eq!(
+      edits,
+      &vec![TextEdit::new(
+        lsp_types::Range::new(
+          lsp_types::Position::new(1, 0),
+          lsp_types::Position::new(1, 5)
+        ),
+        "const b".to_string()
+      )]
+    );
+  }
+
+  #[test]
+  fn to_lsp_diagnostic_related() {
+    let source = "export const 𝒳 = 1;\nexport { 𝒳 };";
+    let uri = Url::parse("file:///project/mod.ts").unwrap();
+    let diagnostic = LintDiagnostic {
+      range: range(source, "𝒳 }"),
+      filename: "/project/mod.ts".to_string(),
+      message: "Duplicate export of '𝒳'".to_string(),
+      code: "no-duplicate-exports".to_string(),
+      hint: Some("Remove one of them".to_string()),
+      fixes: vec![],
+      related: vec![LintRelatedInformation {
+        message: "'𝒳' is first exported here".to_string(),
+        range: range(source, "𝒳 ="),
+        filename: "/project/mod.ts".to_string(),
+      }],
+      stable_location: None,
+    };
+
+    let lsp_diagnostic = to_lsp_diagnostic(&diagnostic, source, &uri);
+    let related_information = lsp_diagnostic.related_information.unwrap();
+    assert_eq!(related_information.len(), 2);
+    assert_eq!(related_information[0].message, "'𝒳' is first exported here");
+    assert_eq!(related_information[0].location.uri, uri);
+    assert_eq!(
+      related_information[0].location.range,
+      lsp_types::Range::new(
+        lsp_types::Position::new(0, 13),
+        lsp_types::Position::new(0, 17)
+      )
+    );
+    assert_eq!(related_information[1].message, "Remove one of them");
+  }
+
+  #[test]
+  fn to_lsp_diagnostic_unused_ignore() {
+    let source = "// deno-lint-ignore no-explicit-any\nlet a = 1;";
+    let uri = Url::parse("file:///project/mod.ts").unwrap();
+    let diagnostic = LintDiagnostic {
+      range: range(source, "// deno-lint-ignore no-explicit-any"),
+      filename: "/project/mod.ts".to_string(),
+      message: "Ignore for code \"no-explicit-any\" was not used.".to_string(),
+      code: "ban-unused-ignore".to_string(),
+      hint: None,
+      fixes: vec![],
+      related: vec![],
+      stable_location: None,
+    };
+
+    let lsp_diagnostic = to_lsp_diagnostic(&diagnostic, source, &uri);
+    assert_eq!(lsp_diagnostic.severity, Some(DiagnosticSeverity::Hint));
+    assert_eq!(lsp_diagnostic.tags, Some(vec![DiagnosticTag::Unnecessary]));
+    assert_eq!(lsp_diagnostic.related_information, None);
+    assert!(code_actions(&lsp_diagnostic, &uri).is_empty());
+  }
+}
@@ -0,0 +1,239 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::context::Context;
+use crate::handler::{Handler, Traverse};
+use crate::rules::complexity::{is_branch, is_function};
+use dprint_swc_ecma_ast_view::{self as AstView, Spanned};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use swc_common::{BytePos, SourceFile};
+use swc_ecmascript::parser::token::TokenAndSpan;
+
+/// Code metrics of a single file, collected along with the diagnostics when
+/// enabled with `LinterBuilder::collect_metrics`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetrics {
+  /// Number of functions, including arrow functions, methods, constructors
+  /// and accessors.
+  pub functions: usize,
+  /// Average cyclomatic complexity of the functions, as computed by the
+  /// `complexity` rule, or `0` if there are no functions.
+  pub average_complexity: f64,
+  /// Deepest nesting of control flow statements (`if`, loops, `switch` and
+  /// `try`) within a function or at the top level. `else if` chains don't add
+  /// to the nesting.
+  pub max_nesting: usize,
+  /// Number of lines containing code, not counting blank lines and lines
+  /// with only comments.
+  pub lines_of_code: usize,
+  /// Number of `TODO`s in comments.
+  pub todo_count: usize,
+}
+
+pub(crate) fn collect_metrics(
+  program: AstView::Program,
+  context: &mut Context,
+  tokens: &[TokenAndSpan],
+  source_file: &SourceFile,
+) -> FileMetrics {
+  let mut handler = MetricsHandler {
+    functions: 0,
+    total_complexity: 0,
+    scores: vec![],
+    depths: vec![0],
+    max_nesting: 0,
+  };
+  handler.traverse(program, context);
+
+  let average_complexity = if handler.functions == 0 {
+    0.0
+  } else {
+    handler.total_complexity as f64 / handler.functions as f64
+  };
+
+  static TODO_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bTODO\b").unwrap());
+  let todo_count = context
+    .all_comments()
+    .map(|comment| TODO_RE.find_iter(&comment.text).count())
+    .sum();
+
+  FileMetrics {
+    functions: handler.functions,
+    average_complexity,
+    max_nesting: handler.max_nesting,
+    lines_of_code: lines_of_code(tokens, source_file),
+    todo_count,
+  }
+}
+
+/// Counts the lines on which at least one token starts, ends or spans over.
+fn lines_of_code(tokens: &[TokenAndSpan], source_file: &SourceFile) -> usize {
+  let mut has_code = vec![false; source_file.count_lines()];
+  for token in tokens {
+    let span = token.span;
+    let first = source_file.lookup_line(span.lo);
+    let last =
+      source_file.lookup_line(BytePos(span.hi.0.max(span.lo.0 + 1) - 1));
+    if let (Some(first), Some(last)) = (first, last) {
+      for line in &mut has_code[first..=last] {
+        *line = true;
+      }
+    }
+  }
+  has_code.into_iter().filter(|line| *line).count()
+}
+
+/// Returns `true` for the statements adding a level of nesting.
+fn is_nesting(node: AstView::Node) -> bool {
+  use AstView::Node;
+  match node {
+    // `else if` continues the enclosing `if` instead of nesting in it
+    Node::IfStmt(if_stmt) => !matches!(
+      if_stmt.parent,
+      Node::IfStmt(parent)
+        if matches!(parent.alt, Some(alt) if alt.span() == if_stmt.span())
+    ),
+    Node::ForStmt(_)
+    | Node::ForInStmt(_)
+    | Node::ForOfStmt(_)
+    | Node::WhileStmt(_)
+    | Node::DoWhileStmt(_)
+    | Node::SwitchStmt(_)
+    | Node::TryStmt(_) => true,
+    _ => false,
+  }
+}
+
+struct MetricsHandler {
+  functions: usize,
+  total_complexity: usize,
+  /// Complexity of the enclosing functions.
+  scores: Vec<usize>,
+  /// Nesting depth within the enclosing functions, the first one being the
+  /// top level.
+  depths: Vec<usize>,
+  max_nesting: usize,
+}
+
+impl Handler for MetricsHandler {
+  fn on_enter_node(&mut self, node: AstView::Node, _ctx: &mut Context) {
+    if is_function(node) {
+      self.functions += 1;
+      self.scores.push(1);
+      self.depths.push(0);
+    } else if is_branch(node) {
+      if let Some(score) = self.scores.last_mut() {
+        *score += 1;
+      }
+    }
+
+    if is_nesting(node) {
+      let depth = self.depths.last_mut().unwrap();
+      *depth += 1;
+      self.max_nesting = self.max_nesting.max(*depth);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: AstView::Node, _ctx: &mut Context) {
+    if is_function(node) {
+      self.total_complexity += self.scores.pop().unwrap();
+      self.depths.pop();
+    }
+    if is_nesting(node) {
+      *self.depths.last_mut().unwrap() -= 1;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+
+  fn metrics(source: &str) -> FileMetrics {
+    let mut linter = LinterBuilder::default().collect_metrics(true).build();
+    let report = linter
+      .lint_report("metrics_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint");
+    report.metrics.expect("Metrics should be collected")
+  }
+
+  #[test]
+  fn empty_file() {
+    assert_eq!(metrics(""), FileMetrics::default());
+  }
+
+  #[test]
+  fn functions_and_complexity() {
+    let source = r#"
+function a(x) {
+  if (x) {
+    return 1;
+  }
+  return x ?? 2;
+}
+
+const b = () => {};
+
+class C {
+  constructor() {}
+  get d() {
+    return this.x || this.y;
+  }
+}
+"#;
+    let metrics = metrics(source);
+    assert_eq!(metrics.functions, 4);
+    // 3 + 1 + 1 + 2
+    assert!((metrics.average_complexity - 1.75).abs() < f64::EPSILON);
+  }
+
+  #[test]
+  fn max_nesting() {
+    let source = r#"
+for (const a of b) {
+  if (a) {
+    while (c) {}
+  } else if (d) {
+  } else if (e) {
+    try {
+      switch (f) {}
+    } catch {}
+  }
+}
+
+function g() {
+  if (a) {
+    if (b) {}
+  }
+}
+"#;
+    assert_eq!(metrics(source).max_nesting, 4);
+  }
+
+  #[test]
+  fn lines_of_code_and_todos() {
+    let source = r#"// TODO(@user): remove
+/**
+ * Docs with a TODO and another TODO.
+ */
+
+const a = `multi
+line`;
+/* TODOS aren't counted */ const b = 1; // todo
+"#;
+    let metrics = metrics(source);
+    assert_eq!(metrics.lines_of_code, 3);
+    assert_eq!(metrics.todo_count, 3);
+  }
+
+  #[test]
+  fn metrics_are_not_collected_by_default() {
+    let mut linter = LinterBuilder::default().build();
+    let report = linter
+      .lint_report("metrics_test.ts".to_string(), "const a = 1;".to_string())
+      .expect("Failed to lint");
+    assert!(report.metrics.is_none());
+  }
+}
@@ -1,42 +1,102 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use crate::context::Context;
+use crate::ignore_directives::edit_distance;
 use dprint_swc_ecma_ast_view::Program as ProgramView;
 
+/// Expands to the documentation of the rule with the given code: the summary
+/// followed by the long-form docs from `docs/rules/<code>.md`.
+#[cfg(feature = "docs")]
+macro_rules! rule_docs {
+  ($summary:expr, $code:literal) => {
+    concat!(
+      $summary,
+      "\n\n",
+      include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/docs/rules/",
+        $code,
+        ".md"
+      ))
+    )
+  };
+}
+
+/// Expands to the summary of the rule only, as the long-form docs are not
+/// embedded without the `docs` feature.
+#[cfg(not(feature = "docs"))]
+macro_rules! rule_docs {
+  ($summary:expr, $code:literal) => {
+    $summary
+  };
+}
+
+pub mod a11y_alt_text;
+pub mod a11y_anchor_is_valid;
+pub mod a11y_aria_props;
 pub mod adjacent_overload_signatures;
+pub mod array_type;
 pub mod ban_ts_comment;
 pub mod ban_types;
 pub mod ban_untagged_ignore;
 pub mod ban_untagged_todo;
 pub mod camelcase;
+pub mod check_spelling;
+pub mod complexity;
+pub mod consistent_generic_constructors;
 pub mod constructor_super;
+pub mod default_case;
+pub mod default_case_last;
 pub mod default_param_last;
 pub mod eqeqeq;
+pub mod exhaustive_deps;
 pub mod explicit_function_return_type;
 pub mod explicit_module_boundary_types;
 pub mod for_direction;
 pub mod getter_return;
+pub mod jsx_boolean_value;
+pub mod jsx_curly_braces;
+pub mod max_export_count;
+pub mod max_statements;
+pub mod max_statements_per_line;
+pub mod method_signature_style;
+pub mod no_accessor_recursion;
 pub mod no_array_constructor;
+pub mod no_assign_module_variable_from_nested_async;
+pub mod no_async_array_callbacks;
 pub mod no_async_promise_executor;
 pub mod no_await_in_loop;
+pub mod no_bare_node_style_specifiers;
+pub mod no_barrel_file;
+pub mod no_callback_in_promise;
 pub mod no_case_declarations;
 pub mod no_class_assign;
 pub mod no_compare_neg_zero;
 pub mod no_cond_assign;
+pub mod no_confusing_non_null_assertion;
+pub mod no_console_error_swallowing;
 pub mod no_const_assign;
 pub mod no_constant_condition;
 pub mod no_control_regex;
 pub mod no_debugger;
 pub mod no_delete_var;
+pub mod no_deno_run;
+pub mod no_deno_run_shell_injection;
 pub mod no_deprecated_deno_api;
+pub mod no_deprecated_std_modules;
 pub mod no_dupe_args;
 pub mod no_dupe_class_members;
 pub mod no_dupe_else_if;
 pub mod no_dupe_keys;
 pub mod no_duplicate_case;
+pub mod no_duplicate_code;
+pub mod no_duplicate_exports;
+pub mod no_duplicate_type_constituents;
+pub mod no_else_return;
 pub mod no_empty;
 pub mod no_empty_character_class;
 pub mod no_empty_interface;
 pub mod no_empty_pattern;
+pub mod no_empty_test;
 pub mod no_eval;
 pub mod no_ex_assign;
 pub mod no_explicit_any;
@@ -44,50 +104,93 @@ pub mod no_extra_boolean_cast;
 pub mod no_extra_non_null_assertion;
 pub mod no_extra_semi;
 pub mod no_fallthrough;
+pub mod no_floating_promises;
 pub mod no_func_assign;
 pub mod no_global_assign;
+pub mod no_global_state_mutation_in_module_scope;
+pub mod no_hardcoded_credentials;
 pub mod no_import_assign;
+pub mod no_import_test_only_modules_in_prod;
 pub mod no_inferrable_types;
 pub mod no_inner_declarations;
+pub mod no_inner_html_template_injection;
+pub mod no_invalid_member_modifiers;
 pub mod no_invalid_regexp;
+pub mod no_invalid_void_type;
 pub mod no_irregular_whitespace;
+pub mod no_kv_key_collision;
+pub mod no_loop_var_capture;
 pub mod no_misused_new;
+pub mod no_misused_promises;
 pub mod no_mixed_spaces_and_tabs;
 pub mod no_namespace;
 pub mod no_new_symbol;
+pub mod no_non_literal_dynamic_import;
 pub mod no_non_null_asserted_optional_chain;
 pub mod no_non_null_assertion;
+pub mod no_npm_specifiers;
 pub mod no_obj_calls;
 pub mod no_octal;
+pub mod no_param_reassign;
+pub mod no_proto_pollution_sinks;
 pub mod no_prototype_builtins;
 pub mod no_redeclare;
+pub mod no_regex_backtracking_bomb;
 pub mod no_regex_spaces;
+pub mod no_return_assign;
 pub mod no_self_assign;
 pub mod no_setter_return;
 pub mod no_shadow_restricted_names;
+pub mod no_slow_types_missing_return_type;
+pub mod no_slow_types_missing_type;
 pub mod no_sparse_arrays;
+pub mod no_then;
 pub mod no_this_alias;
 pub mod no_this_before_super;
 pub mod no_throw_literal;
+pub mod no_top_level_side_effects;
+pub mod no_unchecked_permission_request;
 pub mod no_undef;
+pub mod no_unneeded_ternary;
 pub mod no_unreachable;
 pub mod no_unsafe_finally;
 pub mod no_unsafe_negation;
+pub mod no_unsanitized_html_sink;
+pub mod no_unstable_deno_api;
 pub mod no_unused_labels;
 pub mod no_unused_vars;
 pub mod no_var;
+pub mod no_weak_crypto;
 pub mod no_with;
+pub mod parameter_properties;
+pub mod prefer_array_find;
 pub mod prefer_as_const;
+pub mod prefer_at;
+pub mod prefer_await_to_callbacks;
+pub mod prefer_concise_arrow;
 pub mod prefer_const;
+pub mod prefer_fake_time;
+pub mod prefer_function_type;
+pub mod prefer_includes;
 pub mod prefer_namespace_keyword;
+pub mod prefer_optional_chain;
+pub mod prefer_private_field;
+pub mod prefer_signal_abort;
+pub mod prefer_string_starts_ends_with;
+pub mod prefer_structured_clone;
+pub mod require_assertion;
 pub mod require_await;
+pub mod require_error_cause;
+pub mod require_kv_atomic_commit;
 pub mod require_yield;
+pub mod rules_of_hooks;
 pub mod single_var_declarator;
 pub mod triple_slash_reference;
 pub mod use_isnan;
+pub mod valid_import_map_specifiers;
 pub mod valid_typeof;
 
-const DUMMY_NODE: () = ();
+pub(crate) const DUMMY_NODE: () = ();
 
 pub enum ProgramRef<'view> {
   Module(&'view swc_ecmascript::ast::Module),
@@ -132,49 +235,280 @@ pub trait LintRule {
   }
 
   /// Returns the documentation string for this rule, describing what this rule is for with several
-  /// examples.
+  /// examples. Only the first paragraph, a short summary, is returned unless the `docs` feature is
+  /// enabled.
   fn docs(&self) -> &'static str {
     ""
   }
+
+  /// Configures this rule with the given options, e.g. the ones specified for the rule in a config
+  /// file. Rules that don't take any options ignore them.
+  fn set_options(
+    &mut self,
+    _options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    Ok(())
+  }
+
+  /// Returns `true` if the rule provides fixes for some of its diagnostics.
+  fn fixable(&self) -> bool {
+    false
+  }
+
+  /// Returns `true` if the rule uses the scope analysis from `Context::scope`,
+  /// which is skipped when no enabled rule needs it.
+  fn needs_scope(&self) -> bool {
+    false
+  }
+
+  /// Returns `true` if the rule uses the control flow analysis from
+  /// `Context::control_flow`, which is skipped when no enabled rule needs it.
+  fn needs_cfg(&self) -> bool {
+    false
+  }
+
+  /// Returns `true` if the rule uses type information from the
+  /// `TypeProvider`, which is only queried when an enabled rule needs it.
+  fn needs_types(&self) -> bool {
+    false
+  }
+
+  /// Returns `true` if the rule inspects comments, e.g. through
+  /// `Context::all_comments`. Comments are only exposed to rules when an
+  /// enabled rule needs them.
+  fn needs_comments(&self) -> bool {
+    false
+  }
+
+  /// Returns `true` if the rule must see the whole program when linting a
+  /// range with `Linter::lint_in_range`, e.g. because it relates
+  /// declarations to their uses or compares items with each other. Other
+  /// rules only get the top level items overlapping the range through
+  /// `lint_program`, so rules overriding `lint_program_with_ast_view` must
+  /// return `true` as well.
+  fn needs_whole_program(&self) -> bool {
+    false
+  }
+
+  /// Returns the JSON Schema of the options accepted by `set_options`, which
+  /// editors use to validate and complete rule options in config files.
+  /// Rules that don't take any options return `None`.
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    None
+  }
+}
+
+/// Returns a JSON Schema for the options of all the configurable rules, an
+/// object mapping the code of each rule to the schema of its options.
+pub fn get_options_schema() -> serde_json::Value {
+  let properties: serde_json::Map<_, _> = get_all_rules()
+    .into_iter()
+    .filter_map(|rule| Some((rule.code().to_string(), rule.options_schema()?)))
+    .collect();
+  serde_json::json!({
+    "type": "object",
+    "properties": properties,
+  })
+}
+
+/// Returns a JSON Schema for the lint config, with the `files` to lint and the
+/// `rules` to run: their `tags`, the codes of the rules to `include` and
+/// `exclude`, and their `options`. The schema of `rules` is also available as
+/// `#/definitions/rules` for configs extending it.
+pub fn get_config_schema() -> serde_json::Value {
+  let all_rules = get_all_rules();
+  let codes: Vec<&str> = all_rules.iter().map(|rule| rule.code()).collect();
+  let mut tags: Vec<&str> = all_rules
+    .iter()
+    .flat_map(|rule| rule.tags().iter().copied())
+    .collect();
+  tags.sort_unstable();
+  tags.dedup();
+
+  let mut options = get_options_schema();
+  options["description"] = "Options of the configurable rules".into();
+  options["additionalProperties"] = false.into();
+
+  serde_json::json!({
+    "$schema": "http://json-schema.org/draft-07/schema#",
+    "type": "object",
+    "properties": {
+      "files": {
+        "type": "object",
+        "properties": {
+          "include": {
+            "description": "Globs of the files to lint",
+            "type": "array",
+            "items": { "type": "string" }
+          },
+          "exclude": {
+            "description": "Globs of the files not to lint",
+            "type": "array",
+            "items": { "type": "string" }
+          }
+        }
+      },
+      "rules": { "$ref": "#/definitions/rules" }
+    },
+    "definitions": {
+      "rules": {
+        "type": "object",
+        "properties": {
+          "tags": {
+            "description": "Tags of the rules to run",
+            "type": "array",
+            "items": { "enum": tags }
+          },
+          "include": {
+            "description": "Codes of rules to run in addition to the tagged ones",
+            "type": "array",
+            "items": { "enum": codes }
+          },
+          "exclude": {
+            "description": "Codes of rules not to run",
+            "type": "array",
+            "items": { "enum": codes }
+          },
+          "options": options
+        }
+      }
+    }
+  })
+}
+
+/// Returns the rule with the given code.
+pub fn find_rule(code: &str) -> Option<Box<dyn LintRule>> {
+  get_all_rules().into_iter().find(|rule| rule.code() == code)
+}
+
+/// Returns the rules matching every word of the query, best matches first.
+///
+/// A word matches a rule if it's part of its code, one of its tags or found in
+/// its docs, with matches in the code ranking highest. Words close to the code
+/// or to one of its parts, e.g. `debuger` for `no-debugger`, match too.
+pub fn search_rules(query: &str) -> Vec<Box<dyn LintRule>> {
+  let query = query.to_lowercase();
+  let words: Vec<&str> = query.split_whitespace().collect();
+  if words.is_empty() {
+    return vec![];
+  }
+
+  let mut matches: Vec<(usize, Box<dyn LintRule>)> = get_all_rules()
+    .into_iter()
+    .filter_map(|rule| {
+      let docs = rule.docs().to_lowercase();
+      let mut score = 0;
+      for word in &words {
+        let word_score = search_score(word, rule.code(), rule.tags(), &docs);
+        if word_score == 0 {
+          return None;
+        }
+        score += word_score;
+      }
+      Some((score, rule))
+    })
+    .collect();
+  matches.sort_by(|(a_score, a), (b_score, b)| {
+    b_score.cmp(a_score).then_with(|| a.code().cmp(b.code()))
+  });
+  matches.into_iter().map(|(_, rule)| rule).collect()
+}
+
+/// Scores how well a lowercase word of a search query matches a rule, or
+/// returns 0 if it doesn't.
+fn search_score(word: &str, code: &str, tags: &[&str], docs: &str) -> usize {
+  // Short words are close to too many parts of codes, like `no`
+  let is_close = |candidate: &str| {
+    let max_distance = (candidate.chars().count() / 4).max(1);
+    word.chars().count() > 3 && edit_distance(word, candidate) <= max_distance
+  };
+
+  if word == code {
+    100
+  } else if code.contains(word) {
+    50
+  } else if is_close(code) {
+    40
+  } else if tags.contains(&word) {
+    30
+  } else if code.split('-').any(is_close) {
+    20
+  } else if docs.contains(word) {
+    10
+  } else {
+    0
+  }
 }
 
 pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
   vec![
+    a11y_alt_text::A11yAltText::new(),
+    a11y_anchor_is_valid::A11yAnchorIsValid::new(),
+    a11y_aria_props::A11yAriaProps::new(),
     adjacent_overload_signatures::AdjacentOverloadSignatures::new(),
+    array_type::ArrayType::new(),
     ban_ts_comment::BanTsComment::new(),
     ban_types::BanTypes::new(),
     ban_untagged_ignore::BanUntaggedIgnore::new(),
     ban_untagged_todo::BanUntaggedTodo::new(),
     camelcase::Camelcase::new(),
+    check_spelling::CheckSpelling::new(),
+    complexity::Complexity::new(),
+    consistent_generic_constructors::ConsistentGenericConstructors::new(),
     constructor_super::ConstructorSuper::new(),
+    default_case::DefaultCase::new(),
+    default_case_last::DefaultCaseLast::new(),
     default_param_last::DefaultParamLast::new(),
     eqeqeq::Eqeqeq::new(),
+    exhaustive_deps::ExhaustiveDeps::new(),
     explicit_function_return_type::ExplicitFunctionReturnType::new(),
     explicit_module_boundary_types::ExplicitModuleBoundaryTypes::new(),
     for_direction::ForDirection::new(),
     getter_return::GetterReturn::new(),
+    jsx_boolean_value::JsxBooleanValue::new(),
+    jsx_curly_braces::JsxCurlyBraces::new(),
+    max_export_count::MaxExportCount::new(),
+    max_statements::MaxStatements::new(),
+    max_statements_per_line::MaxStatementsPerLine::new(),
+    method_signature_style::MethodSignatureStyle::new(),
+    no_accessor_recursion::NoAccessorRecursion::new(),
     no_array_constructor::NoArrayConstructor::new(),
+    no_assign_module_variable_from_nested_async::NoAssignModuleVariableFromNestedAsync::new(),
+    no_async_array_callbacks::NoAsyncArrayCallbacks::new(),
     no_async_promise_executor::NoAsyncPromiseExecutor::new(),
     no_await_in_loop::NoAwaitInLoop::new(),
+    no_bare_node_style_specifiers::NoBareNodeStyleSpecifiers::new(),
+    no_barrel_file::NoBarrelFile::new(),
+    no_callback_in_promise::NoCallbackInPromise::new(),
     no_case_declarations::NoCaseDeclarations::new(),
     no_class_assign::NoClassAssign::new(),
     no_compare_neg_zero::NoCompareNegZero::new(),
     no_cond_assign::NoCondAssign::new(),
+    no_confusing_non_null_assertion::NoConfusingNonNullAssertion::new(),
+    no_console_error_swallowing::NoConsoleErrorSwallowing::new(),
     no_const_assign::NoConstAssign::new(),
     no_constant_condition::NoConstantCondition::new(),
     no_control_regex::NoControlRegex::new(),
     no_debugger::NoDebugger::new(),
     no_delete_var::NoDeleteVar::new(),
+    no_deno_run::NoDenoRun::new(),
+    no_deno_run_shell_injection::NoDenoRunShellInjection::new(),
     no_deprecated_deno_api::NoDeprecatedDenoApi::new(),
+    no_deprecated_std_modules::NoDeprecatedStdModules::new(),
     no_dupe_args::NoDupeArgs::new(),
     no_dupe_class_members::NoDupeClassMembers::new(),
     no_dupe_else_if::NoDupeElseIf::new(),
     no_dupe_keys::NoDupeKeys::new(),
     no_duplicate_case::NoDuplicateCase::new(),
+    no_duplicate_code::NoDuplicateCode::new(),
+    no_duplicate_exports::NoDuplicateExports::new(),
+    no_duplicate_type_constituents::NoDuplicateTypeConstituents::new(),
+    no_else_return::NoElseReturn::new(),
     no_empty::NoEmpty::new(),
     no_empty_character_class::NoEmptyCharacterClass::new(),
     no_empty_interface::NoEmptyInterface::new(),
     no_empty_pattern::NoEmptyPattern::new(),
+    no_empty_test::NoEmptyTest::new(),
     no_eval::NoEval::new(),
     no_ex_assign::NoExAssign::new(),
     no_explicit_any::NoExplicitAny::new(),
@@ -182,47 +516,90 @@ pub fn get_all_rules() -> Vec<Box<dyn LintRule>> {
     no_extra_non_null_assertion::NoExtraNonNullAssertion::new(),
     no_extra_semi::NoExtraSemi::new(),
     no_fallthrough::NoFallthrough::new(),
+    no_floating_promises::NoFloatingPromises::new(),
     no_func_assign::NoFuncAssign::new(),
     no_global_assign::NoGlobalAssign::new(),
+    no_global_state_mutation_in_module_scope::NoGlobalStateMutationInModuleScope::new(),
+    no_hardcoded_credentials::NoHardcodedCredentials::new(),
     no_import_assign::NoImportAssign::new(),
+    no_import_test_only_modules_in_prod::NoImportTestOnlyModulesInProd::new(),
     no_inferrable_types::NoInferrableTypes::new(),
     no_inner_declarations::NoInnerDeclarations::new(),
+    no_inner_html_template_injection::NoInnerHtmlTemplateInjection::new(),
+    no_invalid_member_modifiers::NoInvalidMemberModifiers::new(),
     no_invalid_regexp::NoInvalidRegexp::new(),
+    no_invalid_void_type::NoInvalidVoidType::new(),
     no_irregular_whitespace::NoIrregularWhitespace::new(),
+    no_kv_key_collision::NoKvKeyCollision::new(),
+    no_loop_var_capture::NoLoopVarCapture::new(),
     no_misused_new::NoMisusedNew::new(),
+    no_misused_promises::NoMisusedPromises::new(),
     no_mixed_spaces_and_tabs::NoMixedSpacesAndTabs::new(),
     no_namespace::NoNamespace::new(),
     no_new_symbol::NoNewSymbol::new(),
+    no_non_literal_dynamic_import::NoNonLiteralDynamicImport::new(),
     no_non_null_asserted_optional_chain::NoNonNullAssertedOptionalChain::new(),
     no_non_null_assertion::NoNonNullAssertion::new(),
+    no_npm_specifiers::NoNpmSpecifiers::new(),
     no_obj_calls::NoObjCalls::new(),
     no_octal::NoOctal::new(),
+    no_param_reassign::NoParamReassign::new(),
+    no_proto_pollution_sinks::NoProtoPollutionSinks::new(),
     no_prototype_builtins::NoPrototypeBuiltins::new(),
     no_redeclare::NoRedeclare::new(),
+    no_regex_backtracking_bomb::NoRegexBacktrackingBomb::new(),
     no_regex_spaces::NoRegexSpaces::new(),
+    no_return_assign::NoReturnAssign::new(),
     no_self_assign::NoSelfAssign::new(),
     no_setter_return::NoSetterReturn::new(),
     no_shadow_restricted_names::NoShadowRestrictedNames::new(),
+    no_slow_types_missing_return_type::NoSlowTypesMissingReturnType::new(),
+    no_slow_types_missing_type::NoSlowTypesMissingType::new(),
     no_sparse_arrays::NoSparseArrays::new(),
+    no_then::NoThen::new(),
     no_this_alias::NoThisAlias::new(),
     no_this_before_super::NoThisBeforeSuper::new(),
     no_throw_literal::NoThrowLiteral::new(),
+    no_top_level_side_effects::NoTopLevelSideEffects::new(),
+    no_unchecked_permission_request::NoUncheckedPermissionRequest::new(),
     no_undef::NoUndef::new(),
+    no_unneeded_ternary::NoUnneededTernary::new(),
     no_unreachable::NoUnreachable::new(),
     no_unsafe_finally::NoUnsafeFinally::new(),
     no_unsafe_negation::NoUnsafeNegation::new(),
+    no_unsanitized_html_sink::NoUnsanitizedHtmlSink::new(),
+    no_unstable_deno_api::NoUnstableDenoApi::new(),
     no_unused_labels::NoUnusedLabels::new(),
     no_unused_vars::NoUnusedVars::new(),
     no_var::NoVar::new(),
+    no_weak_crypto::NoWeakCrypto::new(),
     no_with::NoWith::new(),
+    parameter_properties::ParameterProperties::new(),
+    prefer_array_find::PreferArrayFind::new(),
     prefer_as_const::PreferAsConst::new(),
+    prefer_at::PreferAt::new(),
+    prefer_await_to_callbacks::PreferAwaitToCallbacks::new(),
+    prefer_concise_arrow::PreferConciseArrow::new(),
     prefer_const::PreferConst::new(),
+    prefer_fake_time::PreferFakeTime::new(),
+    prefer_function_type::PreferFunctionType::new(),
+    prefer_includes::PreferIncludes::new(),
     prefer_namespace_keyword::PreferNamespaceKeyword::new(),
+    prefer_optional_chain::PreferOptionalChain::new(),
+    prefer_private_field::PreferPrivateField::new(),
+    prefer_signal_abort::PreferSignalAbort::new(),
+    prefer_string_starts_ends_with::PreferStringStartsEndsWith::new(),
+    prefer_structured_clone::PreferStructuredClone::new(),
+    require_assertion::RequireAssertion::new(),
     require_await::RequireAwait::new(),
+    require_error_cause::RequireErrorCause::new(),
+    require_kv_atomic_commit::RequireKvAtomicCommit::new(),
     require_yield::RequireYield::new(),
+    rules_of_hooks::RulesOfHooks::new(),
     single_var_declarator::SingleVarDeclarator::new(),
     triple_slash_reference::TripleSlashReference::new(),
     use_isnan::UseIsNaN::new(),
+    valid_import_map_specifiers::ValidImportMapSpecifiers::new(),
     valid_typeof::ValidTypeof::new(),
   ]
 }
@@ -257,4 +634,71 @@ mod tests {
       assert_eq!(sorted.code(), unsorted.code());
     }
   }
+
+  #[test]
+  fn options_schemas_match_options() {
+    // Every value the schemas document must be accepted by the rules
+    for mut rule in get_all_rules() {
+      let schema = match rule.options_schema() {
+        Some(schema) => schema,
+        None => continue,
+      };
+      assert_eq!(schema["type"], "object", "{}", rule.code());
+      let properties = schema["properties"].as_object().unwrap();
+
+      let defaults: serde_json::Map<_, _> = properties
+        .iter()
+        .filter_map(|(name, property)| {
+          Some((name.clone(), property.get("default")?.clone()))
+        })
+        .collect();
+      rule
+        .set_options(defaults.into())
+        .unwrap_or_else(|err| panic!("{}: {}", rule.code(), err));
+
+      for (name, property) in properties {
+        for value in property["enum"].as_array().into_iter().flatten() {
+          rule
+            .set_options(serde_json::json!({ name: value }))
+            .unwrap_or_else(|err| panic!("{}: {}", rule.code(), err));
+        }
+      }
+    }
+
+    let schema = get_options_schema();
+    assert!(schema["properties"]["complexity"].is_object());
+    assert!(schema["properties"]["no-unused-vars"].is_null());
+  }
+
+  #[test]
+  fn find_rule_test() {
+    assert_eq!(find_rule("no-debugger").unwrap().code(), "no-debugger");
+    assert!(find_rule("no-debuger").is_none());
+  }
+
+  #[test]
+  fn search_rules_test() {
+    let codes = |query| -> Vec<&'static str> {
+      search_rules(query).iter().map(|rule| rule.code()).collect()
+    };
+    assert_eq!(codes("no-debugger")[0], "no-debugger");
+    assert_eq!(codes("no-debuger")[0], "no-debugger");
+    assert_eq!(codes("Debugger")[0], "no-debugger");
+    assert!(codes("jsr").contains(&"no-slow-types-missing-type"));
+    assert!(codes("jsx boolean").starts_with(&["jsx-boolean-value"]));
+    assert!(codes("").is_empty());
+    assert!(codes("xyzzy").is_empty());
+  }
+
+  #[test]
+  fn config_schema() {
+    let schema = get_config_schema();
+    let rules = &schema["definitions"]["rules"]["properties"];
+    let tags = rules["tags"]["items"]["enum"].as_array().unwrap();
+    assert!(tags.contains(&"recommended".into()));
+    let codes = rules["include"]["items"]["enum"].as_array().unwrap();
+    assert_eq!(codes.len(), get_all_rules().len());
+    assert!(codes.contains(&"no-explicit-any".into()));
+    assert!(rules["options"]["properties"]["complexity"].is_object());
+  }
 }
@@ -0,0 +1,366 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  has_jsx_attr_value, has_jsx_spread, jsx_attr, jsx_attr_str, jsx_element_name,
+};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{JSXElement, JSXElementChild, JSXOpeningElement};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct A11yAltText {
+  options: A11yAltTextOptions,
+}
+
+/// Components of design systems that render the checked elements, e.g.
+/// `{ "img": ["Image", "Avatar"] }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct A11yAltTextOptions {
+  /// Components rendering an `<img>`.
+  pub img: Vec<String>,
+  /// Components rendering an `<object>`.
+  pub object: Vec<String>,
+  /// Components rendering an `<area>`.
+  pub area: Vec<String>,
+  /// Components rendering an `<input type="image">`.
+  pub input_image: Vec<String>,
+}
+
+const CODE: &str = "a11y-alt-text";
+
+#[derive(Display)]
+enum A11yAltTextMessage {
+  #[display(fmt = "`<{}>` is missing an `alt` attribute", _0)]
+  MissingAlt(String),
+  #[display(fmt = "`<{}>` is missing alternative text", _0)]
+  MissingText(String),
+}
+
+#[derive(Display)]
+enum A11yAltTextHint {
+  #[display(
+    fmt = "Add an `alt` attribute describing the image, or `alt=\"\"` if it's decorative"
+  )]
+  Img,
+  #[display(
+    fmt = "Add a `title` or `aria-label` attribute, or text content, describing the object"
+  )]
+  Object,
+  #[display(
+    fmt = "Add an `alt` or `aria-label` attribute describing the link"
+  )]
+  Area,
+  #[display(
+    fmt = "Add an `alt` or `aria-label` attribute describing the button"
+  )]
+  InputImage,
+}
+
+impl LintRule for A11yAltText {
+  fn new() -> Box<Self> {
+    Box::new(A11yAltText {
+      options: A11yAltTextOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = A11yAltTextVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let components = |element: &str| {
+      json!({
+        "description": format!("Components rendering an `{}`.", element),
+        "type": "array",
+        "items": { "type": "string" },
+        "default": []
+      })
+    };
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "img": components("<img>"),
+        "object": components("<object>"),
+        "area": components("<area>"),
+        "inputImage": components("<input type=\"image\">")
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires alternative text for images, image maps, image buttons and objects in JSX",
+      "a11y-alt-text"
+    )
+  }
+}
+
+#[derive(Clone, Copy)]
+enum ElementKind {
+  Img,
+  Object,
+  Area,
+  InputImage,
+}
+
+/// Returns `true` if the element has the attribute, set to a non-empty value.
+fn has_attr(opening: &JSXOpeningElement, name: &str) -> bool {
+  match jsx_attr(opening, name) {
+    Some(attr) => has_jsx_attr_value(attr),
+    None => false,
+  }
+}
+
+/// Returns `true` if the element is labelled by `aria-label` or
+/// `aria-labelledby`.
+fn has_aria_label(opening: &JSXOpeningElement) -> bool {
+  has_attr(opening, "aria-label") || has_attr(opening, "aria-labelledby")
+}
+
+/// Returns `true` if the element has text content, or children that may
+/// render some.
+fn has_content(element: &JSXElement) -> bool {
+  element.children.iter().any(|child| match child {
+    JSXElementChild::JSXText(text) => !text.value.trim().is_empty(),
+    _ => true,
+  })
+}
+
+struct A11yAltTextVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c A11yAltTextOptions,
+}
+
+impl<'c, 'view> A11yAltTextVisitor<'c, 'view> {
+  fn element_kind(
+    &self,
+    opening: &JSXOpeningElement,
+    name: &str,
+  ) -> Option<ElementKind> {
+    let is = |element: &str, components: &[String]| {
+      name == element || components.iter().any(|component| component == name)
+    };
+    if is("img", &self.options.img) {
+      Some(ElementKind::Img)
+    } else if is("object", &self.options.object) {
+      Some(ElementKind::Object)
+    } else if is("area", &self.options.area) {
+      Some(ElementKind::Area)
+    } else if self
+      .options
+      .input_image
+      .iter()
+      .any(|component| component == name)
+      || (name == "input"
+        && matches!(
+          jsx_attr(opening, "type").and_then(jsx_attr_str),
+          Some(value) if value.eq_ignore_ascii_case("image")
+        ))
+    {
+      Some(ElementKind::InputImage)
+    } else {
+      None
+    }
+  }
+}
+
+impl<'c, 'view> Visit for A11yAltTextVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_jsx_element(&mut self, element: &JSXElement, _: &dyn Node) {
+    let opening = &element.opening;
+    let name = jsx_element_name(&opening.name);
+    // Spread attributes may provide the alternative text
+    if let (Some(kind), false) =
+      (self.element_kind(opening, &name), has_jsx_spread(opening))
+    {
+      let report = match kind {
+        ElementKind::Img => {
+          // `alt=""` marks decorative images, but `alt={undefined}` is
+          // missing
+          let has_alt = match jsx_attr(opening, "alt") {
+            Some(attr) => {
+              jsx_attr_str(attr).is_some() || has_jsx_attr_value(attr)
+            }
+            None => false,
+          };
+          let is_presentation = matches!(
+            jsx_attr(opening, "role").and_then(jsx_attr_str),
+            Some("presentation") | Some("none")
+          );
+          if has_alt || is_presentation || has_aria_label(opening) {
+            None
+          } else {
+            Some((A11yAltTextMessage::MissingAlt(name), A11yAltTextHint::Img))
+          }
+        }
+        ElementKind::Object => {
+          if has_attr(opening, "title")
+            || has_aria_label(opening)
+            || has_content(element)
+          {
+            None
+          } else {
+            Some((
+              A11yAltTextMessage::MissingText(name),
+              A11yAltTextHint::Object,
+            ))
+          }
+        }
+        ElementKind::Area | ElementKind::InputImage => {
+          if has_attr(opening, "alt") || has_aria_label(opening) {
+            None
+          } else if let ElementKind::Area = kind {
+            Some((A11yAltTextMessage::MissingText(name), A11yAltTextHint::Area))
+          } else {
+            Some((
+              A11yAltTextMessage::MissingText(name),
+              A11yAltTextHint::InputImage,
+            ))
+          }
+        }
+      };
+      if let Some((message, hint)) = report {
+        self.context.add_diagnostic_with_hint(
+          opening.span,
+          CODE,
+          message,
+          hint,
+        );
+      }
+    }
+    element.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn a11y_alt_text_valid() {
+    assert_lint_ok! {
+      A11yAltText,
+      { src: "<img src={src} alt=\"A cat sleeping\" />", filename: "foo.tsx" },
+      { src: "<img src=\"divider.png\" alt=\"\" />", filename: "foo.tsx" },
+      { src: "<img src={src} alt={description} />", filename: "foo.tsx" },
+      { src: "<img src={src} role=\"presentation\" />", filename: "foo.tsx" },
+      { src: "<img src={src} aria-label=\"Logo\" />", filename: "foo.tsx" },
+      { src: "<img {...props} />", filename: "foo.tsx" },
+      { src: "<object data=\"movie.mp4\" title=\"A movie\" />", filename: "foo.tsx" },
+      { src: "<object data=\"movie.mp4\">A movie</object>", filename: "foo.tsx" },
+      { src: "<area href=\"/about\" alt=\"About\" />", filename: "foo.tsx" },
+      { src: "<input type=\"image\" src=\"submit.png\" alt=\"Submit\" />", filename: "foo.tsx" },
+      { src: "<input type=\"text\" />", filename: "foo.tsx" },
+      { src: "<Image src={src} />", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn a11y_alt_text_invalid() {
+    assert_lint_err! {
+      A11yAltText,
+      "<img src={src} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 0,
+            message: variant!(A11yAltTextMessage, MissingAlt, "img"),
+            hint: A11yAltTextHint::Img,
+          }
+        ],
+      },
+      "<img src={src} alt={undefined} aria-label=\"\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 0,
+            message: variant!(A11yAltTextMessage, MissingAlt, "img"),
+            hint: A11yAltTextHint::Img,
+          }
+        ],
+      },
+      "<object data=\"movie.mp4\">  </object>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 0,
+            message: variant!(A11yAltTextMessage, MissingText, "object"),
+            hint: A11yAltTextHint::Object,
+          }
+        ],
+      },
+      "<map><area href=\"/about\" alt=\"\" /></map>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAltTextMessage, MissingText, "area"),
+            hint: A11yAltTextHint::Area,
+          }
+        ],
+      },
+      "<input type=\"image\" src=\"submit.png\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 0,
+            message: variant!(A11yAltTextMessage, MissingText, "input"),
+            hint: A11yAltTextHint::InputImage,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      A11yAltText,
+      options: json!({ "img": ["Image", "UI.Avatar"], "inputImage": ["ImageButton"] }),
+      "<div><Image src={src} /><UI.Avatar user={user} /><ImageButton src={src} /></div>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAltTextMessage, MissingAlt, "Image"),
+            hint: A11yAltTextHint::Img,
+          },
+          {
+            col: 24,
+            message: variant!(A11yAltTextMessage, MissingAlt, "UI.Avatar"),
+            hint: A11yAltTextHint::Img,
+          },
+          {
+            col: 49,
+            message: variant!(A11yAltTextMessage, MissingText, "ImageButton"),
+            hint: A11yAltTextHint::InputImage,
+          }
+        ],
+      },
+    };
+  }
+}
@@ -0,0 +1,346 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  has_jsx_spread, jsx_attr, jsx_attr_str, jsx_element_name,
+};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  Expr, JSXAttr, JSXAttrValue, JSXExpr, JSXOpeningElement, Lit,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct A11yAnchorIsValid {
+  options: A11yAnchorIsValidOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct A11yAnchorIsValidOptions {
+  /// Components rendering an `<a>`, e.g. `Link`.
+  pub components: Vec<String>,
+  /// Attributes other than `href` setting the URL of the link, e.g. `to`.
+  pub special_link: Vec<String>,
+  /// The problems to report.
+  pub aspects: Vec<Aspect>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Aspect {
+  /// Anchors without `href`.
+  NoHref,
+  /// Anchors whose `href` doesn't navigate anywhere, like `#`.
+  InvalidHref,
+  /// Anchors with a click handler and no valid `href`, which are buttons.
+  PreferButton,
+}
+
+impl Default for A11yAnchorIsValidOptions {
+  fn default() -> Self {
+    Self {
+      components: Vec::new(),
+      special_link: Vec::new(),
+      aspects: vec![Aspect::NoHref, Aspect::InvalidHref, Aspect::PreferButton],
+    }
+  }
+}
+
+const CODE: &str = "a11y-anchor-is-valid";
+
+#[derive(Display)]
+enum A11yAnchorIsValidMessage {
+  #[display(fmt = "`<{}>` is missing an `href` attribute", _0)]
+  NoHref(String),
+  #[display(fmt = "`<{}>` has an invalid `href` attribute", _0)]
+  InvalidHref(String),
+  #[display(fmt = "`<{}>` is used as a button", _0)]
+  PreferButton(String),
+}
+
+#[derive(Display)]
+enum A11yAnchorIsValidHint {
+  #[display(
+    fmt = "Add an `href` attribute with a valid URL, links without one can't be reached with the keyboard"
+  )]
+  NoHref,
+  #[display(
+    fmt = "Set `href` to a valid URL, `#` and `javascript:` URLs don't navigate anywhere"
+  )]
+  InvalidHref,
+  #[display(
+    fmt = "Use a `<button>` instead, which can be reached and activated with the keyboard"
+  )]
+  PreferButton,
+}
+
+impl LintRule for A11yAnchorIsValid {
+  fn new() -> Box<Self> {
+    Box::new(A11yAnchorIsValid {
+      options: A11yAnchorIsValidOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = A11yAnchorIsValidVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "components": {
+          "description": "Components rendering an `<a>`, e.g. `Link`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        },
+        "specialLink": {
+          "description": "Attributes other than `href` setting the URL of the link, e.g. `to`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        },
+        "aspects": {
+          "description": "The problems to report.",
+          "type": "array",
+          "items": { "enum": ["noHref", "invalidHref", "preferButton"] },
+          "default": ["noHref", "invalidHref", "preferButton"]
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires anchors in JSX to be links with a valid `href`",
+      "a11y-anchor-is-valid"
+    )
+  }
+}
+
+/// Returns `true` for `href` values that don't navigate anywhere: empty
+/// strings, `#`, `javascript:` URLs, `null` and `undefined`.
+fn is_invalid_href(attr: &JSXAttr) -> bool {
+  if let Some(value) = jsx_attr_str(attr) {
+    let value = value.trim();
+    return value.is_empty()
+      || value == "#"
+      || value.to_ascii_lowercase().starts_with("javascript:");
+  }
+  match &attr.value {
+    Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+      JSXExpr::Expr(expr) => match &**expr {
+        Expr::Lit(Lit::Null(_)) => true,
+        Expr::Ident(ident) => ident.sym == *"undefined",
+        _ => false,
+      },
+      JSXExpr::JSXEmptyExpr(_) => true,
+    },
+    Some(_) => false,
+    None => true,
+  }
+}
+
+struct A11yAnchorIsValidVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c A11yAnchorIsValidOptions,
+}
+
+impl<'c, 'view> A11yAnchorIsValidVisitor<'c, 'view> {
+  fn check(&mut self, opening: &JSXOpeningElement) {
+    let name = jsx_element_name(&opening.name);
+    if name != "a" && !self.options.components.contains(&name) {
+      return;
+    }
+
+    let hrefs: Vec<&JSXAttr> = std::iter::once("href")
+      .chain(self.options.special_link.iter().map(|name| name.as_str()))
+      .filter_map(|name| jsx_attr(opening, name))
+      .collect();
+    let has_click_handler = jsx_attr(opening, "onClick").is_some();
+    let aspects = &self.options.aspects;
+
+    let report = if hrefs.is_empty() {
+      // Spread attributes may set `href`
+      if has_jsx_spread(opening) {
+        None
+      } else if has_click_handler && aspects.contains(&Aspect::PreferButton) {
+        Some((
+          A11yAnchorIsValidMessage::PreferButton(name),
+          A11yAnchorIsValidHint::PreferButton,
+        ))
+      } else if aspects.contains(&Aspect::NoHref) {
+        Some((
+          A11yAnchorIsValidMessage::NoHref(name),
+          A11yAnchorIsValidHint::NoHref,
+        ))
+      } else {
+        None
+      }
+    } else if hrefs.iter().all(|href| is_invalid_href(href)) {
+      if has_click_handler && aspects.contains(&Aspect::PreferButton) {
+        Some((
+          A11yAnchorIsValidMessage::PreferButton(name),
+          A11yAnchorIsValidHint::PreferButton,
+        ))
+      } else if aspects.contains(&Aspect::InvalidHref) {
+        Some((
+          A11yAnchorIsValidMessage::InvalidHref(name),
+          A11yAnchorIsValidHint::InvalidHref,
+        ))
+      } else {
+        None
+      }
+    } else {
+      None
+    };
+
+    if let Some((message, hint)) = report {
+      self
+        .context
+        .add_diagnostic_with_hint(opening.span, CODE, message, hint);
+    }
+  }
+}
+
+impl<'c, 'view> Visit for A11yAnchorIsValidVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_jsx_opening_element(
+    &mut self,
+    opening: &JSXOpeningElement,
+    _: &dyn Node,
+  ) {
+    self.check(opening);
+    opening.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn a11y_anchor_is_valid_valid() {
+    assert_lint_ok! {
+      A11yAnchorIsValid,
+      { src: "<a href=\"/about\">About</a>", filename: "foo.tsx" },
+      { src: "<a href={url} onClick={track}>About</a>", filename: "foo.tsx" },
+      { src: "<a href=\"#section\">Section</a>", filename: "foo.tsx" },
+      { src: "<a {...props}>About</a>", filename: "foo.tsx" },
+      { src: "<button onClick={open}>Open</button>", filename: "foo.tsx" },
+      { src: "<Link to=\"#\">About</Link>", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      A11yAnchorIsValid,
+      options: json!({ "components": ["Link"], "specialLink": ["to"] }),
+      { src: "<Link to=\"/about\">About</Link>", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      A11yAnchorIsValid,
+      options: json!({ "aspects": ["invalidHref"] }),
+      { src: "<a>About</a>", filename: "foo.tsx" },
+      { src: "<a onClick={open}>About</a>", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn a11y_anchor_is_valid_invalid() {
+    assert_lint_err! {
+      A11yAnchorIsValid,
+      "<a>About</a>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 0,
+            message: variant!(A11yAnchorIsValidMessage, NoHref, "a"),
+            hint: A11yAnchorIsValidHint::NoHref,
+          }
+        ],
+      },
+      "<p><a href=\"#\">Top</a><a href=\"javascript:void(0)\">Back</a><a href={undefined}>Home</a></p>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 3,
+            message: variant!(A11yAnchorIsValidMessage, InvalidHref, "a"),
+            hint: A11yAnchorIsValidHint::InvalidHref,
+          },
+          {
+            col: 22,
+            message: variant!(A11yAnchorIsValidMessage, InvalidHref, "a"),
+            hint: A11yAnchorIsValidHint::InvalidHref,
+          },
+          {
+            col: 59,
+            message: variant!(A11yAnchorIsValidMessage, InvalidHref, "a"),
+            hint: A11yAnchorIsValidHint::InvalidHref,
+          }
+        ],
+      },
+      "<p><a onClick={open}>Open</a><a href=\"#\" onClick={open}>Open</a></p>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 3,
+            message: variant!(A11yAnchorIsValidMessage, PreferButton, "a"),
+            hint: A11yAnchorIsValidHint::PreferButton,
+          },
+          {
+            col: 29,
+            message: variant!(A11yAnchorIsValidMessage, PreferButton, "a"),
+            hint: A11yAnchorIsValidHint::PreferButton,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      A11yAnchorIsValid,
+      options: json!({ "components": ["Link"], "specialLink": ["to"], "aspects": ["noHref", "invalidHref"] }),
+      "<p><Link>About</Link><Link to=\"#\" onClick={open}>Open</Link></p>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 3,
+            message: variant!(A11yAnchorIsValidMessage, NoHref, "Link"),
+            hint: A11yAnchorIsValidHint::NoHref,
+          },
+          {
+            col: 21,
+            message: variant!(A11yAnchorIsValidMessage, InvalidHref, "Link"),
+            hint: A11yAnchorIsValidHint::InvalidHref,
+          }
+        ],
+      },
+    };
+  }
+}
@@ -0,0 +1,178 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::aria::{
+  closest, is_aria_property, is_aria_role, ARIA_PROPERTIES, ARIA_ROLES,
+};
+use crate::swc_util::{jsx_attr_name, jsx_attr_str};
+use derive_more::Display;
+use swc_ecmascript::ast::JSXAttr;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct A11yAriaProps;
+
+const CODE: &str = "a11y-aria-props";
+
+#[derive(Display)]
+enum A11yAriaPropsMessage {
+  #[display(fmt = "`{}` is not a valid ARIA attribute", _0)]
+  InvalidProperty(String),
+  #[display(fmt = "`{}` is not a valid ARIA role", _0)]
+  InvalidRole(String),
+}
+
+#[derive(Display)]
+enum A11yAriaPropsHint {
+  #[display(fmt = "Did you mean `{}`?", _0)]
+  Suggestion(String),
+  #[display(fmt = "Remove it, or use one of the attributes of the ARIA spec")]
+  UseProperty,
+  #[display(fmt = "Use one of the non-abstract roles of the ARIA spec")]
+  UseRole,
+}
+
+impl LintRule for A11yAriaProps {
+  fn new() -> Box<Self> {
+    Box::new(A11yAriaProps)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = A11yAriaPropsVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows unknown ARIA attributes and roles in JSX",
+      "a11y-aria-props"
+    )
+  }
+}
+
+struct A11yAriaPropsVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> Visit for A11yAriaPropsVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_jsx_attr(&mut self, jsx_attr: &JSXAttr, _: &dyn Node) {
+    let name = jsx_attr_name(&jsx_attr.name);
+    if name.starts_with("aria-") && !is_aria_property(&name) {
+      let hint = match closest(&name, ARIA_PROPERTIES) {
+        Some(suggestion) => A11yAriaPropsHint::Suggestion(suggestion.into()),
+        None => A11yAriaPropsHint::UseProperty,
+      };
+      self.context.add_diagnostic_with_hint(
+        jsx_attr.span,
+        CODE,
+        A11yAriaPropsMessage::InvalidProperty(name),
+        hint,
+      );
+    } else if name == "role" {
+      // Roles set with expressions can't be checked
+      if let Some(value) = jsx_attr_str(jsx_attr) {
+        // Elements may have fallback roles, e.g. `role="switch checkbox"`
+        for role in value.split_ascii_whitespace() {
+          if is_aria_role(&role.to_lowercase()) {
+            continue;
+          }
+          let hint = match closest(role, ARIA_ROLES) {
+            Some(suggestion) => {
+              A11yAriaPropsHint::Suggestion(suggestion.into())
+            }
+            None => A11yAriaPropsHint::UseRole,
+          };
+          self.context.add_diagnostic_with_hint(
+            jsx_attr.span,
+            CODE,
+            A11yAriaPropsMessage::InvalidRole(role.to_string()),
+            hint,
+          );
+        }
+      }
+    }
+    jsx_attr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn a11y_aria_props_valid() {
+    assert_lint_ok! {
+      A11yAriaProps,
+      { src: "<div aria-label=\"Menu\" aria-labelledby=\"title\" />", filename: "foo.tsx" },
+      { src: "<input aria-invalid={invalid} aria-describedby={id} />", filename: "foo.tsx" },
+      { src: "<div role=\"button\" />", filename: "foo.tsx" },
+      { src: "<div role=\"switch checkbox\" />", filename: "foo.tsx" },
+      { src: "<div role={role} />", filename: "foo.tsx" },
+      { src: "<nav role=\"doc-toc\" />", filename: "foo.tsx" },
+      { src: "<div data-aria=\"x\" ariaLabel=\"x\" />", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn a11y_aria_props_invalid() {
+    assert_lint_err! {
+      A11yAriaProps,
+      "<div aria-labeledby=\"title\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAriaPropsMessage, InvalidProperty, "aria-labeledby"),
+            hint: variant!(A11yAriaPropsHint, Suggestion, "aria-labelledby"),
+          }
+        ],
+      },
+      "<div aria-foo=\"bar\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAriaPropsMessage, InvalidProperty, "aria-foo"),
+            hint: A11yAriaPropsHint::UseProperty,
+          }
+        ],
+      },
+      "<div role=\"buton\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAriaPropsMessage, InvalidRole, "buton"),
+            hint: variant!(A11yAriaPropsHint, Suggestion, "button"),
+          }
+        ],
+      },
+      "<div role=\"widget datepicker\" />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(A11yAriaPropsMessage, InvalidRole, "datepicker"),
+            hint: A11yAriaPropsHint::UseRole,
+          },
+          {
+            col: 5,
+            message: variant!(A11yAriaPropsMessage, InvalidRole, "widget"),
+            hint: A11yAriaPropsHint::UseRole,
+          }
+        ],
+      },
+    };
+  }
+}
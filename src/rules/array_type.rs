@@ -0,0 +1,655 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{SourceMap, Span, Spanned};
+use swc_ecmascript::ast::{
+  TsArrayType, TsEntityName, TsType, TsTypeOperatorOp,
+};
+use swc_ecmascript::visit::{Node, Visit, VisitWith};
+
+pub struct ArrayType {
+  options: ArrayTypeOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ArrayTypeOptions {
+  /// The style of mutable array types.
+  pub default: ArrayTypeStyle,
+  /// The style of readonly array types, the same as `default` if not set.
+  pub readonly: Option<ArrayTypeStyle>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArrayTypeStyle {
+  /// `T[]` and `readonly T[]`.
+  Array,
+  /// `Array<T>` and `ReadonlyArray<T>`.
+  Generic,
+  /// `T[]` for simple types such as `string` or `Foo`, `Array<T>` for all
+  /// other types.
+  ArraySimple,
+}
+
+impl Default for ArrayTypeOptions {
+  fn default() -> Self {
+    Self {
+      default: ArrayTypeStyle::Array,
+      readonly: None,
+    }
+  }
+}
+
+const CODE: &str = "array-type";
+
+#[derive(Display)]
+enum ArrayTypeMessage {
+  #[display(fmt = "Array type using `{}T[]` is forbidden", _0)]
+  Array(String),
+  #[display(fmt = "Array type using `{}<T>` is forbidden", _0)]
+  Generic(String),
+  #[display(
+    fmt = "Array type using `{}T[]` is forbidden for non-simple types",
+    _0
+  )]
+  ArrayNonSimple(String),
+  #[display(
+    fmt = "Array type using `{}<T>` is forbidden for simple types",
+    _0
+  )]
+  GenericSimple(String),
+}
+
+#[derive(Display)]
+enum ArrayTypeHint {
+  #[display(fmt = "Use `{}T[]` instead", _0)]
+  UseArray(String),
+  #[display(fmt = "Use `{}<T>` instead", _0)]
+  UseGeneric(String),
+}
+
+#[derive(Display)]
+enum ArrayTypeFix {
+  #[display(fmt = "Change to `{}T[]`", _0)]
+  UseArray(String),
+  #[display(fmt = "Change to `{}<T>`", _0)]
+  UseGeneric(String),
+}
+
+impl LintRule for ArrayType {
+  fn new() -> Box<Self> {
+    Box::new(ArrayType {
+      options: ArrayTypeOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let styles = Styles {
+      mutable: self.options.default,
+      readonly: self.options.readonly.unwrap_or(self.options.default),
+    };
+    let mut visitor = ArrayTypeVisitor { context, styles };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "default": {
+          "description": "The style of mutable array types.",
+          "enum": ["array", "generic", "array-simple"],
+          "default": "array"
+        },
+        "readonly": {
+          "description": "The style of readonly array types, the same as `default` if not set.",
+          "enum": ["array", "generic", "array-simple"]
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!("Enforces a consistent syntax for array types", "array-type")
+  }
+}
+
+#[derive(Clone, Copy)]
+struct Styles {
+  mutable: ArrayTypeStyle,
+  readonly: ArrayTypeStyle,
+}
+
+/// An array type written in the style it shouldn't be.
+struct Violation<'a> {
+  span: Span,
+  readonly: bool,
+  /// Whether the type is written as `T[]` rather than `Array<T>`.
+  is_array_syntax: bool,
+  /// Whether the style depends on the element type being simple.
+  simple_only: bool,
+  elem: &'a TsType,
+}
+
+impl<'a> Violation<'a> {
+  fn array_prefix(&self) -> String {
+    if self.readonly { "readonly " } else { "" }.to_string()
+  }
+
+  fn generic_name(&self) -> String {
+    if self.readonly {
+      "ReadonlyArray"
+    } else {
+      "Array"
+    }
+    .to_string()
+  }
+}
+
+/// Returns the array type wrapped by `readonly`, as in `readonly T[]`.
+fn readonly_array(ty: &TsType) -> Option<&TsArrayType> {
+  match ty {
+    TsType::TsTypeOperator(operator)
+      if operator.op == TsTypeOperatorOp::ReadOnly =>
+    {
+      match &*operator.type_ann {
+        TsType::TsArrayType(array) => Some(array),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Returns the element type of `Array<T>` or `ReadonlyArray<T>`, and whether
+/// the array is readonly.
+fn generic_array(ty: &TsType) -> Option<(&TsType, bool)> {
+  let type_ref = match ty {
+    TsType::TsTypeRef(type_ref) => type_ref,
+    _ => return None,
+  };
+  let readonly = match &type_ref.type_name {
+    TsEntityName::Ident(ident) if ident.sym == *"Array" => false,
+    TsEntityName::Ident(ident) if ident.sym == *"ReadonlyArray" => true,
+    _ => return None,
+  };
+  match type_ref.type_params.as_ref()?.params.as_slice() {
+    [elem] => Some((elem, readonly)),
+    _ => None,
+  }
+}
+
+/// Returns `true` for types that read well as `T[]`, like `string`, `this`,
+/// `Foo` or `Foo.Bar`.
+fn is_simple(ty: &TsType) -> bool {
+  match ty {
+    TsType::TsKeywordType(_) | TsType::TsThisType(_) => true,
+    TsType::TsArrayType(array) => is_simple(&array.elem_type),
+    TsType::TsTypeRef(type_ref) => match generic_array(ty) {
+      Some((elem, false)) => is_simple(elem),
+      Some((_, true)) => false,
+      None => type_ref.type_params.is_none(),
+    },
+    _ => false,
+  }
+}
+
+fn check(ty: &TsType, styles: Styles) -> Option<Violation<'_>> {
+  let (readonly, is_array_syntax, elem) =
+    if let Some((elem, readonly)) = generic_array(ty) {
+      (readonly, false, elem)
+    } else if let Some(array) = readonly_array(ty) {
+      (true, true, &*array.elem_type)
+    } else if let TsType::TsArrayType(array) = ty {
+      (false, true, &*array.elem_type)
+    } else {
+      return None;
+    };
+  let style = if readonly {
+    styles.readonly
+  } else {
+    styles.mutable
+  };
+  let wants_array_syntax = match style {
+    ArrayTypeStyle::Array => true,
+    ArrayTypeStyle::Generic => false,
+    ArrayTypeStyle::ArraySimple => is_simple(elem),
+  };
+  if wants_array_syntax == is_array_syntax {
+    return None;
+  }
+  Some(Violation {
+    span: ty.span(),
+    readonly,
+    is_array_syntax,
+    simple_only: style == ArrayTypeStyle::ArraySimple,
+    elem,
+  })
+}
+
+/// Returns `true` if the type has to be wrapped in parentheses to be used as
+/// the element type of `T[]`.
+fn needs_parens(ty: &TsType, styles: Styles) -> bool {
+  match check(ty, styles) {
+    // `ReadonlyArray<T>` turning into `readonly T[]`
+    Some(violation) => violation.readonly && !violation.is_array_syntax,
+    None => matches!(
+      ty,
+      TsType::TsUnionOrIntersectionType(_)
+        | TsType::TsFnOrConstructorType(_)
+        | TsType::TsConditionalType(_)
+        | TsType::TsInferType(_)
+        | TsType::TsTypeOperator(_)
+        | TsType::TsTypePredicate(_)
+        | TsType::TsOptionalType(_)
+        | TsType::TsRestType(_)
+    ),
+  }
+}
+
+/// Builds the text of array types with all the nested array types written in
+/// the expected style.
+struct Printer<'a> {
+  source_map: &'a SourceMap,
+  styles: Styles,
+}
+
+impl<'a> Printer<'a> {
+  /// Returns the text of the type, with the violations inside of it fixed.
+  fn print(&self, ty: &TsType) -> Option<String> {
+    if let Some(violation) = check(ty, self.styles) {
+      return self.print_fixed(&violation);
+    }
+    let mut collector = FixCollector {
+      printer: self,
+      fixes: vec![],
+    };
+    visit_type_children(ty, &mut collector);
+
+    let span = ty.span();
+    let mut text = String::new();
+    let mut last = span.lo();
+    for (fixed_span, fixed) in collector.fixes {
+      let before = span.with_lo(last).with_hi(fixed_span.lo());
+      text.push_str(&self.source_map.span_to_snippet(before).ok()?);
+      text.push_str(&fixed?);
+      last = fixed_span.hi();
+    }
+    let after = span.with_lo(last);
+    text.push_str(&self.source_map.span_to_snippet(after).ok()?);
+    Some(text)
+  }
+
+  /// Returns the text of the violating array type written in the other style.
+  fn print_fixed(&self, violation: &Violation) -> Option<String> {
+    if violation.is_array_syntax {
+      let elem = match violation.elem {
+        TsType::TsParenthesizedType(paren) => &*paren.type_ann,
+        elem => elem,
+      };
+      Some(format!(
+        "{}<{}>",
+        violation.generic_name(),
+        self.print(elem)?
+      ))
+    } else {
+      let elem = self.print(violation.elem)?;
+      if needs_parens(violation.elem, self.styles) {
+        Some(format!("{}({})[]", violation.array_prefix(), elem))
+      } else {
+        Some(format!("{}{}[]", violation.array_prefix(), elem))
+      }
+    }
+  }
+}
+
+/// Visits the types inside of the type, skipping the array type of
+/// `readonly T[]` which is checked as a whole.
+fn visit_type_children<V: Visit>(ty: &TsType, visitor: &mut V) {
+  match readonly_array(ty) {
+    Some(array) => array.elem_type.visit_with(array, visitor),
+    None => ty.visit_children_with(visitor),
+  }
+}
+
+/// Collects the fixed text of the outermost violations inside of a type.
+struct FixCollector<'p, 'a> {
+  printer: &'p Printer<'a>,
+  fixes: Vec<(Span, Option<String>)>,
+}
+
+impl<'p, 'a> Visit for FixCollector<'p, 'a> {
+  fn visit_ts_type(&mut self, ty: &TsType, _: &dyn Node) {
+    match check(ty, self.printer.styles) {
+      Some(violation) => self
+        .fixes
+        .push((violation.span, self.printer.print_fixed(&violation))),
+      None => visit_type_children(ty, self),
+    }
+  }
+}
+
+struct ArrayTypeVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  styles: Styles,
+}
+
+impl<'c, 'view> ArrayTypeVisitor<'c, 'view> {
+  fn report(&mut self, violation: &Violation) {
+    let (message, hint, fix) = match violation {
+      Violation {
+        is_array_syntax: true,
+        simple_only: false,
+        ..
+      } => (
+        ArrayTypeMessage::Array(violation.array_prefix()),
+        ArrayTypeHint::UseGeneric(violation.generic_name()),
+        ArrayTypeFix::UseGeneric(violation.generic_name()),
+      ),
+      Violation {
+        is_array_syntax: true,
+        simple_only: true,
+        ..
+      } => (
+        ArrayTypeMessage::ArrayNonSimple(violation.array_prefix()),
+        ArrayTypeHint::UseGeneric(violation.generic_name()),
+        ArrayTypeFix::UseGeneric(violation.generic_name()),
+      ),
+      Violation {
+        is_array_syntax: false,
+        simple_only: false,
+        ..
+      } => (
+        ArrayTypeMessage::Generic(violation.generic_name()),
+        ArrayTypeHint::UseArray(violation.array_prefix()),
+        ArrayTypeFix::UseArray(violation.array_prefix()),
+      ),
+      Violation {
+        is_array_syntax: false,
+        simple_only: true,
+        ..
+      } => (
+        ArrayTypeMessage::GenericSimple(violation.generic_name()),
+        ArrayTypeHint::UseArray(violation.array_prefix()),
+        ArrayTypeFix::UseArray(violation.array_prefix()),
+      ),
+    };
+
+    let span = violation.span;
+    let has_comments = self
+      .context
+      .all_comments()
+      .any(|c| span.lo() <= c.span.lo && c.span.hi <= span.hi());
+    let printer = Printer {
+      source_map: &self.context.source_map(),
+      styles: self.styles,
+    };
+    let fixed = if has_comments {
+      None
+    } else {
+      printer.print_fixed(violation)
+    };
+    match fixed {
+      Some(fixed) => {
+        let fix = self.context.create_fix(fix, vec![(span, fixed)]);
+        self.context.add_diagnostic_with_fixes(
+          span,
+          CODE,
+          message,
+          Some(hint.to_string()),
+          vec![fix],
+        );
+      }
+      None => {
+        self
+          .context
+          .add_diagnostic_with_hint(span, CODE, message, hint);
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit for ArrayTypeVisitor<'c, 'view> {
+  fn visit_ts_type(&mut self, ty: &TsType, _: &dyn Node) {
+    if let Some(violation) = check(ty, self.styles) {
+      self.report(&violation);
+    }
+    visit_type_children(ty, self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn array_type_valid() {
+    assert_lint_ok! {
+      ArrayType,
+      "let a: string[] = [];",
+      "let a: readonly number[] = [];",
+      "let a: (string | number)[] = [];",
+      "let a: string[][] = [];",
+      "let a: Foo.Bar[] = [];",
+      "let a: Array = [];",
+      "let a: Set<string> = new Set();",
+      "function f(...args: unknown[]): void {}",
+    };
+
+    assert_lint_ok! {
+      ArrayType,
+      options: json!({ "default": "generic" }),
+      "let a: Array<string> = [];",
+      "let a: ReadonlyArray<number> = [];",
+      "let a: Array<Array<string | number>> = [];",
+    };
+
+    assert_lint_ok! {
+      ArrayType,
+      options: json!({ "default": "array", "readonly": "generic" }),
+      "let a: string[] = [];",
+      "let a: ReadonlyArray<string> = [];",
+      "let a: ReadonlyArray<string[]> = [];",
+    };
+
+    assert_lint_ok! {
+      ArrayType,
+      options: json!({ "default": "array-simple" }),
+      "let a: string[] = [];",
+      "let a: Foo[] = [];",
+      "let a: this[] = [];",
+      "let a: string[][] = [];",
+      "let a: Array<string | number> = [];",
+      "let a: Array<Set<string>> = [];",
+      "let a: ReadonlyArray<{ a: string }> = [];",
+    };
+  }
+
+  #[test]
+  fn array_type_invalid() {
+    assert_lint_err! {
+      ArrayType,
+      "let a: Array<string> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: string[] = [];"),
+        }
+      ],
+      "let a: ReadonlyArray<number> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "ReadonlyArray"),
+          hint: variant!(ArrayTypeHint, UseArray, "readonly "),
+          fix: (variant!(ArrayTypeFix, UseArray, "readonly "), "let a: readonly number[] = [];"),
+        }
+      ],
+      "let a: Array<string | number> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: (string | number)[] = [];"),
+        }
+      ],
+      "let a: Array<Array<string>> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: string[][] = [];"),
+        },
+        {
+          col: 13,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: Array<string[]> = [];"),
+        }
+      ],
+      "let a: Array<ReadonlyArray<string>> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: (readonly string[])[] = [];"),
+        },
+        {
+          col: 13,
+          message: variant!(ArrayTypeMessage, Generic, "ReadonlyArray"),
+          hint: variant!(ArrayTypeHint, UseArray, "readonly "),
+          fix: (variant!(ArrayTypeFix, UseArray, "readonly "), "let a: Array<readonly string[]> = [];"),
+        }
+      ],
+      "let a: Map<string, Array<Set<number>>> = new Map();": [
+        {
+          col: 19,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: Map<string, Set<number>[]> = new Map();"),
+        }
+      ],
+      "let a: Array</* names */ string> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      ArrayType,
+      options: json!({ "default": "generic" }),
+      "let a: string[] = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Array, ""),
+          hint: variant!(ArrayTypeHint, UseGeneric, "Array"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "Array"), "let a: Array<string> = [];"),
+        }
+      ],
+      "let a: readonly (string | number)[] = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Array, "readonly "),
+          hint: variant!(ArrayTypeHint, UseGeneric, "ReadonlyArray"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "ReadonlyArray"), "let a: ReadonlyArray<string | number> = [];"),
+        }
+      ],
+      "let a: string[][] = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Array, ""),
+          hint: variant!(ArrayTypeHint, UseGeneric, "Array"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "Array"), "let a: Array<Array<string>> = [];"),
+        },
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Array, ""),
+          hint: variant!(ArrayTypeHint, UseGeneric, "Array"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "Array"), "let a: Array<string>[] = [];"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      ArrayType,
+      options: json!({ "default": "generic", "readonly": "array" }),
+      "let a: ReadonlyArray<string[]> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, Generic, "ReadonlyArray"),
+          hint: variant!(ArrayTypeHint, UseArray, "readonly "),
+          fix: (variant!(ArrayTypeFix, UseArray, "readonly "), "let a: readonly Array<string>[] = [];"),
+        },
+        {
+          col: 21,
+          message: variant!(ArrayTypeMessage, Array, ""),
+          hint: variant!(ArrayTypeHint, UseGeneric, "Array"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "Array"), "let a: ReadonlyArray<Array<string>> = [];"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      ArrayType,
+      options: json!({ "default": "array-simple" }),
+      "let a: Array<string> = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, GenericSimple, "Array"),
+          hint: variant!(ArrayTypeHint, UseArray, ""),
+          fix: (variant!(ArrayTypeFix, UseArray, ""), "let a: string[] = [];"),
+        }
+      ],
+      "let a: (string | number)[] = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, ArrayNonSimple, ""),
+          hint: variant!(ArrayTypeHint, UseGeneric, "Array"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "Array"), "let a: Array<string | number> = [];"),
+        }
+      ],
+      "let a: readonly Set<string>[] = [];": [
+        {
+          col: 7,
+          message: variant!(ArrayTypeMessage, ArrayNonSimple, "readonly "),
+          hint: variant!(ArrayTypeHint, UseGeneric, "ReadonlyArray"),
+          fix: (variant!(ArrayTypeFix, UseGeneric, "ReadonlyArray"), "let a: ReadonlyArray<Set<string>> = [];"),
+        }
+      ],
+    };
+  }
+}
@@ -1,10 +1,12 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule, ProgramRef};
+use derive_more::Display;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
 use swc_common::comments::Comment;
 use swc_common::comments::CommentKind;
-use swc_common::Span;
 
 /// This rule differs from typescript-eslint. In typescript-eslint the following
 /// defaults apply:
@@ -16,22 +18,84 @@ use swc_common::Span;
 /// - ts-expect-error: allowed with comment
 /// - ts-ignore: allowed with comment
 /// - ts-nocheck: allowed with comment
-pub struct BanTsComment;
+pub struct BanTsComment {
+  options: BanTsCommentOptions,
+}
 
-impl BanTsComment {
-  fn report(&self, context: &mut Context, span: Span) {
-    context.add_diagnostic_with_hint(
-      span,
-      "ban-ts-comment",
-      "ts directives are not allowed without comment",
-      "Add an in-line comment explaining the reason for using this directive",
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct BanTsCommentOptions {
+  pub ts_expect_error: DirectiveOption,
+  pub ts_ignore: DirectiveOption,
+  pub ts_nocheck: DirectiveOption,
+  pub ts_check: DirectiveOption,
+  /// Minimum number of characters of the description of directives allowed
+  /// with a description.
+  #[serde(rename = "minimumDescriptionLength")]
+  pub minimum_description_length: usize,
+}
+
+impl Default for BanTsCommentOptions {
+  fn default() -> Self {
+    let with_description = DirectiveOption::WithDescription(
+      AllowWithDescription::AllowWithDescription,
     );
+    Self {
+      ts_expect_error: with_description,
+      ts_ignore: with_description,
+      ts_nocheck: with_description,
+      ts_check: DirectiveOption::Banned(false),
+      minimum_description_length: 1,
+    }
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum DirectiveOption {
+  /// `true` bans the directive, `false` allows it.
+  Banned(bool),
+  /// `"allow-with-description"` allows the directive when it's followed by a
+  /// description.
+  WithDescription(AllowWithDescription),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AllowWithDescription {
+  AllowWithDescription,
+}
+
+const CODE: &str = "ban-ts-comment";
+
+#[derive(Display)]
+enum BanTsCommentMessage {
+  #[display(fmt = "ts directives are not allowed without comment")]
+  NoDescription,
+  #[display(fmt = "`@{}` directive is not allowed", _0)]
+  Banned(String),
+}
+
+#[derive(Display)]
+enum BanTsCommentHint {
+  #[display(
+    fmt = "Add an in-line comment explaining the reason for using this directive"
+  )]
+  AddDescription,
+  #[display(
+    fmt = "Add an in-line comment of at least {} characters explaining the reason for using this directive",
+    _0
+  )]
+  AddLongerDescription(String),
+  #[display(fmt = "Remove the directive and fix the type errors instead")]
+  Remove,
+}
+
 impl LintRule for BanTsComment {
   fn new() -> Box<Self> {
-    Box::new(BanTsComment)
+    Box::new(BanTsComment {
+      options: BanTsCommentOptions::default(),
+    })
   }
 
   fn tags(&self) -> &'static [&'static str] {
@@ -39,77 +103,125 @@ impl LintRule for BanTsComment {
   }
 
   fn code(&self) -> &'static str {
-    "ban-ts-comment"
+    CODE
   }
 
-  fn lint_program(&self, context: &mut Context, _program: ProgramRef<'_>) {
-    let mut violated_comment_spans = Vec::new();
+  fn needs_comments(&self) -> bool {
+    true
+  }
 
-    violated_comment_spans.extend(context.all_comments().filter_map(|c| {
-      if check_comment(c) {
-        Some(c.span)
-      } else {
-        None
-      }
-    }));
+  fn lint_program(&self, context: &mut Context, _program: ProgramRef<'_>) {
+    let violations: Vec<_> = context
+      .all_comments()
+      .filter_map(|c| self.check_comment(c).map(|v| (c.span, v)))
+      .collect();
 
-    for span in violated_comment_spans {
-      self.report(context, span);
+    for (span, (message, hint)) in violations {
+      context.add_diagnostic_with_hint(span, CODE, message, hint);
     }
   }
 
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let directive = json!({
+      "oneOf": [
+        {
+          "description": "`true` bans the directive, `false` allows it.",
+          "type": "boolean"
+        },
+        {
+          "description": "Allows the directive when it's followed by a description.",
+          "enum": ["allow-with-description"]
+        }
+      ]
+    });
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "ts-expect-error": directive.clone(),
+        "ts-ignore": directive.clone(),
+        "ts-nocheck": directive.clone(),
+        "ts-check": directive,
+        "minimumDescriptionLength": {
+          "description": "Minimum number of characters of the description of directives allowed with a description.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 1
+        }
+      }
+    }))
+  }
+
   fn docs(&self) -> &'static str {
-    r#"Disallows the use of Typescript directives without a comment.
-
-Typescript directives reduce the effectiveness of the compiler, something which should only be done in exceptional circumstances.  The reason why should be documented in a comment alongside the directive.
-
-### Invalid:
-```typescript
-// @ts-expect-error
-let a: number = "I am a string";
-```
-```typescript
-// @ts-ignore
-let a: number = "I am a string";
-```
-```typescript
-// @ts-nocheck
-let a: number = "I am a string";
-```
-
-### Valid:
-```typescript
-// @ts-expect-error: Temporary workaround (see ticket #422)
-let a: number = "I am a string";
-```
-```typescript
-// @ts-ignore: Temporary workaround (see ticket #422)
-let a: number = "I am a string";
-```
-```typescript
-// @ts-nocheck: Temporary workaround (see ticket #422)
-let a: number = "I am a string";
-```
-"#
+    rule_docs!(
+      "Disallows the use of Typescript directives without a comment.",
+      "ban-ts-comment"
+    )
   }
 }
 
-/// Returns `true` if the comment should be reported.
-fn check_comment(comment: &Comment) -> bool {
-  if comment.kind != CommentKind::Line {
-    return false;
-  }
+impl BanTsComment {
+  /// Returns the message and hint to report the comment with, if any.
+  fn check_comment(
+    &self,
+    comment: &Comment,
+  ) -> Option<(BanTsCommentMessage, BanTsCommentHint)> {
+    if comment.kind != CommentKind::Line {
+      return None;
+    }
 
-  static BTC_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"^/*\s*@ts-(expect-error|ignore|nocheck)$"#).unwrap()
-  });
+    static BTC_REGEX: Lazy<Regex> = Lazy::new(|| {
+      Regex::new(r#"^/*\s*@(ts-(?:expect-error|ignore|nocheck|check))\b(.*)$"#)
+        .unwrap()
+    });
 
-  BTC_REGEX.is_match(&comment.text)
+    let captures = BTC_REGEX.captures(&comment.text)?;
+    let directive = captures.get(1).unwrap().as_str();
+    let option = match directive {
+      "ts-expect-error" => self.options.ts_expect_error,
+      "ts-ignore" => self.options.ts_ignore,
+      "ts-nocheck" => self.options.ts_nocheck,
+      _ => self.options.ts_check,
+    };
+    match option {
+      DirectiveOption::Banned(false) => None,
+      DirectiveOption::Banned(true) => Some((
+        BanTsCommentMessage::Banned(directive.to_string()),
+        BanTsCommentHint::Remove,
+      )),
+      DirectiveOption::WithDescription(_) => {
+        let description = captures
+          .get(2)
+          .unwrap()
+          .as_str()
+          .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+          .trim_end();
+        let minimum = self.options.minimum_description_length.max(1);
+        if description.chars().count() >= minimum {
+          return None;
+        }
+        let hint = if minimum == 1 {
+          BanTsCommentHint::AddDescription
+        } else {
+          BanTsCommentHint::AddLongerDescription(minimum.to_string())
+        };
+        Some((BanTsCommentMessage::NoDescription, hint))
+      }
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use serde_json::json;
 
   #[test]
   fn ban_ts_comment_valid() {
@@ -156,6 +268,19 @@ console.log('hello');
 console.log('hello');
 }"#,
     };
+
+    assert_lint_ok! {
+      BanTsComment,
+      options: json!({
+        "ts-ignore": false,
+        "ts-nocheck": true,
+        "minimumDescriptionLength": 10,
+      }),
+      r#"// @ts-ignore"#,
+      r#"// @ts-check"#,
+      r#"// @ts-expect-error: Unreachable code error"#,
+      r#"// @ts-ignored is not a directive"#,
+    };
   }
 
   #[test]
@@ -184,5 +309,47 @@ console.log('hello');
             }
           ]
     };
+
+    assert_lint_err! {
+      BanTsComment,
+      r#"// @ts-ignore:"#: [
+        {
+          col: 0,
+          message: BanTsCommentMessage::NoDescription,
+          hint: BanTsCommentHint::AddDescription,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      BanTsComment,
+      options: json!({
+        "ts-ignore": true,
+        "ts-check": true,
+        "ts-expect-error": "allow-with-description",
+        "minimumDescriptionLength": 10,
+      }),
+      r#"// @ts-ignore: Unreachable code error"#: [
+        {
+          col: 0,
+          message: variant!(BanTsCommentMessage, Banned, "ts-ignore"),
+          hint: BanTsCommentHint::Remove,
+        }
+      ],
+      r#"// @ts-check"#: [
+        {
+          col: 0,
+          message: variant!(BanTsCommentMessage, Banned, "ts-check"),
+          hint: BanTsCommentHint::Remove,
+        }
+      ],
+      r#"// @ts-expect-error: bug"#: [
+        {
+          col: 0,
+          message: BanTsCommentMessage::NoDescription,
+          hint: variant!(BanTsCommentHint, AddLongerDescription, 10),
+        }
+      ],
+    };
   }
 }
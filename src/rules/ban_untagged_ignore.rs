@@ -4,6 +4,10 @@ use swc_common::Span;
 
 pub struct BanUntaggedIgnore;
 
+pub(crate) const CODE: &str = "ban-untagged-ignore";
+pub(crate) const MESSAGE: &str = "Ignore directive requires lint rule name(s)";
+pub(crate) const HINT: &str = "Add one or more lint rule names.  E.g. // deno-lint-ignore adjacent-overload-signatures";
+
 impl LintRule for BanUntaggedIgnore {
   fn new() -> Box<Self> {
     Box::new(BanUntaggedIgnore)
@@ -14,7 +18,7 @@ impl LintRule for BanUntaggedIgnore {
   }
 
   fn code(&self) -> &'static str {
-    "ban-untagged-ignore"
+    CODE
   }
 
   fn lint_program(&self, context: &mut Context, _program: ProgramRef<'_>) {
@@ -31,32 +35,15 @@ impl LintRule for BanUntaggedIgnore {
       .collect();
 
     for span in violated_spans {
-      context.add_diagnostic_with_hint(
-        span,
-        "ban-untagged-ignore",
-        "Ignore directive requires lint rule name(s)",
-        "Add one or more lint rule names.  E.g. // deno-lint-ignore adjacent-overload-signatures",
-      )
+      context.add_diagnostic_with_hint(span, CODE, MESSAGE, HINT)
     }
   }
 
   fn docs(&self) -> &'static str {
-    r#"Requires `deno-lint-ignore` to be annotated with one or more rule names.
-
-Ignoring all rules can mask unexpected or future problems. Therefore you need to explicitly specify which rule(s) are to be ignored.
-
-### Invalid:
-```typescript
-// deno-lint-ignore
-export function duplicateArgumentsFn(a, b, a) { }
-```
-
-### Valid:
-```typescript
-// deno-lint-ignore no-dupe-args
-export function duplicateArgumentsFn(a, b, a) { }
-```
-"#
+    rule_docs!(
+      "Requires `deno-lint-ignore` to be annotated with one or more rule names.",
+      "ban-untagged-ignore"
+    )
   }
 }
 
@@ -26,6 +26,10 @@ impl LintRule for BanUntaggedTodo {
     CODE
   }
 
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, context: &mut Context, _program: ProgramRef<'_>) {
     let mut violated_comment_spans = Vec::new();
 
@@ -43,42 +47,10 @@ impl LintRule for BanUntaggedTodo {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Requires TODOs to be annotated with either a user tag (@user) or an issue reference (#issue).
-
-TODOs without reference to a user or an issue become stale with no easy way to get more information.
-
-### Invalid:
-```typescript
-// TODO Improve calc engine
-export function calcValue(): number { }
-```
-```typescript
-// TODO Improve calc engine (@djones)
-export function calcValue(): number { }
-```
-```typescript
-// TODO Improve calc engine (#332)
-export function calcValue(): number { }
-```
-
-### Valid:
-```typescript
-// TODO(djones) Improve calc engine
-export function calcValue(): number { }
-```
-```typescript
-// TODO(@djones) Improve calc engine
-export function calcValue(): number { }
-```
-```typescript
-// TODO(#332)
-export function calcValue(): number { }
-```
-```typescript
-// TODO(#332) Improve calc engine
-export function calcValue(): number { }
-```
-"#
+    rule_docs!(
+      "Requires TODOs to be annotated with either a user tag (@user) or an issue reference (#issue).",
+      "ban-untagged-todo"
+    )
   }
 }
 
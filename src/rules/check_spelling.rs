@@ -0,0 +1,374 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::dictionary::{correction, split_words};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{BytePos, Span, SyntaxContext};
+use swc_ecmascript::ast::{
+  AssignExpr, AssignPatProp, BindingIdent, ClassDecl, ClassExpr, ClassMethod,
+  ClassProp, Expr, FnDecl, FnExpr, Ident, PatOrExpr, PrivateMethod,
+  PrivateProp, PropName, TsEnumDecl, TsEnumMember, TsEnumMemberId,
+  TsInterfaceDecl, TsTypeAliasDecl,
+};
+use swc_ecmascript::visit::{Node, Visit, VisitWith};
+
+pub struct CheckSpelling {
+  options: CheckSpellingOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CheckSpellingOptions {
+  /// Words that are never reported, e.g. names of the project's domain.
+  pub words: Vec<String>,
+}
+
+const CODE: &str = "check-spelling";
+
+#[derive(Display)]
+enum CheckSpellingMessage {
+  #[display(fmt = "`{}` is probably misspelled", _0)]
+  Misspelled(String),
+}
+
+#[derive(Display)]
+enum CheckSpellingHint {
+  #[display(fmt = "Did you mean `{}`?", _0)]
+  Suggestion(String),
+}
+
+impl LintRule for CheckSpelling {
+  fn new() -> Box<Self> {
+    Box::new(CheckSpelling {
+      options: CheckSpellingOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = CheckSpellingVisitor {
+      context,
+      words: &self.options.words,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+
+    for comment in context.all_comments() {
+      // The text of comments starts after `//` or `/*`
+      let lo = comment.span.lo + BytePos(2);
+      check_text(context, &self.options.words, lo, &comment.text);
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "words": {
+          "description": "Words that are never reported, e.g. names of the project's domain.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Reports misspelled words in declared identifiers and comments",
+      "check-spelling"
+    )
+  }
+}
+
+/// Reports the misspelled words of `text`, which starts at `lo`.
+fn check_text(
+  context: &mut Context,
+  words: &[String],
+  lo: BytePos,
+  text: &str,
+) {
+  for (offset, word) in split_words(text) {
+    if words
+      .iter()
+      .any(|allowed| allowed.eq_ignore_ascii_case(word))
+    {
+      continue;
+    }
+    if let Some(correction) = correction(word) {
+      let span = Span::new(
+        lo + BytePos(offset as u32),
+        lo + BytePos((offset + word.len()) as u32),
+        SyntaxContext::empty(),
+      );
+      context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        CheckSpellingMessage::Misspelled(word.to_string()),
+        CheckSpellingHint::Suggestion(correction),
+      );
+    }
+  }
+}
+
+/// Checks the identifiers where they're declared, so that a misspelled name
+/// is reported once rather than at every use, and names coming from other
+/// modules aren't reported at all.
+struct CheckSpellingVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  words: &'c [String],
+}
+
+impl<'c, 'view> CheckSpellingVisitor<'c, 'view> {
+  fn check_ident(&mut self, ident: &Ident) {
+    check_text(self.context, self.words, ident.span.lo, &ident.sym);
+  }
+}
+
+impl<'c, 'view> Visit for CheckSpellingVisitor<'c, 'view> {
+  fn visit_binding_ident(
+    &mut self,
+    binding_ident: &BindingIdent,
+    _: &dyn Node,
+  ) {
+    self.check_ident(&binding_ident.id);
+    binding_ident.visit_children_with(self);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    // The targets of assignments are declared elsewhere
+    if let PatOrExpr::Expr(expr) = &assign_expr.left {
+      expr.visit_with(assign_expr, self);
+    }
+    assign_expr.right.visit_with(assign_expr, self);
+  }
+
+  fn visit_assign_pat_prop(
+    &mut self,
+    assign_pat_prop: &AssignPatProp,
+    _: &dyn Node,
+  ) {
+    self.check_ident(&assign_pat_prop.key);
+    assign_pat_prop.visit_children_with(self);
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    self.check_ident(&fn_decl.ident);
+    fn_decl.visit_children_with(self);
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, _: &dyn Node) {
+    if let Some(ident) = &fn_expr.ident {
+      self.check_ident(ident);
+    }
+    fn_expr.visit_children_with(self);
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, _: &dyn Node) {
+    self.check_ident(&class_decl.ident);
+    class_decl.visit_children_with(self);
+  }
+
+  fn visit_class_expr(&mut self, class_expr: &ClassExpr, _: &dyn Node) {
+    if let Some(ident) = &class_expr.ident {
+      self.check_ident(ident);
+    }
+    class_expr.visit_children_with(self);
+  }
+
+  fn visit_class_method(&mut self, class_method: &ClassMethod, _: &dyn Node) {
+    if let PropName::Ident(ident) = &class_method.key {
+      self.check_ident(ident);
+    }
+    class_method.visit_children_with(self);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, _: &dyn Node) {
+    if let (Expr::Ident(ident), false) = (&*class_prop.key, class_prop.computed)
+    {
+      self.check_ident(ident);
+    }
+    class_prop.visit_children_with(self);
+  }
+
+  fn visit_private_method(
+    &mut self,
+    private_method: &PrivateMethod,
+    _: &dyn Node,
+  ) {
+    self.check_ident(&private_method.key.id);
+    private_method.visit_children_with(self);
+  }
+
+  fn visit_private_prop(&mut self, private_prop: &PrivateProp, _: &dyn Node) {
+    self.check_ident(&private_prop.key.id);
+    private_prop.visit_children_with(self);
+  }
+
+  fn visit_ts_interface_decl(
+    &mut self,
+    interface_decl: &TsInterfaceDecl,
+    _: &dyn Node,
+  ) {
+    self.check_ident(&interface_decl.id);
+    interface_decl.visit_children_with(self);
+  }
+
+  fn visit_ts_type_alias_decl(
+    &mut self,
+    type_alias_decl: &TsTypeAliasDecl,
+    _: &dyn Node,
+  ) {
+    self.check_ident(&type_alias_decl.id);
+    type_alias_decl.visit_children_with(self);
+  }
+
+  fn visit_ts_enum_decl(&mut self, enum_decl: &TsEnumDecl, _: &dyn Node) {
+    self.check_ident(&enum_decl.id);
+    enum_decl.visit_children_with(self);
+  }
+
+  fn visit_ts_enum_member(&mut self, enum_member: &TsEnumMember, _: &dyn Node) {
+    if let TsEnumMemberId::Ident(ident) = &enum_member.id {
+      self.check_ident(ident);
+    }
+    enum_member.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn check_spelling_valid() {
+    assert_lint_ok! {
+      CheckSpelling,
+      "function receiveMessage(message) { return message; }",
+      "// Receives the message\nconst handler = receive;",
+      r#"import { recieve } from "./legacy.ts"; recieve();"#,
+      "const headers = { referer: url }; callback(response.recieved);",
+      "let value; value = recieved;",
+      "class Foo { [recieve]() {} }",
+      "const url = 'https://example.com/recieve';",
+    };
+
+    assert_lint_ok! {
+      CheckSpelling,
+      options: json!({ "words": ["Recieve"] }),
+      "// recieve\nfunction recieveMessage() {}",
+    };
+  }
+
+  #[test]
+  fn check_spelling_invalid() {
+    assert_lint_err! {
+      CheckSpelling,
+      "function recieveMessage() {}": [
+        {
+          col: 9,
+          message: variant!(CheckSpellingMessage, Misspelled, "recieve"),
+          hint: variant!(CheckSpellingHint, Suggestion, "receive"),
+        }
+      ],
+      "const MAX_LENGHT = 10;": [
+        {
+          col: 10,
+          message: variant!(CheckSpellingMessage, Misspelled, "LENGHT"),
+          hint: variant!(CheckSpellingHint, Suggestion, "LENGTH"),
+        }
+      ],
+      "function parse(input: string, { seperator }: Options) {}": [
+        {
+          col: 32,
+          message: variant!(CheckSpellingMessage, Misspelled, "seperator"),
+          hint: variant!(CheckSpellingHint, Suggestion, "separator"),
+        }
+      ],
+      "class HttpReponse { #adress = ''; getAdress() {} }": [
+        {
+          col: 10,
+          message: variant!(CheckSpellingMessage, Misspelled, "Reponse"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Response"),
+        },
+        {
+          col: 21,
+          message: variant!(CheckSpellingMessage, Misspelled, "adress"),
+          hint: variant!(CheckSpellingHint, Suggestion, "address"),
+        },
+        {
+          col: 37,
+          message: variant!(CheckSpellingMessage, Misspelled, "Adress"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Address"),
+        }
+      ],
+      "interface Enviroment {} type Paramter = string; enum Mode { Defualt }": [
+        {
+          col: 10,
+          message: variant!(CheckSpellingMessage, Misspelled, "Enviroment"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Environment"),
+        },
+        {
+          col: 29,
+          message: variant!(CheckSpellingMessage, Misspelled, "Paramter"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Parameter"),
+        },
+        {
+          col: 60,
+          message: variant!(CheckSpellingMessage, Misspelled, "Defualt"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Default"),
+        }
+      ],
+      "// Retreive the value, untill it's found\nconst value = get();": [
+        {
+          line: 1,
+          col: 3,
+          message: variant!(CheckSpellingMessage, Misspelled, "Retreive"),
+          hint: variant!(CheckSpellingHint, Suggestion, "Retrieve"),
+        },
+        {
+          line: 1,
+          col: 23,
+          message: variant!(CheckSpellingMessage, Misspelled, "untill"),
+          hint: variant!(CheckSpellingHint, Suggestion, "until"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      CheckSpelling,
+      options: json!({ "words": ["lenght"] }),
+      "/* occured */ const lenght = 1;": [
+        {
+          col: 3,
+          message: variant!(CheckSpellingMessage, Misspelled, "occured"),
+          hint: variant!(CheckSpellingHint, Suggestion, "occurred"),
+        }
+      ],
+    };
+  }
+}
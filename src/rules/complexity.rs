@@ -0,0 +1,407 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::StringRepr;
+use derive_more::Display;
+use dprint_swc_ecma_ast_view as AstView;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{AssignOp, BinaryOp, MethodKind};
+
+pub struct Complexity {
+  options: ComplexityOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ComplexityOptions {
+  /// The maximum cyclomatic complexity allowed for a single function.
+  pub max: usize,
+}
+
+impl Default for ComplexityOptions {
+  fn default() -> Self {
+    Self { max: 20 }
+  }
+}
+
+const CODE: &str = "complexity";
+
+#[derive(Display)]
+enum ComplexityMessage {
+  #[display(
+    fmt = "{} has a complexity of {}. Maximum allowed is {}.",
+    _0,
+    _1,
+    _2
+  )]
+  TooComplex(String, usize, usize),
+}
+
+#[derive(Display)]
+enum ComplexityHint {
+  #[display(fmt = "Consider splitting this function into smaller functions")]
+  Split,
+}
+
+impl LintRule for Complexity {
+  fn new() -> Box<Self> {
+    Box::new(Complexity {
+      options: ComplexityOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
+    unreachable!();
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: AstView::Program<'_>,
+  ) {
+    let mut handler = ComplexityHandler {
+      max: self.options.max,
+      scores: Vec::new(),
+    };
+    handler.traverse(program, context);
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "max": {
+          "description": "The maximum cyclomatic complexity allowed for a single function.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 20
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a maximum cyclomatic complexity for functions",
+      "complexity"
+    )
+  }
+}
+
+struct ComplexityHandler {
+  max: usize,
+  /// Complexity scores of the functions being traversed, the innermost being
+  /// the last one.
+  scores: Vec<usize>,
+}
+
+impl ComplexityHandler {
+  fn increment(&mut self) {
+    if let Some(score) = self.scores.last_mut() {
+      *score += 1;
+    }
+  }
+}
+
+impl Handler for ComplexityHandler {
+  fn on_enter_node(&mut self, node: AstView::Node, _ctx: &mut Context) {
+    if is_function(node) {
+      self.scores.push(1);
+    } else if is_branch(node) {
+      self.increment();
+    }
+  }
+
+  fn on_exit_node(&mut self, node: AstView::Node, ctx: &mut Context) {
+    if !is_function(node) {
+      return;
+    }
+
+    let score = self
+      .scores
+      .pop()
+      .expect("Function should have a complexity score");
+    if score > self.max {
+      ctx.add_diagnostic_with_hint(
+        function_span(node),
+        CODE,
+        ComplexityMessage::TooComplex(describe_function(node), score, self.max),
+        ComplexityHint::Split,
+      );
+    }
+  }
+}
+
+/// Returns `true` for the nodes that have their own complexity score.
+pub(crate) fn is_function(node: AstView::Node) -> bool {
+  use AstView::Node;
+  matches!(
+    node,
+    Node::Function(_)
+      | Node::ArrowExpr(_)
+      | Node::Constructor(_)
+      | Node::GetterProp(_)
+      | Node::SetterProp(_)
+  )
+}
+
+/// Returns `true` for the nodes adding a path through the function they're
+/// in, each of them increasing its complexity by one.
+///
+/// Counting these nodes gives the same score as counting the decision points
+/// of the control flow graph, as each of them is one. The graph can't be used
+/// directly: `ControlFlow` only records the reachability of statements, not
+/// the edges between blocks, and knows nothing about the branches inside
+/// expressions like `a && b` or `a ? b : c`.
+pub(crate) fn is_branch(node: AstView::Node) -> bool {
+  use AstView::Node;
+  match node {
+    Node::IfStmt(_)
+    | Node::CondExpr(_)
+    | Node::ForStmt(_)
+    | Node::ForInStmt(_)
+    | Node::ForOfStmt(_)
+    | Node::WhileStmt(_)
+    | Node::DoWhileStmt(_)
+    | Node::CatchClause(_) => true,
+    Node::SwitchCase(case) => case.test.is_some(),
+    Node::BinExpr(bin_expr) => matches!(
+      bin_expr.op(),
+      BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing
+    ),
+    Node::AssignExpr(assign_expr) => matches!(
+      assign_expr.op(),
+      AssignOp::AndAssign | AssignOp::OrAssign | AssignOp::NullishAssign
+    ),
+    _ => false,
+  }
+}
+
+/// Returns the span of the whole function-like node, including the declaring
+/// parent if there is one, e.g. `function foo() {}` rather than just `() {}`.
+pub(crate) fn function_span(node: AstView::Node) -> swc_common::Span {
+  use AstView::Node;
+  match node {
+    Node::Function(function) => match function.parent {
+      Node::FnDecl(_)
+      | Node::FnExpr(_)
+      | Node::ClassMethod(_)
+      | Node::PrivateMethod(_)
+      | Node::MethodProp(_) => function.parent.span(),
+      _ => function.span(),
+    },
+    _ => node.span(),
+  }
+}
+
+/// Returns a human readable description of the function, e.g. `Function 'foo'`.
+pub(crate) fn describe_function(node: AstView::Node) -> String {
+  use AstView::Node;
+  match node {
+    Node::Function(function) => match function.parent {
+      Node::FnDecl(fn_decl) => format!("Function '{}'", fn_decl.ident.sym()),
+      Node::FnExpr(fn_expr) => match fn_expr.ident {
+        Some(ident) => format!("Function '{}'", ident.sym()),
+        None => "Function".to_string(),
+      },
+      Node::ClassMethod(method) => {
+        let kind = match method.method_kind() {
+          MethodKind::Method => "Method",
+          MethodKind::Getter => "Getter",
+          MethodKind::Setter => "Setter",
+        };
+        match method.inner.key.string_repr() {
+          Some(name) => format!("{} '{}'", kind, name),
+          None => kind.to_string(),
+        }
+      }
+      Node::PrivateMethod(method) => {
+        format!("Method '#{}'", method.inner.key.id.sym)
+      }
+      Node::MethodProp(method) => match method.inner.key.string_repr() {
+        Some(name) => format!("Method '{}'", name),
+        None => "Method".to_string(),
+      },
+      _ => "Function".to_string(),
+    },
+    Node::ArrowExpr(_) => "Arrow function".to_string(),
+    Node::Constructor(_) => "Constructor".to_string(),
+    Node::GetterProp(getter) => match getter.inner.key.string_repr() {
+      Some(name) => format!("Getter '{}'", name),
+      None => "Getter".to_string(),
+    },
+    Node::SetterProp(setter) => match setter.inner.key.string_repr() {
+      Some(name) => format!("Setter '{}'", name),
+      None => "Setter".to_string(),
+    },
+    _ => unreachable!(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn complexity_valid() {
+    assert_lint_ok! {
+      Complexity,
+      "function foo() {}",
+      "function foo(a) { if (a) { return 1; } return 2; }",
+      "const foo = () => a && b;",
+      r#"
+function foo(a) {
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+  if (a) {}
+}
+      "#,
+    };
+
+    assert_lint_ok! {
+      Complexity,
+      options: json!({ "max": 2 }),
+      "function foo(a) { if (a) { return 1; } return 2; }",
+      "function foo(a) { return a ? 1 : 2; }",
+      "function foo(a) { for (const x of a) {} }",
+      "function foo(a) { switch (a) { case 1: break; default: break; } }",
+      "function foo(a) { try {} catch { } finally {} }",
+      "const foo = (a, b) => a ?? b;",
+      "class Foo { bar(a) { a ||= 1; } }",
+      // nested functions are scored on their own
+      "function foo(a) { if (a) {} return () => a ? 1 : 2; }",
+      // top-level code isn't part of any function
+      "if (a) {} if (b) {} if (c) {}",
+    };
+  }
+
+  #[test]
+  fn complexity_invalid() {
+    assert_lint_err! {
+      Complexity,
+      options: json!({ "max": 1 }),
+      "function foo(a) { if (a) {} }": [
+        {
+          col: 0,
+          message: ComplexityMessage::TooComplex("Function 'foo'".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        }
+      ],
+      "const foo = function (a) { while (a) {} };": [
+        {
+          col: 12,
+          message: ComplexityMessage::TooComplex("Function".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        }
+      ],
+      "const foo = (a) => a || b;": [
+        {
+          col: 12,
+          message: ComplexityMessage::TooComplex("Arrow function".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        }
+      ],
+      "class Foo { constructor(a) { a &&= 1; } get bar() { return a ? 1 : 2; } #baz() { if (a) {} } }": [
+        {
+          col: 12,
+          message: ComplexityMessage::TooComplex("Constructor".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        },
+        {
+          col: 40,
+          message: ComplexityMessage::TooComplex("Getter 'bar'".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        },
+        {
+          col: 72,
+          message: ComplexityMessage::TooComplex("Method '#baz'".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        }
+      ],
+      "const obj = { foo(a) { for (;;) {} }, set bar(a) { for (const x in a) {} } };": [
+        {
+          col: 14,
+          message: ComplexityMessage::TooComplex("Method 'foo'".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        },
+        {
+          col: 38,
+          message: ComplexityMessage::TooComplex("Setter 'bar'".to_string(), 2, 1),
+          hint: ComplexityHint::Split,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      Complexity,
+      options: json!({ "max": 3 }),
+      r#"
+function foo(a, b) {
+  if (a && b) {
+    return 1;
+  }
+  const inner = () => {
+    do {} while (a);
+    try {} catch (e) {}
+    return b ? 2 : 3;
+  };
+  switch (a) {
+    case 1:
+    case 2:
+      return inner();
+    default:
+      return 0;
+  }
+}
+      "#: [
+        {
+          line: 2,
+          col: 0,
+          message: ComplexityMessage::TooComplex("Function 'foo'".to_string(), 5, 3),
+          hint: ComplexityHint::Split,
+        },
+        {
+          line: 6,
+          col: 16,
+          message: ComplexityMessage::TooComplex("Arrow function".to_string(), 4, 3),
+          hint: ComplexityHint::Split,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,434 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::diagnostic::LintFix;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{
+  AssignPat, ClassProp, Expr, NewExpr, Pat, PrivateProp, TsEntityName, TsType,
+  TsTypeAnn, VarDeclarator,
+};
+use swc_ecmascript::visit::{Node, VisitAll, VisitAllWith};
+
+pub struct ConsistentGenericConstructors {
+  options: ConsistentGenericConstructorsOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConsistentGenericConstructorsOptions {
+  /// Where the type arguments of generic constructor calls go.
+  pub style: ConsistentGenericConstructorsStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConsistentGenericConstructorsStyle {
+  /// `const a = new Map<string, number>()`.
+  Constructor,
+  /// `const a: Map<string, number> = new Map()`.
+  TypeAnnotation,
+}
+
+impl Default for ConsistentGenericConstructorsOptions {
+  fn default() -> Self {
+    Self {
+      style: ConsistentGenericConstructorsStyle::Constructor,
+    }
+  }
+}
+
+const CODE: &str = "consistent-generic-constructors";
+
+#[derive(Display)]
+enum ConsistentGenericConstructorsMessage {
+  #[display(fmt = "Type arguments should be on the constructor call")]
+  Constructor,
+  #[display(fmt = "Type arguments should be on the type annotation")]
+  TypeAnnotation,
+}
+
+#[derive(Display)]
+enum ConsistentGenericConstructorsHint {
+  #[display(fmt = "Move the type arguments to the constructor call")]
+  MoveToConstructor,
+  #[display(fmt = "Move the type arguments to the type annotation")]
+  MoveToTypeAnnotation,
+}
+
+#[derive(Display)]
+enum ConsistentGenericConstructorsFix {
+  #[display(fmt = "Move to the constructor call")]
+  MoveToConstructor,
+  #[display(fmt = "Move to the type annotation")]
+  MoveToTypeAnnotation,
+}
+
+impl LintRule for ConsistentGenericConstructors {
+  fn new() -> Box<Self> {
+    Box::new(ConsistentGenericConstructors {
+      options: ConsistentGenericConstructorsOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = ConsistentGenericConstructorsVisitor {
+      context,
+      style: self.options.style,
+    };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "style": {
+          "description": "Where the type arguments of generic constructor calls go.",
+          "enum": ["constructor", "type-annotation"],
+          "default": "constructor"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a consistent placement of type arguments when calling generic constructors",
+      "consistent-generic-constructors"
+    )
+  }
+}
+
+/// Returns the `new` expression if the value is one calling a constructor by
+/// its name.
+fn constructor_call(value: &Expr) -> Option<(&NewExpr, &str)> {
+  match value {
+    Expr::New(new_expr) => match &*new_expr.callee {
+      Expr::Ident(callee) => Some((new_expr, &*callee.sym)),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+struct ConsistentGenericConstructorsVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  style: ConsistentGenericConstructorsStyle,
+}
+
+impl<'c, 'view> ConsistentGenericConstructorsVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  /// Checks a binding initialized with a value. `annotation_pos` is where a
+  /// type annotation would be inserted, or `None` if it can't be.
+  fn check(
+    &mut self,
+    span: Span,
+    annotation_pos: Option<BytePos>,
+    type_ann: Option<&TsTypeAnn>,
+    value: &Expr,
+  ) {
+    let (new_expr, callee) = match constructor_call(value) {
+      Some(call) => call,
+      None => return,
+    };
+    match (self.style, type_ann) {
+      (ConsistentGenericConstructorsStyle::Constructor, Some(type_ann)) => {
+        let type_ref = match &*type_ann.type_ann {
+          TsType::TsTypeRef(type_ref) => type_ref,
+          _ => return,
+        };
+        let type_args = match (&type_ref.type_name, &type_ref.type_params) {
+          (TsEntityName::Ident(name), Some(type_args))
+            if *name.sym == *callee && new_expr.type_args.is_none() =>
+          {
+            type_args
+          }
+          _ => return,
+        };
+        let fix = self.snippet(type_args.span).map(|type_args| {
+          // `new Foo` has to become `new Foo<T>()`
+          let parens = if new_expr.args.is_none() { "()" } else { "" };
+          let callee_end = new_expr.callee.span().hi;
+          self.context.create_fix(
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            vec![
+              (type_ann.span, "".to_string()),
+              (
+                Span::new(callee_end, callee_end, span.ctxt),
+                format!("{}{}", type_args, parens),
+              ),
+            ],
+          )
+        });
+        self.report(
+          span,
+          ConsistentGenericConstructorsMessage::Constructor,
+          ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix,
+        );
+      }
+      (ConsistentGenericConstructorsStyle::TypeAnnotation, None) => {
+        let type_args = match &new_expr.type_args {
+          Some(type_args) => type_args,
+          None => return,
+        };
+        let callee_end = new_expr.callee.span().hi;
+        let fix = match (annotation_pos, self.snippet(type_args.span)) {
+          (Some(pos), Some(type_args_text)) => Some(self.context.create_fix(
+            ConsistentGenericConstructorsFix::MoveToTypeAnnotation,
+            vec![
+              (
+                Span::new(pos, pos, span.ctxt),
+                format!(": {}{}", callee, type_args_text),
+              ),
+              (
+                Span::new(callee_end, type_args.span.hi, span.ctxt),
+                "".to_string(),
+              ),
+            ],
+          )),
+          _ => None,
+        };
+        self.report(
+          span,
+          ConsistentGenericConstructorsMessage::TypeAnnotation,
+          ConsistentGenericConstructorsHint::MoveToTypeAnnotation,
+          fix,
+        );
+      }
+      _ => {}
+    }
+  }
+
+  fn check_pat(&mut self, span: Span, pat: &Pat, value: &Expr) {
+    if let Pat::Ident(binding) = pat {
+      self.check(
+        span,
+        Some(binding.id.span.hi),
+        binding.type_ann.as_ref(),
+        value,
+      );
+    }
+  }
+
+  fn report(
+    &mut self,
+    span: Span,
+    message: ConsistentGenericConstructorsMessage,
+    hint: ConsistentGenericConstructorsHint,
+    fix: Option<LintFix>,
+  ) {
+    match fix {
+      Some(fix) => self.context.add_diagnostic_with_fixes(
+        span,
+        CODE,
+        message,
+        Some(hint.to_string()),
+        vec![fix],
+      ),
+      None => self
+        .context
+        .add_diagnostic_with_hint(span, CODE, message, hint),
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for ConsistentGenericConstructorsVisitor<'c, 'view> {
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    _: &dyn Node,
+  ) {
+    if let Some(init) = &var_declarator.init {
+      self.check_pat(var_declarator.span, &var_declarator.name, init);
+    }
+  }
+
+  fn visit_assign_pat(&mut self, assign_pat: &AssignPat, _: &dyn Node) {
+    self.check_pat(assign_pat.span, &assign_pat.left, &assign_pat.right);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, _: &dyn Node) {
+    if let Some(value) = &class_prop.value {
+      // The annotation of `[foo]` or `foo?` would go after `]` or `?`
+      let annotation_pos = if class_prop.computed || class_prop.is_optional {
+        None
+      } else {
+        Some(class_prop.key.span().hi)
+      };
+      self.check(
+        class_prop.span,
+        annotation_pos,
+        class_prop.type_ann.as_ref(),
+        value,
+      );
+    }
+  }
+
+  fn visit_private_prop(&mut self, private_prop: &PrivateProp, _: &dyn Node) {
+    if let Some(value) = &private_prop.value {
+      let annotation_pos = if private_prop.is_optional {
+        None
+      } else {
+        Some(private_prop.key.span.hi)
+      };
+      self.check(
+        private_prop.span,
+        annotation_pos,
+        private_prop.type_ann.as_ref(),
+        value,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn consistent_generic_constructors_valid() {
+    assert_lint_ok! {
+      ConsistentGenericConstructors,
+      "const a = new Map<string, number>();",
+      "const a: Map<string, number> = new Map<string, number>();",
+      "const a: ReadonlyMap<string, number> = new Map();",
+      "const a: Map<string, number> = createMap();",
+      "const a: Foo.Bar<string> = new Foo.Bar();",
+      "const a = new Map();",
+      "class A { b = new Set<string>(); }",
+    };
+
+    assert_lint_ok! {
+      ConsistentGenericConstructors,
+      options: json!({ "style": "type-annotation" }),
+      "const a: Map<string, number> = new Map();",
+      "const a: Map<string, number> = new Map<string, number>();",
+      "const a = new Map();",
+      "function foo(a: Set<string> = new Set()) {}",
+    };
+  }
+
+  #[test]
+  fn consistent_generic_constructors_invalid() {
+    assert_lint_err! {
+      ConsistentGenericConstructors,
+      "const a: Map<string, number> = new Map();": [
+        {
+          col: 6,
+          message: ConsistentGenericConstructorsMessage::Constructor,
+          hint: ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            "const a = new Map<string, number>();"
+          ),
+        }
+      ],
+      "let a: Set<string> = new Set;": [
+        {
+          col: 4,
+          message: ConsistentGenericConstructorsMessage::Constructor,
+          hint: ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            "let a = new Set<string>();"
+          ),
+        }
+      ],
+      "function foo(a: Set<string> = new Set([''])) {}": [
+        {
+          col: 13,
+          message: ConsistentGenericConstructorsMessage::Constructor,
+          hint: ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            "function foo(a = new Set<string>([''])) {}"
+          ),
+        }
+      ],
+      "class A { b: Set<string> = new Set(); #c: Set<string> = new Set(); }": [
+        {
+          col: 10,
+          message: ConsistentGenericConstructorsMessage::Constructor,
+          hint: ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            "class A { b = new Set<string>(); #c: Set<string> = new Set(); }"
+          ),
+        },
+        {
+          col: 38,
+          message: ConsistentGenericConstructorsMessage::Constructor,
+          hint: ConsistentGenericConstructorsHint::MoveToConstructor,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToConstructor,
+            "class A { b: Set<string> = new Set(); #c = new Set<string>(); }"
+          ),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      ConsistentGenericConstructors,
+      options: json!({ "style": "type-annotation" }),
+      "const a = new Map<string, number>();": [
+        {
+          col: 6,
+          message: ConsistentGenericConstructorsMessage::TypeAnnotation,
+          hint: ConsistentGenericConstructorsHint::MoveToTypeAnnotation,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToTypeAnnotation,
+            "const a: Map<string, number> = new Map();"
+          ),
+        }
+      ],
+      "class A { b = new Set<string>([]); }": [
+        {
+          col: 10,
+          message: ConsistentGenericConstructorsMessage::TypeAnnotation,
+          hint: ConsistentGenericConstructorsHint::MoveToTypeAnnotation,
+          fix: (
+            ConsistentGenericConstructorsFix::MoveToTypeAnnotation,
+            "class A { b: Set<string> = new Set([]); }"
+          ),
+        }
+      ],
+      "class A { [b] = new Set<string>(); }": [
+        {
+          col: 10,
+          message: ConsistentGenericConstructorsMessage::TypeAnnotation,
+          hint: ConsistentGenericConstructorsHint::MoveToTypeAnnotation,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,232 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::SwitchStmt;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct DefaultCase {
+  comment_pattern: Option<Regex>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DefaultCaseOptions {
+  /// A regular expression matched against the comment that marks a `switch`
+  /// as intentionally not having a `default` clause. Defaults to
+  /// `^no default$`, ignoring case.
+  pub comment_pattern: Option<String>,
+}
+
+const CODE: &str = "default-case";
+
+static DEFAULT_COMMENT_PATTERN: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"(?i)^no default$").unwrap());
+
+#[derive(Display)]
+enum DefaultCaseMessage {
+  #[display(fmt = "`switch` statement has no `default` clause")]
+  Missing,
+}
+
+#[derive(Display)]
+enum DefaultCaseHint {
+  #[display(
+    fmt = "Add a `default` clause, or a `// no default` comment after the last case if the other values can't happen"
+  )]
+  AddDefault,
+}
+
+impl LintRule for DefaultCase {
+  fn new() -> Box<Self> {
+    Box::new(DefaultCase {
+      comment_pattern: None,
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = DefaultCaseVisitor {
+      context,
+      comment_pattern: self
+        .comment_pattern
+        .as_ref()
+        .unwrap_or(&DEFAULT_COMMENT_PATTERN),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: DefaultCaseOptions = serde_json::from_value(options)?;
+    self.comment_pattern = match options.comment_pattern {
+      Some(pattern) => {
+        Some(Regex::new(&pattern).map_err(serde_json::Error::custom)?)
+      }
+      None => None,
+    };
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "commentPattern": {
+          "description": "A regular expression matched against the comment that marks a `switch` as intentionally not having a `default` clause.",
+          "type": "string",
+          "default": "(?i)^no default$"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires `switch` statements to have a `default` clause",
+      "default-case"
+    )
+  }
+}
+
+struct DefaultCaseVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  comment_pattern: &'c Regex,
+}
+
+impl<'c, 'view> DefaultCaseVisitor<'c, 'view> {
+  /// Returns `true` if the last comment of the `switch` after the start of
+  /// its last case matches the comment pattern.
+  fn has_no_default_comment(&self, switch_stmt: &SwitchStmt) -> bool {
+    let last_case = match switch_stmt.cases.last() {
+      Some(case) => case,
+      None => return false,
+    };
+    let last_comment = self
+      .context
+      .all_comments()
+      .filter(|comment| {
+        last_case.span.lo <= comment.span.lo
+          && comment.span.hi <= switch_stmt.span.hi
+      })
+      .max_by_key(|comment| comment.span.lo);
+    match last_comment {
+      Some(comment) => self.comment_pattern.is_match(comment.text.trim()),
+      None => false,
+    }
+  }
+}
+
+impl<'c, 'view> Visit for DefaultCaseVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, _: &dyn Node) {
+    let has_default = switch_stmt.cases.iter().any(|case| case.test.is_none());
+    // An empty `switch` is reported by `no-empty`
+    if !has_default
+      && !switch_stmt.cases.is_empty()
+      && !self.has_no_default_comment(switch_stmt)
+    {
+      self.context.add_diagnostic_with_hint(
+        switch_stmt.span,
+        CODE,
+        DefaultCaseMessage::Missing,
+        DefaultCaseHint::AddDefault,
+      );
+    }
+    switch_stmt.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn default_case_valid() {
+    assert_lint_ok! {
+      DefaultCase,
+      "switch (a) { case 1: break; default: break; }",
+      "switch (a) { default: break; case 1: break; }",
+      "switch (a) {}",
+      "switch (a) { case 1: break; // no default\n }",
+      "switch (a) { case 1: break; /* No Default */ }",
+      "switch (a) {\n  case 1:\n    foo();\n    break;\n  // no default\n}",
+    };
+
+    assert_lint_ok! {
+      DefaultCase,
+      options: json!({ "commentPattern": "^skip default" }),
+      "switch (a) { case 1: break; // skip default: handled above\n }",
+    };
+  }
+
+  #[test]
+  fn default_case_invalid() {
+    assert_lint_err! {
+      DefaultCase,
+      "switch (a) { case 1: break; }": [
+        {
+          col: 0,
+          message: DefaultCaseMessage::Missing,
+          hint: DefaultCaseHint::AddDefault,
+        }
+      ],
+      "switch (a) { case 1: switch (b) { case 2: break; default: } }": [
+        {
+          col: 0,
+          message: DefaultCaseMessage::Missing,
+          hint: DefaultCaseHint::AddDefault,
+        }
+      ],
+      "// no default\nswitch (a) { case 1: break; }": [
+        {
+          line: 2,
+          col: 0,
+          message: DefaultCaseMessage::Missing,
+          hint: DefaultCaseHint::AddDefault,
+        }
+      ],
+      "switch (a) { case 1: // no default\n foo(); // bar\n }": [
+        {
+          col: 0,
+          message: DefaultCaseMessage::Missing,
+          hint: DefaultCaseHint::AddDefault,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      DefaultCase,
+      options: json!({ "commentPattern": "^skip default" }),
+      "switch (a) { case 1: break; // no default\n }": [
+        {
+          col: 0,
+          message: DefaultCaseMessage::Missing,
+          hint: DefaultCaseHint::AddDefault,
+        }
+      ],
+    };
+  }
+}
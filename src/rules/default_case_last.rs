@@ -0,0 +1,121 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_ecmascript::ast::SwitchStmt;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct DefaultCaseLast;
+
+const CODE: &str = "default-case-last";
+
+#[derive(Display)]
+enum DefaultCaseLastMessage {
+  #[display(fmt = "`default` clause should be the last clause")]
+  NotLast,
+}
+
+#[derive(Display)]
+enum DefaultCaseLastHint {
+  #[display(
+    fmt = "Move the `default` clause to the end of the `switch` statement"
+  )]
+  MoveToEnd,
+}
+
+impl LintRule for DefaultCaseLast {
+  fn new() -> Box<Self> {
+    Box::new(DefaultCaseLast)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = DefaultCaseLastVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires the `default` clause of `switch` statements to be the last one",
+      "default-case-last"
+    )
+  }
+}
+
+struct DefaultCaseLastVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> Visit for DefaultCaseLastVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, _: &dyn Node) {
+    let cases = &switch_stmt.cases;
+    let default_index = cases.iter().position(|case| case.test.is_none());
+    if let Some(index) = default_index {
+      if index + 1 < cases.len() {
+        self.context.add_diagnostic_with_hint(
+          cases[index].span,
+          CODE,
+          DefaultCaseLastMessage::NotLast,
+          DefaultCaseLastHint::MoveToEnd,
+        );
+      }
+    }
+    switch_stmt.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn default_case_last_valid() {
+    assert_lint_ok! {
+      DefaultCaseLast,
+      "switch (a) { case 1: break; default: break; }",
+      "switch (a) { case 1: break; case 2: break; }",
+      "switch (a) { default: break; }",
+      "switch (a) {}",
+      "switch (a) { case 1: default: break; }",
+    };
+  }
+
+  #[test]
+  fn default_case_last_invalid() {
+    assert_lint_err! {
+      DefaultCaseLast,
+      "switch (a) { default: break; case 1: break; }": [
+        {
+          col: 13,
+          message: DefaultCaseLastMessage::NotLast,
+          hint: DefaultCaseLastHint::MoveToEnd,
+        }
+      ],
+      "switch (a) { case 1: default: case 2: break; }": [
+        {
+          col: 21,
+          message: DefaultCaseLastMessage::NotLast,
+          hint: DefaultCaseLastHint::MoveToEnd,
+        }
+      ],
+      "switch (a) { case 1: switch (b) { default: case 2: } break; default: }": [
+        {
+          col: 34,
+          message: DefaultCaseLastMessage::NotLast,
+          hint: DefaultCaseLastHint::MoveToEnd,
+        }
+      ],
+    };
+  }
+}
@@ -31,30 +31,10 @@ impl LintRule for DefaultParamLast {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Enforces default parameter(s) to be last in the function signature.
-
-Parameters with default values are optional by nature but cannot be left out
-of the function call without mapping the function inputs to different parameters
-which is confusing and error prone.  Specifying them last allows them to be left
-out without changing the semantics of the other parameters.
-
-### Invalid:
-```typescript
-function f(a = 2, b) {}
-function f(a = 5, b, c = 5) {}
-```
-    
-### Valid:
-```typescript
-function f() {}
-function f(a) {}
-function f(a = 5) {}
-function f(a, b = 5) {}
-function f(a, b = 5, c = 5) {}
-function f(a, b = 5, ...c) {}
-function f(a = 2, b = 3) {}
-```
-"#
+    rule_docs!(
+      "Enforces default parameter(s) to be last in the function signature.",
+      "default-param-last"
+    )
   }
 }
 
@@ -150,11 +130,11 @@ class Foo {
     assert_lint_err::<DefaultParamLast>("const f = function (a = 2, b) {}", 20);
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b = 6, c) {}",
-      vec![18, 11],
+      vec![11, 18],
     );
     assert_lint_err_n::<DefaultParamLast>(
       "function f(a = 5, b, c = 6, d) {}",
-      vec![21, 11],
+      vec![11, 21],
     );
     assert_lint_err::<DefaultParamLast>("function f(a = 5, b, c = 5) {}", 11);
     assert_lint_err::<DefaultParamLast>("const f = (a = 5, b, ...c) => {}", 11);
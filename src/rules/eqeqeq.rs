@@ -1,12 +1,47 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::diagnostic::LintFix;
 use derive_more::Display;
-use swc_ecmascript::ast::{BinExpr, BinaryOp};
+use dprint_swc_ecma_ast_view::{RootNode, Token};
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr, Lit, UnaryOp};
+use swc_ecmascript::parser::token::BinOpToken;
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
 
-pub struct Eqeqeq;
+pub struct Eqeqeq {
+  options: EqeqeqOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EqeqeqOptions {
+  /// Which comparisons are allowed to use `==` and `!=`.
+  pub mode: EqeqeqMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EqeqeqMode {
+  /// No comparison.
+  Always,
+  /// Comparisons with `null`, of two literals, and of the result of `typeof`,
+  /// whose behavior doesn't depend on type coercion.
+  Smart,
+  /// Comparisons with `null`, which also match `undefined`.
+  AllowNull,
+}
+
+impl Default for EqeqeqOptions {
+  fn default() -> Self {
+    Self {
+      mode: EqeqeqMode::Always,
+    }
+  }
+}
 
 const CODE: &str = "eqeqeq";
 
@@ -26,58 +61,176 @@ enum EqeqeqHint {
   UseNoteqeq,
 }
 
+#[derive(Display)]
+enum EqeqeqFix {
+  #[display(fmt = "Change to '==='")]
+  UseEqeqeq,
+  #[display(fmt = "Change to '!=='")]
+  UseNoteqeq,
+}
+
 impl LintRule for Eqeqeq {
   fn new() -> Box<Self> {
-    Box::new(Eqeqeq)
+    Box::new(Eqeqeq {
+      options: EqeqeqOptions::default(),
+    })
   }
 
   fn code(&self) -> &'static str {
     CODE
   }
 
+  fn fixable(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
     program: ProgramRef<'view>,
   ) {
-    let mut visitor = EqeqeqVisitor::new(context);
+    let mut visitor = EqeqeqVisitor::new(context, self.options.mode);
     match program {
       ProgramRef::Module(ref m) => visitor.visit_module(m, &DUMMY_NODE),
       ProgramRef::Script(ref s) => visitor.visit_script(s, &DUMMY_NODE),
     }
   }
 
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "mode": {
+          "description": "Which comparisons are allowed to use `==` and `!=`.",
+          "enum": ["always", "smart", "allow-null"],
+          "default": "always"
+        }
+      }
+    }))
+  }
+
   fn docs(&self) -> &'static str {
-    r#"Enforces the use of type-safe equality operators `===` and `!==`
-instead of the more error prone `==` and `!=` operators.
+    rule_docs!(
+      r#"Enforces the use of type-safe equality operators `===` and `!==`
+instead of the more error prone `==` and `!=` operators."#,
+      "eqeqeq"
+    )
+  }
+}
 
-`===` and `!==` ensure the comparators are of the same type as well as the same
-value.  On the other hand `==` and `!=` do type coercion before value checking
-which can lead to unexpected results.  For example `5 == "5"` is true, while
-`5 === "5"` is false.
+/// The types of values known without type information.
+#[derive(PartialEq)]
+enum KnownType {
+  String,
+  Number,
+  BigInt,
+  Boolean,
+}
 
-### Invalid:
-```typescript
-if (a == 5) {}
-if ("hello world" != input) {}
-```
+fn unwrap_parens(mut expr: &Expr) -> &Expr {
+  while let Expr::Paren(paren) = expr {
+    expr = &paren.expr;
+  }
+  expr
+}
 
-### Valid:
-```typescript
-if (a === 5) {}
-if ("hello world" !== input) {}
-```
-"#
+fn known_type(expr: &Expr) -> Option<KnownType> {
+  match unwrap_parens(expr) {
+    Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => Some(KnownType::String),
+    Expr::Lit(Lit::Num(_)) => Some(KnownType::Number),
+    Expr::Lit(Lit::BigInt(_)) => Some(KnownType::BigInt),
+    Expr::Lit(Lit::Bool(_)) => Some(KnownType::Boolean),
+    Expr::Unary(unary) => match unary.op {
+      UnaryOp::TypeOf => Some(KnownType::String),
+      UnaryOp::Bang => Some(KnownType::Boolean),
+      UnaryOp::Minus | UnaryOp::Plus | UnaryOp::Tilde => {
+        match known_type(&unary.arg) {
+          Some(KnownType::BigInt) if unary.op != UnaryOp::Plus => {
+            Some(KnownType::BigInt)
+          }
+          Some(_) => Some(KnownType::Number),
+          None => None,
+        }
+      }
+      _ => None,
+    },
+    _ => None,
   }
 }
 
+fn is_null(expr: &Expr) -> bool {
+  matches!(unwrap_parens(expr), Expr::Lit(Lit::Null(_)))
+}
+
+fn is_typeof(expr: &Expr) -> bool {
+  matches!(
+    unwrap_parens(expr),
+    Expr::Unary(unary) if unary.op == UnaryOp::TypeOf
+  )
+}
+
+fn is_literal(expr: &Expr) -> bool {
+  matches!(unwrap_parens(expr), Expr::Lit(_))
+}
+
 struct EqeqeqVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  mode: EqeqeqMode,
 }
 
 impl<'c, 'view> EqeqeqVisitor<'c, 'view> {
-  fn new(context: &'c mut Context<'view>) -> Self {
-    Self { context }
+  fn new(context: &'c mut Context<'view>, mode: EqeqeqMode) -> Self {
+    Self { context, mode }
+  }
+
+  fn is_allowed(&self, bin_expr: &BinExpr) -> bool {
+    let (left, right) = (&*bin_expr.left, &*bin_expr.right);
+    match self.mode {
+      EqeqeqMode::Always => false,
+      EqeqeqMode::Smart => {
+        is_null(left)
+          || is_null(right)
+          || is_typeof(left)
+          || is_typeof(right)
+          || (is_literal(left) && is_literal(right))
+      }
+      EqeqeqMode::AllowNull => is_null(left) || is_null(right),
+    }
+  }
+
+  /// Returns the fix of the comparison if both operands are known to be of
+  /// the same type, e.g. `typeof a == "string"`, in which case the strict
+  /// equality operator behaves the same.
+  fn fix(&self, bin_expr: &BinExpr, fix: EqeqeqFix) -> Option<LintFix> {
+    let left_type = known_type(&bin_expr.left)?;
+    if known_type(&bin_expr.right)? != left_type {
+      return None;
+    }
+    let (expected, replacement) = if bin_expr.op == BinaryOp::EqEq {
+      (BinOpToken::EqEq, "===")
+    } else {
+      (BinOpToken::NotEq, "!==")
+    };
+    let operator = self
+      .context
+      .program()
+      .tokens()?
+      .get_tokens_in_range(bin_expr.left.span().hi, bin_expr.right.span().lo)
+      .iter()
+      .find(|token| token.token == Token::BinOp(expected))?;
+    Some(
+      self
+        .context
+        .create_fix(fix, vec![(operator.span, replacement.to_string())]),
+    )
   }
 }
 
@@ -85,15 +238,30 @@ impl<'c, 'view> Visit for EqeqeqVisitor<'c, 'view> {
   noop_visit_type!();
 
   fn visit_bin_expr(&mut self, bin_expr: &BinExpr, parent: &dyn Node) {
-    if matches!(bin_expr.op, BinaryOp::EqEq | BinaryOp::NotEq) {
-      let (message, hint) = if bin_expr.op == BinaryOp::EqEq {
-        (EqeqeqMessage::ExpectedEqual, EqeqeqHint::UseEqeqeq)
+    if matches!(bin_expr.op, BinaryOp::EqEq | BinaryOp::NotEq)
+      && !self.is_allowed(bin_expr)
+    {
+      let (message, hint, fix) = if bin_expr.op == BinaryOp::EqEq {
+        (
+          EqeqeqMessage::ExpectedEqual,
+          EqeqeqHint::UseEqeqeq,
+          EqeqeqFix::UseEqeqeq,
+        )
       } else {
-        (EqeqeqMessage::ExpectedNotEqual, EqeqeqHint::UseNoteqeq)
+        (
+          EqeqeqMessage::ExpectedNotEqual,
+          EqeqeqHint::UseNoteqeq,
+          EqeqeqFix::UseNoteqeq,
+        )
       };
-      self
-        .context
-        .add_diagnostic_with_hint(bin_expr.span, CODE, message, hint)
+      let fixes = self.fix(bin_expr, fix).into_iter().collect();
+      self.context.add_diagnostic_with_fixes(
+        bin_expr.span,
+        CODE,
+        message,
+        Some(hint.to_string()),
+        fixes,
+      );
     }
     swc_ecmascript::visit::visit_bin_expr(self, bin_expr, parent);
   }
@@ -103,6 +271,7 @@ impl<'c, 'view> Visit for EqeqeqVisitor<'c, 'view> {
 mod tests {
   use super::*;
   use crate::test_util::*;
+  use serde_json::json;
 
   #[test]
   fn eqeqeq_valid() {
@@ -158,4 +327,136 @@ b"#,
     assert_lint_err::<Eqeqeq>("(a + b)  !=  c;", 0);
     assert_lint_err::<Eqeqeq>("((1) )  ==  (2);", 0);
   }
+
+  #[test]
+  fn eqeqeq_options_valid() {
+    assert_lint_ok! {
+      Eqeqeq,
+      options: json!({ "mode": "smart" }),
+      "typeof a == 'number'",
+      "'string' != typeof a",
+      "a == null",
+      "null != a",
+      "'hello' != 'world'",
+      "2 == 3",
+    };
+
+    assert_lint_ok! {
+      Eqeqeq,
+      options: json!({ "mode": "allow-null" }),
+      "a == null",
+      "null != a",
+      "(a) == (null)",
+    };
+  }
+
+  #[test]
+  fn eqeqeq_options_invalid() {
+    assert_lint_err! {
+      Eqeqeq,
+      options: json!({ "mode": "smart" }),
+      "a == b": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+        }
+      ],
+      "a != 5": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedNotEqual,
+          hint: EqeqeqHint::UseNoteqeq,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      Eqeqeq,
+      options: json!({ "mode": "allow-null" }),
+      "typeof a == 'number'": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+          fix: (EqeqeqFix::UseEqeqeq, "typeof a === 'number'"),
+        }
+      ],
+      "a == undefined": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn eqeqeq_fix() {
+    assert_lint_err! {
+      Eqeqeq,
+      "typeof a == 'number'": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+          fix: (EqeqeqFix::UseEqeqeq, "typeof a === 'number'"),
+        }
+      ],
+      "`${a}` != typeof b": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedNotEqual,
+          hint: EqeqeqHint::UseNoteqeq,
+          fix: (EqeqeqFix::UseNoteqeq, "`${a}` !== typeof b"),
+        }
+      ],
+      "(typeof a) /* == */ == ('number')": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+          fix: (EqeqeqFix::UseEqeqeq, "(typeof a) /* == */ === ('number')"),
+        }
+      ],
+      "!a != true": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedNotEqual,
+          hint: EqeqeqHint::UseNoteqeq,
+          fix: (EqeqeqFix::UseNoteqeq, "!a !== true"),
+        }
+      ],
+      "-1 == 1": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+          fix: (EqeqeqFix::UseEqeqeq, "-1 === 1"),
+        }
+      ],
+      "a == 5": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+        }
+      ],
+      "1n == 1": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+        }
+      ],
+      "a == null": [
+        {
+          col: 0,
+          message: EqeqeqMessage::ExpectedEqual,
+          hint: EqeqeqHint::UseEqeqeq,
+        }
+      ],
+    };
+  }
 }
@@ -0,0 +1,531 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use std::collections::{BTreeSet, HashSet};
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrayLit, AssignPatProp, BindingIdent, CallExpr, ClassDecl, Expr,
+  ExprOrSuper, FnDecl, MemberExpr, Pat, Prop, VarDeclarator,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct ExhaustiveDeps;
+
+const CODE: &str = "exhaustive-deps";
+
+#[derive(Display)]
+enum ExhaustiveDepsMessage {
+  #[display(fmt = "`{}` has missing dependencies: {}", _0, _1)]
+  Missing(String, String),
+  #[display(fmt = "`{}` has unnecessary dependencies: {}", _0, _1)]
+  Unnecessary(String, String),
+}
+
+#[derive(Display)]
+enum ExhaustiveDepsHint {
+  #[display(fmt = "Update the dependencies to `{}`", _0)]
+  Update(String),
+}
+
+#[derive(Display)]
+enum ExhaustiveDepsFix {
+  #[display(fmt = "Update the dependencies")]
+  Update,
+}
+
+impl LintRule for ExhaustiveDeps {
+  fn new() -> Box<Self> {
+    Box::new(ExhaustiveDeps)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut collector = StableValueCollector {
+      stable: HashSet::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => collector.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => collector.visit_script(s, &DUMMY_NODE),
+    }
+    let mut visitor = ExhaustiveDepsVisitor {
+      context,
+      stable: collector.stable,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces that the dependencies of hooks list the values their callbacks use",
+      "exhaustive-deps"
+    )
+  }
+}
+
+/// Returns the name of the called hook as written, e.g. `React.useEffect`,
+/// along with the name of the hook itself.
+fn hook_name(callee: &ExprOrSuper) -> Option<(String, &str)> {
+  let expr = match callee {
+    ExprOrSuper::Expr(expr) => &**expr,
+    ExprOrSuper::Super(_) => return None,
+  };
+  match expr {
+    Expr::Ident(ident) => Some((ident.sym.to_string(), &*ident.sym)),
+    Expr::Member(MemberExpr {
+      obj: ExprOrSuper::Expr(obj),
+      prop,
+      computed: false,
+      ..
+    }) => match (&**obj, &**prop) {
+      (Expr::Ident(obj), Expr::Ident(prop)) => {
+        Some((format!("{}.{}", obj.sym, prop.sym), &*prop.sym))
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Returns the indices of the callback and of the dependencies of a hook.
+fn hook_args(name: &str) -> Option<(usize, usize)> {
+  match name {
+    "useEffect" | "useLayoutEffect" | "useInsertionEffect" | "useMemo"
+    | "useCallback" => Some((0, 1)),
+    "useImperativeHandle" => Some((1, 2)),
+    _ => None,
+  }
+}
+
+fn unwrap_parens(mut expr: &Expr) -> &Expr {
+  while let Expr::Paren(paren) = expr {
+    expr = &paren.expr;
+  }
+  expr
+}
+
+/// Returns the path of expressions like `a` or `a.b.c`, along with the
+/// identifier it starts with.
+fn member_path(expr: &Expr) -> Option<(String, Id)> {
+  match expr {
+    Expr::Ident(ident) => Some((ident.sym.to_string(), ident.to_id())),
+    Expr::Member(MemberExpr {
+      obj: ExprOrSuper::Expr(obj),
+      prop,
+      computed: false,
+      ..
+    }) => match &**prop {
+      Expr::Ident(prop) => {
+        let (path, id) = member_path(obj)?;
+        Some((format!("{}.{}", path, prop.sym), id))
+      }
+      _ => None,
+    },
+    // `a?.b` depends on the same values as `a.b`
+    Expr::OptChain(opt_chain) => member_path(&opt_chain.expr),
+    _ => None,
+  }
+}
+
+/// Returns `true` if `path` is `base` or one of its members.
+fn is_covered_by(path: &str, base: &str) -> bool {
+  path == base
+    || (path.starts_with(base) && path[base.len()..].starts_with('.'))
+}
+
+fn format_paths<'a>(paths: impl Iterator<Item = &'a str>) -> String {
+  paths
+    .map(|path| format!("`{}`", path))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Collects the values that are the same on every render, i.e. the setters
+/// of `useState()`, the dispatchers of `useReducer()` and refs.
+struct StableValueCollector {
+  stable: HashSet<Id>,
+}
+
+impl Visit for StableValueCollector {
+  noop_visit_type!();
+
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    _: &dyn Node,
+  ) {
+    if let Some(Expr::Call(call_expr)) =
+      var_declarator.init.as_ref().map(|init| unwrap_parens(init))
+    {
+      match (hook_name(&call_expr.callee), &var_declarator.name) {
+        (Some((_, "useRef")), Pat::Ident(binding)) => {
+          self.stable.insert(binding.id.to_id());
+        }
+        (Some((_, "useState")), Pat::Array(array))
+        | (Some((_, "useReducer")), Pat::Array(array))
+        | (Some((_, "useTransition")), Pat::Array(array)) => {
+          if let Some(Some(Pat::Ident(binding))) = array.elems.get(1) {
+            self.stable.insert(binding.id.to_id());
+          }
+        }
+        _ => {}
+      }
+    }
+    var_declarator.visit_children_with(self);
+  }
+}
+
+/// Collects the values a callback uses, and the ones declared in it.
+struct ReferenceCollector {
+  references: Vec<(String, Id)>,
+  declared: HashSet<Id>,
+}
+
+impl Visit for ReferenceCollector {
+  noop_visit_type!();
+
+  fn visit_expr(&mut self, expr: &Expr, _: &dyn Node) {
+    match member_path(expr) {
+      Some(reference) => self.references.push(reference),
+      None => expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    member_expr.obj.visit_with(member_expr, self);
+    if member_expr.computed {
+      member_expr.prop.visit_with(member_expr, self);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    // Calling `a.b()` passes `a` as `this`, so it depends on `a`
+    match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member_expr) if !member_expr.computed => {
+          member_expr.obj.visit_with(member_expr, self);
+        }
+        _ => callee.visit_with(call_expr, self),
+      },
+      ExprOrSuper::Super(_) => {}
+    }
+    call_expr.args.visit_with(call_expr, self);
+  }
+
+  fn visit_prop(&mut self, prop: &Prop, _: &dyn Node) {
+    if let Prop::Shorthand(ident) = prop {
+      self.references.push((ident.sym.to_string(), ident.to_id()));
+    } else {
+      prop.visit_children_with(self);
+    }
+  }
+
+  fn visit_binding_ident(
+    &mut self,
+    binding_ident: &BindingIdent,
+    _: &dyn Node,
+  ) {
+    self.declared.insert(binding_ident.id.to_id());
+    binding_ident.visit_children_with(self);
+  }
+
+  fn visit_assign_pat_prop(
+    &mut self,
+    assign_pat_prop: &AssignPatProp,
+    _: &dyn Node,
+  ) {
+    self.declared.insert(assign_pat_prop.key.to_id());
+    assign_pat_prop.value.visit_with(assign_pat_prop, self);
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    self.declared.insert(fn_decl.ident.to_id());
+    fn_decl.function.visit_with(fn_decl, self);
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, _: &dyn Node) {
+    self.declared.insert(class_decl.ident.to_id());
+    class_decl.class.visit_with(class_decl, self);
+  }
+}
+
+struct ExhaustiveDepsVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  stable: HashSet<Id>,
+}
+
+impl<'c, 'view> ExhaustiveDepsVisitor<'c, 'view> {
+  /// Returns `true` for values declared in the component or hook, which may
+  /// change between renders.
+  fn is_reactive(&self, id: &Id) -> bool {
+    matches!(self.context.scope().var(id), Some(var) if !var.path().is_empty())
+  }
+
+  fn check(&mut self, call_expr: &CallExpr) {
+    let (name, hook) = match hook_name(&call_expr.callee) {
+      Some(name) => name,
+      None => return,
+    };
+    let (callback_index, deps_index) = match hook_args(hook) {
+      Some(indices) => indices,
+      None => return,
+    };
+    let callback = match call_expr.args.get(callback_index) {
+      Some(arg) if arg.spread.is_none() => unwrap_parens(&arg.expr),
+      _ => return,
+    };
+    let deps: &ArrayLit = match call_expr.args.get(deps_index) {
+      Some(arg) if arg.spread.is_none() => match unwrap_parens(&arg.expr) {
+        Expr::Array(array) => array,
+        _ => return,
+      },
+      _ => return,
+    };
+    if !matches!(callback, Expr::Arrow(_) | Expr::Fn(_)) {
+      return;
+    }
+
+    // Dependencies that aren't simple paths can't be checked
+    let mut dependencies = Vec::new();
+    for elem in &deps.elems {
+      match elem {
+        Some(elem) if elem.spread.is_none() => match member_path(&elem.expr) {
+          Some((path, id)) => dependencies.push((path, id, elem.expr.span())),
+          None => return,
+        },
+        _ => return,
+      }
+    }
+
+    let mut collector = ReferenceCollector {
+      references: Vec::new(),
+      declared: HashSet::new(),
+    };
+    callback.visit_with(call_expr, &mut collector);
+    let ReferenceCollector {
+      references,
+      declared,
+    } = collector;
+    let references: Vec<String> = references
+      .into_iter()
+      .filter(|(_, id)| !declared.contains(id) && self.is_reactive(id))
+      .filter(|(_, id)| !self.stable.contains(id))
+      .map(|(path, _)| path)
+      .collect();
+
+    // Effects may list values they don't use to rerun when those change, but
+    // memoized values and callbacks don't need them
+    let must_be_used = hook == "useMemo" || hook == "useCallback";
+    let mut kept = Vec::new();
+    let mut unnecessary = Vec::new();
+    let mut seen = HashSet::new();
+    for (path, id, span) in &dependencies {
+      let is_used = references.iter().any(|reference| {
+        is_covered_by(reference, path) || is_covered_by(path, reference)
+      });
+      if !seen.insert(path.as_str())
+        || !self.is_reactive(id)
+        || (must_be_used && !is_used && !self.stable.contains(id))
+      {
+        unnecessary.push(path.as_str());
+      } else {
+        kept.push((path.as_str(), *span));
+      }
+    }
+
+    let mut missing: BTreeSet<&str> = references
+      .iter()
+      .map(|reference| reference.as_str())
+      .filter(|reference| {
+        !kept.iter().any(|(path, _)| is_covered_by(reference, path))
+      })
+      .collect();
+    // `a.b` is covered by `a` when both are missing
+    let covering: Vec<&str> = missing.iter().copied().collect();
+    missing.retain(|reference| {
+      !covering
+        .iter()
+        .any(|base| base != reference && is_covered_by(reference, base))
+    });
+
+    if missing.is_empty() && unnecessary.is_empty() {
+      return;
+    }
+
+    let source_map = self.context.source_map();
+    // Dependencies covered by a missing one, like `a.b` when `a` is missing,
+    // are replaced by it
+    let mut updated: Vec<String> = kept
+      .iter()
+      .filter(|(path, _)| !missing.iter().any(|base| is_covered_by(path, base)))
+      .map(|(path, span)| {
+        source_map
+          .span_to_snippet(*span)
+          .unwrap_or_else(|_| path.to_string())
+      })
+      .collect();
+    updated.extend(missing.iter().map(|path| path.to_string()));
+    let updated = format!("[{}]", updated.join(", "));
+
+    let message = if missing.is_empty() {
+      ExhaustiveDepsMessage::Unnecessary(
+        name,
+        format_paths(unnecessary.into_iter()),
+      )
+    } else {
+      ExhaustiveDepsMessage::Missing(name, format_paths(missing.into_iter()))
+    };
+    let span = deps.span;
+    let has_comments = self.context.all_comments().any(|comment| {
+      span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+    });
+    let fixes = if has_comments {
+      vec![]
+    } else {
+      vec![self
+        .context
+        .create_fix(ExhaustiveDepsFix::Update, vec![(span, updated.clone())])]
+    };
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      message,
+      Some(ExhaustiveDepsHint::Update(updated).to_string()),
+      fixes,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for ExhaustiveDepsVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    self.check(call_expr);
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn missing(hook: &str, paths: &str) -> String {
+    ExhaustiveDepsMessage::Missing(hook.to_string(), paths.to_string())
+      .to_string()
+  }
+
+  fn unnecessary(hook: &str, paths: &str) -> String {
+    ExhaustiveDepsMessage::Unnecessary(hook.to_string(), paths.to_string())
+      .to_string()
+  }
+
+  #[test]
+  fn exhaustive_deps_valid() {
+    assert_lint_ok! {
+      ExhaustiveDeps,
+      "function Counter({ step }) { const [count, setCount] = useState(0); useEffect(() => { setCount(count + step); }, [count, step]); }",
+      "function Timer() { const ref = useRef(); useEffect(() => { ref.current = setInterval(tick, 1000); }, []); }",
+      "function Search({ query }) { useEffect(() => { const url = `/search?q=${query}`; fetch(url); }, [query]); }",
+      "function Profile(props) { const name = useMemo(() => props.user.name, [props.user]); }",
+      "function Profile(props) { useEffect(() => { props.onLoad(); }, [props]); }",
+      "function Page({ id }) { useEffect(() => { load(id); }); }",
+      "function Page({ id, version }) { useEffect(() => { load(id); }, [id, version]); }",
+      "const API = '/api'; function Page() { useEffect(() => { fetch(API); }, []); }",
+      "function Form({ value }) { const onChange = useCallback((event) => { setValue(event.target.value); }, []); }",
+      "function Input(props, ref) { React.useImperativeHandle(ref, () => ({ focus: props.focus }), [props.focus]); }",
+      "function List({ items }) { const sorted = useMemo(() => [...items].sort(), items); }",
+    };
+  }
+
+  #[test]
+  fn exhaustive_deps_invalid() {
+    assert_lint_err! {
+      ExhaustiveDeps,
+      "function Counter({ step }) { const [count, setCount] = useState(0); useEffect(() => { setCount(count + step); }, [count]); }": [
+        {
+          col: 113,
+          message: missing("useEffect", "`step`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[count, step]"),
+          fix: (
+            ExhaustiveDepsFix::Update,
+            "function Counter({ step }) { const [count, setCount] = useState(0); useEffect(() => { setCount(count + step); }, [count, step]); }"
+          ),
+        }
+      ],
+      "function Profile(props) { const label = React.useMemo(() => props.user.name + props.user.id, []); }": [
+        {
+          col: 93,
+          message: missing("React.useMemo", "`props.user.id`, `props.user.name`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[props.user.id, props.user.name]"),
+          fix: (
+            ExhaustiveDepsFix::Update,
+            "function Profile(props) { const label = React.useMemo(() => props.user.name + props.user.id, [props.user.id, props.user.name]); }"
+          ),
+        }
+      ],
+      "function Profile(props) { useEffect(() => { props.onLoad(props.user.id); }, [props.user]); }": [
+        {
+          col: 76,
+          message: missing("useEffect", "`props`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[props]"),
+          fix: (
+            ExhaustiveDepsFix::Update,
+            "function Profile(props) { useEffect(() => { props.onLoad(props.user.id); }, [props]); }"
+          ),
+        }
+      ],
+      "const API = '/api'; function Page({ id }) { const load = useCallback(() => fetch(API + id), [API, id, count]); const [count] = useState(0); }": [
+        {
+          col: 92,
+          message: unnecessary("useCallback", "`API`, `count`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[id]"),
+          fix: (
+            ExhaustiveDepsFix::Update,
+            "const API = '/api'; function Page({ id }) { const load = useCallback(() => fetch(API + id), [id]); const [count] = useState(0); }"
+          ),
+        }
+      ],
+      "function Page({ id }) { useEffect(() => { load(id); }, [id, id]); }": [
+        {
+          col: 55,
+          message: unnecessary("useEffect", "`id`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[id]"),
+          fix: (
+            ExhaustiveDepsFix::Update,
+            "function Page({ id }) { useEffect(() => { load(id); }, [id]); }"
+          ),
+        }
+      ],
+      "function Page({ id }) { useEffect(() => { load(id); }, [/* none */]); }": [
+        {
+          col: 55,
+          message: missing("useEffect", "`id`"),
+          hint: variant!(ExhaustiveDepsHint, Update, "[id]"),
+        }
+      ],
+    };
+  }
+}
@@ -45,6 +45,10 @@ impl LintRule for GetterReturn {
     CODE
   }
 
+  fn needs_cfg(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -59,37 +63,10 @@ impl LintRule for GetterReturn {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Requires all property getter functions to return a value
-
-Getter functions return the value of a property.  If the function returns no
-value then this contract is broken.
-
-### Invalid:
-```typescript
-let foo = { 
-  get bar() {}
-};
-
-class Person { 
-  get name() {}
-}
-```
-    
-### Valid:
-```typescript
-let foo = { 
-  get bar() { 
-    return true; 
-  }
-};
-
-class Person { 
-  get name() { 
-    return "alice"; 
-  }
-}
-```
-"#
+    rule_docs!(
+      "Requires all property getter functions to return a value",
+      "getter-return"
+    )
   }
 }
 
@@ -0,0 +1,298 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::jsx_attr_name;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{Span, SyntaxContext};
+use swc_ecmascript::ast::{
+  Expr, JSXAttr, JSXAttrName, JSXAttrValue, JSXExpr, Lit,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct JsxBooleanValue {
+  options: JsxBooleanValueOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsxBooleanValueOptions {
+  /// Whether `={true}` is omitted from boolean attributes or always set.
+  pub mode: JsxBooleanValueMode,
+  /// Names of the attributes using the opposite style.
+  pub exceptions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JsxBooleanValueMode {
+  /// `<Foo disabled />`.
+  Never,
+  /// `<Foo disabled={true} />`.
+  Always,
+}
+
+impl Default for JsxBooleanValueOptions {
+  fn default() -> Self {
+    Self {
+      mode: JsxBooleanValueMode::Never,
+      exceptions: Vec::new(),
+    }
+  }
+}
+
+const CODE: &str = "jsx-boolean-value";
+
+#[derive(Display)]
+enum JsxBooleanValueMessage {
+  #[display(fmt = "Value of boolean attribute `{}` must be omitted", _0)]
+  Omit(String),
+  #[display(fmt = "Value of boolean attribute `{}` must be set", _0)]
+  Set(String),
+}
+
+#[derive(Display)]
+enum JsxBooleanValueHint {
+  #[display(fmt = "Remove `={{true}}`")]
+  Remove,
+  #[display(fmt = "Add `={{true}}`")]
+  Add,
+}
+
+#[derive(Display)]
+enum JsxBooleanValueFix {
+  #[display(fmt = "Remove `={{true}}`")]
+  Remove,
+  #[display(fmt = "Add `={{true}}`")]
+  Add,
+}
+
+impl LintRule for JsxBooleanValue {
+  fn new() -> Box<Self> {
+    Box::new(JsxBooleanValue {
+      options: JsxBooleanValueOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = JsxBooleanValueVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "mode": {
+          "description": "Whether `={true}` is omitted from boolean attributes or always set.",
+          "enum": ["never", "always"],
+          "default": "never"
+        },
+        "exceptions": {
+          "description": "Names of the attributes using the opposite style.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a consistent style for boolean attributes in JSX",
+      "jsx-boolean-value"
+    )
+  }
+}
+
+/// Returns `true` for `={true}`.
+fn is_true(value: &JSXAttrValue) -> bool {
+  match value {
+    JSXAttrValue::JSXExprContainer(container) => matches!(
+      &container.expr,
+      JSXExpr::Expr(expr) if matches!(&**expr, Expr::Lit(Lit::Bool(b)) if b.value)
+    ),
+    _ => false,
+  }
+}
+
+struct JsxBooleanValueVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c JsxBooleanValueOptions,
+}
+
+impl<'c, 'view> Visit for JsxBooleanValueVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_jsx_attr(&mut self, jsx_attr: &JSXAttr, _: &dyn Node) {
+    let name = jsx_attr_name(&jsx_attr.name);
+    let omit = (self.options.mode == JsxBooleanValueMode::Never)
+      != self.options.exceptions.contains(&name);
+
+    match &jsx_attr.value {
+      Some(value) if omit && is_true(value) => {
+        let name_hi = match &jsx_attr.name {
+          JSXAttrName::Ident(ident) => ident.span.hi,
+          JSXAttrName::JSXNamespacedName(name) => name.name.span.hi,
+        };
+        let span = Span::new(name_hi, jsx_attr.span.hi, SyntaxContext::empty());
+        let has_comments = self.context.all_comments().any(|comment| {
+          span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+        });
+        let fixes = if has_comments {
+          vec![]
+        } else {
+          vec![self.context.create_fix(
+            JsxBooleanValueFix::Remove,
+            vec![(span, String::new())],
+          )]
+        };
+        self.context.add_diagnostic_with_fixes(
+          jsx_attr.span,
+          CODE,
+          JsxBooleanValueMessage::Omit(name),
+          Some(JsxBooleanValueHint::Remove.to_string()),
+          fixes,
+        );
+      }
+      None if !omit => {
+        let span =
+          Span::new(jsx_attr.span.hi, jsx_attr.span.hi, SyntaxContext::empty());
+        let fix = self
+          .context
+          .create_fix(JsxBooleanValueFix::Add, vec![(span, "={true}".into())]);
+        self.context.add_diagnostic_with_fixes(
+          jsx_attr.span,
+          CODE,
+          JsxBooleanValueMessage::Set(name),
+          Some(JsxBooleanValueHint::Add.to_string()),
+          vec![fix],
+        );
+      }
+      _ => {}
+    }
+    jsx_attr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn jsx_boolean_value_valid() {
+    assert_lint_ok! {
+      JsxBooleanValue,
+      { src: "<input disabled />", filename: "foo.tsx" },
+      { src: "<input disabled={false} />", filename: "foo.tsx" },
+      { src: "<input disabled={isDisabled} />", filename: "foo.tsx" },
+      { src: "<input value=\"true\" />", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      JsxBooleanValue,
+      options: json!({ "mode": "always" }),
+      { src: "<input disabled={true} />", filename: "foo.tsx" },
+      { src: "<input disabled={false} value=\"\" />", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      JsxBooleanValue,
+      options: json!({ "exceptions": ["checked"] }),
+      { src: "<input disabled checked={true} />", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn jsx_boolean_value_invalid() {
+    assert_lint_err! {
+      JsxBooleanValue,
+      "<input disabled={true} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 7,
+            message: variant!(JsxBooleanValueMessage, Omit, "disabled"),
+            hint: JsxBooleanValueHint::Remove,
+            fix: (JsxBooleanValueFix::Remove, "<input disabled />"),
+          }
+        ],
+      },
+      "<svg xlink:hidden={true} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 5,
+            message: variant!(JsxBooleanValueMessage, Omit, "xlink:hidden"),
+            hint: JsxBooleanValueHint::Remove,
+            fix: (JsxBooleanValueFix::Remove, "<svg xlink:hidden />"),
+          }
+        ],
+      },
+      "<input disabled={/* always */ true} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 7,
+            message: variant!(JsxBooleanValueMessage, Omit, "disabled"),
+            hint: JsxBooleanValueHint::Remove,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      JsxBooleanValue,
+      options: json!({ "mode": "always", "exceptions": ["checked"] }),
+      "<input disabled checked={true} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 7,
+            message: variant!(JsxBooleanValueMessage, Set, "disabled"),
+            hint: JsxBooleanValueHint::Add,
+            fix: (JsxBooleanValueFix::Add, "<input disabled={true} checked={true} />"),
+          },
+          {
+            col: 16,
+            message: variant!(JsxBooleanValueMessage, Omit, "checked"),
+            hint: JsxBooleanValueHint::Remove,
+            fix: (JsxBooleanValueFix::Remove, "<input disabled checked />"),
+          }
+        ],
+      },
+    };
+  }
+}
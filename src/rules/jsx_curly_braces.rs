@@ -0,0 +1,416 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Expr, JSXAttr, JSXAttrValue, JSXElementChild, JSXExpr, JSXExprContainer, Lit,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct JsxCurlyBraces {
+  options: JsxCurlyBracesOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsxCurlyBracesOptions {
+  /// Whether string values of attributes use curly braces.
+  pub props: JsxCurlyBracesMode,
+  /// Whether string children of elements use curly braces.
+  pub children: JsxCurlyBracesMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JsxCurlyBracesMode {
+  /// `<Foo bar="baz">text</Foo>`.
+  Never,
+  /// `<Foo bar={"baz"}>{"text"}</Foo>`.
+  Always,
+  /// Either style.
+  Ignore,
+}
+
+impl Default for JsxCurlyBracesOptions {
+  fn default() -> Self {
+    Self {
+      props: JsxCurlyBracesMode::Never,
+      children: JsxCurlyBracesMode::Never,
+    }
+  }
+}
+
+const CODE: &str = "jsx-curly-braces";
+
+#[derive(Display)]
+enum JsxCurlyBracesMessage {
+  #[display(fmt = "Unnecessary curly braces around a string")]
+  Unnecessary,
+  #[display(fmt = "Missing curly braces around a string")]
+  Missing,
+}
+
+#[derive(Display)]
+enum JsxCurlyBracesHint {
+  #[display(fmt = "Remove the curly braces")]
+  Remove,
+  #[display(fmt = "Wrap the string in curly braces")]
+  Add,
+}
+
+#[derive(Display)]
+enum JsxCurlyBracesFix {
+  #[display(fmt = "Remove the curly braces")]
+  Remove,
+  #[display(fmt = "Add curly braces")]
+  Add,
+}
+
+impl LintRule for JsxCurlyBraces {
+  fn new() -> Box<Self> {
+    Box::new(JsxCurlyBraces {
+      options: JsxCurlyBracesOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = JsxCurlyBracesVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "props": {
+          "description": "Whether string values of attributes use curly braces.",
+          "enum": ["never", "always", "ignore"],
+          "default": "never"
+        },
+        "children": {
+          "description": "Whether string children of elements use curly braces.",
+          "enum": ["never", "always", "ignore"],
+          "default": "never"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a consistent use of curly braces around strings in JSX",
+      "jsx-curly-braces"
+    )
+  }
+}
+
+/// Returns the quote to wrap `text` in, if any.
+fn quote_for(text: &str) -> Option<char> {
+  if !text.contains('"') {
+    Some('"')
+  } else if !text.contains('\'') {
+    Some('\'')
+  } else {
+    None
+  }
+}
+
+struct JsxCurlyBracesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c JsxCurlyBracesOptions,
+}
+
+impl<'c, 'view> JsxCurlyBracesVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  /// Returns the text of the string in `{"text"}` or `` {`text`} ``, unless it
+  /// has escapes, which mean something else outside of JavaScript strings.
+  fn string_content(&self, container: &JSXExprContainer) -> Option<String> {
+    let span = match &container.expr {
+      JSXExpr::Expr(expr) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => s.span,
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.span,
+        _ => return None,
+      },
+      JSXExpr::JSXEmptyExpr(_) => return None,
+    };
+    let text = self.snippet(span)?;
+    let content = &text[1..text.len() - 1];
+    if content.contains('\\') {
+      None
+    } else {
+      Some(content.to_string())
+    }
+  }
+
+  fn report(
+    &mut self,
+    span: Span,
+    message: JsxCurlyBracesMessage,
+    hint: JsxCurlyBracesHint,
+    fix: Option<(JsxCurlyBracesFix, String)>,
+  ) {
+    let has_comments = self.context.all_comments().any(|comment| {
+      span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+    });
+    let fixes = match fix {
+      Some((fix, text)) if !has_comments => {
+        vec![self.context.create_fix(fix, vec![(span, text)])]
+      }
+      _ => vec![],
+    };
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      message,
+      Some(hint.to_string()),
+      fixes,
+    );
+  }
+
+  fn check_attr_value(&mut self, value: &JSXAttrValue) {
+    match (self.options.props, value) {
+      (
+        JsxCurlyBracesMode::Never,
+        JSXAttrValue::JSXExprContainer(container),
+      ) => {
+        if let Some(content) = self.string_content(container) {
+          if let Some(quote) = quote_for(&content) {
+            let fixed = format!("{}{}{}", quote, content, quote);
+            self.report(
+              container.span,
+              JsxCurlyBracesMessage::Unnecessary,
+              JsxCurlyBracesHint::Remove,
+              Some((JsxCurlyBracesFix::Remove, fixed)),
+            );
+          }
+        }
+      }
+      (JsxCurlyBracesMode::Always, JSXAttrValue::Lit(Lit::Str(s))) => {
+        // Backslashes are part of the text in attributes, but escapes in
+        // JavaScript strings
+        let fix = self
+          .snippet(s.span)
+          .filter(|text| !text.contains('\\'))
+          .map(|text| (JsxCurlyBracesFix::Add, format!("{{{}}}", text)));
+        self.report(
+          s.span,
+          JsxCurlyBracesMessage::Missing,
+          JsxCurlyBracesHint::Add,
+          fix,
+        );
+      }
+      _ => {}
+    }
+  }
+
+  fn check_child(&mut self, child: &JSXElementChild) {
+    match (self.options.children, child) {
+      (
+        JsxCurlyBracesMode::Never,
+        JSXElementChild::JSXExprContainer(container),
+      ) => {
+        // Whitespace around the text and special characters mean something
+        // else in JSX text
+        let content = self.string_content(container).filter(|content| {
+          !content.trim().is_empty()
+            && content.trim() == content
+            && !content.contains(&['{', '}', '<', '>', '&', '\n', '\r'][..])
+        });
+        if let Some(content) = content {
+          self.report(
+            container.span,
+            JsxCurlyBracesMessage::Unnecessary,
+            JsxCurlyBracesHint::Remove,
+            Some((JsxCurlyBracesFix::Remove, content)),
+          );
+        }
+      }
+      (JsxCurlyBracesMode::Always, JSXElementChild::JSXText(text)) => {
+        let raw = match self.snippet(text.span) {
+          Some(raw) => raw,
+          None => return,
+        };
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+          return;
+        }
+        let fix = quote_for(trimmed)
+          .filter(|_| !trimmed.contains(&['&', '\\', '\n', '\r'][..]))
+          .map(|quote| {
+            let start = raw.len() - raw.trim_start().len();
+            let end = raw.trim_end().len();
+            let fixed = format!(
+              "{}{{{}{}{}}}{}",
+              &raw[..start],
+              quote,
+              trimmed,
+              quote,
+              &raw[end..]
+            );
+            (JsxCurlyBracesFix::Add, fixed)
+          });
+        self.report(
+          text.span,
+          JsxCurlyBracesMessage::Missing,
+          JsxCurlyBracesHint::Add,
+          fix,
+        );
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<'c, 'view> Visit for JsxCurlyBracesVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_jsx_attr(&mut self, jsx_attr: &JSXAttr, _: &dyn Node) {
+    if let Some(value) = &jsx_attr.value {
+      self.check_attr_value(value);
+    }
+    jsx_attr.visit_children_with(self);
+  }
+
+  fn visit_jsx_element_child(&mut self, child: &JSXElementChild, _: &dyn Node) {
+    self.check_child(child);
+    child.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn jsx_curly_braces_valid() {
+    assert_lint_ok! {
+      JsxCurlyBraces,
+      { src: "<a href=\"/home\">Home</a>", filename: "foo.tsx" },
+      { src: "<a href={url}>{label}</a>", filename: "foo.tsx" },
+      { src: "<a title={\"a \\\"quoted\\\" title\"}>{\" \"}</a>", filename: "foo.tsx" },
+      { src: "<p>{\"a < b\"}{\"Tom & Jerry\"}{` ${name}`}</p>", filename: "foo.tsx" },
+      { src: "<p title={`It's \"quoted\"`}>{\"text \"}</p>", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      JsxCurlyBraces,
+      options: json!({ "props": "always", "children": "always" }),
+      { src: "<a href={\"/home\"}>{\"Home\"}</a>", filename: "foo.tsx" },
+      { src: "<a href={url}>\n  {label}\n</a>", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      JsxCurlyBraces,
+      options: json!({ "props": "ignore", "children": "ignore" }),
+      { src: "<a href={\"/home\"} title=\"Home\">{\"Home\"} page</a>", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn jsx_curly_braces_invalid() {
+    assert_lint_err! {
+      JsxCurlyBraces,
+      "<a href={\"/home\"} title={'Say \"hi\"'}>{`Home`}</a>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 8,
+            message: JsxCurlyBracesMessage::Unnecessary,
+            hint: JsxCurlyBracesHint::Remove,
+            fix: (JsxCurlyBracesFix::Remove, "<a href=\"/home\" title={'Say \"hi\"'}>{`Home`}</a>"),
+          },
+          {
+            col: 24,
+            message: JsxCurlyBracesMessage::Unnecessary,
+            hint: JsxCurlyBracesHint::Remove,
+            fix: (JsxCurlyBracesFix::Remove, "<a href={\"/home\"} title='Say \"hi\"'>{`Home`}</a>"),
+          },
+          {
+            col: 37,
+            message: JsxCurlyBracesMessage::Unnecessary,
+            hint: JsxCurlyBracesHint::Remove,
+            fix: (JsxCurlyBracesFix::Remove, "<a href={\"/home\"} title={'Say \"hi\"'}>Home</a>"),
+          }
+        ],
+      },
+      "<p>{/* greeting */ \"Hello\"}</p>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 3,
+            message: JsxCurlyBracesMessage::Unnecessary,
+            hint: JsxCurlyBracesHint::Remove,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      JsxCurlyBraces,
+      options: json!({ "props": "always", "children": "always" }),
+      "<a href=\"/home\">\n  Home page\n</a>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 8,
+            message: JsxCurlyBracesMessage::Missing,
+            hint: JsxCurlyBracesHint::Add,
+            fix: (JsxCurlyBracesFix::Add, "<a href={\"/home\"}>\n  Home page\n</a>"),
+          },
+          {
+            col: 16,
+            message: JsxCurlyBracesMessage::Missing,
+            hint: JsxCurlyBracesHint::Add,
+            fix: (JsxCurlyBracesFix::Add, "<a href=\"/home\">\n  {\"Home page\"}\n</a>"),
+          }
+        ],
+      },
+      "<p>Tom &amp; Jerry</p>": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 3,
+            message: JsxCurlyBracesMessage::Missing,
+            hint: JsxCurlyBracesHint::Add,
+          }
+        ],
+      },
+    };
+  }
+}
@@ -0,0 +1,325 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Decl, DefaultDecl, ExportSpecifier, Ident, Module, ModuleDecl, ModuleItem,
+  VarDeclKind,
+};
+use swc_ecmascript::utils::find_ids;
+
+pub struct MaxExportCount {
+  options: MaxExportCountOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MaxExportCountOptions {
+  /// The maximum number of symbols a module may export.
+  pub max: usize,
+  /// The maximum number of constants a module may export alongside classes.
+  pub max_constants_with_classes: usize,
+}
+
+impl Default for MaxExportCountOptions {
+  fn default() -> Self {
+    Self {
+      max: 20,
+      max_constants_with_classes: 5,
+    }
+  }
+}
+
+const CODE: &str = "max-export-count";
+
+#[derive(Display)]
+enum MaxExportCountMessage {
+  #[display(
+    fmt = "This module exports {} symbols. Maximum allowed is {}.",
+    _0,
+    _1
+  )]
+  TooManyExports(usize, usize),
+  #[display(
+    fmt = "This module exports {} constants alongside classes. Maximum allowed is {}.",
+    _0,
+    _1
+  )]
+  TooManyConstantsWithClasses(usize, usize),
+}
+
+#[derive(Display)]
+enum MaxExportCountHint {
+  #[display(fmt = "Consider splitting this module into smaller modules")]
+  SplitModule,
+  #[display(
+    fmt = "Consider moving the constants into a module of their own, or into the classes as static members"
+  )]
+  MoveConstants,
+}
+
+impl LintRule for MaxExportCount {
+  fn new() -> Box<Self> {
+    Box::new(MaxExportCount {
+      options: MaxExportCountOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let module = match program {
+      ProgramRef::Module(module) => module,
+      ProgramRef::Script(_) => return,
+    };
+
+    let exports = collect_exports(module);
+
+    if exports.len() > self.options.max {
+      context.add_diagnostic_with_hint(
+        exports[self.options.max].span,
+        CODE,
+        MaxExportCountMessage::TooManyExports(exports.len(), self.options.max),
+        MaxExportCountHint::SplitModule,
+      );
+    }
+
+    let has_classes = exports.iter().any(|e| e.kind == ExportKind::Class);
+    let constants: Vec<&Export> = exports
+      .iter()
+      .filter(|e| e.kind == ExportKind::Constant)
+      .collect();
+    let max_constants = self.options.max_constants_with_classes;
+    if has_classes && constants.len() > max_constants {
+      context.add_diagnostic_with_hint(
+        constants[max_constants].span,
+        CODE,
+        MaxExportCountMessage::TooManyConstantsWithClasses(
+          constants.len(),
+          max_constants,
+        ),
+        MaxExportCountHint::MoveConstants,
+      );
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "max": {
+          "description": "The maximum number of symbols a module may export.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 20
+        },
+        "maxConstantsWithClasses": {
+          "description": "The maximum number of constants a module may export alongside classes.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 5
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Limits the number of symbols a module exports",
+      "max-export-count"
+    )
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportKind {
+  Class,
+  Constant,
+  Other,
+}
+
+struct Export {
+  kind: ExportKind,
+  span: Span,
+}
+
+/// Collects the symbols the module exports in source order.
+fn collect_exports(module: &Module) -> Vec<Export> {
+  let mut exports = Vec::new();
+  let mut push = |kind, span| exports.push(Export { kind, span });
+
+  for item in &module.body {
+    let module_decl = match item {
+      ModuleItem::ModuleDecl(module_decl) => module_decl,
+      ModuleItem::Stmt(_) => continue,
+    };
+
+    match module_decl {
+      ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
+        Decl::Class(class_decl) => {
+          push(ExportKind::Class, class_decl.ident.span)
+        }
+        Decl::Var(var_decl) => {
+          let kind = if var_decl.kind == VarDeclKind::Const {
+            ExportKind::Constant
+          } else {
+            ExportKind::Other
+          };
+          for decl in &var_decl.decls {
+            let ids: Vec<Ident> = find_ids(&decl.name);
+            for id in ids {
+              push(kind, id.span);
+            }
+          }
+        }
+        Decl::Fn(fn_decl) => push(ExportKind::Other, fn_decl.ident.span),
+        Decl::TsInterface(interface) => {
+          push(ExportKind::Other, interface.id.span)
+        }
+        Decl::TsTypeAlias(alias) => push(ExportKind::Other, alias.id.span),
+        Decl::TsEnum(enum_decl) => push(ExportKind::Other, enum_decl.id.span),
+        Decl::TsModule(module_decl) => {
+          push(ExportKind::Other, module_decl.span)
+        }
+      },
+      ModuleDecl::ExportNamed(named_export) => {
+        for specifier in &named_export.specifiers {
+          push(ExportKind::Other, specifier_span(specifier));
+        }
+      }
+      ModuleDecl::ExportDefaultDecl(default_decl) => {
+        let kind = if matches!(default_decl.decl, DefaultDecl::Class(_)) {
+          ExportKind::Class
+        } else {
+          ExportKind::Other
+        };
+        push(kind, default_decl.span);
+      }
+      ModuleDecl::ExportDefaultExpr(default_expr) => {
+        push(ExportKind::Other, default_expr.span)
+      }
+      _ => {}
+    }
+  }
+
+  exports
+}
+
+fn specifier_span(specifier: &ExportSpecifier) -> Span {
+  match specifier {
+    ExportSpecifier::Namespace(namespace) => namespace.span,
+    ExportSpecifier::Default(default) => default.exported.span,
+    ExportSpecifier::Named(named) => named.span,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn max_export_count_valid() {
+    assert_lint_ok! {
+      MaxExportCount,
+      "export const a = 1;",
+      "export class A {} export const b = 1, c = 2, d = 3, e = 4, f = 5;",
+      "export let a = 1, b = 2, c = 3, d = 4, e = 5, f = 6; export class G {}",
+      "export * from './a.ts'; export * from './b.ts';",
+      "const a = 1; const b = 2;",
+    };
+
+    assert_lint_ok! {
+      MaxExportCount,
+      options: json!({ "max": 3, "maxConstantsWithClasses": 0 }),
+      "export const a = 1; export const b = 2; export const c = 3;",
+      "export class A {} export function b() {} export { c } from './c.ts';",
+      "export default class {} export interface B {} export type C = string;",
+    };
+  }
+
+  #[test]
+  fn max_export_count_invalid() {
+    assert_lint_err! {
+      MaxExportCount,
+      options: json!({ "max": 2 }),
+      "export const a = 1, { b, c } = obj;": [
+        {
+          col: 25,
+          message: MaxExportCountMessage::TooManyExports(3, 2),
+          hint: MaxExportCountHint::SplitModule,
+        }
+      ],
+      "export function a() {} export { b, c as d } from './b.ts'; export * as e from './e.ts';": [
+        {
+          col: 35,
+          message: MaxExportCountMessage::TooManyExports(4, 2),
+          hint: MaxExportCountHint::SplitModule,
+        }
+      ],
+      "export interface A {} export enum B {} export default 1;": [
+        {
+          col: 39,
+          message: MaxExportCountMessage::TooManyExports(3, 2),
+          hint: MaxExportCountHint::SplitModule,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      MaxExportCount,
+      options: json!({ "maxConstantsWithClasses": 1 }),
+      "export class Client {} export const PORT = 80; export const HOST = 'localhost';": [
+        {
+          col: 60,
+          message: MaxExportCountMessage::TooManyConstantsWithClasses(2, 1),
+          hint: MaxExportCountHint::MoveConstants,
+        }
+      ],
+      "export const PORT = 80, HOST = 'localhost'; export default class {}": [
+        {
+          col: 24,
+          message: MaxExportCountMessage::TooManyConstantsWithClasses(2, 1),
+          hint: MaxExportCountHint::MoveConstants,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      MaxExportCount,
+      options: json!({ "max": 1, "maxConstantsWithClasses": 0 }),
+      "export class A {} export const b = 1;": [
+        {
+          col: 31,
+          message: MaxExportCountMessage::TooManyConstantsWithClasses(1, 0),
+          hint: MaxExportCountHint::MoveConstants,
+        },
+        {
+          col: 31,
+          message: MaxExportCountMessage::TooManyExports(2, 1),
+          hint: MaxExportCountHint::SplitModule,
+        }
+      ],
+    };
+  }
+}
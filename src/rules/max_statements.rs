@@ -0,0 +1,219 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::complexity::{describe_function, function_span, is_function};
+use super::{Context, LintRule, ProgramRef};
+use crate::handler::{Handler, Traverse};
+use derive_more::Display;
+use dprint_swc_ecma_ast_view as AstView;
+use serde::Deserialize;
+use serde_json::json;
+
+pub struct MaxStatements {
+  options: MaxStatementsOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MaxStatementsOptions {
+  /// The maximum number of statements allowed in a single function.
+  pub max: usize,
+}
+
+impl Default for MaxStatementsOptions {
+  fn default() -> Self {
+    Self { max: 10 }
+  }
+}
+
+const CODE: &str = "max-statements";
+
+#[derive(Display)]
+enum MaxStatementsMessage {
+  #[display(fmt = "{} has {} statements. Maximum allowed is {}.", _0, _1, _2)]
+  TooManyStatements(String, usize, usize),
+}
+
+#[derive(Display)]
+enum MaxStatementsHint {
+  #[display(fmt = "Consider splitting this function into smaller functions")]
+  Split,
+}
+
+impl LintRule for MaxStatements {
+  fn new() -> Box<Self> {
+    Box::new(MaxStatements {
+      options: MaxStatementsOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
+    unreachable!();
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: AstView::Program<'_>,
+  ) {
+    let mut handler = MaxStatementsHandler {
+      max: self.options.max,
+      counts: Vec::new(),
+    };
+    handler.traverse(program, context);
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "max": {
+          "description": "The maximum number of statements allowed in a single function.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 10
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a maximum number of statements in functions",
+      "max-statements"
+    )
+  }
+}
+
+struct MaxStatementsHandler {
+  max: usize,
+  /// Statement counts of the functions being traversed, the innermost being
+  /// the last one.
+  counts: Vec<usize>,
+}
+
+impl MaxStatementsHandler {
+  fn add(&mut self, statements: usize) {
+    if let Some(count) = self.counts.last_mut() {
+      *count += statements;
+    }
+  }
+}
+
+impl Handler for MaxStatementsHandler {
+  fn on_enter_node(&mut self, node: AstView::Node, _ctx: &mut Context) {
+    use AstView::Node;
+    match node {
+      node if is_function(node) => self.counts.push(0),
+      Node::BlockStmt(block) => self.add(block.stmts.len()),
+      Node::SwitchCase(case) => self.add(case.cons.len()),
+      _ => {}
+    }
+  }
+
+  fn on_exit_node(&mut self, node: AstView::Node, ctx: &mut Context) {
+    if !is_function(node) {
+      return;
+    }
+
+    let count = self
+      .counts
+      .pop()
+      .expect("Function should have a statement count");
+    if count > self.max {
+      ctx.add_diagnostic_with_hint(
+        function_span(node),
+        CODE,
+        MaxStatementsMessage::TooManyStatements(
+          describe_function(node),
+          count,
+          self.max,
+        ),
+        MaxStatementsHint::Split,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn max_statements_valid() {
+    assert_lint_ok! {
+      MaxStatements,
+      "function foo() {}",
+      "function foo() { a(); b(); c(); d(); e(); f(); g(); h(); i(); j(); }",
+      "const foo = () => a;",
+    };
+
+    assert_lint_ok! {
+      MaxStatements,
+      options: json!({ "max": 2 }),
+      "function foo() { const a = 1; return a; }",
+      "function foo(a) { if (a) b(); }",
+      "class Foo { constructor() { this.a = 1; this.b = 2; } }",
+      // nested functions are counted on their own
+      "function foo() { function bar() { a(); b(); } return bar; }",
+      // top-level code isn't part of any function
+      "a(); b(); c();",
+    };
+  }
+
+  #[test]
+  fn max_statements_invalid() {
+    assert_lint_err! {
+      MaxStatements,
+      options: json!({ "max": 2 }),
+      "function foo() { a(); b(); c(); }": [
+        {
+          col: 0,
+          message: MaxStatementsMessage::TooManyStatements("Function 'foo'".to_string(), 3, 2),
+          hint: MaxStatementsHint::Split,
+        }
+      ],
+      "const foo = (a) => { if (a) { b(); c(); } };": [
+        {
+          col: 12,
+          message: MaxStatementsMessage::TooManyStatements("Arrow function".to_string(), 3, 2),
+          hint: MaxStatementsHint::Split,
+        }
+      ],
+      "class Foo { bar(a) { switch (a) { case 1: b(); break; } } }": [
+        {
+          col: 12,
+          message: MaxStatementsMessage::TooManyStatements("Method 'bar'".to_string(), 3, 2),
+          hint: MaxStatementsHint::Split,
+        }
+      ],
+      "function foo() { try { a(); } finally { b(); } return () => { c(); d(); e(); }; }": [
+        {
+          col: 0,
+          message: MaxStatementsMessage::TooManyStatements("Function 'foo'".to_string(), 4, 2),
+          hint: MaxStatementsHint::Split,
+        },
+        {
+          col: 54,
+          message: MaxStatementsMessage::TooManyStatements("Arrow function".to_string(), 3, 2),
+          hint: MaxStatementsHint::Split,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,305 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{ModuleDecl, Stmt};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct MaxStatementsPerLine {
+  options: MaxStatementsPerLineOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MaxStatementsPerLineOptions {
+  /// The maximum number of statements allowed on a single line.
+  pub max: usize,
+}
+
+impl Default for MaxStatementsPerLineOptions {
+  fn default() -> Self {
+    Self { max: 1 }
+  }
+}
+
+const CODE: &str = "max-statements-per-line";
+
+#[derive(Display)]
+enum MaxStatementsPerLineMessage {
+  #[display(
+    fmt = "This line has {} statements. Maximum allowed is {}.",
+    _0,
+    _1
+  )]
+  TooManyStatements(usize, usize),
+}
+
+#[derive(Display)]
+enum MaxStatementsPerLineHint {
+  #[display(fmt = "Consider moving the extra statements to their own lines")]
+  Split,
+}
+
+impl LintRule for MaxStatementsPerLine {
+  fn new() -> Box<Self> {
+    Box::new(MaxStatementsPerLine {
+      options: MaxStatementsPerLineOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = MaxStatementsPerLineVisitor {
+      context,
+      max: self.options.max,
+      line: 0,
+      count: 0,
+      first_extra: None,
+      bodies: HashSet::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+    visitor.report();
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "max": {
+          "description": "The maximum number of statements allowed on a single line.",
+          "type": "integer",
+          "minimum": 1,
+          "default": 1
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a maximum number of statements on a single line",
+      "max-statements-per-line"
+    )
+  }
+}
+
+/// Returns the statements that are the body of `stmt` without braces, e.g.
+/// `b();` in `if (a) b();`. They may share the line of their parent.
+fn single_stmt_bodies(stmt: &Stmt) -> Vec<&Stmt> {
+  match stmt {
+    Stmt::If(if_stmt) => {
+      let mut bodies = vec![&*if_stmt.cons];
+      bodies.extend(if_stmt.alt.as_deref());
+      bodies
+    }
+    Stmt::For(for_stmt) => vec![&*for_stmt.body],
+    Stmt::ForIn(for_in) => vec![&*for_in.body],
+    Stmt::ForOf(for_of) => vec![&*for_of.body],
+    Stmt::While(while_stmt) => vec![&*while_stmt.body],
+    Stmt::DoWhile(do_while) => vec![&*do_while.body],
+    Stmt::Labeled(labeled) => vec![&*labeled.body],
+    Stmt::With(with) => vec![&*with.body],
+    _ => vec![],
+  }
+}
+
+struct MaxStatementsPerLineVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  max: usize,
+  /// The line the last statement started or ended on.
+  line: usize,
+  /// The number of statements on `line`.
+  count: usize,
+  /// The first statement on `line` exceeding the maximum.
+  first_extra: Option<Span>,
+  /// The spans of the statements that don't count towards their line.
+  bodies: HashSet<Span>,
+}
+
+impl<'c, 'view> MaxStatementsPerLineVisitor<'c, 'view> {
+  fn line_of(&self, pos: BytePos) -> usize {
+    self.context.source_map().lookup_char_pos(pos).line
+  }
+
+  /// Reports the current line if it has too many statements.
+  fn report(&mut self) {
+    if let Some(span) = self.first_extra.take() {
+      self.context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        MaxStatementsPerLineMessage::TooManyStatements(self.count, self.max),
+        MaxStatementsPerLineHint::Split,
+      );
+    }
+  }
+
+  fn start_line(&mut self, line: usize) {
+    self.report();
+    self.line = line;
+    self.count = 1;
+  }
+
+  fn enter_stmt(&mut self, span: Span) {
+    let line = self.line_of(span.lo);
+    if line == self.line {
+      self.count += 1;
+    } else {
+      self.start_line(line);
+    }
+    if self.count == self.max + 1 && self.first_extra.is_none() {
+      self.first_extra = Some(span);
+    }
+  }
+
+  fn exit_stmt(&mut self, span: Span) {
+    let line = self.line_of(span.hi);
+    if line != self.line {
+      self.start_line(line);
+    }
+  }
+}
+
+impl<'c, 'view> Visit for MaxStatementsPerLineVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_stmt(&mut self, stmt: &Stmt, _: &dyn Node) {
+    let is_counted = !matches!(stmt, Stmt::Block(_) | Stmt::Empty(_))
+      && !self.bodies.contains(&stmt.span());
+    self
+      .bodies
+      .extend(single_stmt_bodies(stmt).into_iter().map(|body| body.span()));
+
+    if is_counted {
+      self.enter_stmt(stmt.span());
+    }
+    stmt.visit_children_with(self);
+    if is_counted {
+      self.exit_stmt(stmt.span());
+    }
+  }
+
+  fn visit_module_decl(&mut self, module_decl: &ModuleDecl, _: &dyn Node) {
+    self.enter_stmt(module_decl.span());
+    module_decl.visit_children_with(self);
+    self.exit_stmt(module_decl.span());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn max_statements_per_line_valid() {
+    assert_lint_ok! {
+      MaxStatementsPerLine,
+      "a();\nb();",
+      "if (a) b();",
+      "if (a) b(); else c();",
+      "for (const a of b) c();",
+      "foo: while (a) b();",
+      "function foo() {}",
+      "const foo = function () {};",
+      "import a from './a.ts';\nexport { a };",
+      "if (a) {\n  b();\n}\nc();",
+    };
+
+    assert_lint_ok! {
+      MaxStatementsPerLine,
+      options: json!({ "max": 2 }),
+      "a(); b();",
+      "if (a) { b(); }",
+      "function foo() { return 1; }",
+    };
+  }
+
+  #[test]
+  fn max_statements_per_line_invalid() {
+    assert_lint_err! {
+      MaxStatementsPerLine,
+      "a(); b();": [
+        {
+          col: 5,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+      "if (a) { b(); }": [
+        {
+          col: 9,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+      "const foo = () => { return 1; };": [
+        {
+          col: 20,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+      "switch (a) { default: break; }": [
+        {
+          col: 22,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+      "import a from './a.ts'; export { a };": [
+        {
+          col: 24,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+      "if (a) {\n  b();\n} c();\nd(); e();": [
+        {
+          line: 3,
+          col: 2,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        },
+        {
+          line: 4,
+          col: 5,
+          message: MaxStatementsPerLineMessage::TooManyStatements(2, 1),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      MaxStatementsPerLine,
+      options: json!({ "max": 2 }),
+      "a(); b(); c(); d();": [
+        {
+          col: 10,
+          message: MaxStatementsPerLineMessage::TooManyStatements(4, 2),
+          hint: MaxStatementsPerLineHint::Split,
+        }
+      ],
+    };
+  }
+}
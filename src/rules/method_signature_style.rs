@@ -0,0 +1,442 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{EqIgnoreSpan, Span, Spanned};
+use swc_ecmascript::ast::{
+  TsFnOrConstructorType, TsInterfaceBody, TsMethodSignature,
+  TsPropertySignature, TsThisTypeOrIdent, TsType, TsTypeElement, TsTypeLit,
+};
+use swc_ecmascript::visit::{Node, VisitAll, VisitAllWith};
+
+pub struct MethodSignatureStyle {
+  options: MethodSignatureStyleOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MethodSignatureStyleOptions {
+  /// The style of function members in interfaces and type literals.
+  pub style: MethodSignatureStyleMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MethodSignatureStyleMode {
+  /// `f: () => void`.
+  Property,
+  /// `f(): void`.
+  Method,
+}
+
+impl Default for MethodSignatureStyleOptions {
+  fn default() -> Self {
+    Self {
+      style: MethodSignatureStyleMode::Property,
+    }
+  }
+}
+
+const CODE: &str = "method-signature-style";
+
+#[derive(Display)]
+enum MethodSignatureStyleMessage {
+  #[display(fmt = "Shorthand method signature is forbidden")]
+  Method,
+  #[display(fmt = "Function property signature is forbidden")]
+  Property,
+}
+
+#[derive(Display)]
+enum MethodSignatureStyleHint {
+  #[display(fmt = "Use a property with a function type, e.g. `f: () => void`")]
+  UseProperty,
+  #[display(fmt = "Use a method signature, e.g. `f(): void`")]
+  UseMethod,
+}
+
+#[derive(Display)]
+enum MethodSignatureStyleFix {
+  #[display(fmt = "Change to a function property")]
+  UseProperty,
+  #[display(fmt = "Change to a method signature")]
+  UseMethod,
+}
+
+impl LintRule for MethodSignatureStyle {
+  fn new() -> Box<Self> {
+    Box::new(MethodSignatureStyle {
+      options: MethodSignatureStyleOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = MethodSignatureStyleVisitor {
+      context,
+      style: self.options.style,
+    };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "style": {
+          "description": "The style of function members in interfaces and type literals.",
+          "enum": ["property", "method"],
+          "default": "property"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a consistent style for function members of interfaces and type literals",
+      "method-signature-style"
+    )
+  }
+}
+
+/// Returns `true` for a return type like `this is Foo`, which is only allowed
+/// in method signatures.
+fn is_this_predicate(ty: &TsType) -> bool {
+  matches!(
+    ty,
+    TsType::TsTypePredicate(predicate)
+      if matches!(predicate.param_name, TsThisTypeOrIdent::TsThisType(_))
+  )
+}
+
+struct MethodSignatureStyleVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  style: MethodSignatureStyleMode,
+}
+
+impl<'c, 'view> MethodSignatureStyleVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  fn has_comments(&self, span: Span) -> bool {
+    self
+      .context
+      .all_comments()
+      .any(|comment| span.lo <= comment.span.lo && comment.span.hi <= span.hi)
+  }
+
+  fn check_members(&mut self, members: &[TsTypeElement]) {
+    for member in members {
+      match (member, self.style) {
+        (
+          TsTypeElement::TsMethodSignature(method),
+          MethodSignatureStyleMode::Property,
+        ) => {
+          // Overloads would have to be merged into an intersection type
+          let is_overloaded = members.iter().any(|other| match other {
+            TsTypeElement::TsMethodSignature(other) => {
+              other.span != method.span
+                && other.computed == method.computed
+                && other.key.eq_ignore_span(&method.key)
+            }
+            _ => false,
+          });
+          let fixed = if is_overloaded {
+            None
+          } else {
+            self.print_property(method)
+          };
+          self.report(
+            method.span,
+            MethodSignatureStyleMessage::Method,
+            MethodSignatureStyleHint::UseProperty,
+            MethodSignatureStyleFix::UseProperty,
+            fixed,
+          );
+        }
+        (
+          TsTypeElement::TsPropertySignature(property),
+          MethodSignatureStyleMode::Method,
+        ) => {
+          let fn_type =
+            match property.type_ann.as_ref().map(|ann| &*ann.type_ann) {
+              Some(TsType::TsFnOrConstructorType(
+                TsFnOrConstructorType::TsFnType(fn_type),
+              )) => fn_type,
+              _ => continue,
+            };
+          let fixed = if property.readonly {
+            None
+          } else {
+            self.print_method(
+              property,
+              fn_type.span,
+              &fn_type.type_ann.type_ann,
+            )
+          };
+          self.report(
+            property.span,
+            MethodSignatureStyleMessage::Property,
+            MethodSignatureStyleHint::UseMethod,
+            MethodSignatureStyleFix::UseMethod,
+            fixed,
+          );
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Returns the span to replace and the text of the method signature written
+  /// as a property with a function type.
+  fn print_property(
+    &self,
+    method: &TsMethodSignature,
+  ) -> Option<(Span, String)> {
+    let return_type = &method.type_ann.as_ref()?.type_ann;
+    if is_this_predicate(return_type) {
+      return None;
+    }
+    let return_span = return_type.span();
+    let text = self.snippet(method.span.with_hi(return_span.lo))?;
+    // The type parameters and parameters after the key, `]` and `?`
+    let rest_span =
+      Span::new(method.key.span().hi, return_span.lo, method.span.ctxt);
+    let rest = self.snippet(rest_span)?;
+    let mut rest = &*rest;
+    if method.computed {
+      rest = rest.trim_start().strip_prefix(']')?;
+    }
+    if method.optional {
+      rest = rest.trim_start().strip_prefix('?')?;
+    }
+    let rest = rest.trim_start();
+    let key = text[..text.len() - rest.len()].trim_end();
+    let params = rest.trim_end().strip_suffix(':')?.trim_end();
+    Some((
+      method.span.with_hi(return_span.hi),
+      format!("{}: {} => {}", key, params, self.snippet(return_span)?),
+    ))
+  }
+
+  /// Returns the span to replace and the text of the property written as a
+  /// method signature.
+  fn print_method(
+    &self,
+    property: &TsPropertySignature,
+    fn_span: Span,
+    return_type: &TsType,
+  ) -> Option<(Span, String)> {
+    let return_span = return_type.span();
+    let key = self.snippet(property.span.with_hi(fn_span.lo))?;
+    let key = key.trim_end().strip_suffix(':')?.trim_end();
+    let params = self.snippet(fn_span.with_hi(return_span.lo))?;
+    let params = params.trim_end().strip_suffix("=>")?.trim_end();
+    Some((
+      property.span.with_hi(return_span.hi),
+      format!("{}{}: {}", key, params, self.snippet(return_span)?),
+    ))
+  }
+
+  fn report(
+    &mut self,
+    span: Span,
+    message: MethodSignatureStyleMessage,
+    hint: MethodSignatureStyleHint,
+    fix: MethodSignatureStyleFix,
+    fixed: Option<(Span, String)>,
+  ) {
+    match fixed {
+      Some((fixed_span, fixed)) if !self.has_comments(fixed_span) => {
+        let fix = self.context.create_fix(fix, vec![(fixed_span, fixed)]);
+        self.context.add_diagnostic_with_fixes(
+          span,
+          CODE,
+          message,
+          Some(hint.to_string()),
+          vec![fix],
+        );
+      }
+      _ => {
+        self
+          .context
+          .add_diagnostic_with_hint(span, CODE, message, hint);
+      }
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for MethodSignatureStyleVisitor<'c, 'view> {
+  fn visit_ts_interface_body(
+    &mut self,
+    interface_body: &TsInterfaceBody,
+    _: &dyn Node,
+  ) {
+    self.check_members(&interface_body.body);
+  }
+
+  fn visit_ts_type_lit(&mut self, type_lit: &TsTypeLit, _: &dyn Node) {
+    self.check_members(&type_lit.members);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn method_signature_style_valid() {
+    assert_lint_ok! {
+      MethodSignatureStyle,
+      "interface Foo { a: () => void; b: string; }",
+      "type Foo = { a: <T>(b: T) => T };",
+      "interface Foo { new (): Foo; (): void; }",
+      "class Foo { a(): void {} }",
+    };
+
+    assert_lint_ok! {
+      MethodSignatureStyle,
+      options: json!({ "style": "method" }),
+      "interface Foo { a(): void; b: string; }",
+      "type Foo = { a<T>(b: T): T };",
+      "interface Foo { a: (() => void) | null; }",
+    };
+  }
+
+  #[test]
+  fn method_signature_style_invalid() {
+    assert_lint_err! {
+      MethodSignatureStyle,
+      "interface Foo { a(b: string): void; }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+          fix: (
+            MethodSignatureStyleFix::UseProperty,
+            "interface Foo { a: (b: string) => void; }"
+          ),
+        }
+      ],
+      "type Foo = { a?<T>(b: T): T, [Symbol.iterator](): Iterator<string> };": [
+        {
+          col: 13,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+          fix: (
+            MethodSignatureStyleFix::UseProperty,
+            "type Foo = { a?: <T>(b: T) => T, [Symbol.iterator](): Iterator<string> };"
+          ),
+        },
+        {
+          col: 29,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+          fix: (
+            MethodSignatureStyleFix::UseProperty,
+            "type Foo = { a?<T>(b: T): T, [Symbol.iterator]: () => Iterator<string> };"
+          ),
+        }
+      ],
+      "interface Foo { a(): void; a(b: string): void; }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+        },
+        {
+          col: 27,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+        }
+      ],
+      "interface Foo { isBar(): this is Bar; }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+        }
+      ],
+      "interface Foo { a(); }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Method,
+          hint: MethodSignatureStyleHint::UseProperty,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      MethodSignatureStyle,
+      options: json!({ "style": "method" }),
+      "interface Foo { a: (b: string) => void; }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Property,
+          hint: MethodSignatureStyleHint::UseMethod,
+          fix: (
+            MethodSignatureStyleFix::UseMethod,
+            "interface Foo { a(b: string): void; }"
+          ),
+        }
+      ],
+      "let a: { b?: <T>(c: T) => T, [d]: () => void };": [
+        {
+          col: 9,
+          message: MethodSignatureStyleMessage::Property,
+          hint: MethodSignatureStyleHint::UseMethod,
+          fix: (
+            MethodSignatureStyleFix::UseMethod,
+            "let a: { b?<T>(c: T): T, [d]: () => void };"
+          ),
+        },
+        {
+          col: 29,
+          message: MethodSignatureStyleMessage::Property,
+          hint: MethodSignatureStyleHint::UseMethod,
+          fix: (
+            MethodSignatureStyleFix::UseMethod,
+            "let a: { b?: <T>(c: T) => T, [d](): void };"
+          ),
+        }
+      ],
+      "interface Foo { readonly a: () => void; }": [
+        {
+          col: 16,
+          message: MethodSignatureStyleMessage::Property,
+          hint: MethodSignatureStyleHint::UseMethod,
+        }
+      ],
+    };
+  }
+}
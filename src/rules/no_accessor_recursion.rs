@@ -0,0 +1,354 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::StringRepr;
+use derive_more::Display;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, AssignOp, BlockStmt, Class, ClassMethod, Expr,
+  ExprOrSuper, Function, GetterProp, Lit, MemberExpr, MethodKind, Pat,
+  PatOrExpr, PrivateMethod, SetterProp, UpdateExpr,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoAccessorRecursion;
+
+const CODE: &str = "no-accessor-recursion";
+
+#[derive(Display)]
+enum NoAccessorRecursionMessage {
+  #[display(
+    fmt = "Getter '{}' reads its own property, which recurses infinitely",
+    _0
+  )]
+  Getter(String),
+  #[display(
+    fmt = "Setter '{}' assigns to its own property, which recurses infinitely",
+    _0
+  )]
+  Setter(String),
+}
+
+#[derive(Display)]
+enum NoAccessorRecursionHint {
+  #[display(
+    fmt = "Store the value in a property with a different name, e.g. a `#private` field"
+  )]
+  UseBackingField,
+}
+
+impl LintRule for NoAccessorRecursion {
+  fn new() -> Box<Self> {
+    Box::new(NoAccessorRecursion)
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoAccessorRecursionVisitor {
+      context,
+      accessor: None,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows getters and setters that access their own property on `this`",
+      "no-accessor-recursion"
+    )
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AccessorKind {
+  Getter,
+  Setter,
+}
+
+/// The getter or setter whose body is currently visited.
+struct Accessor {
+  kind: AccessorKind,
+  name: String,
+  is_private: bool,
+}
+
+struct NoAccessorRecursionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  accessor: Option<Accessor>,
+}
+
+impl<'c, 'view> NoAccessorRecursionVisitor<'c, 'view> {
+  fn visit_accessor_body(
+    &mut self,
+    accessor: Option<Accessor>,
+    body: &Option<BlockStmt>,
+    parent: &dyn Node,
+  ) {
+    let prev = std::mem::replace(&mut self.accessor, accessor);
+    body.visit_with(parent, self);
+    self.accessor = prev;
+  }
+
+  fn check_member(&mut self, member: &MemberExpr, kind: AccessorKind) {
+    let accessor = match &self.accessor {
+      Some(accessor) if accessor.kind == kind => accessor,
+      _ => return,
+    };
+    if !matches!(&member.obj, ExprOrSuper::Expr(obj) if matches!(&**obj, Expr::This(_)))
+    {
+      return;
+    }
+
+    let accesses_itself = match &*member.prop {
+      Expr::PrivateName(name) => {
+        accessor.is_private && *name.id.sym == *accessor.name
+      }
+      Expr::Ident(ident) if !member.computed => {
+        !accessor.is_private && *ident.sym == *accessor.name
+      }
+      Expr::Lit(Lit::Str(str)) if member.computed => {
+        !accessor.is_private && *str.value == *accessor.name
+      }
+      _ => false,
+    };
+    if !accesses_itself {
+      return;
+    }
+
+    let message = match kind {
+      AccessorKind::Getter => {
+        NoAccessorRecursionMessage::Getter(accessor.name.clone())
+      }
+      AccessorKind::Setter => {
+        NoAccessorRecursionMessage::Setter(accessor.name.clone())
+      }
+    };
+    self.context.add_diagnostic_with_hint(
+      member.span,
+      CODE,
+      message,
+      NoAccessorRecursionHint::UseBackingField,
+    );
+  }
+}
+
+fn accessor_kind(kind: MethodKind) -> Option<AccessorKind> {
+  match kind {
+    MethodKind::Getter => Some(AccessorKind::Getter),
+    MethodKind::Setter => Some(AccessorKind::Setter),
+    MethodKind::Method => None,
+  }
+}
+
+fn target_member(target: &PatOrExpr) -> Option<&MemberExpr> {
+  let expr = match target {
+    PatOrExpr::Expr(expr) => &**expr,
+    PatOrExpr::Pat(pat) => match &**pat {
+      Pat::Expr(expr) => &**expr,
+      _ => return None,
+    },
+  };
+  match expr {
+    Expr::Member(member) => Some(member),
+    _ => None,
+  }
+}
+
+impl<'c, 'view> Visit for NoAccessorRecursionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_class_method(&mut self, method: &ClassMethod, _: &dyn Node) {
+    method.key.visit_with(method, self);
+    let accessor = accessor_kind(method.kind)
+      .zip(method.key.string_repr())
+      .map(|(kind, name)| Accessor {
+        kind,
+        name,
+        is_private: false,
+      });
+    self.visit_accessor_body(accessor, &method.function.body, method);
+  }
+
+  fn visit_private_method(&mut self, method: &PrivateMethod, _: &dyn Node) {
+    let accessor = accessor_kind(method.kind).map(|kind| Accessor {
+      kind,
+      name: method.key.id.sym.to_string(),
+      is_private: true,
+    });
+    self.visit_accessor_body(accessor, &method.function.body, method);
+  }
+
+  fn visit_getter_prop(&mut self, getter: &GetterProp, _: &dyn Node) {
+    getter.key.visit_with(getter, self);
+    let accessor = getter.key.string_repr().map(|name| Accessor {
+      kind: AccessorKind::Getter,
+      name,
+      is_private: false,
+    });
+    self.visit_accessor_body(accessor, &getter.body, getter);
+  }
+
+  fn visit_setter_prop(&mut self, setter: &SetterProp, _: &dyn Node) {
+    setter.key.visit_with(setter, self);
+    let accessor = setter.key.string_repr().map(|name| Accessor {
+      kind: AccessorKind::Setter,
+      name,
+      is_private: false,
+    });
+    self.visit_accessor_body(accessor, &setter.body, setter);
+  }
+
+  // Functions and classes bind their own `this`. Arrow functions share the
+  // `this` of the accessor, but may be called after it has returned, e.g.
+  // `get foo() { return () => this.foo; }`, so they aren't checked either
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    let prev = self.accessor.take();
+    function.visit_children_with(self);
+    self.accessor = prev;
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    let prev = self.accessor.take();
+    arrow_expr.visit_children_with(self);
+    self.accessor = prev;
+  }
+
+  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+    let prev = self.accessor.take();
+    class.visit_children_with(self);
+    self.accessor = prev;
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    match target_member(&assign_expr.left) {
+      Some(member) => {
+        self.check_member(member, AccessorKind::Setter);
+        // `this.foo = 1` doesn't invoke the getter, `this.foo += 1` does
+        if assign_expr.op == AssignOp::Assign {
+          member.visit_children_with(self);
+        } else {
+          member.visit_with(assign_expr, self);
+        }
+      }
+      None => assign_expr.left.visit_with(assign_expr, self),
+    }
+    assign_expr.right.visit_with(assign_expr, self);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    if let Expr::Member(member) = &*update_expr.arg {
+      self.check_member(member, AccessorKind::Setter);
+    }
+    update_expr.visit_children_with(self);
+  }
+
+  fn visit_member_expr(&mut self, member: &MemberExpr, _: &dyn Node) {
+    self.check_member(member, AccessorKind::Getter);
+    member.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_accessor_recursion_valid() {
+    assert_lint_ok! {
+      NoAccessorRecursion,
+      "class Foo { get bar() { return this._bar; } set bar(v) { this._bar = v; } }",
+      "class Foo { #bar = 1; get bar() { return this.#bar; } set bar(v) { this.#bar = v; } }",
+      "class Foo { get #bar() { return this.bar; } }",
+      "class Foo { get bar() { return other.bar; } }",
+      "class Foo { get bar() { return this[bar]; } }",
+      "class Foo { bar() { return this.bar; } }",
+      "class Foo { set bar(v) { console.log(this.bar); } }",
+      "class Foo { get bar() { this.bar = 1; return 1; } }",
+      "class Foo { get bar() { return function () { return this.bar; }; } }",
+      "class Foo { get bar() { return class { baz = this.bar; }; } }",
+      "class Foo { get bar() { return () => this.bar; } }",
+      "class Foo { set bar(v) { setTimeout(() => { this.bar = v; }); } }",
+      "class Foo extends Base { get bar() { return super.bar; } set bar(v) { super.bar = v; } }",
+      "const foo = { get bar() { return this.baz; }, set bar(v) { this.baz = v; } };",
+      "const foo = { bar() { return this.bar; } };",
+    };
+  }
+
+  #[test]
+  fn no_accessor_recursion_invalid() {
+    assert_lint_err! {
+      NoAccessorRecursion,
+      "class Foo { get bar() { return this.bar; } }": [
+        {
+          col: 31,
+          message: variant!(NoAccessorRecursionMessage, Getter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "class Foo { set bar(v) { this.bar = v; } }": [
+        {
+          col: 25,
+          message: variant!(NoAccessorRecursionMessage, Setter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "class Foo { static get bar() { return this['bar'] ?? 1; } }": [
+        {
+          col: 38,
+          message: variant!(NoAccessorRecursionMessage, Getter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "class Foo { get #bar() { return this.#bar; } set #bar(v) { this.#bar = v; } }": [
+        {
+          col: 32,
+          message: variant!(NoAccessorRecursionMessage, Getter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        },
+        {
+          col: 59,
+          message: variant!(NoAccessorRecursionMessage, Setter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "class Foo { get bar() { this.bar += 1; return 1; } }": [
+        {
+          col: 24,
+          message: variant!(NoAccessorRecursionMessage, Getter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "class Foo { set bar(v) { this.bar++; } }": [
+        {
+          col: 25,
+          message: variant!(NoAccessorRecursionMessage, Setter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+      "const foo = { get bar() { return this.bar; }, set bar(v) { this.bar = v; } };": [
+        {
+          col: 33,
+          message: variant!(NoAccessorRecursionMessage, Getter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        },
+        {
+          col: 59,
+          message: variant!(NoAccessorRecursionMessage, Setter, "bar"),
+          hint: NoAccessorRecursionHint::UseBackingField,
+        }
+      ],
+    };
+  }
+}
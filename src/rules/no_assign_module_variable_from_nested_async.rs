@@ -0,0 +1,313 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::scopes::BindingKind;
+use crate::swc_util::find_lhs_ids;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, CallExpr, Expr, ExprOrSpread, Function, NewExpr,
+  UpdateExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoAssignModuleVariableFromNestedAsync {
+  options: NoAssignModuleVariableFromNestedAsyncOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoAssignModuleVariableFromNestedAsyncOptions {
+  /// Whether to report assignments in async functions.
+  pub check_async_functions: bool,
+  /// Whether to report assignments in functions passed as an argument, e.g.
+  /// to `setTimeout()` or an event handler.
+  pub check_callbacks: bool,
+}
+
+impl Default for NoAssignModuleVariableFromNestedAsyncOptions {
+  fn default() -> Self {
+    Self {
+      check_async_functions: true,
+      check_callbacks: true,
+    }
+  }
+}
+
+const CODE: &str = "no-assign-module-variable-from-nested-async";
+
+#[derive(Display)]
+enum NoAssignModuleVariableFromNestedAsyncMessage {
+  #[display(fmt = "Module-level `{}` is assigned in an async function", _0)]
+  AsyncFunction(String),
+  #[display(fmt = "Module-level `{}` is assigned in a callback", _0)]
+  Callback(String),
+}
+
+#[derive(Display)]
+enum NoAssignModuleVariableFromNestedAsyncHint {
+  #[display(
+    fmt = "Keep the state local or pass it explicitly, concurrent calls can interleave and overwrite each other's values"
+  )]
+  KeepLocal,
+}
+
+impl LintRule for NoAssignModuleVariableFromNestedAsync {
+  fn new() -> Box<Self> {
+    Box::new(NoAssignModuleVariableFromNestedAsync {
+      options: NoAssignModuleVariableFromNestedAsyncOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoAssignModuleVariableFromNestedAsyncVisitor {
+      context,
+      options: &self.options,
+      async_depth: 0,
+      callback_depth: 0,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "checkAsyncFunctions": {
+          "description": "Whether to report assignments in async functions.",
+          "type": "boolean",
+          "default": true
+        },
+        "checkCallbacks": {
+          "description": "Whether to report assignments in functions passed as an argument, e.g. to `setTimeout()` or an event handler.",
+          "type": "boolean",
+          "default": true
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows assigning module-level variables from async functions and callbacks",
+      "no-assign-module-variable-from-nested-async"
+    )
+  }
+}
+
+/// Returns `true` for function expressions passed as arguments, looking
+/// through parentheses.
+fn is_callback(arg: &ExprOrSpread) -> bool {
+  let mut expr = &*arg.expr;
+  while let Expr::Paren(paren) = expr {
+    expr = &paren.expr;
+  }
+  matches!(expr, Expr::Fn(_) | Expr::Arrow(_))
+}
+
+struct NoAssignModuleVariableFromNestedAsyncVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c NoAssignModuleVariableFromNestedAsyncOptions,
+  /// The number of async functions the visitor is in.
+  async_depth: usize,
+  /// The number of callbacks the visitor is in.
+  callback_depth: usize,
+}
+
+impl<'c, 'view> NoAssignModuleVariableFromNestedAsyncVisitor<'c, 'view> {
+  fn check(&mut self, span: Span, id: &Id) {
+    let is_module_variable = matches!(
+      self.context.scope().var(id),
+      Some(var) if var.path().is_empty()
+        && matches!(var.kind(), BindingKind::Let | BindingKind::Var)
+    );
+    if !is_module_variable {
+      return;
+    }
+    let name = id.0.to_string();
+    let message = if self.options.check_async_functions && self.async_depth > 0
+    {
+      NoAssignModuleVariableFromNestedAsyncMessage::AsyncFunction(name)
+    } else if self.options.check_callbacks && self.callback_depth > 0 {
+      NoAssignModuleVariableFromNestedAsyncMessage::Callback(name)
+    } else {
+      return;
+    };
+    self.context.add_diagnostic_with_hint(
+      span,
+      CODE,
+      message,
+      NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+    );
+  }
+
+  fn visit_args(&mut self, args: &[ExprOrSpread], parent: &dyn Node) {
+    for arg in args {
+      if is_callback(arg) {
+        self.callback_depth += 1;
+        arg.visit_with(parent, self);
+        self.callback_depth -= 1;
+      } else {
+        arg.visit_with(parent, self);
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit
+  for NoAssignModuleVariableFromNestedAsyncVisitor<'c, 'view>
+{
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    if function.is_async {
+      self.async_depth += 1;
+      function.visit_children_with(self);
+      self.async_depth -= 1;
+    } else {
+      function.visit_children_with(self);
+    }
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    if arrow_expr.is_async {
+      self.async_depth += 1;
+      arrow_expr.visit_children_with(self);
+      self.async_depth -= 1;
+    } else {
+      arrow_expr.visit_children_with(self);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    call_expr.callee.visit_with(call_expr, self);
+    self.visit_args(&call_expr.args, call_expr);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    new_expr.callee.visit_with(new_expr, self);
+    if let Some(args) = &new_expr.args {
+      self.visit_args(args, new_expr);
+    }
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    for id in find_lhs_ids::<Id>(&assign_expr.left) {
+      self.check(assign_expr.span, &id);
+    }
+    assign_expr.visit_children_with(self);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    if let Expr::Ident(ident) = &*update_expr.arg {
+      self.check(update_expr.span, &ident.to_id());
+    }
+    update_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_assign_module_variable_from_nested_async_valid() {
+    assert_lint_ok! {
+      NoAssignModuleVariableFromNestedAsync,
+      "let count = 0; count++;",
+      "let count = 0; function increment() { count++; }",
+      "async function handle() { let count = 0; count++; }",
+      "let count = 0; async function handle() { const count = 1; return count; }",
+      "let total = 0; for (const item of items) { total += item; }",
+      "const cache = new Map(); async function get(key) { cache.set(key, await load(key)); }",
+      "let user; async function load() { let user = await fetchUser(); user = null; }",
+      "async function handle() { for (let i = 0; i < 3; i++) {} }",
+    };
+
+    assert_lint_ok! {
+      NoAssignModuleVariableFromNestedAsync,
+      options: json!({ "checkCallbacks": false }),
+      "let total = 0; items.forEach((item) => { total += item; });",
+    };
+
+    assert_lint_ok! {
+      NoAssignModuleVariableFromNestedAsync,
+      options: json!({ "checkAsyncFunctions": false }),
+      "let user; async function load() { user = await fetchUser(); }",
+    };
+  }
+
+  #[test]
+  fn no_assign_module_variable_from_nested_async_invalid() {
+    assert_lint_err! {
+      NoAssignModuleVariableFromNestedAsync,
+      "let user; async function load() { user = await fetchUser(); }": [
+        {
+          col: 34,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, AsyncFunction, "user"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        }
+      ],
+      "var requests = 0; Deno.serve(async (req) => { requests++; return new Response(); });": [
+        {
+          col: 46,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, AsyncFunction, "requests"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        }
+      ],
+      "let ready = false; setTimeout(() => { ready = true; }, 100);": [
+        {
+          col: 38,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, Callback, "ready"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        }
+      ],
+      "let a, b; class Foo { async bar() { [a, { b }] = await baz(); } }": [
+        {
+          col: 36,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, AsyncFunction, "a"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        },
+        {
+          col: 36,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, AsyncFunction, "b"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        }
+      ],
+      "let last; async function handle() { items.map(function (item) { last = item; }); }": [
+        {
+          col: 64,
+          message: variant!(NoAssignModuleVariableFromNestedAsyncMessage, AsyncFunction, "last"),
+          hint: NoAssignModuleVariableFromNestedAsyncHint::KeepLocal,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,190 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{async_function_names, is_async_function};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoAsyncArrayCallbacks;
+
+const CODE: &str = "no-async-array-callbacks";
+
+#[derive(Display)]
+enum NoAsyncArrayCallbacksMessage {
+  #[display(
+    fmt = "Async callback passed to '{}' returns a promise that is ignored",
+    _0
+  )]
+  Ignored(String),
+  #[display(
+    fmt = "Async callback passed to '{}' returns a promise that is always truthy",
+    _0
+  )]
+  Truthy(String),
+}
+
+#[derive(Display)]
+enum NoAsyncArrayCallbacksHint {
+  #[display(
+    fmt = "Use a `for...of` loop with `await`, or `await Promise.all()` with `map()`"
+  )]
+  UseForOf,
+  #[display(
+    fmt = "Resolve the values first with `await Promise.all()` and `map()`, then test the results"
+  )]
+  ResolveFirst,
+}
+
+impl LintRule for NoAsyncArrayCallbacks {
+  fn new() -> Box<Self> {
+    Box::new(NoAsyncArrayCallbacks)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoAsyncArrayCallbacksVisitor {
+      context,
+      async_functions: async_function_names(&program),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows async callbacks in array methods that ignore the promises they return",
+      "no-async-array-callbacks"
+    )
+  }
+}
+
+struct NoAsyncArrayCallbacksVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  async_functions: HashSet<JsWord>,
+}
+
+impl<'c, 'view> NoAsyncArrayCallbacksVisitor<'c, 'view> {
+  fn check_call(&mut self, call_expr: &CallExpr) {
+    let member = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) if !member.computed => member,
+        _ => return,
+      },
+      ExprOrSuper::Super(_) => return,
+    };
+    let method = match &*member.prop {
+      Expr::Ident(ident) => &ident.sym,
+      _ => return,
+    };
+    let (message, hint) = match &**method {
+      "forEach" => (
+        NoAsyncArrayCallbacksMessage::Ignored(method.to_string()),
+        NoAsyncArrayCallbacksHint::UseForOf,
+      ),
+      "filter" | "some" | "every" | "find" => (
+        NoAsyncArrayCallbacksMessage::Truthy(method.to_string()),
+        NoAsyncArrayCallbacksHint::ResolveFirst,
+      ),
+      _ => return,
+    };
+    let callback = match call_expr.args.first() {
+      Some(arg) if arg.spread.is_none() => &*arg.expr,
+      _ => return,
+    };
+    let is_async = is_async_function(callback)
+      || matches!(
+        callback,
+        Expr::Ident(ident) if self.async_functions.contains(&ident.sym)
+      );
+    if is_async {
+      self.context.add_diagnostic_with_hint(
+        callback.span(),
+        CODE,
+        message,
+        hint,
+      );
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoAsyncArrayCallbacksVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    self.check_call(call_expr);
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_async_array_callbacks_valid() {
+    assert_lint_ok! {
+      NoAsyncArrayCallbacks,
+      "items.forEach((item) => console.log(item));",
+      "items.filter(function (item) { return item.ok; });",
+      "await Promise.all(items.map(async (item) => await save(item)));",
+      "for (const item of items) { await save(item); }",
+      "async function check() {} items.reduce(check);",
+      "function check() {} items.some(check);",
+      "items.forEach(...callbacks);",
+    };
+  }
+
+  #[test]
+  fn no_async_array_callbacks_invalid() {
+    assert_lint_err! {
+      NoAsyncArrayCallbacks,
+      "items.forEach(async (item) => { await save(item); });": [
+        {
+          col: 14,
+          message: variant!(NoAsyncArrayCallbacksMessage, Ignored, "forEach"),
+          hint: NoAsyncArrayCallbacksHint::UseForOf,
+        }
+      ],
+      "const valid = items.filter(async function (item) { return await check(item); });": [
+        {
+          col: 27,
+          message: variant!(NoAsyncArrayCallbacksMessage, Truthy, "filter"),
+          hint: NoAsyncArrayCallbacksHint::ResolveFirst,
+        }
+      ],
+      "const exists = async (item) => true; items.some(exists); items.every(exists); items.find(exists);": [
+        {
+          col: 48,
+          message: variant!(NoAsyncArrayCallbacksMessage, Truthy, "some"),
+          hint: NoAsyncArrayCallbacksHint::ResolveFirst,
+        },
+        {
+          col: 69,
+          message: variant!(NoAsyncArrayCallbacksMessage, Truthy, "every"),
+          hint: NoAsyncArrayCallbacksHint::ResolveFirst,
+        },
+        {
+          col: 89,
+          message: variant!(NoAsyncArrayCallbacksMessage, Truthy, "find"),
+          hint: NoAsyncArrayCallbacksHint::ResolveFirst,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,248 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::import_map::{is_relative, is_url, ImportMap};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoBareNodeStyleSpecifiers {
+  options: NoBareNodeStyleSpecifiersOptions,
+  import_map: Option<ImportMap>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoBareNodeStyleSpecifiersOptions {
+  /// Path of the import map whose specifiers are allowed.
+  pub import_map: Option<String>,
+}
+
+const CODE: &str = "no-bare-node-style-specifiers";
+
+#[derive(Display)]
+enum NoBareNodeStyleSpecifiersMessage {
+  #[display(
+    fmt = "'{}' is neither a relative path, a URL, nor mapped by the import map",
+    _0
+  )]
+  Bare(String),
+}
+
+#[derive(Display)]
+enum NoBareNodeStyleSpecifiersHint {
+  #[display(
+    fmt = "Import the module by its URL, add it to the import map, or use an `npm:` or `node:` specifier"
+  )]
+  UseUrl,
+}
+
+impl LintRule for NoBareNodeStyleSpecifiers {
+  fn new() -> Box<Self> {
+    Box::new(NoBareNodeStyleSpecifiers {
+      options: NoBareNodeStyleSpecifiersOptions::default(),
+      import_map: None,
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoBareNodeStyleSpecifiersVisitor {
+      context,
+      import_map: self.import_map.as_ref(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: NoBareNodeStyleSpecifiersOptions =
+      serde_json::from_value(options)?;
+    self.import_map = match &options.import_map {
+      Some(path) => Some(ImportMap::load(path)?),
+      None => None,
+    };
+    self.options = options;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "importMap": {
+          "description": "Path of the import map whose specifiers are allowed.",
+          "type": "string"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows bare import specifiers that aren't mapped by the import map",
+      "no-bare-node-style-specifiers"
+    )
+  }
+}
+
+struct NoBareNodeStyleSpecifiersVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  import_map: Option<&'c ImportMap>,
+}
+
+impl<'c, 'view> NoBareNodeStyleSpecifiersVisitor<'c, 'view> {
+  fn check_specifier(&mut self, src: &Str) {
+    let specifier = &*src.value;
+    if is_relative(specifier)
+      || is_url(specifier)
+      || matches!(self.import_map, Some(import_map) if import_map.is_mapped(specifier))
+    {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      src.span,
+      CODE,
+      NoBareNodeStyleSpecifiersMessage::Bare(specifier.to_string()),
+      NoBareNodeStyleSpecifiersHint::UseUrl,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for NoBareNodeStyleSpecifiersVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    self.check_specifier(&import_decl.src);
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_specifier(&export_all.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      self.check_specifier(src);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          if let Expr::Lit(Lit::Str(src)) = &*arg.expr {
+            self.check_specifier(src);
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  /// Writes an import map to a file named after the test, as tests run
+  /// concurrently.
+  fn write_import_map(test: &str) -> String {
+    let path = std::env::temp_dir().join(format!("{}.json", test));
+    std::fs::write(
+      &path,
+      r#"{
+        "imports": { "preact": "https://esm.sh/preact", "std/": "https://deno.land/std@0.200.0/" },
+        "scopes": { "./vendor/": { "lodash": "https://esm.sh/lodash" } }
+      }"#,
+    )
+    .unwrap();
+    path.to_str().unwrap().to_string()
+  }
+
+  #[test]
+  fn no_bare_node_style_specifiers_valid() {
+    assert_lint_ok! {
+      NoBareNodeStyleSpecifiers,
+      r#"import { foo } from "./foo.ts";"#,
+      r#"import { foo } from "../foo.ts";"#,
+      r#"import { foo } from "/foo.ts";"#,
+      r#"import { serve } from "https://deno.land/std/http/server.ts";"#,
+      r#"import { readFile } from "node:fs";"#,
+      r#"import express from "npm:express@4";"#,
+      r#"export * from "jsr:@std/assert";"#,
+      r#"const mod = await import(specifier);"#,
+    };
+
+    assert_lint_ok! {
+      NoBareNodeStyleSpecifiers,
+      options: json!({ "importMap": write_import_map("no_bare_node_style_specifiers_valid") }),
+      r#"import { h } from "preact";"#,
+      r#"import { join } from "std/path/mod.ts";"#,
+      r#"import _ from "lodash";"#,
+    };
+  }
+
+  #[test]
+  fn no_bare_node_style_specifiers_invalid() {
+    assert_lint_err! {
+      NoBareNodeStyleSpecifiers,
+      r#"import express from "express";"#: [
+        {
+          col: 20,
+          message: variant!(NoBareNodeStyleSpecifiersMessage, Bare, "express"),
+          hint: NoBareNodeStyleSpecifiersHint::UseUrl,
+        }
+      ],
+      r#"export { foo } from "foo.ts";"#: [
+        {
+          col: 20,
+          message: variant!(NoBareNodeStyleSpecifiersMessage, Bare, "foo.ts"),
+          hint: NoBareNodeStyleSpecifiersHint::UseUrl,
+        }
+      ],
+      r#"const fs = await import("fs");"#: [
+        {
+          col: 24,
+          message: variant!(NoBareNodeStyleSpecifiersMessage, Bare, "fs"),
+          hint: NoBareNodeStyleSpecifiersHint::UseUrl,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoBareNodeStyleSpecifiers,
+      options: json!({ "importMap": write_import_map("no_bare_node_style_specifiers_invalid") }),
+      r#"import { h } from "preact/hooks"; import { join } from "std/path/mod.ts";"#: [
+        {
+          col: 18,
+          message: variant!(NoBareNodeStyleSpecifiersMessage, Bare, "preact/hooks"),
+          hint: NoBareNodeStyleSpecifiersHint::UseUrl,
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_bare_node_style_specifiers_missing_import_map() {
+    let mut rule = NoBareNodeStyleSpecifiers::new();
+    assert!(rule
+      .set_options(json!({ "importMap": "does_not_exist.json" }))
+      .is_err());
+  }
+}
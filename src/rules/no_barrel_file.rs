@@ -0,0 +1,269 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_ecmascript::ast::{
+  ExportSpecifier, ImportSpecifier, Module, ModuleDecl, ModuleItem,
+};
+
+pub struct NoBarrelFile {
+  options: NoBarrelFileOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoBarrelFileOptions {
+  /// The maximum number of re-export declarations a module consisting only of
+  /// re-exports may have.
+  pub max_reexports: usize,
+}
+
+impl Default for NoBarrelFileOptions {
+  fn default() -> Self {
+    Self { max_reexports: 3 }
+  }
+}
+
+const CODE: &str = "no-barrel-file";
+
+#[derive(Display)]
+enum NoBarrelFileMessage {
+  #[display(
+    fmt = "This module only re-exports other modules ({} re-exports found, maximum allowed is {})",
+    _0,
+    _1
+  )]
+  BarrelFile(usize, usize),
+}
+
+#[derive(Display)]
+enum NoBarrelFileHint {
+  #[display(
+    fmt = "Import directly from the modules that declare the exports instead"
+  )]
+  ImportDirectly,
+}
+
+impl LintRule for NoBarrelFile {
+  fn new() -> Box<Self> {
+    Box::new(NoBarrelFile {
+      options: NoBarrelFileOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    if let ProgramRef::Module(module) = program {
+      if let Some(count) = count_reexports(module) {
+        if count > self.options.max_reexports {
+          context.add_diagnostic_with_hint(
+            module.span,
+            CODE,
+            NoBarrelFileMessage::BarrelFile(count, self.options.max_reexports),
+            NoBarrelFileHint::ImportDirectly,
+          );
+        }
+      }
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "maxReexports": {
+          "description": "The maximum number of re-export declarations a module consisting only of re-exports may have.",
+          "type": "integer",
+          "minimum": 0,
+          "default": 3
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      r#"Disallows modules that consist only of re-exports ("barrel files")"#,
+      "no-barrel-file"
+    )
+  }
+}
+
+/// Returns the number of re-export declarations if the module consists only
+/// of imports and re-exports, `None` otherwise.
+fn count_reexports(module: &Module) -> Option<usize> {
+  let imported: HashSet<&JsWord> = module
+    .body
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => Some(
+        import_decl
+          .specifiers
+          .iter()
+          .map(|specifier| match specifier {
+            ImportSpecifier::Named(named) => &named.local.sym,
+            ImportSpecifier::Default(default) => &default.local.sym,
+            ImportSpecifier::Namespace(namespace) => &namespace.local.sym,
+          }),
+      ),
+      _ => None,
+    })
+    .flatten()
+    .collect();
+
+  let mut count = 0;
+  for item in &module.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(_)) => {}
+      ModuleItem::ModuleDecl(ModuleDecl::ExportAll(_)) => count += 1,
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export))
+        if named_export.src.is_some() =>
+      {
+        count += 1
+      }
+      // `import { a } from "./a.ts"; export { a };`
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export))
+        if !named_export.specifiers.is_empty()
+          && named_export.specifiers.iter().all(|specifier| {
+          matches!(
+            specifier,
+            ExportSpecifier::Named(named) if imported.contains(&named.orig.sym)
+          )
+        }) =>
+      {
+        count += 1
+      }
+      _ => return None,
+    }
+  }
+
+  Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_barrel_file_valid() {
+    assert_lint_ok! {
+      NoBarrelFile,
+      "",
+      "export * from './a.ts';",
+      r#"
+export * from './a.ts';
+export * from './b.ts';
+export { c } from './c.ts';
+      "#,
+      r#"
+export * from './a.ts';
+export * from './b.ts';
+export * from './c.ts';
+export * from './d.ts';
+export const version = '1.0.0';
+      "#,
+      r#"
+import { a } from './a.ts';
+import { b } from './b.ts';
+import { c } from './c.ts';
+import { d } from './d.ts';
+export { a, b, c };
+export const e = d + 1;
+      "#,
+      r#"
+import { a } from './a.ts';
+export * from './b.ts';
+export * from './c.ts';
+export * from './d.ts';
+const e = 1;
+export { a, e };
+      "#,
+      r#"
+export * from './a.ts';
+export * from './b.ts';
+export * from './c.ts';
+export * from './d.ts';
+console.log('side effect');
+      "#,
+    };
+
+    assert_lint_ok! {
+      NoBarrelFile,
+      options: json!({ "maxReexports": 5 }),
+      r#"
+export * from './a.ts';
+export * from './b.ts';
+export * from './c.ts';
+export * from './d.ts';
+export * from './e.ts';
+      "#,
+    };
+  }
+
+  #[test]
+  fn no_barrel_file_invalid() {
+    assert_lint_err! {
+      NoBarrelFile,
+      r#"export * from './a.ts';
+export * from './b.ts';
+export { c } from './c.ts';
+export { d as default } from './d.ts';
+      "#: [
+        {
+          col: 0,
+          message: NoBarrelFileMessage::BarrelFile(4, 3),
+          hint: NoBarrelFileHint::ImportDirectly,
+        }
+      ],
+      r#"import { a } from './a.ts';
+import * as b from './b.ts';
+import c from './c.ts';
+export * from './d.ts';
+export { a };
+export { b, c };
+export { e } from './e.ts';
+      "#: [
+        {
+          col: 0,
+          message: NoBarrelFileMessage::BarrelFile(4, 3),
+          hint: NoBarrelFileHint::ImportDirectly,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoBarrelFile,
+      options: json!({ "maxReexports": 0 }),
+      "export * from './a.ts';": [
+        {
+          col: 0,
+          message: NoBarrelFileMessage::BarrelFile(1, 0),
+          hint: NoBarrelFileHint::ImportDirectly,
+        }
+      ],
+    };
+  }
+}
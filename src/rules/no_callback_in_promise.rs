@@ -0,0 +1,259 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{member_name, method_call};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoCallbackInPromise {
+  options: NoCallbackInPromiseOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoCallbackInPromiseOptions {
+  /// The names of the callbacks that shouldn't be called in promise handlers.
+  pub callbacks: Vec<String>,
+}
+
+impl Default for NoCallbackInPromiseOptions {
+  fn default() -> Self {
+    Self {
+      callbacks: ["callback", "cb", "done", "next"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect(),
+    }
+  }
+}
+
+const CODE: &str = "no-callback-in-promise";
+
+/// The promise methods whose handlers are checked.
+const PROMISE_METHODS: &[&str] = &["then", "catch"];
+
+/// The functions that call their callback later, outside of the promise
+/// chain.
+const DEFERRING_FUNCTIONS: &[&str] =
+  &["setTimeout", "setImmediate", "queueMicrotask", "nextTick"];
+
+#[derive(Display)]
+enum NoCallbackInPromiseMessage {
+  #[display(fmt = "`{}` is called in a promise handler", _0)]
+  Called(String),
+}
+
+#[derive(Display)]
+enum NoCallbackInPromiseHint {
+  #[display(
+    fmt = "If the callback throws, the promise is rejected and a `catch` handler may call it again; defer the call, e.g. with `setTimeout`"
+  )]
+  Defer,
+}
+
+impl LintRule for NoCallbackInPromise {
+  fn new() -> Box<Self> {
+    Box::new(NoCallbackInPromise {
+      options: NoCallbackInPromiseOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoCallbackInPromiseVisitor {
+      context,
+      callbacks: &self.options.callbacks,
+      in_handler: false,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "callbacks": {
+          "description": "The names of the callbacks that shouldn't be called in promise handlers.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": NoCallbackInPromiseOptions::default().callbacks
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows calling callbacks in `then` and `catch` handlers",
+      "no-callback-in-promise"
+    )
+  }
+}
+
+fn is_function(expr: &Expr) -> bool {
+  match expr {
+    Expr::Arrow(_) | Expr::Fn(_) => true,
+    Expr::Paren(paren) => is_function(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns the name of the called function or method, e.g. `nextTick` for
+/// `process.nextTick()`.
+fn callee_name(call_expr: &CallExpr) -> Option<&str> {
+  match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => match &**callee {
+      Expr::Ident(ident) => Some(&ident.sym),
+      Expr::Member(member) => member_name(member),
+      _ => None,
+    },
+    ExprOrSuper::Super(_) => None,
+  }
+}
+
+struct NoCallbackInPromiseVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  callbacks: &'c [String],
+  /// Whether the visited code runs in a `then` or `catch` handler.
+  in_handler: bool,
+}
+
+impl<'c, 'view> NoCallbackInPromiseVisitor<'c, 'view> {
+  /// Visits the arguments of a call, with `in_handler` set for the functions
+  /// among them.
+  fn visit_args(&mut self, call_expr: &CallExpr, in_handler: bool) {
+    call_expr.callee.visit_with(&DUMMY_NODE, self);
+    for arg in &call_expr.args {
+      let prev = self.in_handler;
+      if is_function(&arg.expr) {
+        self.in_handler = in_handler;
+      }
+      arg.visit_with(&DUMMY_NODE, self);
+      self.in_handler = prev;
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoCallbackInPromiseVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Ident(ident) = &**callee {
+        if self.in_handler && self.callbacks.iter().any(|cb| *cb == *ident.sym)
+        {
+          self.context.add_diagnostic_with_hint(
+            call_expr.span,
+            CODE,
+            NoCallbackInPromiseMessage::Called(ident.sym.to_string()),
+            NoCallbackInPromiseHint::Defer,
+          );
+        }
+      }
+    }
+
+    match (method_call(call_expr), callee_name(call_expr)) {
+      (Some((_, method, _)), _) if PROMISE_METHODS.contains(&method) => {
+        self.visit_args(call_expr, true)
+      }
+      (_, Some(name)) if DEFERRING_FUNCTIONS.contains(&name) => {
+        self.visit_args(call_expr, false)
+      }
+      _ => call_expr.visit_children_with(self),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_callback_in_promise_valid() {
+    assert_lint_ok! {
+      NoCallbackInPromise,
+      "function foo(cb) { a().then((data) => data.b); cb(); }",
+      "function foo(cb) { fs.readFile(path, (err, data) => cb(err, data)); }",
+      "function foo(cb) { a().then((data) => { setTimeout(() => cb(null, data)); }); }",
+      "function foo(next) { a().catch((err) => process.nextTick(next, err)); }",
+      "function foo(cb) { a().then((data) => callbackify(data)); }",
+      "function foo(cb) { a().then(cb); }",
+      "function foo(cb) { a().finally(() => cb()); }",
+      "function foo(cb) { cb.then(() => {}); }",
+    };
+
+    assert_lint_ok! {
+      NoCallbackInPromise,
+      options: json!({ "callbacks": ["reply"] }),
+      "function foo(cb) { a().then((data) => cb(null, data)); }",
+    };
+  }
+
+  #[test]
+  fn no_callback_in_promise_invalid() {
+    assert_lint_err! {
+      NoCallbackInPromise,
+      "function foo(cb) { a().then((data) => cb(null, data)); }": [
+        {
+          col: 38,
+          message: variant!(NoCallbackInPromiseMessage, Called, "cb"),
+          hint: NoCallbackInPromiseHint::Defer,
+        }
+      ],
+      "function foo(next) { a().then(() => {}).catch(function (err) { next(err); }); }": [
+        {
+          col: 63,
+          message: variant!(NoCallbackInPromiseMessage, Called, "next"),
+          hint: NoCallbackInPromiseHint::Defer,
+        }
+      ],
+      "function foo(done) { a().then((items) => items.forEach((item) => done(item))); }": [
+        {
+          col: 65,
+          message: variant!(NoCallbackInPromiseMessage, Called, "done"),
+          hint: NoCallbackInPromiseHint::Defer,
+        }
+      ],
+      "function foo(callback) { a().then(() => b().then(() => callback())); }": [
+        {
+          col: 55,
+          message: variant!(NoCallbackInPromiseMessage, Called, "callback"),
+          hint: NoCallbackInPromiseHint::Defer,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoCallbackInPromise,
+      options: json!({ "callbacks": ["reply"] }),
+      "function foo(reply) { a().then((data) => reply(data)); }": [
+        {
+          col: 41,
+          message: variant!(NoCallbackInPromiseMessage, Called, "reply"),
+          hint: NoCallbackInPromiseHint::Defer,
+        }
+      ],
+    };
+  }
+}
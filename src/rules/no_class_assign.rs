@@ -26,6 +26,10 @@ impl LintRule for NoClassAssign {
     CODE
   }
 
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -39,25 +43,10 @@ impl LintRule for NoClassAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows modifying variables of class declarations
-
-Declaring a class such as `class A{}`, creates a variable `A`.  Like any variable
-this can be modified or reassigned. In most cases this is a mistake and not what
-was intended.
-
-### Invalid:
-```typescript
-class A {}
-A = 0;  // reassigning the class variable itself
-```
-    
-### Valid:
-```typescript
-class A{}
-let c = new A();
-c = 0;  // reassigning the variable `c`
-```
-"#
+    rule_docs!(
+      "Disallows modifying variables of class declarations",
+      "no-class-assign"
+    )
   }
 }
 
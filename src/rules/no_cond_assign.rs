@@ -52,40 +52,10 @@ impl LintRule for NoCondAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the use of the assignment operator, `=`, in conditional statements.
-
-Use of the assignment operator within a conditional statement is often the result of mistyping the equality operator, `==`. If an assignment within a conditional statement is required then this rule allows it by wrapping the assignment in parentheses.
-
-### Invalid:
-```typescript
-var x;
-if (x = 0) {
-  var b = 1;
-}
-```
-```typescript
-function setHeight(someNode) {
-  do {
-    someNode.height = "100px";
-  } while (someNode = someNode.parentNode);
-}
-```
-
-### Valid:
-```typescript
-var x;
-if (x === 0) {
-  var b = 1;
-}
-```
-```typescript
-function setHeight(someNode) {
-  do {
-    someNode.height = "100px";
-  } while ((someNode = someNode.parentNode));
-}
-```
-"#
+    rule_docs!(
+      "Disallows the use of the assignment operator, `=`, in conditional statements.",
+      "no-cond-assign"
+    )
   }
 }
 
@@ -0,0 +1,166 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_common::BytePos;
+use swc_ecmascript::ast::{BinExpr, BinaryOp, Expr};
+use swc_ecmascript::visit::{noop_visit_type, Node, VisitAll, VisitAllWith};
+
+pub struct NoConfusingNonNullAssertion;
+
+const CODE: &str = "no-confusing-non-null-assertion";
+
+#[derive(Display)]
+enum NoConfusingNonNullAssertionMessage {
+  #[display(
+    fmt = "Non-null assertion before `{}` is easily confused with `!{}`",
+    _0,
+    _0
+  )]
+  Confusing(String),
+}
+
+#[derive(Display)]
+enum NoConfusingNonNullAssertionHint {
+  #[display(
+    fmt = "Wrap the left-hand side in parentheses, or remove the non-null assertion if it isn't needed"
+  )]
+  WrapOrRemove,
+}
+
+#[derive(Display)]
+enum NoConfusingNonNullAssertionFix {
+  #[display(fmt = "Wrap the left-hand side in parentheses")]
+  Wrap,
+  #[display(fmt = "Remove the non-null assertion")]
+  Remove,
+}
+
+impl LintRule for NoConfusingNonNullAssertion {
+  fn new() -> Box<Self> {
+    Box::new(NoConfusingNonNullAssertion)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoConfusingNonNullAssertionVisitor { context };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows non-null assertions right before an equality operator",
+      "no-confusing-non-null-assertion"
+    )
+  }
+}
+
+struct NoConfusingNonNullAssertionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> VisitAll for NoConfusingNonNullAssertionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    let operator = match bin_expr.op {
+      BinaryOp::EqEq => "==",
+      BinaryOp::EqEqEq => "===",
+      _ => return,
+    };
+    let left = match &*bin_expr.left {
+      Expr::TsNonNull(non_null) => non_null.span,
+      _ => return,
+    };
+
+    // Wrapping keeps the meaning of the comparison, so it's the first fix
+    let wrap = self.context.create_fix(
+      NoConfusingNonNullAssertionFix::Wrap,
+      vec![
+        (left.with_hi(left.lo), "(".to_string()),
+        (left.with_lo(left.hi), ")".to_string()),
+      ],
+    );
+    let remove = self.context.create_fix(
+      NoConfusingNonNullAssertionFix::Remove,
+      vec![(left.with_lo(left.hi - BytePos(1)), "".to_string())],
+    );
+    self.context.add_diagnostic_with_fixes(
+      bin_expr.span,
+      CODE,
+      NoConfusingNonNullAssertionMessage::Confusing(operator.to_string()),
+      Some(NoConfusingNonNullAssertionHint::WrapOrRemove.to_string()),
+      vec![wrap, remove],
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_confusing_non_null_assertion_valid() {
+    assert_lint_ok! {
+      NoConfusingNonNullAssertion,
+      "a == b;",
+      "a !== b;",
+      "a! !== b;",
+      "(a!) == b;",
+      "a == b!;",
+      "a! + b === c;",
+      "a!==b;",
+      "a!.b === c;",
+    };
+  }
+
+  #[test]
+  fn no_confusing_non_null_assertion_invalid() {
+    assert_lint_err! {
+      NoConfusingNonNullAssertion,
+      "a! == b;": [
+        {
+          col: 0,
+          message: variant!(NoConfusingNonNullAssertionMessage, Confusing, "=="),
+          hint: NoConfusingNonNullAssertionHint::WrapOrRemove,
+          fix: (NoConfusingNonNullAssertionFix::Wrap, "(a!) == b;"),
+        }
+      ],
+      "if (a.b! === c) {}": [
+        {
+          col: 4,
+          message: variant!(NoConfusingNonNullAssertionMessage, Confusing, "==="),
+          hint: NoConfusingNonNullAssertionHint::WrapOrRemove,
+          fix: (NoConfusingNonNullAssertionFix::Wrap, "if ((a.b!) === c) {}"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_confusing_non_null_assertion_remove() {
+    use crate::fix::apply_suggestion;
+    use crate::test_util::lint;
+
+    let source = "a! === b;";
+    let diagnostics = lint(
+      NoConfusingNonNullAssertion::new(),
+      source,
+      "file.ts".to_string(),
+    );
+    assert_eq!(apply_suggestion(source, &diagnostics[0], 1), "a === b;");
+  }
+}
@@ -0,0 +1,236 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::member_name;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{CatchClause, Expr, ExprOrSuper, Stmt};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoConsoleErrorSwallowing {
+  options: NoConsoleErrorSwallowingOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoConsoleErrorSwallowingOptions {
+  /// The `console` methods that don't count as handling an error.
+  pub methods: Vec<String>,
+}
+
+impl Default for NoConsoleErrorSwallowingOptions {
+  fn default() -> Self {
+    Self {
+      methods: vec!["log".to_string(), "error".to_string(), "warn".to_string()],
+    }
+  }
+}
+
+const CODE: &str = "no-console-error-swallowing";
+
+#[derive(Display)]
+enum NoConsoleErrorSwallowingMessage {
+  #[display(fmt = "The error is logged with `console.{}` and swallowed", _0)]
+  Swallowed(String),
+}
+
+#[derive(Display)]
+enum NoConsoleErrorSwallowingHint {
+  #[display(
+    fmt = "Rethrow the error, wrap it with a `cause`, or handle it, so the caller can tell the operation failed"
+  )]
+  HandleOrRethrow,
+}
+
+impl LintRule for NoConsoleErrorSwallowing {
+  fn new() -> Box<Self> {
+    Box::new(NoConsoleErrorSwallowing {
+      options: NoConsoleErrorSwallowingOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoConsoleErrorSwallowingVisitor {
+      context,
+      methods: &self.options.methods,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "methods": {
+          "description": "The `console` methods that don't count as handling an error.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": NoConsoleErrorSwallowingOptions::default().methods
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows `catch` blocks that only log the error to the console",
+      "no-console-error-swallowing"
+    )
+  }
+}
+
+struct NoConsoleErrorSwallowingVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  methods: &'c [String],
+}
+
+impl<'c, 'view> NoConsoleErrorSwallowingVisitor<'c, 'view> {
+  /// Returns the method if the statement is a call to one of the configured
+  /// methods of the global `console`, e.g. `console.error(err);`.
+  fn console_method<'a>(&self, stmt: &'a Stmt) -> Option<&'a str> {
+    let call = match stmt {
+      Stmt::Expr(expr_stmt) => match &*expr_stmt.expr {
+        Expr::Call(call) => call,
+        _ => return None,
+      },
+      _ => return None,
+    };
+    let member = match &call.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) => member,
+        _ => return None,
+      },
+      ExprOrSuper::Super(_) => return None,
+    };
+    let is_console = matches!(
+      &member.obj,
+      ExprOrSuper::Expr(obj) if matches!(
+        &**obj,
+        Expr::Ident(ident)
+          if ident.sym == *"console"
+            && self.context.scope().var(&ident.to_id()).is_none()
+      )
+    );
+    if !is_console {
+      return None;
+    }
+    member_name(member)
+      .filter(|method| self.methods.iter().any(|name| name == method))
+  }
+}
+
+impl<'c, 'view> Visit for NoConsoleErrorSwallowingVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_catch_clause(&mut self, catch_clause: &CatchClause, _: &dyn Node) {
+    if let [stmt] = catch_clause.body.stmts.as_slice() {
+      if let Some(method) = self.console_method(stmt) {
+        self.context.add_diagnostic_with_hint(
+          catch_clause.span,
+          CODE,
+          NoConsoleErrorSwallowingMessage::Swallowed(method.to_string()),
+          NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        );
+      }
+    }
+    catch_clause.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_console_error_swallowing_valid() {
+    assert_lint_ok! {
+      NoConsoleErrorSwallowing,
+      "try { foo(); } catch (err) { console.error(err); throw err; }",
+      "try { foo(); } catch (err) { throw new Error('foo failed', { cause: err }); }",
+      "try { foo(); } catch (err) { console.error(err); return null; }",
+      "try { foo(); } catch (err) { logger.error(err); }",
+      "try { foo(); } catch (err) { console.trace(err); }",
+      "try { foo(); } catch {}",
+      "const console = createLogger(); try { foo(); } catch (err) { console.error(err); }",
+      "try { foo(); } catch (err) { if (err instanceof NotFound) { console.log(err); } else { throw err; } }",
+    };
+
+    assert_lint_ok! {
+      NoConsoleErrorSwallowing,
+      options: json!({ "methods": ["log"] }),
+      "try { foo(); } catch (err) { console.error(err); }",
+    };
+  }
+
+  #[test]
+  fn no_console_error_swallowing_invalid() {
+    assert_lint_err! {
+      NoConsoleErrorSwallowing,
+      "try { foo(); } catch (err) { console.error(err); }": [
+        {
+          col: 15,
+          message: variant!(NoConsoleErrorSwallowingMessage, Swallowed, "error"),
+          hint: NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        }
+      ],
+      "try { foo(); } catch (err) { console.log('foo failed:', err.message); }": [
+        {
+          col: 15,
+          message: variant!(NoConsoleErrorSwallowingMessage, Swallowed, "log"),
+          hint: NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        }
+      ],
+      "try { foo(); } catch { console.warn('foo failed'); }": [
+        {
+          col: 15,
+          message: variant!(NoConsoleErrorSwallowingMessage, Swallowed, "warn"),
+          hint: NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        }
+      ],
+      "async function f() { try { await foo(); } catch (err) { try { undo(); } catch (e) { console.error(e); } throw err; } }": [
+        {
+          col: 72,
+          message: variant!(NoConsoleErrorSwallowingMessage, Swallowed, "error"),
+          hint: NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoConsoleErrorSwallowing,
+      options: json!({ "methods": ["debug"] }),
+      "try { foo(); } catch (err) { console.debug(err); }": [
+        {
+          col: 15,
+          message: variant!(NoConsoleErrorSwallowingMessage, Swallowed, "debug"),
+          hint: NoConsoleErrorSwallowingHint::HandleOrRethrow,
+        }
+      ],
+    };
+  }
+}
@@ -22,6 +22,10 @@ impl LintRule for NoConstAssign {
     "no-const-assign"
   }
 
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -35,28 +39,10 @@ impl LintRule for NoConstAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows modifying a variable declared as `const`.
-
-Modifying a variable declared as `const` will result in a runtime error.
-
-### Invalid:
-```typescript
-const a = 0;
-a = 1;
-a += 1;
-a++;
-++a;
-```
-
-### Valid:
-```typescript
-const a = 0;
-const b = a + 1;
-
-// `c` is out of scope on each loop iteration, allowing a new assignment
-for (const c in [1,2,3]) {}
-```
-"#
+    rule_docs!(
+      "Disallows modifying a variable declared as `const`.",
+      "no-const-assign"
+    )
   }
 }
 
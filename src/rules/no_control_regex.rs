@@ -44,6 +44,10 @@ impl LintRule for NoControlRegex {
     CODE
   }
 
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -57,30 +61,10 @@ impl LintRule for NoControlRegex {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the use ascii control characters in regular expressions
-
-Control characters are invisible characters in the ASCII range of 0-31.  It is
-uncommon to use these in a regular expression and more often it is a mistake
-in the regular expression.
-    
-### Invalid:
-```typescript
-// Examples using ASCII (31) Carriage Return (hex x0d)
-const pattern1 = /\x0d/;
-const pattern2 = /\u000d/;
-const pattern3 = new RegExp("\\x0d");
-const pattern4 = new RegExp("\\u000d");
-```
-
-### Valid:
-```typescript
-// Examples using ASCII (32) Space (hex x20)
-const pattern1 = /\x20/;
-const pattern2 = /\u0020/;
-const pattern3 = new RegExp("\\x20");
-const pattern4 = new RegExp("\\u0020");
-```
-"#
+    rule_docs!(
+      "Disallows the use ascii control characters in regular expressions",
+      "no-control-regex"
+    )
   }
 }
 
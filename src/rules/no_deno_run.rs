@@ -0,0 +1,251 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  deno_command_subprocess, deno_run_subprocess, DenoSubprocess,
+};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{CallExpr, Expr, Lit, NewExpr};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoDenoRun {
+  options: NoDenoRunOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoDenoRunOptions {
+  /// Programs that may be run as subprocesses, e.g. `git`.
+  pub allow: Vec<String>,
+}
+
+const CODE: &str = "no-deno-run";
+
+#[derive(Display)]
+enum NoDenoRunMessage {
+  #[display(fmt = "Running '{}' as a subprocess is not allowed", _0)]
+  Program(String),
+  #[display(
+    fmt = "Running a subprocess whose program is not known statically"
+  )]
+  UnknownProgram,
+}
+
+#[derive(Display)]
+enum NoDenoRunHint {
+  #[display(
+    fmt = "Add the program to the `allow` option once its use has been audited"
+  )]
+  AllowProgram,
+  #[display(
+    fmt = "Use a string literal for the program, so it can be checked against the `allow` option"
+  )]
+  UseLiteral,
+}
+
+impl LintRule for NoDenoRun {
+  fn new() -> Box<Self> {
+    Box::new(NoDenoRun {
+      options: NoDenoRunOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoDenoRunVisitor {
+      context,
+      allow: &self.options.allow,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "Programs that may be run as subprocesses, e.g. `git`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows running subprocesses other than the allowed programs",
+      "no-deno-run"
+    )
+  }
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+      Some(tpl.quasis.iter().map(|q| q.raw.value.to_string()).collect())
+    }
+    _ => None,
+  }
+}
+
+/// Returns the file name of a program, e.g. `git` for `/usr/bin/git` and
+/// `git.exe`.
+fn program_name(program: &str) -> &str {
+  let name = program.rsplit(&['/', '\\'][..]).next().unwrap();
+  name.strip_suffix(".exe").unwrap_or(name)
+}
+
+struct NoDenoRunVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [String],
+}
+
+impl<'c, 'view> NoDenoRunVisitor<'c, 'view> {
+  fn check_subprocess(&mut self, subprocess: DenoSubprocess, span: Span) {
+    let program = match subprocess.program {
+      Some(program) => program,
+      None => {
+        self.context.add_diagnostic_with_hint(
+          span,
+          CODE,
+          NoDenoRunMessage::UnknownProgram,
+          NoDenoRunHint::UseLiteral,
+        );
+        return;
+      }
+    };
+    match string_value(program) {
+      Some(value) => {
+        let name = program_name(&value);
+        if !self.allow.iter().any(|allowed| allowed == name) {
+          self.context.add_diagnostic_with_hint(
+            program.span(),
+            CODE,
+            NoDenoRunMessage::Program(name.to_string()),
+            NoDenoRunHint::AllowProgram,
+          );
+        }
+      }
+      None => {
+        self.context.add_diagnostic_with_hint(
+          program.span(),
+          CODE,
+          NoDenoRunMessage::UnknownProgram,
+          NoDenoRunHint::UseLiteral,
+        );
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoDenoRunVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let Some(subprocess) = deno_run_subprocess(call_expr) {
+      self.check_subprocess(subprocess, call_expr.span);
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    if let Some(subprocess) = deno_command_subprocess(new_expr) {
+      self.check_subprocess(subprocess, new_expr.span);
+    }
+    new_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_deno_run_valid() {
+    assert_lint_ok! {
+      NoDenoRun,
+      "new Command('git', { args: ['status'] });",
+      "run({ cmd: ['git', 'status'] });",
+      "const run = Deno.readTextFile('run.ts');",
+    };
+
+    assert_lint_ok! {
+      NoDenoRun,
+      options: json!({ "allow": ["git", "deno"] }),
+      "new Deno.Command('git', { args: ['status'] });",
+      "new Deno.Command(`/usr/bin/git`, { args: ['log'] });",
+      "Deno.run({ cmd: ['C:\\\\Deno\\\\deno.exe', 'fmt'] });",
+    };
+  }
+
+  #[test]
+  fn no_deno_run_invalid() {
+    assert_lint_err! {
+      NoDenoRun,
+      "new Deno.Command('git', { args: ['status'] });": [
+        {
+          col: 17,
+          message: variant!(NoDenoRunMessage, Program, "git"),
+          hint: NoDenoRunHint::AllowProgram,
+        }
+      ],
+      "Deno.run({ cmd: ['/bin/rm', '-rf', dir] });": [
+        {
+          col: 17,
+          message: variant!(NoDenoRunMessage, Program, "rm"),
+          hint: NoDenoRunHint::AllowProgram,
+        }
+      ],
+      "new Deno.Command(program);": [
+        {
+          col: 17,
+          message: NoDenoRunMessage::UnknownProgram,
+          hint: NoDenoRunHint::UseLiteral,
+        }
+      ],
+      "Deno.run(options);": [
+        {
+          col: 0,
+          message: NoDenoRunMessage::UnknownProgram,
+          hint: NoDenoRunHint::UseLiteral,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoDenoRun,
+      options: json!({ "allow": ["git"] }),
+      "new Deno.Command('git'); new Deno.Command('curl', { args: [url] });": [
+        {
+          col: 42,
+          message: variant!(NoDenoRunMessage, Program, "curl"),
+          hint: NoDenoRunHint::AllowProgram,
+        }
+      ],
+    };
+  }
+}
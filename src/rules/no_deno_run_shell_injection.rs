@@ -0,0 +1,305 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  deno_command_subprocess, deno_run_subprocess, is_interpolated_string,
+  DenoSubprocess,
+};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{CallExpr, Expr, Lit, NewExpr};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoDenoRunShellInjection {
+  options: NoDenoRunShellInjectionOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoDenoRunShellInjectionOptions {
+  /// Programs whose arguments may be built dynamically, e.g. `git`.
+  pub allow: Vec<String>,
+}
+
+const CODE: &str = "no-deno-run-shell-injection";
+
+#[derive(Display)]
+enum NoDenoRunShellInjectionMessage {
+  #[display(fmt = "Shell command is built from interpolated values")]
+  ShellCommand,
+  #[display(fmt = "Argument of '{}' is built from interpolated values", _0)]
+  Argument(String),
+}
+
+#[derive(Display)]
+enum NoDenoRunShellInjectionHint {
+  #[display(
+    fmt = "Run the program directly and pass each value as a separate argument instead of going through a shell"
+  )]
+  AvoidShell,
+  #[display(
+    fmt = "Pass each value as a separate argument, or add the program to the `allow` option if its arguments are validated"
+  )]
+  SeparateArguments,
+}
+
+impl LintRule for NoDenoRunShellInjection {
+  fn new() -> Box<Self> {
+    Box::new(NoDenoRunShellInjection {
+      options: NoDenoRunShellInjectionOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoDenoRunShellInjectionVisitor {
+      context,
+      allow: &self.options.allow,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "Programs whose arguments may be built dynamically, e.g. `git`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows subprocess arguments and shell commands built from interpolated values",
+      "no-deno-run-shell-injection"
+    )
+  }
+}
+
+struct NoDenoRunShellInjectionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [String],
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+      Some(tpl.quasis.iter().map(|q| q.raw.value.to_string()).collect())
+    }
+    _ => None,
+  }
+}
+
+/// Returns the file name of a program, e.g. `bash` for `/usr/bin/bash` and
+/// `cmd` for `C:\Windows\System32\cmd.exe`.
+fn program_name(program: &str) -> &str {
+  let name = program.rsplit(&['/', '\\'][..]).next().unwrap();
+  name.strip_suffix(".exe").unwrap_or(name)
+}
+
+fn is_shell(name: &str) -> bool {
+  matches!(
+    name,
+    "sh"
+      | "bash"
+      | "zsh"
+      | "dash"
+      | "ksh"
+      | "fish"
+      | "cmd"
+      | "powershell"
+      | "pwsh"
+  )
+}
+
+/// Returns `true` for the flags making a shell run its next argument as a
+/// command, e.g. `-c`, `-lc` and `/c`.
+fn is_command_flag(arg: &str) -> bool {
+  match arg {
+    "/c" | "/C" | "-Command" => true,
+    _ => match arg.strip_prefix('-') {
+      Some(flags) => {
+        flags.ends_with('c') && flags.chars().all(|c| c.is_ascii_lowercase())
+      }
+      None => false,
+    },
+  }
+}
+
+impl<'c, 'view> NoDenoRunShellInjectionVisitor<'c, 'view> {
+  fn check_subprocess(&mut self, subprocess: DenoSubprocess) {
+    let program = subprocess.program.and_then(string_value);
+    let name = program.as_deref().map(program_name);
+    if let Some(name) = name {
+      if self.allow.iter().any(|allowed| allowed == name) {
+        return;
+      }
+    }
+
+    if matches!(name, Some(name) if is_shell(name)) {
+      // Arguments following the command are passed to it as positional
+      // parameters, which is the safe way of using values in shell scripts
+      let command = subprocess.args.windows(2).find_map(|pair| {
+        match string_value(pair[0]) {
+          Some(flag) if is_command_flag(&flag) => Some(pair[1]),
+          _ => None,
+        }
+      });
+      if let Some(command) = command {
+        if is_interpolated_string(command) {
+          self.context.add_diagnostic_with_hint(
+            command.span(),
+            CODE,
+            NoDenoRunShellInjectionMessage::ShellCommand,
+            NoDenoRunShellInjectionHint::AvoidShell,
+          );
+        }
+      }
+      return;
+    }
+
+    let name = name.unwrap_or("subprocess").to_string();
+    for arg in subprocess.args {
+      if is_interpolated_string(arg) {
+        self.context.add_diagnostic_with_hint(
+          arg.span(),
+          CODE,
+          NoDenoRunShellInjectionMessage::Argument(name.clone()),
+          NoDenoRunShellInjectionHint::SeparateArguments,
+        );
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoDenoRunShellInjectionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let Some(subprocess) = deno_run_subprocess(call_expr) {
+      self.check_subprocess(subprocess);
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    if let Some(subprocess) = deno_command_subprocess(new_expr) {
+      self.check_subprocess(subprocess);
+    }
+    new_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_deno_run_shell_injection_valid() {
+    assert_lint_ok! {
+      NoDenoRunShellInjection,
+      "new Deno.Command('git', { args: ['log', '--author', author] });",
+      "new Deno.Command('git', { args: ['log', `--oneline`, 'a' + 'b'] });",
+      "new Deno.Command(program, { args });",
+      "Deno.run({ cmd: ['ls', '-la', dir] });",
+      "Deno.run({ cmd: ['sh', '-c', 'ls \"$1\"', 'sh', dir] });",
+      "new Deno.Command('sh', { args: ['-c', 'echo \"$1\"', 'sh', `${name}!`] });",
+      "new Command('git', { args: [`--author=${author}`] });",
+      "run({ cmd: ['sh', '-c', `ls ${dir}`] });",
+    };
+
+    assert_lint_ok! {
+      NoDenoRunShellInjection,
+      options: json!({ "allow": ["git"] }),
+      "new Deno.Command('git', { args: [`--author=${author}`] });",
+      "new Deno.Command('/usr/bin/git', { args: ['-C', root + '/repo'] });",
+    };
+  }
+
+  #[test]
+  fn no_deno_run_shell_injection_invalid() {
+    assert_lint_err! {
+      NoDenoRunShellInjection,
+      "new Deno.Command('sh', { args: ['-c', `ls ${dir}`] });": [
+        {
+          col: 38,
+          message: NoDenoRunShellInjectionMessage::ShellCommand,
+          hint: NoDenoRunShellInjectionHint::AvoidShell,
+        }
+      ],
+      "Deno.run({ cmd: ['/bin/bash', '-lc', 'rm -rf ' + dir] });": [
+        {
+          col: 37,
+          message: NoDenoRunShellInjectionMessage::ShellCommand,
+          hint: NoDenoRunShellInjectionHint::AvoidShell,
+        }
+      ],
+      "new Deno.Command('cmd.exe', { args: ['/c', `del ${file}`] });": [
+        {
+          col: 43,
+          message: NoDenoRunShellInjectionMessage::ShellCommand,
+          hint: NoDenoRunShellInjectionHint::AvoidShell,
+        }
+      ],
+      "new Deno.Command('git', { args: ['log', `--author=${author}`] });": [
+        {
+          col: 40,
+          message: variant!(NoDenoRunShellInjectionMessage, Argument, "git"),
+          hint: NoDenoRunShellInjectionHint::SeparateArguments,
+        }
+      ],
+      "Deno.run({ cmd: ['curl', 'https://' + host + '/api'] });": [
+        {
+          col: 25,
+          message: variant!(NoDenoRunShellInjectionMessage, Argument, "curl"),
+          hint: NoDenoRunShellInjectionHint::SeparateArguments,
+        }
+      ],
+      "new Deno.Command(program, { args: [`--out=${out}`] });": [
+        {
+          col: 35,
+          message: variant!(NoDenoRunShellInjectionMessage, Argument, "subprocess"),
+          hint: NoDenoRunShellInjectionHint::SeparateArguments,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoDenoRunShellInjection,
+      options: json!({ "allow": ["git"] }),
+      "new Deno.Command('curl', { args: [`${host}/api`] });": [
+        {
+          col: 34,
+          message: variant!(NoDenoRunShellInjectionMessage, Argument, "curl"),
+          hint: NoDenoRunShellInjectionHint::SeparateArguments,
+        }
+      ],
+    };
+  }
+}
@@ -25,6 +25,14 @@ impl LintRule for NoDeprecatedDenoApi {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
     unreachable!();
   }
@@ -38,63 +46,10 @@ impl LintRule for NoDeprecatedDenoApi {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Warns the usage of the deprecated Deno APIs
-
-The following APIs in `Deno` namespace are now marked as deprecated and will get
-removed from the namespace in the future.
-
-- `Deno.Buffer`
-- `Deno.readAll`
-- `Deno.readAllSync`
-- `Deno.writeAll`
-- `Deno.writeAllSync`
-- `Deno.iter`
-- `Deno.iterSync`
-
-They are already available in `std`, so replace these deprecated ones with
-alternatives from `std`.
-For more detail, see [the tracking issue](https://github.com/denoland/deno/issues/9795).
-
-### Invalid:
-```typescript
-// buffer
-const a = Deno.Buffer();
-
-// read
-const b = await Deno.readAll(reader);
-const c = Deno.readAllSync(reader);
-
-// write
-await Deno.writeAll(writer, data);
-Deno.writeAllSync(writer, data);
-
-// iter
-for await (const x of Deno.iter(xs)) {}
-for (const y of Deno.iterSync(ys)) {}
-```
-
-### Valid:
-```typescript
-// buffer
-import { Buffer } from "https://deno.land/std/io/buffer.ts";
-const a = new Buffer();
-
-// read
-import { readAll, readAllSync } from "https://deno.land/std/io/util.ts";
-const b = await readAll(reader);
-const c = readAllSync(reader);
-
-// write
-import { writeAll, writeAllSync } from "https://deno.land/std/io/util.ts";
-await writeAll(writer, data);
-writeAllSync(writer, data);
-
-// iter
-import { iter, iterSync } from "https://deno.land/std/io/util.ts";
-for await (const x of iter(xs)) {}
-for (const y of iterSync(ys)) {}
-```
-"#
+    rule_docs!(
+      "Warns the usage of the deprecated Deno APIs",
+      "no-deprecated-deno-api"
+    )
   }
 }
 
@@ -0,0 +1,251 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoDeprecatedStdModules;
+
+const CODE: &str = "no-deprecated-std-modules";
+
+/// What replaces a module removed from the standard library.
+#[derive(Clone, Copy)]
+enum Replacement {
+  /// Another module of the standard library, with the same exports.
+  Module(&'static str),
+  /// A runtime API, or another way of doing the same.
+  Api(&'static str),
+}
+
+/// Modules removed from `deno.land/std`, along with their replacement. Paths
+/// ending with `/` cover all the modules of a directory.
+static REMOVED_MODULES: &[(&str, Replacement)] = &[
+  ("encoding/csv.ts", Replacement::Module("csv/mod.ts")),
+  (
+    "encoding/front_matter.ts",
+    Replacement::Module("front_matter/mod.ts"),
+  ),
+  (
+    "encoding/json_streams.ts",
+    Replacement::Module("json/mod.ts"),
+  ),
+  ("encoding/jsonc.ts", Replacement::Module("jsonc/mod.ts")),
+  ("encoding/toml.ts", Replacement::Module("toml/mod.ts")),
+  (
+    "encoding/utf8.ts",
+    Replacement::Api("`TextEncoder` and `TextDecoder`"),
+  ),
+  ("encoding/yaml.ts", Replacement::Module("yaml/mod.ts")),
+  ("flags/mod.ts", Replacement::Module("cli/parse_args.ts")),
+  ("hash/", Replacement::Module("crypto/mod.ts")),
+  ("http/http_status.ts", Replacement::Module("http/status.ts")),
+  ("http/server.ts", Replacement::Api("`Deno.serve()`")),
+  ("io/bufio.ts", Replacement::Module("io/buffer.ts")),
+  ("io/ioutil.ts", Replacement::Module("streams/mod.ts")),
+  ("io/streams.ts", Replacement::Module("streams/mod.ts")),
+  (
+    "node/",
+    Replacement::Api("`node:` specifiers, e.g. `node:fs`"),
+  ),
+  ("permissions/mod.ts", Replacement::Api("`Deno.permissions`")),
+  (
+    "signal/mod.ts",
+    Replacement::Api("`Deno.addSignalListener()`"),
+  ),
+  ("testing/asserts.ts", Replacement::Module("assert/mod.ts")),
+  (
+    "textproto/mod.ts",
+    Replacement::Api("`TextLineStream` of `streams/mod.ts`"),
+  ),
+  ("ws/mod.ts", Replacement::Api("`Deno.upgradeWebSocket()`")),
+];
+
+#[derive(Display)]
+enum NoDeprecatedStdModulesMessage {
+  #[display(fmt = "`std/{}` has been removed from the standard library", _0)]
+  Removed(String),
+}
+
+#[derive(Display)]
+enum NoDeprecatedStdModulesHint {
+  #[display(fmt = "Import `std/{}` instead", _0)]
+  Module(String),
+  #[display(fmt = "Use {} instead", _0)]
+  Api(String),
+}
+
+impl LintRule for NoDeprecatedStdModules {
+  fn new() -> Box<Self> {
+    Box::new(NoDeprecatedStdModules)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoDeprecatedStdModulesVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows importing modules removed from the standard library",
+      "no-deprecated-std-modules"
+    )
+  }
+}
+
+/// Returns the path of a module of `deno.land/std`, e.g. `fs/mod.ts` for
+/// `https://deno.land/std@0.100.0/fs/mod.ts`.
+fn std_module_path(specifier: &str) -> Option<&str> {
+  let rest = specifier.split("deno.land/std").nth(1)?;
+  let rest = match rest.strip_prefix('@') {
+    Some(versioned) => &versioned[versioned.find('/')?..],
+    None => rest,
+  };
+  rest.strip_prefix('/')
+}
+
+fn replacement(path: &str) -> Option<Replacement> {
+  REMOVED_MODULES.iter().find_map(|(removed, replacement)| {
+    let matches = if removed.ends_with('/') {
+      path.starts_with(removed)
+    } else {
+      path == *removed
+    };
+    if matches {
+      Some(*replacement)
+    } else {
+      None
+    }
+  })
+}
+
+struct NoDeprecatedStdModulesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> NoDeprecatedStdModulesVisitor<'c, 'view> {
+  fn check_specifier(&mut self, src: &Str) {
+    let path = match std_module_path(&src.value) {
+      Some(path) => path,
+      None => return,
+    };
+    let hint = match replacement(path) {
+      Some(Replacement::Module(module)) => {
+        NoDeprecatedStdModulesHint::Module(module.to_string())
+      }
+      Some(Replacement::Api(api)) => {
+        NoDeprecatedStdModulesHint::Api(api.to_string())
+      }
+      None => return,
+    };
+    self.context.add_diagnostic_with_hint(
+      src.span,
+      CODE,
+      NoDeprecatedStdModulesMessage::Removed(path.to_string()),
+      hint,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for NoDeprecatedStdModulesVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    self.check_specifier(&import_decl.src);
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_specifier(&export_all.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      self.check_specifier(src);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          if let Expr::Lit(Lit::Str(src)) = &*arg.expr {
+            self.check_specifier(src);
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_deprecated_std_modules_valid() {
+    assert_lint_ok! {
+      NoDeprecatedStdModules,
+      r#"import { assertEquals } from "https://deno.land/std@0.200.0/assert/mod.ts";"#,
+      r#"import { parse } from "https://deno.land/std/yaml/mod.ts";"#,
+      r#"import { walk } from "https://deno.land/std@0.100.0/fs/walk.ts";"#,
+      r#"import { Hash } from "https://deno.land/x/hash/mod.ts";"#,
+      r#"import { assertEquals } from "jsr:@std/assert";"#,
+      r#"import { readFile } from "node:fs";"#,
+    };
+  }
+
+  #[test]
+  fn no_deprecated_std_modules_invalid() {
+    assert_lint_err! {
+      NoDeprecatedStdModules,
+      r#"import { assertEquals } from "https://deno.land/std@0.150.0/testing/asserts.ts";"#: [
+        {
+          col: 29,
+          message: variant!(NoDeprecatedStdModulesMessage, Removed, "testing/asserts.ts"),
+          hint: variant!(NoDeprecatedStdModulesHint, Module, "assert/mod.ts"),
+        }
+      ],
+      r#"export * from "https://deno.land/std/encoding/yaml.ts";"#: [
+        {
+          col: 14,
+          message: variant!(NoDeprecatedStdModulesMessage, Removed, "encoding/yaml.ts"),
+          hint: variant!(NoDeprecatedStdModulesHint, Module, "yaml/mod.ts"),
+        }
+      ],
+      r#"export { Sha256 } from "https://deno.land/std@0.100.0/hash/sha256.ts";"#: [
+        {
+          col: 23,
+          message: variant!(NoDeprecatedStdModulesMessage, Removed, "hash/sha256.ts"),
+          hint: variant!(NoDeprecatedStdModulesHint, Module, "crypto/mod.ts"),
+        }
+      ],
+      r#"const { serve } = await import("https://deno.land/std@0.150.0/http/server.ts");"#: [
+        {
+          col: 31,
+          message: variant!(NoDeprecatedStdModulesMessage, Removed, "http/server.ts"),
+          hint: variant!(NoDeprecatedStdModulesHint, Api, "`Deno.serve()`"),
+        }
+      ],
+      r#"import fs from "https://deno.land/std@0.170.0/node/fs.ts";"#: [
+        {
+          col: 15,
+          message: variant!(NoDeprecatedStdModulesMessage, Removed, "node/fs.ts"),
+          hint: variant!(NoDeprecatedStdModulesHint, Api, "`node:` specifiers, e.g. `node:fs`"),
+        }
+      ],
+    };
+  }
+}
@@ -52,27 +52,10 @@ impl LintRule for NoDupeClassMembers {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows using a class member function name more than once
-
-Declaring a function of the same name twice in a class will cause the previous
-declaration(s) to be overwritten, causing unexpected behaviors.
-    
-### Invalid:
-```typescript
-class Foo {
-  bar() {}
-  bar() {}
-}
-```
-
-### Valid:
-```typescript
-class Foo {
-  bar() {}
-  fizz() {}
-}
-```
-"#
+    rule_docs!(
+      "Disallows using a class member function name more than once",
+      "no-dupe-class-members"
+    )
   }
 }
 
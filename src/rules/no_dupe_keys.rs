@@ -53,36 +53,10 @@ impl LintRule for NoDupeKeys {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows duplicate keys in object literals.
-
-Setting the same key multiple times in an object literal will override other assignments to that key and can cause unexpected behaviour.
-
-### Invalid:
-```typescript
-const foo = {
-  bar: "baz",
-  bar: "qux"
-};
-```
-```typescript
-const foo = {
-  "bar": "baz",
-  bar: "qux"
-};
-```
-```typescript
-const foo = {
-  0x1: "baz",
-  1: "qux"
-};
-```
-### Valid:
-```typescript
-var foo = {
-  bar: "baz",
-  quxx: "qux"
-};
-```"#
+    rule_docs!(
+      "Disallows duplicate keys in object literals.",
+      "no-dupe-keys"
+    )
   }
 }
 
@@ -50,41 +50,10 @@ impl LintRule for NoDuplicateCase {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows using the same case clause in a switch statement more than once
-
-When you reuse a case test expression in a `switch` statement, the duplicate case will
-never be reached meaning this is almost always a bug.
-    
-### Invalid:
-```typescript
-const someText = "a";
-switch (someText) {
-  case "a":
-    break;
-  case "b":
-    break;
-  case "a": // duplicate test expression
-    break;
-  default:
-    break;
-}
-```
-
-### Valid:
-```typescript
-const someText = "a";
-switch (someText) {
-  case "a":
-    break;
-  case "b":
-    break;
-  case "c":
-    break;
-  default:
-    break;
-}
-```
-"#
+    rule_docs!(
+      "Disallows using the same case clause in a switch statement more than once",
+      "no-duplicate-case"
+    )
   }
 }
 
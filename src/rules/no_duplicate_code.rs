@@ -0,0 +1,407 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use dprint_swc_ecma_ast_view::{RootNode, Token};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{BlockStmt, Module, Script, SwitchCase};
+use swc_ecmascript::parser::token::Word;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoDuplicateCode {
+  options: NoDuplicateCodeOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoDuplicateCodeOptions {
+  /// The minimum number of tokens of a duplicated sequence of statements.
+  pub min_tokens: usize,
+  /// Whether sequences only differing by the names of identifiers are
+  /// considered duplicates.
+  pub ignore_identifiers: bool,
+}
+
+impl Default for NoDuplicateCodeOptions {
+  fn default() -> Self {
+    Self {
+      min_tokens: 50,
+      ignore_identifiers: false,
+    }
+  }
+}
+
+const CODE: &str = "no-duplicate-code";
+
+#[derive(Display)]
+enum NoDuplicateCodeMessage {
+  #[display(fmt = "This code duplicates code found earlier in the file")]
+  Duplicate,
+}
+
+#[derive(Display)]
+enum NoDuplicateCodeRelated {
+  #[display(fmt = "The first occurrence is here")]
+  FirstOccurrence,
+}
+
+#[derive(Display)]
+enum NoDuplicateCodeHint {
+  #[display(fmt = "Extract the duplicated code into a function")]
+  Extract,
+}
+
+impl LintRule for NoDuplicateCode {
+  fn new() -> Box<Self> {
+    Box::new(NoDuplicateCode {
+      options: NoDuplicateCodeOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut collector = StatementListCollector { lists: Vec::new() };
+    match program {
+      ProgramRef::Module(m) => collector.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => collector.visit_script(s, &DUMMY_NODE),
+    }
+    let lists: Vec<Vec<Statement>> = collector
+      .lists
+      .iter()
+      .map(|spans| {
+        spans
+          .iter()
+          .map(|span| self.normalize(context, *span))
+          .collect()
+      })
+      .collect();
+    self.report_duplicates(context, &lists);
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "minTokens": {
+          "description": "The minimum number of tokens of a duplicated sequence of statements.",
+          "type": "integer",
+          "minimum": 1,
+          "default": 50
+        },
+        "ignoreIdentifiers": {
+          "description": "Whether sequences only differing by the names of identifiers are considered duplicates.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows duplicated sequences of statements in a file",
+      "no-duplicate-code"
+    )
+  }
+}
+
+/// A statement along with its tokens, which ignore formatting and comments.
+struct Statement {
+  span: Span,
+  text: String,
+  token_count: usize,
+}
+
+impl NoDuplicateCode {
+  fn normalize(&self, context: &Context, span: Span) -> Statement {
+    let program = context.program();
+    let source_file = program
+      .source_file()
+      .expect("Program should have its source file, but doesn't");
+    let tokens = program
+      .tokens()
+      .expect("Program should have its tokens, but doesn't")
+      .get_tokens_in_range(span.lo, span.hi);
+
+    let mut text = String::new();
+    for token in tokens {
+      if self.options.ignore_identifiers
+        && matches!(token.token, Token::Word(Word::Ident(_)))
+      {
+        text.push('$');
+      } else {
+        let lo = (token.span.lo - source_file.start_pos).0 as usize;
+        let hi = (token.span.hi - source_file.start_pos).0 as usize;
+        text.push_str(&source_file.src[lo..hi]);
+      }
+      // Tokens are separated by a character that can't appear outside of
+      // string literals, so `a b` and `ab` don't collide
+      text.push('\n');
+    }
+    Statement {
+      span,
+      text,
+      token_count: tokens.len(),
+    }
+  }
+
+  fn report_duplicates(&self, context: &mut Context, lists: &[Vec<Statement>]) {
+    let min_tokens = self.options.min_tokens.max(1);
+    // The first occurrence of each sequence, as the list and the range of its
+    // statements
+    let mut seen: HashMap<String, (usize, usize, usize)> = HashMap::new();
+    let mut reported: Vec<Span> = Vec::new();
+
+    for (list_index, list) in lists.iter().enumerate() {
+      let mut start = 0;
+      while start < list.len() {
+        // The shortest sequence starting here with enough tokens
+        let mut end = start;
+        let mut token_count = list[start].token_count;
+        while token_count < min_tokens && end + 1 < list.len() {
+          end += 1;
+          token_count += list[end].token_count;
+        }
+        if token_count < min_tokens {
+          break;
+        }
+
+        let key: String =
+          list[start..=end].iter().map(|s| s.text.as_str()).collect();
+        let (first_list, first_start, mut first_end) = match seen.get(&key) {
+          Some(&(first_list, first_start, first_end))
+            // Overlapping occurrences, e.g. in `a(); a(); a();`, aren't
+            // duplicates of each other
+            if first_list != list_index || first_end < start =>
+          {
+            (first_list, first_start, first_end)
+          }
+          Some(_) => {
+            start += 1;
+            continue;
+          }
+          None => {
+            seen.insert(key, (list_index, start, end));
+            start += 1;
+            continue;
+          }
+        };
+
+        // Extend the duplicate as far as it goes
+        let first = &lists[first_list];
+        while end + 1 < list.len()
+          && first_end + 1 < first.len()
+          && (first_list != list_index || first_end + 1 < start)
+          && first[first_end + 1].text == list[end + 1].text
+        {
+          end += 1;
+          first_end += 1;
+        }
+
+        let span = list[start].span.to(list[end].span);
+        // Duplicates nested in a reported duplicate are reported with it
+        if !reported.iter().any(|r| r.contains(span)) {
+          let related = context.create_related(
+            first[first_start].span.to(first[first_end].span),
+            NoDuplicateCodeRelated::FirstOccurrence,
+          );
+          context.add_diagnostic_with_related(
+            span,
+            CODE,
+            NoDuplicateCodeMessage::Duplicate,
+            Some(NoDuplicateCodeHint::Extract.to_string()),
+            vec![related],
+          );
+          reported.push(span);
+        }
+        start = end + 1;
+      }
+    }
+  }
+}
+
+/// Collects the spans of the statements of every statement list in the
+/// program, outer lists first.
+struct StatementListCollector {
+  lists: Vec<Vec<Span>>,
+}
+
+impl Visit for StatementListCollector {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, module: &Module, _: &dyn Node) {
+    self
+      .lists
+      .push(module.body.iter().map(|item| item.span()).collect());
+    module.visit_children_with(self);
+  }
+
+  fn visit_script(&mut self, script: &Script, _: &dyn Node) {
+    self
+      .lists
+      .push(script.body.iter().map(|stmt| stmt.span()).collect());
+    script.visit_children_with(self);
+  }
+
+  fn visit_block_stmt(&mut self, block_stmt: &BlockStmt, _: &dyn Node) {
+    self
+      .lists
+      .push(block_stmt.stmts.iter().map(|stmt| stmt.span()).collect());
+    block_stmt.visit_children_with(self);
+  }
+
+  fn visit_switch_case(&mut self, switch_case: &SwitchCase, _: &dyn Node) {
+    self
+      .lists
+      .push(switch_case.cons.iter().map(|stmt| stmt.span()).collect());
+    switch_case.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_duplicate_code_valid() {
+    assert_lint_ok! {
+      NoDuplicateCode,
+      r#"
+function a() { const x = foo(1, 2, 3); bar(x); }
+function b() { const x = foo(1, 2, 3); bar(x); }
+"#,
+      r#"
+async function a() {
+  const response = await fetch(url, { method: "POST", body: JSON.stringify(data) });
+  if (!response.ok) throw new Error(`Request failed: ${response.status}`);
+  return await response.json();
+}
+async function b() {
+  const response = await fetch(url, { method: "PUT", body: JSON.stringify(data) });
+  if (!response.ok) throw new Error(`Request failed: ${response.status}`);
+  return await response.json();
+}
+"#,
+    };
+
+    assert_lint_ok! {
+      NoDuplicateCode,
+      options: json!({ "minTokens": 5 }),
+      "a(); a(); a();",
+    };
+  }
+
+  #[test]
+  fn no_duplicate_code_invalid() {
+    assert_lint_err! {
+      NoDuplicateCode,
+      r#"async function a() {
+  const response = await fetch(url, { method: "POST", body: JSON.stringify(data) });
+  if (!response.ok) throw new Error(`Request failed: ${response.status}`);
+  return await response.json();
+}
+async function b() {
+  const response = await fetch(url, {
+    method: "POST",
+    body: JSON.stringify(data) // the payload
+  });
+  if (!response.ok) throw new Error(`Request failed: ${response.status}`);
+  return await response.json();
+}"#: [
+        {
+          line: 7,
+          col: 2,
+          message: NoDuplicateCodeMessage::Duplicate,
+          hint: NoDuplicateCodeHint::Extract,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoDuplicateCode,
+      options: json!({ "minTokens": 30 }),
+      r#"if (a) {
+  const total = items.reduce((sum, item) => sum + item.price * item.quantity, 0);
+  console.log(`Total: ${total.toFixed(2)}`);
+}
+if (b) {
+  const total = items.reduce((sum, item) => sum + item.price * item.quantity, 0);
+  console.log(`Total: ${total.toFixed(2)}`);
+}
+if (a) {
+  const total = items.reduce((sum, item) => sum + item.price * item.quantity, 0);
+  console.log(`Total: ${total.toFixed(2)}`);
+}"#: [
+        {
+          line: 6,
+          col: 2,
+          message: NoDuplicateCodeMessage::Duplicate,
+          hint: NoDuplicateCodeHint::Extract,
+        },
+        {
+          line: 9,
+          col: 0,
+          message: NoDuplicateCodeMessage::Duplicate,
+          hint: NoDuplicateCodeHint::Extract,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoDuplicateCode,
+      options: json!({ "minTokens": 10, "ignoreIdentifiers": true }),
+      "const a = foo(1, 2); bar(a, 3);\nconst b = baz(1, 2); qux(b, 3);": [
+        {
+          line: 2,
+          col: 0,
+          message: NoDuplicateCodeMessage::Duplicate,
+          hint: NoDuplicateCodeHint::Extract,
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_duplicate_code_related() {
+    use crate::test_util::lint;
+
+    let mut rule = NoDuplicateCode::new();
+    rule.set_options(json!({ "minTokens": 10 })).unwrap();
+    let diagnostics = lint(
+      rule,
+      "if (a) {\n  b(1, 2);\n  c(3);\n}\nif (d) {\n  b(1, 2);\n  c(3);\n}",
+      "file.ts".to_string(),
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 6);
+    let related = &diagnostics[0].related;
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].message, "The first occurrence is here");
+    assert_eq!(related[0].range.start.line, 2);
+    assert_eq!(related[0].range.start.col, 2);
+    assert_eq!(related[0].range.end.line, 3);
+    assert_eq!(related[0].range.end.col, 7);
+  }
+}
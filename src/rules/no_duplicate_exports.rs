@@ -0,0 +1,290 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use derive_more::Display;
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  Decl, DefaultDecl, ExportSpecifier, Module, ModuleDecl, ModuleItem,
+  VarDeclarator,
+};
+use swc_ecmascript::utils::find_ids;
+
+pub struct NoDuplicateExports;
+
+const CODE: &str = "no-duplicate-exports";
+
+#[derive(Display)]
+enum NoDuplicateExportsMessage {
+  #[display(fmt = "Duplicate export of '{}'", _0)]
+  Duplicate(String),
+}
+
+#[derive(Display)]
+enum NoDuplicateExportsHint {
+  #[display(
+    fmt = "'{}' is already exported, remove or rename one of them",
+    _0
+  )]
+  AlreadyExported(String),
+}
+
+#[derive(Display)]
+enum NoDuplicateExportsRelated {
+  #[display(fmt = "'{}' is first exported here", _0)]
+  FirstExport(String),
+}
+
+impl LintRule for NoDuplicateExports {
+  fn new() -> Box<Self> {
+    Box::new(NoDuplicateExports)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    // Scripts can't have exports at all
+    if let ProgramRef::Module(module) = program {
+      check_module(context, module);
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows exporting the same name more than once from a module",
+      "no-duplicate-exports"
+    )
+  }
+}
+
+fn check_module(context: &mut Context, module: &Module) {
+  let mut exported: HashMap<JsWord, Span> = HashMap::new();
+
+  for (name, span) in exported_names(module) {
+    if let Some(first_span) = exported.get(&name) {
+      let related = context.create_related(
+        *first_span,
+        NoDuplicateExportsRelated::FirstExport(name.to_string()),
+      );
+      context.add_diagnostic_with_related(
+        span,
+        CODE,
+        NoDuplicateExportsMessage::Duplicate(name.to_string()),
+        Some(
+          NoDuplicateExportsHint::AlreadyExported(name.to_string()).to_string(),
+        ),
+        vec![related],
+      );
+    } else {
+      exported.insert(name, span);
+    }
+  }
+}
+
+/// Collects the names of the value exports in the module in source order,
+/// together with the span of the node that exports each of them.
+/// Type-only exports and declarations that may legitimately merge (overload
+/// signatures, enums, namespaces, interfaces) are not collected.
+fn exported_names(module: &Module) -> Vec<(JsWord, Span)> {
+  let mut names = Vec::new();
+
+  for item in &module.body {
+    let module_decl = match item {
+      ModuleItem::ModuleDecl(module_decl) => module_decl,
+      ModuleItem::Stmt(_) => continue,
+    };
+
+    match module_decl {
+      ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
+        Decl::Class(class_decl) if !class_decl.declare => {
+          names.push((class_decl.ident.sym.clone(), class_decl.ident.span));
+        }
+        Decl::Fn(fn_decl)
+          if !fn_decl.declare && fn_decl.function.body.is_some() =>
+        {
+          names.push((fn_decl.ident.sym.clone(), fn_decl.ident.span));
+        }
+        Decl::Var(var_decl) if !var_decl.declare => {
+          for VarDeclarator { name, .. } in &var_decl.decls {
+            let ids: Vec<swc_ecmascript::ast::Ident> = find_ids(name);
+            names.extend(ids.into_iter().map(|id| (id.sym, id.span)));
+          }
+        }
+        _ => {}
+      },
+      ModuleDecl::ExportNamed(named_export) if !named_export.type_only => {
+        for specifier in &named_export.specifiers {
+          match specifier {
+            ExportSpecifier::Namespace(namespace) => {
+              names.push((namespace.name.sym.clone(), namespace.span));
+            }
+            ExportSpecifier::Default(default) => {
+              names.push(("default".into(), default.exported.span));
+            }
+            ExportSpecifier::Named(named) => {
+              let exported = named.exported.as_ref().unwrap_or(&named.orig);
+              names.push((exported.sym.clone(), named.span));
+            }
+          }
+        }
+      }
+      ModuleDecl::ExportDefaultDecl(default_decl)
+        if !matches!(default_decl.decl, DefaultDecl::TsInterfaceDecl(_)) =>
+      {
+        names.push(("default".into(), default_decl.span));
+      }
+      ModuleDecl::ExportDefaultExpr(default_expr) => {
+        names.push(("default".into(), default_expr.span));
+      }
+      _ => {}
+    }
+  }
+
+  names
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_duplicate_exports_valid() {
+    assert_lint_ok! {
+      NoDuplicateExports,
+      "export const a = 1; export const b = 2;",
+      "const a = 1; export { a, a as b };",
+      "export { a } from './a.ts'; export { b } from './b.ts';",
+      "export { a as b } from './a.ts'; export { a } from './b.ts';",
+      "export * from './a.ts'; export * from './b.ts';",
+      "export * as a from './a.ts'; export * as b from './b.ts';",
+      "export default function () {}",
+      "export default class {} export { Foo };",
+      "const a = 1; export default a;",
+      "export { default } from './a.ts'; export { b } from './a.ts';",
+
+      // overload signatures
+      r#"
+export function foo(a: string): void;
+export function foo(a: number): void;
+export function foo(a: any) {}
+      "#,
+
+      // declaration merging
+      "export enum Foo { A } export enum Foo { B = 1 }",
+      "export namespace Foo {} export namespace Foo {}",
+      "export interface Foo {} export interface Foo {}",
+      "export class Foo {} export interface Foo {}",
+      "export function Foo() {} export namespace Foo {}",
+      "export default interface Foo {} export default class Bar {}",
+
+      // type only exports live in a different namespace
+      "export const Foo = 1; export type Foo = number;",
+      "export const Foo = 1; export type { Foo } from './foo.ts';",
+
+      // not a module
+      "const a = 1; const a2 = a;",
+    };
+  }
+
+  #[test]
+  fn no_duplicate_exports_invalid() {
+    assert_lint_err! {
+      NoDuplicateExports,
+      "export const a = 1; export { a };": [
+        {
+          col: 29,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "a"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "a"),
+        }
+      ],
+      "const a = 1; export { a }; export { a as b, a };": [
+        {
+          col: 44,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "a"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "a"),
+        }
+      ],
+      "export { a } from './a.ts'; export { b as a } from './b.ts';": [
+        {
+          col: 37,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "a"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "a"),
+        }
+      ],
+      "export * as a from './a.ts'; export * as a from './b.ts';": [
+        {
+          col: 36,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "a"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "a"),
+        }
+      ],
+      "export function foo() {} export class foo {}": [
+        {
+          col: 38,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "foo"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "foo"),
+        }
+      ],
+      "export const { a, b: [c] } = obj; export { c };": [
+        {
+          col: 43,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "c"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "c"),
+        }
+      ],
+      r#"
+export default function () {}
+export { foo as default };
+export { default } from "./foo.ts";
+      "#: [
+        {
+          line: 3,
+          col: 9,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "default"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "default"),
+        },
+        {
+          line: 4,
+          col: 9,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "default"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "default"),
+        }
+      ],
+      "const a = 1; export default a; export default class {}": [
+        {
+          col: 31,
+          message: variant!(NoDuplicateExportsMessage, Duplicate, "default"),
+          hint: variant!(NoDuplicateExportsHint, AlreadyExported, "default"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_duplicate_exports_related() {
+    use crate::test_util::lint;
+
+    let diagnostics = lint(
+      NoDuplicateExports::new(),
+      "export const a = 1;\nexport { a };",
+      "file.ts".to_string(),
+    );
+    let related = &diagnostics[0].related;
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].message, "'a' is first exported here");
+    assert_eq!(related[0].filename, "file.ts");
+    assert_eq!(related[0].range.start.line, 1);
+    assert_eq!(related[0].range.start.col, 13);
+    assert_eq!(related[0].range.end.col, 14);
+  }
+}
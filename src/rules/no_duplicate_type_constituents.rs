@@ -0,0 +1,192 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_common::{EqIgnoreSpan, Span, Spanned};
+use swc_ecmascript::ast::{TsIntersectionType, TsType, TsUnionType};
+use swc_ecmascript::visit::{Node, VisitAll, VisitAllWith};
+
+pub struct NoDuplicateTypeConstituents;
+
+const CODE: &str = "no-duplicate-type-constituents";
+
+#[derive(Display)]
+enum NoDuplicateTypeConstituentsMessage {
+  #[display(fmt = "'{}' is duplicated in the union type", _0)]
+  Union(String),
+  #[display(fmt = "'{}' is duplicated in the intersection type", _0)]
+  Intersection(String),
+}
+
+#[derive(Display)]
+enum NoDuplicateTypeConstituentsHint {
+  #[display(fmt = "Remove the duplicate, it doesn't change the type")]
+  Remove,
+}
+
+#[derive(Display)]
+enum NoDuplicateTypeConstituentsFix {
+  #[display(fmt = "Remove the duplicate")]
+  Remove,
+}
+
+impl LintRule for NoDuplicateTypeConstituents {
+  fn new() -> Box<Self> {
+    Box::new(NoDuplicateTypeConstituents)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoDuplicateTypeConstituentsVisitor { context };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows duplicate constituents in union and intersection types",
+      "no-duplicate-type-constituents"
+    )
+  }
+}
+
+struct NoDuplicateTypeConstituentsVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> NoDuplicateTypeConstituentsVisitor<'c, 'view> {
+  fn check_types(
+    &mut self,
+    types: &[Box<TsType>],
+    message: fn(String) -> NoDuplicateTypeConstituentsMessage,
+  ) {
+    for (index, ty) in types.iter().enumerate().skip(1) {
+      let is_duplicate =
+        types[..index].iter().any(|prev| prev.eq_ignore_span(ty));
+      if !is_duplicate {
+        continue;
+      }
+      let span = ty.span();
+      let text = match self.context.source_map().span_to_snippet(span) {
+        Ok(text) => text,
+        Err(_) => continue,
+      };
+      // Removes the duplicate along with the operator before it
+      let removed = Span::new(types[index - 1].span().hi, span.hi, span.ctxt);
+      let fix = self.context.create_fix(
+        NoDuplicateTypeConstituentsFix::Remove,
+        vec![(removed, "".to_string())],
+      );
+      self.context.add_diagnostic_with_fixes(
+        span,
+        CODE,
+        message(text),
+        Some(NoDuplicateTypeConstituentsHint::Remove.to_string()),
+        vec![fix],
+      );
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for NoDuplicateTypeConstituentsVisitor<'c, 'view> {
+  fn visit_ts_union_type(&mut self, union_type: &TsUnionType, _: &dyn Node) {
+    self.check_types(
+      &union_type.types,
+      NoDuplicateTypeConstituentsMessage::Union,
+    );
+  }
+
+  fn visit_ts_intersection_type(
+    &mut self,
+    intersection_type: &TsIntersectionType,
+    _: &dyn Node,
+  ) {
+    self.check_types(
+      &intersection_type.types,
+      NoDuplicateTypeConstituentsMessage::Intersection,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_duplicate_type_constituents_valid() {
+    assert_lint_ok! {
+      NoDuplicateTypeConstituents,
+      "type A = string | number;",
+      "type A = 'a' | 'b' | 1 | true;",
+      "type A = { a: string } & { b: string };",
+      "type A = (string | number) | (number | boolean);",
+      "type A = Array<string> | Array<number>;",
+      "let a: string | null | undefined;",
+    };
+  }
+
+  #[test]
+  fn no_duplicate_type_constituents_invalid() {
+    assert_lint_err! {
+      NoDuplicateTypeConstituents,
+      "type A = string | string;": [
+        {
+          col: 18,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Union, "string"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "type A = string;"),
+        }
+      ],
+      "let a: 'a' | 'b' | 'a';": [
+        {
+          col: 19,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Union, "'a'"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "let a: 'a' | 'b';"),
+        }
+      ],
+      "type A = Foo & { a: 1 } & Foo;": [
+        {
+          col: 26,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Intersection, "Foo"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "type A = Foo & { a: 1 };"),
+        }
+      ],
+      "type A = { a: string } | number | {a:string};": [
+        {
+          col: 34,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Union, "{a:string}"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "type A = { a: string } | number;"),
+        }
+      ],
+      "function f(a: number | Array<number> | number | Array<number>) {}": [
+        {
+          col: 39,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Union, "number"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "function f(a: number | Array<number> | Array<number>) {}"),
+        },
+        {
+          col: 48,
+          message: variant!(NoDuplicateTypeConstituentsMessage, Union, "Array<number>"),
+          hint: NoDuplicateTypeConstituentsHint::Remove,
+          fix: (NoDuplicateTypeConstituentsFix::Remove, "function f(a: number | Array<number> | number) {}"),
+        }
+      ],
+    };
+  }
+}
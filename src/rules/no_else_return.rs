@@ -0,0 +1,429 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::diagnostic::LintFix;
+use derive_more::Display;
+use dprint_swc_ecma_ast_view::{RootNode, Token};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecmascript::ast::{Decl, IfStmt, ModuleItem, Stmt, VarDeclKind};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoElseReturn {
+  options: NoElseReturnOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoElseReturnOptions {
+  /// Whether `else if` is allowed after a branch that returns.
+  pub allow_else_if: bool,
+}
+
+impl Default for NoElseReturnOptions {
+  fn default() -> Self {
+    Self {
+      allow_else_if: true,
+    }
+  }
+}
+
+const CODE: &str = "no-else-return";
+
+#[derive(Display)]
+enum NoElseReturnMessage {
+  #[display(fmt = "Unnecessary `else` after `return` or `throw`")]
+  Unnecessary,
+}
+
+#[derive(Display)]
+enum NoElseReturnHint {
+  #[display(
+    fmt = "Move the statements of the `else` block after the `if` statement, they only run when the condition is false anyway"
+  )]
+  Unwrap,
+}
+
+#[derive(Display)]
+enum NoElseReturnFix {
+  #[display(fmt = "Remove the `else`")]
+  Unwrap,
+}
+
+impl LintRule for NoElseReturn {
+  fn new() -> Box<Self> {
+    Box::new(NoElseReturn {
+      options: NoElseReturnOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoElseReturnVisitor {
+      context,
+      allow_else_if: self.options.allow_else_if,
+      in_statement_list: HashSet::new(),
+      checked_span: HashSet::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allowElseIf": {
+          "description": "Whether `else if` is allowed after a branch that returns.",
+          "type": "boolean",
+          "default": true
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows `else` blocks after `if` branches that return or throw",
+      "no-else-return"
+    )
+  }
+}
+
+/// Returns `true` if the statement always returns or throws.
+fn always_exits(stmt: &Stmt) -> bool {
+  match stmt {
+    Stmt::Return(_) | Stmt::Throw(_) => true,
+    Stmt::Block(block) => block.stmts.iter().any(always_exits),
+    Stmt::If(IfStmt {
+      cons,
+      alt: Some(alt),
+      ..
+    }) => always_exits(cons) && always_exits(alt),
+    _ => false,
+  }
+}
+
+/// Returns `true` if moving the statements out of the block could change
+/// what they declare, e.g. by shadowing the bindings of the enclosing scope.
+fn has_block_scoped_decls(stmts: &[Stmt]) -> bool {
+  stmts.iter().any(|stmt| {
+    matches!(stmt, Stmt::Decl(decl) if !matches!(
+      decl,
+      Decl::Var(var_decl) if var_decl.kind == VarDeclKind::Var
+    ))
+  })
+}
+
+struct NoElseReturnVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow_else_if: bool,
+  /// The `if` statements directly in a list of statements, which can be
+  /// followed by the statements of their `else` block.
+  in_statement_list: HashSet<Span>,
+  /// The `else if` statements checked as part of their chain.
+  checked_span: HashSet<Span>,
+}
+
+impl<'c, 'view> NoElseReturnVisitor<'c, 'view> {
+  fn check_chain(&mut self, head: &IfStmt) {
+    let mut if_stmt = head;
+    let mut all_exit = true;
+    loop {
+      all_exit = all_exit && always_exits(&if_stmt.cons);
+      match &if_stmt.alt {
+        Some(alt) => {
+          if let Stmt::If(else_if) = &**alt {
+            self.checked_span.insert(else_if.span);
+            if !self.allow_else_if && all_exit {
+              self.report(head, if_stmt, alt);
+              return;
+            }
+            if_stmt = else_if;
+          } else {
+            if all_exit {
+              self.report(head, if_stmt, alt);
+            }
+            return;
+          }
+        }
+        None => return,
+      }
+    }
+  }
+
+  /// Reports the `else` of `if_stmt`, part of the chain starting at `head`.
+  fn report(&mut self, head: &IfStmt, if_stmt: &IfStmt, alt: &Stmt) {
+    let fixes = if self.in_statement_list.contains(&head.span) {
+      self.fix(head, if_stmt, alt).into_iter().collect()
+    } else {
+      vec![]
+    };
+    self.context.add_diagnostic_with_fixes(
+      alt.span(),
+      CODE,
+      NoElseReturnMessage::Unnecessary,
+      Some(NoElseReturnHint::Unwrap.to_string()),
+      fixes,
+    );
+  }
+
+  /// Returns the fix moving the statements of `alt` after the chain, with the
+  /// indentation of `head`.
+  fn fix(
+    &self,
+    head: &IfStmt,
+    if_stmt: &IfStmt,
+    alt: &Stmt,
+  ) -> Option<LintFix> {
+    let body = match alt {
+      Stmt::Block(block) => {
+        if has_block_scoped_decls(&block.stmts) {
+          return None;
+        }
+        match (block.stmts.first(), block.stmts.last()) {
+          (Some(first), Some(last)) => Some(first.span().to(last.span())),
+          _ => None,
+        }
+      }
+      Stmt::Decl(_) => return None,
+      alt => Some(alt.span()),
+    };
+
+    // Comments in between the statements are kept, but the ones around the
+    // `else` keyword and the braces would be removed
+    let removed_comments = self.context.all_comments().any(|comment| {
+      let span = comment.span;
+      span.lo >= if_stmt.cons.span().hi
+        && span.hi <= alt.span().hi
+        && !matches!(body, Some(body) if body.contains(span))
+    });
+    if removed_comments {
+      return None;
+    }
+
+    let program = self.context.program();
+    let source_file = program.source_file()?;
+    let src: &str = &source_file.src;
+    let offset = |pos: BytePos| (pos - source_file.start_pos).0 as usize;
+    let indent_at = |pos: BytePos| {
+      let line_start = src[..offset(pos)].rfind('\n').map_or(0, |i| i + 1);
+      let line = &src[line_start..];
+      let indent_len =
+        line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+      (&line[..indent_len], line_start + indent_len == offset(pos))
+    };
+
+    let (indent, _) = indent_at(head.span.lo);
+    let text = match body {
+      Some(body) => {
+        let text = &src[offset(body.lo)..offset(body.hi)];
+        let (body_indent, starts_line) = indent_at(body.lo);
+        // Lines of template literals are part of their value
+        let tokens = program.tokens()?.get_tokens_in_range(body.lo, body.hi);
+        let has_multiline_template = tokens.iter().any(|token| {
+          matches!(&token.token, Token::Template { raw, .. } if raw.contains('\n'))
+        });
+        if starts_line && !has_multiline_template {
+          text
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| match line.strip_prefix(body_indent) {
+              Some(rest) if i > 0 => format!("{}{}", indent, rest),
+              _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+        } else {
+          text.to_string()
+        }
+      }
+      None => String::new(),
+    };
+    let replacement = if text.is_empty() {
+      text
+    } else {
+      format!("\n{}{}", indent, text)
+    };
+
+    Some(self.context.create_fix(
+      NoElseReturnFix::Unwrap,
+      vec![(
+        Span::new(if_stmt.cons.span().hi, alt.span().hi, Default::default()),
+        replacement,
+      )],
+    ))
+  }
+}
+
+impl<'c, 'view> Visit for NoElseReturnVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_module_items(&mut self, items: &[ModuleItem], parent: &dyn Node) {
+    for item in items {
+      if let ModuleItem::Stmt(Stmt::If(if_stmt)) = item {
+        self.in_statement_list.insert(if_stmt.span);
+      }
+    }
+    swc_ecmascript::visit::visit_module_items(self, items, parent);
+  }
+
+  fn visit_stmts(&mut self, stmts: &[Stmt], parent: &dyn Node) {
+    for stmt in stmts {
+      if let Stmt::If(if_stmt) = stmt {
+        self.in_statement_list.insert(if_stmt.span);
+      }
+    }
+    swc_ecmascript::visit::visit_stmts(self, stmts, parent);
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _: &dyn Node) {
+    if !self.checked_span.contains(&if_stmt.span) {
+      self.check_chain(if_stmt);
+    }
+    if_stmt.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_else_return_valid() {
+    assert_lint_ok! {
+      NoElseReturn,
+      "function f() { if (a) { return 1; } return 2; }",
+      "function f() { if (a) { foo(); } else { return 2; } }",
+      "function f() { if (a) { if (b) { return 1; } } else { return 2; } }",
+      "function f() { if (a) return 1; else if (b) return 2; else if (c) foo(); else bar(); }",
+      "function f() { if (a) { return 1; } else if (b) { return 2; } }",
+      "function f() { if (a) { foo(); } else if (b) { return 2; } else { bar(); } }",
+    };
+
+    assert_lint_ok! {
+      NoElseReturn,
+      options: json!({ "allowElseIf": false }),
+      "function f() { if (a) { foo(); } else if (b) { bar(); } }",
+    };
+  }
+
+  #[test]
+  fn no_else_return_invalid() {
+    assert_lint_err! {
+      NoElseReturn,
+      r#"function f() {
+  if (a) {
+    return 1;
+  } else {
+    foo();
+    return 2;
+  }
+}"#: [
+        {
+          line: 4,
+          col: 9,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+          fix: (
+            NoElseReturnFix::Unwrap,
+            "function f() {\n  if (a) {\n    return 1;\n  }\n  foo();\n  return 2;\n}"
+          ),
+        }
+      ],
+      "function f() { if (a) throw err; else { foo(); } }": [
+        {
+          col: 38,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+          fix: (
+            NoElseReturnFix::Unwrap,
+            "function f() { if (a) throw err;\nfoo(); }"
+          ),
+        }
+      ],
+      "function f() { if (a) return 1; else if (b) return 2; else return 3; }": [
+        {
+          col: 59,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+          fix: (
+            NoElseReturnFix::Unwrap,
+            "function f() { if (a) return 1; else if (b) return 2;\nreturn 3; }"
+          ),
+        }
+      ],
+      "function f() { if (a) { if (b) return 1; throw err; } else { const x = 1; return x; } }": [
+        {
+          col: 59,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+        }
+      ],
+      "function f() { for (;;) if (a) return 1; else foo(); }": [
+        {
+          col: 46,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+        }
+      ],
+      "function f() { if (a) { return 1; } else /* why */ { foo(); } }": [
+        {
+          col: 51,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoElseReturn,
+      options: json!({ "allowElseIf": false }),
+      r#"function f() {
+  if (a) {
+    return 1;
+  } else if (b) {
+    return 2;
+  }
+}"#: [
+        {
+          line: 4,
+          col: 9,
+          message: NoElseReturnMessage::Unnecessary,
+          hint: NoElseReturnHint::Unwrap,
+          fix: (
+            NoElseReturnFix::Unwrap,
+            "function f() {\n  if (a) {\n    return 1;\n  }\n  if (b) {\n    return 2;\n  }\n}"
+          ),
+        }
+      ],
+    };
+  }
+}
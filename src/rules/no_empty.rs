@@ -22,6 +22,10 @@ impl LintRule for NoEmpty {
     CODE
   }
 
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -35,59 +39,7 @@ impl LintRule for NoEmpty {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the use of empty block statements.
-
-Empty block statements are legal but often represent that something was missed and can make code less readable. This rule ignores block statements that only contain comments. This rule also ignores empty constructors and function bodies (including arrow functions), which are covered by the `no-empty-function` rule.
-
-### Invalid:
-```typescript
-if (foo) {
-}
-```
-```typescript
-while (foo) {
-}
-```
-```typescript
-switch(foo) {
-}
-```
-```typescript
-try {
-  doSomething();
-} catch(ex) {
-
-} finally {
-
-}
-```
-
-### Valid:
-```typescript
-if (foo) {
-  // empty
-}
-```
-```typescript
-while (foo) {
-  /* empty */
-}
-```
-```typescript
-try {
-  doSomething();
-} catch (ex) {
-  // continue regardless of error
-}
-```
-```typescript
-try {
-  doSomething();
-} finally {
-  /* continue regardless of error */
-}
-```
-"#
+    rule_docs!("Disallows the use of empty block statements.", "no-empty")
   }
 }
 
@@ -0,0 +1,139 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::deno_test_fn;
+use derive_more::Display;
+use swc_ecmascript::ast::CallExpr;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoEmptyTest;
+
+const CODE: &str = "no-empty-test";
+
+#[derive(Display)]
+enum NoEmptyTestMessage {
+  #[display(fmt = "Test has an empty body")]
+  Empty,
+}
+
+#[derive(Display)]
+enum NoEmptyTestHint {
+  #[display(fmt = "Implement the test, or remove it")]
+  ImplementOrRemove,
+}
+
+impl LintRule for NoEmptyTest {
+  fn new() -> Box<Self> {
+    Box::new(NoEmptyTest)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoEmptyTestVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!("Disallows tests with an empty body", "no-empty-test")
+  }
+}
+
+struct NoEmptyTestVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> Visit for NoEmptyTestVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let Some(test_fn) = deno_test_fn(call_expr) {
+      if test_fn.is_empty() {
+        self.context.add_diagnostic_with_hint(
+          call_expr.span,
+          CODE,
+          NoEmptyTestMessage::Empty,
+          NoEmptyTestHint::ImplementOrRemove,
+        );
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_empty_test_valid() {
+    assert_lint_ok! {
+      NoEmptyTest,
+      "Deno.test('adds', () => { assertEquals(add(1, 2), 3); });",
+      "Deno.test('adds', () => assertEquals(add(1, 2), 3));",
+      "Deno.test(function adds() { assertEquals(add(1, 2), 3); });",
+      "Deno.test({ name: 'adds', fn() { assertEquals(add(1, 2), 3); } });",
+      "Deno.test('adds', { permissions: {} }, async () => { await check(); });",
+      "test('adds', () => {});",
+      "Deno.bench('adds', () => {});",
+      "Deno.test('adds', handler);",
+    };
+  }
+
+  #[test]
+  fn no_empty_test_invalid() {
+    assert_lint_err! {
+      NoEmptyTest,
+      "Deno.test('adds', () => {});": [
+        {
+          col: 0,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+      "Deno.test(function adds() {});": [
+        {
+          col: 0,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+      "Deno.test({ name: 'adds', fn: async () => {} });": [
+        {
+          col: 0,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+      "Deno.test({ name: 'adds', fn() {} });": [
+        {
+          col: 0,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+      "Deno.test.only('adds', { sanitizeOps: false }, () => {\n  // TODO\n});": [
+        {
+          col: 0,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+      "describe(() => { Deno.test('a', () => {}); });": [
+        {
+          col: 17,
+          message: NoEmptyTestMessage::Empty,
+          hint: NoEmptyTestHint::ImplementOrRemove,
+        }
+      ],
+    };
+  }
+}
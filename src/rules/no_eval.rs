@@ -10,6 +10,7 @@ use swc_ecmascript::ast::VarDeclarator;
 use swc_ecmascript::visit::noop_visit_type;
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::Visit;
+use swc_ecmascript::visit::VisitWith;
 
 pub struct NoEval;
 
@@ -39,25 +40,7 @@ impl LintRule for NoEval {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the use of `eval` 
-
-`eval` is a potentially dangerous function which can open your code to a number
-of security vulnerabilities.  In addition to being slow, `eval` is also often
-unnecessary with better solutions available.
-    
-### Invalid:
-```typescript
-const obj = { x: "foo" };
-const key = "x",
-const value = eval("obj." + key);
-```
-
-### Valid:
-```typescript
-const obj = { x: "foo" };
-const value = obj[x];
-```
-"#
+    rule_docs!("Disallows the use of `eval` ", "no-eval")
   }
 }
 
@@ -110,6 +93,7 @@ impl<'c, 'view> Visit for NoEvalVisitor<'c, 'view> {
         self.maybe_add_diagnostic(ident, v.span);
       }
     }
+    v.visit_children_with(self);
   }
 
   fn visit_call_expr(&mut self, call_expr: &CallExpr, _parent: &dyn Node) {
@@ -143,6 +127,7 @@ mod tests {
       "(0, eval)('var a = 0');": [{col: 4, message: MESSAGE, hint: HINT}],
       "((eval))('var a = 0');": [{col: 2, message: MESSAGE, hint: HINT}],
       "var foo = eval;": [{col: 4, message: MESSAGE, hint: HINT}],
+      "const foo = eval('bar');": [{col: 12, message: MESSAGE, hint: HINT}],
 
       // TODO (see: https://github.com/denoland/deno_lint/pull/490)
       // "this.eval("123");": [{col: 0, message: MESSAGE, hint: HINT}],
@@ -35,6 +35,10 @@ impl LintRule for NoExAssign {
     CODE
   }
 
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -48,30 +52,10 @@ impl LintRule for NoExAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the reassignment of exception parameters 
-
-There is generally no good reason to reassign an exception parameter.  Once
-reassigned the code from that point on has no reference to the error anymore.
-    
-### Invalid:
-```typescript
-try {
-  someFunc();
-} catch (e) {
-  e = true;
-  // can no longer access the thrown error
-}
-```
-
-### Valid:
-```typescript
-try {
-  someFunc();
-} catch (e) {
-  const anotherVar = true;
-}
-```
-"#
+    rule_docs!(
+      "Disallows the reassignment of exception parameters ",
+      "no-ex-assign"
+    )
   }
 }
 
@@ -1,16 +1,39 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
 use derive_more::Display;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde_json::json;
 use swc_common::{comments::Comment, Span, Spanned, DUMMY_SP};
 use swc_ecmascript::{
   ast::*,
   visit::{noop_visit_type, Node, Visit, VisitWith},
 };
 
-pub struct NoFallthrough;
+pub struct NoFallthrough {
+  comment_pattern: Option<Regex>,
+  allow_empty_case: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoFallthroughOptions {
+  /// A regular expression matched against the comments that mark a
+  /// fallthrough as intentional. Defaults to `falls?\s*through`, ignoring
+  /// case.
+  pub comment_pattern: Option<String>,
+  /// Allows empty cases that are followed by blank lines or comments, which
+  /// are otherwise reported as they may be missing their body.
+  pub allow_empty_case: bool,
+}
 
 const CODE: &str = "no-fallthrough";
 
+static DEFAULT_COMMENT_PATTERN: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"(?i)falls?\s*through").unwrap());
+
 #[derive(Display)]
 enum NoFallthroughMessage {
   #[display(fmt = "Fallthrough is not allowed")]
@@ -27,7 +50,10 @@ enum NoFallthroughHint {
 
 impl LintRule for NoFallthrough {
   fn new() -> Box<Self> {
-    Box::new(NoFallthrough)
+    Box::new(NoFallthrough {
+      comment_pattern: None,
+      allow_empty_case: false,
+    })
   }
 
   fn tags(&self) -> &'static [&'static str] {
@@ -38,70 +64,87 @@ impl LintRule for NoFallthrough {
     CODE
   }
 
+  fn needs_cfg(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
     program: ProgramRef<'view>,
   ) {
-    let mut visitor = NoFallthroughVisitor { context };
+    let mut visitor = NoFallthroughVisitor {
+      context,
+      comment_pattern: self
+        .comment_pattern
+        .as_ref()
+        .unwrap_or(&DEFAULT_COMMENT_PATTERN),
+      allow_empty_case: self.allow_empty_case,
+    };
     match program {
       ProgramRef::Module(ref m) => visitor.visit_module(m, &DUMMY_NODE),
       ProgramRef::Script(ref s) => visitor.visit_script(s, &DUMMY_NODE),
     }
   }
 
-  fn docs(&self) -> &'static str {
-    r#"Disallows the implicit fallthrough of case statements
-
-Case statements without a `break` will execute their body and then fallthrough
-to the next case or default block and execute this block as well.  While this
-is sometimes intentional, many times the developer has forgotten to add a break
-statement, intending only for a single case statement to be executed.  This
-rule enforces that you either end each case statement with a break statement or
-an explicit comment that fallthrough was intentional.  The fallthrough comment
-must contain one of `fallthrough`, `falls through` or `fall through`.
-    
-### Invalid:
-```typescript
-switch(myVar) {
-  case 1:
-    console.log('1');
-
-  case 2:
-    console.log('2');
-}
-// If myVar = 1, outputs both `1` and `2`.  Was this intentional?
-```
-
-### Valid:
-```typescript
-switch(myVar) {
-  case 1:
-    console.log('1');
-    break;
-
-  case 2:
-    console.log('2');
-    break;
-}
-// If myVar = 1, outputs only `1`
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: NoFallthroughOptions = serde_json::from_value(options)?;
+    self.comment_pattern = match options.comment_pattern {
+      Some(pattern) => {
+        Some(Regex::new(&pattern).map_err(serde_json::Error::custom)?)
+      }
+      None => None,
+    };
+    self.allow_empty_case = options.allow_empty_case;
+    Ok(())
+  }
 
-switch(myVar) {
-  case 1:
-    console.log('1');
-    /* falls through */
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "commentPattern": {
+          "description": "A regular expression matched against the comments that mark a fallthrough as intentional.",
+          "type": "string",
+          "default": "(?i)falls?\\s*through"
+        },
+        "allowEmptyCase": {
+          "description": "Allows empty cases that are followed by blank lines or comments.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
 
-  case 2:
-    console.log('2');
-}
-// If myVar = 1, intentionally outputs both `1` and `2`
-```
-"#
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows the implicit fallthrough of case statements",
+      "no-fallthrough"
+    )
   }
 }
 
 struct NoFallthroughVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  comment_pattern: &'c Regex,
+  allow_empty_case: bool,
+}
+
+impl<'c, 'view> NoFallthroughVisitor<'c, 'view> {
+  fn allow_fall_through(
+    &self,
+    mut comments: impl Iterator<Item = &'view Comment>,
+  ) -> bool {
+    comments.any(|comment| self.comment_pattern.is_match(&comment.text))
+  }
 }
 
 impl<'c, 'view> Visit for NoFallthroughVisitor<'c, 'view> {
@@ -116,7 +159,7 @@ impl<'c, 'view> Visit for NoFallthroughVisitor<'c, 'view> {
 
       if should_emit_err {
         let comments = self.context.leading_comments_at(case.span.lo);
-        if !allow_fall_through(comments) {
+        if !self.allow_fall_through(comments) {
           self.context.add_diagnostic_with_hint(
             prev_span,
             CODE,
@@ -139,7 +182,7 @@ impl<'c, 'view> Visit for NoFallthroughVisitor<'c, 'view> {
 
         if last {
           let comments = self.context.trailing_comments_at(stmt.span().hi);
-          if allow_fall_through(comments) {
+          if self.allow_fall_through(comments) {
             should_emit_err = false;
             // User comment beats everything
             prev_span = case.span;
@@ -165,7 +208,7 @@ impl<'c, 'view> Visit for NoFallthroughVisitor<'c, 'view> {
         // This means there are no statements detected so we must detect case
         // bodies made up of only new lines by counting the total amount of new lines.
         // If there's more than 2 new lines and `case.cons` is empty this indicates the case body only contains new lines.
-        should_emit_err = span_lines.lines.len() > 2;
+        should_emit_err = !self.allow_empty_case && span_lines.lines.len() > 2;
       }
 
       prev_span = case.span;
@@ -173,20 +216,10 @@ impl<'c, 'view> Visit for NoFallthroughVisitor<'c, 'view> {
   }
 }
 
-fn allow_fall_through<'c>(
-  mut comments: impl Iterator<Item = &'c Comment>,
-) -> bool {
-  comments.any(|comment| {
-    let l = comment.text.to_ascii_lowercase();
-    l.contains("fallthrough")
-      || l.contains("falls through")
-      || l.contains("fall through")
-  })
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
+  use serde_json::json;
 
   #[test]
   fn no_fallthrough_valid() {
@@ -226,6 +259,15 @@ mod tests {
       "switch('test') { case 'symbol':\n case 'function':\n default: b(); }",
       "switch('test') { case 'symbol': case 'function': default: b(); }",
     };
+
+    assert_lint_ok! {
+      NoFallthrough,
+      options: json!({ "commentPattern": "break omitted", "allowEmptyCase": true }),
+      "switch(foo) { case 0: a(); // break omitted\n case 1: b(); }",
+      "switch(foo) { case 0: a(); /* break omitted: b() needs a() */ case 1: b(); }",
+      "switch(foo) { case 0:\n\n default: b() }",
+      "switch(foo) { case 0:\n // comment\n default: b() }",
+    };
   }
 
   #[test]
@@ -303,6 +345,25 @@ mod tests {
         }
       ]
     };
+
+    assert_lint_err! {
+      NoFallthrough,
+      options: json!({ "commentPattern": "break omitted", "allowEmptyCase": true }),
+      "switch(foo) { case 0: a(); /* falls through */ case 1: b(); }": [
+        {
+          col: 14,
+          message: NoFallthroughMessage::Unexpected,
+          hint: NoFallthroughHint::BreakOrComment,
+        }
+      ],
+      "switch(foo) { case 0: a();\n\n case 1: b(); }": [
+        {
+          col: 14,
+          message: NoFallthroughMessage::Unexpected,
+          hint: NoFallthroughHint::BreakOrComment,
+        }
+      ]
+    };
   }
 
   #[test]
@@ -0,0 +1,258 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{async_function_names, is_known_promise};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper, ExprStmt, UnaryOp};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoFloatingPromises;
+
+const CODE: &str = "no-floating-promises";
+
+#[derive(Display)]
+enum NoFloatingPromisesMessage {
+  #[display(fmt = "Promises must be awaited or have their rejections handled")]
+  Floating,
+}
+
+#[derive(Display)]
+enum NoFloatingPromisesHint {
+  #[display(
+    fmt = "Add `await`, handle rejections with `.catch()`, or use `void` to ignore the promise explicitly"
+  )]
+  HandlePromise,
+}
+
+impl LintRule for NoFloatingPromises {
+  fn new() -> Box<Self> {
+    Box::new(NoFloatingPromises)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn needs_types(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoFloatingPromisesVisitor {
+      context,
+      async_functions: async_function_names(&program),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires promises used as statements to be awaited or have their rejections handled",
+      "no-floating-promises"
+    )
+  }
+}
+
+struct NoFloatingPromisesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  async_functions: HashSet<JsWord>,
+}
+
+impl<'c, 'view> NoFloatingPromisesVisitor<'c, 'view> {
+  fn is_floating(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Paren(paren) => self.is_floating(&paren.expr),
+      Expr::Seq(seq) => seq.exprs.iter().any(|expr| self.is_floating(expr)),
+      Expr::Cond(cond) => {
+        self.is_floating(&cond.cons) || self.is_floating(&cond.alt)
+      }
+      Expr::Unary(unary) if unary.op == UnaryOp::Void => false,
+      Expr::Await(_) | Expr::Assign(_) => false,
+      Expr::Call(call) if handles_rejection(call) => false,
+      _ => match self.context.is_promise(expr.span()) {
+        Some(is_promise) => is_promise,
+        None => is_known_promise(expr, &self.async_functions),
+      },
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoFloatingPromisesVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, _: &dyn Node) {
+    if self.is_floating(&expr_stmt.expr) {
+      self.context.add_diagnostic_with_hint(
+        expr_stmt.span,
+        CODE,
+        NoFloatingPromisesMessage::Floating,
+        NoFloatingPromisesHint::HandlePromise,
+      );
+    }
+    expr_stmt.visit_children_with(self);
+  }
+}
+
+/// Returns `true` for `promise.catch(handler)` and
+/// `promise.then(onFulfilled, onRejected)`.
+fn handles_rejection(call: &CallExpr) -> bool {
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => &**callee,
+    ExprOrSuper::Super(_) => return false,
+  };
+  match callee {
+    Expr::Member(member) if !member.computed => match &*member.prop {
+      Expr::Ident(prop) if prop.sym == *"catch" => !call.args.is_empty(),
+      Expr::Ident(prop) if prop.sym == *"then" => call.args.len() >= 2,
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::Range;
+  use crate::linter::LinterBuilder;
+  use crate::type_provider::TypeProvider;
+  use std::rc::Rc;
+
+  #[test]
+  fn no_floating_promises_valid() {
+    assert_lint_ok! {
+      NoFloatingPromises,
+      "async function foo() {} await foo();",
+      "async function foo() {} void foo();",
+      "async function foo() {} const p = foo();",
+      "async function foo() {} foo().catch(console.error);",
+      "async function foo() {} foo().then(console.log, console.error);",
+      "async function foo() {} foo().then(console.log).catch(console.error);",
+      "function foo() {} foo();",
+      "fetch('https://deno.land').then(() => {}, () => {});",
+      "const foo = async () => {}; function bar() { return foo(); }",
+      "let p; p = new Promise(() => {});",
+      "Promise.resolve().catch(() => {});",
+      "promise;",
+      "bar.then;",
+    };
+  }
+
+  #[test]
+  fn no_floating_promises_invalid() {
+    assert_lint_err! {
+      NoFloatingPromises,
+      "async function foo() {} foo();": [
+        {
+          col: 24,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "const foo = async () => {}; foo();": [
+        {
+          col: 28,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "const foo = async function () {}; function bar() { foo(); }": [
+        {
+          col: 51,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "fetch('https://deno.land');": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "new Promise((resolve) => resolve(1));": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "Promise.all([a, b]);": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "(async () => {})();": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "foo.then(console.log);": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "fetch(url).finally(done);": [
+        {
+          col: 0,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+      "async function foo() {} a ? foo() : null;": [
+        {
+          col: 24,
+          message: NoFloatingPromisesMessage::Floating,
+          hint: NoFloatingPromisesHint::HandlePromise,
+        }
+      ],
+    };
+  }
+
+  /// Answers that calls to `send` evaluate to promises.
+  struct SendTypeProvider {
+    source: &'static str,
+  }
+
+  impl TypeProvider for SendTypeProvider {
+    fn is_promise(&self, _file_name: &str, range: &Range) -> Option<bool> {
+      let text = &self.source[range.start.byte_pos..range.end.byte_pos];
+      Some(text.starts_with("send("))
+    }
+  }
+
+  #[test]
+  fn no_floating_promises_type_provider() {
+    let source = "send(); fetch(url); Promise.reject(1); void send();";
+    let mut linter = LinterBuilder::default()
+      .rules(vec![NoFloatingPromises::new()])
+      .type_provider(Rc::new(SendTypeProvider { source }))
+      .build();
+
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint");
+    let cols: Vec<usize> =
+      diagnostics.iter().map(|d| d.range.start.col).collect();
+    assert_eq!(cols, vec![0]);
+  }
+}
@@ -42,6 +42,10 @@ impl LintRule for NoGlobalAssign {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -63,19 +67,10 @@ impl LintRule for NoGlobalAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows assignment to native Javascript objects
-
-In Javascript, `String` and `Object` for example are native objects.  Like any
-object, they can be reassigned, but it is almost never wise to do so as this
-can lead to unexpected results and difficult to track down bugs.
-    
-### Invalid:
-```typescript
-Object = null;
-undefined = true;
-window = {};
-```
-"#
+    rule_docs!(
+      "Disallows assignment to native Javascript objects",
+      "no-global-assign"
+    )
   }
 }
 
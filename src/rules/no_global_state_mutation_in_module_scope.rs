@@ -0,0 +1,319 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::globals::GLOBALS;
+use crate::scopes::BindingKind;
+use derive_more::Display;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, CallExpr, Class, Expr, ExprOrSuper, Function, Ident,
+  Pat, PatOrExpr, UnaryExpr, UnaryOp, UpdateExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoGlobalStateMutationInModuleScope;
+
+const CODE: &str = "no-global-state-mutation-in-module-scope";
+
+#[derive(Display)]
+enum NoGlobalStateMutationInModuleScopeMessage {
+  #[display(
+    fmt = "The global `{}` is mutated when the module is imported",
+    _0
+  )]
+  Global(String),
+  #[display(
+    fmt = "The imported `{}` is mutated when the module is imported",
+    _0
+  )]
+  Imported(String),
+}
+
+#[derive(Display)]
+enum NoGlobalStateMutationInModuleScopeHint {
+  #[display(
+    fmt = "Move the mutation into a function called explicitly, so it doesn't depend on the order modules are imported in"
+  )]
+  MoveIntoFunction,
+}
+
+impl LintRule for NoGlobalStateMutationInModuleScope {
+  fn new() -> Box<Self> {
+    Box::new(NoGlobalStateMutationInModuleScope)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoGlobalStateMutationInModuleScopeVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows mutating globals and imported objects at the top level of modules",
+      "no-global-state-mutation-in-module-scope"
+    )
+  }
+}
+
+/// Returns the identifier a chain of member accesses starts from, e.g. `Array`
+/// for `Array.prototype.foo`.
+fn target_root(mut expr: &Expr) -> Option<&Ident> {
+  loop {
+    expr = match expr {
+      Expr::Ident(ident) => return Some(ident),
+      Expr::Member(member) => match &member.obj {
+        ExprOrSuper::Expr(obj) => obj,
+        ExprOrSuper::Super(_) => return None,
+      },
+      Expr::Paren(paren) => &paren.expr,
+      Expr::TsNonNull(non_null) => &non_null.expr,
+      Expr::TsAs(as_expr) => &as_expr.expr,
+      Expr::TsTypeAssertion(assertion) => &assertion.expr,
+      _ => return None,
+    };
+  }
+}
+
+/// Returns `true` for the functions mutating the object passed as their first
+/// argument, e.g. `Object.assign`.
+fn is_mutating_function(object: &str, method: &str) -> bool {
+  match object {
+    "Object" => matches!(
+      method,
+      "assign" | "defineProperty" | "defineProperties" | "setPrototypeOf"
+    ),
+    "Reflect" => matches!(
+      method,
+      "set" | "defineProperty" | "deleteProperty" | "setPrototypeOf"
+    ),
+    _ => false,
+  }
+}
+
+struct NoGlobalStateMutationInModuleScopeVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> NoGlobalStateMutationInModuleScopeVisitor<'c, 'view> {
+  fn is_global(&self, ident: &Ident) -> bool {
+    self.context.scope().var(&ident.to_id()).is_none()
+      && GLOBALS.iter().any(|(name, _)| ident.sym == **name)
+  }
+
+  /// Reports the mutation of a property of `target`. If `whole_object` is
+  /// `true`, mutations of `target` itself are reported too.
+  fn check_target(&mut self, span: Span, target: &Expr, whole_object: bool) {
+    if !whole_object && matches!(target, Expr::Ident(_)) {
+      // Assigning to the binding itself is `no-global-assign`'s and
+      // `no-import-assign`'s business
+      return;
+    }
+    let root = match target_root(target) {
+      Some(root) => root,
+      None => return,
+    };
+    let message = match self.context.scope().var(&root.to_id()) {
+      Some(var) if var.kind() == BindingKind::Import => {
+        NoGlobalStateMutationInModuleScopeMessage::Imported(
+          root.sym.to_string(),
+        )
+      }
+      None if self.is_global(root) => {
+        NoGlobalStateMutationInModuleScopeMessage::Global(root.sym.to_string())
+      }
+      _ => return,
+    };
+    self.context.add_diagnostic_with_hint(
+      span,
+      CODE,
+      message,
+      NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for NoGlobalStateMutationInModuleScopeVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  // The bodies of functions and classes don't run when the module is imported
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+
+  fn visit_class(&mut self, _: &Class, _: &dyn Node) {}
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        _ => None,
+      },
+    };
+    if let Some(target) = target {
+      self.check_target(assign_expr.span, target, false);
+    }
+    assign_expr.right.visit_with(assign_expr, self);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    self.check_target(update_expr.span, &update_expr.arg, false);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete {
+      self.check_target(unary_expr.span, &unary_expr.arg, false);
+    } else {
+      unary_expr.visit_children_with(self);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Member(member) = &**callee {
+        let object = match &member.obj {
+          ExprOrSuper::Expr(obj) => match &**obj {
+            Expr::Ident(ident) if self.is_global(ident) => Some(ident),
+            _ => None,
+          },
+          ExprOrSuper::Super(_) => None,
+        };
+        if let (Some(object), Expr::Ident(method), false) =
+          (object, &*member.prop, member.computed)
+        {
+          if is_mutating_function(&object.sym, &method.sym) {
+            if let Some(target) = call_expr.args.first() {
+              if target.spread.is_none() {
+                self.check_target(call_expr.span, &target.expr, true);
+              }
+            }
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_global_state_mutation_in_module_scope_valid() {
+    assert_lint_ok! {
+      NoGlobalStateMutationInModuleScope,
+      "const config = {}; config.debug = true;",
+      "let count = 0; count++;",
+      "export function setup() { globalThis.foo = 1; }",
+      "export const setup = () => { Array.prototype.last = last; };",
+      "export class Foo { foo = (window.foo = 1); bar() { Deno.env.foo = 1; } }",
+      r#"import * as mod from "./mod.ts"; export function patch() { mod.foo = 1; }"#,
+      r#"import { config } from "./config.ts"; const debug = config.debug;"#,
+      "const globalThis = {}; globalThis.foo = 1;",
+      "const obj = Object.assign({}, defaults);",
+      "Object.defineProperty(exports, 'foo', { value: 1 });",
+      "foo.bar = 1;",
+    };
+  }
+
+  #[test]
+  fn no_global_state_mutation_in_module_scope_invalid() {
+    assert_lint_err! {
+      NoGlobalStateMutationInModuleScope,
+      "globalThis.fetch = mockFetch;": [
+        {
+          col: 0,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "globalThis"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "Array.prototype.last = function () { return this[this.length - 1]; };": [
+        {
+          col: 0,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "Array"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      r#"import * as path from "./path.ts"; path.sep = "/";"#: [
+        {
+          col: 35,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Imported, "path"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      r#"import config from "./config.ts"; config.retries += 1;"#: [
+        {
+          col: 34,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Imported, "config"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      r#"import { counters } from "./counters.ts"; counters.loaded++;"#: [
+        {
+          col: 42,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Imported, "counters"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "delete window.localStorage;": [
+        {
+          col: 0,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "window"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "Object.assign(globalThis, { foo: 1 });": [
+        {
+          col: 0,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "globalThis"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "Object.defineProperty(String.prototype, 'foo', { value: 1 });": [
+        {
+          col: 0,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "String"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      r#"import * as mod from "./mod.ts"; Reflect.set(mod.config, "debug", true);"#: [
+        {
+          col: 33,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Imported, "mod"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "if (!globalThis.foo) { (globalThis as any).foo = 1; }": [
+        {
+          col: 23,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "globalThis"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+      "export const handler = (Deno.env.foo = 1);": [
+        {
+          col: 24,
+          message: variant!(NoGlobalStateMutationInModuleScopeMessage, Global, "Deno"),
+          hint: NoGlobalStateMutationInModuleScopeHint::MoveIntoFunction,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,372 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::prop_name;
+use derive_more::Display;
+use regex::{Regex, RegexBuilder};
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  AssignExpr, AssignOp, AssignPat, ClassProp, Expr, KeyValueProp, Lit, Pat,
+  PatOrExpr, PropName, VarDeclarator,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoHardcodedCredentials {
+  options: NoHardcodedCredentialsOptions,
+  patterns: Regex,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoHardcodedCredentialsOptions {
+  /// Case-insensitive regular expressions matching the names of variables and
+  /// properties holding credentials.
+  pub patterns: Vec<String>,
+  /// Minimum Shannon entropy, in bits per character, of the strings to
+  /// report.
+  pub min_entropy: f64,
+}
+
+impl Default for NoHardcodedCredentialsOptions {
+  fn default() -> Self {
+    Self {
+      patterns: vec![
+        "passw(or)?d".to_string(),
+        "pwd".to_string(),
+        "secret".to_string(),
+        "token".to_string(),
+        "api[_-]?key".to_string(),
+        "access[_-]?key".to_string(),
+        "private[_-]?key".to_string(),
+        "credential".to_string(),
+      ],
+      min_entropy: 2.5,
+    }
+  }
+}
+
+impl NoHardcodedCredentialsOptions {
+  /// Combines the patterns into a single regular expression.
+  fn compile(&self) -> Result<Regex, regex::Error> {
+    let alternatives: Vec<String> = self
+      .patterns
+      .iter()
+      .map(|pattern| format!("(?:{})", pattern))
+      .collect();
+    // An empty alternation would match every name
+    let pattern = if alternatives.is_empty() {
+      "[^\\s\\S]".to_string()
+    } else {
+      alternatives.join("|")
+    };
+    RegexBuilder::new(&pattern).case_insensitive(true).build()
+  }
+}
+
+const CODE: &str = "no-hardcoded-credentials";
+
+#[derive(Display)]
+enum NoHardcodedCredentialsMessage {
+  #[display(fmt = "`{}` looks like a hardcoded credential", _0)]
+  Credential(String),
+}
+
+#[derive(Display)]
+enum NoHardcodedCredentialsHint {
+  #[display(
+    fmt = "Load the credential at runtime instead, e.g. with `Deno.env.get()`, and revoke the committed one"
+  )]
+  LoadAtRuntime,
+}
+
+impl LintRule for NoHardcodedCredentials {
+  fn new() -> Box<Self> {
+    let options = NoHardcodedCredentialsOptions::default();
+    let patterns = options.compile().unwrap();
+    Box::new(NoHardcodedCredentials { options, patterns })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoHardcodedCredentialsVisitor {
+      context,
+      patterns: &self.patterns,
+      min_entropy: self.options.min_entropy,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: NoHardcodedCredentialsOptions =
+      serde_json::from_value(options)?;
+    self.patterns = options
+      .compile()
+      .map_err(|err| serde_json::Error::custom(err.to_string()))?;
+    self.options = options;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "patterns": {
+          "description": "Case-insensitive regular expressions matching the names of variables and properties holding credentials.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": NoHardcodedCredentialsOptions::default().patterns
+        },
+        "minEntropy": {
+          "description": "Minimum Shannon entropy, in bits per character, of the strings to report.",
+          "type": "number",
+          "minimum": 0,
+          "default": 2.5
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows assigning string literals that look like secrets to credential variables",
+      "no-hardcoded-credentials"
+    )
+  }
+}
+
+/// Returns the Shannon entropy of the string, in bits per character.
+fn entropy(s: &str) -> f64 {
+  let mut counts = std::collections::HashMap::new();
+  for c in s.chars() {
+    *counts.entry(c).or_insert(0) += 1;
+  }
+  let len = s.chars().count() as f64;
+  counts
+    .values()
+    .map(|count| {
+      let p = *count as f64 / len;
+      -p * p.log2()
+    })
+    .sum()
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Lit(Lit::Str(s)) => Some(s.value.to_string()),
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+      Some(tpl.quasis.iter().map(|q| q.raw.value.to_string()).collect())
+    }
+    _ => None,
+  }
+}
+
+struct NoHardcodedCredentialsVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  patterns: &'c Regex,
+  min_entropy: f64,
+}
+
+impl<'c, 'view> NoHardcodedCredentialsVisitor<'c, 'view> {
+  fn check(&mut self, name: &str, value: &Expr) {
+    if !self.patterns.is_match(name) {
+      return;
+    }
+    let secret = match string_value(value) {
+      Some(secret) => secret,
+      None => return,
+    };
+    // Secrets don't contain spaces, unlike labels and messages such as
+    // "Enter your password"
+    if secret.is_empty()
+      || secret.contains(char::is_whitespace)
+      || entropy(&secret) < self.min_entropy
+    {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      value.span(),
+      CODE,
+      NoHardcodedCredentialsMessage::Credential(name.to_string()),
+      NoHardcodedCredentialsHint::LoadAtRuntime,
+    );
+  }
+
+  fn check_pat(&mut self, pat: &Pat, value: &Expr) {
+    if let Pat::Ident(ident) = pat {
+      self.check(&ident.id.sym, value);
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoHardcodedCredentialsVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    if let Some(init) = &declarator.init {
+      self.check_pat(&declarator.name, init);
+    }
+    declarator.visit_children_with(self);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    if assign_expr.op == AssignOp::Assign {
+      match &assign_expr.left {
+        PatOrExpr::Pat(pat) => match &**pat {
+          Pat::Expr(expr) => {
+            if let Expr::Member(member) = &**expr {
+              if let Some(name) = prop_name(member) {
+                self.check(name, &assign_expr.right);
+              }
+            }
+          }
+          pat => self.check_pat(pat, &assign_expr.right),
+        },
+        PatOrExpr::Expr(expr) => match &**expr {
+          Expr::Ident(ident) => self.check(&ident.sym, &assign_expr.right),
+          Expr::Member(member) => {
+            if let Some(name) = prop_name(member) {
+              self.check(name, &assign_expr.right);
+            }
+          }
+          _ => {}
+        },
+      }
+    }
+    assign_expr.visit_children_with(self);
+  }
+
+  fn visit_assign_pat(&mut self, assign_pat: &AssignPat, _: &dyn Node) {
+    self.check_pat(&assign_pat.left, &assign_pat.right);
+    assign_pat.visit_children_with(self);
+  }
+
+  fn visit_key_value_prop(&mut self, prop: &KeyValueProp, _: &dyn Node) {
+    match &prop.key {
+      PropName::Ident(ident) => self.check(&ident.sym, &prop.value),
+      PropName::Str(s) => self.check(&s.value, &prop.value),
+      _ => {}
+    }
+    prop.visit_children_with(self);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, _: &dyn Node) {
+    if let (Expr::Ident(ident), Some(value), false) =
+      (&*class_prop.key, &class_prop.value, class_prop.computed)
+    {
+      self.check(&ident.sym, value);
+    }
+    class_prop.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_hardcoded_credentials_valid() {
+    assert_lint_ok! {
+      NoHardcodedCredentials,
+      r#"const password = Deno.env.get("PASSWORD");"#,
+      r#"const password = "";"#,
+      r#"const token = "xxxxxxxx";"#,
+      r#"const passwordLabel = "Enter your password";"#,
+      r#"const TOKEN_TYPE = "Bearer";"#,
+      r#"const passwordField = "pass";"#,
+      r#"const greeting = "aB3$kL9!mQ2#";"#,
+      r#"const apiKey = `${prefix}k8Jd9aQ2Lm`;"#,
+      r#"headers.authorization = "Zx81Kd0aPq";"#,
+    };
+
+    assert_lint_ok! {
+      NoHardcodedCredentials,
+      options: json!({ "minEntropy": 4.5 }),
+      r#"const apiKey = "sk_live_4eC39HqL";"#,
+    };
+  }
+
+  #[test]
+  fn no_hardcoded_credentials_invalid() {
+    assert_lint_err! {
+      NoHardcodedCredentials,
+      r#"const password = "hunter2";"#: [
+        {
+          col: 17,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "password"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+      r#"const adminPassword = "admin123";"#: [
+        {
+          col: 22,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "adminPassword"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+      r#"const config = { apiKey: "sk_live_4eC39HqL", "x-api-key": `AKIA7Y2Q9ZP` };"#: [
+        {
+          col: 25,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "apiKey"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        },
+        {
+          col: 58,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "x-api-key"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+      r#"client.secret = "9fQ2kL7xPz"; GITHUB_TOKEN = "ghp_8sK2mQ9x";"#: [
+        {
+          col: 16,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "secret"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        },
+        {
+          col: 45,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "GITHUB_TOKEN"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+      r#"class Client { privateKey = "MIIEvQIBADAN"; connect(pwd = "s3cR7T!xq") {} }"#: [
+        {
+          col: 28,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "privateKey"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        },
+        {
+          col: 58,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "pwd"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoHardcodedCredentials,
+      options: json!({ "patterns": ["^dbPass$"], "minEntropy": 2 }),
+      r#"const dbPass = "letmein"; const password = "hunter2";"#: [
+        {
+          col: 15,
+          message: variant!(NoHardcodedCredentialsMessage, Credential, "dbPass"),
+          hint: NoHardcodedCredentialsHint::LoadAtRuntime,
+        }
+      ],
+    };
+  }
+}
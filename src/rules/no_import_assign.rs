@@ -32,6 +32,10 @@ impl LintRule for NoImportAssign {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -60,34 +64,10 @@ impl LintRule for NoImportAssign {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows reassignment of imported module bindings
-
-ES module import bindings should be treated as read-only since modifying them
-during code execution will likely result in runtime errors.  It also makes for
-poor code readability and difficult maintenance.
-
-### Invalid:
-```typescript
-import defaultMod, { namedMod } from './mod.js'; 
-import * as modNameSpace from './mod2.js';
-
-defaultMod = 0;
-namedMod = true;
-modNameSpace.someExportedMember = "hello";
-modNameSpace = {};
-```
-
-### Valid:
-```typescript
-import defaultMod, { namedMod } from './mod.js'; 
-import * as modNameSpace from './mod2.js';
-
-// properties of bound imports may be set
-defaultMod.prop = 1;
-namedMod.prop = true;
-modNameSpace.someExportedMember.prop = "hello";
-```
-"#
+    rule_docs!(
+      "Disallows reassignment of imported module bindings",
+      "no-import-assign"
+    )
   }
 }
 
@@ -0,0 +1,314 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoImportTestOnlyModulesInProd {
+  options: NoImportTestOnlyModulesInProdOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoImportTestOnlyModulesInProdOptions {
+  /// Patterns matched against import specifiers, a module is test-only if
+  /// its specifier contains any of them.
+  pub test_modules: Vec<String>,
+  /// Patterns matched against file paths, a file is a test file if its path
+  /// contains any of them.
+  pub test_files: Vec<String>,
+}
+
+impl Default for NoImportTestOnlyModulesInProdOptions {
+  fn default() -> Self {
+    Self {
+      test_modules: vec![
+        "@std/testing".to_string(),
+        "@std/expect".to_string(),
+        "/testing/".to_string(),
+        "/expect/".to_string(),
+        "/mock".to_string(),
+      ],
+      test_files: vec![
+        "_test.".to_string(),
+        ".test.".to_string(),
+        "/test/".to_string(),
+        "/tests/".to_string(),
+        "/testdata/".to_string(),
+        "/__tests__/".to_string(),
+        "test_util".to_string(),
+      ],
+    }
+  }
+}
+
+const CODE: &str = "no-import-test-only-modules-in-prod";
+
+#[derive(Display)]
+enum NoImportTestOnlyModulesInProdMessage {
+  #[display(
+    fmt = "Test-only module '{}' is imported from a non-test file",
+    _0
+  )]
+  TestOnly(String),
+}
+
+#[derive(Display)]
+enum NoImportTestOnlyModulesInProdHint {
+  #[display(
+    fmt = "Move the code using it to a test file, or add this file to the `testFiles` option if it's only used by tests"
+  )]
+  MoveToTest,
+}
+
+impl LintRule for NoImportTestOnlyModulesInProd {
+  fn new() -> Box<Self> {
+    Box::new(NoImportTestOnlyModulesInProd {
+      options: NoImportTestOnlyModulesInProdOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    // Match relative paths such as `test/a.ts` against `/test/` too
+    let path = format!("/{}", context.file_name().replace('\\', "/"));
+    if self
+      .options
+      .test_files
+      .iter()
+      .any(|pattern| path.contains(pattern.as_str()))
+    {
+      return;
+    }
+
+    let mut visitor = NoImportTestOnlyModulesInProdVisitor {
+      context,
+      test_modules: &self.options.test_modules,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let defaults = NoImportTestOnlyModulesInProdOptions::default();
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "testModules": {
+          "description": "Patterns matched against import specifiers, a module is test-only if its specifier contains any of them.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": defaults.test_modules
+        },
+        "testFiles": {
+          "description": "Patterns matched against file paths, a file is a test file if its path contains any of them.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": defaults.test_files
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows importing testing modules from non-test files",
+      "no-import-test-only-modules-in-prod"
+    )
+  }
+}
+
+struct NoImportTestOnlyModulesInProdVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  test_modules: &'c [String],
+}
+
+impl<'c, 'view> NoImportTestOnlyModulesInProdVisitor<'c, 'view> {
+  fn check_specifier(&mut self, src: &Str) {
+    if self
+      .test_modules
+      .iter()
+      .any(|pattern| src.value.contains(pattern.as_str()))
+    {
+      self.context.add_diagnostic_with_hint(
+        src.span,
+        CODE,
+        NoImportTestOnlyModulesInProdMessage::TestOnly(src.value.to_string()),
+        NoImportTestOnlyModulesInProdHint::MoveToTest,
+      );
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoImportTestOnlyModulesInProdVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    // Type-only imports are erased and never end up in a bundle
+    if !import_decl.type_only {
+      self.check_specifier(&import_decl.src);
+    }
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_specifier(&export_all.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      if !named_export.type_only {
+        self.check_specifier(src);
+      }
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          if let Expr::Lit(Lit::Str(src)) = &*arg.expr {
+            self.check_specifier(src);
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_import_test_only_modules_in_prod_valid() {
+    assert_lint_ok! {
+      NoImportTestOnlyModulesInProd,
+      {
+        src: r#"import { assertEquals } from "jsr:@std/assert";"#,
+        filename: "src/mod.ts",
+      },
+      {
+        src: r#"import { spy } from "jsr:@std/testing/mock";"#,
+        filename: "src/mod_test.ts",
+      },
+      {
+        src: r#"import { expect } from "jsr:@std/expect";"#,
+        filename: "src/parser.test.ts",
+      },
+      {
+        src: r#"import { FakeTime } from "https://deno.land/std@0.200.0/testing/time.ts";"#,
+        filename: "tests/time.ts",
+      },
+      {
+        src: r#"import { stub } from "jsr:@std/testing/mock";"#,
+        filename: "C:\\project\\test\\helpers.ts",
+      },
+      {
+        src: r#"import type { Spy } from "jsr:@std/testing/mock";"#,
+        filename: "src/mod.ts",
+      },
+      {
+        src: r#"export type { Stub } from "jsr:@std/testing/mock";"#,
+        filename: "src/mod.ts",
+      },
+      {
+        src: r#"const mod = await import(specifier);"#,
+        filename: "src/mod.ts",
+      },
+    };
+
+    assert_lint_ok! {
+      NoImportTestOnlyModulesInProd,
+      options: json!({ "testFiles": ["/fixtures/"] }),
+      {
+        src: r#"import { spy } from "jsr:@std/testing/mock";"#,
+        filename: "fixtures/spies.ts",
+      },
+    };
+  }
+
+  #[test]
+  fn no_import_test_only_modules_in_prod_invalid() {
+    assert_lint_err! {
+      NoImportTestOnlyModulesInProd,
+      r#"import { spy } from "jsr:@std/testing/mock";"#: {
+        filename: "src/mod.ts",
+        errors: [
+          {
+            col: 20,
+            message: variant!(NoImportTestOnlyModulesInProdMessage, TestOnly, "jsr:@std/testing/mock"),
+            hint: NoImportTestOnlyModulesInProdHint::MoveToTest,
+          }
+        ],
+      },
+      r#"import { expect } from "jsr:@std/expect";"#: {
+        filename: "src/mod.ts",
+        errors: [
+          {
+            col: 23,
+            message: variant!(NoImportTestOnlyModulesInProdMessage, TestOnly, "jsr:@std/expect"),
+            hint: NoImportTestOnlyModulesInProdHint::MoveToTest,
+          }
+        ],
+      },
+      r#"export * from "https://deno.land/std@0.200.0/testing/bdd.ts";"#: {
+        filename: "mod.ts",
+        errors: [
+          {
+            col: 14,
+            message: variant!(NoImportTestOnlyModulesInProdMessage, TestOnly, "https://deno.land/std@0.200.0/testing/bdd.ts"),
+            hint: NoImportTestOnlyModulesInProdHint::MoveToTest,
+          }
+        ],
+      },
+      r#"const { stub } = await import("./mocks/db.ts");"#: {
+        filename: "src/db.ts",
+        errors: [
+          {
+            col: 30,
+            message: variant!(NoImportTestOnlyModulesInProdMessage, TestOnly, "./mocks/db.ts"),
+            hint: NoImportTestOnlyModulesInProdHint::MoveToTest,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      NoImportTestOnlyModulesInProd,
+      options: json!({ "testModules": ["npm:sinon"] }),
+      r#"import sinon from "npm:sinon";"#: {
+        filename: "src/mod.ts",
+        errors: [
+          {
+            col: 18,
+            message: variant!(NoImportTestOnlyModulesInProdMessage, TestOnly, "npm:sinon"),
+            hint: NoImportTestOnlyModulesInProdHint::MoveToTest,
+          }
+        ],
+      },
+    };
+  }
+}
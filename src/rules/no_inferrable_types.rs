@@ -1,6 +1,9 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
 use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
 use swc_ecmascript::ast::{
   ArrowExpr, CallExpr, ClassProp, Expr, ExprOrSuper, Function, Ident, Lit,
   NewExpr, OptChainExpr, Pat, PrivateProp, TsEntityName, TsKeywordType,
@@ -9,7 +12,18 @@ use swc_ecmascript::ast::{
 use swc_ecmascript::visit::Node;
 use swc_ecmascript::visit::{VisitAll, VisitAllWith};
 
-pub struct NoInferrableTypes;
+pub struct NoInferrableTypes {
+  options: NoInferrableTypesOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoInferrableTypesOptions {
+  /// Whether to allow inferrable types on function parameters.
+  pub ignore_parameters: bool,
+  /// Whether to allow inferrable types on class properties.
+  pub ignore_properties: bool,
+}
 
 const CODE: &str = "no-inferrable-types";
 
@@ -25,9 +39,17 @@ enum NoInferrableTypesHint {
   Remove,
 }
 
+#[derive(Display)]
+enum NoInferrableTypesFix {
+  #[display(fmt = "Remove the type annotation")]
+  RemoveTypeAnnotation,
+}
+
 impl LintRule for NoInferrableTypes {
   fn new() -> Box<Self> {
-    Box::new(NoInferrableTypes)
+    Box::new(NoInferrableTypes {
+      options: NoInferrableTypesOptions::default(),
+    })
   }
 
   fn tags(&self) -> &'static [&'static str] {
@@ -38,103 +60,102 @@ impl LintRule for NoInferrableTypes {
     CODE
   }
 
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
     program: ProgramRef<'view>,
   ) {
-    let mut visitor = NoInferrableTypesVisitor::new(context);
+    let mut visitor = NoInferrableTypesVisitor::new(context, &self.options);
     match program {
       ProgramRef::Module(ref m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
       ProgramRef::Script(ref s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
     }
   }
 
-  fn docs(&self) -> &'static str {
-    r#"Disallows easily inferrable types
-
-Variable initializations to javascript primitives (and null) are obvious
-in their type.  Specifying their type can add additional verbosity to the code.
-For example, with `const x: number = 5`, specifying `number` is unnecessary as
-it is obvious that `5` is a number.
-    
-### Invalid:
-```typescript
-const a: bigint = 10n;
-const b: bigint = BigInt(10);
-const c: boolean = true;
-const d: boolean = !0;
-const e: number = 10;
-const f: number = Number('1');
-const g: number = Infinity;
-const h: number = NaN;
-const i: null = null;
-const j: RegExp = /a/;
-const k: RegExp = RegExp('a');
-const l: RegExp = new RegExp('a');
-const m: string = 'str';
-const n: string = `str`;
-const o: string = String(1);
-const p: symbol = Symbol('a');
-const q: undefined = undefined;
-const r: undefined = void someValue;
-
-class Foo {
-  prop: number = 5;
-}
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
 
-function fn(s: number = 5, t: boolean = true) {}
-```
-
-### Valid:
-```typescript
-const a = 10n;
-const b = BigInt(10);
-const c = true;
-const d = !0;
-const e = 10;
-const f = Number('1');
-const g = Infinity;
-const h = NaN;
-const i = null;
-const j = /a/;
-const k = RegExp('a');
-const l = new RegExp('a');
-const m = 'str';
-const n = `str`;
-const o = String(1);
-const p = Symbol('a');
-const q = undefined;
-const r = void someValue;
-
-class Foo {
-  prop = 5;
-}
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "ignoreParameters": {
+          "description": "Whether to allow inferrable types on function parameters.",
+          "type": "boolean",
+          "default": false
+        },
+        "ignoreProperties": {
+          "description": "Whether to allow inferrable types on class properties.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
 
-function fn(s = 5, t = true) {}
-```
-"#
+  fn docs(&self) -> &'static str {
+    rule_docs!("Disallows easily inferrable types", "no-inferrable-types")
   }
 }
 
 struct NoInferrableTypesVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  ignore_parameters: bool,
+  ignore_properties: bool,
+  /// The span of the type annotation being checked, if it can be removed
+  /// without changing the type of the binding.
+  removable_type_ann: Option<Span>,
 }
 
 impl<'c, 'view> NoInferrableTypesVisitor<'c, 'view> {
-  fn new(context: &'c mut Context<'view>) -> Self {
-    Self { context }
+  fn new(
+    context: &'c mut Context<'view>,
+    options: &NoInferrableTypesOptions,
+  ) -> Self {
+    Self {
+      context,
+      ignore_parameters: options.ignore_parameters,
+      ignore_properties: options.ignore_properties,
+      removable_type_ann: None,
+    }
   }
 
   fn add_diagnostic_helper(&mut self, span: swc_common::Span) {
-    self.context.add_diagnostic_with_hint(
+    let fixes = match self.removable_type_ann {
+      Some(type_ann_span) => vec![self.context.create_fix(
+        NoInferrableTypesFix::RemoveTypeAnnotation,
+        vec![(type_ann_span, "".to_string())],
+      )],
+      None => vec![],
+    };
+    self.context.add_diagnostic_with_fixes(
       span,
       CODE,
       NoInferrableTypesMessage::NotAllowed,
-      NoInferrableTypesHint::Remove,
+      Some(NoInferrableTypesHint::Remove.to_string()),
+      fixes,
     )
   }
 
+  fn has_comments(&self, span: Span) -> bool {
+    self.context.all_comments().any(|comment| {
+      span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+    })
+  }
+
   fn check_callee(
     &mut self,
     callee: &ExprOrSuper,
@@ -339,6 +360,22 @@ impl<'c, 'view> NoInferrableTypesVisitor<'c, 'view> {
     ts_type: &TsTypeAnn,
     span: swc_common::Span,
   ) {
+    // Without an annotation, bindings initialized with `null` or `undefined`
+    // are widened to `any`, and comments in the annotation would be lost
+    let widens = matches!(
+      &*ts_type.type_ann,
+      TsType::TsKeywordType(TsKeywordType {
+        kind: TsKeywordTypeKind::TsNullKeyword
+          | TsKeywordTypeKind::TsUndefinedKeyword,
+        ..
+      })
+    );
+    self.removable_type_ann = if widens || self.has_comments(ts_type.span) {
+      None
+    } else {
+      Some(ts_type.span)
+    };
+
     if let TsType::TsKeywordType(ts_type) = &*ts_type.type_ann {
       self.check_keyword_type(&value, ts_type, span);
     } else if let TsType::TsTypeRef(ts_type) = &*ts_type.type_ann {
@@ -349,6 +386,9 @@ impl<'c, 'view> NoInferrableTypesVisitor<'c, 'view> {
 
 impl<'c, 'view> VisitAll for NoInferrableTypesVisitor<'c, 'view> {
   fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    if self.ignore_parameters {
+      return;
+    }
     for param in &function.params {
       if let Pat::Assign(assign_pat) = &param.pat {
         if let Pat::Ident(ident) = &*assign_pat.left {
@@ -361,6 +401,9 @@ impl<'c, 'view> VisitAll for NoInferrableTypesVisitor<'c, 'view> {
   }
 
   fn visit_arrow_expr(&mut self, arr_expr: &ArrowExpr, _: &dyn Node) {
+    if self.ignore_parameters {
+      return;
+    }
     for param in &arr_expr.params {
       if let Pat::Assign(assign_pat) = &param {
         if let Pat::Ident(ident) = &*assign_pat.left {
@@ -377,7 +420,7 @@ impl<'c, 'view> VisitAll for NoInferrableTypesVisitor<'c, 'view> {
   }
 
   fn visit_class_prop(&mut self, prop: &ClassProp, _: &dyn Node) {
-    if prop.readonly || prop.is_optional {
+    if self.ignore_properties || prop.readonly || prop.is_optional {
       return;
     }
     if let Some(init) = &prop.value {
@@ -390,7 +433,7 @@ impl<'c, 'view> VisitAll for NoInferrableTypesVisitor<'c, 'view> {
   }
 
   fn visit_private_prop(&mut self, prop: &PrivateProp, _: &dyn Node) {
-    if prop.readonly || prop.is_optional {
+    if self.ignore_properties || prop.readonly || prop.is_optional {
       return;
     }
     if let Some(init) = &prop.value {
@@ -416,6 +459,7 @@ impl<'c, 'view> VisitAll for NoInferrableTypesVisitor<'c, 'view> {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use serde_json::json;
 
   #[test]
   fn no_inferrable_types_valid() {
@@ -480,6 +524,20 @@ mod tests {
       }",
       "const fn = function (a: any = 5, b: any = true, c: any = 'foo') {};",
     };
+
+    assert_lint_ok! {
+      NoInferrableTypes,
+      options: json!({ "ignoreParameters": true }),
+      "function fn(a: number = 5, b: boolean = true) {}",
+      "const fn = (a: string = 'foo') => {};",
+      "class A { a(x: number = 42) {} }",
+    };
+
+    assert_lint_ok! {
+      NoInferrableTypes,
+      options: json!({ "ignoreProperties": true }),
+      "class A { a: number = 42; #b: string = ''; }",
+    };
   }
 
   #[test]
@@ -491,6 +549,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = 10n"),
         }
       ],
       "const a: bigint = -10n": [
@@ -498,6 +557,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -10n"),
         }
       ],
       "const a: bigint = BigInt(10)": [
@@ -505,6 +565,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = BigInt(10)"),
         }
       ],
       "const a: bigint = -BigInt?.(10)": [
@@ -512,6 +573,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -BigInt?.(10)"),
         }
       ],
       "const a: bigint = -BigInt?.(10)": [
@@ -519,6 +581,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -BigInt?.(10)"),
         }
       ],
       "const a: boolean = false": [
@@ -526,6 +589,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = false"),
         }
       ],
       "const a: boolean = true": [
@@ -533,6 +597,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = true"),
         }
       ],
       "const a: boolean = Boolean(true)": [
@@ -540,6 +605,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Boolean(true)"),
         }
       ],
       "const a: boolean = Boolean(null)": [
@@ -547,6 +613,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Boolean(null)"),
         }
       ],
       "const a: boolean = Boolean?.(null)": [
@@ -554,6 +621,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Boolean?.(null)"),
         }
       ],
       "const a: boolean = !0": [
@@ -561,6 +629,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = !0"),
         }
       ],
       "const a: number = 10": [
@@ -568,6 +637,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = 10"),
         }
       ],
       "const a: number = +10": [
@@ -575,6 +645,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = +10"),
         }
       ],
       "const a: number = -10": [
@@ -582,6 +653,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -10"),
         }
       ],
       "const a: number = Number('1')": [
@@ -589,6 +661,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Number('1')"),
         }
       ],
       "const a: number = +Number('1')": [
@@ -596,6 +669,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = +Number('1')"),
         }
       ],
       "const a: number = -Number('1')": [
@@ -603,6 +677,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -Number('1')"),
         }
       ],
       "const a: number = Number?.('1')": [
@@ -610,6 +685,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Number?.('1')"),
         }
       ],
       "const a: number = +Number?.('1')": [
@@ -617,6 +693,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = +Number?.('1')"),
         }
       ],
       "const a: number = -Number?.('1')": [
@@ -624,6 +701,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -Number?.('1')"),
         }
       ],
       "const a: number = Infinity": [
@@ -631,6 +709,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Infinity"),
         }
       ],
       "const a: number = +Infinity": [
@@ -638,6 +717,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = +Infinity"),
         }
       ],
       "const a: number = -Infinity": [
@@ -645,6 +725,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -Infinity"),
         }
       ],
       "const a: number = NaN": [
@@ -652,6 +733,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = NaN"),
         }
       ],
       "const a: number = +NaN": [
@@ -659,6 +741,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = +NaN"),
         }
       ],
       "const a: number = -NaN": [
@@ -666,6 +749,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = -NaN"),
         }
       ],
       "const a: null = null": [
@@ -680,6 +764,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = /a/"),
         }
       ],
       "const a: RegExp = RegExp('a')": [
@@ -687,6 +772,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = RegExp('a')"),
         }
       ],
       "const a: RegExp = RegExp?.('a')": [
@@ -694,6 +780,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = RegExp?.('a')"),
         }
       ],
       "const a: RegExp = new RegExp?.('a')": [
@@ -701,6 +788,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = new RegExp?.('a')"),
         }
       ],
       "const a: string = 'str'": [
@@ -708,6 +796,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = 'str'"),
         }
       ],
       r#"const a: string = "str""#: [
@@ -715,6 +804,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, r#"const a = "str""#),
         }
       ],
       "const a: string = `str`": [
@@ -722,6 +812,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = `str`"),
         }
       ],
       "const a: string = String(1)": [
@@ -729,6 +820,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = String(1)"),
         }
       ],
       "const a: string = String?.(1)": [
@@ -736,6 +828,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = String?.(1)"),
         }
       ],
       "const a: symbol = Symbol('a')": [
@@ -743,6 +836,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Symbol('a')"),
         }
       ],
       "const a: symbol = Symbol?.('a')": [
@@ -750,6 +844,7 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = Symbol?.('a')"),
         }
       ],
       "const a: undefined = undefined": [
@@ -771,11 +866,13 @@ mod tests {
           col: 6,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = 0, b: string = 'foo';"),
         },
         {
           col: 21,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a: number = 0, b = 'foo';"),
         }
       ],
       "function f(a: number = 5) {};": [
@@ -783,6 +880,7 @@ mod tests {
           col: 11,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "function f(a = 5) {};"),
         }
       ],
       "const fn = (a: number = 5, b: boolean = true, c: string = 'foo') => {};": [
@@ -790,16 +888,19 @@ mod tests {
           col: 12,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const fn = (a = 5, b: boolean = true, c: string = 'foo') => {};"),
         },
         {
           col: 27,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const fn = (a: number = 5, b = true, c: string = 'foo') => {};"),
         },
         {
           col: 46,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const fn = (a: number = 5, b: boolean = true, c = 'foo') => {};"),
         }
       ],
       "class A { a: number = 42; }": [
@@ -807,6 +908,7 @@ mod tests {
           col: 10,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { a = 42; }"),
         }
       ],
       "class A { a(x: number = 42) {} }": [
@@ -814,6 +916,7 @@ mod tests {
           col: 12,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { a(x = 42) {} }"),
         }
       ],
 
@@ -823,6 +926,7 @@ mod tests {
           col: 10,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { #foo = '' }"),
         }
       ],
       "class A { static #foo: string = '' }": [
@@ -830,6 +934,7 @@ mod tests {
           col: 10,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { static #foo = '' }"),
         }
       ],
       "class A { #foo(x: number = 42) {} }": [
@@ -837,6 +942,7 @@ mod tests {
           col: 15,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { #foo(x = 42) {} }"),
         }
       ],
       "class A { static #foo(x: number = 42) {} }": [
@@ -844,6 +950,7 @@ mod tests {
           col: 22,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { static #foo(x = 42) {} }"),
         }
       ],
 
@@ -853,6 +960,7 @@ mod tests {
           col: 21,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "function a() { const x = 5; }"),
         }
       ],
       "const a = () => { const b = (x: number = 42) => {}; };": [
@@ -860,6 +968,7 @@ mod tests {
           col: 29,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = () => { const b = (x = 42) => {}; };"),
         }
       ],
       "class A { a = class { b: number = 42; }; }": [
@@ -867,6 +976,7 @@ mod tests {
           col: 22,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { a = class { b = 42; }; }"),
         }
       ],
       "const a = function () { let x: number = 42; };": [
@@ -874,6 +984,45 @@ mod tests {
           col: 28,
           message: NoInferrableTypesMessage::NotAllowed,
           hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "const a = function () { let x = 42; };"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInferrableTypes,
+      options: json!({ "ignoreParameters": true }),
+      "function f(a: number = 5) { const b: number = a; const c: number = 5; }": [
+        {
+          col: 55,
+          message: NoInferrableTypesMessage::NotAllowed,
+          hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "function f(a: number = 5) { const b: number = a; const c = 5; }"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInferrableTypes,
+      options: json!({ "ignoreProperties": true }),
+      "class A { a: number = 42; b(x: number = 42) {} }": [
+        {
+          col: 28,
+          message: NoInferrableTypesMessage::NotAllowed,
+          hint: NoInferrableTypesHint::Remove,
+          fix: (NoInferrableTypesFix::RemoveTypeAnnotation, "class A { a: number = 42; b(x = 42) {} }"),
+        }
+      ],
+    };
+
+    // comments in the annotation would be lost
+    assert_lint_err! {
+      NoInferrableTypes,
+      "const a: /* count */ number = 10": [
+        {
+          col: 6,
+          message: NoInferrableTypesMessage::NotAllowed,
+          hint: NoInferrableTypesHint::Remove,
         }
       ],
     };
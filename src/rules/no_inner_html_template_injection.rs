@@ -0,0 +1,474 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  deno_command_subprocess, deno_run_subprocess, member_name, DenoSubprocess,
+};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, Function,
+  NewExpr, Pat, PatOrExpr, VarDeclarator,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoInnerHtmlTemplateInjection {
+  options: NoInnerHtmlTemplateInjectionOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoInnerHtmlTemplateInjectionOptions {
+  /// Words that mark an identifier as holding user input, e.g. `req` matches
+  /// `req`, `reqBody` and `raw_req`.
+  pub patterns: Vec<String>,
+}
+
+impl Default for NoInnerHtmlTemplateInjectionOptions {
+  fn default() -> Self {
+    Self {
+      patterns: ["req", "request", "params", "query", "input"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect(),
+    }
+  }
+}
+
+const CODE: &str = "no-inner-html-template-injection";
+
+#[derive(Display)]
+enum NoInnerHtmlTemplateInjectionMessage {
+  #[display(
+    fmt = "Template literal interpolating user input is passed to `{}`",
+    _0
+  )]
+  Injection(String),
+}
+
+#[derive(Display)]
+enum NoInnerHtmlTemplateInjectionHint {
+  #[display(
+    fmt = "Use `textContent`, or escape the interpolated values before building HTML"
+  )]
+  EscapeHtml,
+  #[display(fmt = "Parse the input instead of evaluating it as code")]
+  AvoidEval,
+  #[display(
+    fmt = "Validate the input before passing it to a subprocess, and pass each value as a separate argument"
+  )]
+  ValidateArgs,
+}
+
+/// Where a template literal ends up.
+#[derive(Clone, Copy)]
+enum Sink {
+  InnerHtml,
+  Eval,
+  DenoRun,
+  DenoCommand,
+}
+
+impl Sink {
+  fn name(self) -> &'static str {
+    match self {
+      Sink::InnerHtml => "innerHTML",
+      Sink::Eval => "eval",
+      Sink::DenoRun => "Deno.run",
+      Sink::DenoCommand => "Deno.Command",
+    }
+  }
+
+  fn hint(self) -> NoInnerHtmlTemplateInjectionHint {
+    match self {
+      Sink::InnerHtml => NoInnerHtmlTemplateInjectionHint::EscapeHtml,
+      Sink::Eval => NoInnerHtmlTemplateInjectionHint::AvoidEval,
+      Sink::DenoRun | Sink::DenoCommand => {
+        NoInnerHtmlTemplateInjectionHint::ValidateArgs
+      }
+    }
+  }
+}
+
+impl LintRule for NoInnerHtmlTemplateInjection {
+  fn new() -> Box<Self> {
+    Box::new(NoInnerHtmlTemplateInjection {
+      options: NoInnerHtmlTemplateInjectionOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoInnerHtmlTemplateInjectionVisitor {
+      context,
+      patterns: self
+        .options
+        .patterns
+        .iter()
+        .map(|p| p.to_lowercase())
+        .collect(),
+      frame: Frame::default(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "patterns": {
+          "description": "Words that mark an identifier as holding user input, e.g. `req` matches `req`, `reqBody` and `raw_req`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": NoInnerHtmlTemplateInjectionOptions::default().patterns
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows template literals interpolating user input from reaching `innerHTML`, `eval` or subprocess arguments",
+      "no-inner-html-template-injection"
+    )
+  }
+}
+
+/// Variables of the function being visited that hold user input.
+#[derive(Default)]
+struct Frame {
+  /// Variables holding values derived from user input.
+  tainted: HashSet<Id>,
+  /// Variables holding template literals that interpolate user input.
+  templates: HashSet<Id>,
+}
+
+struct NoInnerHtmlTemplateInjectionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  patterns: Vec<String>,
+  frame: Frame,
+}
+
+/// Splits an identifier into lowercase words, e.g. `rawReqBody` into `raw`,
+/// `req` and `body`, and `URLParams` into `url` and `params`.
+fn words(name: &str) -> Vec<String> {
+  let chars: Vec<char> = name.chars().collect();
+  let mut words = vec![];
+  let mut word = String::new();
+  for (i, &c) in chars.iter().enumerate() {
+    if c == '_' || c == '$' {
+      if !word.is_empty() {
+        words.push(std::mem::take(&mut word));
+      }
+      continue;
+    }
+    if c.is_uppercase() && !word.is_empty() {
+      let prev_lower = chars[i - 1].is_lowercase() || chars[i - 1].is_numeric();
+      let next_lower = chars.get(i + 1).map(|c| c.is_lowercase());
+      if prev_lower || (chars[i - 1].is_uppercase() && next_lower == Some(true))
+      {
+        words.push(std::mem::take(&mut word));
+      }
+    }
+    word.extend(c.to_lowercase());
+  }
+  if !word.is_empty() {
+    words.push(word);
+  }
+  words
+}
+
+impl<'c, 'view> NoInnerHtmlTemplateInjectionVisitor<'c, 'view> {
+  fn is_input_name(&self, name: &str) -> bool {
+    words(name)
+      .iter()
+      .any(|word| self.patterns.iter().any(|p| p == word))
+  }
+
+  /// Returns `true` if the value of `expr` is derived from user input.
+  fn is_tainted(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Ident(ident) => {
+        self.frame.tainted.contains(&ident.to_id())
+          || self.is_input_name(&ident.sym)
+      }
+      Expr::Member(member) => {
+        let obj_tainted = match &member.obj {
+          ExprOrSuper::Expr(obj) => self.is_tainted(obj),
+          ExprOrSuper::Super(_) => false,
+        };
+        obj_tainted
+          || matches!(member_name(member), Some(name) if self.is_input_name(name))
+      }
+      Expr::Call(call) => {
+        let callee_tainted = match &call.callee {
+          ExprOrSuper::Expr(callee) => self.is_tainted(callee),
+          ExprOrSuper::Super(_) => false,
+        };
+        callee_tainted || call.args.iter().any(|arg| self.is_tainted(&arg.expr))
+      }
+      Expr::Tpl(tpl) => tpl.exprs.iter().any(|expr| self.is_tainted(expr)),
+      Expr::Bin(bin) => {
+        self.is_tainted(&bin.left) || self.is_tainted(&bin.right)
+      }
+      Expr::Cond(cond) => {
+        self.is_tainted(&cond.cons) || self.is_tainted(&cond.alt)
+      }
+      Expr::Array(array) => array
+        .elems
+        .iter()
+        .flatten()
+        .any(|elem| self.is_tainted(&elem.expr)),
+      Expr::Seq(seq) => {
+        matches!(seq.exprs.last(), Some(expr) if self.is_tainted(expr))
+      }
+      Expr::Assign(assign) => self.is_tainted(&assign.right),
+      Expr::Paren(paren) => self.is_tainted(&paren.expr),
+      Expr::Await(await_expr) => self.is_tainted(&await_expr.arg),
+      Expr::TsAs(as_expr) => self.is_tainted(&as_expr.expr),
+      Expr::TsNonNull(non_null) => self.is_tainted(&non_null.expr),
+      Expr::TsTypeAssertion(assertion) => self.is_tainted(&assertion.expr),
+      Expr::OptChain(opt_chain) => self.is_tainted(&opt_chain.expr),
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if `expr` evaluates to a template literal that
+  /// interpolates user input. Tagged templates are assumed to escape their
+  /// values.
+  fn is_tainted_template(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Tpl(tpl) => tpl.exprs.iter().any(|expr| self.is_tainted(expr)),
+      Expr::Ident(ident) => self.frame.templates.contains(&ident.to_id()),
+      Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+        self.is_tainted_template(&bin.left)
+          || self.is_tainted_template(&bin.right)
+      }
+      Expr::Cond(cond) => {
+        self.is_tainted_template(&cond.cons)
+          || self.is_tainted_template(&cond.alt)
+      }
+      Expr::Paren(paren) => self.is_tainted_template(&paren.expr),
+      _ => false,
+    }
+  }
+
+  /// Records that the variables `ids` now hold `value`.
+  fn bind(&mut self, ids: Vec<Id>, value: &Expr) {
+    let tainted = self.is_tainted(value);
+    let template = self.is_tainted_template(value);
+    for id in ids {
+      if tainted {
+        self.frame.tainted.insert(id.clone());
+      }
+      if template {
+        self.frame.templates.insert(id);
+      }
+    }
+  }
+
+  fn check_sink(&mut self, value: &Expr, sink: Sink) {
+    if self.is_tainted_template(value) {
+      self.context.add_diagnostic_with_hint(
+        value.span(),
+        CODE,
+        NoInnerHtmlTemplateInjectionMessage::Injection(sink.name().to_string()),
+        sink.hint(),
+      );
+    }
+  }
+
+  fn check_subprocess(&mut self, subprocess: DenoSubprocess, sink: Sink) {
+    for value in subprocess.program.into_iter().chain(subprocess.args) {
+      self.check_sink(value, sink);
+    }
+  }
+
+  fn with_frame(&mut self, f: impl FnOnce(&mut Self)) {
+    let prev = std::mem::take(&mut self.frame);
+    f(self);
+    self.frame = prev;
+  }
+}
+
+impl<'c, 'view> Visit for NoInnerHtmlTemplateInjectionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.with_frame(|v| function.visit_children_with(v));
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.with_frame(|v| arrow_expr.visit_children_with(v));
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    declarator.visit_children_with(self);
+    if let Some(init) = &declarator.init {
+      self.bind(find_ids(&declarator.name), init);
+    }
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    assign_expr.visit_children_with(self);
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => &**expr,
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => &**expr,
+        pat => {
+          self.bind(find_ids(pat), &assign_expr.right);
+          return;
+        }
+      },
+    };
+    match target {
+      Expr::Ident(ident) => self.bind(vec![ident.to_id()], &assign_expr.right),
+      Expr::Member(member) if member_name(member) == Some("innerHTML") => {
+        self.check_sink(&assign_expr.right, Sink::InnerHtml);
+      }
+      _ => {}
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    call_expr.visit_children_with(self);
+    if let Some(subprocess) = deno_run_subprocess(call_expr) {
+      self.check_subprocess(subprocess, Sink::DenoRun);
+      return;
+    }
+    let callee = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => &**callee,
+      ExprOrSuper::Super(_) => return,
+    };
+    match (callee, call_expr.args.first()) {
+      (Expr::Ident(ident), Some(arg))
+        if ident.sym == *"eval" && arg.spread.is_none() =>
+      {
+        self.check_sink(&arg.expr, Sink::Eval);
+      }
+      _ => {}
+    }
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    new_expr.visit_children_with(self);
+    if let Some(subprocess) = deno_command_subprocess(new_expr) {
+      self.check_subprocess(subprocess, Sink::DenoCommand);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn words_split_identifiers() {
+    assert_eq!(words("req"), vec!["req"]);
+    assert_eq!(words("rawReqBody"), vec!["raw", "req", "body"]);
+    assert_eq!(words("URLParams"), vec!["url", "params"]);
+    assert_eq!(words("user_input"), vec!["user", "input"]);
+    assert_eq!(words("frequency"), vec!["frequency"]);
+  }
+
+  #[test]
+  fn no_inner_html_template_injection_valid() {
+    assert_lint_ok! {
+      NoInnerHtmlTemplateInjection,
+      "el.innerHTML = `<b>${title}</b>`;",
+      "el.textContent = `<b>${req.query.name}</b>`;",
+      "el.innerHTML = html`<b>${req.query.name}</b>`;",
+      "el.innerHTML = `<b>${escape(frequency)}</b>`;",
+      "const name = req.query.name; el.innerHTML = `<b>${title}</b>`;",
+      "eval(`1 + ${count}`);",
+      "Deno.run({ cmd: ['ls', dir] });",
+      "new Deno.Command('ls', { args: [`--color=${mode}`] });",
+      "function f(req) { const name = req.body.name; } function g(name) { el.innerHTML = `${name}`; }",
+    };
+  }
+
+  #[test]
+  fn no_inner_html_template_injection_invalid() {
+    assert_lint_err! {
+      NoInnerHtmlTemplateInjection,
+      "el.innerHTML = `<b>${req.query.name}</b>`;": [
+        {
+          col: 15,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "innerHTML"),
+          hint: NoInnerHtmlTemplateInjectionHint::EscapeHtml,
+        }
+      ],
+      "function handle(ctx) { const { name } = ctx.params; const html = `<h1>${name}</h1>`; document.body.innerHTML = html; }": [
+        {
+          col: 111,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "innerHTML"),
+          hint: NoInnerHtmlTemplateInjectionHint::EscapeHtml,
+        }
+      ],
+      "const q = url.searchParams.get('q'); el.innerHTML += '<p>' + `${q}` + '</p>';": [
+        {
+          col: 53,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "innerHTML"),
+          hint: NoInnerHtmlTemplateInjectionHint::EscapeHtml,
+        }
+      ],
+      "const userInput = prompt(); eval(`compute(${userInput})`);": [
+        {
+          col: 33,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "eval"),
+          hint: NoInnerHtmlTemplateInjectionHint::AvoidEval,
+        }
+      ],
+      "async function handler(request) { const file = (await request.json()).file; Deno.run({ cmd: ['sh', '-c', `cat ${file}`] }); }": [
+        {
+          col: 105,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "Deno.run"),
+          hint: NoInnerHtmlTemplateInjectionHint::ValidateArgs,
+        }
+      ],
+      "new Deno.Command('git', { args: ['log', `--author=${req.params.author}`] });": [
+        {
+          col: 40,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "Deno.Command"),
+          hint: NoInnerHtmlTemplateInjectionHint::ValidateArgs,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInnerHtmlTemplateInjection,
+      options: json!({ "patterns": ["payload"] }),
+      "el.innerHTML = `${event.payload.title}`;": [
+        {
+          col: 15,
+          message: variant!(NoInnerHtmlTemplateInjectionMessage, Injection, "innerHTML"),
+          hint: NoInnerHtmlTemplateInjectionHint::EscapeHtml,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,415 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::StringRepr;
+use derive_more::Display;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  Accessibility, Class, ClassDecl, ClassMember, ClassMethod, Function,
+  MethodKind, PropName,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoInvalidMemberModifiers;
+
+const CODE: &str = "no-invalid-member-modifiers";
+
+#[derive(Display)]
+enum NoInvalidMemberModifiersMessage {
+  #[display(fmt = "Method '{}' cannot be declared with `declare`", _0)]
+  DeclareMethod(String),
+  #[display(fmt = "Declared property '{}' cannot have an initializer", _0)]
+  DeclareWithInitializer(String),
+  #[display(fmt = "Abstract method '{}' cannot have an implementation", _0)]
+  AbstractWithBody(String),
+  #[display(fmt = "Abstract property '{}' cannot have an initializer", _0)]
+  AbstractWithInitializer(String),
+  #[display(fmt = "Abstract member '{}' cannot be private", _0)]
+  PrivateAbstract(String),
+  #[display(
+    fmt = "Abstract member '{}' can only appear in an abstract class",
+    _0
+  )]
+  AbstractInConcreteClass(String),
+  #[display(fmt = "Accessor '{}' must have a body", _0)]
+  AccessorWithoutBody(String),
+  #[display(fmt = "Class constructor cannot be a generator")]
+  GeneratorConstructor,
+  #[display(fmt = "Class constructor cannot be an accessor")]
+  AccessorConstructor,
+}
+
+#[derive(Display)]
+enum NoInvalidMemberModifiersHint {
+  #[display(fmt = "Remove the `declare` modifier")]
+  RemoveDeclare,
+  #[display(fmt = "Remove the initializer or the `declare` modifier")]
+  RemoveDeclareOrInitializer,
+  #[display(fmt = "Remove the body or the `abstract` modifier")]
+  RemoveAbstractOrBody,
+  #[display(fmt = "Remove the initializer or the `abstract` modifier")]
+  RemoveAbstractOrInitializer,
+  #[display(
+    fmt = "Make the member protected or remove the `abstract` modifier"
+  )]
+  RemovePrivate,
+  #[display(fmt = "Make the class abstract or remove the `abstract` modifier")]
+  MakeClassAbstract,
+  #[display(fmt = "Add a body to the accessor")]
+  AddBody,
+  #[display(fmt = "Move the logic into a separate method")]
+  UseMethod,
+}
+
+impl LintRule for NoInvalidMemberModifiers {
+  fn new() -> Box<Self> {
+    Box::new(NoInvalidMemberModifiers)
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoInvalidMemberModifiersVisitor {
+      context,
+      in_declare_class: false,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows class members with modifiers that can't be combined with their declaration",
+      "no-invalid-member-modifiers"
+    )
+  }
+}
+
+struct NoInvalidMemberModifiersVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  /// `true` if the class about to be visited is declared with `declare`.
+  in_declare_class: bool,
+}
+
+impl<'c, 'view> NoInvalidMemberModifiersVisitor<'c, 'view> {
+  fn report(
+    &mut self,
+    span: Span,
+    message: NoInvalidMemberModifiersMessage,
+    hint: NoInvalidMemberModifiersHint,
+  ) {
+    self
+      .context
+      .add_diagnostic_with_hint(span, CODE, message, hint);
+  }
+
+  fn check_abstract(
+    &mut self,
+    class: &Class,
+    span: Span,
+    name: &str,
+    accessibility: Option<Accessibility>,
+  ) {
+    if !class.is_abstract {
+      self.report(
+        span,
+        NoInvalidMemberModifiersMessage::AbstractInConcreteClass(
+          name.to_string(),
+        ),
+        NoInvalidMemberModifiersHint::MakeClassAbstract,
+      );
+    }
+    if accessibility == Some(Accessibility::Private) {
+      self.report(
+        span,
+        NoInvalidMemberModifiersMessage::PrivateAbstract(name.to_string()),
+        NoInvalidMemberModifiersHint::RemovePrivate,
+      );
+    }
+  }
+
+  fn check_method(&mut self, class: &Class, method: &ClassMethod) {
+    let span = method.key.span();
+    let name = method.key.string_repr().unwrap_or_default();
+
+    if !method.is_static && is_constructor_key(&method.key) {
+      if method.function.is_generator {
+        self.report(
+          span,
+          NoInvalidMemberModifiersMessage::GeneratorConstructor,
+          NoInvalidMemberModifiersHint::UseMethod,
+        );
+      } else if method.kind != MethodKind::Method {
+        self.report(
+          span,
+          NoInvalidMemberModifiersMessage::AccessorConstructor,
+          NoInvalidMemberModifiersHint::UseMethod,
+        );
+      }
+    }
+
+    // The parser accepts `declare` on methods but doesn't keep it in the AST
+    if self.has_declare_modifier(method.span, span) {
+      self.report(
+        span,
+        NoInvalidMemberModifiersMessage::DeclareMethod(name.clone()),
+        NoInvalidMemberModifiersHint::RemoveDeclare,
+      );
+    }
+
+    if method.is_abstract {
+      self.check_abstract(class, span, &name, method.accessibility);
+    }
+    self.check_function(
+      span,
+      &name,
+      method.kind,
+      method.is_abstract,
+      &method.function,
+    );
+  }
+
+  fn check_function(
+    &mut self,
+    span: Span,
+    name: &str,
+    kind: MethodKind,
+    is_abstract: bool,
+    function: &Function,
+  ) {
+    if is_abstract {
+      if function.body.is_some() {
+        self.report(
+          span,
+          NoInvalidMemberModifiersMessage::AbstractWithBody(name.to_string()),
+          NoInvalidMemberModifiersHint::RemoveAbstractOrBody,
+        );
+      }
+    } else if kind != MethodKind::Method
+      && function.body.is_none()
+      && !self.in_declare_class
+    {
+      self.report(
+        span,
+        NoInvalidMemberModifiersMessage::AccessorWithoutBody(name.to_string()),
+        NoInvalidMemberModifiersHint::AddBody,
+      );
+    }
+  }
+
+  /// Returns `true` if the modifiers between the start of the member and its
+  /// key include `declare`.
+  fn has_declare_modifier(&self, member_span: Span, key_span: Span) -> bool {
+    if key_span.lo() <= member_span.lo() {
+      return false;
+    }
+    let modifiers = self
+      .context
+      .source_map()
+      .span_to_snippet(member_span.with_hi(key_span.lo()));
+    match modifiers {
+      Ok(modifiers) => modifiers.split_whitespace().any(|m| m == "declare"),
+      Err(_) => false,
+    }
+  }
+}
+
+fn is_constructor_key(key: &PropName) -> bool {
+  match key {
+    PropName::Ident(ident) => ident.sym == *"constructor",
+    PropName::Str(str) => str.value == *"constructor",
+    _ => false,
+  }
+}
+
+impl<'c, 'view> Visit for NoInvalidMemberModifiersVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, _: &dyn Node) {
+    self.in_declare_class = class_decl.declare;
+    class_decl.visit_children_with(self);
+  }
+
+  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+    for member in &class.body {
+      match member {
+        ClassMember::Method(method) => self.check_method(class, method),
+        ClassMember::PrivateMethod(method) => {
+          let name = format!("#{}", method.key.id.sym);
+          if method.is_abstract {
+            self.check_abstract(
+              class,
+              method.key.span,
+              &name,
+              method.accessibility,
+            );
+          }
+          self.check_function(
+            method.key.span,
+            &name,
+            method.kind,
+            method.is_abstract,
+            &method.function,
+          );
+        }
+        ClassMember::ClassProp(prop) => {
+          let span = prop.key.span();
+          let name = prop.key.string_repr().unwrap_or_default();
+          if prop.is_abstract {
+            self.check_abstract(class, span, &name, prop.accessibility);
+            if prop.value.is_some() {
+              self.report(
+                span,
+                NoInvalidMemberModifiersMessage::AbstractWithInitializer(
+                  name.clone(),
+                ),
+                NoInvalidMemberModifiersHint::RemoveAbstractOrInitializer,
+              );
+            }
+          }
+          if prop.declare && prop.value.is_some() {
+            self.report(
+              span,
+              NoInvalidMemberModifiersMessage::DeclareWithInitializer(name),
+              NoInvalidMemberModifiersHint::RemoveDeclareOrInitializer,
+            );
+          }
+        }
+        _ => {}
+      }
+    }
+
+    // Classes nested in this one aren't ambient, even if this one is
+    self.in_declare_class = false;
+    class.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_invalid_member_modifiers_valid() {
+    assert_lint_ok! {
+      NoInvalidMemberModifiers,
+      "class Foo { foo() {} get bar() { return 1; } set bar(v) {} }",
+      "abstract class Foo { abstract foo(): void; abstract bar: number; protected abstract get baz(): number; }",
+      "class Foo { declare foo: string; declare: number; declare() {} }",
+      "declare class Foo { foo(): void; get bar(): number; }",
+      "class Foo { static constructor() {} static *['constructor']() {} }",
+      "class Foo { constructor() {} #foo() {} get #bar() { return 1; } }",
+      "class Foo { foo(a: string): void; foo(a: any) {} }",
+    };
+  }
+
+  #[test]
+  fn no_invalid_member_modifiers_invalid() {
+    assert_lint_err! {
+      NoInvalidMemberModifiers,
+      "class Foo { declare foo() {} }": [
+        {
+          col: 20,
+          message: variant!(NoInvalidMemberModifiersMessage, DeclareMethod, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemoveDeclare,
+        }
+      ],
+      "class Foo { private declare foo(): void; }": [
+        {
+          col: 28,
+          message: variant!(NoInvalidMemberModifiersMessage, DeclareMethod, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemoveDeclare,
+        }
+      ],
+      "class Foo { declare foo = 1; }": [
+        {
+          col: 20,
+          message: variant!(NoInvalidMemberModifiersMessage, DeclareWithInitializer, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemoveDeclareOrInitializer,
+        }
+      ],
+      "abstract class Foo { abstract foo() {} abstract bar = 1; }": [
+        {
+          col: 30,
+          message: variant!(NoInvalidMemberModifiersMessage, AbstractWithBody, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemoveAbstractOrBody,
+        },
+        {
+          col: 48,
+          message: variant!(NoInvalidMemberModifiersMessage, AbstractWithInitializer, "bar"),
+          hint: NoInvalidMemberModifiersHint::RemoveAbstractOrInitializer,
+        }
+      ],
+      "abstract class Foo { abstract get foo() { return 1; } }": [
+        {
+          col: 34,
+          message: variant!(NoInvalidMemberModifiersMessage, AbstractWithBody, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemoveAbstractOrBody,
+        }
+      ],
+      "abstract class Foo { private abstract foo(): void; }": [
+        {
+          col: 38,
+          message: variant!(NoInvalidMemberModifiersMessage, PrivateAbstract, "foo"),
+          hint: NoInvalidMemberModifiersHint::RemovePrivate,
+        }
+      ],
+      "class Foo { abstract foo(): void; abstract bar: string; }": [
+        {
+          col: 21,
+          message: variant!(NoInvalidMemberModifiersMessage, AbstractInConcreteClass, "foo"),
+          hint: NoInvalidMemberModifiersHint::MakeClassAbstract,
+        },
+        {
+          col: 43,
+          message: variant!(NoInvalidMemberModifiersMessage, AbstractInConcreteClass, "bar"),
+          hint: NoInvalidMemberModifiersHint::MakeClassAbstract,
+        }
+      ],
+      "class Foo { get foo(); set foo(v); }": [
+        {
+          col: 16,
+          message: variant!(NoInvalidMemberModifiersMessage, AccessorWithoutBody, "foo"),
+          hint: NoInvalidMemberModifiersHint::AddBody,
+        },
+        {
+          col: 27,
+          message: variant!(NoInvalidMemberModifiersMessage, AccessorWithoutBody, "foo"),
+          hint: NoInvalidMemberModifiersHint::AddBody,
+        }
+      ],
+      "class Foo { *constructor() {} }": [
+        {
+          col: 13,
+          message: NoInvalidMemberModifiersMessage::GeneratorConstructor,
+          hint: NoInvalidMemberModifiersHint::UseMethod,
+        }
+      ],
+      "class Foo { get constructor() { return 1; } }": [
+        {
+          col: 16,
+          message: NoInvalidMemberModifiersMessage::AccessorConstructor,
+          hint: NoInvalidMemberModifiersHint::UseMethod,
+        }
+      ],
+      "declare class Foo { foo(): void; } class Bar { get baz(); }": [
+        {
+          col: 51,
+          message: variant!(NoInvalidMemberModifiersMessage, AccessorWithoutBody, "baz"),
+          hint: NoInvalidMemberModifiersHint::AddBody,
+        }
+      ],
+    };
+  }
+}
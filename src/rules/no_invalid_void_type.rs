@@ -0,0 +1,474 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use crate::handler::{Handler, Traverse};
+use derive_more::Display;
+use dprint_swc_ecma_ast_view::{self as AstView, NodeTrait};
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{ExprOrSuper, TsKeywordTypeKind, TsType};
+
+pub struct NoInvalidVoidType {
+  options: NoInvalidVoidTypeOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoInvalidVoidTypeOptions {
+  /// Whether `void` is allowed as a generic type argument and as the default
+  /// of a type parameter.
+  pub allow_in_generic_type_arguments: AllowInGenericTypeArguments,
+  /// Whether `void` is allowed as the type of a `this` parameter.
+  pub allow_as_this_parameter: bool,
+}
+
+impl Default for NoInvalidVoidTypeOptions {
+  fn default() -> Self {
+    Self {
+      allow_in_generic_type_arguments: AllowInGenericTypeArguments::Allowed(
+        true,
+      ),
+      allow_as_this_parameter: false,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum AllowInGenericTypeArguments {
+  /// `true` allows `void` as the type argument of any generic type.
+  Allowed(bool),
+  /// Allows `void` as the type argument of the listed generic types only,
+  /// e.g. `["Promise"]`.
+  Allowlist(Vec<String>),
+}
+
+const CODE: &str = "no-invalid-void-type";
+
+#[derive(Display)]
+enum NoInvalidVoidTypeMessage {
+  #[display(fmt = "`void` is only valid as a return type")]
+  NotReturn,
+  #[display(
+    fmt = "`void` is only valid as a return type or a generic type argument"
+  )]
+  NotReturnOrGeneric,
+  #[display(
+    fmt = "`void` is only valid as a return type or the type of a `this` parameter"
+  )]
+  NotReturnOrThisParam,
+  #[display(
+    fmt = "`void` is only valid as a return type, a generic type argument or the type of a `this` parameter"
+  )]
+  NotReturnOrThisParamOrGeneric,
+  #[display(fmt = "`{}` may not have `void` as a type argument", _0)]
+  ForGeneric(String),
+  #[display(fmt = "`void` is not valid as a constituent in a union type")]
+  UnionConstituent,
+}
+
+#[derive(Display)]
+enum NoInvalidVoidTypeHint {
+  #[display(fmt = "Use `undefined` instead")]
+  UseUndefined,
+  #[display(
+    fmt = "Use `undefined` instead, or add `{}` to the `allowInGenericTypeArguments` option",
+    _0
+  )]
+  UseUndefinedOrAllow(String),
+}
+
+impl LintRule for NoInvalidVoidType {
+  fn new() -> Box<Self> {
+    Box::new(NoInvalidVoidType {
+      options: NoInvalidVoidTypeOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
+    unreachable!();
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: AstView::Program<'_>,
+  ) {
+    let mut handler = NoInvalidVoidTypeHandler {
+      options: &self.options,
+    };
+    handler.traverse(program, context);
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allowInGenericTypeArguments": {
+          "description": "Whether `void` is allowed as a generic type argument, or the generic types it's allowed for.",
+          "oneOf": [
+            { "type": "boolean" },
+            { "type": "array", "items": { "type": "string" } }
+          ],
+          "default": true
+        },
+        "allowAsThisParameter": {
+          "description": "Whether `void` is allowed as the type of a `this` parameter.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows `void` outside of return types and generic type arguments",
+      "no-invalid-void-type"
+    )
+  }
+}
+
+fn is_void(ty: &TsType) -> bool {
+  matches!(
+    ty,
+    TsType::TsKeywordType(keyword)
+      if keyword.kind == TsKeywordTypeKind::TsVoidKeyword
+  )
+}
+
+/// Returns `true` for the types a union with `void` may consist of: `void`,
+/// `never` and generic types with `void` as a type argument, e.g.
+/// `void | Promise<void>`.
+fn is_valid_union_member(ty: &TsType) -> bool {
+  match ty {
+    TsType::TsKeywordType(keyword) => matches!(
+      keyword.kind,
+      TsKeywordTypeKind::TsVoidKeyword | TsKeywordTypeKind::TsNeverKeyword
+    ),
+    TsType::TsTypeRef(type_ref) => match &type_ref.type_params {
+      Some(type_params) => {
+        type_params.params.iter().any(|param| is_void(param))
+      }
+      None => false,
+    },
+    _ => false,
+  }
+}
+
+struct NoInvalidVoidTypeHandler<'a> {
+  options: &'a NoInvalidVoidTypeOptions,
+}
+
+impl<'a> NoInvalidVoidTypeHandler<'a> {
+  fn allows_generics(&self) -> bool {
+    self.options.allow_in_generic_type_arguments
+      != AllowInGenericTypeArguments::Allowed(false)
+  }
+
+  fn not_return_message(&self) -> NoInvalidVoidTypeMessage {
+    match (self.allows_generics(), self.options.allow_as_this_parameter) {
+      (false, false) => NoInvalidVoidTypeMessage::NotReturn,
+      (true, false) => NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+      (false, true) => NoInvalidVoidTypeMessage::NotReturnOrThisParam,
+      (true, true) => NoInvalidVoidTypeMessage::NotReturnOrThisParamOrGeneric,
+    }
+  }
+
+  /// Checks the position of `void`, or of a union containing it.
+  fn check(
+    &self,
+    node: AstView::Node,
+    ctx: &Context,
+  ) -> Option<(NoInvalidVoidTypeMessage, NoInvalidVoidTypeHint)> {
+    use AstView::Node;
+    let not_return = Some((
+      self.not_return_message(),
+      NoInvalidVoidTypeHint::UseUndefined,
+    ));
+    match node.parent()? {
+      Node::TsTypeParamInstantiation(instantiation) => {
+        self.check_type_argument(instantiation, ctx)
+      }
+      Node::TsTypeParam(type_param)
+        if matches!(
+          type_param.default,
+          Some(default) if default.span() == node.span()
+        ) =>
+      {
+        if self.allows_generics() {
+          None
+        } else {
+          not_return
+        }
+      }
+      Node::TsUnionType(union) => {
+        if union.inner.types.iter().all(|ty| is_valid_union_member(ty)) {
+          self.check(union.into_node(), ctx)
+        } else {
+          Some((
+            NoInvalidVoidTypeMessage::UnionConstituent,
+            NoInvalidVoidTypeHint::UseUndefined,
+          ))
+        }
+      }
+      Node::TsTypeAnn(type_ann) => match type_ann.parent {
+        Node::Function(_)
+        | Node::ArrowExpr(_)
+        | Node::TsFnType(_)
+        | Node::TsConstructorType(_)
+        | Node::TsMethodSignature(_)
+        | Node::TsCallSignatureDecl(_)
+        | Node::TsConstructSignatureDecl(_)
+        | Node::TsGetterSignature(_) => None,
+        Node::BindingIdent(ident)
+          if self.options.allow_as_this_parameter
+            && ident.id.inner.sym == *"this" =>
+        {
+          None
+        }
+        _ => not_return,
+      },
+      _ => not_return,
+    }
+  }
+
+  fn check_type_argument(
+    &self,
+    instantiation: &AstView::TsTypeParamInstantiation,
+    ctx: &Context,
+  ) -> Option<(NoInvalidVoidTypeMessage, NoInvalidVoidTypeHint)> {
+    use AstView::Node;
+    let allowlist = match &self.options.allow_in_generic_type_arguments {
+      AllowInGenericTypeArguments::Allowed(true) => return None,
+      AllowInGenericTypeArguments::Allowed(false) => {
+        return Some((
+          self.not_return_message(),
+          NoInvalidVoidTypeHint::UseUndefined,
+        ))
+      }
+      AllowInGenericTypeArguments::Allowlist(allowlist) => allowlist,
+    };
+
+    let name_span = match instantiation.parent {
+      Node::TsTypeRef(type_ref) => type_ref.inner.type_name.span(),
+      Node::TsExprWithTypeArgs(expr) => expr.inner.expr.span(),
+      Node::NewExpr(new_expr) => new_expr.inner.callee.span(),
+      Node::CallExpr(call_expr) => match &call_expr.inner.callee {
+        ExprOrSuper::Expr(callee) => callee.span(),
+        ExprOrSuper::Super(_) => return None,
+      },
+      parent => parent.span().with_hi(instantiation.span().lo()),
+    };
+    // Names are compared without whitespace, e.g. `Foo . Bar` is `Foo.Bar`
+    let name: String = ctx
+      .source_map()
+      .span_to_snippet(name_span)
+      .ok()?
+      .split_whitespace()
+      .collect();
+    if allowlist
+      .iter()
+      .any(|allowed| allowed.split_whitespace().collect::<String>() == name)
+    {
+      None
+    } else {
+      Some((
+        NoInvalidVoidTypeMessage::ForGeneric(name.clone()),
+        NoInvalidVoidTypeHint::UseUndefinedOrAllow(name),
+      ))
+    }
+  }
+}
+
+impl<'a> Handler for NoInvalidVoidTypeHandler<'a> {
+  fn ts_keyword_type(
+    &mut self,
+    keyword: &AstView::TsKeywordType,
+    ctx: &mut Context,
+  ) {
+    if keyword.keyword_kind() != TsKeywordTypeKind::TsVoidKeyword {
+      return;
+    }
+    if let Some((message, hint)) = self.check(keyword.into_node(), ctx) {
+      ctx.add_diagnostic_with_hint(keyword.span(), CODE, message, hint);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_invalid_void_type_valid() {
+    assert_lint_ok! {
+      NoInvalidVoidType,
+      "function foo(): void {}",
+      "const foo = (): void => {};",
+      "class Foo { bar(): void {} }",
+      "type Foo = () => void;",
+      "type Foo = new () => void;",
+      "interface Foo { bar(): void; (): void; new (): void; }",
+      "async function foo(): Promise<void> {}",
+      "const foo: Map<string, void> = new Map<string, void>();",
+      "type Foo<T = void> = T;",
+      "function foo(): void | never {}",
+      "function foo(): void | Promise<void> {}",
+      "type Foo = Promise<void | never>;",
+      "let foo: undefined;",
+    };
+
+    assert_lint_ok! {
+      NoInvalidVoidType,
+      options: json!({ "allowAsThisParameter": true }),
+      "function foo(this: void) {}",
+      "type Foo = (this: void) => void;",
+    };
+
+    assert_lint_ok! {
+      NoInvalidVoidType,
+      options: json!({ "allowInGenericTypeArguments": ["Promise", "Foo.Bar"] }),
+      "async function foo(): Promise<void> {}",
+      "let foo: Foo.Bar<void>;",
+      "const foo = new Promise<void>(() => {});",
+    };
+  }
+
+  #[test]
+  fn no_invalid_void_type_invalid() {
+    assert_lint_err! {
+      NoInvalidVoidType,
+      "let foo: void;": [
+        {
+          col: 9,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "function foo(bar: void) {}": [
+        {
+          col: 18,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "function foo(this: void) {}": [
+        {
+          col: 19,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "class Foo { bar: void; }": [
+        {
+          col: 17,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "interface Foo { bar: void; }": [
+        {
+          col: 21,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "function foo(): string | void {}": [
+        {
+          col: 25,
+          message: NoInvalidVoidTypeMessage::UnionConstituent,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "let foo: void | never;": [
+        {
+          col: 9,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "type Foo = void[];": [
+        {
+          col: 11,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "type Foo<T extends void> = T;": [
+        {
+          col: 19,
+          message: NoInvalidVoidTypeMessage::NotReturnOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInvalidVoidType,
+      options: json!({ "allowInGenericTypeArguments": false }),
+      "type Foo = Promise<void>;": [
+        {
+          col: 19,
+          message: NoInvalidVoidTypeMessage::NotReturn,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+      "type Foo<T = void> = T;": [
+        {
+          col: 13,
+          message: NoInvalidVoidTypeMessage::NotReturn,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInvalidVoidType,
+      options: json!({ "allowAsThisParameter": true }),
+      "function foo(bar: void) {}": [
+        {
+          col: 18,
+          message: NoInvalidVoidTypeMessage::NotReturnOrThisParamOrGeneric,
+          hint: NoInvalidVoidTypeHint::UseUndefined,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoInvalidVoidType,
+      options: json!({ "allowInGenericTypeArguments": ["Promise"] }),
+      "let foo: Map<string, void>;": [
+        {
+          col: 21,
+          message: variant!(NoInvalidVoidTypeMessage, ForGeneric, "Map"),
+          hint: variant!(NoInvalidVoidTypeHint, UseUndefinedOrAllow, "Map"),
+        }
+      ],
+      "let foo: Foo . Bar<void>;": [
+        {
+          col: 19,
+          message: variant!(NoInvalidVoidTypeMessage, ForGeneric, "Foo.Bar"),
+          hint: variant!(NoInvalidVoidTypeHint, UseUndefinedOrAllow, "Foo.Bar"),
+        }
+      ],
+    };
+  }
+}
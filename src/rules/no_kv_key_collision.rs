@@ -0,0 +1,227 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{is_interpolated_string, member_name, method_chain};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper, Pat, VarDeclarator};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoKvKeyCollision;
+
+const CODE: &str = "no-kv-key-collision";
+
+#[derive(Display)]
+enum NoKvKeyCollisionMessage {
+  #[display(fmt = "KV key is built by string concatenation")]
+  Concatenated,
+}
+
+#[derive(Display)]
+enum NoKvKeyCollisionHint {
+  #[display(
+    fmt = "Use separate key parts instead, e.g. `[\"users\", id]`, so that keys can't collide and can be listed by prefix"
+  )]
+  UseKeyParts,
+}
+
+impl LintRule for NoKvKeyCollision {
+  fn new() -> Box<Self> {
+    Box::new(NoKvKeyCollision)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoKvKeyCollisionVisitor {
+      context,
+      kv_vars: HashSet::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows Deno KV keys built by string concatenation",
+      "no-kv-key-collision"
+    )
+  }
+}
+
+struct NoKvKeyCollisionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  /// Variables initialized with `await Deno.openKv()`.
+  kv_vars: HashSet<Id>,
+}
+
+/// Returns `true` for `Deno.openKv()` and `await Deno.openKv()`.
+fn is_open_kv(expr: &Expr) -> bool {
+  let expr = match expr {
+    Expr::Await(await_expr) => &*await_expr.arg,
+    expr => expr,
+  };
+  match method_chain(expr) {
+    (methods, Expr::Ident(root)) => {
+      matches!(methods.as_slice(), [("openKv", _)]) && root.sym == *"Deno"
+    }
+    _ => false,
+  }
+}
+
+impl<'c, 'view> NoKvKeyCollisionVisitor<'c, 'view> {
+  /// Returns `true` if the expression looks like a `Deno.Kv` or an atomic
+  /// operation: a variable initialized with `Deno.openKv()` or named like
+  /// `kv`, or a chain of calls starting with `.atomic()`.
+  fn is_kv(&self, expr: &Expr) -> bool {
+    let (methods, root) = method_chain(expr);
+    if methods.iter().any(|(method, _)| *method == "atomic") {
+      return true;
+    }
+    match root {
+      Expr::Ident(ident) if methods.is_empty() => {
+        self.kv_vars.contains(&ident.to_id())
+          || ident.sym.to_lowercase().contains("kv")
+      }
+      Expr::Member(member) if methods.is_empty() => {
+        matches!(member_name(member), Some(name) if name.to_lowercase().contains("kv"))
+      }
+      _ => false,
+    }
+  }
+
+  fn check_key(&mut self, key: &Expr) {
+    let parts = match key {
+      Expr::Array(array) => array
+        .elems
+        .iter()
+        .flatten()
+        .filter(|elem| elem.spread.is_none())
+        .map(|elem| &*elem.expr)
+        .collect(),
+      key => vec![key],
+    };
+    for part in parts {
+      if is_interpolated_string(part) {
+        self.context.add_diagnostic_with_hint(
+          part.span(),
+          CODE,
+          NoKvKeyCollisionMessage::Concatenated,
+          NoKvKeyCollisionHint::UseKeyParts,
+        );
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoKvKeyCollisionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    if let (Pat::Ident(binding), Some(init)) =
+      (&declarator.name, &declarator.init)
+    {
+      if is_open_kv(init) {
+        self.kv_vars.insert(binding.id.to_id());
+      }
+    }
+    declarator.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    let member = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) => Some(member),
+        _ => None,
+      },
+      ExprOrSuper::Super(_) => None,
+    };
+    if let Some(member) = member {
+      let is_key_method = matches!(
+        member_name(member),
+        Some(name) if matches!(name, "get" | "set" | "delete")
+      );
+      let key = match call_expr.args.first() {
+        Some(arg) if arg.spread.is_none() => Some(&*arg.expr),
+        _ => None,
+      };
+      if let (true, ExprOrSuper::Expr(obj), Some(key)) =
+        (is_key_method, &member.obj, key)
+      {
+        if self.is_kv(obj) {
+          self.check_key(key);
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_kv_key_collision_valid() {
+    assert_lint_ok! {
+      NoKvKeyCollision,
+      "await kv.set(['users', id], user);",
+      "await kv.get(['users', id, 'profile']);",
+      "await kv.set(['users:admin'], user);",
+      "await kv.set(['users:' + 'admin'], user);",
+      "const db = await Deno.openKv(); await db.delete(['users', id]);",
+      "await kv.atomic().check(entry).set(['users', id], user).commit();",
+      "map.set('users:' + id, user);",
+      "params.get(`${prefix}:id`);",
+    };
+  }
+
+  #[test]
+  fn no_kv_key_collision_invalid() {
+    assert_lint_err! {
+      NoKvKeyCollision,
+      "await kv.set(['users:' + id], user);": [
+        {
+          col: 14,
+          message: NoKvKeyCollisionMessage::Concatenated,
+          hint: NoKvKeyCollisionHint::UseKeyParts,
+        }
+      ],
+      "const db = await Deno.openKv(); await db.get([`users/${id}`]);": [
+        {
+          col: 46,
+          message: NoKvKeyCollisionMessage::Concatenated,
+          hint: NoKvKeyCollisionHint::UseKeyParts,
+        }
+      ],
+      "await this.kv.delete(['sessions', `${user}:${token}`]);": [
+        {
+          col: 34,
+          message: NoKvKeyCollisionMessage::Concatenated,
+          hint: NoKvKeyCollisionHint::UseKeyParts,
+        }
+      ],
+      "await store.atomic().set([prefix + id], value).commit();": [
+        {
+          col: 26,
+          message: NoKvKeyCollisionMessage::Concatenated,
+          hint: NoKvKeyCollisionHint::UseKeyParts,
+        }
+      ],
+    };
+  }
+}
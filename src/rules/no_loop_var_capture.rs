@@ -0,0 +1,271 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::scopes::BindingKind;
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_ecmascript::ast::{
+  ArrowExpr, ForInStmt, ForOfStmt, ForStmt, Function, Ident, MemberExpr,
+  VarDecl, VarDeclKind, VarDeclOrExpr, VarDeclOrPat,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoLoopVarCapture;
+
+const CODE: &str = "no-loop-var-capture";
+
+#[derive(Display)]
+enum NoLoopVarCaptureMessage {
+  #[display(
+    fmt = "Loop variable '{}' is declared with `var` and captured by a closure",
+    _0
+  )]
+  Captured(String),
+}
+
+#[derive(Display)]
+enum NoLoopVarCaptureHint {
+  #[display(
+    fmt = "Declare it with `let` so that each iteration gets its own binding"
+  )]
+  UseLet,
+}
+
+impl LintRule for NoLoopVarCapture {
+  fn new() -> Box<Self> {
+    Box::new(NoLoopVarCapture)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoLoopVarCaptureVisitor {
+      context,
+      loop_vars: vec![],
+      closures: vec![],
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows closures created in a loop from capturing loop variables declared with `var`",
+      "no-loop-var-capture"
+    )
+  }
+}
+
+struct NoLoopVarCaptureVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  /// `var` bindings of the loops being visited, along with the number of
+  /// enclosing closures at the loop.
+  loop_vars: Vec<(Id, usize)>,
+  /// Loop variables already reported for each enclosing closure.
+  closures: Vec<HashSet<Id>>,
+}
+
+impl<'c, 'view> NoLoopVarCaptureVisitor<'c, 'view> {
+  /// Visits the parts of a loop that run on every iteration with the `var`
+  /// bindings of its head in scope.
+  fn with_loop_vars(
+    &mut self,
+    var_decl: Option<&VarDecl>,
+    f: impl FnOnce(&mut Self),
+  ) {
+    let len = self.loop_vars.len();
+    if let Some(var_decl) = var_decl {
+      if var_decl.kind == VarDeclKind::Var {
+        let ids: Vec<Id> = find_ids(&var_decl.decls);
+        for id in ids {
+          let is_var = matches!(
+            self.context.scope().var(&id),
+            Some(var) if var.kind() == BindingKind::Var
+          );
+          if is_var {
+            self.loop_vars.push((id, self.closures.len()));
+          }
+        }
+      }
+    }
+    f(self);
+    self.loop_vars.truncate(len);
+  }
+
+  fn with_closure(&mut self, f: impl FnOnce(&mut Self)) {
+    self.closures.push(HashSet::new());
+    f(self);
+    self.closures.pop();
+  }
+}
+
+impl<'c, 'view> Visit for NoLoopVarCaptureVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _: &dyn Node) {
+    for_stmt.init.visit_with(for_stmt, self);
+    let var_decl = match &for_stmt.init {
+      Some(VarDeclOrExpr::VarDecl(var_decl)) => Some(var_decl),
+      _ => None,
+    };
+    self.with_loop_vars(var_decl, |v| {
+      for_stmt.test.visit_with(for_stmt, v);
+      for_stmt.update.visit_with(for_stmt, v);
+      for_stmt.body.visit_with(for_stmt, v);
+    });
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _: &dyn Node) {
+    for_in_stmt.left.visit_with(for_in_stmt, self);
+    for_in_stmt.right.visit_with(for_in_stmt, self);
+    let var_decl = match &for_in_stmt.left {
+      VarDeclOrPat::VarDecl(var_decl) => Some(var_decl),
+      VarDeclOrPat::Pat(_) => None,
+    };
+    self.with_loop_vars(var_decl, |v| {
+      for_in_stmt.body.visit_with(for_in_stmt, v);
+    });
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _: &dyn Node) {
+    for_of_stmt.left.visit_with(for_of_stmt, self);
+    for_of_stmt.right.visit_with(for_of_stmt, self);
+    let var_decl = match &for_of_stmt.left {
+      VarDeclOrPat::VarDecl(var_decl) => Some(var_decl),
+      VarDeclOrPat::Pat(_) => None,
+    };
+    self.with_loop_vars(var_decl, |v| {
+      for_of_stmt.body.visit_with(for_of_stmt, v);
+    });
+  }
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.with_closure(|v| function.visit_children_with(v));
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.with_closure(|v| arrow_expr.visit_children_with(v));
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    member_expr.obj.visit_with(member_expr, self);
+    if member_expr.computed {
+      member_expr.prop.visit_with(member_expr, self);
+    }
+  }
+
+  fn visit_ident(&mut self, ident: &Ident, _: &dyn Node) {
+    let id = ident.to_id();
+    let depth = match self.loop_vars.iter().rev().find(|(v, _)| *v == id) {
+      Some((_, depth)) => *depth,
+      None => return,
+    };
+    if self.closures.len() <= depth {
+      return;
+    }
+    // Report each variable once per closure
+    let reported = self.closures.last_mut().unwrap();
+    if reported.insert(id) {
+      self.context.add_diagnostic_with_hint(
+        ident.span,
+        CODE,
+        NoLoopVarCaptureMessage::Captured(ident.sym.to_string()),
+        NoLoopVarCaptureHint::UseLet,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_loop_var_capture_valid() {
+    assert_lint_ok! {
+      NoLoopVarCapture,
+      "for (let i = 0; i < 3; i++) { setTimeout(() => console.log(i)); }",
+      "for (const x of xs) { setTimeout(() => console.log(x)); }",
+      "for (var i = 0; i < 3; i++) { console.log(i); }",
+      "for (var i = 0; i < 3; i++) {} setTimeout(() => console.log(i));",
+      "for (var i = 0; i < 3; i++) { const j = i; setTimeout(() => console.log(j)); }",
+      "for (var i = 0; i < 3; i++) { setTimeout((i) => console.log(i), 0, i); }",
+      "for (var i = 0; i < 3; i++) { setTimeout(() => console.log(obj.i)); }",
+      "function f() { for (var i = 0; i < 3; i++) { console.log(i); } }",
+      "const f = () => { for (var k in obj) { console.log(k); } };",
+    };
+  }
+
+  #[test]
+  fn no_loop_var_capture_invalid() {
+    assert_lint_err! {
+      NoLoopVarCapture,
+      "for (var i = 0; i < 3; i++) { setTimeout(() => console.log(i)); }": [
+        {
+          col: 59,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "i"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+      "for (var i = 0; i < 3; i++) { fns.push(function () { return i + i; }); }": [
+        {
+          col: 60,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "i"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+      "for (var k in obj) { handlers[k] = () => obj[k]; }": [
+        {
+          col: 45,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "k"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+      "for (var [a, b] of pairs) { fns.push(() => ({ a, b })); }": [
+        {
+          col: 46,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "a"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        },
+        {
+          col: 49,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "b"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+      "function f() { for (var i = 0; i < 3; i++) { for (var j = 0; j < 3; j++) { fns.push(() => i * j); } } }": [
+        {
+          col: 90,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "i"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        },
+        {
+          col: 94,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "j"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+      "for (var i = 0; i < 3; i++) { el.onclick = function () { setTimeout(() => alert(i)); }; }": [
+        {
+          col: 80,
+          message: variant!(NoLoopVarCaptureMessage, Captured, "i"),
+          hint: NoLoopVarCaptureHint::UseLet,
+        }
+      ],
+    };
+  }
+}
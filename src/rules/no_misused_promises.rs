@@ -0,0 +1,360 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  async_function_names, is_async_function, is_known_promise,
+};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, CallExpr, CondExpr, DoWhileStmt, Expr, ExprOrSuper,
+  ForStmt, IfStmt, UnaryExpr, UnaryOp, WhileStmt,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoMisusedPromises;
+
+const CODE: &str = "no-misused-promises";
+
+#[derive(Display)]
+enum NoMisusedPromisesMessage {
+  #[display(fmt = "Promise used as a condition is always truthy")]
+  Condition,
+  #[display(
+    fmt = "Promise returned by the callback passed to '{}' is ignored",
+    _0
+  )]
+  VoidCallback(String),
+  #[display(
+    fmt = "Promise returned by the callback passed to '{}' is always truthy",
+    _0
+  )]
+  PredicateCallback(String),
+}
+
+#[derive(Display)]
+enum NoMisusedPromisesHint {
+  #[display(fmt = "Did you forget to `await` it?")]
+  Await,
+  #[display(
+    fmt = "Handle rejections inside the callback, or `await` each promise in a `for...of` loop"
+  )]
+  HandleInCallback,
+  #[display(fmt = "Resolve the values first, e.g. with `await Promise.all()`")]
+  ResolveFirst,
+}
+
+enum CallbackKind {
+  /// The return value of the callback is ignored.
+  Void,
+  /// The return value of the callback is used as a boolean.
+  Predicate,
+}
+
+/// Returns the kind of callback expected by the method and the index of the
+/// argument it's passed as.
+fn callback_kind(method: &str) -> Option<(CallbackKind, usize)> {
+  match method {
+    "forEach" => Some((CallbackKind::Void, 0)),
+    "addEventListener" => Some((CallbackKind::Void, 1)),
+    "every" | "filter" | "find" | "findIndex" | "some" => {
+      Some((CallbackKind::Predicate, 0))
+    }
+    _ => None,
+  }
+}
+
+impl LintRule for NoMisusedPromises {
+  fn new() -> Box<Self> {
+    Box::new(NoMisusedPromises)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn needs_types(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoMisusedPromisesVisitor {
+      context,
+      async_functions: async_function_names(&program),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows promises in places that don't handle them, such as conditions and callbacks whose return value is ignored",
+      "no-misused-promises"
+    )
+  }
+}
+
+struct NoMisusedPromisesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  async_functions: HashSet<JsWord>,
+}
+
+impl<'c, 'view> NoMisusedPromisesVisitor<'c, 'view> {
+  fn check_condition(&mut self, test: &Expr) {
+    let is_promise = match self.context.is_promise(test.span()) {
+      Some(is_promise) => is_promise,
+      None => is_known_promise(test, &self.async_functions),
+    };
+    if is_promise {
+      self.context.add_diagnostic_with_hint(
+        test.span(),
+        CODE,
+        NoMisusedPromisesMessage::Condition,
+        NoMisusedPromisesHint::Await,
+      );
+    }
+  }
+
+  fn returns_promise(&self, callback: &Expr) -> bool {
+    if is_async_function(callback) {
+      return true;
+    }
+    match self.context.returns_promise(callback.span()) {
+      Some(returns_promise) => returns_promise,
+      None => matches!(
+        callback,
+        Expr::Ident(ident) if self.async_functions.contains(&ident.sym)
+      ),
+    }
+  }
+
+  fn check_callback(&mut self, call_expr: &CallExpr) {
+    let member = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) if !member.computed => member,
+        _ => return,
+      },
+      ExprOrSuper::Super(_) => return,
+    };
+    let method = match &*member.prop {
+      Expr::Ident(ident) => &ident.sym,
+      _ => return,
+    };
+    let (kind, index) = match callback_kind(method) {
+      Some(kind) => kind,
+      None => return,
+    };
+    let callback = match call_expr.args.get(index) {
+      Some(arg) if arg.spread.is_none() => &*arg.expr,
+      _ => return,
+    };
+    if !self.returns_promise(callback) {
+      return;
+    }
+
+    let (message, hint) = match kind {
+      CallbackKind::Void => (
+        NoMisusedPromisesMessage::VoidCallback(method.to_string()),
+        NoMisusedPromisesHint::HandleInCallback,
+      ),
+      CallbackKind::Predicate => (
+        NoMisusedPromisesMessage::PredicateCallback(method.to_string()),
+        NoMisusedPromisesHint::ResolveFirst,
+      ),
+    };
+    self
+      .context
+      .add_diagnostic_with_hint(callback.span(), CODE, message, hint);
+  }
+}
+
+impl<'c, 'view> Visit for NoMisusedPromisesVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _: &dyn Node) {
+    self.check_condition(&if_stmt.test);
+    if_stmt.visit_children_with(self);
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _: &dyn Node) {
+    self.check_condition(&while_stmt.test);
+    while_stmt.visit_children_with(self);
+  }
+
+  fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt, _: &dyn Node) {
+    self.check_condition(&do_while.test);
+    do_while.visit_children_with(self);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _: &dyn Node) {
+    if let Some(test) = &for_stmt.test {
+      self.check_condition(test);
+    }
+    for_stmt.visit_children_with(self);
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _: &dyn Node) {
+    self.check_condition(&cond_expr.test);
+    cond_expr.visit_children_with(self);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::Bang {
+      self.check_condition(&unary_expr.arg);
+    }
+    unary_expr.visit_children_with(self);
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    // The right operand is the value of `a && b` and `a || b`
+    if matches!(bin_expr.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) {
+      self.check_condition(&bin_expr.left);
+    }
+    bin_expr.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    self.check_callback(call_expr);
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::Range;
+  use crate::linter::LinterBuilder;
+  use crate::type_provider::TypeProvider;
+  use std::rc::Rc;
+
+  #[test]
+  fn no_misused_promises_valid() {
+    assert_lint_ok! {
+      NoMisusedPromises,
+      "async function foo() {} if (await foo()) {}",
+      "async function foo() {} const p = foo(); while (await p) {}",
+      "function foo() {} if (foo()) {}",
+      "if (promise) {}",
+      "const a = (await fetch(url)) ? 1 : 2;",
+      "const ok = (await fetch(url)).ok || false;",
+      "items.forEach((item) => console.log(item));",
+      "items.map(async (item) => await save(item));",
+      "await Promise.all(items.map(async (item) => await save(item)));",
+      "for (const item of items) { await save(item); }",
+      "items.filter(function (item) { return item.ok; });",
+      "async function check() {} items.reduce(check);",
+    };
+  }
+
+  #[test]
+  fn no_misused_promises_invalid() {
+    assert_lint_err! {
+      NoMisusedPromises,
+      "async function foo() {} if (foo()) {}": [
+        {
+          col: 28,
+          message: NoMisusedPromisesMessage::Condition,
+          hint: NoMisusedPromisesHint::Await,
+        }
+      ],
+      "while (fetch(url)) {}": [
+        {
+          col: 7,
+          message: NoMisusedPromisesMessage::Condition,
+          hint: NoMisusedPromisesHint::Await,
+        }
+      ],
+      "const a = !Promise.resolve(1);": [
+        {
+          col: 11,
+          message: NoMisusedPromisesMessage::Condition,
+          hint: NoMisusedPromisesHint::Await,
+        }
+      ],
+      "const check = async () => true; const a = check() ? 1 : 2; const b = check() && 1;": [
+        {
+          col: 42,
+          message: NoMisusedPromisesMessage::Condition,
+          hint: NoMisusedPromisesHint::Await,
+        },
+        {
+          col: 69,
+          message: NoMisusedPromisesMessage::Condition,
+          hint: NoMisusedPromisesHint::Await,
+        }
+      ],
+      "items.forEach(async (item) => { await save(item); });": [
+        {
+          col: 14,
+          message: variant!(NoMisusedPromisesMessage, VoidCallback, "forEach"),
+          hint: NoMisusedPromisesHint::HandleInCallback,
+        }
+      ],
+      "async function isValid(item) {} const valid = items.filter(isValid);": [
+        {
+          col: 59,
+          message: variant!(NoMisusedPromisesMessage, PredicateCallback, "filter"),
+          hint: NoMisusedPromisesHint::ResolveFirst,
+        }
+      ],
+      "items.some(async function (item) { return await exists(item); });": [
+        {
+          col: 11,
+          message: variant!(NoMisusedPromisesMessage, PredicateCallback, "some"),
+          hint: NoMisusedPromisesHint::ResolveFirst,
+        }
+      ],
+      "socket.addEventListener('message', async (e) => { await handle(e); });": [
+        {
+          col: 35,
+          message: variant!(NoMisusedPromisesMessage, VoidCallback, "addEventListener"),
+          hint: NoMisusedPromisesHint::HandleInCallback,
+        }
+      ],
+    };
+  }
+
+  /// Answers that `load()` evaluates to a promise and `handler` returns one.
+  struct LoadTypeProvider {
+    source: &'static str,
+  }
+
+  impl TypeProvider for LoadTypeProvider {
+    fn is_promise(&self, _file_name: &str, range: &Range) -> Option<bool> {
+      let text = &self.source[range.start.byte_pos..range.end.byte_pos];
+      Some(text == "load()")
+    }
+
+    fn returns_promise(&self, _file_name: &str, range: &Range) -> Option<bool> {
+      let text = &self.source[range.start.byte_pos..range.end.byte_pos];
+      Some(text == "handler")
+    }
+  }
+
+  #[test]
+  fn no_misused_promises_type_provider() {
+    let source = "if (load()) {} if (fetch(url)) {} a.forEach(handler);";
+    let mut linter = LinterBuilder::default()
+      .rules(vec![NoMisusedPromises::new()])
+      .type_provider(Rc::new(LoadTypeProvider { source }))
+      .build();
+
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint");
+    let cols: Vec<usize> =
+      diagnostics.iter().map(|d| d.range.start.col).collect();
+    assert_eq!(cols, vec![4, 44]);
+  }
+}
@@ -33,6 +33,10 @@ impl LintRule for NoMixedSpacesAndTabs {
     CODE
   }
 
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
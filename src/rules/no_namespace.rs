@@ -2,16 +2,42 @@
 use super::{Context, LintRule, ProgramRef};
 use crate::handler::{Handler, Traverse};
 use dprint_swc_ecma_ast_view::{self as AstView, NodeTrait};
+use serde::Deserialize;
+use serde_json::json;
 use swc_common::Spanned;
+use swc_ecmascript::ast::TsModuleName;
 
-pub struct NoNamespace;
+pub struct NoNamespace {
+  options: NoNamespaceOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoNamespaceOptions {
+  /// Whether to allow namespaces in ambient declaration contexts, e.g.
+  /// `declare namespace foo {}`.
+  pub allow_declarations: bool,
+  /// Whether to allow namespaces in `.d.ts` files.
+  pub allow_definition_files: bool,
+}
+
+impl Default for NoNamespaceOptions {
+  fn default() -> Self {
+    Self {
+      allow_declarations: true,
+      allow_definition_files: true,
+    }
+  }
+}
 
 const CODE: &str = "no-namespace";
 const MESSAGE: &str = "custom typescript modules are outdated";
 
 impl LintRule for NoNamespace {
   fn new() -> Box<Self> {
-    Box::new(NoNamespace)
+    Box::new(NoNamespace {
+      options: NoNamespaceOptions::default(),
+    })
   }
 
   fn tags(&self) -> &'static [&'static str] {
@@ -22,6 +48,10 @@ impl LintRule for NoNamespace {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
     unreachable!();
   }
@@ -31,15 +61,48 @@ impl LintRule for NoNamespace {
     context: &mut Context,
     program: dprint_swc_ecma_ast_view::Program<'_>,
   ) {
-    if context.file_name().ends_with(".d.ts") {
+    if self.options.allow_definition_files
+      && context.file_name().ends_with(".d.ts")
+    {
       return;
     }
 
-    NoNamespaceHandler.traverse(program, context);
+    let mut handler = NoNamespaceHandler {
+      allow_declarations: self.options.allow_declarations,
+    };
+    handler.traverse(program, context);
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allowDeclarations": {
+          "description": "Whether to allow namespaces in ambient declaration contexts, e.g. `declare namespace foo {}`.",
+          "type": "boolean",
+          "default": true
+        },
+        "allowDefinitionFiles": {
+          "description": "Whether to allow namespaces in `.d.ts` files.",
+          "type": "boolean",
+          "default": true
+        }
+      }
+    }))
   }
 }
 
-struct NoNamespaceHandler;
+struct NoNamespaceHandler {
+  allow_declarations: bool,
+}
 
 impl Handler for NoNamespaceHandler {
   fn ts_module_decl(
@@ -58,7 +121,17 @@ impl Handler for NoNamespaceHandler {
       }
     }
 
-    if !inside_ambient_context(module_decl.into_node()) {
+    // Global augmentations and external module declarations can't be
+    // written without a namespace
+    if module_decl.global()
+      || matches!(module_decl.inner.id, TsModuleName::Str(_))
+    {
+      return;
+    }
+
+    if !(self.allow_declarations
+      && inside_ambient_context(module_decl.into_node()))
+    {
       ctx.add_diagnostic(module_decl.span(), CODE, MESSAGE);
     }
   }
@@ -67,6 +140,7 @@ impl Handler for NoNamespaceHandler {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use serde_json::json;
 
   #[test]
   fn no_namespace_valid() {
@@ -151,4 +225,56 @@ export declare namespace Utility {
       ],
     };
   }
+
+  #[test]
+  fn no_namespace_options_valid() {
+    assert_lint_ok! {
+      NoNamespace,
+      options: json!({ "allowDeclarations": false }),
+      r#"declare global {}"#,
+      r#"declare module 'foo' {}"#,
+      {
+        src: r#"declare namespace foo {}"#,
+        filename: "test.d.ts",
+      },
+    };
+
+    assert_lint_ok! {
+      NoNamespace,
+      options: json!({ "allowDefinitionFiles": false }),
+      {
+        src: r#"declare namespace foo {}"#,
+        filename: "test.d.ts",
+      },
+    };
+  }
+
+  #[test]
+  fn no_namespace_options_invalid() {
+    assert_lint_err! {
+      NoNamespace,
+      options: json!({ "allowDeclarations": false }),
+      "declare namespace foo {}": [{ col: 0, message: MESSAGE }],
+      "declare module foo {}": [{ col: 0, message: MESSAGE }],
+      "declare global { namespace foo {} }": [{ col: 17, message: MESSAGE }],
+    };
+
+    assert_lint_err! {
+      NoNamespace,
+      options: json!({ "allowDefinitionFiles": false }),
+      "namespace foo {}": {
+        filename: "test.d.ts",
+        errors: [{ col: 0, message: MESSAGE }],
+      },
+    };
+
+    assert_lint_err! {
+      NoNamespace,
+      options: json!({ "allowDeclarations": false, "allowDefinitionFiles": false }),
+      "declare namespace foo {}": {
+        filename: "test.d.ts",
+        errors: [{ col: 0, message: MESSAGE }],
+      },
+    };
+  }
 }
@@ -0,0 +1,228 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{BinaryOp, CallExpr, Expr, ExprOrSuper, Lit};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoNonLiteralDynamicImport {
+  options: NoNonLiteralDynamicImportOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoNonLiteralDynamicImportOptions {
+  /// Specifier prefixes under which modules may be imported with specifiers
+  /// built at runtime, e.g. `./locales/`.
+  pub allow: Vec<String>,
+}
+
+const CODE: &str = "no-non-literal-dynamic-import";
+
+#[derive(Display)]
+enum NoNonLiteralDynamicImportMessage {
+  #[display(fmt = "Dynamic import specifier is not a string literal")]
+  NonLiteral,
+}
+
+#[derive(Display)]
+enum NoNonLiteralDynamicImportHint {
+  #[display(
+    fmt = "Import the module with a string literal, or add the prefix the specifier starts with to the `allow` option"
+  )]
+  UseLiteral,
+}
+
+impl LintRule for NoNonLiteralDynamicImport {
+  fn new() -> Box<Self> {
+    Box::new(NoNonLiteralDynamicImport {
+      options: NoNonLiteralDynamicImportOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoNonLiteralDynamicImportVisitor {
+      context,
+      allow: &self.options.allow,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "Specifier prefixes under which modules may be imported with specifiers built at runtime, e.g. `./locales/`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires the specifiers of dynamic imports to be string literals",
+      "no-non-literal-dynamic-import"
+    )
+  }
+}
+
+/// Returns `true` for strings known without running the code, e.g. `"./a.ts"`,
+/// `` `./a.ts` `` and `"./" + "a.ts"`.
+fn is_static_string(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(Lit::Str(_)) => true,
+    Expr::Tpl(tpl) => tpl.exprs.iter().all(|expr| is_static_string(expr)),
+    Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+      is_static_string(&bin.left) && is_static_string(&bin.right)
+    }
+    Expr::Paren(paren) => is_static_string(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns the start of a string that is known without running the code,
+/// e.g. `./locales/` for `` `./locales/${lang}.json` ``.
+fn static_prefix(expr: &Expr) -> String {
+  match expr {
+    Expr::Lit(Lit::Str(s)) => s.value.to_string(),
+    Expr::Tpl(tpl) => tpl
+      .quasis
+      .first()
+      .map(|quasi| quasi.raw.value.to_string())
+      .unwrap_or_default(),
+    Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+      if is_static_string(&bin.left) {
+        static_prefix(&bin.left) + &static_prefix(&bin.right)
+      } else {
+        static_prefix(&bin.left)
+      }
+    }
+    Expr::Paren(paren) => static_prefix(&paren.expr),
+    _ => String::new(),
+  }
+}
+
+struct NoNonLiteralDynamicImportVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [String],
+}
+
+impl<'c, 'view> Visit for NoNonLiteralDynamicImportVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          let is_allowed = arg.spread.is_none()
+            && (is_static_string(&arg.expr) || {
+              let prefix = static_prefix(&arg.expr);
+              self
+                .allow
+                .iter()
+                .any(|allowed| prefix.starts_with(allowed.as_str()))
+            });
+          if !is_allowed {
+            self.context.add_diagnostic_with_hint(
+              arg.expr.span(),
+              CODE,
+              NoNonLiteralDynamicImportMessage::NonLiteral,
+              NoNonLiteralDynamicImportHint::UseLiteral,
+            );
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_non_literal_dynamic_import_valid() {
+    assert_lint_ok! {
+      NoNonLiteralDynamicImport,
+      r#"const mod = await import("./mod.ts");"#,
+      r#"const mod = await import(`https://deno.land/x/mod@1.0.0/mod.ts`);"#,
+      r#"const mod = await import("./plugins/" + "markdown.ts");"#,
+      r#"const mod = await import(`./${"mod"}.ts`);"#,
+      r#"const mod = await load(specifier);"#,
+    };
+
+    assert_lint_ok! {
+      NoNonLiteralDynamicImport,
+      options: json!({ "allow": ["./locales/", "https://deno.land/x/"] }),
+      r#"const messages = await import(`./locales/${lang}.json`, { assert: { type: "json" } });"#,
+      r#"const plugin = await import("https://deno.land/x/" + name + "/mod.ts");"#,
+    };
+  }
+
+  #[test]
+  fn no_non_literal_dynamic_import_invalid() {
+    assert_lint_err! {
+      NoNonLiteralDynamicImport,
+      r#"const mod = await import(specifier);"#: [
+        {
+          col: 25,
+          message: NoNonLiteralDynamicImportMessage::NonLiteral,
+          hint: NoNonLiteralDynamicImportHint::UseLiteral,
+        }
+      ],
+      r#"const mod = await import(`./plugins/${name}.ts`);"#: [
+        {
+          col: 25,
+          message: NoNonLiteralDynamicImportMessage::NonLiteral,
+          hint: NoNonLiteralDynamicImportHint::UseLiteral,
+        }
+      ],
+      r#"import(base + "/mod.ts").then(start);"#: [
+        {
+          col: 7,
+          message: NoNonLiteralDynamicImportMessage::NonLiteral,
+          hint: NoNonLiteralDynamicImportHint::UseLiteral,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoNonLiteralDynamicImport,
+      options: json!({ "allow": ["./locales/"] }),
+      r#"await import(`./locales/${lang}.json`); await import(`./${dir}/locales/x.json`);"#: [
+        {
+          col: 53,
+          message: NoNonLiteralDynamicImportMessage::NonLiteral,
+          hint: NoNonLiteralDynamicImportHint::UseLiteral,
+        }
+      ],
+    };
+  }
+}
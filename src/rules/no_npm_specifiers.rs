@@ -0,0 +1,236 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoNpmSpecifiers {
+  options: NoNpmSpecifiersOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoNpmSpecifiersOptions {
+  /// Packages that may be imported with `npm:` specifiers, e.g. `preact` or
+  /// `@types/node`.
+  pub allow: Vec<String>,
+}
+
+const CODE: &str = "no-npm-specifiers";
+
+#[derive(Display)]
+enum NoNpmSpecifiersMessage {
+  #[display(fmt = "npm package '{}' is imported", _0)]
+  Npm(String),
+}
+
+#[derive(Display)]
+enum NoNpmSpecifiersHint {
+  #[display(
+    fmt = "Import a URL or standard library module instead, or add the package to the `allow` option"
+  )]
+  UseUrl,
+}
+
+impl LintRule for NoNpmSpecifiers {
+  fn new() -> Box<Self> {
+    Box::new(NoNpmSpecifiers {
+      options: NoNpmSpecifiersOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoNpmSpecifiersVisitor {
+      context,
+      allow: &self.options.allow,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "Packages that may be imported with `npm:` specifiers, e.g. `preact` or `@types/node`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!("Disallows importing npm packages", "no-npm-specifiers")
+  }
+}
+
+/// Returns the package imported by an `npm:` specifier, e.g. `@std/path` for
+/// `npm:@std/path@1.0.0/posix`.
+fn npm_package(specifier: &str) -> Option<&str> {
+  let rest = specifier.strip_prefix("npm:")?;
+  let rest = rest.strip_prefix('/').unwrap_or(rest);
+  // The name of scoped packages contains a `/` and starts with `@`
+  let scope_end = if rest.starts_with('@') {
+    rest.find('/').map_or(rest.len(), |slash| slash + 1)
+  } else {
+    0
+  };
+  let name_end = rest[scope_end..]
+    .find(&['@', '/'][..])
+    .map_or(rest.len(), |end| scope_end + end);
+  Some(&rest[..name_end])
+}
+
+struct NoNpmSpecifiersVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [String],
+}
+
+impl<'c, 'view> NoNpmSpecifiersVisitor<'c, 'view> {
+  fn check_specifier(&mut self, src: &Str) {
+    let package = match npm_package(&src.value) {
+      Some(package) => package,
+      None => return,
+    };
+    if self.allow.iter().any(|allowed| allowed == package) {
+      return;
+    }
+    self.context.add_diagnostic_with_hint(
+      src.span,
+      CODE,
+      NoNpmSpecifiersMessage::Npm(package.to_string()),
+      NoNpmSpecifiersHint::UseUrl,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for NoNpmSpecifiersVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    self.check_specifier(&import_decl.src);
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_specifier(&export_all.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      self.check_specifier(src);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          if let Expr::Lit(Lit::Str(src)) = &*arg.expr {
+            self.check_specifier(src);
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn npm_package_test() {
+    assert_eq!(npm_package("npm:preact"), Some("preact"));
+    assert_eq!(npm_package("npm:preact@10.5.0/hooks"), Some("preact"));
+    assert_eq!(npm_package("npm:/chalk@5"), Some("chalk"));
+    assert_eq!(npm_package("npm:@types/node"), Some("@types/node"));
+    assert_eq!(npm_package("npm:@std/path@1.0.0/posix"), Some("@std/path"));
+    assert_eq!(npm_package("https://esm.sh/preact"), None);
+  }
+
+  #[test]
+  fn no_npm_specifiers_valid() {
+    assert_lint_ok! {
+      NoNpmSpecifiers,
+      r#"import { serve } from "https://deno.land/std/http/server.ts";"#,
+      r#"import { readFile } from "node:fs";"#,
+      r#"import { h } from "preact";"#,
+      r#"const mod = await import(specifier);"#,
+    };
+
+    assert_lint_ok! {
+      NoNpmSpecifiers,
+      options: json!({ "allow": ["preact", "@types/node"] }),
+      r#"import { h } from "npm:preact@10";"#,
+      r#"import { useState } from "npm:preact@10/hooks";"#,
+      r#"import type { Buffer } from "npm:@types/node";"#,
+    };
+  }
+
+  #[test]
+  fn no_npm_specifiers_invalid() {
+    assert_lint_err! {
+      NoNpmSpecifiers,
+      r#"import express from "npm:express@4";"#: [
+        {
+          col: 20,
+          message: variant!(NoNpmSpecifiersMessage, Npm, "express"),
+          hint: NoNpmSpecifiersHint::UseUrl,
+        }
+      ],
+      r#"export * from "npm:@std/path@1/posix";"#: [
+        {
+          col: 14,
+          message: variant!(NoNpmSpecifiersMessage, Npm, "@std/path"),
+          hint: NoNpmSpecifiersHint::UseUrl,
+        }
+      ],
+      r#"const chalk = await import("npm:chalk");"#: [
+        {
+          col: 27,
+          message: variant!(NoNpmSpecifiersMessage, Npm, "chalk"),
+          hint: NoNpmSpecifiersHint::UseUrl,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoNpmSpecifiers,
+      options: json!({ "allow": ["preact"] }),
+      r#"import { h } from "npm:preact"; import { render } from "npm:preact-render-to-string";"#: [
+        {
+          col: 55,
+          message: variant!(NoNpmSpecifiersMessage, Npm, "preact-render-to-string"),
+          hint: NoNpmSpecifiersHint::UseUrl,
+        }
+      ],
+    };
+  }
+}
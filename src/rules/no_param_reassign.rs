@@ -0,0 +1,328 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::scopes::BindingKind;
+use crate::swc_util::find_lhs_ids;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  AssignExpr, Expr, ExprOrSuper, ForInStmt, ForOfStmt, Ident, Pat, PatOrExpr,
+  UnaryExpr, UnaryOp, UpdateExpr, VarDeclOrPat,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::{find_ids, Id};
+use swc_ecmascript::visit::{noop_visit_type, Node, VisitAll, VisitAllWith};
+
+pub struct NoParamReassign {
+  options: NoParamReassignOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoParamReassignOptions {
+  /// Also reports assignments to properties of parameters, e.g.
+  /// `options.timeout = 0`.
+  pub props: bool,
+  /// Parameters whose properties may be modified when `props` is enabled,
+  /// e.g. `acc` for the accumulator of `reduce`.
+  pub ignore_property_modifications_for: Vec<String>,
+}
+
+const CODE: &str = "no-param-reassign";
+
+#[derive(Display)]
+enum NoParamReassignMessage {
+  #[display(fmt = "Assignment to function parameter '{}'", _0)]
+  Reassign(String),
+  #[display(fmt = "Assignment to property of function parameter '{}'", _0)]
+  Modify(String),
+}
+
+#[derive(Display)]
+enum NoParamReassignHint {
+  #[display(fmt = "Assign the value to a local variable instead")]
+  UseLocal,
+  #[display(fmt = "Modify a copy of the parameter, or return a new value")]
+  UseCopy,
+}
+
+impl LintRule for NoParamReassign {
+  fn new() -> Box<Self> {
+    Box::new(NoParamReassign {
+      options: NoParamReassignOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoParamReassignVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "props": {
+          "description": "Also reports assignments to properties of parameters, e.g. `options.timeout = 0`.",
+          "type": "boolean",
+          "default": false
+        },
+        "ignorePropertyModificationsFor": {
+          "description": "Parameters whose properties may be modified when `props` is enabled, e.g. `acc` for the accumulator of `reduce`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows reassigning function parameters",
+      "no-param-reassign"
+    )
+  }
+}
+
+/// Returns the identifier at the root of a chain of member expressions, e.g.
+/// `a` in `a.b[c]`.
+fn member_root(mut expr: &Expr) -> Option<&Ident> {
+  let mut is_member = false;
+  loop {
+    match expr {
+      Expr::Member(member) => match &member.obj {
+        ExprOrSuper::Expr(obj) => {
+          is_member = true;
+          expr = obj;
+        }
+        ExprOrSuper::Super(_) => return None,
+      },
+      Expr::Paren(paren) => expr = &paren.expr,
+      Expr::Ident(ident) if is_member => return Some(ident),
+      _ => return None,
+    }
+  }
+}
+
+struct NoParamReassignVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c NoParamReassignOptions,
+}
+
+impl<'c, 'view> NoParamReassignVisitor<'c, 'view> {
+  fn is_param(&self, id: &Id) -> bool {
+    matches!(
+      self.context.scope().var(id).map(|var| var.kind()),
+      Some(BindingKind::Param)
+    )
+  }
+
+  fn check_ids(&mut self, span: Span, ids: Vec<Id>) {
+    for id in ids {
+      if self.is_param(&id) {
+        self.context.add_diagnostic_with_hint(
+          span,
+          CODE,
+          NoParamReassignMessage::Reassign(id.0.to_string()),
+          NoParamReassignHint::UseLocal,
+        );
+      }
+    }
+  }
+
+  /// Checks an expression that is assigned, updated or deleted.
+  fn check_target(&mut self, span: Span, target: &Expr) {
+    match target {
+      Expr::Ident(ident) => self.check_ids(span, vec![ident.to_id()]),
+      Expr::Paren(paren) => self.check_target(span, &paren.expr),
+      _ if self.options.props => {
+        let root = match member_root(target) {
+          Some(root) => root,
+          None => return,
+        };
+        let is_ignored = self
+          .options
+          .ignore_property_modifications_for
+          .iter()
+          .any(|name| *name == *root.sym);
+        if !is_ignored && self.is_param(&root.to_id()) {
+          self.context.add_diagnostic_with_hint(
+            span,
+            CODE,
+            NoParamReassignMessage::Modify(root.sym.to_string()),
+            NoParamReassignHint::UseCopy,
+          );
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn check_loop_left(&mut self, span: Span, left: &VarDeclOrPat) {
+    if let VarDeclOrPat::Pat(pat) = left {
+      match pat {
+        Pat::Expr(expr) => self.check_target(span, expr),
+        _ => self.check_ids(span, find_ids(pat)),
+      }
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for NoParamReassignVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    match &assign_expr.left {
+      PatOrExpr::Expr(expr) => self.check_target(assign_expr.span, expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => self.check_target(assign_expr.span, expr),
+        _ => self.check_ids(assign_expr.span, find_lhs_ids(&assign_expr.left)),
+      },
+    }
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    self.check_target(update_expr.span, &update_expr.arg);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete {
+      self.check_target(unary_expr.span, &unary_expr.arg);
+    }
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _: &dyn Node) {
+    self.check_loop_left(for_in_stmt.span, &for_in_stmt.left);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _: &dyn Node) {
+    self.check_loop_left(for_of_stmt.span, &for_of_stmt.left);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_param_reassign_valid() {
+    assert_lint_ok! {
+      NoParamReassign,
+      "function foo(a) { const b = a + 1; return b; }",
+      "function foo(a) { a.b = 0; a[0]++; delete a.c; }",
+      "function foo(a) { let b = a; b = 1; }",
+      "function foo() { a = 1; }",
+      "function foo(a) { { let a; a = 1; } }",
+      "const foo = (a) => { for (const a of []) {} };",
+      "function foo([a]) { const b = a; }",
+    };
+
+    assert_lint_ok! {
+      NoParamReassign,
+      options: json!({ "props": true, "ignorePropertyModificationsFor": ["acc"] }),
+      "function foo(a) { const b = { ...a }; b.c = 1; }",
+      "items.reduce((acc, item) => { acc[item.id] = item; return acc; }, {});",
+      "function foo(a) { a.b(); }",
+    };
+  }
+
+  #[test]
+  fn no_param_reassign_invalid() {
+    assert_lint_err! {
+      NoParamReassign,
+      "function foo(a) { a = 1; }": [
+        {
+          col: 18,
+          message: variant!(NoParamReassignMessage, Reassign, "a"),
+          hint: NoParamReassignHint::UseLocal,
+        }
+      ],
+      "const foo = (a) => a++;": [
+        {
+          col: 19,
+          message: variant!(NoParamReassignMessage, Reassign, "a"),
+          hint: NoParamReassignHint::UseLocal,
+        }
+      ],
+      "function foo({ a }, [b]) { [a, b] = [b, a]; }": [
+        {
+          col: 27,
+          message: variant!(NoParamReassignMessage, Reassign, "a"),
+          hint: NoParamReassignHint::UseLocal,
+        },
+        {
+          col: 27,
+          message: variant!(NoParamReassignMessage, Reassign, "b"),
+          hint: NoParamReassignHint::UseLocal,
+        }
+      ],
+      "function foo(a) { for (a of []) {} }": [
+        {
+          col: 18,
+          message: variant!(NoParamReassignMessage, Reassign, "a"),
+          hint: NoParamReassignHint::UseLocal,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoParamReassign,
+      options: json!({ "props": true, "ignorePropertyModificationsFor": ["acc"] }),
+      "function foo(options) { options.timeout ??= 0; }": [
+        {
+          col: 24,
+          message: variant!(NoParamReassignMessage, Modify, "options"),
+          hint: NoParamReassignHint::UseCopy,
+        }
+      ],
+      "function foo(a) { a.b.c++; delete a[0]; }": [
+        {
+          col: 18,
+          message: variant!(NoParamReassignMessage, Modify, "a"),
+          hint: NoParamReassignHint::UseCopy,
+        },
+        {
+          col: 27,
+          message: variant!(NoParamReassignMessage, Modify, "a"),
+          hint: NoParamReassignHint::UseCopy,
+        }
+      ],
+      "items.reduce((total, item) => { total.sum += item; return total; }, {});": [
+        {
+          col: 32,
+          message: variant!(NoParamReassignMessage, Modify, "total"),
+          hint: NoParamReassignHint::UseCopy,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,412 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{find_lhs_ids, prop_name};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, Expr, ExprOrSuper, ForInStmt, ForOfStmt, Function,
+  Ident, Pat, PatOrExpr, Str, VarDeclOrPat, VarDeclarator,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoProtoPollutionSinks;
+
+const CODE: &str = "no-proto-pollution-sinks";
+
+#[derive(Display)]
+enum NoProtoPollutionSinksMessage {
+  #[display(fmt = "Assignment modifies `{}`", _0)]
+  Prototype(String),
+  #[display(
+    fmt = "Property is written to an object looked up with a key derived from a parameter"
+  )]
+  DynamicKey,
+}
+
+#[derive(Display)]
+enum NoProtoPollutionSinksHint {
+  #[display(
+    fmt = "Don't modify prototypes at runtime, define the properties on the objects or classes instead"
+  )]
+  Prototype,
+  #[display(
+    fmt = "Reject the `__proto__`, `constructor` and `prototype` keys, or store the data in a `Map` or an object created with `Object.create(null)`"
+  )]
+  DynamicKey,
+}
+
+impl LintRule for NoProtoPollutionSinks {
+  fn new() -> Box<Self> {
+    Box::new(NoProtoPollutionSinks)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoProtoPollutionSinksVisitor {
+      context,
+      frame: Frame::default(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows writing to prototypes, and to objects looked up with keys derived from parameters",
+      "no-proto-pollution-sinks"
+    )
+  }
+}
+
+/// A step of the member chain an assignment writes to, e.g. `a`, `b` and
+/// `[c]` for `a.b[c] = value`.
+enum Step<'a> {
+  Name(&'a str),
+  Computed(&'a Expr),
+}
+
+/// Returns the member chain of an assignment target, from its root object,
+/// or `None` if the target isn't a member expression.
+fn member_chain(mut expr: &Expr) -> Option<Vec<Step<'_>>> {
+  let mut steps = Vec::new();
+  loop {
+    match expr {
+      Expr::Member(member) => {
+        match prop_name(member) {
+          Some(name) => steps.push(Step::Name(name)),
+          None => steps.push(Step::Computed(&member.prop)),
+        }
+        expr = match &member.obj {
+          ExprOrSuper::Expr(obj) => obj,
+          ExprOrSuper::Super(_) => return None,
+        };
+      }
+      Expr::Paren(paren) => expr = &paren.expr,
+      Expr::Ident(ident) => {
+        steps.push(Step::Name(&ident.sym));
+        break;
+      }
+      _ => {
+        steps.push(Step::Computed(expr));
+        break;
+      }
+    }
+  }
+  if steps.len() < 2 {
+    return None;
+  }
+  steps.reverse();
+  Some(steps)
+}
+
+/// Returns the prototype a member chain writes to, if any.
+fn written_prototype(steps: &[Step]) -> Option<&'static str> {
+  let names: Vec<Option<&str>> = steps
+    .iter()
+    .map(|step| match step {
+      Step::Name(name) => Some(*name),
+      Step::Computed(_) => None,
+    })
+    .collect();
+  if names.starts_with(&[Some("Object"), Some("prototype")]) {
+    Some("Object.prototype")
+  } else if names.contains(&Some("__proto__")) {
+    Some("__proto__")
+  } else if names
+    .windows(2)
+    .any(|pair| pair == [Some("constructor"), Some("prototype")])
+  {
+    Some("constructor.prototype")
+  } else {
+    None
+  }
+}
+
+/// Collects the identifiers an expression references.
+struct IdentCollector {
+  ids: Vec<Id>,
+}
+
+impl Visit for IdentCollector {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, ident: &Ident, _: &dyn Node) {
+    self.ids.push(ident.to_id());
+  }
+
+  // Only the object of `a.b` is a reference
+  fn visit_member_expr(
+    &mut self,
+    member: &swc_ecmascript::ast::MemberExpr,
+    _: &dyn Node,
+  ) {
+    member.obj.visit_with(member, self);
+    if member.computed {
+      member.prop.visit_with(member, self);
+    }
+  }
+}
+
+/// Finds `"__proto__"` strings, which functions rejecting the key mention.
+#[derive(Default)]
+struct ProtoGuardFinder {
+  found: bool,
+}
+
+impl Visit for ProtoGuardFinder {
+  noop_visit_type!();
+
+  fn visit_str(&mut self, s: &Str, _: &dyn Node) {
+    if s.value == *"__proto__" {
+      self.found = true;
+    }
+  }
+}
+
+fn mentions_proto<N: VisitWith<ProtoGuardFinder>>(node: &N) -> bool {
+  let mut finder = ProtoGuardFinder::default();
+  node.visit_children_with(&mut finder);
+  finder.found
+}
+
+/// Variables holding values derived from parameters in the function being
+/// visited, including the ones of enclosing functions.
+#[derive(Clone, Default)]
+struct Frame {
+  tainted: HashSet<Id>,
+  guarded: bool,
+}
+
+struct NoProtoPollutionSinksVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  frame: Frame,
+}
+
+impl<'c, 'view> NoProtoPollutionSinksVisitor<'c, 'view> {
+  /// Returns `true` if the expression references a variable derived from a
+  /// parameter.
+  fn is_tainted(&self, expr: &Expr) -> bool {
+    if let Expr::Lit(_) = expr {
+      return false;
+    }
+    let mut collector = IdentCollector { ids: Vec::new() };
+    expr.visit_with(&DUMMY_NODE, &mut collector);
+    collector
+      .ids
+      .iter()
+      .any(|id| self.frame.tainted.contains(id))
+  }
+
+  fn with_function<N: VisitWith<Self>>(
+    &mut self,
+    params: Vec<Id>,
+    guarded: bool,
+    node: &N,
+  ) {
+    let prev = self.frame.clone();
+    self.frame.tainted.extend(params);
+    self.frame.guarded |= guarded;
+    node.visit_children_with(self);
+    self.frame = prev;
+  }
+
+  fn check_target(&mut self, target: &Expr) {
+    let steps = match member_chain(target) {
+      Some(steps) => steps,
+      None => return,
+    };
+
+    if let Some(prototype) = written_prototype(&steps) {
+      self.context.add_diagnostic_with_hint(
+        target.span(),
+        CODE,
+        NoProtoPollutionSinksMessage::Prototype(prototype.to_string()),
+        NoProtoPollutionSinksHint::Prototype,
+      );
+      return;
+    }
+
+    // `obj[key]` may be `Object.prototype` when `key` is `__proto__`, so
+    // writing to its properties pollutes all objects
+    let looks_up_dynamic_object =
+      steps[..steps.len() - 1]
+        .iter()
+        .skip(1)
+        .any(|step| match step {
+          Step::Computed(key) => self.is_tainted(key),
+          Step::Name(_) => false,
+        });
+    if looks_up_dynamic_object && !self.frame.guarded {
+      self.context.add_diagnostic_with_hint(
+        target.span(),
+        CODE,
+        NoProtoPollutionSinksMessage::DynamicKey,
+        NoProtoPollutionSinksHint::DynamicKey,
+      );
+    }
+  }
+
+  fn taint_pat(&mut self, pat: &Pat, value: &Expr) {
+    if self.is_tainted(value) {
+      let ids: Vec<Id> = find_ids(pat);
+      self.frame.tainted.extend(ids);
+    }
+  }
+
+  fn taint_left(&mut self, left: &VarDeclOrPat, right: &Expr) {
+    match left {
+      VarDeclOrPat::VarDecl(var_decl) => {
+        for decl in &var_decl.decls {
+          self.taint_pat(&decl.name, right);
+        }
+      }
+      VarDeclOrPat::Pat(pat) => self.taint_pat(pat, right),
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoProtoPollutionSinksVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    let params: Vec<Id> = find_ids(&function.params);
+    self.with_function(params, mentions_proto(function), function);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    let params: Vec<Id> = find_ids(&arrow_expr.params);
+    self.with_function(params, mentions_proto(arrow_expr), arrow_expr);
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    declarator.visit_children_with(self);
+    if let Some(init) = &declarator.init {
+      self.taint_pat(&declarator.name, init);
+    }
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    assign_expr.visit_children_with(self);
+    match &assign_expr.left {
+      PatOrExpr::Expr(expr) => self.check_target(expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => self.check_target(expr),
+        _ => {
+          if self.is_tainted(&assign_expr.right) {
+            let ids: Vec<Id> = find_lhs_ids(&assign_expr.left);
+            self.frame.tainted.extend(ids);
+          }
+        }
+      },
+    }
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in: &ForInStmt, _: &dyn Node) {
+    self.taint_left(&for_in.left, &for_in.right);
+    for_in.visit_children_with(self);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of: &ForOfStmt, _: &dyn Node) {
+    self.taint_left(&for_of.left, &for_of.right);
+    for_of.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_proto_pollution_sinks_valid() {
+    assert_lint_ok! {
+      NoProtoPollutionSinks,
+      r#"function set(obj, key, value) { obj[key] = value; }"#,
+      r#"function set(obj, key, value) { obj.items[key] = value; }"#,
+      r#"const config = {}; config["a"]["b"] = 1;"#,
+      r#"function fill(grid, value) { grid[0][1] = value; }"#,
+      r#"const cache = {}; function get(key) { return cache[key][0]; }"#,
+      r#"function f() { const data = load(); data[name].value = 1; }"#,
+      r#"
+function merge(target, source) {
+  for (const key in source) {
+    if (key === "__proto__" || key === "constructor") continue;
+    target[key].value = source[key];
+  }
+}
+      "#,
+      r#"Foo.prototype.bar = function () {};"#,
+    };
+  }
+
+  #[test]
+  fn no_proto_pollution_sinks_invalid() {
+    assert_lint_err! {
+      NoProtoPollutionSinks,
+      r#"obj.__proto__.isAdmin = true;"#: [
+        {
+          col: 0,
+          message: variant!(NoProtoPollutionSinksMessage, Prototype, "__proto__"),
+          hint: NoProtoPollutionSinksHint::Prototype,
+        }
+      ],
+      r#"Object.prototype.isAdmin = true;"#: [
+        {
+          col: 0,
+          message: variant!(NoProtoPollutionSinksMessage, Prototype, "Object.prototype"),
+          hint: NoProtoPollutionSinksHint::Prototype,
+        }
+      ],
+      r#"function f(input) { input.constructor.prototype[input.key] = input.value; }"#: [
+        {
+          col: 20,
+          message: variant!(NoProtoPollutionSinksMessage, Prototype, "constructor.prototype"),
+          hint: NoProtoPollutionSinksHint::Prototype,
+        }
+      ],
+      r#"function set(obj, path, value) { obj[path[0]][path[1]] = value; }"#: [
+        {
+          col: 33,
+          message: NoProtoPollutionSinksMessage::DynamicKey,
+          hint: NoProtoPollutionSinksHint::DynamicKey,
+        }
+      ],
+      r#"
+function merge(target, source) {
+  for (const key of Object.keys(source)) {
+    const value = source[key];
+    Object.keys(value).forEach((k) => {
+      target[key][k] = value[k];
+    });
+  }
+}
+      "#: [
+        {
+          line: 6,
+          col: 6,
+          message: NoProtoPollutionSinksMessage::DynamicKey,
+          hint: NoProtoPollutionSinksHint::DynamicKey,
+        }
+      ],
+      r#"app.post("/settings", (req) => { const { section, name } = req.body; settings[section].name = name; });"#: [
+        {
+          col: 69,
+          message: NoProtoPollutionSinksMessage::DynamicKey,
+          hint: NoProtoPollutionSinksHint::DynamicKey,
+        }
+      ],
+    };
+  }
+}
@@ -26,6 +26,10 @@ impl LintRule for NoRedeclare {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
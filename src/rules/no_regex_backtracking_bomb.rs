@@ -0,0 +1,397 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::js_regex::*;
+use derive_more::Display;
+use swc_common::{BytePos, Span};
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, Ident, Lit, NewExpr, Regex, Str,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoRegexBacktrackingBomb;
+
+const CODE: &str = "no-regex-backtracking-bomb";
+
+#[derive(Clone, Copy, Display)]
+enum NoRegexBacktrackingBombMessage {
+  #[display(
+    fmt = "Nested quantifiers in this subpattern are prone to catastrophic backtracking"
+  )]
+  NestedQuantifiers,
+  #[display(
+    fmt = "Overlapping alternatives in this repeated subpattern are prone to catastrophic backtracking"
+  )]
+  OverlappingAlternatives,
+}
+
+#[derive(Display)]
+enum NoRegexBacktrackingBombHint {
+  #[display(
+    fmt = "Rewrite the subpattern so that its repetitions can't match the same input in several ways, e.g. `(a+)+` as `a+`"
+  )]
+  NestedQuantifiers,
+  #[display(
+    fmt = "Rewrite the alternatives so that they can't match the same input, e.g. `(a|aa)+` as `a+`"
+  )]
+  OverlappingAlternatives,
+}
+
+impl NoRegexBacktrackingBombMessage {
+  fn hint(self) -> NoRegexBacktrackingBombHint {
+    match self {
+      Self::NestedQuantifiers => NoRegexBacktrackingBombHint::NestedQuantifiers,
+      Self::OverlappingAlternatives => {
+        NoRegexBacktrackingBombHint::OverlappingAlternatives
+      }
+    }
+  }
+}
+
+impl LintRule for NoRegexBacktrackingBomb {
+  fn new() -> Box<Self> {
+    Box::new(NoRegexBacktrackingBomb)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoRegexBacktrackingBombVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows regular expressions with nested quantifiers prone to catastrophic backtracking",
+      "no-regex-backtracking-bomb"
+    )
+  }
+}
+
+/// Returns `true` if the node can match the empty string.
+fn can_match_empty(node: &RegexNode) -> bool {
+  match &node.kind {
+    RegexNodeKind::Disjunction(alternatives) => {
+      alternatives.iter().any(can_match_empty)
+    }
+    RegexNodeKind::Alternative(terms) => terms.iter().all(can_match_empty),
+    RegexNodeKind::Group(node) => can_match_empty(node),
+    RegexNodeKind::Quantifier { node, min, .. } => {
+      *min == 0 || can_match_empty(node)
+    }
+    RegexNodeKind::Lookaround(_) | RegexNodeKind::Assertion => true,
+    RegexNodeKind::Atom => false,
+  }
+}
+
+/// Returns `true` if the node is an unbounded repetition, apart from parts
+/// that can match the empty string, like `a+` or `(\s?\w*)`. Repeating such a
+/// node lets the engine split the same input between the iterations in
+/// exponentially many ways.
+fn is_unbounded_repetition(node: &RegexNode) -> bool {
+  match &node.kind {
+    RegexNodeKind::Disjunction(alternatives) => {
+      alternatives.iter().any(is_unbounded_repetition)
+    }
+    RegexNodeKind::Alternative(terms) => {
+      terms.iter().enumerate().any(|(i, term)| {
+        is_unbounded_repetition(term)
+          && terms
+            .iter()
+            .enumerate()
+            .all(|(j, other)| i == j || can_match_empty(other))
+      })
+    }
+    RegexNodeKind::Group(node) => is_unbounded_repetition(node),
+    RegexNodeKind::Quantifier { node, max, .. } => {
+      max.is_none() || is_unbounded_repetition(node)
+    }
+    RegexNodeKind::Lookaround(_)
+    | RegexNodeKind::Assertion
+    | RegexNodeKind::Atom => false,
+  }
+}
+
+/// Returns `true` if the node is a disjunction where an alternative starts
+/// with all the terms of another one, like `a|aa` or `ab|abc`. Repeating such
+/// a disjunction lets the engine match the same input with either of them.
+fn has_overlapping_alternatives(node: &RegexNode, pattern: &str) -> bool {
+  let alternatives = match &node.kind {
+    RegexNodeKind::Group(node) => match &node.kind {
+      RegexNodeKind::Disjunction(alternatives) => alternatives,
+      _ => return false,
+    },
+    RegexNodeKind::Disjunction(alternatives) => alternatives,
+    _ => return false,
+  };
+  let terms: Vec<Vec<&str>> = alternatives
+    .iter()
+    .map(|alternative| match &alternative.kind {
+      RegexNodeKind::Alternative(terms) => terms
+        .iter()
+        .map(|term| &pattern[term.start..term.end])
+        .collect(),
+      _ => vec![&pattern[alternative.start..alternative.end]],
+    })
+    .collect();
+  terms.iter().enumerate().any(|(i, prefix)| {
+    !prefix.is_empty()
+      && terms
+        .iter()
+        .enumerate()
+        .any(|(j, other)| i != j && other.starts_with(prefix))
+  })
+}
+
+/// Collects the outermost unbounded quantifiers repeating an unbounded
+/// repetition or overlapping alternatives.
+fn collect_bombs<'a>(
+  node: &'a RegexNode,
+  pattern: &str,
+  bombs: &mut Vec<(&'a RegexNode, NoRegexBacktrackingBombMessage)>,
+) {
+  match &node.kind {
+    RegexNodeKind::Quantifier {
+      node: quantified,
+      max: None,
+      ..
+    } if is_unbounded_repetition(quantified) => {
+      bombs.push((node, NoRegexBacktrackingBombMessage::NestedQuantifiers))
+    }
+    RegexNodeKind::Quantifier {
+      node: quantified,
+      max: None,
+      ..
+    } if has_overlapping_alternatives(quantified, pattern) => bombs.push((
+      node,
+      NoRegexBacktrackingBombMessage::OverlappingAlternatives,
+    )),
+    RegexNodeKind::Disjunction(nodes) | RegexNodeKind::Alternative(nodes) => {
+      for node in nodes {
+        collect_bombs(node, pattern, bombs);
+      }
+    }
+    RegexNodeKind::Group(node)
+    | RegexNodeKind::Lookaround(node)
+    | RegexNodeKind::Quantifier { node, .. } => {
+      collect_bombs(node, pattern, bombs)
+    }
+    RegexNodeKind::Assertion | RegexNodeKind::Atom => {}
+  }
+}
+
+struct NoRegexBacktrackingBombVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> NoRegexBacktrackingBombVisitor<'c, 'view> {
+  /// Reports the bombs in the pattern. Their spans are computed from `lo`, the
+  /// position of the pattern in the source, if given, otherwise the whole
+  /// `span` is reported.
+  fn check_pattern(&mut self, pattern: &str, lo: Option<BytePos>, span: Span) {
+    let node = match parse_regex_pattern(pattern) {
+      Some(node) => node,
+      None => return,
+    };
+    let mut bombs = Vec::new();
+    collect_bombs(&node, pattern, &mut bombs);
+    for (bomb, message) in bombs {
+      let span = match lo {
+        Some(lo) => Span::new(
+          lo + BytePos(bomb.start as u32),
+          lo + BytePos(bomb.end as u32),
+          span.ctxt,
+        ),
+        None => span,
+      };
+      self.context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        message,
+        message.hint(),
+      );
+    }
+  }
+
+  /// Checks the pattern passed as a string to `RegExp` or `new RegExp`.
+  fn check_regexp_call(&mut self, callee: &Ident, args: &[ExprOrSpread]) {
+    if callee.sym != *"RegExp"
+      || self.context.scope().var(&callee.to_id()).is_some()
+    {
+      return;
+    }
+    if let Some(Expr::Lit(Lit::Str(str))) = args.first().map(|arg| &*arg.expr) {
+      self.check_pattern(&str.value, string_lo(self.context, str), str.span);
+    }
+  }
+}
+
+/// Returns the position of the value of the string literal in the source, if
+/// its value is written as is, i.e. without escape sequences.
+fn string_lo(context: &Context, str: &Str) -> Option<BytePos> {
+  let source = context.source_map().span_to_snippet(str.span).ok()?;
+  if source.len() == str.value.len() + 2 && source[1..].starts_with(&*str.value)
+  {
+    Some(str.span.lo + BytePos(1))
+  } else {
+    None
+  }
+}
+
+impl<'c, 'view> Visit for NoRegexBacktrackingBombVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_regex(&mut self, regex: &Regex, _: &dyn Node) {
+    // The pattern starts after the opening `/`
+    let lo = regex.span.lo + BytePos(1);
+    self.check_pattern(&regex.exp, Some(lo), regex.span);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    if let (Expr::Ident(callee), Some(args)) =
+      (&*new_expr.callee, &new_expr.args)
+    {
+      self.check_regexp_call(callee, args);
+    }
+    new_expr.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Ident(callee) = &**callee {
+        self.check_regexp_call(callee, &call_expr.args);
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_regex_backtracking_bomb_valid() {
+    assert_lint_ok! {
+      NoRegexBacktrackingBomb,
+      r"/a+/;",
+      r"/(a+)?/;",
+      r"/(ab)+/;",
+      r"/(\d+,)*\d+/;",
+      r"/(?:a+b)*/;",
+      r"/(a{1,3}){2,}/;",
+      r"/[(a+)]+/;",
+      r"/\(a+\)+/;",
+      r"/(a+){3}/;",
+      r"/(a|b)+/;",
+      r"/(ab|ac)*/;",
+      r"/(a|ba)+/;",
+      r"/(a|aa){2}/;",
+      r#"new RegExp("(a+){3}");"#,
+      r#"new RegExp(pattern);"#,
+      r#"const RegExp = Foo; new RegExp("(a+)+");"#,
+    };
+  }
+
+  #[test]
+  fn no_regex_backtracking_bomb_invalid() {
+    assert_lint_err! {
+      NoRegexBacktrackingBomb,
+      r"/^(a+)+$/;": [
+        {
+          col: 2,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/(a*)*b/;": [
+        {
+          col: 1,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/^(\w+\s?)*$/;": [
+        {
+          col: 2,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/(?:x|y+)+z/;": [
+        {
+          col: 1,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/^((a+)?b?){2,}$/;": [
+        {
+          col: 2,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/(?=(a+)+)|(b*)*/;": [
+        {
+          col: 4,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        },
+        {
+          col: 11,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"/^(a|aa)+$/;": [
+        {
+          col: 2,
+          message: NoRegexBacktrackingBombMessage::OverlappingAlternatives,
+          hint: NoRegexBacktrackingBombHint::OverlappingAlternatives,
+        }
+      ],
+      r"/(?:\d|x|\dx)*y/;": [
+        {
+          col: 1,
+          message: NoRegexBacktrackingBombMessage::OverlappingAlternatives,
+          hint: NoRegexBacktrackingBombHint::OverlappingAlternatives,
+        }
+      ],
+      r#"new RegExp("^(a+)+$");"#: [
+        {
+          col: 13,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r#"const re = RegExp("^(\\w+\\s?)*$", "u");"#: [
+        {
+          col: 18,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+      r"const re = /ü(.*)*/;": [
+        {
+          col: 13,
+          message: NoRegexBacktrackingBombMessage::NestedQuantifiers,
+          hint: NoRegexBacktrackingBombHint::NestedQuantifiers,
+        }
+      ],
+    };
+  }
+}
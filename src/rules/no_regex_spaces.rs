@@ -27,6 +27,10 @@ impl LintRule for NoRegexSpaces {
     CODE
   }
 
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
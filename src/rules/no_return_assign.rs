@@ -0,0 +1,302 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, BlockStmtOrExpr, Class, Expr, Function, ParenExpr,
+  ReturnStmt,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoReturnAssign {
+  options: NoReturnAssignOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoReturnAssignOptions {
+  /// Whether assignments wrapped in parentheses are allowed.
+  pub mode: NoReturnAssignMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NoReturnAssignMode {
+  /// Assignments wrapped in parentheses, e.g. `return (a = b);`, are allowed.
+  ExceptParens,
+  /// All assignments are reported.
+  Always,
+}
+
+impl Default for NoReturnAssignOptions {
+  fn default() -> Self {
+    Self {
+      mode: NoReturnAssignMode::ExceptParens,
+    }
+  }
+}
+
+const CODE: &str = "no-return-assign";
+
+#[derive(Display)]
+enum NoReturnAssignMessage {
+  #[display(fmt = "Return statement should not contain an assignment")]
+  Return,
+  #[display(fmt = "Arrow function should not return an assignment")]
+  Arrow,
+}
+
+#[derive(Display)]
+enum NoReturnAssignHint {
+  #[display(
+    fmt = "Move the assignment to its own statement, or wrap it in parentheses if it's intentional"
+  )]
+  MoveOrParenthesize,
+  #[display(fmt = "Move the assignment to its own statement")]
+  Move,
+}
+
+impl LintRule for NoReturnAssign {
+  fn new() -> Box<Self> {
+    Box::new(NoReturnAssign {
+      options: NoReturnAssignOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoReturnAssignVisitor {
+      context,
+      mode: self.options.mode,
+      returned_by: None,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "mode": {
+          "description": "Whether assignments wrapped in parentheses are allowed.",
+          "enum": ["except-parens", "always"],
+          "default": "except-parens"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows assignments in return statements and arrow function bodies",
+      "no-return-assign"
+    )
+  }
+}
+
+#[derive(Clone, Copy)]
+enum ReturnedBy {
+  ReturnStmt,
+  Arrow,
+}
+
+struct NoReturnAssignVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  mode: NoReturnAssignMode,
+  /// Set while visiting the value returned by a return statement or an arrow
+  /// function.
+  returned_by: Option<ReturnedBy>,
+}
+
+impl<'c, 'view> NoReturnAssignVisitor<'c, 'view> {
+  fn with_returned_by(
+    &mut self,
+    returned_by: Option<ReturnedBy>,
+    f: impl FnOnce(&mut Self),
+  ) {
+    let prev = std::mem::replace(&mut self.returned_by, returned_by);
+    f(self);
+    self.returned_by = prev;
+  }
+}
+
+impl<'c, 'view> Visit for NoReturnAssignVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, _: &dyn Node) {
+    self.with_returned_by(Some(ReturnedBy::ReturnStmt), |v| {
+      return_stmt.visit_children_with(v);
+    });
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.with_returned_by(None, |v| {
+      arrow_expr.params.visit_with(arrow_expr, v);
+    });
+    let returned_by = match &arrow_expr.body {
+      BlockStmtOrExpr::Expr(_) => Some(ReturnedBy::Arrow),
+      BlockStmtOrExpr::BlockStmt(_) => None,
+    };
+    self.with_returned_by(returned_by, |v| {
+      arrow_expr.body.visit_with(arrow_expr, v);
+    });
+  }
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.with_returned_by(None, |v| function.visit_children_with(v));
+  }
+
+  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+    self.with_returned_by(None, |v| class.visit_children_with(v));
+  }
+
+  fn visit_paren_expr(&mut self, paren_expr: &ParenExpr, _: &dyn Node) {
+    match &*paren_expr.expr {
+      Expr::Assign(assign_expr)
+        if self.mode == NoReturnAssignMode::ExceptParens =>
+      {
+        assign_expr.visit_children_with(self);
+      }
+      _ => paren_expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    if let Some(returned_by) = self.returned_by {
+      let message = match returned_by {
+        ReturnedBy::ReturnStmt => NoReturnAssignMessage::Return,
+        ReturnedBy::Arrow => NoReturnAssignMessage::Arrow,
+      };
+      let hint = match self.mode {
+        NoReturnAssignMode::ExceptParens => {
+          NoReturnAssignHint::MoveOrParenthesize
+        }
+        NoReturnAssignMode::Always => NoReturnAssignHint::Move,
+      };
+      self.context.add_diagnostic_with_hint(
+        assign_expr.span,
+        CODE,
+        message,
+        hint,
+      );
+    }
+    assign_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_return_assign_valid() {
+    assert_lint_ok! {
+      NoReturnAssign,
+      "function f() { a = b; return a; }",
+      "function f() { return a === b; }",
+      "function f() { return (a = b); }",
+      "function f() { return ((a = b)); }",
+      "const f = () => (a = b);",
+      "const f = () => { a = b; };",
+      "function f() { return function () { a = b; }; }",
+      "function f() { return () => { a = b; }; }",
+      "function f() { return class { foo() { a = b; } }; }",
+      "const f = (a = 1) => a;",
+    };
+
+    assert_lint_ok! {
+      NoReturnAssign,
+      options: json!({ "mode": "always" }),
+      "function f() { a = b; return a; }",
+      "const f = () => { a = b; };",
+    };
+  }
+
+  #[test]
+  fn no_return_assign_invalid() {
+    assert_lint_err! {
+      NoReturnAssign,
+      "function f() { return a = b; }": [
+        {
+          col: 22,
+          message: NoReturnAssignMessage::Return,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+      "function f() { return a += 1; }": [
+        {
+          col: 22,
+          message: NoReturnAssignMessage::Return,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+      "function f() { return foo(a = b) && c; }": [
+        {
+          col: 26,
+          message: NoReturnAssignMessage::Return,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+      "function f() { return (a = b) || (c = d, e); }": [
+        {
+          col: 34,
+          message: NoReturnAssignMessage::Return,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+      "const f = () => a = b;": [
+        {
+          col: 16,
+          message: NoReturnAssignMessage::Arrow,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+      "function f() { return () => a = b; }": [
+        {
+          col: 28,
+          message: NoReturnAssignMessage::Arrow,
+          hint: NoReturnAssignHint::MoveOrParenthesize,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoReturnAssign,
+      options: json!({ "mode": "always" }),
+      "function f() { return (a = b); }": [
+        {
+          col: 23,
+          message: NoReturnAssignMessage::Return,
+          hint: NoReturnAssignHint::Move,
+        }
+      ],
+      "const f = () => (a = b);": [
+        {
+          col: 17,
+          message: NoReturnAssignMessage::Arrow,
+          hint: NoReturnAssignHint::Move,
+        }
+      ],
+    };
+  }
+}
@@ -513,13 +513,13 @@ mod tests {
       ],
       "({a, b} = {b, a})": [
         {
-          col: 14,
-          message: variant!(NoSelfAssignMessage, Invalid, "a"),
+          col: 11,
+          message: variant!(NoSelfAssignMessage, Invalid, "b"),
           hint: NoSelfAssignHint::Mistake,
         },
         {
-          col: 11,
-          message: variant!(NoSelfAssignMessage, Invalid, "b"),
+          col: 14,
+          message: variant!(NoSelfAssignMessage, Invalid, "a"),
           hint: NoSelfAssignHint::Mistake,
         }
       ],
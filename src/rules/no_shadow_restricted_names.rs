@@ -44,6 +44,10 @@ impl LintRule for NoShadowRestrictedNames {
   fn code(&self) -> &'static str {
     CODE
   }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
 }
 
 struct NoShadowRestrictedNamesVisitor<'c, 'view> {
@@ -0,0 +1,347 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use crate::swc_util::{
+  exported_local_names, overloaded_functions, overloaded_methods, StringRepr,
+};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  Accessibility, Class, ClassMember, Decl, DefaultDecl, Expr, MethodKind,
+  Module, ModuleDecl, ModuleItem, Pat, Stmt,
+};
+
+pub struct NoSlowTypesMissingReturnType;
+
+const CODE: &str = "no-slow-types-missing-return-type";
+
+#[derive(Display)]
+enum NoSlowTypesMissingReturnTypeMessage {
+  #[display(fmt = "Missing explicit return type in the public API")]
+  MissingReturnType,
+}
+
+#[derive(Display)]
+enum NoSlowTypesMissingReturnTypeHint {
+  #[display(
+    fmt = "Add an explicit return type so it can be determined without type inference"
+  )]
+  AddReturnType,
+}
+
+impl LintRule for NoSlowTypesMissingReturnType {
+  fn new() -> Box<Self> {
+    Box::new(NoSlowTypesMissingReturnType)
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsr"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    // Scripts don't have a public API
+    if let ProgramRef::Module(module) = program {
+      check_module(context, module);
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires explicit return types on functions that are part of the public API of a module",
+      "no-slow-types-missing-return-type"
+    )
+  }
+}
+
+fn check_module(context: &mut Context, module: &Module) {
+  let exported_names = exported_local_names(module);
+  let overloaded = overloaded_functions(module);
+
+  for item in &module.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        check_decl(context, &export_decl.decl, None, &overloaded);
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default_decl)) => {
+        match &default_decl.decl {
+          DefaultDecl::Fn(fn_expr) => {
+            if fn_expr.function.return_type.is_none() {
+              report(context, default_decl.span);
+            }
+          }
+          DefaultDecl::Class(class_expr) => {
+            check_class(context, &class_expr.class)
+          }
+          DefaultDecl::TsInterfaceDecl(_) => {}
+        }
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_expr)) => {
+        if let Some(span) = untyped_function_span(&default_expr.expr) {
+          report(context, span);
+        }
+      }
+      ModuleItem::Stmt(Stmt::Decl(decl)) => {
+        check_decl(context, decl, Some(&exported_names), &overloaded);
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Checks the declaration, or only the bindings in `exported_names` if given.
+fn check_decl(
+  context: &mut Context,
+  decl: &Decl,
+  exported_names: Option<&HashSet<JsWord>>,
+  overloaded: &HashSet<JsWord>,
+) {
+  let is_exported = |name: &JsWord| match exported_names {
+    Some(names) => names.contains(name),
+    None => true,
+  };
+
+  match decl {
+    Decl::Fn(fn_decl) if is_exported(&fn_decl.ident.sym) => {
+      // The implementation of an overloaded function isn't part of its type
+      let is_overload_implementation = fn_decl.function.body.is_some()
+        && overloaded.contains(&fn_decl.ident.sym);
+      if fn_decl.function.return_type.is_none() && !is_overload_implementation {
+        report(context, fn_decl.ident.span);
+      }
+    }
+    Decl::Class(class_decl) if is_exported(&class_decl.ident.sym) => {
+      check_class(context, &class_decl.class);
+    }
+    Decl::Var(var_decl) => {
+      for declarator in &var_decl.decls {
+        let binding = match &declarator.name {
+          Pat::Ident(binding) if binding.type_ann.is_none() => binding,
+          _ => continue,
+        };
+        if !is_exported(&binding.id.sym) {
+          continue;
+        }
+        if let Some(init) = &declarator.init {
+          if untyped_function_span(init).is_some() {
+            report(context, binding.id.span);
+          }
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+fn check_class(context: &mut Context, class: &Class) {
+  let overloaded = overloaded_methods(class);
+
+  for member in &class.body {
+    match member {
+      ClassMember::Method(method) => {
+        if method.accessibility == Some(Accessibility::Private)
+          || method.kind == MethodKind::Setter
+          || method.function.return_type.is_some()
+        {
+          continue;
+        }
+        let is_overload_implementation = method.function.body.is_some()
+          && matches!(
+            method.key.string_repr(),
+            Some(key) if overloaded.contains(&key)
+          );
+        if !is_overload_implementation {
+          report(context, method.key.span());
+        }
+      }
+      ClassMember::ClassProp(prop) => {
+        if prop.accessibility == Some(Accessibility::Private)
+          || prop.type_ann.is_some()
+        {
+          continue;
+        }
+        if prop
+          .value
+          .as_deref()
+          .and_then(untyped_function_span)
+          .is_some()
+        {
+          report(context, prop.key.span());
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Returns the span of the expression if it's a function or an arrow function
+/// without a return type.
+fn untyped_function_span(expr: &Expr) -> Option<Span> {
+  match expr {
+    Expr::Arrow(arrow) if arrow.return_type.is_none() => Some(arrow.span),
+    Expr::Fn(fn_expr) if fn_expr.function.return_type.is_none() => {
+      Some(fn_expr.function.span)
+    }
+    Expr::Paren(paren) => untyped_function_span(&paren.expr),
+    _ => None,
+  }
+}
+
+fn report(context: &mut Context, span: Span) {
+  context.add_diagnostic_with_hint(
+    span,
+    CODE,
+    NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+    NoSlowTypesMissingReturnTypeHint::AddReturnType,
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_slow_types_missing_return_type_valid() {
+    assert_lint_ok! {
+      NoSlowTypesMissingReturnType,
+      "export function foo(): string { return 'foo'; }",
+      "export async function foo(): Promise<void> {}",
+      "export declare function foo(): void;",
+      "export default function (): number { return 1; }",
+      "export default (): number => 1;",
+      "export const foo = (): number => 1;",
+      "export const foo = function (): number { return 1; };",
+      "export const foo: () => number = () => 1;",
+      "export const foo = 1;",
+      "export class Foo { bar(): void {} get baz(): number { return 1; } set baz(v: number) {} }",
+      "export class Foo { constructor() {} private bar() {} #baz() {} }",
+      "export class Foo { bar = (): number => 1; baz: () => number = () => 1; private qux = () => 1; }",
+      "export class Foo { bar(a: string): void; bar(a: number): void; bar(a: any) {} }",
+
+      // overloads
+      r#"
+export function foo(a: string): string;
+export function foo(a: number): number;
+export function foo(a: any) {
+  return a;
+}
+      "#,
+
+      // not exported
+      "function foo() {}",
+      "const foo = () => 1;",
+      "class Foo { bar() {} }",
+      "function foo() {} export { bar } from './bar.ts';",
+      "function foo() {} export type { foo };",
+    };
+  }
+
+  #[test]
+  fn no_slow_types_missing_return_type_invalid() {
+    assert_lint_err! {
+      NoSlowTypesMissingReturnType,
+      "export function foo() {}": [
+        {
+          col: 16,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export async function* foo() {}": [
+        {
+          col: 23,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export declare function foo();": [
+        {
+          col: 24,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export default function () {}": [
+        {
+          col: 15,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export default () => 1;": [
+        {
+          col: 15,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export const foo = () => 1, bar = function () {};": [
+        {
+          col: 13,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        },
+        {
+          col: 28,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export class Foo { bar() {} protected get baz() { return 1; } qux = () => 1; }": [
+        {
+          col: 19,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        },
+        {
+          col: 42,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        },
+        {
+          col: 62,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "export default class { static create() {} }": [
+        {
+          col: 30,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "function foo() {} const bar = () => 1, baz = 1; export { foo, bar as qux };": [
+        {
+          col: 9,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        },
+        {
+          col: 24,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+      "class Foo { bar() {} } export default Foo;": [
+        {
+          col: 12,
+          message: NoSlowTypesMissingReturnTypeMessage::MissingReturnType,
+          hint: NoSlowTypesMissingReturnTypeHint::AddReturnType,
+        }
+      ],
+    };
+  }
+}
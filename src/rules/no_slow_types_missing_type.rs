@@ -0,0 +1,506 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use crate::swc_util::{
+  exported_local_names, overloaded_functions, overloaded_methods, StringRepr,
+};
+use derive_more::Display;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  Accessibility, Class, ClassMember, Decl, DefaultDecl, Expr, Function, Lit,
+  Module, ModuleDecl, ModuleItem, ParamOrTsParamProp, Pat, Prop, PropName,
+  PropOrSpread, Stmt, TsParamPropParam, UnaryOp,
+};
+
+pub struct NoSlowTypesMissingType;
+
+const CODE: &str = "no-slow-types-missing-type";
+
+#[derive(Display)]
+enum NoSlowTypesMissingTypeMessage {
+  #[display(fmt = "Missing explicit type in the public API")]
+  MissingType,
+}
+
+#[derive(Display)]
+enum NoSlowTypesMissingTypeHint {
+  #[display(
+    fmt = "Add an explicit type annotation so it can be determined without type inference"
+  )]
+  AddType,
+}
+
+impl LintRule for NoSlowTypesMissingType {
+  fn new() -> Box<Self> {
+    Box::new(NoSlowTypesMissingType)
+  }
+
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsr"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    // Scripts don't have a public API
+    if let ProgramRef::Module(module) = program {
+      check_module(context, module);
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires explicit types on variables, parameters and class properties that are part of the public API of a module",
+      "no-slow-types-missing-type"
+    )
+  }
+}
+
+fn check_module(context: &mut Context, module: &Module) {
+  let exported_names = exported_local_names(module);
+  let overloaded = overloaded_functions(module);
+
+  for item in &module.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        check_decl(context, &export_decl.decl, None, &overloaded);
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default_decl)) => {
+        match &default_decl.decl {
+          DefaultDecl::Fn(fn_expr) => {
+            check_function_params(context, &fn_expr.function)
+          }
+          DefaultDecl::Class(class_expr) => {
+            check_class(context, &class_expr.class)
+          }
+          DefaultDecl::TsInterfaceDecl(_) => {}
+        }
+      }
+      // Exported bindings are checked where they're declared
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_expr))
+        if !matches!(&*default_expr.expr, Expr::Ident(_)) =>
+      {
+        if is_type_evident(Some(&default_expr.expr)) {
+          check_function_expr_params(context, &default_expr.expr);
+        } else {
+          report(context, default_expr.expr.span());
+        }
+      }
+      ModuleItem::Stmt(Stmt::Decl(decl)) => {
+        check_decl(context, decl, Some(&exported_names), &overloaded);
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Checks the declaration, or only the bindings in `exported_names` if given.
+fn check_decl(
+  context: &mut Context,
+  decl: &Decl,
+  exported_names: Option<&HashSet<JsWord>>,
+  overloaded: &HashSet<JsWord>,
+) {
+  let is_exported = |name: &JsWord| match exported_names {
+    Some(names) => names.contains(name),
+    None => true,
+  };
+
+  match decl {
+    Decl::Fn(fn_decl) if is_exported(&fn_decl.ident.sym) => {
+      // The implementation of an overloaded function isn't part of its type
+      let is_overload_implementation = fn_decl.function.body.is_some()
+        && overloaded.contains(&fn_decl.ident.sym);
+      if !is_overload_implementation {
+        check_function_params(context, &fn_decl.function);
+      }
+    }
+    Decl::Class(class_decl) if is_exported(&class_decl.ident.sym) => {
+      check_class(context, &class_decl.class);
+    }
+    Decl::Var(var_decl) => {
+      for declarator in &var_decl.decls {
+        let binding = match &declarator.name {
+          Pat::Ident(binding) if binding.type_ann.is_none() => binding,
+          _ => continue,
+        };
+        if !is_exported(&binding.id.sym) {
+          continue;
+        }
+        if !is_type_evident(declarator.init.as_deref()) {
+          report(context, binding.id.span);
+        } else if let Some(init) = &declarator.init {
+          check_function_expr_params(context, init);
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+fn check_class(context: &mut Context, class: &Class) {
+  let overloaded = overloaded_methods(class);
+
+  for member in &class.body {
+    match member {
+      ClassMember::ClassProp(prop)
+        if prop.accessibility != Some(Accessibility::Private) =>
+      {
+        if prop.type_ann.is_some() {
+          continue;
+        }
+        if !is_type_evident(prop.value.as_deref()) {
+          report(context, prop.key.span());
+        } else if let Some(value) = &prop.value {
+          check_function_expr_params(context, value);
+        }
+      }
+      ClassMember::Method(method)
+        if method.accessibility != Some(Accessibility::Private) =>
+      {
+        let is_overload_implementation = method.function.body.is_some()
+          && matches!(
+            method.key.string_repr(),
+            Some(key) if overloaded.contains(&key)
+          );
+        if !is_overload_implementation {
+          check_function_params(context, &method.function);
+        }
+      }
+      ClassMember::Constructor(constructor)
+        if constructor.accessibility != Some(Accessibility::Private) =>
+      {
+        // Parameter properties are part of the public API even when the
+        // constructor is an overload implementation
+        let is_overload_implementation = constructor.body.is_some()
+          && class.body.iter().any(|member| {
+            matches!(member, ClassMember::Constructor(c) if c.body.is_none())
+          });
+        for param in &constructor.params {
+          match param {
+            ParamOrTsParamProp::Param(param) => {
+              if !is_overload_implementation {
+                check_param(context, &param.pat);
+              }
+            }
+            ParamOrTsParamProp::TsParamProp(param_prop)
+              if param_prop.accessibility != Some(Accessibility::Private) =>
+            {
+              match &param_prop.param {
+                TsParamPropParam::Ident(binding)
+                  if binding.type_ann.is_none() =>
+                {
+                  report(context, binding.id.span);
+                }
+                TsParamPropParam::Assign(assign) => match &*assign.left {
+                  Pat::Ident(binding)
+                    if binding.type_ann.is_none()
+                      && !is_type_evident(Some(&assign.right)) =>
+                  {
+                    report(context, binding.id.span);
+                  }
+                  _ => {}
+                },
+                _ => {}
+              }
+            }
+            ParamOrTsParamProp::TsParamProp(_) => {}
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn check_function_params(context: &mut Context, function: &Function) {
+  for param in &function.params {
+    check_param(context, &param.pat);
+  }
+}
+
+/// Checks the parameters of the expression if it's a function or an arrow
+/// function.
+fn check_function_expr_params(context: &mut Context, expr: &Expr) {
+  match expr {
+    Expr::Arrow(arrow) => {
+      for param in &arrow.params {
+        check_param(context, param);
+      }
+    }
+    Expr::Fn(fn_expr) => check_function_params(context, &fn_expr.function),
+    Expr::Paren(paren) => check_function_expr_params(context, &paren.expr),
+    _ => {}
+  }
+}
+
+/// Reports a parameter without a type annotation, unless its default value
+/// has an evident type.
+fn check_param(context: &mut Context, pat: &Pat) {
+  let has_type = match pat {
+    Pat::Ident(binding) => binding.type_ann.is_some(),
+    Pat::Array(array) => array.type_ann.is_some(),
+    Pat::Object(object) => object.type_ann.is_some(),
+    Pat::Rest(rest) => rest.type_ann.is_some(),
+    Pat::Assign(assign) => {
+      assign.type_ann.is_some()
+        || match &*assign.left {
+          Pat::Ident(binding) => binding.type_ann.is_some(),
+          Pat::Array(array) => array.type_ann.is_some(),
+          Pat::Object(object) => object.type_ann.is_some(),
+          _ => false,
+        }
+        || is_type_evident(Some(&assign.right))
+    }
+    _ => true,
+  };
+  if !has_type {
+    report(context, pat.span());
+  }
+}
+
+/// Returns `true` if the type of a binding initialized with the given
+/// expression can be determined without type inference. Functions are
+/// considered evident as their return types are checked by
+/// `no-slow-types-missing-return-type`, and their parameters separately.
+fn is_type_evident(init: Option<&Expr>) -> bool {
+  let expr = match init {
+    Some(expr) => expr,
+    None => return false,
+  };
+
+  match expr {
+    Expr::Lit(Lit::JSXText(_)) => false,
+    Expr::Lit(_) => true,
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    Expr::Unary(unary) => {
+      matches!(unary.op, UnaryOp::Minus | UnaryOp::Plus)
+        && matches!(
+          &*unary.arg,
+          Expr::Lit(Lit::Num(_)) | Expr::Lit(Lit::BigInt(_))
+        )
+    }
+    Expr::TsAs(_) | Expr::TsTypeAssertion(_) => true,
+    Expr::TsConstAssertion(assertion) => is_type_evident(Some(&assertion.expr)),
+    Expr::Paren(paren) => is_type_evident(Some(&paren.expr)),
+    Expr::Arrow(_) | Expr::Fn(_) => true,
+    Expr::Array(array) => array.elems.iter().all(|elem| match elem {
+      Some(elem) => elem.spread.is_none() && is_type_evident(Some(&elem.expr)),
+      None => false,
+    }),
+    Expr::Object(object) => object.props.iter().all(|prop| match prop {
+      PropOrSpread::Prop(prop) => match &**prop {
+        Prop::KeyValue(key_value) => {
+          !matches!(key_value.key, PropName::Computed(_))
+            && is_type_evident(Some(&key_value.value))
+        }
+        _ => false,
+      },
+      PropOrSpread::Spread(_) => false,
+    }),
+    _ => false,
+  }
+}
+
+fn report(context: &mut Context, span: Span) {
+  context.add_diagnostic_with_hint(
+    span,
+    CODE,
+    NoSlowTypesMissingTypeMessage::MissingType,
+    NoSlowTypesMissingTypeHint::AddType,
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_slow_types_missing_type_valid() {
+    assert_lint_ok! {
+      NoSlowTypesMissingType,
+      "export const a = 1, b = 'b', c = true, d = null, e = 1n, f = /f/;",
+      "export const a = -1, b = `b`;",
+      "export const a = foo() as string, b = <number>bar();",
+      "export const a = ['a', 'b'] as const;",
+      "export const a = [1, 2], b = { c: 'c', 'd': [true] };",
+      "export const a: Foo = new Foo();",
+      "export let a: string;",
+      "export const a = () => 1;",
+      "export const { a, b } = foo();",
+      "export class Foo { a = 1; b: string; c: Bar = new Bar(); private d = foo(); #e = foo(); }",
+      "export class Foo { constructor(public a: string, readonly b = 1, private c = foo()) {} }",
+      "export default class { a = 'a'; }",
+      "export function foo(a: string, b = 1, { c }: Bar = {}, ...d: number[]) {}",
+      "export const foo = (a: string) => a, bar = function (b = 'b') {};",
+      "export function foo(a: string): void; export function foo(a) {}",
+      "export class Foo { bar(a: string): void; bar(a) {} private baz(b) {} }",
+      "export class Foo { constructor(a: string); constructor(a) {} }",
+      "export default function (a: string) {}",
+      "export default { a: 1 };",
+      "const a: Foo = foo(); export default a;",
+
+      // not exported
+      "const a = foo();",
+      "class Foo { a = foo(); }",
+      "const a = foo(); export { b } from './b.ts';",
+    };
+  }
+
+  #[test]
+  fn no_slow_types_missing_type_invalid() {
+    assert_lint_err! {
+      NoSlowTypesMissingType,
+      "export const a = foo();": [
+        {
+          col: 13,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export let a, b = 1;": [
+        {
+          col: 11,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export const a = new Foo(), b = `${c}`;": [
+        {
+          col: 13,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 28,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export const a = [b, ...c], d = { e: 1, f };": [
+        {
+          col: 13,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 28,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export class Foo { a; protected b = foo(); static c = bar; }": [
+        {
+          col: 19,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 32,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 50,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export class Foo { constructor(public a, readonly b = foo()) {} }": [
+        {
+          col: 38,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 50,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export function foo(a, [b], { c }, d = bar(), ...e) {}": [
+        {
+          col: 20,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 23,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 28,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 35,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 46,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export const foo = (a) => a;": [
+        {
+          col: 20,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export class Foo { constructor(a) {} bar(b) {} }": [
+        {
+          col: 31,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 41,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "function foo(a) {} export default foo;": [
+        {
+          col: 13,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "export default foo();": [
+        {
+          col: 15,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+      "const a = foo(), b = foo(); class C { d = e; } export { a, C as default };": [
+        {
+          col: 6,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        },
+        {
+          col: 38,
+          message: NoSlowTypesMissingTypeMessage::MissingType,
+          hint: NoSlowTypesMissingTypeHint::AddType,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,257 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{method_call, method_chain};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, CallExpr, Constructor, ExprStmt, Function,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoThen {
+  options: NoThenOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoThenOptions {
+  /// Allows promise chains ending with `.catch()` whose result isn't used,
+  /// e.g. `sendMetrics().catch(console.error);`.
+  pub allow_fire_and_forget_catch: bool,
+}
+
+const CODE: &str = "no-then";
+
+const PROMISE_METHODS: &[&str] = &["then", "catch", "finally"];
+
+#[derive(Display)]
+enum NoThenMessage {
+  #[display(fmt = "`.{}()` is used in an async function", _0)]
+  PromiseMethod(String),
+}
+
+#[derive(Display)]
+enum NoThenHint {
+  #[display(
+    fmt = "Use `await`, with `try`/`catch`/`finally` to handle errors"
+  )]
+  UseAwait,
+}
+
+impl LintRule for NoThen {
+  fn new() -> Box<Self> {
+    Box::new(NoThen {
+      options: NoThenOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoThenVisitor {
+      context,
+      options: &self.options,
+      in_async_fn: false,
+      allowed: HashSet::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allowFireAndForgetCatch": {
+          "description": "Allows promise chains ending with `.catch()` whose result isn't used, e.g. `sendMetrics().catch(console.error);`.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows `.then()`, `.catch()` and `.finally()` in async functions",
+      "no-then"
+    )
+  }
+}
+
+struct NoThenVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c NoThenOptions,
+  /// Whether the closest function is an async one.
+  in_async_fn: bool,
+  /// The spans of the calls of allowed fire-and-forget chains.
+  allowed: HashSet<Span>,
+}
+
+impl<'c, 'view> NoThenVisitor<'c, 'view> {
+  fn visit_fn_body<N: VisitWith<Self>>(&mut self, is_async: bool, node: &N) {
+    let prev = self.in_async_fn;
+    self.in_async_fn = is_async;
+    node.visit_children_with(self);
+    self.in_async_fn = prev;
+  }
+}
+
+impl<'c, 'view> Visit for NoThenVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.visit_fn_body(function.is_async, function);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.visit_fn_body(arrow_expr.is_async, arrow_expr);
+  }
+
+  fn visit_constructor(&mut self, constructor: &Constructor, _: &dyn Node) {
+    self.visit_fn_body(false, constructor);
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, _: &dyn Node) {
+    if self.options.allow_fire_and_forget_catch {
+      let (methods, _) = method_chain(&expr_stmt.expr);
+      if matches!(methods.first(), Some(("catch", _))) {
+        self
+          .allowed
+          .extend(methods.iter().map(|(_, call)| call.span));
+      }
+    }
+    expr_stmt.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if self.in_async_fn && !self.allowed.contains(&call_expr.span) {
+      if let Some((_, method, _)) = method_call(call_expr) {
+        if PROMISE_METHODS.contains(&method) {
+          self.context.add_diagnostic_with_hint(
+            call_expr.span,
+            CODE,
+            NoThenMessage::PromiseMethod(method.to_string()),
+            NoThenHint::UseAwait,
+          );
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_then_valid() {
+    assert_lint_ok! {
+      NoThen,
+      "async function foo() { const a = await b(); }",
+      "function foo() { return a().then(b); }",
+      "a().then(b).catch(c);",
+      "async function foo() { bar(function () { a.then(b); }); }",
+      "async function foo() { bar(() => a.then(b)); }",
+      "async function foo() { class A { constructor() { a.then(b); } } }",
+      "async function foo() { a.thenable(); }",
+    };
+
+    assert_lint_ok! {
+      NoThen,
+      options: json!({ "allowFireAndForgetCatch": true }),
+      "async function foo() { a().catch(b); }",
+      "async function foo() { a().then(b).finally(c).catch(d); }",
+    };
+  }
+
+  #[test]
+  fn no_then_invalid() {
+    assert_lint_err! {
+      NoThen,
+      "async function foo() { return a().then(b); }": [
+        {
+          col: 30,
+          message: variant!(NoThenMessage, PromiseMethod, "then"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+      "const foo = async () => { a.then(b).catch(c).finally(d); };": [
+        {
+          col: 26,
+          message: variant!(NoThenMessage, PromiseMethod, "catch"),
+          hint: NoThenHint::UseAwait,
+        },
+        {
+          col: 26,
+          message: variant!(NoThenMessage, PromiseMethod, "finally"),
+          hint: NoThenHint::UseAwait,
+        },
+        {
+          col: 26,
+          message: variant!(NoThenMessage, PromiseMethod, "then"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+      "class A { async foo() { await a.then(b); } }": [
+        {
+          col: 30,
+          message: variant!(NoThenMessage, PromiseMethod, "then"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+      "async function foo() { a().catch(b); }": [
+        {
+          col: 23,
+          message: variant!(NoThenMessage, PromiseMethod, "catch"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoThen,
+      options: json!({ "allowFireAndForgetCatch": true }),
+      "async function foo() { const c = a().catch(b); }": [
+        {
+          col: 33,
+          message: variant!(NoThenMessage, PromiseMethod, "catch"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+      "async function foo() { a().catch(b).then(c); }": [
+        {
+          col: 23,
+          message: variant!(NoThenMessage, PromiseMethod, "catch"),
+          hint: NoThenHint::UseAwait,
+        },
+        {
+          col: 23,
+          message: variant!(NoThenMessage, PromiseMethod, "then"),
+          hint: NoThenHint::UseAwait,
+        }
+      ],
+    };
+  }
+}
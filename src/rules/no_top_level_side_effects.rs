@@ -0,0 +1,517 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, AssignExpr, AwaitExpr, CallExpr, Class, Decl, Expr, ExprOrSuper,
+  Function, MetaPropExpr, ModuleDecl, ModuleItem, NewExpr, Stmt, TaggedTpl,
+  UnaryExpr, UnaryOp, UpdateExpr,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoTopLevelSideEffects {
+  options: NoTopLevelSideEffectsOptions,
+  library_files: GlobSet,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoTopLevelSideEffectsOptions {
+  /// Functions and constructors that are free of side effects, called or
+  /// constructed at the top level, e.g. `Symbol` or `Object.freeze`.
+  pub allowed_calls: Vec<String>,
+  /// Globs matching the files that are checked, e.g. `**/lib/**`. All files
+  /// are checked if empty.
+  pub library_files: Vec<String>,
+}
+
+impl Default for NoTopLevelSideEffectsOptions {
+  fn default() -> Self {
+    Self {
+      allowed_calls: [
+        "Symbol",
+        "Symbol.for",
+        "Object.freeze",
+        "Map",
+        "Set",
+        "WeakMap",
+        "WeakSet",
+        "RegExp",
+        "TextEncoder",
+        "TextDecoder",
+      ]
+      .iter()
+      .map(|name| name.to_string())
+      .collect(),
+      library_files: vec![],
+    }
+  }
+}
+
+impl NoTopLevelSideEffectsOptions {
+  fn compile(&self) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in &self.library_files {
+      builder.add(Glob::new(glob)?);
+    }
+    builder.build()
+  }
+}
+
+const CODE: &str = "no-top-level-side-effects";
+
+#[derive(Display)]
+enum NoTopLevelSideEffectsMessage {
+  #[display(fmt = "Top-level call runs when the module is imported")]
+  Call,
+  #[display(fmt = "Top-level `await` runs when the module is imported")]
+  Await,
+  #[display(fmt = "Top-level mutation runs when the module is imported")]
+  Mutation,
+  #[display(fmt = "Top-level statement runs when the module is imported")]
+  Statement,
+}
+
+#[derive(Display)]
+enum NoTopLevelSideEffectsHint {
+  #[display(
+    fmt = "Move it into a function called explicitly, or mark a pure call with `/* @__PURE__ */`"
+  )]
+  MoveIntoFunction,
+}
+
+impl LintRule for NoTopLevelSideEffects {
+  fn new() -> Box<Self> {
+    Box::new(NoTopLevelSideEffects {
+      options: NoTopLevelSideEffectsOptions::default(),
+      library_files: GlobSet::empty(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    if !self.options.library_files.is_empty()
+      && !self
+        .library_files
+        .is_match(context.file_name().replace('\\', "/"))
+    {
+      return;
+    }
+
+    match program {
+      ProgramRef::Module(module) => {
+        for item in &module.body {
+          match item {
+            ModuleItem::Stmt(stmt) => self.check_stmt(context, stmt),
+            ModuleItem::ModuleDecl(module_decl) => {
+              self.check_module_decl(context, module_decl)
+            }
+          }
+        }
+      }
+      ProgramRef::Script(script) => {
+        for stmt in &script.body {
+          self.check_stmt(context, stmt);
+        }
+      }
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: NoTopLevelSideEffectsOptions =
+      serde_json::from_value(options)?;
+    self.library_files = options
+      .compile()
+      .map_err(|err| serde_json::Error::custom(err.to_string()))?;
+    self.options = options;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allowedCalls": {
+          "description": "Functions and constructors that are free of side effects, called or constructed at the top level, e.g. `Symbol` or `Object.freeze`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": NoTopLevelSideEffectsOptions::default().allowed_calls
+        },
+        "libraryFiles": {
+          "description": "Globs matching the files that are checked, e.g. `**/lib/**`. All files are checked if empty.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows statements with side effects at the top level of modules",
+      "no-top-level-side-effects"
+    )
+  }
+}
+
+impl NoTopLevelSideEffects {
+  fn check_module_decl(&self, context: &mut Context, module_decl: &ModuleDecl) {
+    match module_decl {
+      ModuleDecl::ExportDecl(export_decl) => {
+        self.check_decl(context, &export_decl.decl)
+      }
+      ModuleDecl::ExportDefaultExpr(export_default) => {
+        self.check_node(context, &*export_default.expr)
+      }
+      ModuleDecl::TsExportAssignment(export_assignment) => {
+        self.check_node(context, &*export_assignment.expr)
+      }
+      _ => {}
+    }
+  }
+
+  fn check_decl(&self, context: &mut Context, decl: &Decl) {
+    if let Decl::Var(var_decl) = decl {
+      self.check_node(context, var_decl);
+    }
+  }
+
+  fn check_stmt(&self, context: &mut Context, stmt: &Stmt) {
+    match stmt {
+      Stmt::Decl(decl) => self.check_decl(context, decl),
+      Stmt::Expr(expr_stmt) => self.check_node(context, &*expr_stmt.expr),
+      Stmt::Empty(_) => {}
+      // The entry point of a program, e.g. `if (import.meta.main) main();`
+      Stmt::If(if_stmt) if is_import_meta_main(&if_stmt.test) => {}
+      _ => self.report(
+        context,
+        stmt.span(),
+        NoTopLevelSideEffectsMessage::Statement,
+      ),
+    }
+  }
+
+  fn check_node<N: for<'f> VisitWith<SideEffectFinder<'f>>>(
+    &self,
+    context: &mut Context,
+    node: &N,
+  ) {
+    let mut finder = SideEffectFinder {
+      context,
+      allowed_calls: &self.options.allowed_calls,
+      found: None,
+    };
+    node.visit_with(&DUMMY_NODE, &mut finder);
+    if let Some((span, message)) = finder.found {
+      self.report(context, span, message);
+    }
+  }
+
+  fn report(
+    &self,
+    context: &mut Context,
+    span: Span,
+    message: NoTopLevelSideEffectsMessage,
+  ) {
+    context.add_diagnostic_with_hint(
+      span,
+      CODE,
+      message,
+      NoTopLevelSideEffectsHint::MoveIntoFunction,
+    );
+  }
+}
+
+fn is_import_meta_main(expr: &Expr) -> bool {
+  match expr {
+    Expr::Member(member) if !member.computed => {
+      matches!(
+        &member.obj,
+        ExprOrSuper::Expr(obj) if matches!(
+          &**obj,
+          Expr::MetaProp(MetaPropExpr { meta, prop })
+            if meta.sym == *"import" && prop.sym == *"meta"
+        )
+      ) && matches!(&*member.prop, Expr::Ident(prop) if prop.sym == *"main")
+    }
+    _ => false,
+  }
+}
+
+/// Returns the name of an identifier or a chain of non-computed members, e.g.
+/// `Object.freeze`.
+fn dotted_name(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Member(member) if !member.computed => {
+      let obj = match &member.obj {
+        ExprOrSuper::Expr(obj) => dotted_name(obj)?,
+        ExprOrSuper::Super(_) => return None,
+      };
+      match &*member.prop {
+        Expr::Ident(prop) => Some(format!("{}.{}", obj, prop.sym)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Finds the first expression with side effects, without looking into
+/// functions and classes as their bodies don't run when they're defined.
+struct SideEffectFinder<'a> {
+  context: &'a Context<'a>,
+  allowed_calls: &'a [String],
+  found: Option<(Span, NoTopLevelSideEffectsMessage)>,
+}
+
+impl<'a> SideEffectFinder<'a> {
+  fn found(&mut self, span: Span, message: NoTopLevelSideEffectsMessage) {
+    if self.found.is_none() {
+      self.found = Some((span, message));
+    }
+  }
+
+  /// Returns `true` if the call is known to be pure, either from its callee
+  /// or from a `/* @__PURE__ */` annotation.
+  fn is_pure_call(&self, span: Span, callee: &Expr) -> bool {
+    let allowed = matches!(
+      dotted_name(callee),
+      Some(name) if self.allowed_calls.contains(&name)
+    );
+    allowed
+      || self.context.leading_comments_at(span.lo).any(|comment| {
+        let text = comment.text.trim();
+        text == "@__PURE__" || text == "#__PURE__"
+      })
+  }
+}
+
+impl<'a> Visit for SideEffectFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+
+  fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+
+  fn visit_class(&mut self, _: &Class, _: &dyn Node) {}
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    match &call_expr.callee {
+      ExprOrSuper::Expr(callee)
+        if self.is_pure_call(call_expr.span, callee) =>
+      {
+        call_expr.args.visit_with(call_expr, self);
+      }
+      _ => self.found(call_expr.span, NoTopLevelSideEffectsMessage::Call),
+    }
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    if self.is_pure_call(new_expr.span, &new_expr.callee) {
+      new_expr.args.visit_with(new_expr, self);
+    } else {
+      self.found(new_expr.span, NoTopLevelSideEffectsMessage::Call);
+    }
+  }
+
+  fn visit_tagged_tpl(&mut self, tagged_tpl: &TaggedTpl, _: &dyn Node) {
+    self.found(tagged_tpl.span, NoTopLevelSideEffectsMessage::Call);
+  }
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, _: &dyn Node) {
+    self.found(await_expr.span, NoTopLevelSideEffectsMessage::Await);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    self.found(assign_expr.span, NoTopLevelSideEffectsMessage::Mutation);
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    self.found(update_expr.span, NoTopLevelSideEffectsMessage::Mutation);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete {
+      self.found(unary_expr.span, NoTopLevelSideEffectsMessage::Mutation);
+    } else {
+      unary_expr.visit_children_with(self);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_top_level_side_effects_valid() {
+    assert_lint_ok! {
+      NoTopLevelSideEffects,
+      r#"import { foo } from "./foo.ts";"#,
+      r#"export * from "./foo.ts";"#,
+      "export const VERSION = '1.0.0';",
+      "const a = 1, b = [a, { c: a + 1 }], d = `${a}`;",
+      "let cache = new Map();",
+      "export const KEY = Symbol.for('key');",
+      "export const CONFIG = Object.freeze({ debug: false });",
+      "const id = /* @__PURE__ */ createId();",
+      "export function foo() { bar(); }",
+      "export const foo = async () => { await bar(); };",
+      "export class Foo { bar = baz(); static qux() { quux(); } }",
+      "export default function () { foo(); }",
+      "export default { foo: 1 };",
+      "interface Foo {} type Bar = Foo; enum Baz { A }",
+      "'use strict';",
+      "const { a, b } = obj;",
+      "if (import.meta.main) { main(); }",
+    };
+
+    assert_lint_ok! {
+      NoTopLevelSideEffects,
+      options: json!({ "libraryFiles": ["**/lib/**", "mod.ts"] }),
+      { src: "console.log('loaded');", filename: "scripts/build.ts" },
+      { src: "const config = loadConfig();", filename: "main.ts" },
+    };
+  }
+
+  #[test]
+  fn no_top_level_side_effects_invalid() {
+    assert_lint_err! {
+      NoTopLevelSideEffects,
+      "console.log('loaded');": [
+        {
+          col: 0,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "export const db = await connect();": [
+        {
+          col: 18,
+          message: NoTopLevelSideEffectsMessage::Await,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "const config = loadConfig();": [
+        {
+          col: 15,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "globalThis.foo = 1;": [
+        {
+          col: 0,
+          message: NoTopLevelSideEffectsMessage::Mutation,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "counter++;": [
+        {
+          col: 0,
+          message: NoTopLevelSideEffectsMessage::Mutation,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "const { a = init() } = obj;": [
+        {
+          col: 12,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "export default createApp();": [
+        {
+          col: 15,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "const style = css`color: red;`;": [
+        {
+          col: 14,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "const server = new Server();": [
+        {
+          col: 15,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "for (const plugin of plugins) register(plugin);": [
+        {
+          col: 0,
+          message: NoTopLevelSideEffectsMessage::Statement,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "if (Deno.env.get('DEBUG')) enableDebug();": [
+        {
+          col: 0,
+          message: NoTopLevelSideEffectsMessage::Statement,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+      "const a = Object.freeze(setup());": [
+        {
+          col: 24,
+          message: NoTopLevelSideEffectsMessage::Call,
+          hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoTopLevelSideEffects,
+      options: json!({ "libraryFiles": ["**/lib/**", "mod.ts"] }),
+      "console.log('loaded');": {
+        filename: "src/lib/log.ts",
+        errors: [
+          {
+            col: 0,
+            message: NoTopLevelSideEffectsMessage::Call,
+            hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+          }
+        ]
+      },
+      "const token = Deno.env.get('TOKEN');": {
+        filename: "mod.ts",
+        errors: [
+          {
+            col: 14,
+            message: NoTopLevelSideEffectsMessage::Call,
+            hint: NoTopLevelSideEffectsHint::MoveIntoFunction,
+          }
+        ]
+      },
+    };
+  }
+}
@@ -0,0 +1,396 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::member_name;
+use derive_more::Display;
+use std::collections::{HashMap, HashSet};
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  AwaitExpr, BinExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, ExprStmt, Lit,
+  SwitchStmt, VarDeclarator,
+};
+use swc_ecmascript::utils::find_ids;
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoUncheckedPermissionRequest;
+
+const CODE: &str = "no-unchecked-permission-request";
+
+#[derive(Display)]
+enum NoUncheckedPermissionRequestMessage {
+  #[display(
+    fmt = "Promise returned by `Deno.permissions.request()` is not awaited"
+  )]
+  NotAwaited,
+  #[display(
+    fmt = "Result of `Deno.permissions.request()` is not checked against \"granted\""
+  )]
+  Unchecked,
+}
+
+#[derive(Display)]
+enum NoUncheckedPermissionRequestHint {
+  #[display(
+    fmt = "Await the result and check that its `state` is \"granted\""
+  )]
+  AwaitAndCheck,
+  #[display(
+    fmt = "Check that `status.state === \"granted\"` before using the permission"
+  )]
+  Check,
+}
+
+impl LintRule for NoUncheckedPermissionRequest {
+  fn new() -> Box<Self> {
+    Box::new(NoUncheckedPermissionRequest)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut collector = RequestCollector::default();
+    match program {
+      ProgramRef::Module(m) => collector.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => collector.visit_script(s, &DUMMY_NODE),
+    }
+
+    let RequestCollector {
+      requests, checked, ..
+    } = collector;
+    for request in requests {
+      let message = match request.result {
+        RequestResult::Discarded if !request.awaited => {
+          NoUncheckedPermissionRequestMessage::NotAwaited
+        }
+        RequestResult::Discarded => {
+          NoUncheckedPermissionRequestMessage::Unchecked
+        }
+        RequestResult::Bound(_) if !request.awaited => {
+          NoUncheckedPermissionRequestMessage::NotAwaited
+        }
+        RequestResult::Bound(ids)
+          if !ids.iter().any(|id| checked.contains(id)) =>
+        {
+          NoUncheckedPermissionRequestMessage::Unchecked
+        }
+        _ => continue,
+      };
+      let hint = match message {
+        NoUncheckedPermissionRequestMessage::NotAwaited => {
+          NoUncheckedPermissionRequestHint::AwaitAndCheck
+        }
+        NoUncheckedPermissionRequestMessage::Unchecked => {
+          NoUncheckedPermissionRequestHint::Check
+        }
+      };
+      context.add_diagnostic_with_hint(request.span, CODE, message, hint);
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires the result of `Deno.permissions.request()` to be awaited and checked",
+      "no-unchecked-permission-request"
+    )
+  }
+}
+
+/// What happens to the status returned by a permission request.
+enum RequestResult {
+  /// The status is thrown away, e.g. `await Deno.permissions.request(desc);`.
+  Discarded,
+  /// The status is stored in these variables.
+  Bound(Vec<Id>),
+  /// The status is used in a way that isn't followed, e.g. it's returned or
+  /// passed to another function.
+  Escaped,
+}
+
+struct Request {
+  span: Span,
+  awaited: bool,
+  result: RequestResult,
+}
+
+#[derive(Default)]
+struct RequestCollector {
+  requests: Vec<Request>,
+  /// Variables holding the promise of a request that isn't awaited yet, and
+  /// the index of the request.
+  pending: HashMap<Id, usize>,
+  /// Variables compared against a permission state, either holding the
+  /// status, as in `status.state === "granted"`, or the state itself.
+  checked: HashSet<Id>,
+}
+
+/// Returns a call to `Deno.permissions.request()` or
+/// `Deno.permissions.requestSync()`, and whether it returns a promise.
+fn request_call(expr: &Expr) -> Option<(&CallExpr, bool)> {
+  let call = match expr {
+    Expr::Call(call) => call,
+    Expr::Paren(paren) => return request_call(&paren.expr),
+    _ => return None,
+  };
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => match &**callee {
+      Expr::Member(member) => member,
+      _ => return None,
+    },
+    ExprOrSuper::Super(_) => return None,
+  };
+  let is_async = match member_name(callee) {
+    Some("request") => true,
+    Some("requestSync") => false,
+    _ => return None,
+  };
+  let permissions = match &callee.obj {
+    ExprOrSuper::Expr(obj) => match &**obj {
+      Expr::Member(permissions) => permissions,
+      _ => return None,
+    },
+    ExprOrSuper::Super(_) => return None,
+  };
+  let is_deno = match &permissions.obj {
+    ExprOrSuper::Expr(obj) => {
+      matches!(&**obj, Expr::Ident(ident) if ident.sym == *"Deno")
+    }
+    ExprOrSuper::Super(_) => false,
+  };
+  if is_deno && member_name(permissions) == Some("permissions") {
+    Some((call, is_async))
+  } else {
+    None
+  }
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_paren(&paren.expr),
+    _ => expr,
+  }
+}
+
+/// Returns the variable of `status.state` or `state`.
+fn state_var(expr: &Expr) -> Option<Id> {
+  match unwrap_paren(expr) {
+    Expr::Ident(ident) => Some(ident.to_id()),
+    Expr::Member(member) if member_name(member) == Some("state") => {
+      match &member.obj {
+        ExprOrSuper::Expr(obj) => match &**obj {
+          Expr::Ident(ident) => Some(ident.to_id()),
+          _ => None,
+        },
+        ExprOrSuper::Super(_) => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+fn is_permission_state(expr: &Expr) -> bool {
+  match unwrap_paren(expr) {
+    Expr::Lit(Lit::Str(s)) => {
+      matches!(&*s.value, "granted" | "denied" | "prompt")
+    }
+    _ => false,
+  }
+}
+
+impl RequestCollector {
+  /// Records a request, returning its index. Calls to
+  /// `Deno.permissions.requestSync()` count as awaited.
+  fn record(
+    &mut self,
+    expr: &Expr,
+    result: impl FnOnce() -> RequestResult,
+  ) -> Option<usize> {
+    let (call, awaited) = match unwrap_paren(expr) {
+      Expr::Await(await_expr) => match request_call(&await_expr.arg) {
+        Some((call, _)) => (call, true),
+        None => return None,
+      },
+      expr => match request_call(expr) {
+        Some((call, is_async)) => (call, !is_async),
+        None => return None,
+      },
+    };
+    self.requests.push(Request {
+      span: call.span,
+      awaited,
+      result: result(),
+    });
+    Some(self.requests.len() - 1)
+  }
+}
+
+impl Visit for RequestCollector {
+  noop_visit_type!();
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, _: &dyn Node) {
+    self.record(&expr_stmt.expr, || RequestResult::Discarded);
+    expr_stmt.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    declarator.visit_children_with(self);
+    let init = match &declarator.init {
+      Some(init) => unwrap_paren(init),
+      None => return,
+    };
+    let ids: Vec<Id> = find_ids(&declarator.name);
+
+    // `const status = await promise;` where `promise` is a pending request
+    if let Expr::Await(await_expr) = init {
+      if let Expr::Ident(ident) = unwrap_paren(&await_expr.arg) {
+        if let Some(&index) = self.pending.get(&ident.to_id()) {
+          let request = &mut self.requests[index];
+          request.awaited = true;
+          request.result = RequestResult::Bound(ids);
+          return;
+        }
+      }
+    }
+
+    let index = self.record(init, || RequestResult::Bound(ids.clone()));
+    if let Some(index) = index {
+      if !self.requests[index].awaited {
+        for id in ids {
+          self.pending.insert(id, index);
+        }
+      }
+    }
+  }
+
+  fn visit_await_expr(&mut self, await_expr: &AwaitExpr, _: &dyn Node) {
+    // The status of a pending request awaited anywhere but in a variable
+    // declaration is not followed
+    if let Expr::Ident(ident) = unwrap_paren(&await_expr.arg) {
+      if let Some(&index) = self.pending.get(&ident.to_id()) {
+        let request = &mut self.requests[index];
+        if !request.awaited {
+          request.awaited = true;
+          request.result = RequestResult::Escaped;
+        }
+      }
+    }
+    await_expr.visit_children_with(self);
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    if matches!(
+      bin_expr.op,
+      BinaryOp::EqEqEq | BinaryOp::NotEqEq | BinaryOp::EqEq | BinaryOp::NotEq
+    ) {
+      let var = if is_permission_state(&bin_expr.right) {
+        state_var(&bin_expr.left)
+      } else if is_permission_state(&bin_expr.left) {
+        state_var(&bin_expr.right)
+      } else {
+        None
+      };
+      if let Some(var) = var {
+        self.checked.insert(var);
+      }
+    }
+    bin_expr.visit_children_with(self);
+  }
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, _: &dyn Node) {
+    let has_state_case = switch_stmt.cases.iter().any(
+      |case| matches!(&case.test, Some(test) if is_permission_state(test)),
+    );
+    if has_state_case {
+      if let Some(var) = state_var(&switch_stmt.discriminant) {
+        self.checked.insert(var);
+      }
+    }
+    switch_stmt.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_unchecked_permission_request_valid() {
+    assert_lint_ok! {
+      NoUncheckedPermissionRequest,
+      r#"const status = await Deno.permissions.request({ name: "read" });
+    if (status.state === "granted") { await Deno.readTextFile("a.txt"); }"#,
+      r#"const { state } = await Deno.permissions.request({ name: "net" });
+    if (state !== "granted") throw new Error("denied");"#,
+      r#"const status = Deno.permissions.requestSync({ name: "env" });
+    if (status.state == "granted") console.log(Deno.env.get("HOME"));"#,
+      r#"const promise = Deno.permissions.request({ name: "read" });
+const status = await promise;
+switch (status.state) { case "granted": break; default: Deno.exit(1); }"#,
+      r#"if ((await Deno.permissions.request({ name: "read" })).state === "granted") {}"#,
+      r#"function request() { return Deno.permissions.request({ name: "read" }); }"#,
+      r#"async function request() { return await Deno.permissions.request({ name: "read" }); }"#,
+      r#"Deno.permissions.request({ name: "read" }).then((s) => s.state === "granted");"#,
+      r#"await Deno.permissions.query({ name: "read" });"#,
+      r#"permissions.request({ name: "read" });"#,
+    };
+  }
+
+  #[test]
+  fn no_unchecked_permission_request_invalid() {
+    assert_lint_err! {
+      NoUncheckedPermissionRequest,
+      r#"Deno.permissions.request({ name: "read" });"#: [
+        {
+          col: 0,
+          message: NoUncheckedPermissionRequestMessage::NotAwaited,
+          hint: NoUncheckedPermissionRequestHint::AwaitAndCheck,
+        }
+      ],
+      r#"await Deno.permissions.request({ name: "read" });"#: [
+        {
+          col: 6,
+          message: NoUncheckedPermissionRequestMessage::Unchecked,
+          hint: NoUncheckedPermissionRequestHint::Check,
+        }
+      ],
+      r#"const status = Deno.permissions.request({ name: "read" });
+    if (status.state === "granted") {}"#: [
+        {
+          col: 15,
+          message: NoUncheckedPermissionRequestMessage::NotAwaited,
+          hint: NoUncheckedPermissionRequestHint::AwaitAndCheck,
+        }
+      ],
+      r#"const status = await Deno.permissions.request({ name: "read" });
+    console.log(status.state);"#: [
+        {
+          col: 21,
+          message: NoUncheckedPermissionRequestMessage::Unchecked,
+          hint: NoUncheckedPermissionRequestHint::Check,
+        }
+      ],
+      r#"const promise = Deno.permissions.request({ name: "read" });
+const status = await promise;
+await Deno.readTextFile("a.txt");"#: [
+        {
+          col: 16,
+          message: NoUncheckedPermissionRequestMessage::Unchecked,
+          hint: NoUncheckedPermissionRequestHint::Check,
+        }
+      ],
+      r#"Deno.permissions.requestSync({ name: "env" });"#: [
+        {
+          col: 0,
+          message: NoUncheckedPermissionRequestMessage::Unchecked,
+          hint: NoUncheckedPermissionRequestHint::Check,
+        }
+      ],
+    };
+  }
+}
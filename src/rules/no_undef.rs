@@ -23,6 +23,10 @@ impl LintRule for NoUndef {
     "no-undef"
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
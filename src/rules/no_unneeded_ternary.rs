@@ -0,0 +1,500 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{BinaryOp, CondExpr, Expr, Lit, UnaryOp};
+use swc_ecmascript::utils::drop_span;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoUnneededTernary {
+  options: NoUnneededTernaryOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoUnneededTernaryOptions {
+  /// Whether `x ? x : y` is allowed instead of `x || y`.
+  pub default_assignment: bool,
+  /// How conditions that aren't booleans are converted by the fix.
+  pub boolean_cast: BooleanCast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BooleanCast {
+  /// `!!x`.
+  DoubleNegation,
+  /// `Boolean(x)`.
+  BooleanCall,
+}
+
+impl Default for NoUnneededTernaryOptions {
+  fn default() -> Self {
+    Self {
+      default_assignment: true,
+      boolean_cast: BooleanCast::DoubleNegation,
+    }
+  }
+}
+
+const CODE: &str = "no-unneeded-ternary";
+
+#[derive(Display)]
+enum NoUnneededTernaryMessage {
+  #[display(
+    fmt = "Unnecessary use of boolean literals in conditional expression"
+  )]
+  BooleanLiterals,
+  #[display(
+    fmt = "Unnecessary conditional expression, it's the same as its condition"
+  )]
+  SameAsCondition,
+  #[display(
+    fmt = "Unnecessary use of conditional expression for default assignment"
+  )]
+  DefaultAssignment,
+}
+
+#[derive(Display)]
+enum NoUnneededTernaryHint {
+  #[display(
+    fmt = "Use the condition instead, negated or converted to a boolean if needed"
+  )]
+  UseCondition,
+  #[display(fmt = "Use `||` instead")]
+  UseOr,
+}
+
+#[derive(Display)]
+enum NoUnneededTernaryFix {
+  #[display(fmt = "Replace with the condition")]
+  UseCondition,
+  #[display(fmt = "Replace with `||`")]
+  UseOr,
+}
+
+impl LintRule for NoUnneededTernary {
+  fn new() -> Box<Self> {
+    Box::new(NoUnneededTernary {
+      options: NoUnneededTernaryOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoUnneededTernaryVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "defaultAssignment": {
+          "description": "Whether `x ? x : y` is allowed instead of `x || y`.",
+          "type": "boolean",
+          "default": true
+        },
+        "booleanCast": {
+          "description": "How conditions that aren't booleans are converted by the fix.",
+          "enum": ["double-negation", "boolean-call"],
+          "default": "double-negation"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows conditional expressions that can be replaced by simpler expressions",
+      "no-unneeded-ternary"
+    )
+  }
+}
+
+fn unwrap_parens(mut expr: &Expr) -> &Expr {
+  while let Expr::Paren(paren) = expr {
+    expr = &paren.expr;
+  }
+  expr
+}
+
+fn bool_value(expr: &Expr) -> Option<bool> {
+  match unwrap_parens(expr) {
+    Expr::Lit(Lit::Bool(b)) => Some(b.value),
+    _ => None,
+  }
+}
+
+/// Returns `true` if the expression always evaluates to a boolean.
+fn is_boolean(expr: &Expr) -> bool {
+  match unwrap_parens(expr) {
+    Expr::Lit(Lit::Bool(_)) => true,
+    Expr::Unary(unary) => unary.op == UnaryOp::Bang,
+    Expr::Bin(bin) => matches!(
+      bin.op,
+      BinaryOp::EqEq
+        | BinaryOp::NotEq
+        | BinaryOp::EqEqEq
+        | BinaryOp::NotEqEq
+        | BinaryOp::Lt
+        | BinaryOp::LtEq
+        | BinaryOp::Gt
+        | BinaryOp::GtEq
+        | BinaryOp::In
+        | BinaryOp::InstanceOf
+    ),
+    _ => false,
+  }
+}
+
+/// Returns `true` if the expression can be the operand of a unary operator
+/// without parentheses.
+fn is_unary_operand(expr: &Expr) -> bool {
+  matches!(
+    expr,
+    Expr::Ident(_)
+      | Expr::Lit(_)
+      | Expr::Member(_)
+      | Expr::Call(_)
+      | Expr::New(_)
+      | Expr::Paren(_)
+      | Expr::This(_)
+      | Expr::Array(_)
+      | Expr::Object(_)
+      | Expr::Tpl(_)
+      | Expr::Unary(_)
+  )
+}
+
+/// Returns `true` if the expression can be the right operand of `||` without
+/// parentheses.
+fn is_or_operand(expr: &Expr) -> bool {
+  match expr {
+    Expr::Cond(_)
+    | Expr::Assign(_)
+    | Expr::Seq(_)
+    | Expr::Arrow(_)
+    | Expr::Yield(_) => false,
+    Expr::Bin(bin) => bin.op != BinaryOp::NullishCoalescing,
+    _ => true,
+  }
+}
+
+struct NoUnneededTernaryVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c NoUnneededTernaryOptions,
+}
+
+impl<'c, 'view> NoUnneededTernaryVisitor<'c, 'view> {
+  fn snippet(&self, expr: &Expr) -> Option<String> {
+    self.context.source_map().span_to_snippet(expr.span()).ok()
+  }
+
+  /// Returns the text of the negated condition, e.g. `a !== b` for `a === b`.
+  fn negated(&self, test: &Expr) -> Option<String> {
+    if let Expr::Bin(bin) = test {
+      let op = match bin.op {
+        BinaryOp::EqEq => Some("!="),
+        BinaryOp::NotEq => Some("=="),
+        BinaryOp::EqEqEq => Some("!=="),
+        BinaryOp::NotEqEq => Some("==="),
+        _ => None,
+      };
+      if let Some(op) = op {
+        let left = self.snippet(&bin.left)?;
+        let right = self.snippet(&bin.right)?;
+        return Some(format!("{} {} {}", left, op, right));
+      }
+    }
+    let text = self.snippet(test)?;
+    if is_unary_operand(test) {
+      Some(format!("!{}", text))
+    } else {
+      Some(format!("!({})", text))
+    }
+  }
+
+  /// Returns the text of the condition converted to a boolean.
+  fn cast(&self, test: &Expr) -> Option<String> {
+    let text = self.snippet(test)?;
+    if is_boolean(test) {
+      return Some(text);
+    }
+    match self.options.boolean_cast {
+      BooleanCast::DoubleNegation if is_unary_operand(test) => {
+        Some(format!("!!{}", text))
+      }
+      BooleanCast::DoubleNegation => Some(format!("!!({})", text)),
+      BooleanCast::BooleanCall if matches!(test, Expr::Seq(_)) => {
+        Some(format!("Boolean(({}))", text))
+      }
+      BooleanCast::BooleanCall => Some(format!("Boolean({})", text)),
+    }
+  }
+
+  fn report(
+    &mut self,
+    cond_expr: &CondExpr,
+    message: NoUnneededTernaryMessage,
+    hint: NoUnneededTernaryHint,
+    fix: Option<(NoUnneededTernaryFix, String)>,
+  ) {
+    let span = cond_expr.span;
+    let has_comments = self.context.all_comments().any(|comment| {
+      span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+    });
+    let fixes = match fix {
+      Some((fix, text)) if !has_comments => {
+        vec![self.context.create_fix(fix, vec![(span, text)])]
+      }
+      _ => vec![],
+    };
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      message,
+      Some(hint.to_string()),
+      fixes,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for NoUnneededTernaryVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _: &dyn Node) {
+    let test = &*cond_expr.test;
+    match (bool_value(&cond_expr.cons), bool_value(&cond_expr.alt)) {
+      (Some(cons), Some(alt)) => {
+        let fix = match (cons, alt) {
+          (true, false) => self.cast(test),
+          (false, true) => self.negated(test),
+          // The condition may have side effects, so it can't be removed
+          _ => None,
+        };
+        self.report(
+          cond_expr,
+          NoUnneededTernaryMessage::BooleanLiterals,
+          NoUnneededTernaryHint::UseCondition,
+          fix.map(|text| (NoUnneededTernaryFix::UseCondition, text)),
+        );
+      }
+      (None, Some(false))
+        if is_boolean(test)
+          && *drop_span(cond_expr.cons.clone()) == drop_span(test.clone()) =>
+      {
+        let fix = self.snippet(test);
+        self.report(
+          cond_expr,
+          NoUnneededTernaryMessage::SameAsCondition,
+          NoUnneededTernaryHint::UseCondition,
+          fix.map(|text| (NoUnneededTernaryFix::UseCondition, text)),
+        );
+      }
+      _ => {
+        if let (Expr::Ident(test), Expr::Ident(cons)) =
+          (test, unwrap_parens(&cond_expr.cons))
+        {
+          if !self.options.default_assignment && test.sym == cons.sym {
+            let alt = &*cond_expr.alt;
+            let fix = self.snippet(alt).map(|alt_text| {
+              let alt_text = if is_or_operand(alt) {
+                alt_text
+              } else {
+                format!("({})", alt_text)
+              };
+              (
+                NoUnneededTernaryFix::UseOr,
+                format!("{} || {}", test.sym, alt_text),
+              )
+            });
+            self.report(
+              cond_expr,
+              NoUnneededTernaryMessage::DefaultAssignment,
+              NoUnneededTernaryHint::UseOr,
+              fix,
+            );
+          }
+        }
+      }
+    }
+    cond_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_unneeded_ternary_valid() {
+    assert_lint_ok! {
+      NoUnneededTernary,
+      "const a = x === 2 ? 'Yes' : 'No';",
+      "const a = x ? 1 : 0;",
+      "const a = x ? x : 1;",
+      "const a = x === 1 ? y === 1 : false;",
+      "const a = x ? x : false;",
+      "const a = x ? true : y;",
+    };
+
+    assert_lint_ok! {
+      NoUnneededTernary,
+      options: json!({ "defaultAssignment": false }),
+      "const a = x ? y : x;",
+      "const a = x.y ? x.y : 1;",
+    };
+  }
+
+  #[test]
+  fn no_unneeded_ternary_invalid() {
+    assert_lint_err! {
+      NoUnneededTernary,
+      "const a = x === 2 ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = x === 2;"),
+        }
+      ],
+      "const a = x === 2 ? false : true;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = x !== 2;"),
+        }
+      ],
+      "const a = x < 2 ? false : true;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = !(x < 2);"),
+        }
+      ],
+      "const a = x ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = !!x;"),
+        }
+      ],
+      "const a = x || y ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = !!(x || y);"),
+        }
+      ],
+      "const a = foo() ? false : true;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = !foo();"),
+        }
+      ],
+      "const a = foo() ? true : true;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+        }
+      ],
+      "const a = x /* ok */ ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+        }
+      ],
+      "const a = x === 1 ? x === 1 : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::SameAsCondition,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = x === 1;"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoUnneededTernary,
+      options: json!({ "booleanCast": "boolean-call" }),
+      "const a = x ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = Boolean(x);"),
+        }
+      ],
+      "const a = x === 2 ? true : false;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::BooleanLiterals,
+          hint: NoUnneededTernaryHint::UseCondition,
+          fix: (NoUnneededTernaryFix::UseCondition, "const a = x === 2;"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoUnneededTernary,
+      options: json!({ "defaultAssignment": false }),
+      "const a = x ? x : 1;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::DefaultAssignment,
+          hint: NoUnneededTernaryHint::UseOr,
+          fix: (NoUnneededTernaryFix::UseOr, "const a = x || 1;"),
+        }
+      ],
+      "const a = x ? x : y ? 1 : 2;": [
+        {
+          col: 10,
+          message: NoUnneededTernaryMessage::DefaultAssignment,
+          hint: NoUnneededTernaryHint::UseOr,
+          fix: (NoUnneededTernaryFix::UseOr, "const a = x || (y ? 1 : 2);"),
+        }
+      ],
+    };
+  }
+}
@@ -24,6 +24,10 @@ impl LintRule for NoUnreachable {
     CODE
   }
 
+  fn needs_cfg(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
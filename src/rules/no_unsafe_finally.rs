@@ -47,6 +47,10 @@ impl LintRule for NoUnsafeFinally {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
     unreachable!();
   }
@@ -60,45 +64,10 @@ impl LintRule for NoUnsafeFinally {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the use of control flow statements within `finally` blocks.
-
-Use of the control flow statements (`return`, `throw`, `break` and `continue`) overrides the usage of any control flow statements that might have been used in the `try` or `catch` blocks, which is usually not the desired behaviour.
-
-### Invalid:
-```typescript
-let foo = function() {
-  try {
-    return 1;
-  } catch(err) {
-    return 2;
-  } finally {
-    return 3;
-  }
-};
-```
-```typescript
-let foo = function() {
-  try {
-    return 1;
-  } catch(err) {
-    return 2;
-  } finally {
-    throw new Error;
-  }
-};
-```
-### Valid:
-```typescript
-let foo = function() {
-  try {
-    return 1;
-  } catch(err) {
-    return 2;
-  } finally {
-    console.log("hola!");
-  }
-};
-```"#
+    rule_docs!(
+      "Disallows the use of control flow statements within `finally` blocks.",
+      "no-unsafe-finally"
+    )
   }
 }
 
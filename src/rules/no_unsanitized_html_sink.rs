@@ -0,0 +1,322 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{jsx_attr_name, member_name, object_prop, prop_name};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  AssignExpr, AssignOp, BinaryOp, CallExpr, Expr, ExprOrSpread, ExprOrSuper,
+  JSXAttr, JSXAttrValue, JSXExpr, Pat, PatOrExpr,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoUnsanitizedHtmlSink {
+  options: NoUnsanitizedHtmlSinkOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoUnsanitizedHtmlSinkOptions {
+  /// Functions whose return values are sanitized HTML, e.g.
+  /// `DOMPurify.sanitize`.
+  pub sanitizers: Vec<String>,
+}
+
+const CODE: &str = "no-unsanitized-html-sink";
+
+#[derive(Display)]
+enum NoUnsanitizedHtmlSinkMessage {
+  #[display(fmt = "Unsanitized HTML is written to `{}`", _0)]
+  Sink(String),
+}
+
+#[derive(Display)]
+enum NoUnsanitizedHtmlSinkHint {
+  #[display(
+    fmt = "Sanitize the HTML with a function listed in the `sanitizers` option, or set `textContent` for plain text"
+  )]
+  Sanitize,
+}
+
+impl LintRule for NoUnsanitizedHtmlSink {
+  fn new() -> Box<Self> {
+    Box::new(NoUnsanitizedHtmlSink {
+      options: NoUnsanitizedHtmlSinkOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoUnsanitizedHtmlSinkVisitor {
+      context,
+      sanitizers: &self.options.sanitizers,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "sanitizers": {
+          "description": "Functions whose return values are sanitized HTML, e.g. `DOMPurify.sanitize`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows writing non-literal HTML to `innerHTML`, `document.write()` and other HTML sinks",
+      "no-unsanitized-html-sink"
+    )
+  }
+}
+
+/// Returns the dotted path of a callee, e.g. `DOMPurify.sanitize`.
+fn callee_path(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Ident(ident) => Some(ident.sym.to_string()),
+    Expr::Member(member) => {
+      let obj = match &member.obj {
+        ExprOrSuper::Expr(obj) => callee_path(obj)?,
+        ExprOrSuper::Super(_) => return None,
+      };
+      Some(format!("{}.{}", obj, member_name(member)?))
+    }
+    _ => None,
+  }
+}
+
+struct NoUnsanitizedHtmlSinkVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  sanitizers: &'c [String],
+}
+
+impl<'c, 'view> NoUnsanitizedHtmlSinkVisitor<'c, 'view> {
+  /// Returns `true` for literals, strings built from literals only, and
+  /// values returned by sanitizers.
+  fn is_safe(&self, expr: &Expr) -> bool {
+    match expr {
+      Expr::Lit(_) => true,
+      Expr::Tpl(tpl) => tpl.exprs.iter().all(|expr| self.is_safe(expr)),
+      Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+        self.is_safe(&bin.left) && self.is_safe(&bin.right)
+      }
+      Expr::Cond(cond) => self.is_safe(&cond.cons) && self.is_safe(&cond.alt),
+      Expr::Paren(paren) => self.is_safe(&paren.expr),
+      Expr::TsAs(ts_as) => self.is_safe(&ts_as.expr),
+      Expr::TsNonNull(non_null) => self.is_safe(&non_null.expr),
+      Expr::Call(call) => match &call.callee {
+        ExprOrSuper::Expr(callee) => match callee_path(callee) {
+          Some(path) => self.sanitizers.contains(&path),
+          None => false,
+        },
+        ExprOrSuper::Super(_) => false,
+      },
+      _ => false,
+    }
+  }
+
+  fn check(&mut self, expr: &Expr, sink: &str) {
+    if !self.is_safe(expr) {
+      self.context.add_diagnostic_with_hint(
+        expr.span(),
+        CODE,
+        NoUnsanitizedHtmlSinkMessage::Sink(sink.to_string()),
+        NoUnsanitizedHtmlSinkHint::Sanitize,
+      );
+    }
+  }
+
+  fn check_args(&mut self, args: &[ExprOrSpread], sink: &str) {
+    for arg in args {
+      self.check(&arg.expr, sink);
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoUnsanitizedHtmlSinkVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        _ => None,
+      },
+    };
+    if let Some(Expr::Member(member)) = target {
+      let writes_html =
+        matches!(assign_expr.op, AssignOp::Assign | AssignOp::AddAssign);
+      match prop_name(member) {
+        Some(name @ "innerHTML") | Some(name @ "outerHTML") if writes_html => {
+          self.check(&assign_expr.right, name)
+        }
+        _ => {}
+      }
+    }
+    assign_expr.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Member(member) = &**callee {
+        let is_document = matches!(
+          &member.obj,
+          ExprOrSuper::Expr(obj)
+            if matches!(&**obj, Expr::Ident(ident) if ident.sym == *"document")
+        );
+        match prop_name(member) {
+          Some("write") if is_document => {
+            self.check_args(&call_expr.args, "document.write")
+          }
+          Some("writeln") if is_document => {
+            self.check_args(&call_expr.args, "document.writeln")
+          }
+          // The first argument is the position, e.g. `"beforeend"`
+          Some("insertAdjacentHTML") => {
+            if let Some(html) = call_expr.args.get(1) {
+              self.check(&html.expr, "insertAdjacentHTML")
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_jsx_attr(&mut self, jsx_attr: &JSXAttr, _: &dyn Node) {
+    if jsx_attr_name(&jsx_attr.name) == "dangerouslySetInnerHTML" {
+      if let Some(JSXAttrValue::JSXExprContainer(container)) = &jsx_attr.value {
+        if let JSXExpr::Expr(expr) = &container.expr {
+          // Objects built elsewhere can't be checked
+          if let Some(html) = object_prop(expr, "__html") {
+            self.check(html, "dangerouslySetInnerHTML");
+          }
+        }
+      }
+    }
+    jsx_attr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_unsanitized_html_sink_valid() {
+    assert_lint_ok! {
+      NoUnsanitizedHtmlSink,
+      r#"el.innerHTML = "<b>Hello</b>";"#,
+      r#"el.innerHTML = `<ul>${"<li>"}</ul>`;"#,
+      r#"el.outerHTML = open ? "<p>Open</p>" : "";"#,
+      r#"el.innerHTML += "<hr>";"#,
+      r#"el.textContent = name;"#,
+      r#"el.innerText = name;"#,
+      r#"document.write("<p>Loading</p>");"#,
+      r#"el.insertAdjacentHTML(position, "<hr>");"#,
+      r#"writer.write(chunk);"#,
+      { src: "<div dangerouslySetInnerHTML={{ __html: \"<b>Hi</b>\" }} />", filename: "foo.tsx" },
+      { src: "<div dangerouslySetInnerHTML={markup} />", filename: "foo.tsx" },
+    };
+
+    assert_lint_ok! {
+      NoUnsanitizedHtmlSink,
+      options: json!({ "sanitizers": ["DOMPurify.sanitize", "escape"] }),
+      r#"el.innerHTML = DOMPurify.sanitize(html);"#,
+      r#"el.innerHTML = "<p>" + escape(name) + "</p>";"#,
+      { src: "<div dangerouslySetInnerHTML={{ __html: DOMPurify.sanitize(html) }} />", filename: "foo.tsx" },
+    };
+  }
+
+  #[test]
+  fn no_unsanitized_html_sink_invalid() {
+    assert_lint_err! {
+      NoUnsanitizedHtmlSink,
+      r#"el.innerHTML = html;"#: [
+        {
+          col: 15,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "innerHTML"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+      r#"el["outerHTML"] = `<p>${name}</p>`;"#: [
+        {
+          col: 18,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "outerHTML"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+      r#"el.innerHTML += "<li>" + item + "</li>";"#: [
+        {
+          col: 16,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "innerHTML"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+      r#"document.write("<p>", text, "</p>");"#: [
+        {
+          col: 22,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "document.write"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+      r#"list.insertAdjacentHTML("beforeend", DOMPurify.sanitize(item));"#: [
+        {
+          col: 37,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "insertAdjacentHTML"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+      "<div dangerouslySetInnerHTML={{ __html: html }} />": {
+        filename: "foo.tsx",
+        errors: [
+          {
+            col: 40,
+            message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "dangerouslySetInnerHTML"),
+            hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+          }
+        ],
+      },
+    };
+
+    assert_lint_err! {
+      NoUnsanitizedHtmlSink,
+      options: json!({ "sanitizers": ["escape"] }),
+      r#"el.innerHTML = `<p>${escape(name)}</p><p>${bio}</p>`;"#: [
+        {
+          col: 15,
+          message: variant!(NoUnsanitizedHtmlSinkMessage, Sink, "innerHTML"),
+          hint: NoUnsanitizedHtmlSinkHint::Sanitize,
+        }
+      ],
+    };
+  }
+}
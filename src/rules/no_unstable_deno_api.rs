@@ -0,0 +1,426 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  BinaryOp, CondExpr, DoWhileStmt, Expr, ExprOrSuper, ForStmt, IfStmt, Lit,
+  MemberExpr, ObjectPatProp, Pat, PropName, UnaryExpr, UnaryOp, VarDeclarator,
+  WhileStmt,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoUnstableDenoApi {
+  options: NoUnstableDenoApiOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoUnstableDenoApiOptions {
+  /// Unstable features that may be used, e.g. `"kv"` for the APIs enabled by
+  /// `--unstable-kv`.
+  pub allow: Vec<String>,
+}
+
+const CODE: &str = "no-unstable-deno-api";
+
+/// Members of the `Deno` namespace and the `--unstable-*` flag enabling them.
+const UNSTABLE_DENO_APIS: &[(&str, &str)] = &[
+  ("dlopen", "ffi"),
+  ("UnsafeCallback", "ffi"),
+  ("UnsafeFnPointer", "ffi"),
+  ("UnsafePointer", "ffi"),
+  ("UnsafePointerView", "ffi"),
+  ("openKv", "kv"),
+  ("AtomicOperation", "kv"),
+  ("Kv", "kv"),
+  ("KvListIterator", "kv"),
+  ("KvU64", "kv"),
+  ("cron", "cron"),
+  ("createHttpClient", "http"),
+  ("listenDatagram", "net"),
+];
+
+/// Globals and the `--unstable-*` flag enabling them.
+const UNSTABLE_GLOBALS: &[(&str, &str)] = &[("Temporal", "temporal")];
+
+#[derive(Display)]
+enum NoUnstableDenoApiMessage {
+  #[display(fmt = "`{}` requires the `--unstable-{}` flag", _0, _1)]
+  Unstable(String, String),
+}
+
+#[derive(Display)]
+enum NoUnstableDenoApiHint {
+  #[display(
+    fmt = "Avoid depending on unstable APIs, or add \"{}\" to the `allow` option if it's intended",
+    _0
+  )]
+  AvoidOrAllow(String),
+}
+
+impl LintRule for NoUnstableDenoApi {
+  fn new() -> Box<Self> {
+    Box::new(NoUnstableDenoApi {
+      options: NoUnstableDenoApiOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let allow = self
+      .options
+      .allow
+      .iter()
+      .map(|flag| {
+        let flag = flag.trim_start_matches('-');
+        flag.strip_prefix("unstable-").unwrap_or(flag).to_string()
+      })
+      .collect();
+    let mut visitor = NoUnstableDenoApiVisitor { context, allow };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "Unstable features that may be used, e.g. `\"kv\"` for the APIs enabled by `--unstable-kv`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows the usage of Deno APIs that require an `--unstable-*` flag",
+      "no-unstable-deno-api"
+    )
+  }
+}
+
+struct NoUnstableDenoApiVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  /// Allowed flags, without the `--unstable-` prefix.
+  allow: Vec<String>,
+}
+
+/// Returns the statically known name of the accessed property.
+fn prop_name(member_expr: &MemberExpr) -> Option<&str> {
+  match &*member_expr.prop {
+    Expr::Ident(ident) if !member_expr.computed => Some(&ident.sym),
+    Expr::Lit(Lit::Str(s)) if member_expr.computed => Some(&s.value),
+    _ => None,
+  }
+}
+
+fn lookup(table: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+  table
+    .iter()
+    .find(|(api, _)| *api == name)
+    .map(|(_, flag)| *flag)
+}
+
+/// Returns the statically known name of the destructured property.
+fn pat_prop_name(prop: &ObjectPatProp) -> Option<&str> {
+  match prop {
+    ObjectPatProp::KeyValue(key_value) => match &key_value.key {
+      PropName::Ident(ident) => Some(&ident.sym),
+      PropName::Str(s) => Some(&s.value),
+      _ => None,
+    },
+    ObjectPatProp::Assign(assign) => Some(&assign.key.sym),
+    ObjectPatProp::Rest(_) => None,
+  }
+}
+
+impl<'c, 'view> NoUnstableDenoApiVisitor<'c, 'view> {
+  fn report(&mut self, span: Span, name: String, flag: &str) {
+    if !self.allow.iter().any(|allowed| allowed == flag) {
+      self.context.add_diagnostic_with_hint(
+        span,
+        CODE,
+        NoUnstableDenoApiMessage::Unstable(name, flag.to_string()),
+        NoUnstableDenoApiHint::AvoidOrAllow(flag.to_string()),
+      );
+    }
+  }
+
+  /// Returns `true` if the global with the given name is shadowed by a
+  /// declaration in the file.
+  fn is_shadowed(&self, name: &str) -> bool {
+    self.context.scope().ids_with_symbol(&name.into()).is_some()
+  }
+
+  /// Visits an expression used as a condition, where accessing an API only
+  /// checks whether it's available, e.g. `if (Deno.dlopen)` or
+  /// `Deno.openKv && !Deno.cron`.
+  fn visit_feature_check(&mut self, expr: &Expr, parent: &dyn Node) {
+    match expr {
+      Expr::Paren(paren) => self.visit_feature_check(&paren.expr, paren),
+      Expr::Unary(unary) if unary.op == UnaryOp::Bang => {
+        self.visit_feature_check(&unary.arg, unary)
+      }
+      Expr::Bin(bin)
+        if matches!(bin.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) =>
+      {
+        self.visit_feature_check(&bin.left, bin);
+        self.visit_feature_check(&bin.right, bin);
+      }
+      Expr::Member(member_expr) => member_expr.visit_children_with(self),
+      _ => expr.visit_with(parent, self),
+    }
+  }
+
+  /// Returns the unstable API accessed by the member expression, and the flag
+  /// enabling it.
+  fn unstable_api(
+    &self,
+    member_expr: &MemberExpr,
+  ) -> Option<(String, &'static str)> {
+    let obj = match &member_expr.obj {
+      ExprOrSuper::Expr(obj) => match &**obj {
+        Expr::Ident(obj) => &obj.sym,
+        _ => return None,
+      },
+      ExprOrSuper::Super(_) => return None,
+    };
+    let (name, flag) = if obj == "Deno" {
+      let prop = prop_name(member_expr)?;
+      let flag = lookup(UNSTABLE_DENO_APIS, prop)?;
+      (format!("Deno.{}", prop), flag)
+    } else {
+      let flag = lookup(UNSTABLE_GLOBALS, obj)?;
+      (obj.to_string(), flag)
+    };
+    if self.is_shadowed(obj) {
+      return None;
+    }
+    Some((name, flag))
+  }
+}
+
+impl<'c, 'view> Visit for NoUnstableDenoApiVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    if let Some((name, flag)) = self.unstable_api(member_expr) {
+      self.report(member_expr.span, name, flag);
+    }
+    member_expr.visit_children_with(self);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::TypeOf {
+      self.visit_feature_check(&unary_expr.arg, unary_expr);
+    } else {
+      unary_expr.visit_children_with(self);
+    }
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _: &dyn Node) {
+    self.visit_feature_check(&if_stmt.test, if_stmt);
+    if_stmt.cons.visit_with(if_stmt, self);
+    if_stmt.alt.visit_with(if_stmt, self);
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _: &dyn Node) {
+    self.visit_feature_check(&cond_expr.test, cond_expr);
+    cond_expr.cons.visit_with(cond_expr, self);
+    cond_expr.alt.visit_with(cond_expr, self);
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _: &dyn Node) {
+    self.visit_feature_check(&while_stmt.test, while_stmt);
+    while_stmt.body.visit_with(while_stmt, self);
+  }
+
+  fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt, _: &dyn Node) {
+    do_while.body.visit_with(do_while, self);
+    self.visit_feature_check(&do_while.test, do_while);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _: &dyn Node) {
+    for_stmt.init.visit_with(for_stmt, self);
+    if let Some(test) = &for_stmt.test {
+      self.visit_feature_check(test, for_stmt);
+    }
+    for_stmt.update.visit_with(for_stmt, self);
+    for_stmt.body.visit_with(for_stmt, self);
+  }
+
+  // `const { dlopen } = Deno;`
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    _: &dyn Node,
+  ) {
+    let is_deno = matches!(
+      var_declarator.init.as_deref(),
+      Some(Expr::Ident(ident)) if ident.sym == *"Deno"
+    );
+    if let Pat::Object(object_pat) = &var_declarator.name {
+      if is_deno && !self.is_shadowed("Deno") {
+        for prop in &object_pat.props {
+          let api = pat_prop_name(prop).and_then(|name| {
+            lookup(UNSTABLE_DENO_APIS, name).map(|flag| (name, flag))
+          });
+          if let Some((name, flag)) = api {
+            self.report(prop.span(), format!("Deno.{}", name), flag);
+          }
+        }
+      }
+    }
+    var_declarator.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_unstable_deno_api_valid() {
+    assert_lint_ok! {
+      NoUnstableDenoApi,
+      "Deno.readTextFile('a.txt');",
+      "Deno.foo.dlopen('lib.so', {});",
+      "foo.Deno.openKv();",
+      "const Deno = {}; Deno.openKv();",
+      "import { Temporal } from 'npm:@js-temporal/polyfill'; Temporal.Now.instant();",
+      "Deno[name]();",
+      "const kv: Deno.Kv = await open();",
+      "const { readTextFile, foo: dlopen } = Deno;",
+      "const { dlopen } = Deno.foo;",
+      "const Deno = {}; const { openKv } = Deno;",
+      "if (Deno.dlopen) { console.log('ffi is available'); }",
+      "if (!(Deno.openKv && Deno[\"cron\"])) { console.log('unavailable'); }",
+      "const hasKv = typeof Deno.openKv === 'function';",
+      "const kind = Deno.dlopen ? 'ffi' : 'wasm';",
+      "while (!Deno.cron) { await wait(); }",
+    };
+
+    assert_lint_ok! {
+      NoUnstableDenoApi,
+      options: json!({ "allow": ["kv", "--unstable-ffi", "unstable-temporal"] }),
+      "const kv = await Deno.openKv();",
+      "const lib = Deno.dlopen('lib.so', {});",
+      "Temporal.Now.instant();",
+    };
+  }
+
+  #[test]
+  fn no_unstable_deno_api_invalid() {
+    assert_lint_err! {
+      NoUnstableDenoApi,
+      "const lib = Deno.dlopen('lib.so', {});": [
+        {
+          col: 12,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.dlopen", "ffi"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "ffi"),
+        }
+      ],
+      "const kv = await Deno.openKv();": [
+        {
+          col: 17,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.openKv", "kv"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "kv"),
+        }
+      ],
+      "Deno[\"cron\"]('job', '* * * * *', () => {});": [
+        {
+          col: 0,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.cron", "cron"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "cron"),
+        }
+      ],
+      "const lib = Deno[\"dlopen\"]('lib.so', {});": [
+        {
+          col: 12,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.dlopen", "ffi"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "ffi"),
+        }
+      ],
+      "const { dlopen, readTextFile, 'openKv': open } = Deno;": [
+        {
+          col: 8,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.dlopen", "ffi"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "ffi"),
+        },
+        {
+          col: 30,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.openKv", "kv"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "kv"),
+        }
+      ],
+      "if (Deno.dlopen) { Deno.dlopen('lib.so', {}); }": [
+        {
+          col: 19,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.dlopen", "ffi"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "ffi"),
+        }
+      ],
+      "const kv = Deno.openKv ? await Deno.openKv() : null;": [
+        {
+          col: 31,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.openKv", "kv"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "kv"),
+        }
+      ],
+      "const now = Temporal.Now.instant();": [
+        {
+          col: 12,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Temporal", "temporal"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "temporal"),
+        }
+      ],
+      "const ptr = new Deno.UnsafePointerView(p);": [
+        {
+          col: 16,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.UnsafePointerView", "ffi"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "ffi"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoUnstableDenoApi,
+      options: json!({ "allow": ["ffi"] }),
+      "Deno.dlopen('lib.so', {}); await Deno.openKv();": [
+        {
+          col: 33,
+          message: variant!(NoUnstableDenoApiMessage, Unstable, "Deno.openKv", "kv"),
+          hint: variant!(NoUnstableDenoApiHint, AvoidOrAllow, "kv"),
+        }
+      ],
+    };
+  }
+}
@@ -29,6 +29,10 @@ impl LintRule for NoUnusedLabels {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
     unreachable!();
   }
@@ -43,44 +47,7 @@ impl LintRule for NoUnusedLabels {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows unused labels.
-
-A label that is declared but never used is most likely developer's mistake. If
-that label is meant to be used, then write a code so that it will be used.
-Otherwise, remove the label.
-
-### Invalid:
-```typescript
-LABEL1: while (true) {
-  console.log(42);
-}
-
-LABEL2: for (let i = 0; i < 5; i++) {
-  console.log(42);
-}
-
-LABEL3: for (const x of xs) {
-  console.log(x);
-}
-```
-
-### Valid:
-```typescript
-LABEL1: while (true) {
-  console.log(42);
-  break LABEL1;
-}
-
-LABEL2: for (let i = 0; i < 5; i++) {
-  console.log(42);
-  continue LABEL2;
-}
-
-for (const x of xs) {
-  console.log(x);
-}
-```
-"#
+    rule_docs!("Disallows unused labels.", "no-unused-labels")
   }
 }
 
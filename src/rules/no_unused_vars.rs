@@ -48,6 +48,14 @@ impl LintRule for NoUnusedVars {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn needs_cfg(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -0,0 +1,455 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::dictionary::split_words;
+use crate::swc_util::{member_name, object_prop, prop_name};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  AssignExpr, CallExpr, ClassMethod, ClassProp, Expr, ExprOrSuper, FnDecl,
+  KeyValueProp, Lit, MethodProp, Pat, PatOrExpr, PropName, VarDeclarator,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct NoWeakCrypto {
+  options: NoWeakCryptoOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NoWeakCryptoOptions {
+  /// Overrides of the severity of `md4`, `md5`, `sha1` and `Math.random`.
+  pub severity: HashMap<String, Severity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+  /// Not reported.
+  Off,
+  /// Reported as weak, for algorithms whose known attacks are impractical in
+  /// some uses.
+  Warning,
+  /// Reported as broken.
+  Error,
+}
+
+impl NoWeakCryptoOptions {
+  fn severity(&self, algorithm: &str) -> Severity {
+    match self.severity.get(algorithm) {
+      Some(severity) => *severity,
+      None => match algorithm {
+        "md4" | "md5" => Severity::Error,
+        _ => Severity::Warning,
+      },
+    }
+  }
+}
+
+const CODE: &str = "no-weak-crypto";
+
+#[derive(Display)]
+enum NoWeakCryptoMessage {
+  #[display(fmt = "`{}` is broken and must not be used", _0)]
+  Broken(String),
+  #[display(fmt = "`{}` is weak", _0)]
+  Weak(String),
+  #[display(fmt = "`Math.random()` is not cryptographically secure")]
+  Random,
+}
+
+#[derive(Display)]
+enum NoWeakCryptoHint {
+  #[display(fmt = "Use `SHA-256` or a stronger hash algorithm instead")]
+  Hash,
+  #[display(
+    fmt = "Use `crypto.randomUUID()` or `crypto.getRandomValues()` to generate tokens and ids"
+  )]
+  Random,
+}
+
+impl LintRule for NoWeakCrypto {
+  fn new() -> Box<Self> {
+    Box::new(NoWeakCrypto {
+      options: NoWeakCryptoOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = NoWeakCryptoVisitor {
+      context,
+      options: &self.options,
+      names: Vec::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let severity = |default: &str| {
+      json!({
+        "enum": ["off", "warning", "error"],
+        "default": default
+      })
+    };
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "severity": {
+          "description": "Overrides of the severity of `md4`, `md5`, `sha1` and `Math.random`.",
+          "type": "object",
+          "properties": {
+            "md4": severity("error"),
+            "md5": severity("error"),
+            "sha1": severity("warning"),
+            "Math.random": severity("warning")
+          }
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows weak hash algorithms, and `Math.random()` for generating tokens and ids",
+      "no-weak-crypto"
+    )
+  }
+}
+
+/// Returns the weak algorithm named by a hash algorithm argument, e.g. `sha1`
+/// for `"SHA-1"` and `{ name: "SHA-1" }`.
+fn weak_algorithm(expr: &Expr) -> Option<&'static str> {
+  let name = match expr {
+    Expr::Lit(Lit::Str(s)) => &s.value,
+    Expr::Object(_) => match object_prop(expr, "name")? {
+      Expr::Lit(Lit::Str(s)) => &s.value,
+      _ => return None,
+    },
+    _ => return None,
+  };
+  let normalized: String = name
+    .chars()
+    .filter(|c| !matches!(c, '-' | '_'))
+    .collect::<String>()
+    .to_lowercase();
+  match normalized.as_str() {
+    "md4" => Some("md4"),
+    "md5" => Some("md5"),
+    "sha1" => Some("sha1"),
+    _ => None,
+  }
+}
+
+/// Returns `true` for names of variables, properties and functions holding
+/// or generating values that must be unpredictable, e.g. `sessionId` and
+/// `generateToken`.
+fn is_secret_name(name: &str) -> bool {
+  split_words(name).iter().any(|(_, word)| {
+    matches!(
+      word.to_lowercase().as_str(),
+      "token"
+        | "secret"
+        | "key"
+        | "password"
+        | "salt"
+        | "nonce"
+        | "otp"
+        | "csrf"
+        | "session"
+        | "id"
+        | "uuid"
+        | "guid"
+    )
+  })
+}
+
+/// Returns `true` if the expression is `obj.name`, with `obj` being an
+/// identifier.
+fn is_member_of(expr: &Expr, obj: &str, name: &str) -> bool {
+  match expr {
+    Expr::Member(member) => {
+      let is_obj = match &member.obj {
+        ExprOrSuper::Expr(expr) => {
+          matches!(&**expr, Expr::Ident(ident) if ident.sym == *obj)
+        }
+        ExprOrSuper::Super(_) => false,
+      };
+      is_obj && member_name(member) == Some(name)
+    }
+    _ => false,
+  }
+}
+
+fn prop_name_str(key: &PropName) -> Option<&str> {
+  match key {
+    PropName::Ident(ident) => Some(&ident.sym),
+    PropName::Str(s) => Some(&s.value),
+    _ => None,
+  }
+}
+
+struct NoWeakCryptoVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c NoWeakCryptoOptions,
+  /// Names of the variables, properties and functions whose values are being
+  /// visited.
+  names: Vec<String>,
+}
+
+impl<'c, 'view> NoWeakCryptoVisitor<'c, 'view> {
+  fn check_algorithm(&mut self, arg: &Expr) {
+    let algorithm = match weak_algorithm(arg) {
+      Some(algorithm) => algorithm,
+      None => return,
+    };
+    let message = match self.options.severity(algorithm) {
+      Severity::Off => return,
+      Severity::Warning => NoWeakCryptoMessage::Weak(algorithm.to_string()),
+      Severity::Error => NoWeakCryptoMessage::Broken(algorithm.to_string()),
+    };
+    self.context.add_diagnostic_with_hint(
+      arg.span(),
+      CODE,
+      message,
+      NoWeakCryptoHint::Hash,
+    );
+  }
+
+  fn visit_named<N: VisitWith<Self>>(&mut self, name: Option<&str>, node: &N) {
+    match name {
+      Some(name) => {
+        self.names.push(name.to_string());
+        node.visit_children_with(self);
+        self.names.pop();
+      }
+      None => node.visit_children_with(self),
+    }
+  }
+}
+
+impl<'c, 'view> Visit for NoWeakCryptoVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      let first_arg = call_expr.args.first().map(|arg| &*arg.expr);
+      let (is_digest, is_create_hash) = match &**callee {
+        Expr::Member(member) => {
+          let is_subtle = match &member.obj {
+            ExprOrSuper::Expr(obj) => match &**obj {
+              Expr::Ident(ident) => ident.sym == *"subtle",
+              Expr::Member(obj) => member_name(obj) == Some("subtle"),
+              _ => false,
+            },
+            ExprOrSuper::Super(_) => false,
+          };
+          let name = member_name(member);
+          (
+            is_subtle && name == Some("digest"),
+            name == Some("createHash"),
+          )
+        }
+        Expr::Ident(ident) => (false, ident.sym == *"createHash"),
+        _ => (false, false),
+      };
+      if is_digest || is_create_hash {
+        if let Some(arg) = first_arg {
+          self.check_algorithm(arg);
+        }
+      } else if is_member_of(callee, "Math", "random")
+        && self.names.iter().any(|name| is_secret_name(name))
+        && self.options.severity("Math.random") != Severity::Off
+      {
+        self.context.add_diagnostic_with_hint(
+          call_expr.span,
+          CODE,
+          NoWeakCryptoMessage::Random,
+          NoWeakCryptoHint::Random,
+        );
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    let name = match &declarator.name {
+      Pat::Ident(ident) => Some(&*ident.id.sym),
+      _ => None,
+    };
+    self.visit_named(name, declarator);
+  }
+
+  fn visit_assign_expr(&mut self, assign_expr: &AssignExpr, _: &dyn Node) {
+    let target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) => Some(&**expr),
+      PatOrExpr::Pat(pat) => match &**pat {
+        Pat::Expr(expr) => Some(&**expr),
+        Pat::Ident(ident) => {
+          self.visit_named(Some(&ident.id.sym), assign_expr);
+          return;
+        }
+        _ => None,
+      },
+    };
+    let name = match target {
+      Some(Expr::Ident(ident)) => Some(&*ident.sym),
+      Some(Expr::Member(member)) => prop_name(member),
+      _ => None,
+    };
+    self.visit_named(name, assign_expr);
+  }
+
+  fn visit_key_value_prop(&mut self, prop: &KeyValueProp, _: &dyn Node) {
+    self.visit_named(prop_name_str(&prop.key), prop);
+  }
+
+  fn visit_method_prop(&mut self, prop: &MethodProp, _: &dyn Node) {
+    self.visit_named(prop_name_str(&prop.key), prop);
+  }
+
+  fn visit_class_method(&mut self, class_method: &ClassMethod, _: &dyn Node) {
+    self.visit_named(prop_name_str(&class_method.key), class_method);
+  }
+
+  fn visit_class_prop(&mut self, class_prop: &ClassProp, _: &dyn Node) {
+    let name = match &*class_prop.key {
+      Expr::Ident(ident) if !class_prop.computed => Some(&*ident.sym),
+      _ => None,
+    };
+    self.visit_named(name, class_prop);
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    self.visit_named(Some(&fn_decl.ident.sym), fn_decl);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn no_weak_crypto_valid() {
+    assert_lint_ok! {
+      NoWeakCrypto,
+      r#"await crypto.subtle.digest("SHA-256", data);"#,
+      r#"await crypto.subtle.digest({ name: "SHA-384" }, data);"#,
+      r#"createHash("sha256").update(data).digest("hex");"#,
+      r#"await crypto.subtle.digest(algorithm, data);"#,
+      r#"const hash = md5(data);"#,
+      r#"const delay = Math.random() * 1000;"#,
+      r#"function shuffle(items) { return items.sort(() => Math.random() - 0.5); }"#,
+      r#"const sessionId = crypto.randomUUID();"#,
+      r#"const valid = Math.random() > 0.5;"#,
+    };
+
+    assert_lint_ok! {
+      NoWeakCrypto,
+      options: json!({ "severity": { "sha1": "off", "Math.random": "off" } }),
+      r#"await crypto.subtle.digest("SHA-1", data);"#,
+      r#"const token = Math.random().toString(36);"#,
+    };
+  }
+
+  #[test]
+  fn no_weak_crypto_invalid() {
+    assert_lint_err! {
+      NoWeakCrypto,
+      r#"await crypto.subtle.digest("SHA-1", data);"#: [
+        {
+          col: 27,
+          message: variant!(NoWeakCryptoMessage, Weak, "sha1"),
+          hint: NoWeakCryptoHint::Hash,
+        }
+      ],
+      r#"await window.crypto.subtle.digest({ name: "sha1" }, data);"#: [
+        {
+          col: 34,
+          message: variant!(NoWeakCryptoMessage, Weak, "sha1"),
+          hint: NoWeakCryptoHint::Hash,
+        }
+      ],
+      r#"import { createHash } from "node:crypto"; createHash("md5");"#: [
+        {
+          col: 53,
+          message: variant!(NoWeakCryptoMessage, Broken, "md5"),
+          hint: NoWeakCryptoHint::Hash,
+        }
+      ],
+      r#"crypto.createHash("MD5").update(password).digest("hex");"#: [
+        {
+          col: 18,
+          message: variant!(NoWeakCryptoMessage, Broken, "md5"),
+          hint: NoWeakCryptoHint::Hash,
+        }
+      ],
+      r#"const token = Math.random().toString(36).slice(2);"#: [
+        {
+          col: 14,
+          message: NoWeakCryptoMessage::Random,
+          hint: NoWeakCryptoHint::Random,
+        }
+      ],
+      r#"function generateId() { return String(Math.random()); }"#: [
+        {
+          col: 38,
+          message: NoWeakCryptoMessage::Random,
+          hint: NoWeakCryptoHint::Random,
+        }
+      ],
+      r#"user.resetToken = `${Date.now()}${Math.random()}`;"#: [
+        {
+          col: 34,
+          message: NoWeakCryptoMessage::Random,
+          hint: NoWeakCryptoHint::Random,
+        }
+      ],
+      r#"const session = { csrf: Math.floor(Math.random() * 1e9) };"#: [
+        {
+          col: 35,
+          message: NoWeakCryptoMessage::Random,
+          hint: NoWeakCryptoHint::Random,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      NoWeakCrypto,
+      options: json!({ "severity": { "sha1": "error", "md5": "warning" } }),
+      r#"createHash("sha1"); createHash("md5");"#: [
+        {
+          col: 11,
+          message: variant!(NoWeakCryptoMessage, Broken, "sha1"),
+          hint: NoWeakCryptoHint::Hash,
+        },
+        {
+          col: 31,
+          message: variant!(NoWeakCryptoMessage, Weak, "md5"),
+          hint: NoWeakCryptoHint::Hash,
+        }
+      ],
+    };
+  }
+}
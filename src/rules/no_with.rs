@@ -21,6 +21,10 @@ impl LintRule for NoWith {
     CODE
   }
 
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
   fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
     unreachable!();
   }
@@ -34,20 +38,7 @@ impl LintRule for NoWith {
   }
 
   fn docs(&self) -> &'static str {
-    r#"Disallows the usage of `with` statements.
-
-The `with` statement is discouraged as it may be the source of confusing bugs
-and compatibility issues. For more details, see [with - JavaScript | MDN].
-
-[with - JavaScript | MDN]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/with
-
-### Invalid:
-```typescript
-with (someVar) {
-  console.log('foo');
-}
-```
-"#
+    rule_docs!("Disallows the usage of `with` statements.", "no-with")
   }
 }
 
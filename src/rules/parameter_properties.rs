@@ -0,0 +1,249 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{Accessibility, Pat, TsParamProp, TsParamPropParam};
+use swc_ecmascript::visit::{Node, VisitAll, VisitAllWith};
+
+pub struct ParameterProperties {
+  options: ParameterPropertiesOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParameterPropertiesOptions {
+  /// The modifiers of the parameter properties that are allowed.
+  pub allow: Vec<ParameterPropertyModifiers>,
+}
+
+/// The modifiers declaring a parameter property.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ParameterPropertyModifiers {
+  #[serde(rename = "readonly")]
+  Readonly,
+  #[serde(rename = "private")]
+  Private,
+  #[serde(rename = "protected")]
+  Protected,
+  #[serde(rename = "public")]
+  Public,
+  #[serde(rename = "private readonly")]
+  PrivateReadonly,
+  #[serde(rename = "protected readonly")]
+  ProtectedReadonly,
+  #[serde(rename = "public readonly")]
+  PublicReadonly,
+}
+
+impl ParameterPropertyModifiers {
+  fn of(param_prop: &TsParamProp) -> Self {
+    use ParameterPropertyModifiers::*;
+    match (param_prop.accessibility, param_prop.readonly) {
+      (None, _) => Readonly,
+      (Some(Accessibility::Private), false) => Private,
+      (Some(Accessibility::Protected), false) => Protected,
+      (Some(Accessibility::Public), false) => Public,
+      (Some(Accessibility::Private), true) => PrivateReadonly,
+      (Some(Accessibility::Protected), true) => ProtectedReadonly,
+      (Some(Accessibility::Public), true) => PublicReadonly,
+    }
+  }
+
+  fn as_str(self) -> &'static str {
+    use ParameterPropertyModifiers::*;
+    match self {
+      Readonly => "readonly",
+      Private => "private",
+      Protected => "protected",
+      Public => "public",
+      PrivateReadonly => "private readonly",
+      ProtectedReadonly => "protected readonly",
+      PublicReadonly => "public readonly",
+    }
+  }
+}
+
+const CODE: &str = "parameter-properties";
+
+#[derive(Display)]
+enum ParameterPropertiesMessage {
+  #[display(
+    fmt = "Parameter property `{}` declared with `{}` is not allowed",
+    _0,
+    _1
+  )]
+  NotAllowed(String, String),
+}
+
+#[derive(Display)]
+enum ParameterPropertiesHint {
+  #[display(
+    fmt = "Declare `{}` as a class property and assign it in the constructor",
+    _0
+  )]
+  UseClassProperty(String),
+}
+
+impl LintRule for ParameterProperties {
+  fn new() -> Box<Self> {
+    Box::new(ParameterProperties {
+      options: ParameterPropertiesOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = ParameterPropertiesVisitor {
+      context,
+      allow: &self.options.allow,
+    };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "The modifiers of the parameter properties that are allowed.",
+          "type": "array",
+          "items": {
+            "enum": [
+              "readonly",
+              "private",
+              "protected",
+              "public",
+              "private readonly",
+              "protected readonly",
+              "public readonly"
+            ]
+          },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows parameter properties in class constructors",
+      "parameter-properties"
+    )
+  }
+}
+
+struct ParameterPropertiesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [ParameterPropertyModifiers],
+}
+
+impl<'c, 'view> VisitAll for ParameterPropertiesVisitor<'c, 'view> {
+  fn visit_ts_param_prop(&mut self, param_prop: &TsParamProp, _: &dyn Node) {
+    let modifiers = ParameterPropertyModifiers::of(param_prop);
+    if self.allow.contains(&modifiers) {
+      return;
+    }
+    let name = match &param_prop.param {
+      TsParamPropParam::Ident(binding) => &binding.id.sym,
+      TsParamPropParam::Assign(assign_pat) => match &*assign_pat.left {
+        Pat::Ident(binding) => &binding.id.sym,
+        _ => return,
+      },
+    };
+    self.context.add_diagnostic_with_hint(
+      param_prop.span,
+      CODE,
+      ParameterPropertiesMessage::NotAllowed(
+        name.to_string(),
+        modifiers.as_str().to_string(),
+      ),
+      ParameterPropertiesHint::UseClassProperty(name.to_string()),
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn parameter_properties_valid() {
+    assert_lint_ok! {
+      ParameterProperties,
+      "class Foo { constructor(a: string) {} }",
+      "class Foo { private a: string; constructor(a: string) { this.a = a; } }",
+      "function foo(a: string) {}",
+    };
+
+    assert_lint_ok! {
+      ParameterProperties,
+      options: json!({ "allow": ["private readonly", "readonly"] }),
+      "class Foo { constructor(private readonly a: string, readonly b = 1) {} }",
+    };
+  }
+
+  #[test]
+  fn parameter_properties_invalid() {
+    assert_lint_err! {
+      ParameterProperties,
+      "class Foo { constructor(private a: string) {} }": [
+        {
+          col: 24,
+          message: variant!(ParameterPropertiesMessage, NotAllowed, "a", "private"),
+          hint: variant!(ParameterPropertiesHint, UseClassProperty, "a"),
+        }
+      ],
+      "class Foo { constructor(readonly a = 1, public readonly b: number) {} }": [
+        {
+          col: 24,
+          message: variant!(ParameterPropertiesMessage, NotAllowed, "a", "readonly"),
+          hint: variant!(ParameterPropertiesHint, UseClassProperty, "a"),
+        },
+        {
+          col: 40,
+          message: variant!(ParameterPropertiesMessage, NotAllowed, "b", "public readonly"),
+          hint: variant!(ParameterPropertiesHint, UseClassProperty, "b"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      ParameterProperties,
+      options: json!({ "allow": ["private readonly"] }),
+      "class Foo { constructor(private readonly a: string, public b: string) {} }": [
+        {
+          col: 52,
+          message: variant!(ParameterPropertiesMessage, NotAllowed, "b", "public"),
+          hint: variant!(ParameterPropertiesHint, UseClassProperty, "b"),
+        }
+      ],
+      "class Foo { constructor(private a: string) {} }": [
+        {
+          col: 24,
+          message: variant!(ParameterPropertiesMessage, NotAllowed, "a", "private"),
+          hint: variant!(ParameterPropertiesHint, UseClassProperty, "a"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,192 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::method_call;
+use derive_more::Display;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSuper, Lit, MemberExpr};
+use swc_ecmascript::visit::{noop_visit_type, Node, VisitAll, VisitAllWith};
+
+pub struct PreferArrayFind;
+
+const CODE: &str = "prefer-array-find";
+
+#[derive(Display)]
+enum PreferArrayFindMessage {
+  #[display(fmt = "Use `find` instead of `filter` to get the first match")]
+  UseFind,
+}
+
+#[derive(Display)]
+enum PreferArrayFindHint {
+  #[display(
+    fmt = "`find` stops at the first match instead of building a new array"
+  )]
+  UseFind,
+}
+
+#[derive(Display)]
+enum PreferArrayFindFix {
+  #[display(fmt = "Replace with `find`")]
+  UseFind,
+}
+
+impl LintRule for PreferArrayFind {
+  fn new() -> Box<Self> {
+    Box::new(PreferArrayFind)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferArrayFindVisitor { context };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires `find` over taking the first element of `filter`",
+      "prefer-array-find"
+    )
+  }
+}
+
+fn is_zero(expr: &Expr) -> bool {
+  matches!(expr, Expr::Lit(Lit::Num(num)) if num.value == 0.0)
+}
+
+/// Returns the span of `filter` in a call like `a.filter(b)`.
+fn filter_name(expr: &Expr) -> Option<Span> {
+  let call = match expr {
+    Expr::Call(call) => call,
+    _ => return None,
+  };
+  match method_call(call) {
+    Some((_, "filter", args)) if !args.is_empty() && args.len() <= 2 => {}
+    _ => return None,
+  }
+  match &call.callee {
+    ExprOrSuper::Expr(callee) => match &**callee {
+      Expr::Member(member) => Some(member.prop.span()),
+      _ => None,
+    },
+    ExprOrSuper::Super(_) => None,
+  }
+}
+
+struct PreferArrayFindVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> PreferArrayFindVisitor<'c, 'view> {
+  /// Reports taking the first element of `filter_call` in `span`.
+  fn report(&mut self, span: Span, filter_call: &Expr) {
+    let filter_name = match filter_name(filter_call) {
+      Some(filter_name) => filter_name,
+      None => return,
+    };
+    let filter_end = filter_call.span().hi;
+    let fix = self.context.create_fix(
+      PreferArrayFindFix::UseFind,
+      vec![
+        (filter_name, "find".to_string()),
+        (span.with_lo(filter_end), "".to_string()),
+      ],
+    );
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      PreferArrayFindMessage::UseFind,
+      Some(PreferArrayFindHint::UseFind.to_string()),
+      vec![fix],
+    );
+  }
+}
+
+impl<'c, 'view> VisitAll for PreferArrayFindVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  // `a.filter(b)[0]`
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    if !member_expr.computed || !is_zero(&member_expr.prop) {
+      return;
+    }
+    if let ExprOrSuper::Expr(obj) = &member_expr.obj {
+      self.report(member_expr.span, obj);
+    }
+  }
+
+  // `a.filter(b).at(0)` and `a.filter(b).shift()`
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    match method_call(call_expr) {
+      Some((obj, "at", [index])) if is_zero(&index.expr) => {
+        self.report(call_expr.span, obj)
+      }
+      Some((obj, "shift", [])) => self.report(call_expr.span, obj),
+      _ => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_array_find_valid() {
+    assert_lint_ok! {
+      PreferArrayFind,
+      "a.find(b);",
+      "a.filter(b);",
+      "a.filter(b)[1];",
+      "a.filter(b).at(-1);",
+      "a.filter(b).length;",
+      "a.filter()[0];",
+      "a.map(b)[0];",
+      "a.filter[0];",
+    };
+  }
+
+  #[test]
+  fn prefer_array_find_invalid() {
+    assert_lint_err! {
+      PreferArrayFind,
+      "a.filter(b)[0];": [
+        {
+          col: 0,
+          message: PreferArrayFindMessage::UseFind,
+          hint: PreferArrayFindHint::UseFind,
+          fix: (PreferArrayFindFix::UseFind, "a.find(b);"),
+        }
+      ],
+      "const c = a.b.filter((d) => d.e, f).at(0);": [
+        {
+          col: 10,
+          message: PreferArrayFindMessage::UseFind,
+          hint: PreferArrayFindHint::UseFind,
+          fix: (PreferArrayFindFix::UseFind, "const c = a.b.find((d) => d.e, f);"),
+        }
+      ],
+      "a.filter(isB).shift().c;": [
+        {
+          col: 0,
+          message: PreferArrayFindMessage::UseFind,
+          hint: PreferArrayFindHint::UseFind,
+          fix: (PreferArrayFindFix::UseFind, "a.find(isB).c;"),
+        }
+      ],
+    };
+  }
+}
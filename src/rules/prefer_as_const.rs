@@ -25,6 +25,12 @@ enum PreferAsConstHint {
   AddAsConst,
 }
 
+#[derive(Display)]
+enum PreferAsConstFix {
+  #[display(fmt = "Use a `const` assertion")]
+  UseConstAssertion,
+}
+
 pub struct PreferAsConst;
 
 impl LintRule for PreferAsConst {
@@ -40,6 +46,10 @@ impl LintRule for PreferAsConst {
     CODE
   }
 
+  fn fixable(&self) -> bool {
+    true
+  }
+
   fn lint_program<'view>(
     &self,
     context: &mut Context<'view>,
@@ -63,39 +73,55 @@ impl<'c, 'view> PreferAsConstVisitor<'c, 'view> {
     Self { context }
   }
 
-  fn add_diagnostic_helper(&mut self, span: Span) {
-    self.context.add_diagnostic_with_hint(
+  /// Reports the literal type at `span`, replacing the text of `changes`.
+  fn add_diagnostic_helper(
+    &mut self,
+    span: Span,
+    changes: Vec<(Span, String)>,
+  ) {
+    let fix = self
+      .context
+      .create_fix(PreferAsConstFix::UseConstAssertion, changes);
+    self.context.add_diagnostic_with_fixes(
       span,
       CODE,
       PreferAsConstMessage::ExpectedConstAssertion,
-      PreferAsConstHint::AddAsConst,
+      Some(PreferAsConstHint::AddAsConst.to_string()),
+      vec![fix],
     );
   }
 
-  fn compare(&mut self, type_ann: &TsType, expr: &Expr, span: Span) {
+  /// Returns `true` if the type is the literal type of the expression.
+  fn is_same_literal(&self, type_ann: &TsType, expr: &Expr) -> bool {
     if let TsType::TsLitType(lit_type) = &*type_ann {
       if let Expr::Lit(expr_lit) = &*expr {
-        match (expr_lit, &lit_type.lit) {
+        return match (expr_lit, &lit_type.lit) {
           (Lit::Str(value_literal), TsLit::Str(type_literal)) => {
-            if value_literal.value == type_literal.value {
-              self.add_diagnostic_helper(span)
-            }
+            value_literal.value == type_literal.value
           }
           (Lit::Num(value_literal), TsLit::Number(type_literal)) => {
-            if (value_literal.value - type_literal.value).abs() < f64::EPSILON {
-              self.add_diagnostic_helper(span)
-            }
+            (value_literal.value - type_literal.value).abs() < f64::EPSILON
           }
-          _ => return,
-        }
+          _ => false,
+        };
       }
     }
+    false
+  }
+
+  /// Checks `expr as 'a'` and `<'a'>expr`, which both become const
+  /// assertions by replacing the type with `const`.
+  fn check_assertion(&mut self, type_ann: &TsType, expr: &Expr) {
+    if self.is_same_literal(type_ann, expr) {
+      let span = type_ann.span();
+      self.add_diagnostic_helper(span, vec![(span, "const".to_string())]);
+    }
   }
 }
 
 impl<'c, 'view> VisitAll for PreferAsConstVisitor<'c, 'view> {
   fn visit_ts_as_expr(&mut self, as_expr: &TsAsExpr, _: &dyn Node) {
-    self.compare(&as_expr.type_ann, &as_expr.expr, as_expr.type_ann.span());
+    self.check_assertion(&as_expr.type_ann, &as_expr.expr);
   }
 
   fn visit_ts_type_assertion(
@@ -103,11 +129,7 @@ impl<'c, 'view> VisitAll for PreferAsConstVisitor<'c, 'view> {
     type_assertion: &TsTypeAssertion,
     _: &dyn Node,
   ) {
-    self.compare(
-      &type_assertion.type_ann,
-      &type_assertion.expr,
-      type_assertion.type_ann.span(),
-    );
+    self.check_assertion(&type_assertion.type_ann, &type_assertion.expr);
   }
 
   fn visit_var_decl(&mut self, var_decl: &VarDecl, _: &dyn Node) {
@@ -117,8 +139,18 @@ impl<'c, 'view> VisitAll for PreferAsConstVisitor<'c, 'view> {
         | Pat::Object(ObjectPat { type_ann, .. })
         | Pat::Ident(BindingIdent { type_ann, .. }) = &decl.name
         {
-          if let Some(TsTypeAnn { type_ann, .. }) = &type_ann {
-            self.compare(type_ann, &init, type_ann.span());
+          if let Some(TsTypeAnn { span, type_ann }) = &type_ann {
+            if self.is_same_literal(type_ann, init) {
+              // `let a: 'a' = 'a';` becomes `let a = 'a' as const;`
+              let end = init.span().hi();
+              self.add_diagnostic_helper(
+                type_ann.span(),
+                vec![
+                  (*span, "".to_string()),
+                  (init.span().with_lo(end), " as const".to_string()),
+                ],
+              );
+            }
           }
         }
       }
@@ -172,6 +204,7 @@ mod tests {
           col: 26,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = { bar: 'baz' as const };"),
         }
       ],
       "let foo = { bar: 1 as 1 };": [
@@ -179,6 +212,7 @@ mod tests {
           col: 22,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = { bar: 1 as const };"),
         }
       ],
       "let [x]: 'bar' = 'bar';": [
@@ -186,6 +220,7 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let [x] = 'bar' as const;"),
         }
       ],
       "let {x}: 'bar' = 'bar';": [
@@ -193,6 +228,7 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let {x} = 'bar' as const;"),
         }
       ],
       "let foo: 'bar' = 'bar';": [
@@ -200,6 +236,7 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 'bar' as const;"),
         }
       ],
       "let foo: 2 = 2;": [
@@ -207,6 +244,7 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 2 as const;"),
         }
       ],
       "let foo: 'bar' = 'bar' as 'bar';": [
@@ -214,6 +252,7 @@ mod tests {
           col: 26,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo: 'bar' = 'bar' as const;"),
         }
       ],
       "let foo = <'bar'>'bar';": [
@@ -221,6 +260,7 @@ mod tests {
           col: 11,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = <const>'bar';"),
         }
       ],
       "let foo = <4>4;": [
@@ -228,6 +268,7 @@ mod tests {
           col: 11,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = <const>4;"),
         }
       ],
       "let foo = 'bar' as 'bar';": [
@@ -235,6 +276,7 @@ mod tests {
           col: 19,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 'bar' as const;"),
         }
       ],
       "let foo = 5 as 5;": [
@@ -242,6 +284,7 @@ mod tests {
           col: 15,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 5 as const;"),
         }
       ],
       "let foo: 1.23456 = 1.23456;": [
@@ -249,6 +292,7 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 1.23456 as const;"),
         }
       ],
       "let foo: 2 = 2, bar: 3 = 3;": [
@@ -256,11 +300,13 @@ mod tests {
           col: 9,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = 2 as const, bar: 3 = 3;"),
         },
         {
           col: 21,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo: 2 = 2, bar = 3 as const;"),
         }
       ],
 
@@ -270,6 +316,7 @@ mod tests {
           col: 25,
           message: PreferAsConstMessage::ExpectedConstAssertion,
           hint: PreferAsConstHint::AddAsConst,
+          fix: (PreferAsConstFix::UseConstAssertion, "let foo = () => { let x = 'x' as const; };"),
         }
       ],
     };
@@ -0,0 +1,244 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::member_name;
+use derive_more::Display;
+use swc_common::{EqIgnoreSpan, Spanned};
+use swc_ecmascript::ast::{
+  BinaryOp, Expr, ExprOrSuper, Lit, MemberExpr, Pat, PatOrExpr, UnaryExpr,
+  UnaryOp, UpdateExpr,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferAt;
+
+const CODE: &str = "prefer-at";
+
+#[derive(Display)]
+enum PreferAtMessage {
+  #[display(fmt = "Use `at({})` to access an element from the end", _0)]
+  UseAt(String),
+}
+
+#[derive(Display)]
+enum PreferAtHint {
+  #[display(fmt = "`at` accepts negative indexes, counting from the end")]
+  UseAt,
+}
+
+#[derive(Display)]
+enum PreferAtFix {
+  #[display(fmt = "Replace with `at`")]
+  UseAt,
+}
+
+impl LintRule for PreferAt {
+  fn new() -> Box<Self> {
+    Box::new(PreferAt)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferAtVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires `at` over indexing with `length` to access elements from the end",
+      "prefer-at"
+    )
+  }
+}
+
+/// Returns `true` if evaluating the expression twice has no side effects,
+/// e.g. `a`, `this.a` or `a[b].c`, ignoring getters.
+fn is_pure(expr: &Expr) -> bool {
+  match expr {
+    Expr::Ident(_) | Expr::This(_) => true,
+    Expr::Paren(paren) => is_pure(&paren.expr),
+    Expr::Member(member) => {
+      let is_pure_obj = match &member.obj {
+        ExprOrSuper::Expr(obj) => is_pure(obj),
+        ExprOrSuper::Super(_) => true,
+      };
+      let is_pure_prop = !member.computed
+        || matches!(&*member.prop, Expr::Lit(_))
+        || is_pure(&member.prop);
+      is_pure_obj && is_pure_prop
+    }
+    _ => false,
+  }
+}
+
+/// Returns the offset from the end in `obj[obj.length - offset]`.
+fn offset_from_end(obj: &Expr, index: &Expr) -> Option<f64> {
+  let bin = match index {
+    Expr::Bin(bin) if bin.op == BinaryOp::Sub => bin,
+    _ => return None,
+  };
+  let is_length = match &*bin.left {
+    Expr::Member(member) if member_name(member) == Some("length") => {
+      matches!(&member.obj, ExprOrSuper::Expr(length_obj) if (**length_obj).eq_ignore_span(obj))
+    }
+    _ => false,
+  };
+  match &*bin.right {
+    Expr::Lit(Lit::Num(num))
+      if is_length && num.value >= 1.0 && num.value.fract() == 0.0 =>
+    {
+      Some(num.value)
+    }
+    _ => None,
+  }
+}
+
+struct PreferAtVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> PreferAtVisitor<'c, 'view> {
+  fn check_member(&mut self, member_expr: &MemberExpr) {
+    let obj = match &member_expr.obj {
+      ExprOrSuper::Expr(obj) => obj,
+      ExprOrSuper::Super(_) => return,
+    };
+    if !member_expr.computed || !is_pure(obj) {
+      return;
+    }
+    let offset = match offset_from_end(obj, &member_expr.prop) {
+      Some(offset) => offset,
+      None => return,
+    };
+    let index = format!("-{}", offset);
+    let obj_text =
+      match self.context.source_map().span_to_snippet(obj.span()).ok() {
+        Some(obj_text) => obj_text,
+        None => return,
+      };
+    let fix = self.context.create_fix(
+      PreferAtFix::UseAt,
+      vec![(member_expr.span, format!("{}.at({})", obj_text, index))],
+    );
+    self.context.add_diagnostic_with_fixes(
+      member_expr.span,
+      CODE,
+      PreferAtMessage::UseAt(index),
+      Some(PreferAtHint::UseAt.to_string()),
+      vec![fix],
+    );
+  }
+
+  /// Visits an assignment target, which can't be replaced by a call.
+  fn visit_target(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Member(member) => member.visit_children_with(self),
+      expr => expr.visit_with(&DUMMY_NODE, self),
+    }
+  }
+}
+
+impl<'c, 'view> Visit for PreferAtVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    self.check_member(member_expr);
+    member_expr.visit_children_with(self);
+  }
+
+  fn visit_pat_or_expr(&mut self, pat_or_expr: &PatOrExpr, _: &dyn Node) {
+    match pat_or_expr {
+      PatOrExpr::Expr(expr) => self.visit_target(expr),
+      PatOrExpr::Pat(pat) => pat.visit_with(&DUMMY_NODE, self),
+    }
+  }
+
+  fn visit_pat(&mut self, pat: &Pat, _: &dyn Node) {
+    match pat {
+      Pat::Expr(expr) => self.visit_target(expr),
+      pat => pat.visit_children_with(self),
+    }
+  }
+
+  fn visit_update_expr(&mut self, update_expr: &UpdateExpr, _: &dyn Node) {
+    self.visit_target(&update_expr.arg);
+  }
+
+  fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr, _: &dyn Node) {
+    if unary_expr.op == UnaryOp::Delete {
+      self.visit_target(&unary_expr.arg);
+    } else {
+      unary_expr.visit_children_with(self);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_at_valid() {
+    assert_lint_ok! {
+      PreferAt,
+      "a.at(-1);",
+      "a[a.length];",
+      "a[a.length + 1];",
+      "a[b.length - 1];",
+      "a[a.length - b];",
+      "a[a.length - 1.5];",
+      "a.b[a.length - 1];",
+      "foo()[foo().length - 1];",
+      "a[b++][a[b++].length - 1];",
+      "a[a.length - 1] = b;",
+      "[a[a.length - 1]] = b;",
+      "a[a.length - 1]++;",
+      "delete a[a.length - 1];",
+      "for (a[a.length - 1] of b) {}",
+    };
+  }
+
+  #[test]
+  fn prefer_at_invalid() {
+    assert_lint_err! {
+      PreferAt,
+      "a[a.length - 1];": [
+        {
+          col: 0,
+          message: variant!(PreferAtMessage, UseAt, "-1"),
+          hint: PreferAtHint::UseAt,
+          fix: (PreferAtFix::UseAt, "a.at(-1);"),
+        }
+      ],
+      "const b = this.a.c[this.a.c.length - 2].d;": [
+        {
+          col: 10,
+          message: variant!(PreferAtMessage, UseAt, "-2"),
+          hint: PreferAtHint::UseAt,
+          fix: (PreferAtFix::UseAt, "const b = this.a.c.at(-2).d;"),
+        }
+      ],
+      "a[b][a[b].length - 1] = c[c.length - 1];": [
+        {
+          col: 24,
+          message: variant!(PreferAtMessage, UseAt, "-1"),
+          hint: PreferAtHint::UseAt,
+          fix: (PreferAtFix::UseAt, "a[b][a[b].length - 1] = c.at(-1);"),
+        }
+      ],
+    };
+  }
+}
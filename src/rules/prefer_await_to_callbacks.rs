@@ -0,0 +1,275 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::member_name;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Spanned;
+use swc_ecmascript::ast::{
+  ArrowExpr, CallExpr, Constructor, Expr, ExprOrSuper, Function, Pat,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferAwaitToCallbacks {
+  options: PreferAwaitToCallbacksOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreferAwaitToCallbacksOptions {
+  /// The functions and methods whose callbacks are allowed, e.g. the ones
+  /// registering event listeners.
+  pub allow: Vec<String>,
+}
+
+impl Default for PreferAwaitToCallbacksOptions {
+  fn default() -> Self {
+    Self {
+      allow: [
+        "on",
+        "once",
+        "off",
+        "addListener",
+        "removeListener",
+        "prependListener",
+        "prependOnceListener",
+        "addEventListener",
+        "removeEventListener",
+      ]
+      .iter()
+      .map(|name| name.to_string())
+      .collect(),
+    }
+  }
+}
+
+const CODE: &str = "prefer-await-to-callbacks";
+
+/// The names of the first parameter of a Node-style callback.
+const ERROR_PARAMS: &[&str] = &["err", "error"];
+
+/// The promise methods, whose rejection handlers take an error too.
+const PROMISE_METHODS: &[&str] = &["then", "catch"];
+
+#[derive(Display)]
+enum PreferAwaitToCallbacksMessage {
+  #[display(fmt = "A Node-style callback is used in an async function")]
+  Callback,
+}
+
+#[derive(Display)]
+enum PreferAwaitToCallbacksHint {
+  #[display(fmt = "Use the promise-based version of the API and `await` it")]
+  UseAwait,
+}
+
+impl LintRule for PreferAwaitToCallbacks {
+  fn new() -> Box<Self> {
+    Box::new(PreferAwaitToCallbacks {
+      options: PreferAwaitToCallbacksOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferAwaitToCallbacksVisitor {
+      context,
+      allow: &self.options.allow,
+      in_async_fn: false,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "allow": {
+          "description": "The functions and methods whose callbacks are allowed, e.g. the ones registering event listeners.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": PreferAwaitToCallbacksOptions::default().allow
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows passing Node-style callbacks in async functions",
+      "prefer-await-to-callbacks"
+    )
+  }
+}
+
+/// Returns `true` if the first parameter is named like an error, e.g.
+/// `(err, data) => {}`.
+fn is_error_first<'a>(mut params: impl Iterator<Item = &'a Pat>) -> bool {
+  matches!(
+    params.next(),
+    Some(Pat::Ident(binding)) if ERROR_PARAMS.contains(&&*binding.id.sym)
+  )
+}
+
+fn is_node_style_callback(expr: &Expr) -> bool {
+  match expr {
+    Expr::Arrow(arrow) => is_error_first(arrow.params.iter()),
+    Expr::Fn(fn_expr) => {
+      is_error_first(fn_expr.function.params.iter().map(|param| &param.pat))
+    }
+    Expr::Paren(paren) => is_node_style_callback(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns the name of the called function or method, e.g. `readFile` for
+/// both `readFile()` and `fs.readFile()`.
+fn callee_name(call_expr: &CallExpr) -> Option<&str> {
+  match &call_expr.callee {
+    ExprOrSuper::Expr(callee) => match &**callee {
+      Expr::Ident(ident) => Some(&ident.sym),
+      Expr::Member(member) => member_name(member),
+      _ => None,
+    },
+    ExprOrSuper::Super(_) => None,
+  }
+}
+
+struct PreferAwaitToCallbacksVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  allow: &'c [String],
+  /// Whether the closest function is an async one.
+  in_async_fn: bool,
+}
+
+impl<'c, 'view> PreferAwaitToCallbacksVisitor<'c, 'view> {
+  fn visit_fn_body<N: VisitWith<Self>>(&mut self, is_async: bool, node: &N) {
+    let prev = self.in_async_fn;
+    self.in_async_fn = is_async;
+    node.visit_children_with(self);
+    self.in_async_fn = prev;
+  }
+}
+
+impl<'c, 'view> Visit for PreferAwaitToCallbacksVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.visit_fn_body(function.is_async, function);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.visit_fn_body(arrow_expr.is_async, arrow_expr);
+  }
+
+  fn visit_constructor(&mut self, constructor: &Constructor, _: &dyn Node) {
+    self.visit_fn_body(false, constructor);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    let is_allowed = matches!(
+      callee_name(call_expr),
+      Some(name) if PROMISE_METHODS.contains(&name)
+        || self.allow.iter().any(|allowed| allowed == name)
+    );
+    if self.in_async_fn && !is_allowed {
+      for arg in &call_expr.args {
+        if is_node_style_callback(&arg.expr) {
+          self.context.add_diagnostic_with_hint(
+            arg.expr.span(),
+            CODE,
+            PreferAwaitToCallbacksMessage::Callback,
+            PreferAwaitToCallbacksHint::UseAwait,
+          );
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn prefer_await_to_callbacks_valid() {
+    assert_lint_ok! {
+      PreferAwaitToCallbacks,
+      "function foo() { fs.readFile(path, (err, data) => {}); }",
+      "async function foo() { const data = await fs.promises.readFile(path); }",
+      "async function foo() { a.map((value, index) => value); }",
+      "async function foo() { server.on('error', (err) => {}); }",
+      "async function foo() { emitter.once('end', function (error) {}); }",
+      "async function foo() { window.addEventListener('error', (error) => {}); }",
+      "async function foo() { bar(function () { fs.readFile(path, (err, data) => {}); }); }",
+      "async function foo() { a.then(() => {}, (err) => {}); }",
+      "async function foo() { a.catch((err) => {}); }",
+    };
+
+    assert_lint_ok! {
+      PreferAwaitToCallbacks,
+      options: json!({ "allow": ["subscribe"] }),
+      "async function foo() { stream.subscribe((err, value) => {}); }",
+    };
+  }
+
+  #[test]
+  fn prefer_await_to_callbacks_invalid() {
+    assert_lint_err! {
+      PreferAwaitToCallbacks,
+      "async function foo() { fs.readFile(path, (err, data) => {}); }": [
+        {
+          col: 41,
+          message: PreferAwaitToCallbacksMessage::Callback,
+          hint: PreferAwaitToCallbacksHint::UseAwait,
+        }
+      ],
+      "const foo = async () => { exec('ls', function (error, stdout) {}); };": [
+        {
+          col: 37,
+          message: PreferAwaitToCallbacksMessage::Callback,
+          hint: PreferAwaitToCallbacksHint::UseAwait,
+        }
+      ],
+      "class A { async foo() { db.query(sql, ((err) => {})); } }": [
+        {
+          col: 38,
+          message: PreferAwaitToCallbacksMessage::Callback,
+          hint: PreferAwaitToCallbacksHint::UseAwait,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      PreferAwaitToCallbacks,
+      options: json!({ "allow": ["subscribe"] }),
+      "async function foo() { server.on('error', (err) => {}); }": [
+        {
+          col: 42,
+          message: PreferAwaitToCallbacksMessage::Callback,
+          hint: PreferAwaitToCallbacksHint::UseAwait,
+        }
+      ],
+    };
+  }
+}
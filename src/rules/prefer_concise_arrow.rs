@@ -0,0 +1,315 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{ArrowExpr, BlockStmtOrExpr, Expr, Stmt};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferConciseArrow {
+  options: PreferConciseArrowOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreferConciseArrowOptions {
+  /// The style of arrow function bodies to enforce.
+  pub style: ArrowBodyStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArrowBodyStyle {
+  /// Bodies only returning a value must be expressions, e.g. `() => 1`.
+  Concise,
+  /// Bodies must always be blocks, e.g. `() => { return 1; }`.
+  Block,
+}
+
+impl Default for PreferConciseArrowOptions {
+  fn default() -> Self {
+    Self {
+      style: ArrowBodyStyle::Concise,
+    }
+  }
+}
+
+const CODE: &str = "prefer-concise-arrow";
+
+#[derive(Display)]
+enum PreferConciseArrowMessage {
+  #[display(fmt = "Arrow function body only returns a value")]
+  OnlyReturns,
+  #[display(fmt = "Arrow function body should be a block")]
+  ExpectedBlock,
+}
+
+#[derive(Display)]
+enum PreferConciseArrowHint {
+  #[display(fmt = "Use the returned value as the body, e.g. `() => value`")]
+  UseConcise,
+  #[display(
+    fmt = "Use a block body with a `return` statement, e.g. `() => {{ return value; }}`"
+  )]
+  UseBlock,
+}
+
+#[derive(Display)]
+enum PreferConciseArrowFix {
+  #[display(fmt = "Use a concise body")]
+  UseConcise,
+  #[display(fmt = "Use a block body")]
+  UseBlock,
+}
+
+impl LintRule for PreferConciseArrow {
+  fn new() -> Box<Self> {
+    Box::new(PreferConciseArrow {
+      options: PreferConciseArrowOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferConciseArrowVisitor {
+      context,
+      style: self.options.style,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "style": {
+          "description": "The style of arrow function bodies to enforce.",
+          "enum": ["concise", "block"],
+          "default": "concise"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces a consistent style of arrow function bodies",
+      "prefer-concise-arrow"
+    )
+  }
+}
+
+struct PreferConciseArrowVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  style: ArrowBodyStyle,
+}
+
+impl<'c, 'view> PreferConciseArrowVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  fn has_comments(&self, span: Span) -> bool {
+    self.context.all_comments().any(|comment| {
+      span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+    })
+  }
+
+  fn check_block(&mut self, span: Span, stmts: &[Stmt]) {
+    let value = match stmts {
+      [Stmt::Return(return_stmt)] => match &return_stmt.arg {
+        Some(value) => value,
+        None => return,
+      },
+      _ => return,
+    };
+
+    // Comments in the block would be lost
+    let fixes = match self.snippet(value.span()) {
+      Some(text) if !self.has_comments(span) => {
+        // `() => { return {}; }` must become `() => ({})`
+        let text = match &**value {
+          Expr::Object(_) | Expr::Seq(_) => format!("({})", text),
+          _ => text,
+        };
+        vec![self
+          .context
+          .create_fix(PreferConciseArrowFix::UseConcise, vec![(span, text)])]
+      }
+      _ => vec![],
+    };
+
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      PreferConciseArrowMessage::OnlyReturns,
+      Some(PreferConciseArrowHint::UseConcise.to_string()),
+      fixes,
+    );
+  }
+
+  fn check_expr(&mut self, expr: &Expr) {
+    let span = expr.span();
+    let fixes = match self.snippet(span) {
+      Some(text) => vec![self.context.create_fix(
+        PreferConciseArrowFix::UseBlock,
+        vec![(span, format!("{{ return {}; }}", text))],
+      )],
+      None => vec![],
+    };
+
+    self.context.add_diagnostic_with_fixes(
+      span,
+      CODE,
+      PreferConciseArrowMessage::ExpectedBlock,
+      Some(PreferConciseArrowHint::UseBlock.to_string()),
+      fixes,
+    );
+  }
+}
+
+impl<'c, 'view> Visit for PreferConciseArrowVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    match (&arrow_expr.body, self.style) {
+      (BlockStmtOrExpr::BlockStmt(block), ArrowBodyStyle::Concise) => {
+        self.check_block(block.span, &block.stmts);
+      }
+      (BlockStmtOrExpr::Expr(expr), ArrowBodyStyle::Block) => {
+        self.check_expr(expr);
+      }
+      _ => {}
+    }
+    arrow_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn prefer_concise_arrow_valid() {
+    assert_lint_ok! {
+      PreferConciseArrow,
+      "const a = () => 1;",
+      "const a = () => {};",
+      "const a = () => { return; };",
+      "const a = () => { foo(); return 1; };",
+      "const a = () => { if (foo) { return 1; } };",
+      "const a = function () { return 1; };",
+    };
+
+    assert_lint_ok! {
+      PreferConciseArrow,
+      options: json!({ "style": "block" }),
+      "const a = () => { return 1; };",
+      "const a = () => {};",
+    };
+  }
+
+  #[test]
+  fn prefer_concise_arrow_invalid() {
+    assert_lint_err! {
+      PreferConciseArrow,
+      "const a = () => { return 1; };": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+          fix: (PreferConciseArrowFix::UseConcise, "const a = () => 1;"),
+        }
+      ],
+      "const a = async (b) => {\n  return await b;\n};": [
+        {
+          col: 23,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+          fix: (PreferConciseArrowFix::UseConcise, "const a = async (b) => await b;"),
+        }
+      ],
+      "const a = () => { return { b: 1 }; };": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+          fix: (PreferConciseArrowFix::UseConcise, "const a = () => ({ b: 1 });"),
+        }
+      ],
+      "const a = () => { return b, c; };": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+          fix: (PreferConciseArrowFix::UseConcise, "const a = () => (b, c);"),
+        }
+      ],
+      "const a = () => () => { return 1; };": [
+        {
+          col: 22,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+          fix: (PreferConciseArrowFix::UseConcise, "const a = () => () => 1;"),
+        }
+      ],
+      // comments would be lost
+      "const a = () => {\n  // one\n  return 1;\n};": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::OnlyReturns,
+          hint: PreferConciseArrowHint::UseConcise,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      PreferConciseArrow,
+      options: json!({ "style": "block" }),
+      "const a = () => 1;": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::ExpectedBlock,
+          hint: PreferConciseArrowHint::UseBlock,
+          fix: (PreferConciseArrowFix::UseBlock, "const a = () => { return 1; };"),
+        }
+      ],
+      "const a = () => ({ b: 1 });": [
+        {
+          col: 16,
+          message: PreferConciseArrowMessage::ExpectedBlock,
+          hint: PreferConciseArrowHint::UseBlock,
+          fix: (PreferConciseArrowFix::UseBlock, "const a = () => { return ({ b: 1 }); };"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,323 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::scopes::Scope;
+use crate::swc_util::deno_test_fn;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSpread, ExprOrSuper, Ident, Lit, NewExpr,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferFakeTime {
+  options: PreferFakeTimeOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreferFakeTimeOptions {
+  /// Patterns matched against file paths, files whose path contains any of
+  /// them aren't checked, e.g. integration tests relying on the real time.
+  pub ignore_files: Vec<String>,
+}
+
+const CODE: &str = "prefer-fake-time";
+
+#[derive(Display)]
+enum PreferFakeTimeMessage {
+  #[display(fmt = "`Date.now()` makes the test depend on the real time")]
+  DateNow,
+  #[display(fmt = "`new Date()` makes the test depend on the real time")]
+  NewDate,
+  #[display(fmt = "Waiting with `setTimeout` makes the test slow and flaky")]
+  Sleep,
+}
+
+#[derive(Display)]
+enum PreferFakeTimeHint {
+  #[display(
+    fmt = "Control the time with `FakeTime` from `@std/testing/time`, or pass the date to the code under test"
+  )]
+  UseFakeTime,
+  #[display(
+    fmt = "Use `FakeTime` from `@std/testing/time` and advance the clock with `time.tick()`"
+  )]
+  TickFakeTime,
+}
+
+impl LintRule for PreferFakeTime {
+  fn new() -> Box<Self> {
+    Box::new(PreferFakeTime {
+      options: PreferFakeTimeOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    // Match relative paths such as `integration/a_test.ts` against
+    // `/integration/` too
+    let path = format!("/{}", context.file_name().replace('\\', "/"));
+    if self
+      .options
+      .ignore_files
+      .iter()
+      .any(|pattern| path.contains(pattern.as_str()))
+    {
+      return;
+    }
+
+    let mut visitor = PreferFakeTimeVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "ignoreFiles": {
+          "description": "Patterns matched against file paths, files whose path contains any of them aren't checked, e.g. integration tests relying on the real time.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": []
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Disallows depending on the real time in tests",
+      "prefer-fake-time"
+    )
+  }
+}
+
+struct PreferFakeTimeVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> Visit for PreferFakeTimeVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    let test_fn = match deno_test_fn(call_expr) {
+      Some(test_fn) => test_fn,
+      None => {
+        call_expr.visit_children_with(self);
+        return;
+      }
+    };
+
+    let mut finder = RealTimeFinder {
+      scope: self.context.scope(),
+      uses_fake_time: false,
+      found: Vec::new(),
+    };
+    test_fn.visit_body_with(&mut finder);
+    // Within a test faking the time, the globals are replaced
+    if finder.uses_fake_time {
+      return;
+    }
+    for (span, message, hint) in finder.found {
+      self
+        .context
+        .add_diagnostic_with_hint(span, CODE, message, hint);
+    }
+  }
+}
+
+/// Collects the uses of the real time in a test, and whether the test sets up
+/// a `FakeTime`.
+struct RealTimeFinder<'a> {
+  scope: &'a Scope,
+  uses_fake_time: bool,
+  found: Vec<(Span, PreferFakeTimeMessage, PreferFakeTimeHint)>,
+}
+
+impl<'a> RealTimeFinder<'a> {
+  fn is_global(&self, ident: &Ident, name: &str) -> bool {
+    ident.sym == *name && self.scope.var(&ident.to_id()).is_none()
+  }
+}
+
+/// Returns `true` unless the delay is omitted or `0`, which only defers the
+/// callback to the next turn of the event loop.
+fn has_delay(args: &[ExprOrSpread]) -> bool {
+  match args.get(1) {
+    Some(delay) => !matches!(
+      &*delay.expr,
+      Expr::Lit(Lit::Num(num)) if num.value == 0.0
+    ),
+    None => false,
+  }
+}
+
+impl<'a> Visit for RealTimeFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      match &**callee {
+        Expr::Member(member) if !member.computed => {
+          let is_date_now = matches!(
+            &member.obj,
+            ExprOrSuper::Expr(obj) if matches!(
+              &**obj,
+              Expr::Ident(obj) if self.is_global(obj, "Date")
+            )
+          ) && matches!(&*member.prop, Expr::Ident(prop) if prop.sym == *"now");
+          if is_date_now {
+            self.found.push((
+              call_expr.span,
+              PreferFakeTimeMessage::DateNow,
+              PreferFakeTimeHint::UseFakeTime,
+            ));
+          }
+        }
+        Expr::Ident(ident)
+          if self.is_global(ident, "setTimeout")
+            && has_delay(&call_expr.args) =>
+        {
+          self.found.push((
+            call_expr.span,
+            PreferFakeTimeMessage::Sleep,
+            PreferFakeTimeHint::TickFakeTime,
+          ));
+        }
+        _ => {}
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+
+  fn visit_new_expr(&mut self, new_expr: &NewExpr, _: &dyn Node) {
+    if let Expr::Ident(ident) = &*new_expr.callee {
+      if ident.sym == *"FakeTime" {
+        self.uses_fake_time = true;
+      } else if self.is_global(ident, "Date")
+        && !matches!(&new_expr.args, Some(args) if !args.is_empty())
+      {
+        self.found.push((
+          new_expr.span,
+          PreferFakeTimeMessage::NewDate,
+          PreferFakeTimeHint::UseFakeTime,
+        ));
+      }
+    }
+    new_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn prefer_fake_time_valid() {
+    assert_lint_ok! {
+      PreferFakeTime,
+      "const start = Date.now();",
+      "function now() { return new Date(); }",
+      "Deno.test('formats', () => { format(new Date(2020, 0, 1)); });",
+      "Deno.test('parses', () => { Date.parse('2020-01-01'); });",
+      "Deno.test('yields', async () => { await new Promise((r) => setTimeout(r)); });",
+      "Deno.test('yields', async () => { await new Promise((r) => setTimeout(r, 0)); });",
+      r#"
+import { FakeTime } from "jsr:@std/testing/time";
+Deno.test("expires", async () => {
+  const time = new FakeTime();
+  const start = Date.now();
+  setTimeout(() => {}, 1000);
+  time.tick(1000);
+});
+"#,
+      r#"
+class Date {}
+Deno.test("local", () => { new Date(); Date.now(); });
+"#,
+      "Deno.test('stubbed', () => { const setTimeout = stub(); setTimeout(f, 100); });",
+      "Deno.bench('now', () => { Date.now(); });",
+    };
+
+    assert_lint_ok! {
+      PreferFakeTime,
+      options: json!({ "ignoreFiles": ["/integration/"] }),
+      {
+        src: "Deno.test('waits', async () => { await new Promise((r) => setTimeout(r, 100)); });",
+        filename: "integration/server_test.ts",
+      },
+    };
+  }
+
+  #[test]
+  fn prefer_fake_time_invalid() {
+    assert_lint_err! {
+      PreferFakeTime,
+      "Deno.test('expires', () => { const start = Date.now(); });": [
+        {
+          col: 43,
+          message: PreferFakeTimeMessage::DateNow,
+          hint: PreferFakeTimeHint::UseFakeTime,
+        }
+      ],
+      "Deno.test({ name: 'expires', fn() { isExpired(new Date()); } });": [
+        {
+          col: 46,
+          message: PreferFakeTimeMessage::NewDate,
+          hint: PreferFakeTimeHint::UseFakeTime,
+        }
+      ],
+      "Deno.test('waits', async () => { await new Promise((r) => setTimeout(r, 100)); });": [
+        {
+          col: 58,
+          message: PreferFakeTimeMessage::Sleep,
+          hint: PreferFakeTimeHint::TickFakeTime,
+        }
+      ],
+      r#"Deno.test("steps", async (t) => {
+  await t.step("now", () => {
+    check(Date.now(), new Date);
+  });
+});"#: [
+        {
+          line: 3,
+          col: 10,
+          message: PreferFakeTimeMessage::DateNow,
+          hint: PreferFakeTimeHint::UseFakeTime,
+        },
+        {
+          line: 3,
+          col: 22,
+          message: PreferFakeTimeMessage::NewDate,
+          hint: PreferFakeTimeHint::UseFakeTime,
+        }
+      ],
+    };
+  }
+}
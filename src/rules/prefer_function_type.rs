@@ -0,0 +1,466 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use std::collections::HashMap;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ClassDecl, DefaultDecl, ExportDefaultDecl, TsCallSignatureDecl,
+  TsInterfaceDecl, TsModuleDecl, TsModuleName, TsThisType, TsType,
+  TsTypeAliasDecl, TsTypeAnn, TsTypeElement, TsTypeLit,
+};
+use swc_ecmascript::visit::{Node, Visit, VisitWith};
+
+pub struct PreferFunctionType;
+
+const CODE: &str = "prefer-function-type";
+
+#[derive(Display)]
+enum PreferFunctionTypeMessage {
+  #[display(fmt = "Interface '{}' only has a call signature", _0)]
+  Interface(String),
+  #[display(fmt = "Type literal only has a call signature")]
+  TypeLiteral,
+}
+
+#[derive(Display)]
+enum PreferFunctionTypeHint {
+  #[display(fmt = "Use a function type instead")]
+  UseFunctionType,
+}
+
+#[derive(Display)]
+enum PreferFunctionTypeFix {
+  #[display(fmt = "Change to a function type")]
+  UseFunctionType,
+}
+
+impl LintRule for PreferFunctionType {
+  fn new() -> Box<Self> {
+    Box::new(PreferFunctionType)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut counter = DeclCounter {
+      counts: HashMap::new(),
+    };
+    match program {
+      ProgramRef::Module(m) => counter.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => counter.visit_script(s, &DUMMY_NODE),
+    }
+    let mut visitor = PreferFunctionTypeVisitor {
+      context,
+      decl_counts: counter.counts,
+      in_augmentation: false,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires function types instead of interfaces or type literals with only a call signature",
+      "prefer-function-type"
+    )
+  }
+}
+
+/// Counts the declarations that an interface can be merged with, i.e.
+/// interfaces, classes and namespaces, by name.
+struct DeclCounter {
+  counts: HashMap<JsWord, usize>,
+}
+
+impl DeclCounter {
+  fn add(&mut self, name: &JsWord) {
+    *self.counts.entry(name.clone()).or_insert(0) += 1;
+  }
+}
+
+impl Visit for DeclCounter {
+  fn visit_ts_interface_decl(
+    &mut self,
+    interface_decl: &TsInterfaceDecl,
+    _: &dyn Node,
+  ) {
+    self.add(&interface_decl.id.sym);
+  }
+
+  fn visit_class_decl(&mut self, class_decl: &ClassDecl, _: &dyn Node) {
+    self.add(&class_decl.ident.sym);
+    class_decl.visit_children_with(self);
+  }
+
+  fn visit_ts_module_decl(&mut self, module_decl: &TsModuleDecl, _: &dyn Node) {
+    if let TsModuleName::Ident(ident) = &module_decl.id {
+      self.add(&ident.sym);
+    }
+    module_decl.visit_children_with(self);
+  }
+}
+
+/// Finds `this` types, which refer to the interface they are used in.
+struct ThisTypeFinder {
+  found: bool,
+}
+
+impl Visit for ThisTypeFinder {
+  fn visit_ts_this_type(&mut self, _: &TsThisType, _: &dyn Node) {
+    self.found = true;
+  }
+}
+
+/// Returns the call signature if it's the only member.
+fn only_call_signature(
+  members: &[TsTypeElement],
+) -> Option<&TsCallSignatureDecl> {
+  match members {
+    [TsTypeElement::TsCallSignatureDecl(call_signature)] => {
+      Some(call_signature)
+    }
+    _ => None,
+  }
+}
+
+struct PreferFunctionTypeVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  decl_counts: HashMap<JsWord, usize>,
+  /// Whether the visitor is in a `declare global` or `declare module "foo"`
+  /// block, where interfaces augment existing declarations.
+  in_augmentation: bool,
+}
+
+impl<'c, 'view> PreferFunctionTypeVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  /// Returns the text of the call signature written as a function type, or
+  /// `None` if it has no return type.
+  fn print_function_type(
+    &self,
+    call_signature: &TsCallSignatureDecl,
+  ) -> Option<String> {
+    let return_type = call_signature.type_ann.as_ref()?.type_ann.span();
+    let signature =
+      self.snippet(call_signature.span.with_hi(return_type.lo))?;
+    let params = signature.trim_end().strip_suffix(':')?.trim_end();
+    Some(format!("{} => {}", params, self.snippet(return_type)?))
+  }
+
+  fn has_comments(&self, span: Span) -> bool {
+    self
+      .context
+      .all_comments()
+      .any(|comment| span.lo <= comment.span.lo && comment.span.hi <= span.hi)
+  }
+
+  fn report(
+    &mut self,
+    span: Span,
+    message: PreferFunctionTypeMessage,
+    fixed: Option<String>,
+  ) {
+    match fixed {
+      Some(fixed) if !self.has_comments(span) => {
+        let fix = self.context.create_fix(
+          PreferFunctionTypeFix::UseFunctionType,
+          vec![(span, fixed)],
+        );
+        self.context.add_diagnostic_with_fixes(
+          span,
+          CODE,
+          message,
+          Some(PreferFunctionTypeHint::UseFunctionType.to_string()),
+          vec![fix],
+        );
+      }
+      _ => {
+        self.context.add_diagnostic_with_hint(
+          span,
+          CODE,
+          message,
+          PreferFunctionTypeHint::UseFunctionType,
+        );
+      }
+    }
+  }
+
+  fn check_interface(
+    &mut self,
+    interface_decl: &TsInterfaceDecl,
+    fixable: bool,
+  ) {
+    let call_signature = match only_call_signature(&interface_decl.body.body) {
+      Some(call_signature) => call_signature,
+      None => return,
+    };
+    let is_merged = self
+      .decl_counts
+      .get(&interface_decl.id.sym)
+      .map(|count| *count > 1)
+      .unwrap_or(false);
+    if !interface_decl.extends.is_empty() || is_merged || self.in_augmentation {
+      return;
+    }
+
+    let mut this_finder = ThisTypeFinder { found: false };
+    call_signature.visit_with(&DUMMY_NODE, &mut this_finder);
+    let fixed = if fixable && !this_finder.found {
+      self.print_type_alias(interface_decl, call_signature)
+    } else {
+      None
+    };
+    self.report(
+      interface_decl.span,
+      PreferFunctionTypeMessage::Interface(interface_decl.id.sym.to_string()),
+      fixed,
+    );
+  }
+
+  /// Returns the text of the interface written as a type alias.
+  fn print_type_alias(
+    &self,
+    interface_decl: &TsInterfaceDecl,
+    call_signature: &TsCallSignatureDecl,
+  ) -> Option<String> {
+    // Keeps `declare` if it's part of the span
+    let keyword = self
+      .snippet(interface_decl.span.with_hi(interface_decl.id.span.lo))?
+      .replacen("interface", "type", 1);
+    let type_params = match &interface_decl.type_params {
+      Some(type_params) => self.snippet(type_params.span)?,
+      None => String::new(),
+    };
+    Some(format!(
+      "{}{}{} = {};",
+      keyword,
+      interface_decl.id.sym,
+      type_params,
+      self.print_function_type(call_signature)?
+    ))
+  }
+
+  /// Checks a type literal, which has to be wrapped in parentheses unless
+  /// it's the whole type of an alias or an annotation.
+  fn check_type_lit(&mut self, type_lit: &TsTypeLit, needs_parens: bool) {
+    if let Some(call_signature) = only_call_signature(&type_lit.members) {
+      let fixed = self.print_function_type(call_signature).map(|fixed| {
+        if needs_parens {
+          format!("({})", fixed)
+        } else {
+          fixed
+        }
+      });
+      self.report(type_lit.span, PreferFunctionTypeMessage::TypeLiteral, fixed);
+    }
+    type_lit.visit_children_with(self);
+  }
+}
+
+impl<'c, 'view> Visit for PreferFunctionTypeVisitor<'c, 'view> {
+  fn visit_ts_interface_decl(
+    &mut self,
+    interface_decl: &TsInterfaceDecl,
+    _: &dyn Node,
+  ) {
+    self.check_interface(interface_decl, true);
+    interface_decl.visit_children_with(self);
+  }
+
+  fn visit_export_default_decl(
+    &mut self,
+    export_default_decl: &ExportDefaultDecl,
+    _: &dyn Node,
+  ) {
+    match &export_default_decl.decl {
+      // `export default type` isn't valid
+      DefaultDecl::TsInterfaceDecl(interface_decl) => {
+        self.check_interface(interface_decl, false);
+        interface_decl.visit_children_with(self);
+      }
+      _ => export_default_decl.visit_children_with(self),
+    }
+  }
+
+  fn visit_ts_module_decl(&mut self, module_decl: &TsModuleDecl, _: &dyn Node) {
+    let prev = self.in_augmentation;
+    if module_decl.global || matches!(module_decl.id, TsModuleName::Str(_)) {
+      self.in_augmentation = true;
+    }
+    module_decl.visit_children_with(self);
+    self.in_augmentation = prev;
+  }
+
+  fn visit_ts_type_alias_decl(
+    &mut self,
+    type_alias_decl: &TsTypeAliasDecl,
+    _: &dyn Node,
+  ) {
+    type_alias_decl
+      .type_params
+      .visit_with(type_alias_decl, self);
+    match &*type_alias_decl.type_ann {
+      TsType::TsTypeLit(type_lit) => self.check_type_lit(type_lit, false),
+      ty => ty.visit_with(type_alias_decl, self),
+    }
+  }
+
+  fn visit_ts_type_ann(&mut self, type_ann: &TsTypeAnn, _: &dyn Node) {
+    match &*type_ann.type_ann {
+      TsType::TsTypeLit(type_lit) => self.check_type_lit(type_lit, false),
+      ty => ty.visit_with(type_ann, self),
+    }
+  }
+
+  fn visit_ts_type_lit(&mut self, type_lit: &TsTypeLit, _: &dyn Node) {
+    self.check_type_lit(type_lit, true);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_function_type_valid() {
+    assert_lint_ok! {
+      PreferFunctionType,
+      "type Foo = (a: string) => void;",
+      "interface Foo { (a: string): void; b: number; }",
+      "interface Foo { new (a: string): Foo; }",
+      "interface Foo extends Bar { (a: string): void; }",
+      "interface Foo { (a: string): void; }\ninterface Foo { (a: number): void; }",
+      "interface Foo { (a: string): void; }\nnamespace Foo { export const a = 1; }",
+      "declare global { interface Foo { (a: string): void; } }",
+      "declare module 'foo' { interface Foo { (a: string): void; } }",
+      "let a: { b: (c: string) => void };",
+      "let a: { (): void; (b: string): void };",
+    };
+  }
+
+  #[test]
+  fn prefer_function_type_invalid() {
+    assert_lint_err! {
+      PreferFunctionType,
+      "interface Foo { (a: string): void; }": [
+        {
+          col: 0,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "type Foo = (a: string) => void;"
+          ),
+        }
+      ],
+      "export interface Foo<T> {\n  <U>(a: T, b: U): a is T;\n}": [
+        {
+          col: 7,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "export type Foo<T> = <U>(a: T, b: U) => a is T;"
+          ),
+        }
+      ],
+      "declare interface Foo { (): void }": [
+        {
+          col: 0,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "declare type Foo = () => void;"
+          ),
+        }
+      ],
+      "type Foo = { (a: string): void };": [
+        {
+          col: 11,
+          message: PreferFunctionTypeMessage::TypeLiteral,
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "type Foo = (a: string) => void;"
+          ),
+        }
+      ],
+      "let a: { (): void } | string;": [
+        {
+          col: 7,
+          message: PreferFunctionTypeMessage::TypeLiteral,
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "let a: (() => void) | string;"
+          ),
+        }
+      ],
+      "function foo(a: { (): { (): void } }) {}": [
+        {
+          col: 16,
+          message: PreferFunctionTypeMessage::TypeLiteral,
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "function foo(a: () => { (): void }) {}"
+          ),
+        },
+        {
+          col: 22,
+          message: PreferFunctionTypeMessage::TypeLiteral,
+          hint: PreferFunctionTypeHint::UseFunctionType,
+          fix: (
+            PreferFunctionTypeFix::UseFunctionType,
+            "function foo(a: { (): () => void }) {}"
+          ),
+        }
+      ],
+      "interface Foo { (): this; }": [
+        {
+          col: 0,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+        }
+      ],
+      "interface Foo {\n  // Called on change\n  (a: string): void;\n}": [
+        {
+          col: 0,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+        }
+      ],
+      "export default interface Foo { (): void }": [
+        {
+          col: 15,
+          message: variant!(PreferFunctionTypeMessage, Interface, "Foo"),
+          hint: PreferFunctionTypeHint::UseFunctionType,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,270 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{is_member_object, method_call, regex_literal_text};
+use derive_more::Display;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{BinExpr, BinaryOp, CallExpr, Expr, Lit, UnaryOp};
+use swc_ecmascript::visit::{noop_visit_type, Node, VisitAll, VisitAllWith};
+
+pub struct PreferIncludes;
+
+const CODE: &str = "prefer-includes";
+
+#[derive(Display)]
+enum PreferIncludesMessage {
+  #[display(fmt = "Use `includes` to check whether a value is contained")]
+  UseIncludes,
+}
+
+#[derive(Display)]
+enum PreferIncludesHint {
+  #[display(fmt = "Replace the check with an `includes` call")]
+  UseIncludes,
+}
+
+#[derive(Display)]
+enum PreferIncludesFix {
+  #[display(fmt = "Replace with `includes`")]
+  UseIncludes,
+}
+
+impl LintRule for PreferIncludes {
+  fn new() -> Box<Self> {
+    Box::new(PreferIncludes)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferIncludesVisitor { context };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires `includes` over `indexOf` comparisons and simple regular expression tests",
+      "prefer-includes"
+    )
+  }
+}
+
+/// Returns the value of a number literal, including negative ones like `-1`.
+fn number(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Lit(Lit::Num(num)) => Some(num.value),
+    Expr::Unary(unary) if unary.op == UnaryOp::Minus => match &*unary.arg {
+      Expr::Lit(Lit::Num(num)) => Some(-num.value),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// Returns whether `index <op> value` checks that the index is found, or
+/// `None` if it isn't an existence check.
+fn is_found_check(op: BinaryOp, value: f64) -> Option<bool> {
+  match op {
+    BinaryOp::NotEq | BinaryOp::NotEqEq | BinaryOp::Gt if value == -1.0 => {
+      Some(true)
+    }
+    BinaryOp::EqEq | BinaryOp::EqEqEq if value == -1.0 => Some(false),
+    BinaryOp::GtEq if value == 0.0 => Some(true),
+    BinaryOp::Lt if value == 0.0 => Some(false),
+    _ => None,
+  }
+}
+
+/// Returns the operator of `b <op> a` that is the same as `a <op> b`.
+fn flip(op: BinaryOp) -> BinaryOp {
+  match op {
+    BinaryOp::Gt => BinaryOp::Lt,
+    BinaryOp::GtEq => BinaryOp::LtEq,
+    BinaryOp::Lt => BinaryOp::Gt,
+    BinaryOp::LtEq => BinaryOp::GtEq,
+    op => op,
+  }
+}
+
+struct PreferIncludesVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> PreferIncludesVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  /// Returns the text of `!obj.includes(args)`, without `!` if `found`.
+  fn print_includes(
+    &self,
+    obj: &Expr,
+    args: Option<String>,
+    found: bool,
+  ) -> Option<String> {
+    let obj_text = self.snippet(obj.span())?;
+    let obj_text = if is_member_object(obj) {
+      obj_text
+    } else {
+      format!("({})", obj_text)
+    };
+    let not = if found { "" } else { "!" };
+    Some(format!("{}{}.includes({})", not, obj_text, args?))
+  }
+
+  fn report(&mut self, span: Span, fixed: Option<String>) {
+    match fixed {
+      Some(fixed) => {
+        let fix = self
+          .context
+          .create_fix(PreferIncludesFix::UseIncludes, vec![(span, fixed)]);
+        self.context.add_diagnostic_with_fixes(
+          span,
+          CODE,
+          PreferIncludesMessage::UseIncludes,
+          Some(PreferIncludesHint::UseIncludes.to_string()),
+          vec![fix],
+        );
+      }
+      None => {
+        self.context.add_diagnostic_with_hint(
+          span,
+          CODE,
+          PreferIncludesMessage::UseIncludes,
+          PreferIncludesHint::UseIncludes,
+        );
+      }
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for PreferIncludesVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    let (call, op, value) = match (&*bin_expr.left, &*bin_expr.right) {
+      (Expr::Call(call), value) => (call, bin_expr.op, value),
+      (value, Expr::Call(call)) => (call, flip(bin_expr.op), value),
+      _ => return,
+    };
+    let found = match number(value).and_then(|value| is_found_check(op, value))
+    {
+      Some(found) => found,
+      None => return,
+    };
+    let (obj, args) = match method_call(call) {
+      // `indexOf(value, fromIndex)` is the same as `includes(value, fromIndex)`
+      Some((obj, "indexOf", args)) if !args.is_empty() && args.len() <= 2 => {
+        (obj, args)
+      }
+      _ => return,
+    };
+    let args_span = args[0].span().to(args[args.len() - 1].span());
+    let fixed = self.print_includes(obj, self.snippet(args_span), found);
+    self.report(bin_expr.span, fixed);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    let (regex, arg) = match method_call(call_expr) {
+      Some((Expr::Lit(Lit::Regex(regex)), "test", [arg])) => (regex, arg),
+      _ => return,
+    };
+    // Flags change what is matched, e.g. `i` or `m`
+    if !regex.flags.is_empty() {
+      return;
+    }
+    if let Some(text) = regex_literal_text(&regex.exp) {
+      let fixed = self.print_includes(&arg.expr, Some(text), true);
+      self.report(call_expr.span, fixed);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_includes_valid() {
+    assert_lint_ok! {
+      PreferIncludes,
+      "a.includes(b);",
+      "a.indexOf(b) === 0;",
+      "a.indexOf(b) > 0;",
+      "a.indexOf(b) <= -1;",
+      "const i = a.indexOf(b);",
+      "a.indexOf() !== -1;",
+      "a.lastIndexOf(b) !== -1;",
+      "/^foo/.test(a);",
+      "/foo/i.test(a);",
+      "/fo+/.test(a);",
+    };
+  }
+
+  #[test]
+  fn prefer_includes_invalid() {
+    assert_lint_err! {
+      PreferIncludes,
+      "a.indexOf(b) !== -1;": [
+        {
+          col: 0,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "a.includes(b);"),
+        }
+      ],
+      "if (a.b.indexOf('c', 1) >= 0) {}": [
+        {
+          col: 4,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "if (a.b.includes('c', 1)) {}"),
+        }
+      ],
+      "a.indexOf(b) == -1;": [
+        {
+          col: 0,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "!a.includes(b);"),
+        }
+      ],
+      "[1, 2].indexOf(a) < 0;": [
+        {
+          col: 0,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "![1, 2].includes(a);"),
+        }
+      ],
+      "-1 < a.indexOf(b);": [
+        {
+          col: 0,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "a.includes(b);"),
+        }
+      ],
+      "/foo\\/bar/.test(a ?? b);": [
+        {
+          col: 0,
+          message: PreferIncludesMessage::UseIncludes,
+          hint: PreferIncludesHint::UseIncludes,
+          fix: (PreferIncludesFix::UseIncludes, "(a ?? b).includes(\"foo/bar\");"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,485 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef};
+use crate::handler::{Handler, Traverse};
+use derive_more::Display;
+use dprint_swc_ecma_ast_view as AstView;
+use swc_common::{EqIgnoreSpan, Span, Spanned};
+use swc_ecmascript::ast::{BinaryOp, Expr, ExprOrSuper, UnaryOp};
+
+pub struct PreferOptionalChain;
+
+const CODE: &str = "prefer-optional-chain";
+
+#[derive(Display)]
+enum PreferOptionalChainMessage {
+  #[display(
+    fmt = "Prefer an optional chain over checking every step of the chain"
+  )]
+  PreferOptionalChain,
+}
+
+#[derive(Display)]
+enum PreferOptionalChainHint {
+  #[display(fmt = "Use optional chaining, e.g. `foo?.bar`")]
+  UseOptionalChain,
+  #[display(
+    fmt = "Consider `{}` instead, if the checked values can't be falsy values like `0` or `\"\"`, for which it evaluates to `undefined`",
+    _0
+  )]
+  UseOptionalChainIfNullish(String),
+}
+
+#[derive(Display)]
+enum PreferOptionalChainFix {
+  #[display(fmt = "Use optional chaining")]
+  UseOptionalChain,
+}
+
+impl LintRule for PreferOptionalChain {
+  fn new() -> Box<Self> {
+    Box::new(PreferOptionalChain)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_comments(&self) -> bool {
+    true
+  }
+
+  fn lint_program(&self, _context: &mut Context, _program: ProgramRef<'_>) {
+    unreachable!();
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: AstView::Program<'_>,
+  ) {
+    PreferOptionalChainHandler.traverse(program, context);
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces the use of optional chaining over chained logical expressions",
+      "prefer-optional-chain"
+    )
+  }
+}
+
+struct PreferOptionalChainHandler;
+
+impl Handler for PreferOptionalChainHandler {
+  fn bin_expr(&mut self, bin_expr: &AstView::BinExpr, ctx: &mut Context) {
+    let op = bin_expr.op();
+    if !matches!(op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) {
+      return;
+    }
+    // Only the outermost expression of `a && b && c` is checked
+    if matches!(bin_expr.parent, AstView::Node::BinExpr(parent) if parent.op() == op)
+    {
+      return;
+    }
+
+    // `!a || !a.b` is the negation of `a && a.b`
+    let negated = op == BinaryOp::LogicalOr;
+    let mut operands = Vec::new();
+    collect_operands(&bin_expr.inner.left, op, &mut operands);
+    operands.push(&bin_expr.inner.right);
+    let chains: Vec<Option<&Expr>> = operands
+      .iter()
+      .map(|operand| checked_chain(operand, negated))
+      .collect();
+
+    // `a && a.b` evaluates to `a` if it's falsy while `a?.b` evaluates to
+    // `undefined`, so the fix is only safe if only the truthiness matters.
+    let is_fixable = negated || is_in_boolean_context(bin_expr);
+
+    let mut start = 0;
+    while start < chains.len() {
+      let mut end = start;
+      while end + 1 < chains.len() && extends(chains[end + 1], chains[end]) {
+        end += 1;
+      }
+      if end > start {
+        let chain: Vec<&Expr> =
+          chains[start..=end].iter().map(|c| c.unwrap()).collect();
+        let span = Span::new(
+          operands[start].span().lo(),
+          operands[end].span().hi(),
+          Default::default(),
+        );
+        report(ctx, span, &chain, negated, is_fixable);
+      }
+      start = end + 1;
+    }
+  }
+}
+
+/// Collects the operands of a chain of logical expressions with the same
+/// operator, e.g. `a`, `b` and `c` in `a && b && c`.
+fn collect_operands<'a>(expr: &'a Expr, op: BinaryOp, out: &mut Vec<&'a Expr>) {
+  match expr {
+    Expr::Bin(bin_expr) if bin_expr.op == op => {
+      collect_operands(&bin_expr.left, op, out);
+      out.push(&bin_expr.right);
+    }
+    _ => out.push(expr),
+  }
+}
+
+/// Returns the chain of member accesses and calls checked by the operand, i.e.
+/// the operand itself or its argument if `negated`.
+fn checked_chain(operand: &Expr, negated: bool) -> Option<&Expr> {
+  let chain = if negated {
+    match operand {
+      Expr::Unary(unary) if unary.op == UnaryOp::Bang => &*unary.arg,
+      _ => return None,
+    }
+  } else {
+    operand
+  };
+  if is_chain(chain) {
+    Some(chain)
+  } else {
+    None
+  }
+}
+
+/// Returns `true` if the expression only consists of member accesses and
+/// calls on an identifier or `this`, e.g. `foo.bar[0]()`. Computed members
+/// must be identifiers or literals so evaluating them twice has no effect.
+fn is_chain(expr: &Expr) -> bool {
+  match expr {
+    Expr::Ident(_) | Expr::This(_) => true,
+    Expr::Member(member)
+      if member.computed
+        && !matches!(&*member.prop, Expr::Ident(_) | Expr::Lit(_)) =>
+    {
+      false
+    }
+    _ => matches!(chain_object(expr), Some(object) if is_chain(object)),
+  }
+}
+
+/// Returns `true` if the chain doesn't contain any calls, which would be
+/// evaluated once less after merging the chains.
+fn is_call_free(chain: &Expr) -> bool {
+  match chain {
+    Expr::Call(_) => false,
+    _ => match chain_object(chain) {
+      Some(object) => is_call_free(object),
+      None => true,
+    },
+  }
+}
+
+/// Returns the object of a member access or the callee of a call.
+fn chain_object(expr: &Expr) -> Option<&Expr> {
+  match expr {
+    Expr::Member(member) => match &member.obj {
+      ExprOrSuper::Expr(obj) => Some(obj),
+      ExprOrSuper::Super(_) => None,
+    },
+    Expr::Call(call) => match &call.callee {
+      ExprOrSuper::Expr(callee) => Some(callee),
+      ExprOrSuper::Super(_) => None,
+    },
+    _ => None,
+  }
+}
+
+/// Returns the node in `chain` that is equal to `prefix`, e.g. the `foo.bar`
+/// in `foo.bar.baz()` for the prefix `foo.bar`.
+fn find_prefix<'a>(chain: &'a Expr, prefix: &Expr) -> Option<&'a Expr> {
+  let mut current = chain;
+  while let Some(object) = chain_object(current) {
+    if object.eq_ignore_span(prefix) {
+      return Some(object);
+    }
+    current = object;
+  }
+  None
+}
+
+fn extends(chain: Option<&Expr>, prefix: Option<&Expr>) -> bool {
+  match (chain, prefix) {
+    (Some(chain), Some(prefix)) => {
+      is_call_free(prefix) && find_prefix(chain, prefix).is_some()
+    }
+    _ => false,
+  }
+}
+
+fn is_in_boolean_context(bin_expr: &AstView::BinExpr) -> bool {
+  use AstView::Node;
+  // Look through parentheses, e.g. `!(a && a.b)`
+  let mut span = bin_expr.inner.span;
+  let mut parent = bin_expr.parent;
+  while let Node::ParenExpr(paren_expr) = parent {
+    span = paren_expr.inner.span;
+    parent = paren_expr.parent;
+  }
+  match parent {
+    // The value of an expression statement is discarded
+    Node::ExprStmt(_)
+    | Node::IfStmt(_)
+    | Node::WhileStmt(_)
+    | Node::DoWhileStmt(_) => true,
+    Node::ForStmt(for_stmt) => {
+      matches!(&for_stmt.inner.test, Some(test) if test.span() == span)
+    }
+    Node::CondExpr(cond_expr) => cond_expr.inner.test.span() == span,
+    Node::UnaryExpr(unary_expr) => unary_expr.op() == UnaryOp::Bang,
+    _ => false,
+  }
+}
+
+fn report(
+  ctx: &mut Context,
+  span: Span,
+  chain: &[&Expr],
+  negated: bool,
+  is_fixable: bool,
+) {
+  let has_comments = ctx.all_comments().any(|comment| {
+    span.lo() <= comment.span.lo && comment.span.hi <= span.hi()
+  });
+  let fixed = if has_comments {
+    None
+  } else {
+    optional_chain_text(ctx, chain).map(|text| {
+      if negated {
+        format!("!{}", text)
+      } else {
+        text
+      }
+    })
+  };
+  // Where the value of the expression is used, the rewrite is only suggested
+  let (hint, fixes) = match fixed {
+    Some(text) if is_fixable => (
+      PreferOptionalChainHint::UseOptionalChain,
+      vec![ctx.create_fix(
+        PreferOptionalChainFix::UseOptionalChain,
+        vec![(span, text)],
+      )],
+    ),
+    Some(text) => (
+      PreferOptionalChainHint::UseOptionalChainIfNullish(text),
+      vec![],
+    ),
+    None => (PreferOptionalChainHint::UseOptionalChain, vec![]),
+  };
+
+  ctx.add_diagnostic_with_fixes(
+    span,
+    CODE,
+    PreferOptionalChainMessage::PreferOptionalChain,
+    Some(hint.to_string()),
+    fixes,
+  );
+}
+
+/// Returns the source of the last chain with every member access or call
+/// following one of the previous chains made optional, e.g. `foo?.bar?.baz`
+/// for `foo`, `foo.bar` and `foo.bar.baz`.
+fn optional_chain_text(ctx: &Context, chain: &[&Expr]) -> Option<String> {
+  let (last, prefixes) = chain.split_last()?;
+  let source = ctx.source_map().span_to_snippet(last.span()).ok()?;
+  let lo = last.span().lo();
+
+  let mut offsets = Vec::new();
+  for prefix in prefixes {
+    let prefix = find_prefix(last, prefix)?;
+    offsets.push((prefix.span().hi() - lo).0 as usize);
+  }
+  offsets.sort_unstable();
+
+  let mut text = String::with_capacity(source.len() + 2 * offsets.len());
+  let mut last_offset = 0;
+  for offset in offsets {
+    text.push_str(&source[last_offset..offset]);
+    let rest = &source[offset..];
+    let whitespace_len = rest.len() - rest.trim_start().len();
+    if rest.trim_start().starts_with('.') {
+      // `foo.bar` -> `foo?.bar`
+      text.push_str(&rest[..whitespace_len]);
+      text.push_str("?.");
+      last_offset = offset + whitespace_len + 1;
+    } else {
+      // `foo[0]` -> `foo?.[0]`, `foo()` -> `foo?.()`
+      text.push_str("?.");
+      last_offset = offset;
+    }
+  }
+  text.push_str(&source[last_offset..]);
+  Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_optional_chain_valid() {
+    assert_lint_ok! {
+      PreferOptionalChain,
+      "foo?.bar;",
+      "foo && bar;",
+      "foo && bar.foo;",
+      "foo.bar && foo.baz;",
+      "foo && foo;",
+      "foo.bar && foo.bar;",
+      "foo || foo.bar;",
+      "!foo && !foo.bar;",
+      "(foo || bar) && (foo || bar).baz;",
+      "foo() && foo().bar;",
+      "foo.bar() && foo.bar().baz;",
+      "foo[bar()] && foo[bar()].baz;",
+      "foo!.bar && foo!.bar.baz;",
+      "foo?.bar && foo?.bar.baz;",
+      "foo && typeof foo.bar;",
+      "super.foo && super.foo.bar;",
+      "foo + foo.bar;",
+    };
+  }
+
+  #[test]
+  fn prefer_optional_chain_invalid() {
+    assert_lint_err! {
+      PreferOptionalChain,
+      "if (foo && foo.bar) {}": [
+        {
+          col: 4,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "if (foo?.bar) {}"),
+        }
+      ],
+      "if (foo && foo.bar && foo.bar.baz) {}": [
+        {
+          col: 4,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "if (foo?.bar?.baz) {}"),
+        }
+      ],
+      "foo && foo();": [
+        {
+          col: 0,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "foo?.();"),
+        }
+      ],
+      "this.foo && this.foo[bar] && this.foo[bar].baz();": [
+        {
+          col: 0,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "this.foo?.[bar]?.baz();"),
+        }
+      ],
+      "while (foo.bar && foo.bar.baz && foo.bar.baz(1).qux) {}": [
+        {
+          col: 7,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "while (foo.bar?.baz?.(1).qux) {}"),
+        }
+      ],
+      "const a = foo && foo.bar ? 1 : 2;": [
+        {
+          col: 10,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "const a = foo?.bar ? 1 : 2;"),
+        }
+      ],
+      "const a = !(foo && foo.bar);": [
+        {
+          col: 12,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "const a = !(foo?.bar);"),
+        }
+      ],
+      "if (a && foo && foo.bar && b && b.c) {}": [
+        {
+          col: 9,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "if (a && foo?.bar && b && b.c) {}"),
+        },
+        {
+          col: 27,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "if (a && foo && foo.bar && b?.c) {}"),
+        }
+      ],
+      "if (foo &&\n  foo\n    .bar) {}": [
+        {
+          col: 4,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "if (foo\n    ?.bar) {}"),
+        }
+      ],
+
+      // negation
+      "const a = !foo || !foo.bar || !foo.bar.baz;": [
+        {
+          col: 10,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+          fix: (PreferOptionalChainFix::UseOptionalChain, "const a = !foo?.bar?.baz;"),
+        }
+      ],
+
+      // the value of `foo && foo.bar` differs from `foo?.bar` if `foo` is a
+      // falsy value other than `null` or `undefined`
+      "const a = foo && foo.bar;": [
+        {
+          col: 10,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: variant!(PreferOptionalChainHint, UseOptionalChainIfNullish, "foo?.bar"),
+        }
+      ],
+      "function f() { return foo && foo.bar && baz; }": [
+        {
+          col: 22,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: variant!(PreferOptionalChainHint, UseOptionalChainIfNullish, "foo?.bar"),
+        }
+      ],
+
+      "foo && foo.bar || baz;": [
+        {
+          col: 0,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: variant!(PreferOptionalChainHint, UseOptionalChainIfNullish, "foo?.bar"),
+        }
+      ],
+
+      // comments would be lost
+      "if (foo && /* bar */ foo.bar) {}": [
+        {
+          col: 4,
+          message: PreferOptionalChainMessage::PreferOptionalChain,
+          hint: PreferOptionalChainHint::UseOptionalChain,
+        }
+      ],
+    };
+  }
+}
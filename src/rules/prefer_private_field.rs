@@ -0,0 +1,254 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  Class, ClassMember, Expr, Ident, ParamOrTsParamProp, PropName,
+  TsParamPropParam,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferPrivateField {
+  options: PreferPrivateFieldOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreferPrivateFieldOptions {
+  /// Only report classes that already declare `#private` members.
+  pub only_if_private_used: bool,
+}
+
+const CODE: &str = "prefer-private-field";
+
+#[derive(Display)]
+enum PreferPrivateFieldMessage {
+  #[display(fmt = "`{}` is only private by naming convention", _0)]
+  UnderscorePrefixed(String),
+}
+
+#[derive(Display)]
+enum PreferPrivateFieldHint {
+  #[display(fmt = "Rename it to `#{}` to make it private to the class", _0)]
+  UsePrivateName(String),
+}
+
+impl LintRule for PreferPrivateField {
+  fn new() -> Box<Self> {
+    Box::new(PreferPrivateField {
+      options: PreferPrivateFieldOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferPrivateFieldVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "onlyIfPrivateUsed": {
+          "description": "Only report classes that already declare `#private` members.",
+          "type": "boolean",
+          "default": false
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Prefers `#private` class members over the leading underscore naming convention",
+      "prefer-private-field"
+    )
+  }
+}
+
+struct PreferPrivateFieldVisitor<'c, 'view, 'o> {
+  context: &'c mut Context<'view>,
+  options: &'o PreferPrivateFieldOptions,
+}
+
+impl<'c, 'view, 'o> PreferPrivateFieldVisitor<'c, 'view, 'o> {
+  fn check_ident(&mut self, ident: &Ident) {
+    let name: &str = &ident.sym;
+    let private_name = name.trim_start_matches('_');
+    if name.len() == private_name.len() || private_name.is_empty() {
+      return;
+    }
+
+    self.context.add_diagnostic_with_hint(
+      ident.span,
+      CODE,
+      PreferPrivateFieldMessage::UnderscorePrefixed(name.to_string()),
+      PreferPrivateFieldHint::UsePrivateName(private_name.to_string()),
+    );
+  }
+}
+
+impl<'c, 'view, 'o> Visit for PreferPrivateFieldVisitor<'c, 'view, 'o> {
+  noop_visit_type!();
+
+  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+    let uses_private = class.body.iter().any(|member| {
+      matches!(
+        member,
+        ClassMember::PrivateProp(_) | ClassMember::PrivateMethod(_)
+      )
+    });
+
+    if uses_private || !self.options.only_if_private_used {
+      for member in &class.body {
+        match member {
+          // Abstract, declared and overriding members have to keep their
+          // names, and overload signatures are reported at the implementation
+          ClassMember::ClassProp(prop)
+            if !prop.computed
+              && !prop.is_abstract
+              && !prop.declare
+              && !prop.is_override =>
+          {
+            if let Expr::Ident(ident) = &*prop.key {
+              self.check_ident(ident);
+            }
+          }
+          ClassMember::Method(method)
+            if !method.is_abstract
+              && !method.is_override
+              && method.function.body.is_some() =>
+          {
+            if let PropName::Ident(ident) = &method.key {
+              self.check_ident(ident);
+            }
+          }
+          ClassMember::Constructor(constructor) => {
+            for param in &constructor.params {
+              if let ParamOrTsParamProp::TsParamProp(param_prop) = param {
+                if let TsParamPropParam::Ident(binding) = &param_prop.param {
+                  self.check_ident(&binding.id);
+                }
+              }
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+
+    class.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn prefer_private_field_valid() {
+    assert_lint_ok! {
+      PreferPrivateField,
+      "class Foo { #bar = 1; #baz() {} }",
+      "class Foo { bar = 1; baz() {} }",
+      "class Foo { _ = 1; __ = 2; }",
+      "class Foo { ['_bar'] = 1; '_baz'() {} }",
+      "abstract class Foo { abstract _bar: number; abstract _baz(): void; }",
+      "class Foo extends Bar { override _bar = 1; override _baz() {} }",
+      "class Foo { declare _bar: number; }",
+      "class Foo { constructor(_bar) {} }",
+      "const foo = { _bar: 1, _baz() {} };",
+    };
+
+    assert_lint_ok! {
+      PreferPrivateField,
+      options: json!({ "onlyIfPrivateUsed": true }),
+      "class Foo { _bar = 1; _baz() {} }",
+      "class Foo { #bar = 1; baz() {} }",
+      "class Foo { #bar = 1; baz() { return class { _qux = 1; }; } }",
+    };
+  }
+
+  #[test]
+  fn prefer_private_field_invalid() {
+    assert_lint_err! {
+      PreferPrivateField,
+      "class Foo { _bar = 1; }": [
+        {
+          col: 12,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_bar"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "bar"),
+        }
+      ],
+      "class Foo { static __bar() {} get _baz() { return 1; } }": [
+        {
+          col: 19,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "__bar"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "bar"),
+        },
+        {
+          col: 34,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_baz"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "baz"),
+        }
+      ],
+      "class Foo { constructor(private readonly _bar: string) {} }": [
+        {
+          col: 41,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_bar"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "bar"),
+        }
+      ],
+      "class Foo { _bar(a: string): void; _bar(a: any) {} }": [
+        {
+          col: 35,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_bar"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "bar"),
+        }
+      ],
+      "const Foo = class { bar() { return class { _baz = 1; }; } };": [
+        {
+          col: 43,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_baz"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "baz"),
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      PreferPrivateField,
+      options: json!({ "onlyIfPrivateUsed": true }),
+      "class Foo { #bar = 1; _baz = 2; }": [
+        {
+          col: 22,
+          message: variant!(PreferPrivateFieldMessage, UnderscorePrefixed, "_baz"),
+          hint: variant!(PreferPrivateFieldHint, UsePrivateName, "baz"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,510 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::scopes::Scope;
+use crate::swc_util::member_name;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, CallExpr, Class, ClassMember, Expr, ExprOrSpread, ExprOrSuper,
+  Function, Ident, Lit, Prop, PropName, PropOrSpread,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferSignalAbort {
+  options: PreferSignalAbortOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PreferSignalAbortOptions {
+  /// Whether to check `fetch` calls raced against a `setTimeout` in the same
+  /// function.
+  pub check_fetch: bool,
+  /// Whether to check event listeners added by classes with a dispose method.
+  pub check_event_listeners: bool,
+  /// The names of the methods releasing the resources of a class, in addition
+  /// to `[Symbol.dispose]` and `[Symbol.asyncDispose]`.
+  pub dispose_methods: Vec<String>,
+}
+
+impl Default for PreferSignalAbortOptions {
+  fn default() -> Self {
+    Self {
+      check_fetch: true,
+      check_event_listeners: true,
+      dispose_methods: vec!["dispose".to_string(), "close".to_string()],
+    }
+  }
+}
+
+const CODE: &str = "prefer-signal-abort";
+
+#[derive(Display)]
+enum PreferSignalAbortMessage {
+  #[display(
+    fmt = "`fetch` keeps running after the timeout it's raced against"
+  )]
+  FetchRacedWithTimeout,
+  #[display(
+    fmt = "Event listener is never removed when the object is disposed"
+  )]
+  ListenerNeverRemoved,
+}
+
+#[derive(Display)]
+enum PreferSignalAbortHint {
+  #[display(
+    fmt = "Pass `{{ signal: AbortSignal.timeout(ms) }}` to `fetch` to cancel the request instead"
+  )]
+  FetchSignal,
+  #[display(
+    fmt = "Pass the `signal` of an `AbortController` aborted when the object is disposed, e.g. `{{ signal: this.#controller.signal }}`"
+  )]
+  ListenerSignal,
+}
+
+impl LintRule for PreferSignalAbort {
+  fn new() -> Box<Self> {
+    Box::new(PreferSignalAbort {
+      options: PreferSignalAbortOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferSignalAbortVisitor {
+      context,
+      options: &self.options,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "checkFetch": {
+          "description": "Whether to check `fetch` calls raced against a `setTimeout` in the same function.",
+          "type": "boolean",
+          "default": true
+        },
+        "checkEventListeners": {
+          "description": "Whether to check event listeners added by classes with a dispose method.",
+          "type": "boolean",
+          "default": true
+        },
+        "disposeMethods": {
+          "description": "The names of the methods releasing the resources of a class, in addition to `[Symbol.dispose]` and `[Symbol.asyncDispose]`.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": PreferSignalAbortOptions::default().dispose_methods
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces cancelling requests and event listeners with an `AbortSignal`",
+      "prefer-signal-abort"
+    )
+  }
+}
+
+/// Returns `false` if the options object passed to `fetch` or
+/// `addEventListener` certainly has no `signal`, i.e. it's omitted, not an
+/// object, or an object literal without a `signal` property or a spread.
+fn may_pass_signal(options: Option<&ExprOrSpread>) -> bool {
+  let options = match options {
+    Some(options) if options.spread.is_none() => &*options.expr,
+    Some(_) => return true,
+    None => return false,
+  };
+  let object = match options {
+    Expr::Object(object) => object,
+    Expr::Lit(_) => return false,
+    _ => return true,
+  };
+  object.props.iter().any(|prop| match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(kv) => match &kv.key {
+        PropName::Ident(ident) => ident.sym == *"signal",
+        PropName::Str(s) => s.value == *"signal",
+        _ => true,
+      },
+      Prop::Shorthand(ident) => ident.sym == *"signal",
+      _ => false,
+    },
+    PropOrSpread::Spread(_) => true,
+  })
+}
+
+/// Returns `true` if the listener options contain `once: true`, the listener
+/// is then removed after the first event.
+fn is_once(options: Option<&ExprOrSpread>) -> bool {
+  let object = match options.map(|options| &*options.expr) {
+    Some(Expr::Object(object)) => object,
+    _ => return false,
+  };
+  object.props.iter().any(|prop| {
+    matches!(
+      prop,
+      PropOrSpread::Prop(prop) if matches!(
+        &**prop,
+        Prop::KeyValue(kv)
+          if matches!(&kv.key, PropName::Ident(key) if key.sym == *"once")
+            && matches!(&*kv.value, Expr::Lit(Lit::Bool(value)) if value.value)
+      )
+    )
+  })
+}
+
+fn is_global(scope: &Scope, ident: &Ident, name: &str) -> bool {
+  ident.sym == *name && scope.var(&ident.to_id()).is_none()
+}
+
+struct PreferSignalAbortVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  options: &'c PreferSignalAbortOptions,
+}
+
+impl<'c, 'view> PreferSignalAbortVisitor<'c, 'view> {
+  fn check_function_body<N: for<'f> VisitWith<FetchFinder<'f>>>(
+    &mut self,
+    body: &N,
+    parent: &dyn Node,
+  ) {
+    if !self.options.check_fetch {
+      return;
+    }
+    let mut finder = FetchFinder {
+      scope: self.context.scope(),
+      fetches: Vec::new(),
+      has_timeout: false,
+      depth: 0,
+    };
+    body.visit_with(parent, &mut finder);
+    if finder.has_timeout {
+      for span in finder.fetches {
+        self.context.add_diagnostic_with_hint(
+          span,
+          CODE,
+          PreferSignalAbortMessage::FetchRacedWithTimeout,
+          PreferSignalAbortHint::FetchSignal,
+        );
+      }
+    }
+  }
+
+  fn is_dispose_method(&self, key: &PropName) -> bool {
+    match key {
+      PropName::Ident(ident) => self
+        .options
+        .dispose_methods
+        .iter()
+        .any(|name| ident.sym == *name.as_str()),
+      PropName::Str(s) => self
+        .options
+        .dispose_methods
+        .iter()
+        .any(|name| s.value == *name.as_str()),
+      PropName::Computed(computed) => matches!(
+        &*computed.expr,
+        Expr::Member(member) if matches!(
+          &member.obj,
+          ExprOrSuper::Expr(obj) if matches!(&**obj, Expr::Ident(obj) if obj.sym == *"Symbol")
+        ) && matches!(member_name(member), Some("dispose") | Some("asyncDispose"))
+      ),
+      _ => false,
+    }
+  }
+}
+
+impl<'c, 'view> Visit for PreferSignalAbortVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    if let Some(body) = &function.body {
+      self.check_function_body(body, function);
+    }
+    function.visit_children_with(self);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.check_function_body(&arrow_expr.body, arrow_expr);
+    arrow_expr.visit_children_with(self);
+  }
+
+  fn visit_class(&mut self, class: &Class, _: &dyn Node) {
+    let is_disposable = class.body.iter().any(|member| {
+      matches!(member, ClassMember::Method(method) if self.is_dispose_method(&method.key))
+    });
+    if self.options.check_event_listeners && is_disposable {
+      let mut finder = ListenerFinder {
+        listeners: Vec::new(),
+        removes_listeners: false,
+      };
+      class.body.visit_with(class, &mut finder);
+      if !finder.removes_listeners {
+        for span in finder.listeners {
+          self.context.add_diagnostic_with_hint(
+            span,
+            CODE,
+            PreferSignalAbortMessage::ListenerNeverRemoved,
+            PreferSignalAbortHint::ListenerSignal,
+          );
+        }
+      }
+    }
+    class.visit_children_with(self);
+  }
+}
+
+/// Collects the `fetch` calls without a signal made directly by a function,
+/// and whether the function or the functions in it set a timeout, e.g. in
+/// `Promise.race([fetch(url), new Promise((_, reject) => setTimeout(reject))])`.
+struct FetchFinder<'a> {
+  scope: &'a Scope,
+  fetches: Vec<Span>,
+  has_timeout: bool,
+  depth: usize,
+}
+
+impl<'a> Visit for FetchFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.depth += 1;
+    function.visit_children_with(self);
+    self.depth -= 1;
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.depth += 1;
+    arrow_expr.visit_children_with(self);
+    self.depth -= 1;
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Ident(ident) = &**callee {
+        if is_global(self.scope, ident, "setTimeout") {
+          self.has_timeout = true;
+        } else if is_global(self.scope, ident, "fetch")
+          && self.depth == 0
+          && !may_pass_signal(call_expr.args.get(1))
+        {
+          self.fetches.push(call_expr.span);
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+/// Collects the `addEventListener` calls without a signal in a class, and
+/// whether the class removes listeners at all.
+struct ListenerFinder {
+  listeners: Vec<Span>,
+  removes_listeners: bool,
+}
+
+impl Visit for ListenerFinder {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Expr::Member(member) = &**callee {
+        match member_name(member) {
+          Some("addEventListener") => {
+            let options = call_expr.args.get(2);
+            if !may_pass_signal(options) && !is_once(options) {
+              self.listeners.push(call_expr.span);
+            }
+          }
+          Some("removeEventListener") => self.removes_listeners = true,
+          _ => {}
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn prefer_signal_abort_valid() {
+    assert_lint_ok! {
+      PreferSignalAbort,
+      "async function get(url) { return await fetch(url); }",
+      "async function get(url) { return await fetch(url, { signal: AbortSignal.timeout(1000) }); }",
+      r#"
+async function get(url, signal) {
+  const timer = setTimeout(() => console.log("slow"), 1000);
+  const res = await fetch(url, { signal });
+  clearTimeout(timer);
+  return res;
+}
+"#,
+      r#"
+async function get(url, init) {
+  setTimeout(retry, 1000);
+  return await fetch(url, init);
+}
+"#,
+      r#"
+function poll(url) {
+  setTimeout(() => poll(url), 1000);
+}
+async function get(url) { return await fetch(url); }
+"#,
+      r#"
+class Watcher {
+  #controller = new AbortController();
+  constructor(target) {
+    target.addEventListener("change", this.#onChange, { signal: this.#controller.signal });
+  }
+  close() { this.#controller.abort(); }
+}
+"#,
+      r#"
+class Watcher {
+  constructor(target) {
+    this.target = target;
+    target.addEventListener("change", this.onChange);
+  }
+  close() { this.target.removeEventListener("change", this.onChange); }
+}
+"#,
+      r#"
+class Watcher {
+  constructor(target) {
+    target.addEventListener("load", this.onLoad, { once: true });
+  }
+  dispose() {}
+}
+"#,
+      r#"
+class Button {
+  constructor(el) { el.addEventListener("click", this.onClick); }
+}
+"#,
+    };
+
+    assert_lint_ok! {
+      PreferSignalAbort,
+      options: json!({ "checkFetch": false, "disposeMethods": ["stop"] }),
+      r#"
+async function get(url) {
+  return await Promise.race([fetch(url), new Promise((_, reject) => setTimeout(reject, 1000))]);
+}
+"#,
+      r#"
+class Watcher {
+  constructor(target) { target.addEventListener("change", this.onChange); }
+  close() {}
+}
+"#,
+    };
+  }
+
+  #[test]
+  fn prefer_signal_abort_invalid() {
+    assert_lint_err! {
+      PreferSignalAbort,
+      r#"async function get(url) {
+  return await Promise.race([
+    fetch(url),
+    new Promise((_, reject) => setTimeout(() => reject(new Error("timeout")), 1000)),
+  ]);
+}"#: [
+        {
+          line: 3,
+          col: 4,
+          message: PreferSignalAbortMessage::FetchRacedWithTimeout,
+          hint: PreferSignalAbortHint::FetchSignal,
+        }
+      ],
+      r#"const get = async (url) => {
+  const timeout = new Promise((resolve) => setTimeout(resolve, 1000));
+  return await Promise.race([fetch(url, { method: "GET" }), timeout]);
+};"#: [
+        {
+          line: 3,
+          col: 29,
+          message: PreferSignalAbortMessage::FetchRacedWithTimeout,
+          hint: PreferSignalAbortHint::FetchSignal,
+        }
+      ],
+      r#"class Watcher {
+  constructor(target) {
+    target.addEventListener("change", this.onChange);
+  }
+  close() {}
+}"#: [
+        {
+          line: 3,
+          col: 4,
+          message: PreferSignalAbortMessage::ListenerNeverRemoved,
+          hint: PreferSignalAbortHint::ListenerSignal,
+        }
+      ],
+      r#"class Socket {
+  open() { globalThis.addEventListener("unload", () => this.close(), true); }
+  [Symbol.dispose]() {}
+}"#: [
+        {
+          line: 2,
+          col: 11,
+          message: PreferSignalAbortMessage::ListenerNeverRemoved,
+          hint: PreferSignalAbortHint::ListenerSignal,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      PreferSignalAbort,
+      options: json!({ "disposeMethods": ["stop"] }),
+      r#"class Server {
+  start(target) { target.addEventListener("request", this.onRequest); }
+  stop() {}
+}"#: [
+        {
+          line: 2,
+          col: 18,
+          message: PreferSignalAbortMessage::ListenerNeverRemoved,
+          hint: PreferSignalAbortHint::ListenerSignal,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,347 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::{
+  is_member_object, member_name, method_call, regex_literal_text,
+};
+use derive_more::Display;
+use swc_common::{EqIgnoreSpan, Span, Spanned};
+use swc_ecmascript::ast::{
+  BinExpr, BinaryOp, CallExpr, Expr, ExprOrSuper, Lit, UnaryOp,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, VisitAll, VisitAllWith};
+
+pub struct PreferStringStartsEndsWith;
+
+const CODE: &str = "prefer-string-starts-ends-with";
+
+#[derive(Display)]
+enum PreferStringStartsEndsWithMessage {
+  #[display(fmt = "Use `startsWith` to check the start of a string")]
+  StartsWith,
+  #[display(fmt = "Use `endsWith` to check the end of a string")]
+  EndsWith,
+}
+
+#[derive(Display)]
+enum PreferStringStartsEndsWithHint {
+  #[display(fmt = "Replace the check with a `startsWith` call")]
+  StartsWith,
+  #[display(fmt = "Replace the check with an `endsWith` call")]
+  EndsWith,
+}
+
+#[derive(Display)]
+enum PreferStringStartsEndsWithFix {
+  #[display(fmt = "Replace with `startsWith`")]
+  StartsWith,
+  #[display(fmt = "Replace with `endsWith`")]
+  EndsWith,
+}
+
+impl LintRule for PreferStringStartsEndsWith {
+  fn new() -> Box<Self> {
+    Box::new(PreferStringStartsEndsWith)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferStringStartsEndsWithVisitor { context };
+    match program {
+      ProgramRef::Module(m) => m.visit_all_with(&DUMMY_NODE, &mut visitor),
+      ProgramRef::Script(s) => s.visit_all_with(&DUMMY_NODE, &mut visitor),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires `startsWith` and `endsWith` over equivalent checks of the start or end of a string",
+      "prefer-string-starts-ends-with"
+    )
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Check {
+  StartsWith,
+  EndsWith,
+}
+
+/// A check that can be written with `startsWith` or `endsWith`.
+struct Violation<'a> {
+  check: Check,
+  string: &'a Expr,
+  /// The text of the argument of the replacing call, or `None` if the check
+  /// can't be replaced safely.
+  arg: Option<String>,
+}
+
+fn number(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Lit(Lit::Num(num)) => Some(num.value),
+    _ => None,
+  }
+}
+
+/// Returns `expr` of `-expr`.
+fn negated(expr: &Expr) -> Option<&Expr> {
+  match expr {
+    Expr::Unary(unary) if unary.op == UnaryOp::Minus => Some(&unary.arg),
+    _ => None,
+  }
+}
+
+/// Returns `true` if `length` is the length of `value`, i.e. either the
+/// number of UTF-16 code units of a string literal or `value.length`.
+fn is_length_of(length: &Expr, value: &Expr) -> bool {
+  if let (Some(length), Expr::Lit(Lit::Str(value))) = (number(length), value) {
+    return length == value.value.encode_utf16().count() as f64;
+  }
+  match length {
+    Expr::Member(member) if member_name(member) == Some("length") => {
+      matches!(&member.obj, ExprOrSuper::Expr(obj) if (**obj).eq_ignore_span(value))
+    }
+    _ => false,
+  }
+}
+
+struct PreferStringStartsEndsWithVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> PreferStringStartsEndsWithVisitor<'c, 'view> {
+  fn snippet(&self, span: Span) -> Option<String> {
+    self.context.source_map().span_to_snippet(span).ok()
+  }
+
+  /// Checks `call === other` for `indexOf` and `slice` calls.
+  fn comparison<'a>(
+    &self,
+    call: &'a Expr,
+    other: &'a Expr,
+  ) -> Option<Violation<'a>> {
+    let (string, method, args) = match call {
+      Expr::Call(call) => method_call(call)?,
+      _ => return None,
+    };
+    let (check, arg) = match (method, args) {
+      ("indexOf", [search]) if number(other) == Some(0.0) => {
+        (Check::StartsWith, Some(&*search.expr))
+      }
+      ("slice", [start, end]) if number(&start.expr) == Some(0.0) => (
+        Check::StartsWith,
+        Some(&*end.expr).filter(|end| is_length_of(end, other)),
+      ),
+      ("slice", [start]) => {
+        let length = negated(&start.expr)?;
+        (
+          Check::EndsWith,
+          Some(length).filter(|length| is_length_of(length, other)),
+        )
+      }
+      _ => return None,
+    };
+    // `s.slice(-3) === "ab"` is always false, not an `endsWith` check
+    if arg.is_none() && matches!(other, Expr::Lit(Lit::Str(_))) {
+      return None;
+    }
+    let arg = match (method, arg) {
+      ("indexOf", Some(search)) => self.snippet(search.span()),
+      (_, Some(_)) => self.snippet(other.span()),
+      (_, None) => None,
+    };
+    Some(Violation { check, string, arg })
+  }
+
+  fn report(&mut self, span: Span, violation: Violation, negate: bool) {
+    let (message, hint, fix, method) = match violation.check {
+      Check::StartsWith => (
+        PreferStringStartsEndsWithMessage::StartsWith,
+        PreferStringStartsEndsWithHint::StartsWith,
+        PreferStringStartsEndsWithFix::StartsWith,
+        "startsWith",
+      ),
+      Check::EndsWith => (
+        PreferStringStartsEndsWithMessage::EndsWith,
+        PreferStringStartsEndsWithHint::EndsWith,
+        PreferStringStartsEndsWithFix::EndsWith,
+        "endsWith",
+      ),
+    };
+    let string = self.snippet(violation.string.span()).map(|string| {
+      if is_member_object(violation.string) {
+        string
+      } else {
+        format!("({})", string)
+      }
+    });
+    match (string, violation.arg) {
+      (Some(string), Some(arg)) => {
+        let not = if negate { "!" } else { "" };
+        let fixed = format!("{}{}.{}({})", not, string, method, arg);
+        let fix = self.context.create_fix(fix, vec![(span, fixed)]);
+        self.context.add_diagnostic_with_fixes(
+          span,
+          CODE,
+          message,
+          Some(hint.to_string()),
+          vec![fix],
+        );
+      }
+      _ => {
+        self
+          .context
+          .add_diagnostic_with_hint(span, CODE, message, hint);
+      }
+    }
+  }
+}
+
+impl<'c, 'view> VisitAll for PreferStringStartsEndsWithVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    let negate = match bin_expr.op {
+      BinaryOp::EqEq | BinaryOp::EqEqEq => false,
+      BinaryOp::NotEq | BinaryOp::NotEqEq => true,
+      _ => return,
+    };
+    let violation = self
+      .comparison(&bin_expr.left, &bin_expr.right)
+      .or_else(|| self.comparison(&bin_expr.right, &bin_expr.left));
+    if let Some(violation) = violation {
+      self.report(bin_expr.span, violation, negate);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    let (regex, arg) = match method_call(call_expr) {
+      Some((Expr::Lit(Lit::Regex(regex)), "test", [arg])) => (regex, arg),
+      _ => return,
+    };
+    // Flags change what is matched, e.g. `i` or `m`
+    if !regex.flags.is_empty() {
+      return;
+    }
+    let (check, text) = match regex.exp.strip_prefix('^') {
+      Some(start) => (Check::StartsWith, regex_literal_text(start)),
+      None => (
+        Check::EndsWith,
+        regex.exp.strip_suffix('$').and_then(regex_literal_text),
+      ),
+    };
+    if let Some(text) = text {
+      let violation = Violation {
+        check,
+        string: &arg.expr,
+        arg: Some(text),
+      };
+      self.report(call_expr.span, violation, false);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_string_starts_ends_with_valid() {
+    assert_lint_ok! {
+      PreferStringStartsEndsWith,
+      "a.startsWith(b);",
+      "a.indexOf(b) === 1;",
+      "a.indexOf(b) > 0;",
+      "a.indexOf(b, 1) === 0;",
+      "a.slice(-3) === 'ab';",
+      "a.slice(1) === b;",
+      "a.slice(0, 2) === 'abc';",
+      "/^foo$/.test(a);",
+      "/^foo/i.test(a);",
+      "/^fo+/.test(a);",
+      "/^\\d/.test(a);",
+      "/foo/.test(a);",
+      "/^/.test(a);",
+    };
+  }
+
+  #[test]
+  fn prefer_string_starts_ends_with_invalid() {
+    assert_lint_err! {
+      PreferStringStartsEndsWith,
+      "a.indexOf(b) === 0;": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: (PreferStringStartsEndsWithFix::StartsWith, "a.startsWith(b);"),
+        }
+      ],
+      "if (0 != a.b.indexOf('c')) {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: (PreferStringStartsEndsWithFix::StartsWith, "if (!a.b.startsWith('c')) {}"),
+        }
+      ],
+      "a.slice(-3) === 'abc';": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+          fix: (PreferStringStartsEndsWithFix::EndsWith, "a.endsWith('abc');"),
+        }
+      ],
+      "a.slice(-b.length) !== b;": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+          fix: (PreferStringStartsEndsWithFix::EndsWith, "!a.endsWith(b);"),
+        }
+      ],
+      "a.slice(0, 2) == 'ab';": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: (PreferStringStartsEndsWithFix::StartsWith, "a.startsWith('ab');"),
+        }
+      ],
+      "a.slice(-n) === b;": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+        }
+      ],
+      "/^foo\\.bar/.test(a);": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: (PreferStringStartsEndsWithFix::StartsWith, "a.startsWith(\"foo.bar\");"),
+        }
+      ],
+      "/\"q\"$/.test(a + b);": [
+        {
+          col: 0,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+          fix: (PreferStringStartsEndsWithFix::EndsWith, "(a + b).endsWith(\"\\\"q\\\"\");"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,195 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_ecmascript::ast::{CallExpr, Expr, ExprOrSpread, ExprOrSuper};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct PreferStructuredClone;
+
+const CODE: &str = "prefer-structured-clone";
+
+#[derive(Display)]
+enum PreferStructuredCloneMessage {
+  #[display(fmt = "Prefer `structuredClone` over a JSON round trip")]
+  JsonRoundTrip,
+}
+
+#[derive(Display)]
+enum PreferStructuredCloneHint {
+  #[display(
+    fmt = "Use `structuredClone(value)`, which also copies `Date`s, `Map`s, `Set`s and cyclic references"
+  )]
+  UseStructuredClone,
+}
+
+impl LintRule for PreferStructuredClone {
+  fn new() -> Box<Self> {
+    Box::new(PreferStructuredClone)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = PreferStructuredCloneVisitor { context };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces the use of `structuredClone` over `JSON.parse(JSON.stringify(x))`",
+      "prefer-structured-clone"
+    )
+  }
+}
+
+struct PreferStructuredCloneVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+}
+
+impl<'c, 'view> PreferStructuredCloneVisitor<'c, 'view> {
+  /// Returns the only argument of a call to `JSON.<method>`, provided `JSON`
+  /// is the global object and the argument isn't spread.
+  fn json_call_arg<'a>(
+    &self,
+    call_expr: &'a CallExpr,
+    method: &str,
+  ) -> Option<&'a Expr> {
+    let member = match &call_expr.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) if !member.computed => member,
+        _ => return None,
+      },
+      ExprOrSuper::Super(_) => return None,
+    };
+    let is_global_json = matches!(
+      &member.obj,
+      ExprOrSuper::Expr(obj) if matches!(
+        &**obj,
+        Expr::Ident(ident)
+          if ident.sym == *"JSON"
+            && self.context.scope().var(&ident.to_id()).is_none()
+      )
+    );
+    if !is_global_json
+      || !matches!(&*member.prop, Expr::Ident(prop) if prop.sym == *method)
+    {
+      return None;
+    }
+    // A reviver or a replacer changes the result, so it's not a plain clone
+    match call_expr.args.as_slice() {
+      [ExprOrSpread { spread: None, expr }] => Some(expr),
+      _ => None,
+    }
+  }
+
+  /// Returns the cloned value if the call is `JSON.parse(JSON.stringify(x))`.
+  fn cloned_value<'a>(&self, call_expr: &'a CallExpr) -> Option<&'a Expr> {
+    let mut arg = self.json_call_arg(call_expr, "parse")?;
+    while let Expr::Paren(paren) = arg {
+      arg = &paren.expr;
+    }
+    match arg {
+      Expr::Call(inner) => self.json_call_arg(inner, "stringify"),
+      _ => None,
+    }
+  }
+}
+
+impl<'c, 'view> Visit for PreferStructuredCloneVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    // No fix: `structuredClone` throws on functions and symbols and ignores
+    // `toJSON`, so replacing the round trip can change behavior
+    if self.cloned_value(call_expr).is_some() {
+      self.context.add_diagnostic_with_hint(
+        call_expr.span,
+        CODE,
+        PreferStructuredCloneMessage::JsonRoundTrip,
+        PreferStructuredCloneHint::UseStructuredClone,
+      );
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_structured_clone_valid() {
+    assert_lint_ok! {
+      PreferStructuredClone,
+      "const copy = structuredClone(value);",
+      "const data = JSON.parse(text);",
+      "const text = JSON.stringify(value);",
+      "const copy = JSON.parse(JSON.stringify(value, replacer));",
+      "const copy = JSON.parse(JSON.stringify(value), reviver);",
+      "const copy = JSON.parse(JSON.stringify(value, null, 2));",
+      "const copy = JSON.parse(JSON.stringify(...args));",
+      "const copy = JSON.parse(toJSON(value));",
+      "const copy = JSON.parse(String(JSON.stringify(value)));",
+      "const copy = JSON[parse](JSON.stringify(value));",
+      "const copy = YAML.parse(YAML.stringify(value));",
+      "import { JSON } from './json.ts'; const copy = JSON.parse(JSON.stringify(value));",
+      "function f(JSON) { return JSON.parse(JSON.stringify(value)); }",
+    };
+  }
+
+  #[test]
+  fn prefer_structured_clone_invalid() {
+    assert_lint_err! {
+      PreferStructuredClone,
+      "const copy = JSON.parse(JSON.stringify(value));": [
+        {
+          col: 13,
+          message: PreferStructuredCloneMessage::JsonRoundTrip,
+          hint: PreferStructuredCloneHint::UseStructuredClone,
+        }
+      ],
+      "const copy = JSON.parse((JSON.stringify(foo.bar[0])));": [
+        {
+          col: 13,
+          message: PreferStructuredCloneMessage::JsonRoundTrip,
+          hint: PreferStructuredCloneHint::UseStructuredClone,
+        }
+      ],
+      "const copy = JSON.parse(JSON.stringify(a ?? b)) as Foo;": [
+        {
+          col: 13,
+          message: PreferStructuredCloneMessage::JsonRoundTrip,
+          hint: PreferStructuredCloneHint::UseStructuredClone,
+        }
+      ],
+      "const copy = JSON.parse(JSON.stringify(/* state */ state));": [
+        {
+          col: 13,
+          message: PreferStructuredCloneMessage::JsonRoundTrip,
+          hint: PreferStructuredCloneHint::UseStructuredClone,
+        }
+      ],
+      "function f() { return JSON.parse(JSON.stringify(this)); }": [
+        {
+          col: 22,
+          message: PreferStructuredCloneMessage::JsonRoundTrip,
+          hint: PreferStructuredCloneHint::UseStructuredClone,
+        }
+      ],
+    };
+  }
+}
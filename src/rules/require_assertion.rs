@@ -0,0 +1,315 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::deno_test_fn;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use swc_ecmascript::ast::{
+  CallExpr, Expr, ExprOrSuper, ImportSpecifier, ModuleDecl, ModuleItem,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct RequireAssertion {
+  options: RequireAssertionOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RequireAssertionOptions {
+  /// Patterns matched against import specifiers, an import is an assertion
+  /// module if its specifier contains any of them.
+  pub assertion_modules: Vec<String>,
+}
+
+impl Default for RequireAssertionOptions {
+  fn default() -> Self {
+    Self {
+      assertion_modules: vec![
+        "@std/assert".to_string(),
+        "@std/expect".to_string(),
+        "/assert/".to_string(),
+        "/expect/".to_string(),
+        "/testing/asserts".to_string(),
+        "/testing/mock".to_string(),
+        "node:assert".to_string(),
+        "chai".to_string(),
+      ],
+    }
+  }
+}
+
+const CODE: &str = "require-assertion";
+
+#[derive(Display)]
+enum RequireAssertionMessage {
+  #[display(fmt = "Test never calls an assertion")]
+  NoAssertion,
+}
+
+#[derive(Display)]
+enum RequireAssertionHint {
+  #[display(
+    fmt = "Check the outcome with a function imported from an assertion module, e.g. `assertEquals()`"
+  )]
+  AddAssertion,
+}
+
+impl LintRule for RequireAssertion {
+  fn new() -> Box<Self> {
+    Box::new(RequireAssertion {
+      options: RequireAssertionOptions::default(),
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let assertions = match program {
+      ProgramRef::Module(module) => {
+        assertion_imports(&module.body, &self.options.assertion_modules)
+      }
+      ProgramRef::Script(_) => HashSet::new(),
+    };
+    let mut visitor = RequireAssertionVisitor {
+      context,
+      assertions,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "assertionModules": {
+          "description": "Patterns matched against import specifiers, an import is an assertion module if its specifier contains any of them.",
+          "type": "array",
+          "items": { "type": "string" },
+          "default": RequireAssertionOptions::default().assertion_modules
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires tests to call a function from an assertion module",
+      "require-assertion"
+    )
+  }
+}
+
+/// Returns the local names of everything imported from assertion modules,
+/// including namespace and default imports.
+fn assertion_imports(items: &[ModuleItem], patterns: &[String]) -> HashSet<Id> {
+  items
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+        Some(import_decl)
+      }
+      _ => None,
+    })
+    .filter(|import_decl| {
+      patterns
+        .iter()
+        .any(|pattern| import_decl.src.value.contains(pattern.as_str()))
+    })
+    .flat_map(|import_decl| &import_decl.specifiers)
+    .map(|specifier| match specifier {
+      ImportSpecifier::Named(named) => named.local.to_id(),
+      ImportSpecifier::Default(default) => default.local.to_id(),
+      ImportSpecifier::Namespace(namespace) => namespace.local.to_id(),
+    })
+    .collect()
+}
+
+/// Returns the identifier a callee starts from, e.g. `expect` for
+/// `expect(a).toBe` and `asserts` for `asserts.equal`.
+fn callee_root(mut expr: &Expr) -> Option<&Expr> {
+  loop {
+    expr = match expr {
+      Expr::Ident(_) => return Some(expr),
+      Expr::Member(member) => match &member.obj {
+        ExprOrSuper::Expr(obj) => obj,
+        ExprOrSuper::Super(_) => return None,
+      },
+      Expr::Call(call) => match &call.callee {
+        ExprOrSuper::Expr(callee) => callee,
+        ExprOrSuper::Super(_) => return None,
+      },
+      Expr::Paren(paren) => &paren.expr,
+      _ => return None,
+    };
+  }
+}
+
+struct RequireAssertionVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  assertions: HashSet<Id>,
+}
+
+/// Looks for a call to an assertion, anywhere in a test including the
+/// functions defined in it, e.g. test steps.
+struct AssertionFinder<'a> {
+  assertions: &'a HashSet<Id>,
+  found: bool,
+}
+
+impl<'a> Visit for AssertionFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if let Some(Expr::Ident(ident)) = callee_root(callee) {
+        if self.assertions.contains(&ident.to_id()) {
+          self.found = true;
+          return;
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+impl<'c, 'view> Visit for RequireAssertionVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let Some(test_fn) = deno_test_fn(call_expr) {
+      let mut finder = AssertionFinder {
+        assertions: &self.assertions,
+        found: false,
+      };
+      test_fn.visit_body_with(&mut finder);
+      if !finder.found {
+        self.context.add_diagnostic_with_hint(
+          call_expr.span,
+          CODE,
+          RequireAssertionMessage::NoAssertion,
+          RequireAssertionHint::AddAssertion,
+        );
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn require_assertion_valid() {
+    assert_lint_ok! {
+      RequireAssertion,
+      r#"
+import { assertEquals } from "jsr:@std/assert";
+Deno.test("adds", () => {
+  assertEquals(add(1, 2), 3);
+});
+"#,
+      r#"
+import { assertEquals } from "https://deno.land/std@0.200.0/testing/asserts.ts";
+Deno.test("adds", () => assertEquals(add(1, 2), 3));
+"#,
+      r#"
+import { expect } from "jsr:@std/expect";
+Deno.test({ name: "adds", fn() { expect(add(1, 2)).toBe(3); } });
+"#,
+      r#"
+import * as asserts from "jsr:@std/assert";
+Deno.test("adds", async (t) => {
+  await t.step("positive", () => {
+    asserts.assertEquals(add(1, 2), 3);
+  });
+});
+"#,
+      r#"
+import assert from "node:assert";
+Deno.test("adds", async () => {
+  await Promise.all([1, 2].map(async (n) => assert(await check(n))));
+});
+"#,
+      "Deno.bench('adds', () => { add(1, 2); });",
+      "test('adds', () => { add(1, 2); });",
+    };
+
+    assert_lint_ok! {
+      RequireAssertion,
+      options: json!({ "assertionModules": ["./test_util.ts"] }),
+      r#"
+import { check } from "./test_util.ts";
+Deno.test("adds", () => { check(add(1, 2), 3); });
+"#,
+    };
+  }
+
+  #[test]
+  fn require_assertion_invalid() {
+    assert_lint_err! {
+      RequireAssertion,
+      r#"import { assertEquals } from "jsr:@std/assert";
+Deno.test("adds", () => {
+  add(1, 2);
+});"#: [
+        {
+          line: 2,
+          col: 0,
+          message: RequireAssertionMessage::NoAssertion,
+          hint: RequireAssertionHint::AddAssertion,
+        }
+      ],
+      r#"import { assertEquals } from "jsr:@std/assert";
+Deno.test("adds", () => { assertEquals(add(1, 2), 3); });
+Deno.test("subtracts", () => { const assertEquals = () => {}; assertEquals(sub(2, 1), 1); });"#: [
+        {
+          line: 3,
+          col: 0,
+          message: RequireAssertionMessage::NoAssertion,
+          hint: RequireAssertionHint::AddAssertion,
+        }
+      ],
+      "import { assertEquals } from './helpers.ts';\nDeno.test({ name: 'adds', fn: () => { assertEquals(add(1, 2), 3); } });": [
+        {
+          line: 2,
+          col: 0,
+          message: RequireAssertionMessage::NoAssertion,
+          hint: RequireAssertionHint::AddAssertion,
+        }
+      ],
+      "Deno.test('adds', () => {});": [
+        {
+          col: 0,
+          message: RequireAssertionMessage::NoAssertion,
+          hint: RequireAssertionHint::AddAssertion,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,314 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, CatchClause, Expr, Function, Ident, NewExpr, Pat, ThrowStmt,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::{find_ids, Id};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct RequireErrorCause;
+
+const CODE: &str = "require-error-cause";
+
+/// The built-in errors whose constructor accepts `{ cause }` as the second
+/// argument.
+const BUILTIN_ERRORS: &[&str] = &[
+  "Error",
+  "EvalError",
+  "RangeError",
+  "ReferenceError",
+  "SyntaxError",
+  "TypeError",
+  "URIError",
+];
+
+#[derive(Display)]
+enum RequireErrorCauseMessage {
+  #[display(fmt = "The thrown error doesn't reference the caught error")]
+  MissingCause,
+}
+
+#[derive(Display)]
+enum RequireErrorCauseHint {
+  #[display(
+    fmt = "Pass the caught error as the `cause`, e.g. `new Error(\"...\", {{ cause: err }})`"
+  )]
+  AddCause,
+}
+
+#[derive(Display)]
+enum RequireErrorCauseFix {
+  #[display(fmt = "Add `{{ cause: {} }}`", _0)]
+  AddCause(String),
+}
+
+impl LintRule for RequireErrorCause {
+  fn new() -> Box<Self> {
+    Box::new(RequireErrorCause)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn fixable(&self) -> bool {
+    true
+  }
+
+  fn needs_scope(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = RequireErrorCauseVisitor {
+      context,
+      caught: None,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires errors thrown in `catch` blocks to reference the caught error",
+      "require-error-cause"
+    )
+  }
+}
+
+/// The errors caught by the `catch` blocks around a statement.
+struct Caught {
+  /// The bindings of the caught errors, including those of outer `catch`
+  /// blocks of the same function.
+  ids: Vec<Id>,
+  /// The name of the caught error of the closest `catch` block, if it's bound
+  /// to a plain identifier.
+  name: Option<String>,
+}
+
+/// Finds whether an expression references one of the given bindings.
+struct ReferenceFinder<'a> {
+  ids: &'a [Id],
+  found: bool,
+}
+
+impl<'a> Visit for ReferenceFinder<'a> {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, ident: &Ident, _: &dyn Node) {
+    if self.ids.contains(&ident.to_id()) {
+      self.found = true;
+    }
+  }
+}
+
+/// Returns the name of the constructor in `new Foo(...)` if it looks like an
+/// error class, i.e. its name ends with `Error`.
+fn error_class(new_expr: &NewExpr) -> Option<&Ident> {
+  match &*new_expr.callee {
+    Expr::Ident(ident) if ident.sym.ends_with("Error") => Some(ident),
+    _ => None,
+  }
+}
+
+struct RequireErrorCauseVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  caught: Option<Caught>,
+}
+
+impl<'c, 'view> RequireErrorCauseVisitor<'c, 'view> {
+  fn with_caught<N: VisitWith<Self>>(
+    &mut self,
+    caught: Option<Caught>,
+    node: &N,
+  ) {
+    let prev = std::mem::replace(&mut self.caught, caught);
+    node.visit_children_with(self);
+    self.caught = prev;
+  }
+
+  /// Returns the position to insert `, { cause: err }` at, if the error is a
+  /// built-in one that is constructed with only a message.
+  fn cause_position(&self, new_expr: &NewExpr, class: &Ident) -> Option<Span> {
+    let is_builtin = BUILTIN_ERRORS.contains(&&*class.sym)
+      && self.context.scope().var(&class.to_id()).is_none();
+    match new_expr.args.as_deref() {
+      Some([message]) if is_builtin && message.spread.is_none() => {
+        let pos = message.expr.span().hi;
+        Some(Span::new(pos, pos, new_expr.span.ctxt))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl<'c, 'view> Visit for RequireErrorCauseVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.with_caught(None, function);
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.with_caught(None, arrow_expr);
+  }
+
+  fn visit_catch_clause(&mut self, catch_clause: &CatchClause, _: &dyn Node) {
+    let mut ids = self
+      .caught
+      .as_ref()
+      .map(|caught| caught.ids.clone())
+      .unwrap_or_default();
+    if let Some(param) = &catch_clause.param {
+      ids.extend(find_ids::<_, Id>(param));
+    }
+    let name = match &catch_clause.param {
+      Some(Pat::Ident(binding)) => Some(binding.id.sym.to_string()),
+      _ => None,
+    };
+    self.with_caught(Some(Caught { ids, name }), catch_clause);
+  }
+
+  fn visit_throw_stmt(&mut self, throw_stmt: &ThrowStmt, _: &dyn Node) {
+    throw_stmt.visit_children_with(self);
+
+    let caught = match &self.caught {
+      Some(caught) => caught,
+      None => return,
+    };
+    let new_expr = match &*throw_stmt.arg {
+      Expr::New(new_expr) => new_expr,
+      _ => return,
+    };
+    let class = match error_class(new_expr) {
+      Some(class) => class,
+      None => return,
+    };
+    let mut finder = ReferenceFinder {
+      ids: &caught.ids,
+      found: false,
+    };
+    if let Some(args) = &new_expr.args {
+      for arg in args {
+        arg.visit_with(&DUMMY_NODE, &mut finder);
+      }
+    }
+    if finder.found {
+      return;
+    }
+
+    let name = caught.name.clone();
+    match (name, self.cause_position(new_expr, class)) {
+      (Some(name), Some(position)) => {
+        let fix = self.context.create_fix(
+          RequireErrorCauseFix::AddCause(name.clone()),
+          vec![(position, format!(", {{ cause: {} }}", name))],
+        );
+        self.context.add_diagnostic_with_fixes(
+          new_expr.span,
+          CODE,
+          RequireErrorCauseMessage::MissingCause,
+          Some(RequireErrorCauseHint::AddCause.to_string()),
+          vec![fix],
+        );
+      }
+      _ => {
+        self.context.add_diagnostic_with_hint(
+          new_expr.span,
+          CODE,
+          RequireErrorCauseMessage::MissingCause,
+          RequireErrorCauseHint::AddCause,
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn require_error_cause_valid() {
+    assert_lint_ok! {
+      RequireErrorCause,
+      "throw new Error('a');",
+      "try { a(); } catch (err) { throw err; }",
+      "try { a(); } catch (err) { throw new Error('a failed', { cause: err }); }",
+      "try { a(); } catch (cause) { throw new Error('a failed', { cause }); }",
+      "try { a(); } catch (err) { throw new Error(`a failed: ${err.message}`); }",
+      "try { a(); } catch (err) { throw new Error('a failed: ' + String(err)); }",
+      "try { a(); } catch ({ message }) { throw new TypeError(message); }",
+      "try { a(); } catch (err) { try { b(); } catch { throw new Error('b', { cause: err }); } }",
+      "try { a(); } catch (err) { throw createError(); }",
+      "try { a(); } catch (err) { return () => { throw new Error('a'); }; }",
+      "try { a(); } catch (err) { throw new errors.NotFound('a'); }",
+    };
+  }
+
+  #[test]
+  fn require_error_cause_invalid() {
+    assert_lint_err! {
+      RequireErrorCause,
+      "try { a(); } catch (err) { throw new Error('a failed'); }": [
+        {
+          col: 33,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+          fix: (
+            variant!(RequireErrorCauseFix, AddCause, "err"),
+            "try { a(); } catch (err) { throw new Error('a failed', { cause: err }); }"
+          ),
+        }
+      ],
+      "try { a(); } catch (e) { if (e.code) { throw new RangeError(`${code}`); } }": [
+        {
+          col: 45,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+          fix: (
+            variant!(RequireErrorCauseFix, AddCause, "e"),
+            "try { a(); } catch (e) { if (e.code) { throw new RangeError(`${code}`, { cause: e }); } }"
+          ),
+        }
+      ],
+      "try { a(); } catch { throw new Error('a failed'); }": [
+        {
+          col: 27,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+        }
+      ],
+      "try { a(); } catch (err) { throw new HttpError(500, 'a failed'); }": [
+        {
+          col: 33,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+        }
+      ],
+      "class Error {} try { a(); } catch (err) { throw new Error('a'); }": [
+        {
+          col: 48,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+        }
+      ],
+      "try { a(); } catch (err) { const message = 'a'; throw new Error(message, b); }": [
+        {
+          col: 54,
+          message: RequireErrorCauseMessage::MissingCause,
+          hint: RequireErrorCauseHint::AddCause,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,284 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::swc_util::method_chain;
+use derive_more::Display;
+use swc_common::Span;
+use swc_ecmascript::ast::{
+  ArrowExpr, Expr, ExprStmt, Function, Ident, MemberExpr, Module, Pat,
+  PropName, Script, VarDeclarator,
+};
+use swc_ecmascript::utils::ident::IdentLike;
+use swc_ecmascript::utils::Id;
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct RequireKvAtomicCommit;
+
+const CODE: &str = "require-kv-atomic-commit";
+
+#[derive(Display)]
+enum RequireKvAtomicCommitMessage {
+  #[display(fmt = "Atomic operation is never committed")]
+  NotCommitted,
+}
+
+#[derive(Display)]
+enum RequireKvAtomicCommitHint {
+  #[display(
+    fmt = "Call `.commit()` on the atomic operation, otherwise none of its mutations are applied"
+  )]
+  Commit,
+}
+
+impl LintRule for RequireKvAtomicCommit {
+  fn new() -> Box<Self> {
+    Box::new(RequireKvAtomicCommit)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = RequireKvAtomicCommitVisitor {
+      context,
+      frames: vec![],
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires Deno KV atomic operations to be committed",
+      "require-kv-atomic-commit"
+    )
+  }
+}
+
+/// Returns the span of the `.atomic()` call in a chain of calls that doesn't
+/// end with `.commit()`.
+fn uncommitted_atomic(expr: &Expr) -> Option<Span> {
+  let (methods, _) = method_chain(expr);
+  // Methods are ordered from the last call to the first one
+  for (method, call) in methods {
+    match method {
+      "commit" => return None,
+      "atomic" if call.args.is_empty() => return Some(call.span),
+      _ => {}
+    }
+  }
+  None
+}
+
+struct RequireKvAtomicCommitVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  /// Variables holding an uncommitted atomic operation, for each enclosing
+  /// function.
+  frames: Vec<Vec<(Id, Span)>>,
+}
+
+impl<'c, 'view> RequireKvAtomicCommitVisitor<'c, 'view> {
+  fn with_frame(&mut self, f: impl FnOnce(&mut Self)) {
+    self.frames.push(vec![]);
+    f(self);
+    for (_, span) in self.frames.pop().unwrap() {
+      self.report(span);
+    }
+  }
+
+  fn report(&mut self, span: Span) {
+    self.context.add_diagnostic_with_hint(
+      span,
+      CODE,
+      RequireKvAtomicCommitMessage::NotCommitted,
+      RequireKvAtomicCommitHint::Commit,
+    );
+  }
+
+  /// Stops tracking the variable.
+  fn resolve(&mut self, id: &Id) {
+    for frame in self.frames.iter_mut().rev() {
+      if let Some(index) = frame.iter().position(|(pending, _)| pending == id) {
+        frame.remove(index);
+        return;
+      }
+    }
+  }
+
+  fn is_pending(&self, id: &Id) -> bool {
+    self
+      .frames
+      .iter()
+      .any(|frame| frame.iter().any(|(pending, _)| pending == id))
+  }
+}
+
+impl<'c, 'view> Visit for RequireKvAtomicCommitVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_module(&mut self, module: &Module, _: &dyn Node) {
+    self.with_frame(|a| module.visit_children_with(a));
+  }
+
+  fn visit_script(&mut self, script: &Script, _: &dyn Node) {
+    self.with_frame(|a| script.visit_children_with(a));
+  }
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.with_frame(|a| function.visit_children_with(a));
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.with_frame(|a| arrow_expr.visit_children_with(a));
+  }
+
+  fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt, _: &dyn Node) {
+    let expr = match &*expr_stmt.expr {
+      Expr::Await(await_expr) => &*await_expr.arg,
+      expr => expr,
+    };
+    if let Some(span) = uncommitted_atomic(expr) {
+      self.report(span);
+    }
+    expr_stmt.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    declarator.visit_children_with(self);
+    if let (Pat::Ident(binding), Some(init)) =
+      (&declarator.name, &declarator.init)
+    {
+      if let Some(span) = uncommitted_atomic(init) {
+        self
+          .frames
+          .last_mut()
+          .unwrap()
+          .push((binding.id.to_id(), span));
+      }
+    }
+  }
+
+  fn visit_expr(&mut self, expr: &Expr, _: &dyn Node) {
+    let (methods, root) = method_chain(expr);
+    match root {
+      Expr::Ident(ident) if !methods.is_empty() => {
+        let id = ident.to_id();
+        if self.is_pending(&id) {
+          // Adding mutations to the operation keeps it pending, committing it
+          // resolves it
+          if methods.iter().any(|(method, _)| *method == "commit") {
+            self.resolve(&id);
+          }
+          for (_, call) in methods {
+            call.args.visit_with(call, self);
+          }
+          return;
+        }
+      }
+      _ => {}
+    }
+    expr.visit_children_with(self);
+  }
+
+  fn visit_member_expr(&mut self, member_expr: &MemberExpr, _: &dyn Node) {
+    member_expr.obj.visit_with(member_expr, self);
+    if member_expr.computed {
+      member_expr.prop.visit_with(member_expr, self);
+    }
+  }
+
+  fn visit_prop_name(&mut self, prop_name: &PropName, _: &dyn Node) {
+    if let PropName::Computed(computed) = prop_name {
+      computed.visit_with(prop_name, self);
+    }
+  }
+
+  fn visit_ident(&mut self, ident: &Ident, _: &dyn Node) {
+    // Any other use of the operation, e.g. returning it or passing it to a
+    // function, may commit it elsewhere
+    self.resolve(&ident.to_id());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn require_kv_atomic_commit_valid() {
+    assert_lint_ok! {
+      RequireKvAtomicCommit,
+      "await kv.atomic().check(entry).set(['users', id], user).commit();",
+      "const res = await kv.atomic().set(['a'], 1).commit();",
+      r#"
+const op = kv.atomic();
+op.set(['a'], 1);
+op.delete(['b']);
+await op.commit();
+"#,
+      r#"
+const op = kv.atomic().check(entry);
+for (const item of items) op.set(['items', item.id], item);
+const res = await op.commit();
+"#,
+      r#"
+function prepare(kv) {
+  const op = kv.atomic().set(['a'], 1);
+  return op;
+}
+"#,
+      r#"
+const op = kv.atomic();
+await retry(() => op.commit());
+"#,
+      "const op = kv.atomic(); submit(op);",
+      "const op = kv.atomic(); const ops = { op };",
+      "foo.atomic(1);",
+    };
+  }
+
+  #[test]
+  fn require_kv_atomic_commit_invalid() {
+    assert_lint_err! {
+      RequireKvAtomicCommit,
+      "await kv.atomic().set(['users', id], user);": [
+        {
+          col: 6,
+          message: RequireKvAtomicCommitMessage::NotCommitted,
+          hint: RequireKvAtomicCommitHint::Commit,
+        }
+      ],
+      "kv.atomic().check(entry).delete(['users', id]);": [
+        {
+          col: 0,
+          message: RequireKvAtomicCommitMessage::NotCommitted,
+          hint: RequireKvAtomicCommitHint::Commit,
+        }
+      ],
+      "const op = kv.atomic(); op.set(['a'], 1); op.delete(['b']);": [
+        {
+          col: 11,
+          message: RequireKvAtomicCommitMessage::NotCommitted,
+          hint: RequireKvAtomicCommitHint::Commit,
+        }
+      ],
+      "async function save(kv) { const op = kv.atomic().set(['a'], 1); await op.check(entry); } const x = { op: 1 };": [
+        {
+          col: 37,
+          message: RequireKvAtomicCommitMessage::NotCommitted,
+          hint: RequireKvAtomicCommitHint::Commit,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,559 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use derive_more::Display;
+use swc_common::{Span, Spanned};
+use swc_ecmascript::ast::{
+  ArrowExpr, BinExpr, BinaryOp, CallExpr, CondExpr, DefaultDecl, DoWhileStmt,
+  ExportDefaultDecl, ExportDefaultExpr, Expr, ExprOrSpread, ExprOrSuper,
+  FnDecl, FnExpr, ForInStmt, ForOfStmt, ForStmt, Function, IfStmt,
+  OptChainExpr, Pat, ReturnStmt, SwitchStmt, TryStmt, VarDeclarator, WhileStmt,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct RulesOfHooks;
+
+const CODE: &str = "rules-of-hooks";
+
+#[derive(Display)]
+enum RulesOfHooksMessage {
+  #[display(fmt = "Hook `{}` is called conditionally", _0)]
+  Conditional(String),
+  #[display(fmt = "Hook `{}` is called in a loop", _0)]
+  Loop(String),
+  #[display(fmt = "Hook `{}` is called in a nested function", _0)]
+  NestedFunction(String),
+  #[display(
+    fmt = "Hook `{}` is called outside of a component or custom hook",
+    _0
+  )]
+  OutsideComponent(String),
+}
+
+#[derive(Display)]
+enum RulesOfHooksHint {
+  #[display(
+    fmt = "Hooks must be called in the same order on every render, call it at the top level of the component or custom hook"
+  )]
+  TopLevel,
+  #[display(
+    fmt = "Call it from a component, whose name starts with an uppercase letter, or from a custom hook, whose name starts with `use`"
+  )]
+  ComponentOrHook,
+}
+
+impl LintRule for RulesOfHooks {
+  fn new() -> Box<Self> {
+    Box::new(RulesOfHooks)
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let mut visitor = RulesOfHooksVisitor {
+      context,
+      frames: Vec::new(),
+      pending_kind: None,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Enforces that hooks are called unconditionally from components and custom hooks",
+      "rules-of-hooks"
+    )
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionKind {
+  Component,
+  Hook,
+  Other,
+}
+
+impl FunctionKind {
+  fn from_name(name: &str) -> Self {
+    if is_hook_name(name) {
+      FunctionKind::Hook
+    } else if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+      FunctionKind::Component
+    } else {
+      FunctionKind::Other
+    }
+  }
+}
+
+/// Returns `true` for names like `useState` or `useSignal`.
+fn is_hook_name(name: &str) -> bool {
+  match name.strip_prefix("use") {
+    Some(rest) => {
+      rest.starts_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit())
+    }
+    None => false,
+  }
+}
+
+fn unwrap_parens(mut expr: &Expr) -> &Expr {
+  while let Expr::Paren(paren) = expr {
+    expr = &paren.expr;
+  }
+  expr
+}
+
+fn is_function(expr: &Expr) -> bool {
+  matches!(unwrap_parens(expr), Expr::Fn(_) | Expr::Arrow(_))
+}
+
+/// Returns the name of the called hook, for `useFoo()` and calls on
+/// namespaces like `React.useFoo()`.
+fn hook_name(callee: &ExprOrSuper) -> Option<String> {
+  match callee {
+    ExprOrSuper::Expr(expr) => match &**expr {
+      Expr::Ident(ident) if is_hook_name(&ident.sym) => {
+        Some(ident.sym.to_string())
+      }
+      Expr::Member(member) if !member.computed => {
+        match (&member.obj, &*member.prop) {
+          (ExprOrSuper::Expr(obj), Expr::Ident(prop))
+            if is_hook_name(&prop.sym) =>
+          {
+            match &**obj {
+              Expr::Ident(obj)
+                if obj.sym.starts_with(|c: char| c.is_ascii_uppercase()) =>
+              {
+                Some(format!("{}.{}", obj.sym, prop.sym))
+              }
+              _ => None,
+            }
+          }
+          _ => None,
+        }
+      }
+      _ => None,
+    },
+    ExprOrSuper::Super(_) => None,
+  }
+}
+
+/// Returns `true` for `memo` and `forwardRef`, whose arguments are components
+/// even though they're anonymous.
+fn is_component_wrapper(callee: &ExprOrSuper) -> bool {
+  let name = match callee {
+    ExprOrSuper::Expr(expr) => match &**expr {
+      Expr::Ident(ident) => &ident.sym,
+      Expr::Member(member) if !member.computed => match &*member.prop {
+        Expr::Ident(prop) => &prop.sym,
+        _ => return false,
+      },
+      _ => return false,
+    },
+    ExprOrSuper::Super(_) => return false,
+  };
+  name == "memo" || name == "forwardRef"
+}
+
+/// The state of the function the visitor is in.
+struct Frame {
+  kind: FunctionKind,
+  /// The number of conditionally evaluated expressions and statements the
+  /// visitor is in.
+  conditional_depth: usize,
+  /// The number of loops the visitor is in.
+  loop_depth: usize,
+  /// Whether a `return` statement was visited, so that the rest of the
+  /// function may not run.
+  may_have_returned: bool,
+}
+
+struct RulesOfHooksVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  frames: Vec<Frame>,
+  /// The kind of the next visited function, determined by its parent, e.g.
+  /// `const Foo = () => {}` is a component.
+  pending_kind: Option<FunctionKind>,
+}
+
+impl<'c, 'view> RulesOfHooksVisitor<'c, 'view> {
+  fn enter_function(&mut self) {
+    let kind = self.pending_kind.take().unwrap_or(FunctionKind::Other);
+    self.frames.push(Frame {
+      kind,
+      conditional_depth: 0,
+      loop_depth: 0,
+      may_have_returned: false,
+    });
+  }
+
+  fn exit_function(&mut self) {
+    self.frames.pop();
+  }
+
+  fn visit_conditional<N: VisitWith<Self>>(
+    &mut self,
+    node: &N,
+    parent: &dyn Node,
+  ) {
+    if let Some(frame) = self.frames.last_mut() {
+      frame.conditional_depth += 1;
+    }
+    node.visit_with(parent, self);
+    if let Some(frame) = self.frames.last_mut() {
+      frame.conditional_depth -= 1;
+    }
+  }
+
+  fn visit_loop<N: VisitWith<Self>>(&mut self, node: &N, parent: &dyn Node) {
+    if let Some(frame) = self.frames.last_mut() {
+      frame.loop_depth += 1;
+    }
+    node.visit_with(parent, self);
+    if let Some(frame) = self.frames.last_mut() {
+      frame.loop_depth -= 1;
+    }
+  }
+
+  fn check(&mut self, span: Span, name: String) {
+    let (message, hint) = match self.frames.last() {
+      Some(frame) if frame.kind == FunctionKind::Other => {
+        if self.frames.iter().any(|f| f.kind != FunctionKind::Other) {
+          (
+            RulesOfHooksMessage::NestedFunction(name),
+            RulesOfHooksHint::TopLevel,
+          )
+        } else {
+          (
+            RulesOfHooksMessage::OutsideComponent(name),
+            RulesOfHooksHint::ComponentOrHook,
+          )
+        }
+      }
+      Some(frame) if frame.loop_depth > 0 => {
+        (RulesOfHooksMessage::Loop(name), RulesOfHooksHint::TopLevel)
+      }
+      Some(frame) if frame.conditional_depth > 0 || frame.may_have_returned => {
+        (
+          RulesOfHooksMessage::Conditional(name),
+          RulesOfHooksHint::TopLevel,
+        )
+      }
+      Some(_) => return,
+      None => (
+        RulesOfHooksMessage::OutsideComponent(name),
+        RulesOfHooksHint::ComponentOrHook,
+      ),
+    };
+    self
+      .context
+      .add_diagnostic_with_hint(span, CODE, message, hint);
+  }
+}
+
+impl<'c, 'view> Visit for RulesOfHooksVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, function: &Function, _: &dyn Node) {
+    self.enter_function();
+    function.visit_children_with(self);
+    self.exit_function();
+  }
+
+  fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr, _: &dyn Node) {
+    self.enter_function();
+    arrow_expr.visit_children_with(self);
+    self.exit_function();
+  }
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    self.pending_kind = Some(FunctionKind::from_name(&fn_decl.ident.sym));
+    fn_decl.visit_children_with(self);
+  }
+
+  fn visit_fn_expr(&mut self, fn_expr: &FnExpr, _: &dyn Node) {
+    if let (None, Some(ident)) = (self.pending_kind, &fn_expr.ident) {
+      self.pending_kind = Some(FunctionKind::from_name(&ident.sym));
+    }
+    fn_expr.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(
+    &mut self,
+    var_declarator: &VarDeclarator,
+    _: &dyn Node,
+  ) {
+    var_declarator.name.visit_with(var_declarator, self);
+    if let Some(init) = &var_declarator.init {
+      if let (Pat::Ident(binding), true) =
+        (&var_declarator.name, is_function(init))
+      {
+        self.pending_kind = Some(FunctionKind::from_name(&binding.id.sym));
+      }
+      init.visit_with(var_declarator, self);
+    }
+  }
+
+  fn visit_export_default_decl(
+    &mut self,
+    export_default_decl: &ExportDefaultDecl,
+    _: &dyn Node,
+  ) {
+    // Anonymous default exports are usually the components of routes and
+    // islands
+    if let DefaultDecl::Fn(FnExpr { ident: None, .. }) =
+      &export_default_decl.decl
+    {
+      self.pending_kind = Some(FunctionKind::Component);
+    }
+    export_default_decl.visit_children_with(self);
+  }
+
+  fn visit_export_default_expr(
+    &mut self,
+    export_default_expr: &ExportDefaultExpr,
+    _: &dyn Node,
+  ) {
+    if is_function(&export_default_expr.expr) {
+      self.pending_kind = Some(FunctionKind::Component);
+    }
+    export_default_expr.visit_children_with(self);
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let Some(name) = hook_name(&call_expr.callee) {
+      self.check(call_expr.callee.span(), name);
+    }
+    call_expr.callee.visit_with(call_expr, self);
+    let is_wrapper = is_component_wrapper(&call_expr.callee);
+    for arg in &call_expr.args {
+      if is_wrapper && arg.spread.is_none() && is_function(&arg.expr) {
+        self.pending_kind = Some(FunctionKind::Component);
+      }
+      arg.visit_with(call_expr, self);
+    }
+  }
+
+  fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt, _: &dyn Node) {
+    return_stmt.visit_children_with(self);
+    if let Some(frame) = self.frames.last_mut() {
+      frame.may_have_returned = true;
+    }
+  }
+
+  fn visit_if_stmt(&mut self, if_stmt: &IfStmt, _: &dyn Node) {
+    if_stmt.test.visit_with(if_stmt, self);
+    self.visit_conditional(&if_stmt.cons, if_stmt);
+    if let Some(alt) = &if_stmt.alt {
+      self.visit_conditional(alt, if_stmt);
+    }
+  }
+
+  fn visit_cond_expr(&mut self, cond_expr: &CondExpr, _: &dyn Node) {
+    cond_expr.test.visit_with(cond_expr, self);
+    self.visit_conditional(&cond_expr.cons, cond_expr);
+    self.visit_conditional(&cond_expr.alt, cond_expr);
+  }
+
+  fn visit_bin_expr(&mut self, bin_expr: &BinExpr, _: &dyn Node) {
+    bin_expr.left.visit_with(bin_expr, self);
+    if matches!(
+      bin_expr.op,
+      BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing
+    ) {
+      self.visit_conditional(&bin_expr.right, bin_expr);
+    } else {
+      bin_expr.right.visit_with(bin_expr, self);
+    }
+  }
+
+  fn visit_opt_chain_expr(
+    &mut self,
+    opt_chain_expr: &OptChainExpr,
+    _: &dyn Node,
+  ) {
+    // Only the part after `?.` is conditional
+    match &*opt_chain_expr.expr {
+      Expr::Member(member) => {
+        member.obj.visit_with(member, self);
+        self.visit_conditional(&member.prop, member);
+      }
+      Expr::Call(call_expr) => {
+        call_expr.callee.visit_with(call_expr, self);
+        self.visit_conditional::<Vec<ExprOrSpread>>(&call_expr.args, call_expr);
+      }
+      _ => opt_chain_expr.visit_children_with(self),
+    }
+  }
+
+  fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt, _: &dyn Node) {
+    switch_stmt.discriminant.visit_with(switch_stmt, self);
+    self.visit_conditional(&switch_stmt.cases, switch_stmt);
+  }
+
+  fn visit_try_stmt(&mut self, try_stmt: &TryStmt, _: &dyn Node) {
+    try_stmt.block.visit_with(try_stmt, self);
+    if let Some(handler) = &try_stmt.handler {
+      self.visit_conditional(handler, try_stmt);
+    }
+    try_stmt.finalizer.visit_with(try_stmt, self);
+  }
+
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt, _: &dyn Node) {
+    for_stmt.init.visit_with(for_stmt, self);
+    self.visit_loop(&for_stmt.test, for_stmt);
+    self.visit_loop(&for_stmt.update, for_stmt);
+    self.visit_loop(&for_stmt.body, for_stmt);
+  }
+
+  fn visit_for_in_stmt(&mut self, for_in_stmt: &ForInStmt, _: &dyn Node) {
+    for_in_stmt.left.visit_with(for_in_stmt, self);
+    for_in_stmt.right.visit_with(for_in_stmt, self);
+    self.visit_loop(&for_in_stmt.body, for_in_stmt);
+  }
+
+  fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt, _: &dyn Node) {
+    for_of_stmt.left.visit_with(for_of_stmt, self);
+    for_of_stmt.right.visit_with(for_of_stmt, self);
+    self.visit_loop(&for_of_stmt.body, for_of_stmt);
+  }
+
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt, _: &dyn Node) {
+    self.visit_loop(&while_stmt.test, while_stmt);
+    self.visit_loop(&while_stmt.body, while_stmt);
+  }
+
+  fn visit_do_while_stmt(&mut self, do_while_stmt: &DoWhileStmt, _: &dyn Node) {
+    self.visit_loop(&do_while_stmt.body, do_while_stmt);
+    self.visit_loop(&do_while_stmt.test, do_while_stmt);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rules_of_hooks_valid() {
+    assert_lint_ok! {
+      RulesOfHooks,
+      "function Counter() { const [count, setCount] = useState(0); return count; }",
+      "const Counter = () => { const count = useSignal(0); return count.value; };",
+      "function useCounter() { const [count, setCount] = useState(0); useEffect(() => {}, [count]); return count; }",
+      "const useCounter = function () { return React.useState(0); };",
+      "export default function () { const [value] = useState(''); return value; }",
+      "export default () => { useEffect(() => {}); };",
+      "const Input = forwardRef((props, ref) => { useImperativeHandle(ref, () => ({})); });",
+      "const Item = memo(function (props) { const id = useId(); return id; });",
+      "function Profile() { const user = useUser()?.name; const items = useItems() || []; return user; }",
+      "function Profile({ id }) { const data = useData(id); if (!data) { return null; } return data; }",
+      "function List({ items }) { const ref = useRef(); return items.map((item) => item.id); }",
+      "function App() { user && user.use(); return use(context); }",
+      "class Foo { render() { return this.useless(); } }",
+    };
+
+    assert_lint_ok! {
+      RulesOfHooks,
+      {
+        src: "export default function Home() { const count = useSignal(0); return <p>{count}</p>; }",
+        filename: "routes/index.tsx",
+      },
+    };
+  }
+
+  #[test]
+  fn rules_of_hooks_invalid() {
+    assert_lint_err! {
+      RulesOfHooks,
+      "function Counter({ enabled }) { if (enabled) { useEffect(() => {}); } }": [
+        {
+          col: 47,
+          message: variant!(RulesOfHooksMessage, Conditional, "useEffect"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+      "function Counter({ a }) { const b = a ? useMemo(() => a) : null; const c = a && useState(); }": [
+        {
+          col: 40,
+          message: variant!(RulesOfHooksMessage, Conditional, "useMemo"),
+          hint: RulesOfHooksHint::TopLevel,
+        },
+        {
+          col: 80,
+          message: variant!(RulesOfHooksMessage, Conditional, "useState"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+      "function Profile({ user }) { if (!user) return null; const [name] = useState(user.name); }": [
+        {
+          col: 68,
+          message: variant!(RulesOfHooksMessage, Conditional, "useState"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+      "function useItems(ids) { for (const id of ids) { useItem(id); } }": [
+        {
+          col: 49,
+          message: variant!(RulesOfHooksMessage, Loop, "useItem"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+      "const List = ({ items }) => { items.forEach((item) => { React.useEffect(() => {}); }); };": [
+        {
+          col: 56,
+          message: variant!(RulesOfHooksMessage, NestedFunction, "React.useEffect"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+      "function handleClick() { const [count] = useState(0); }": [
+        {
+          col: 41,
+          message: variant!(RulesOfHooksMessage, OutsideComponent, "useState"),
+          hint: RulesOfHooksHint::ComponentOrHook,
+        }
+      ],
+      "const count = useSignal(0);": [
+        {
+          col: 14,
+          message: variant!(RulesOfHooksMessage, OutsideComponent, "useSignal"),
+          hint: RulesOfHooksHint::ComponentOrHook,
+        }
+      ],
+      "class Counter { render() { useState(); } }": [
+        {
+          col: 27,
+          message: variant!(RulesOfHooksMessage, OutsideComponent, "useState"),
+          hint: RulesOfHooksHint::ComponentOrHook,
+        }
+      ],
+      "function Counter({ enabled }) { switch (enabled) { case true: useEffect(() => {}); } }": [
+        {
+          col: 62,
+          message: variant!(RulesOfHooksMessage, Conditional, "useEffect"),
+          hint: RulesOfHooksHint::TopLevel,
+        }
+      ],
+    };
+
+    assert_lint_err! {
+      RulesOfHooks,
+      "export default function Home({ open }) { const ref = open ? useRef() : null; return <div ref={ref} />; }": {
+        filename: "islands/Home.tsx",
+        errors: [
+          {
+            col: 60,
+            message: variant!(RulesOfHooksMessage, Conditional, "useRef"),
+            hint: RulesOfHooksHint::TopLevel,
+          }
+        ],
+      },
+    };
+  }
+}
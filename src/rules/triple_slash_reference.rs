@@ -1,67 +1,199 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule, ProgramRef};
+use derive_more::Display;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
 use swc_common::comments::Comment;
 use swc_common::comments::CommentKind;
 use swc_common::Span;
+use swc_ecmascript::ast::{ModuleDecl, ModuleItem, TsModuleRef};
 
-pub struct TripleSlashReference;
+pub struct TripleSlashReference {
+  options: TripleSlashReferenceOptions,
+}
 
-impl TripleSlashReference {
-  fn report(&self, context: &mut Context, span: Span) {
-    context.add_diagnostic(
-      span,
-      "triple-slash-reference",
-      "`triple slash reference` is not allowed",
-    );
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TripleSlashReferenceOptions {
+  pub path: ReferenceOption,
+  pub types: ReferenceOption,
+  pub lib: ReferenceOption,
+}
+
+impl Default for TripleSlashReferenceOptions {
+  fn default() -> Self {
+    Self {
+      path: ReferenceOption::Never,
+      types: ReferenceOption::Never,
+      lib: ReferenceOption::Never,
+    }
   }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceOption {
+  /// The directive is allowed.
+  Always,
+  /// The directive is not allowed.
+  Never,
+  /// The directive is not allowed for modules that are also imported.
+  PreferImport,
+}
+
+const CODE: &str = "triple-slash-reference";
+
+#[derive(Display)]
+enum TripleSlashReferenceMessage {
+  #[display(fmt = "`triple slash reference` is not allowed")]
+  NotAllowed,
+  #[display(
+    fmt = "Do not use a triple slash reference for '{}', use `import` style instead",
+    _0
+  )]
+  PreferImport(String),
+}
+
+#[derive(Display)]
+enum TripleSlashReferenceHint {
+  #[display(fmt = "Remove the `{}` reference", _0)]
+  Remove(String),
+  #[display(fmt = "Remove the reference, the module is already imported")]
+  RemoveImported,
+}
+
 impl LintRule for TripleSlashReference {
   fn new() -> Box<Self> {
-    Box::new(TripleSlashReference)
+    Box::new(TripleSlashReference {
+      options: TripleSlashReferenceOptions::default(),
+    })
   }
 
   fn code(&self) -> &'static str {
-    "triple-slash-reference"
+    CODE
   }
 
-  fn lint_program(&self, context: &mut Context, _program: ProgramRef<'_>) {
-    let mut violated_comment_spans = Vec::new();
+  fn needs_whole_program(&self) -> bool {
+    true
+  }
 
-    violated_comment_spans.extend(context.all_comments().filter_map(|c| {
-      if check_comment(c) {
-        Some(c.span)
-      } else {
-        None
-      }
-    }));
+  fn needs_comments(&self) -> bool {
+    true
+  }
 
-    for span in violated_comment_spans {
-      self.report(context, span);
+  fn lint_program(&self, context: &mut Context, program: ProgramRef<'_>) {
+    let imports = match program {
+      ProgramRef::Module(module) => imported_modules(&module.body),
+      ProgramRef::Script(_) => HashSet::new(),
+    };
+
+    let violations: Vec<_> = context
+      .all_comments()
+      .filter_map(|comment| self.check_comment(comment, &imports))
+      .collect();
+
+    for (span, message, hint) in violations {
+      context.add_diagnostic_with_hint(span, CODE, message, hint);
     }
   }
-}
 
-/// Returns `true` if the comment should be reported.
-fn check_comment(comment: &Comment) -> bool {
-  if comment.kind != CommentKind::Line {
-    return false;
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    self.options = serde_json::from_value(options)?;
+    Ok(())
   }
 
-  static TSR_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"^/\s*<reference\s*(types|path|lib)\s*=\s*["|'](.*)["|']"#)
-      .unwrap()
-  });
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    let directive = json!({
+      "description": "`always` allows the directive, `never` bans it and `prefer-import` bans it for modules that are also imported.",
+      "enum": ["always", "never", "prefer-import"],
+      "default": "never"
+    });
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "path": directive.clone(),
+        "types": directive.clone(),
+        "lib": directive
+      }
+    }))
+  }
+}
+
+impl TripleSlashReference {
+  /// Returns the diagnostic to report for the comment, if any.
+  fn check_comment(
+    &self,
+    comment: &Comment,
+    imports: &HashSet<String>,
+  ) -> Option<(Span, TripleSlashReferenceMessage, TripleSlashReferenceHint)> {
+    if comment.kind != CommentKind::Line {
+      return None;
+    }
 
-  TSR_REGEX.is_match(&comment.text)
+    static TSR_REGEX: Lazy<Regex> = Lazy::new(|| {
+      Regex::new(r#"^/\s*<reference\s*(types|path|lib)\s*=\s*["|'](.*)["|']"#)
+        .unwrap()
+    });
+
+    let caps = TSR_REGEX.captures(&comment.text)?;
+    let kind = caps.get(1).unwrap().as_str();
+    let module = caps.get(2).unwrap().as_str();
+    let option = match kind {
+      "path" => self.options.path,
+      "types" => self.options.types,
+      _ => self.options.lib,
+    };
+
+    match option {
+      ReferenceOption::Always => None,
+      ReferenceOption::Never => Some((
+        comment.span,
+        TripleSlashReferenceMessage::NotAllowed,
+        TripleSlashReferenceHint::Remove(kind.to_string()),
+      )),
+      ReferenceOption::PreferImport if imports.contains(module) => Some((
+        comment.span,
+        TripleSlashReferenceMessage::PreferImport(module.to_string()),
+        TripleSlashReferenceHint::RemoveImported,
+      )),
+      ReferenceOption::PreferImport => None,
+    }
+  }
+}
+
+/// Returns the specifiers of the modules imported with `import` declarations
+/// or `import foo = require("foo")`.
+fn imported_modules(items: &[ModuleItem]) -> HashSet<String> {
+  items
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+        Some(import_decl.src.value.to_string())
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals)) => {
+        match &import_equals.module_ref {
+          TsModuleRef::TsExternalModuleRef(external) => {
+            Some(external.expr.value.to_string())
+          }
+          TsModuleRef::TsEntityName(_) => None,
+        }
+      }
+      _ => None,
+    })
+    .collect()
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::test_util::*;
+  use serde_json::json;
 
   #[test]
   fn magurotuna() {
@@ -130,4 +262,56 @@ import foo = require('foo');
       0,
     );
   }
+
+  #[test]
+  fn triple_slash_reference_options_valid() {
+    assert_lint_ok! {
+      TripleSlashReference,
+      options: json!({ "lib": "always", "types": "prefer-import" }),
+      r#"/// <reference lib="dom" />"#,
+      r#"/// <reference types="node" />"#,
+      r#"
+/// <reference types="node" />
+import * as fs from "fs";
+"#,
+    };
+
+    assert_lint_ok! {
+      TripleSlashReference,
+      options: json!({ "path": "always", "types": "always", "lib": "always" }),
+      r#"/// <reference path="./globals.d.ts" />"#,
+      r#"/// <reference types="node" />"#,
+    };
+  }
+
+  #[test]
+  fn triple_slash_reference_options_invalid() {
+    assert_lint_err! {
+      TripleSlashReference,
+      options: json!({ "lib": "always", "types": "prefer-import" }),
+      r#"/// <reference path="./globals.d.ts" />"#: [
+        {
+          col: 0,
+          message: TripleSlashReferenceMessage::NotAllowed,
+          hint: variant!(TripleSlashReferenceHint, Remove, "path"),
+        }
+      ],
+      r#"/// <reference types="node" />
+    import * as node from "node";"#: [
+        {
+          col: 0,
+          message: variant!(TripleSlashReferenceMessage, PreferImport, "node"),
+          hint: TripleSlashReferenceHint::RemoveImported,
+        }
+      ],
+      r#"/// <reference types="foo" />
+    import foo = require("foo");"#: [
+        {
+          col: 0,
+          message: variant!(TripleSlashReferenceMessage, PreferImport, "foo"),
+          hint: TripleSlashReferenceHint::RemoveImported,
+        }
+      ],
+    };
+  }
 }
@@ -0,0 +1,279 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule, ProgramRef, DUMMY_NODE};
+use crate::import_map::{is_relative, is_url, ImportMap, Resolution};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::json;
+use swc_ecmascript::ast::{
+  CallExpr, ExportAll, Expr, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
+
+pub struct ValidImportMapSpecifiers {
+  options: ValidImportMapSpecifiersOptions,
+  import_map: Option<ImportMap>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ValidImportMapSpecifiersOptions {
+  /// Path of the import map specifiers are resolved with.
+  pub import_map: Option<String>,
+}
+
+const CODE: &str = "valid-import-map-specifiers";
+
+#[derive(Display)]
+enum ValidImportMapSpecifiersMessage {
+  #[display(fmt = "'{}' is not mapped by the import map", _0)]
+  Unmapped(String),
+  #[display(fmt = "'{}' is mapped to the bare specifier '{}'", _0, _1)]
+  MappedToBare(String, String),
+  #[display(fmt = "'{}' is mapped to '{}', which doesn't exist", _0, _1)]
+  Missing(String, String),
+}
+
+#[derive(Display)]
+enum ValidImportMapSpecifiersHint {
+  #[display(fmt = "Add the specifier to the import map")]
+  AddMapping,
+  #[display(
+    fmt = "Map the specifier to a URL or to a path starting with `./`, `../` or `/`"
+  )]
+  FixMapping,
+  #[display(
+    fmt = "Fix the path the specifier is mapped to, or remove the mapping"
+  )]
+  FixPath,
+}
+
+impl LintRule for ValidImportMapSpecifiers {
+  fn new() -> Box<Self> {
+    Box::new(ValidImportMapSpecifiers {
+      options: ValidImportMapSpecifiersOptions::default(),
+      import_map: None,
+    })
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program<'view>(
+    &self,
+    context: &mut Context<'view>,
+    program: ProgramRef<'view>,
+  ) {
+    let import_map = match &self.import_map {
+      Some(import_map) => import_map,
+      None => return,
+    };
+    let mut visitor = ValidImportMapSpecifiersVisitor {
+      context,
+      import_map,
+    };
+    match program {
+      ProgramRef::Module(m) => visitor.visit_module(m, &DUMMY_NODE),
+      ProgramRef::Script(s) => visitor.visit_script(s, &DUMMY_NODE),
+    }
+  }
+
+  fn set_options(
+    &mut self,
+    options: serde_json::Value,
+  ) -> Result<(), serde_json::Error> {
+    let options: ValidImportMapSpecifiersOptions =
+      serde_json::from_value(options)?;
+    self.import_map = match &options.import_map {
+      Some(path) => Some(ImportMap::load(path)?),
+      None => None,
+    };
+    self.options = options;
+    Ok(())
+  }
+
+  fn options_schema(&self) -> Option<serde_json::Value> {
+    Some(json!({
+      "type": "object",
+      "properties": {
+        "importMap": {
+          "description": "Path of the import map specifiers are resolved with.",
+          "type": "string"
+        }
+      }
+    }))
+  }
+
+  fn docs(&self) -> &'static str {
+    rule_docs!(
+      "Requires import specifiers to resolve through the import map",
+      "valid-import-map-specifiers"
+    )
+  }
+}
+
+struct ValidImportMapSpecifiersVisitor<'c, 'view> {
+  context: &'c mut Context<'view>,
+  import_map: &'c ImportMap,
+}
+
+impl<'c, 'view> ValidImportMapSpecifiersVisitor<'c, 'view> {
+  fn check_specifier(&mut self, src: &Str) {
+    let specifier = &*src.value;
+    let resolution =
+      match self.import_map.resolve(specifier, self.context.file_name()) {
+        Some(resolution) => resolution,
+        None if is_relative(specifier) || is_url(specifier) => return,
+        None => {
+          self.context.add_diagnostic_with_hint(
+            src.span,
+            CODE,
+            ValidImportMapSpecifiersMessage::Unmapped(specifier.to_string()),
+            ValidImportMapSpecifiersHint::AddMapping,
+          );
+          return;
+        }
+      };
+    match resolution {
+      Resolution::Url(_) => {}
+      Resolution::Path(path) => {
+        if !path.exists() {
+          self.context.add_diagnostic_with_hint(
+            src.span,
+            CODE,
+            ValidImportMapSpecifiersMessage::Missing(
+              specifier.to_string(),
+              path.to_string_lossy().to_string(),
+            ),
+            ValidImportMapSpecifiersHint::FixPath,
+          );
+        }
+      }
+      Resolution::Bare(address) => {
+        self.context.add_diagnostic_with_hint(
+          src.span,
+          CODE,
+          ValidImportMapSpecifiersMessage::MappedToBare(
+            specifier.to_string(),
+            address,
+          ),
+          ValidImportMapSpecifiersHint::FixMapping,
+        );
+      }
+    }
+  }
+}
+
+impl<'c, 'view> Visit for ValidImportMapSpecifiersVisitor<'c, 'view> {
+  noop_visit_type!();
+
+  fn visit_import_decl(&mut self, import_decl: &ImportDecl, _: &dyn Node) {
+    self.check_specifier(&import_decl.src);
+  }
+
+  fn visit_export_all(&mut self, export_all: &ExportAll, _: &dyn Node) {
+    self.check_specifier(&export_all.src);
+  }
+
+  fn visit_named_export(&mut self, named_export: &NamedExport, _: &dyn Node) {
+    if let Some(src) = &named_export.src {
+      self.check_specifier(src);
+    }
+  }
+
+  fn visit_call_expr(&mut self, call_expr: &CallExpr, _: &dyn Node) {
+    if let ExprOrSuper::Expr(callee) = &call_expr.callee {
+      if matches!(&**callee, Expr::Ident(ident) if ident.sym == *"import") {
+        if let Some(arg) = call_expr.args.first() {
+          if let Expr::Lit(Lit::Str(src)) = &*arg.expr {
+            self.check_specifier(src);
+          }
+        }
+      }
+    }
+    call_expr.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  /// Writes an import map and the module it maps to a directory named after
+  /// the test, as tests run concurrently.
+  fn write_import_map(test: &str) -> String {
+    let dir = std::env::temp_dir().join(test);
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/utils.ts"), "export {};").unwrap();
+    let path = dir.join("import_map.json");
+    std::fs::write(
+      &path,
+      r#"{
+        "imports": {
+          "preact": "https://esm.sh/preact",
+          "std/": "https://deno.land/std@0.200.0/",
+          "utils": "./src/utils.ts",
+          "helpers": "./src/helpers.ts",
+          "lodash": "lodash-es"
+        }
+      }"#,
+    )
+    .unwrap();
+    path.to_str().unwrap().to_string()
+  }
+
+  #[test]
+  fn valid_import_map_specifiers_valid() {
+    assert_lint_ok! {
+      ValidImportMapSpecifiers,
+      r#"import express from "express";"#,
+    };
+
+    assert_lint_ok! {
+      ValidImportMapSpecifiers,
+      options: json!({ "importMap": write_import_map("valid_import_map_specifiers_valid") }),
+      r#"import { h } from "preact";"#,
+      r#"import { join } from "std/path/mod.ts";"#,
+      r#"import { foo } from "utils";"#,
+      r#"import { foo } from "./foo.ts";"#,
+      r#"import { readFile } from "node:fs";"#,
+      r#"const mod = await import(specifier);"#,
+    };
+  }
+
+  #[test]
+  fn valid_import_map_specifiers_invalid() {
+    let import_map = write_import_map("valid_import_map_specifiers_invalid");
+    let helpers = std::path::Path::new(&import_map)
+      .parent()
+      .unwrap()
+      .join("src/helpers.ts");
+    let helpers = helpers.to_str().unwrap();
+    assert_lint_err! {
+      ValidImportMapSpecifiers,
+      options: json!({ "importMap": import_map }),
+      r#"import { h } from "preact/hooks";"#: [
+        {
+          col: 18,
+          message: variant!(ValidImportMapSpecifiersMessage, Unmapped, "preact/hooks"),
+          hint: ValidImportMapSpecifiersHint::AddMapping,
+        }
+      ],
+      r#"export * from "helpers";"#: [
+        {
+          col: 14,
+          message: variant!(ValidImportMapSpecifiersMessage, Missing, "helpers", helpers),
+          hint: ValidImportMapSpecifiersHint::FixPath,
+        }
+      ],
+      r#"const _ = await import("lodash");"#: [
+        {
+          col: 23,
+          message: variant!(ValidImportMapSpecifiersMessage, MappedToBare, "lodash", "lodash-es"),
+          hint: ValidImportMapSpecifiersHint::FixMapping,
+        }
+      ],
+    };
+  }
+}
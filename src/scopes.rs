@@ -59,7 +59,6 @@ pub struct Var {
 
 impl Var {
   /// Empty path means root scope.
-  #[allow(dead_code)]
   pub fn path(&self) -> &[ScopeKind] {
     &self.path
   }
@@ -144,7 +143,14 @@ impl Analyzer<'_> {
 
 impl Visit for Analyzer<'_> {
   fn visit_arrow_expr(&mut self, n: &ArrowExpr, _: &dyn Node) {
-    self.with(ScopeKind::Arrow, |a| n.visit_children_with(a))
+    self.with(ScopeKind::Arrow, |a| {
+      // Unlike the ones of functions, the parameters of arrow functions
+      // aren't `Param` nodes
+      for param in &n.params {
+        a.declare_pat(BindingKind::Param, param);
+      }
+      n.visit_children_with(a)
+    })
   }
 
   /// Overriden not to add ScopeKind::Block
@@ -374,4 +380,12 @@ try {
     assert_eq!(var(&scope, "e").kind(), BindingKind::CatchClause);
     assert_eq!(var(&scope, "e").path(), &[]);
   }
+
+  #[test]
+  fn arrow_params() {
+    let scope = test_scope("const f = (a, { b }) => a + b;");
+    assert_eq!(var(&scope, "a").kind(), BindingKind::Param);
+    assert_eq!(var(&scope, "a").path(), &[ScopeKind::Arrow]);
+    assert_eq!(var(&scope, "b").kind(), BindingKind::Param);
+  }
 }
@@ -1,10 +1,18 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::rules::{ProgramRef, DUMMY_NODE};
 use crate::scopes::Scope;
+use std::collections::HashSet;
+use swc_atoms::JsWord;
 use swc_ecmascript::ast::{
-  ComputedPropName, Expr, ExprOrSpread, Ident, Lit, MemberExpr, PatOrExpr,
-  PrivateName, Prop, PropName, PropOrSpread, Str, Tpl,
+  ArrowExpr, BinaryOp, BlockStmtOrExpr, CallExpr, Class, ClassMember,
+  ComputedPropName, Decl, ExportSpecifier, Expr, ExprOrSpread, ExprOrSuper,
+  FnDecl, Function, Ident, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue,
+  JSXElementName, JSXExpr, JSXObject, JSXOpeningElement, Lit, MemberExpr,
+  Module, ModuleDecl, ModuleItem, NewExpr, Pat, PatOrExpr, PrivateName, Prop,
+  PropName, PropOrSpread, Stmt, Str, Tpl, VarDeclarator,
 };
 use swc_ecmascript::utils::{find_ids, ident::IdentLike};
+use swc_ecmascript::visit::{noop_visit_type, Node, Visit, VisitWith};
 
 /// Extracts regex string from an expression, using ScopeManager.
 /// If the passed expression is not regular expression, this will return `None`.
@@ -161,3 +169,576 @@ where
     PatOrExpr::Pat(p) => find_ids(p),
   }
 }
+
+/// Returns the names of the local bindings that are exported from the module
+/// through an export list or a default export, e.g. `a` and `b` in
+/// `export { a, b as c }; export default b;`.
+pub(crate) fn exported_local_names(module: &Module) -> HashSet<JsWord> {
+  let mut names = HashSet::new();
+  for item in &module.body {
+    match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export))
+        if named_export.src.is_none() && !named_export.type_only =>
+      {
+        for specifier in &named_export.specifiers {
+          if let ExportSpecifier::Named(named) = specifier {
+            names.insert(named.orig.sym.clone());
+          }
+        }
+      }
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_expr)) => {
+        if let Expr::Ident(ident) = &*default_expr.expr {
+          names.insert(ident.sym.clone());
+        }
+      }
+      _ => {}
+    }
+  }
+  names
+}
+
+/// Returns the names of the functions declared at the top level of the module
+/// that have overload signatures.
+pub(crate) fn overloaded_functions(module: &Module) -> HashSet<JsWord> {
+  module
+    .body
+    .iter()
+    .filter_map(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+        Some(&export_decl.decl)
+      }
+      ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+      _ => None,
+    })
+    .filter_map(|decl| match decl {
+      Decl::Fn(fn_decl) if fn_decl.function.body.is_none() => {
+        Some(fn_decl.ident.sym.clone())
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Returns the names of the methods of the class that have overload
+/// signatures.
+pub(crate) fn overloaded_methods(class: &Class) -> HashSet<String> {
+  class
+    .body
+    .iter()
+    .filter_map(|member| match member {
+      ClassMember::Method(method) if method.function.body.is_none() => {
+        method.key.string_repr()
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Returns the names of the async functions declared in the program,
+/// regardless of the scope they are declared in, e.g. `a` and `b` in
+/// `async function a() {} const b = async () => {};`.
+pub(crate) fn async_function_names(program: &ProgramRef) -> HashSet<JsWord> {
+  let mut collector = AsyncFunctionCollector::default();
+  match program {
+    ProgramRef::Module(m) => collector.visit_module(m, &DUMMY_NODE),
+    ProgramRef::Script(s) => collector.visit_script(s, &DUMMY_NODE),
+  }
+  collector.names
+}
+
+#[derive(Default)]
+struct AsyncFunctionCollector {
+  names: HashSet<JsWord>,
+}
+
+impl Visit for AsyncFunctionCollector {
+  noop_visit_type!();
+
+  fn visit_fn_decl(&mut self, fn_decl: &FnDecl, _: &dyn Node) {
+    if fn_decl.function.is_async {
+      self.names.insert(fn_decl.ident.sym.clone());
+    }
+    fn_decl.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, declarator: &VarDeclarator, _: &dyn Node) {
+    if let (Pat::Ident(binding), Some(init)) =
+      (&declarator.name, &declarator.init)
+    {
+      if is_async_function(init) {
+        self.names.insert(binding.id.sym.clone());
+      }
+    }
+    declarator.visit_children_with(self);
+  }
+}
+
+/// Returns `true` if the expression is an async function or arrow function.
+pub(crate) fn is_async_function(expr: &Expr) -> bool {
+  match expr {
+    Expr::Arrow(arrow) => arrow.is_async,
+    Expr::Fn(fn_expr) => fn_expr.function.is_async,
+    Expr::Paren(paren) => is_async_function(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns `true` if the expression evaluates to a promise as far as can be
+/// told without type information: calls to `async_functions`, `fetch()`,
+/// `new Promise()`, the static `Promise` methods, `.then()`, `.catch()`,
+/// `.finally()` and async IIFEs.
+pub(crate) fn is_known_promise(
+  expr: &Expr,
+  async_functions: &HashSet<JsWord>,
+) -> bool {
+  match expr {
+    Expr::Paren(paren) => is_known_promise(&paren.expr, async_functions),
+    Expr::New(new_expr) => {
+      matches!(&*new_expr.callee, Expr::Ident(ident) if ident.sym == *"Promise")
+    }
+    Expr::Call(call) => match &call.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Ident(ident) => {
+          ident.sym == *"fetch" || async_functions.contains(&ident.sym)
+        }
+        Expr::Paren(paren) => is_async_function(&paren.expr),
+        Expr::Member(member) if !member.computed => {
+          match (&member.obj, &*member.prop) {
+            (_, Expr::Ident(prop))
+              if matches!(&*prop.sym, "then" | "catch" | "finally") =>
+            {
+              true
+            }
+            (ExprOrSuper::Expr(obj), Expr::Ident(prop)) => {
+              matches!(&**obj, Expr::Ident(obj) if obj.sym == *"Promise")
+                && matches!(
+                  &*prop.sym,
+                  "all" | "allSettled" | "any" | "race" | "reject" | "resolve"
+                )
+            }
+            _ => false,
+          }
+        }
+        _ => false,
+      },
+      ExprOrSuper::Super(_) => false,
+    },
+    _ => false,
+  }
+}
+
+/// Returns the name of a non-computed member, e.g. `b` in `a.b`.
+pub(crate) fn member_name(member: &MemberExpr) -> Option<&str> {
+  if member.computed {
+    return None;
+  }
+  match &*member.prop {
+    Expr::Ident(ident) => Some(&ident.sym),
+    _ => None,
+  }
+}
+
+/// Returns the name of a property, e.g. `b` in both `a.b` and `a["b"]`.
+pub(crate) fn prop_name(member: &MemberExpr) -> Option<&str> {
+  if let Some(name) = member_name(member) {
+    return Some(name);
+  }
+  match &*member.prop {
+    Expr::Lit(Lit::Str(s)) if member.computed => Some(&s.value),
+    _ => None,
+  }
+}
+
+/// Returns `true` for `Deno.<name>`.
+fn is_deno_member(expr: &Expr, name: &str) -> bool {
+  match expr {
+    Expr::Member(member) => {
+      let is_deno = match &member.obj {
+        ExprOrSuper::Expr(obj) => {
+          matches!(&**obj, Expr::Ident(ident) if ident.sym == *"Deno")
+        }
+        ExprOrSuper::Super(_) => false,
+      };
+      is_deno && member_name(member) == Some(name)
+    }
+    _ => false,
+  }
+}
+
+/// Returns the value of the `key` property of an object literal.
+pub(crate) fn object_prop<'a>(expr: &'a Expr, key: &str) -> Option<&'a Expr> {
+  let object = match expr {
+    Expr::Object(object) => object,
+    _ => return None,
+  };
+  object.props.iter().find_map(|prop| match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(kv) => match &kv.key {
+        PropName::Ident(ident) if ident.sym == *key => Some(&*kv.value),
+        PropName::Str(s) if s.value == *key => Some(&*kv.value),
+        _ => None,
+      },
+      _ => None,
+    },
+    PropOrSpread::Spread(_) => None,
+  })
+}
+
+/// Returns the elements of an array literal, skipping holes and spreads.
+fn array_elements(expr: &Expr) -> Option<Vec<&Expr>> {
+  match expr {
+    Expr::Array(array) => Some(
+      array
+        .elems
+        .iter()
+        .flatten()
+        .filter(|elem| elem.spread.is_none())
+        .map(|elem| &*elem.expr)
+        .collect(),
+    ),
+    _ => None,
+  }
+}
+
+fn non_spread(arg: Option<&ExprOrSpread>) -> Option<&Expr> {
+  match arg {
+    Some(arg) if arg.spread.is_none() => Some(&*arg.expr),
+    _ => None,
+  }
+}
+
+/// A subprocess spawned with `Deno.run()` or `new Deno.Command()`, as far as
+/// its program and arguments are written as literals.
+pub(crate) struct DenoSubprocess<'a> {
+  pub program: Option<&'a Expr>,
+  pub args: Vec<&'a Expr>,
+}
+
+/// Returns the subprocess spawned by `Deno.run({ cmd: [program, ...args] })`.
+pub(crate) fn deno_run_subprocess(
+  call: &CallExpr,
+) -> Option<DenoSubprocess<'_>> {
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => &**callee,
+    ExprOrSuper::Super(_) => return None,
+  };
+  if !is_deno_member(callee, "run") {
+    return None;
+  }
+  let cmd = non_spread(call.args.first())
+    .and_then(|options| object_prop(options, "cmd"))
+    .and_then(array_elements)
+    .unwrap_or_default();
+  let mut cmd = cmd.into_iter();
+  Some(DenoSubprocess {
+    program: cmd.next(),
+    args: cmd.collect(),
+  })
+}
+
+/// Returns the subprocess spawned by
+/// `new Deno.Command(program, { args: [...args] })`.
+pub(crate) fn deno_command_subprocess(
+  new_expr: &NewExpr,
+) -> Option<DenoSubprocess<'_>> {
+  if !is_deno_member(&new_expr.callee, "Command") {
+    return None;
+  }
+  let args = new_expr.args.as_deref().unwrap_or_default();
+  Some(DenoSubprocess {
+    program: non_spread(args.first()),
+    args: non_spread(args.get(1))
+      .and_then(|options| object_prop(options, "args"))
+      .and_then(array_elements)
+      .unwrap_or_default(),
+  })
+}
+
+/// The function of a test registered with `Deno.test()`.
+pub(crate) enum DenoTestFn<'a> {
+  Function(&'a Function),
+  Arrow(&'a ArrowExpr),
+}
+
+impl<'a> DenoTestFn<'a> {
+  /// Returns `true` if the body of the test has no statements.
+  pub fn is_empty(&self) -> bool {
+    match self {
+      DenoTestFn::Function(function) => {
+        matches!(&function.body, Some(body) if body.stmts.is_empty())
+      }
+      DenoTestFn::Arrow(arrow) => {
+        matches!(&arrow.body, BlockStmtOrExpr::BlockStmt(body) if body.stmts.is_empty())
+      }
+    }
+  }
+
+  pub fn visit_body_with<V: Visit>(&self, visitor: &mut V) {
+    match self {
+      DenoTestFn::Function(function) => {
+        function.body.visit_with(*function, visitor)
+      }
+      DenoTestFn::Arrow(arrow) => arrow.body.visit_with(*arrow, visitor),
+    }
+  }
+}
+
+fn test_fn(expr: &Expr) -> Option<DenoTestFn<'_>> {
+  let method = match expr {
+    Expr::Fn(fn_expr) => Some(DenoTestFn::Function(&fn_expr.function)),
+    Expr::Arrow(arrow) => Some(DenoTestFn::Arrow(arrow)),
+    Expr::Paren(paren) => test_fn(&paren.expr),
+    Expr::Object(object) => object.props.iter().find_map(|prop| match prop {
+      PropOrSpread::Prop(prop) => match &**prop {
+        Prop::Method(method) => match &method.key {
+          PropName::Ident(ident) if ident.sym == *"fn" => {
+            Some(DenoTestFn::Function(&method.function))
+          }
+          _ => None,
+        },
+        _ => None,
+      },
+      PropOrSpread::Spread(_) => None,
+    }),
+    _ => None,
+  };
+  method.or_else(|| object_prop(expr, "fn").and_then(test_fn))
+}
+
+/// Returns the function of a test registered with `Deno.test()`, including
+/// `Deno.test.only()` and `Deno.test.ignore()`, in any of its forms, e.g.
+/// `Deno.test("name", () => {})` or `Deno.test({ name, fn() {} })`.
+pub(crate) fn deno_test_fn(call: &CallExpr) -> Option<DenoTestFn<'_>> {
+  let callee = match &call.callee {
+    ExprOrSuper::Expr(callee) => &**callee,
+    ExprOrSuper::Super(_) => return None,
+  };
+  let is_deno_test = is_deno_member(callee, "test")
+    || match callee {
+      Expr::Member(member) => {
+        matches!(member_name(member), Some(name) if name == "only" || name == "ignore")
+          && matches!(&member.obj, ExprOrSuper::Expr(obj) if is_deno_member(obj, "test"))
+      }
+      _ => false,
+    };
+  if !is_deno_test {
+    return None;
+  }
+  call
+    .args
+    .iter()
+    .rev()
+    .filter(|arg| arg.spread.is_none())
+    .find_map(|arg| test_fn(&arg.expr))
+}
+
+/// Returns `true` for template literals with interpolations, and for string
+/// concatenations involving anything but literals, e.g. `"a" + b`.
+pub(crate) fn is_interpolated_string(expr: &Expr) -> bool {
+  fn is_literal(expr: &Expr) -> bool {
+    match expr {
+      Expr::Lit(_) => true,
+      Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+      _ => false,
+    }
+  }
+
+  match expr {
+    Expr::Tpl(tpl) => !tpl.exprs.is_empty(),
+    Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+      is_interpolated_string(&bin.left)
+        || is_interpolated_string(&bin.right)
+        || !is_literal(&bin.left)
+        || !is_literal(&bin.right)
+    }
+    Expr::Paren(paren) => is_interpolated_string(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns the methods called by a chain such as `a.b().c()`, from the last
+/// call to the first (`c` and `b`), along with the object the chain starts
+/// from (`a`).
+pub(crate) fn method_chain(mut expr: &Expr) -> (Vec<(&str, &CallExpr)>, &Expr) {
+  let mut methods = vec![];
+  loop {
+    let call = match expr {
+      Expr::Call(call) => call,
+      Expr::Paren(paren) => {
+        expr = &paren.expr;
+        continue;
+      }
+      _ => break,
+    };
+    let member = match &call.callee {
+      ExprOrSuper::Expr(callee) => match &**callee {
+        Expr::Member(member) => member,
+        _ => break,
+      },
+      ExprOrSuper::Super(_) => break,
+    };
+    match (member_name(member), &member.obj) {
+      (Some(name), ExprOrSuper::Expr(obj)) => {
+        methods.push((name, call));
+        expr = obj;
+      }
+      _ => break,
+    }
+  }
+  (methods, expr)
+}
+
+/// Returns the object, name and arguments of a method call, e.g. `a`,
+/// `indexOf` and `[b]` in `a.indexOf(b)`.
+pub(crate) fn method_call(
+  call: &CallExpr,
+) -> Option<(&Expr, &str, &[ExprOrSpread])> {
+  let member = match &call.callee {
+    ExprOrSuper::Expr(callee) => match &**callee {
+      Expr::Member(member) => member,
+      _ => return None,
+    },
+    ExprOrSuper::Super(_) => return None,
+  };
+  let obj = match &member.obj {
+    ExprOrSuper::Expr(obj) => obj,
+    ExprOrSuper::Super(_) => return None,
+  };
+  if call.args.iter().any(|arg| arg.spread.is_some()) {
+    return None;
+  }
+  Some((obj, member_name(member)?, &call.args))
+}
+
+/// Returns the text a regular expression pattern matches, quoted as a string
+/// literal, or `None` if it matches anything but literal text, e.g. `"a.b"`
+/// for `a\.b`.
+pub(crate) fn regex_literal_text(pattern: &str) -> Option<String> {
+  let mut text = String::from("\"");
+  let mut chars = pattern.chars();
+  while let Some(c) = chars.next() {
+    let c = match c {
+      '\\' => match chars.next() {
+        Some(escaped) if escaped.is_ascii_punctuation() => escaped,
+        _ => return None,
+      },
+      '^' | '$' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}'
+      | '|' | '/' => return None,
+      c => c,
+    };
+    if c == '"' || c == '\\' {
+      text.push('\\');
+    }
+    text.push(c);
+  }
+  if text.len() == 1 {
+    return None;
+  }
+  text.push('"');
+  Some(text)
+}
+
+/// Returns `true` if a member like `.foo()` can follow the expression without
+/// wrapping it in parentheses.
+pub(crate) fn is_member_object(expr: &Expr) -> bool {
+  matches!(
+    expr,
+    Expr::Ident(_)
+      | Expr::Lit(Lit::Str(_))
+      | Expr::Member(_)
+      | Expr::Call(_)
+      | Expr::Paren(_)
+      | Expr::This(_)
+      | Expr::Array(_)
+      | Expr::Tpl(_)
+      | Expr::TaggedTpl(_)
+  )
+}
+
+/// Returns the name of a JSX element as written, e.g. `img`, `Foo.Bar` or
+/// `svg:rect`.
+pub(crate) fn jsx_element_name(name: &JSXElementName) -> String {
+  fn object_name(object: &JSXObject) -> String {
+    match object {
+      JSXObject::Ident(ident) => ident.sym.to_string(),
+      JSXObject::JSXMemberExpr(member) => {
+        format!("{}.{}", object_name(&member.obj), member.prop.sym)
+      }
+    }
+  }
+
+  match name {
+    JSXElementName::Ident(ident) => ident.sym.to_string(),
+    JSXElementName::JSXMemberExpr(member) => {
+      format!("{}.{}", object_name(&member.obj), member.prop.sym)
+    }
+    JSXElementName::JSXNamespacedName(name) => {
+      format!("{}:{}", name.ns.sym, name.name.sym)
+    }
+  }
+}
+
+/// Returns the name of a JSX attribute, e.g. `alt` or `xlink:href`.
+pub(crate) fn jsx_attr_name(name: &JSXAttrName) -> String {
+  match name {
+    JSXAttrName::Ident(ident) => ident.sym.to_string(),
+    JSXAttrName::JSXNamespacedName(name) => {
+      format!("{}:{}", name.ns.sym, name.name.sym)
+    }
+  }
+}
+
+/// Returns the attribute of a JSX element with the given name.
+pub(crate) fn jsx_attr<'a>(
+  opening: &'a JSXOpeningElement,
+  name: &str,
+) -> Option<&'a JSXAttr> {
+  opening.attrs.iter().find_map(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(attr) if jsx_attr_name(&attr.name) == name => {
+      Some(attr)
+    }
+    _ => None,
+  })
+}
+
+/// Returns `true` if a JSX element has spread attributes, e.g. `{...props}`,
+/// which may set any attribute.
+pub(crate) fn has_jsx_spread(opening: &JSXOpeningElement) -> bool {
+  opening
+    .attrs
+    .iter()
+    .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+}
+
+/// Returns the value of a JSX attribute written as a string, e.g. `a` in
+/// `title="a"` or `title={"a"}`.
+pub(crate) fn jsx_attr_str(attr: &JSXAttr) -> Option<&str> {
+  match &attr.value {
+    Some(JSXAttrValue::Lit(Lit::Str(s))) => Some(&s.value),
+    Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+      JSXExpr::Expr(expr) => match &**expr {
+        Expr::Lit(Lit::Str(s)) => Some(&s.value),
+        _ => None,
+      },
+      JSXExpr::JSXEmptyExpr(_) => None,
+    },
+    _ => None,
+  }
+}
+
+/// Returns `true` if a JSX attribute may have a non-empty value, i.e. unless
+/// it's set to an empty string or `undefined`.
+pub(crate) fn has_jsx_attr_value(attr: &JSXAttr) -> bool {
+  if let Some(value) = jsx_attr_str(attr) {
+    return !value.trim().is_empty();
+  }
+  match &attr.value {
+    Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+      JSXExpr::Expr(expr) => {
+        !matches!(&**expr, Expr::Ident(ident) if ident.sym == *"undefined")
+      }
+      JSXExpr::JSXEmptyExpr(_) => false,
+    },
+    _ => true,
+  }
+}
@@ -2,6 +2,7 @@
 
 use crate::ast_parser;
 use crate::diagnostic::LintDiagnostic;
+use crate::fix::apply_fixes;
 use crate::linter::LinterBuilder;
 use crate::rules::LintRule;
 use dprint_swc_ecma_ast_view::TokenAndSpan;
@@ -14,6 +15,16 @@ use swc_ecmascript::parser::{Syntax, TsConfig};
 
 #[macro_export]
 macro_rules! assert_lint_ok {
+  ($rule:ty, options: $options:expr, $($test:tt),+ $(,)?) => {
+    $(
+      let (src, filename) = parse_ok_test!($test);
+      $crate::test_util::assert_lint_ok_with_options::<$rule>(
+        src,
+        filename,
+        $options,
+      );
+    )*
+  };
   ($rule:ty, $($test:tt),+ $(,)?) => {
     $(
       let (src, filename) = parse_ok_test!($test);
@@ -24,6 +35,23 @@ macro_rules! assert_lint_ok {
 
 #[macro_export]
 macro_rules! assert_lint_err {
+  (
+    $rule:ty,
+    options: $options:expr,
+    $($src:literal : $test:tt),+
+    $(,)?
+  ) => {
+    $(
+      let (errors, filename) = parse_err_test!($test);
+      let tester = $crate::test_util::LintErrTester::<$rule>::new(
+        $src,
+        errors,
+        filename,
+      )
+      .options($options);
+      tester.run();
+    )*
+  };
   (
     $rule:ty,
     $($src:literal : $test:tt),+
@@ -93,7 +121,7 @@ macro_rules! parse_err_test {
     }
   ) => {{
     let (errors, _) = parse_err_test!($errors);
-    (errors, $filename)
+    (errors, Some($filename))
   }};
 }
 
@@ -102,6 +130,7 @@ pub struct LintErrTester<T: LintRule + 'static> {
   src: &'static str,
   errors: Vec<LintErr>,
   filename: String,
+  options: Option<serde_json::Value>,
   rule: PhantomData<T>,
 }
 
@@ -118,12 +147,21 @@ impl<T: LintRule + 'static> LintErrTester<T> {
         Some(f) => f.to_string(),
         None => "deno_lint_err_test.ts".to_string(),
       },
+      options: None,
       rule: PhantomData,
     }
   }
 
+  pub fn options(mut self, options: serde_json::Value) -> Self {
+    self.options = Some(options);
+    self
+  }
+
   pub fn run(self) {
-    let rule = T::new();
+    let mut rule = T::new();
+    if let Some(options) = self.options {
+      rule.set_options(options).expect("Failed to set options");
+    }
     let rule_code = rule.code();
     let diagnostics = lint(rule, self.src, self.filename);
     assert_eq!(
@@ -141,6 +179,7 @@ impl<T: LintRule + 'static> LintErrTester<T> {
         col,
         message,
         hint,
+        fix,
       } = error;
       assert_diagnostic_2(
         diagnostic,
@@ -151,6 +190,7 @@ impl<T: LintRule + 'static> LintErrTester<T> {
         message,
         hint.as_deref(),
       );
+      assert_fix(diagnostic, self.src, fix.as_ref());
     }
   }
 }
@@ -161,6 +201,8 @@ pub struct LintErr {
   pub col: usize,
   pub message: String,
   pub hint: Option<String>,
+  /// The description of the first fix and the source after applying it.
+  pub fix: Option<(String, String)>,
 }
 
 #[derive(Default)]
@@ -169,6 +211,7 @@ pub struct LintErrBuilder {
   col: Option<usize>,
   message: Option<String>,
   hint: Option<String>,
+  fix: Option<(String, String)>,
 }
 
 impl LintErrBuilder {
@@ -198,12 +241,21 @@ impl LintErrBuilder {
     self
   }
 
+  pub fn fix(
+    &mut self,
+    (description, fixed_source): (impl ToString, impl ToString),
+  ) -> &mut Self {
+    self.fix = Some((description.to_string(), fixed_source.to_string()));
+    self
+  }
+
   pub fn build(self) -> LintErr {
     LintErr {
       line: self.line.unwrap_or(1),
       col: self.col.unwrap_or(0),
       message: self.message.unwrap_or_else(|| "".to_string()),
       hint: self.hint,
+      fix: self.fix,
     }
   }
 }
@@ -218,12 +270,12 @@ fn get_ts_config_with_tsx() -> Syntax {
   Syntax::Typescript(ts_config)
 }
 
-fn lint(
+pub fn lint(
   rule: Box<dyn LintRule>,
   source: &str,
   filename: String,
 ) -> Vec<LintDiagnostic> {
-  let linter = LinterBuilder::default()
+  let mut linter = LinterBuilder::default()
     .lint_unused_ignore_directives(false)
     .lint_unknown_rules(false)
     .syntax(if filename.ends_with(".tsx") {
@@ -308,6 +360,39 @@ fn assert_diagnostic_2(
   );
 }
 
+fn assert_fix(
+  diagnostic: &LintDiagnostic,
+  source: &str,
+  expected: Option<&(String, String)>,
+) {
+  let (description, fixed_source) = match expected {
+    Some(expected) => expected,
+    None => {
+      assert!(
+        diagnostic.fixes.is_empty(),
+        "No fix is expected, but got {:#?}\n\nsource:\n{}\n",
+        diagnostic.fixes,
+        source
+      );
+      return;
+    }
+  };
+  let fix = diagnostic.fixes.first().unwrap_or_else(|| {
+    panic!("A fix is expected, but got none\n\nsource:\n{}\n", source)
+  });
+  assert_eq!(
+    description, &fix.description,
+    "Fix description is expected to be \"{}\", but got \"{}\"\n\nsource:\n{}\n",
+    description, fix.description, source
+  );
+  assert_eq!(
+    fixed_source,
+    &apply_fixes(source, std::iter::once(fix)),
+    "Unexpected source after applying the fix\n\nsource:\n{}\n",
+    source
+  );
+}
+
 pub fn assert_lint_ok<T: LintRule + 'static>(
   source: &str,
   filename: Option<&str>,
@@ -326,6 +411,26 @@ pub fn assert_lint_ok<T: LintRule + 'static>(
   }
 }
 
+pub fn assert_lint_ok_with_options<T: LintRule + 'static>(
+  source: &str,
+  filename: Option<&str>,
+  options: serde_json::Value,
+) {
+  let mut rule = T::new();
+  rule.set_options(options).expect("Failed to set options");
+  let filename = match filename {
+    Some(f) => f.to_string(),
+    None => "deno_lint_ok_test.ts".to_string(),
+  };
+  let diagnostics = lint(rule, source, filename);
+  if !diagnostics.is_empty() {
+    panic!(
+      "Unexpected diagnostics found:\n{:#?}\n\nsource:\n{}\n",
+      diagnostics, source
+    );
+  }
+}
+
 pub fn assert_lint_err<T: LintRule + 'static>(source: &str, col: usize) {
   assert_lint_err_on_line::<T>(source, 1, col)
 }
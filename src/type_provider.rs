@@ -0,0 +1,19 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::Range;
+
+/// Provides type information about the files being linted, e.g. by querying a
+/// type checker running alongside the linter.
+///
+/// Type-aware rules only report what they can tell from the syntax alone when
+/// no type provider is set on the `LinterBuilder`, or when it answers `None`.
+pub trait TypeProvider {
+  /// Returns whether the expression at `range` in the file evaluates to a
+  /// `Promise` or another thenable, or `None` if it isn't known.
+  fn is_promise(&self, file_name: &str, range: &Range) -> Option<bool>;
+
+  /// Returns whether the expression at `range` in the file is a function
+  /// returning a `Promise` or another thenable, or `None` if it isn't known.
+  fn returns_promise(&self, _file_name: &str, _range: &Range) -> Option<bool> {
+    None
+  }
+}